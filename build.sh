@@ -13,6 +13,7 @@ BUILD_CLI=true
 BUILD_GUI=false
 RELEASE_MODE=""
 MAC_APP=false
+CLI_FEATURES=""
 
 for arg in "$@"; do
     case $arg in
@@ -35,6 +36,10 @@ for arg in "$@"; do
             RELEASE_MODE="--release"
             shift
             ;;
+        --verify-only)
+            CLI_FEATURES="--no-default-features"
+            shift
+            ;;
         --mac-app)
             BUILD_CLI=false
             BUILD_GUI=true
@@ -50,6 +55,7 @@ for arg in "$@"; do
             echo "  --gui-only    Build only the GUI tool"
             echo "  --all         Build both CLI and GUI"
             echo "  --release     Build in release mode"
+            echo "  --verify-only Build the CLI without signing support (no private-key handling)"
             echo "  --mac-app     Build GUI in release and create macOS crTool.app bundle (macOS only)"
             echo "  --help        Show this help message"
             echo ""
@@ -81,8 +87,12 @@ echo ""
 
 # Build CLI
 if [ "$BUILD_CLI" = true ]; then
-    echo "→ Building CLI tool..."
-    cargo build $RELEASE_MODE -p crTool
+    if [ -n "$CLI_FEATURES" ]; then
+        echo "→ Building CLI tool (verify-only)..."
+    else
+        echo "→ Building CLI tool..."
+    fi
+    cargo build $RELEASE_MODE $CLI_FEATURES -p crTool
     echo "✓ CLI build complete"
     echo ""
 fi
@@ -0,0 +1,135 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `assess` and `extract --trust-manifest`: JPEG Trust conformance assessment of assets or
+//! extracted crJSON.
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use c2pa::Settings;
+use crtool::trust_manifest::{
+    assess, assess_extraction, ClauseStatus, ConformanceReport, TrustManifest,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Log the per-clause outcome of a report.
+fn print_report(report: &ConformanceReport, logger: &mut Logger) {
+    for clause in &report.clauses {
+        let mark = match clause.status {
+            ClauseStatus::Pass => "✓",
+            ClauseStatus::Fail => "✗",
+            ClauseStatus::NotApplicable => "–",
+        };
+        match &clause.message {
+            Some(msg) => logger.info(&format!(
+                "  {} {} ({}): {}",
+                mark, clause.id, clause.indicator, msg
+            )),
+            None => logger.info(&format!("  {} {} ({})", mark, clause.id, clause.indicator)),
+        }
+    }
+    logger.info(&format!(
+        "  Conformance: {} ({} passed, {} failed, {} not applicable)",
+        if report.conforms { "yes" } else { "no" },
+        report.passed,
+        report.failed,
+        report.not_applicable
+    ));
+}
+
+/// Write `report` as `<stem>-conformance.json` in `dir`. Returns the written path.
+fn write_report(
+    report: &ConformanceReport,
+    stem: &str,
+    dir: &Path,
+    logger: &mut Logger,
+) -> Result<PathBuf> {
+    let report_path = dir.join(format!("{}-conformance.json", stem));
+    let json =
+        serde_json::to_string_pretty(report).context("Failed to format conformance report")?;
+    fs::write(&report_path, json).context("Failed to write conformance report")?;
+    logger.info(&format!("  Report: {:?}", report_path));
+    Ok(report_path)
+}
+
+/// Assess an asset against the Trust Manifest. The report goes to `output_dir` when given,
+/// otherwise next to the asset. Returns the report.
+pub fn assess_asset(
+    input_path: &Path,
+    tm: &TrustManifest,
+    output_dir: Option<&Path>,
+    settings: &Settings,
+    logger: &mut Logger,
+) -> Result<ConformanceReport> {
+    logger.info("Assessing against trust manifest...");
+    logger.info(&format!("  Input: {:?}", input_path));
+    let extraction = crtool::extract_crjson_manifest_with_settings(input_path, settings).context(
+        "Failed to read C2PA data from input file. The file may not contain a C2PA manifest.",
+    )?;
+    let report = assess_extraction(tm, &extraction);
+    print_report(&report, logger);
+
+    let stem = input_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Input file has no filename")?;
+    let dir = match output_dir {
+        Some(dir) => {
+            fs::create_dir_all(dir).context("Failed to create output directory")?;
+            dir.to_path_buf()
+        }
+        None => input_path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+    };
+    write_report(&report, stem, &dir, logger)?;
+    Ok(report)
+}
+
+/// Assess an extracted crJSON file; the report is written next to it.
+pub fn assess_crjson_file(
+    crjson_path: &Path,
+    tm: &TrustManifest,
+    logger: &mut Logger,
+) -> Result<ConformanceReport> {
+    logger.info("Assessing against trust manifest...");
+    let text = fs::read_to_string(crjson_path).context("Failed to read crJSON file")?;
+    let indicators: serde_json::Value =
+        serde_json::from_str(&text).context("Failed to parse crJSON file")?;
+    // crJSON lists the active manifest first.
+    let active_label = indicators
+        .get("manifests")
+        .and_then(|m| m.as_array())
+        .and_then(|m| m.first())
+        .and_then(|m| m.get("label"))
+        .and_then(|l| l.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let report = assess(
+        tm,
+        &indicators,
+        &active_label,
+        &crjson_path.to_string_lossy(),
+    );
+    print_report(&report, logger);
+
+    let stem = crjson_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("crJSON path has no filename")?;
+    write_report(
+        &report,
+        stem.trim_end_matches("_cr"),
+        crjson_path.parent().unwrap_or(Path::new(".")),
+        logger,
+    )?;
+    Ok(report)
+}
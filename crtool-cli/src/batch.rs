@@ -12,7 +12,6 @@ governing permissions and limitations under the License.
 
 use super::{run_cli, Cli, Logger};
 use anyhow::{Context, Result};
-use clap::Parser;
 use serde::Deserialize;
 use std::path::Path;
 
@@ -50,38 +49,44 @@ pub fn run_batch(batch_path: &Path, logger: &mut Logger) -> Result<()> {
             cmd.command
         ));
 
-        // Build synthetic argv: binary name + input files + extra arguments
+        // Build synthetic argv: binary name + subcommand + input files + extra arguments.
+        // `test-cases` puts the arguments first, as they start with the test case. Entries that
+        // still pass the deprecated mode flag keep working.
+        let legacy_flag =
+            |flags: &[&str]| cmd.arguments.iter().any(|a| flags.contains(&a.as_str()));
+        let (subcommand, arguments_first): (&[&str], bool) = match cmd.command.as_str() {
+            "extract" if !legacy_flag(&["--extract", "-e"]) => (&["extract"], false),
+            "validate" if !legacy_flag(&["--validate", "-v"]) => (&["validate"], false),
+            "profile" if !legacy_flag(&["--extract", "-e"]) => (&["profile", "asset"], false),
+            "test-cases" if !legacy_flag(&["--create-test", "-t"]) => (&["sign"], true),
+            _ => (&[], false),
+        };
         let mut argv = vec!["crTool".to_string()];
-        argv.extend(cmd.input_files.clone());
-        argv.extend(cmd.arguments.clone());
-
-        // Inject the required mode flag based on command type when not already present
-        match cmd.command.as_str() {
-            "extract" => {
-                if !argv.iter().any(|a| a == "--extract" || a == "-e") {
-                    argv.push("--extract".to_string());
-                }
-            }
-            "validate" => {
-                if !argv.iter().any(|a| a == "--validate" || a == "-v") {
-                    argv.push("--validate".to_string());
-                }
-            }
-            // "profile" and "test-cases" supply their own flags via arguments
-            _ => {}
+        argv.extend(subcommand.iter().map(|word| word.to_string()));
+        if arguments_first {
+            argv.extend(cmd.arguments.clone());
+            argv.extend(cmd.input_files.clone());
+        } else {
+            argv.extend(cmd.input_files.clone());
+            argv.extend(cmd.arguments.clone());
         }
 
-        match Cli::try_parse_from(&argv) {
-            Ok(cli) => match run_cli(cli, logger) {
-                Ok(_) => {
-                    logger.info(&format!("✅ Command [{idx}/{total}] complete"));
-                    succeeded += 1;
+        match Cli::try_parse_args(&argv) {
+            Ok((cli, legacy_alias)) => {
+                if let Some(alias) = legacy_alias {
+                    alias.warn(logger);
                 }
-                Err(e) => {
-                    logger.error(&format!("❌ Command [{idx}/{total}] failed: {e}"));
-                    failed += 1;
+                match run_cli(cli, logger) {
+                    Ok(_) => {
+                        logger.info(&format!("✅ Command [{idx}/{total}] complete"));
+                        succeeded += 1;
+                    }
+                    Err(e) => {
+                        logger.error(&format!("❌ Command [{idx}/{total}] failed: {e}"));
+                        failed += 1;
+                    }
                 }
-            },
+            }
             Err(e) => {
                 logger.error(&format!(
                     "❌ Command [{idx}/{total}] invalid arguments: {e}"
@@ -0,0 +1,261 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `--batch-csv`: sign many assets from a spreadsheet. Each row names an input, an output, a
+//! title, an author, and a preset; the manifest and signing credentials come from the test case
+//! given with `--create-test`, which acts as the template.
+
+use crate::processing::SignSettings;
+use crate::test_case::{load_test_case, sign_test_case, TestCase};
+use crate::Logger;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use std::path::{Path, PathBuf};
+
+const IPTC_SOURCE_TYPES: &str = "http://cv.iptc.org/newscodes/digitalsourcetype/";
+
+/// One row of the jobs CSV. Paths are relative to the CSV file's directory.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CsvJob {
+    pub input: String,
+    pub output: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub preset: Option<String>,
+}
+
+/// Named `c2pa.created` actions a row can pick instead of the template's actions.
+pub const PRESETS: &[(&str, &str)] = &[
+    ("captured", "digitalCapture"),
+    ("created", "digitalCreation"),
+    ("ai-generated", "trainedAlgorithmicMedia"),
+    ("composite", "compositeWithTrainedAlgorithmicMedia"),
+];
+
+/// Parse the jobs CSV. The header row must name the columns `input`, `output`, `title`,
+/// `author`, and `preset` (the last three may be left empty or omitted).
+pub fn read_jobs(csv_path: &Path) -> Result<Vec<CsvJob>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(csv_path)
+        .with_context(|| format!("Failed to read batch CSV {:?}", csv_path))?;
+    reader
+        .deserialize()
+        .enumerate()
+        .map(|(i, row)| row.with_context(|| format!("Invalid row {} in {:?}", i + 2, csv_path)))
+        .collect()
+}
+
+/// Replace `{{title}}` / `{{author}}` placeholders in every string of `value`.
+fn substitute(value: &mut JsonValue, title: &str, author: &str) {
+    match value {
+        JsonValue::String(s) if s.contains("{{") => {
+            *s = s.replace("{{title}}", title).replace("{{author}}", author);
+        }
+        JsonValue::Array(items) => items.iter_mut().for_each(|v| substitute(v, title, author)),
+        JsonValue::Object(map) => map.values_mut().for_each(|v| substitute(v, title, author)),
+        _ => {}
+    }
+}
+
+/// Build the manifest for one row from the template manifest.
+///
+/// Placeholders are filled in; a title sets the manifest `title`; an author without an
+/// `{{author}}` placeholder in the template adds a `stds.schema-org.CreativeWork` assertion; a
+/// preset replaces the template's `c2pa.actions` assertion.
+pub fn manifest_for_job(template: &JsonValue, job: &CsvJob) -> Result<JsonValue> {
+    let title = job.title.as_deref().filter(|t| !t.is_empty());
+    let author = job.author.as_deref().filter(|a| !a.is_empty());
+    let uses_author_placeholder = template.to_string().contains("{{author}}");
+
+    let mut manifest = template.clone();
+    substitute(&mut manifest, title.unwrap_or(""), author.unwrap_or(""));
+    let map = manifest
+        .as_object_mut()
+        .context("Template manifest must be a JSON object")?;
+    if let Some(title) = title {
+        map.insert("title".to_string(), json!(title));
+    }
+
+    let assertions = map
+        .entry("assertions")
+        .or_insert_with(|| json!([]))
+        .as_array_mut()
+        .context("Template manifest `assertions` must be an array")?;
+    if let (Some(author), false) = (author, uses_author_placeholder) {
+        assertions.push(json!({
+            "label": "stds.schema-org.CreativeWork",
+            "data": {
+                "@context": "https://schema.org",
+                "@type": "CreativeWork",
+                "author": [{ "@type": "Person", "name": author }]
+            }
+        }));
+    }
+    if let Some(preset) = job.preset.as_deref().filter(|p| !p.is_empty()) {
+        let source_type = PRESETS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(preset))
+            .map(|(_, source_type)| *source_type)
+            .with_context(|| {
+                format!(
+                    "Unknown preset {:?}; expected one of: {}",
+                    preset,
+                    PRESETS
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })?;
+        assertions.retain(|a| a.get("label").and_then(|l| l.as_str()) != Some("c2pa.actions"));
+        assertions.push(json!({
+            "label": "c2pa.actions",
+            "data": {
+                "actions": [{
+                    "action": "c2pa.created",
+                    "digitalSourceType": format!("{}{}", IPTC_SOURCE_TYPES, source_type)
+                }]
+            }
+        }));
+    }
+    Ok(manifest)
+}
+
+/// Run every row of `csv_path` against the `template_path` test case. Relative output paths are
+/// resolved against `output_dir` when given, otherwise against the CSV's directory. Calls
+/// `on_signed` for each signed output; returns the number of failed rows.
+pub fn run_batch_csv(
+    csv_path: &Path,
+    template_path: &Path,
+    output_dir: Option<&Path>,
+    settings: &SignSettings,
+    logger: &mut Logger,
+    mut on_signed: impl FnMut(&Path, &mut Logger),
+) -> Result<usize> {
+    let template: TestCase = load_test_case(template_path)?;
+    let template_dir = template_path.parent().unwrap_or(Path::new("."));
+    let csv_dir = csv_path.parent().unwrap_or(Path::new("."));
+    let jobs = read_jobs(csv_path)?;
+    if jobs.is_empty() {
+        anyhow::bail!("Batch CSV {:?} has no rows", csv_path);
+    }
+
+    logger.info(&format!(
+        "📋 Batch CSV: {} row(s) from {}",
+        jobs.len(),
+        csv_path.display()
+    ));
+    let mut failed = 0;
+    for (i, job) in jobs.iter().enumerate() {
+        let input: PathBuf = csv_dir.join(&job.input);
+        let output = output_dir.unwrap_or(csv_dir).join(&job.output);
+        logger.info(&format!(
+            "  📄 [{}/{}] {} → {} ...",
+            i + 1,
+            jobs.len(),
+            input.display(),
+            output.display()
+        ));
+        let result = manifest_for_job(&template.manifest, job).and_then(|manifest| {
+            let test_case = TestCase {
+                manifest,
+                ..template.clone()
+            };
+            sign_test_case(&test_case, template_dir, &input, &output, settings)
+        });
+        match result {
+            Ok(output_path) => {
+                logger.info("     ✅ Done");
+                on_signed(&output_path, logger);
+            }
+            Err(e) => {
+                logger.error(&format!("     ❌ Error: {e:#}"));
+                failed += 1;
+            }
+        }
+    }
+    Ok(failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(title: &str, author: &str, preset: &str) -> CsvJob {
+        CsvJob {
+            input: "in.jpg".to_string(),
+            output: "out.jpg".to_string(),
+            title: Some(title.to_string()),
+            author: Some(author.to_string()),
+            preset: Some(preset.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_manifest_for_job() {
+        let template = json!({
+            "title": "{{title}}",
+            "assertions": [
+                { "label": "c2pa.actions", "data": { "actions": [{ "action": "c2pa.opened" }] } }
+            ]
+        });
+
+        let manifest = manifest_for_job(&template, &job("Sunset", "Ada", "ai-generated")).unwrap();
+        assert_eq!(manifest["title"], "Sunset");
+        let assertions = manifest["assertions"].as_array().unwrap();
+        assert_eq!(assertions.len(), 2);
+        assert_eq!(assertions[0]["label"], "stds.schema-org.CreativeWork");
+        assert_eq!(assertions[0]["data"]["author"][0]["name"], "Ada");
+        assert_eq!(
+            assertions[1]["data"]["actions"][0]["digitalSourceType"],
+            "http://cv.iptc.org/newscodes/digitalsourcetype/trainedAlgorithmicMedia"
+        );
+
+        // An empty preset keeps the template's actions
+        let manifest = manifest_for_job(&template, &job("Sunset", "", "")).unwrap();
+        assert_eq!(manifest["assertions"], template["assertions"]);
+
+        assert!(manifest_for_job(&template, &job("Sunset", "", "watercolor")).is_err());
+    }
+
+    #[test]
+    fn test_author_placeholder_skips_creative_work() {
+        let template = json!({ "claim_generator_info": [{ "name": "{{author}}'s studio" }] });
+        let manifest = manifest_for_job(&template, &job("", "Ada", "")).unwrap();
+        assert_eq!(manifest["claim_generator_info"][0]["name"], "Ada's studio");
+        assert_eq!(manifest["assertions"], json!([]));
+        assert!(manifest.get("title").is_none());
+    }
+
+    #[test]
+    fn test_read_jobs() {
+        let path = std::env::temp_dir().join("crtool_batch_csv_jobs.csv");
+        std::fs::write(
+            &path,
+            "input,output,title,author,preset\n\
+             a.jpg,out/a.jpg,\"Sunset, day 1\",Ada,captured\n\
+             b.png,out/b.png,,,\n",
+        )
+        .unwrap();
+        let jobs = read_jobs(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].title.as_deref(), Some("Sunset, day 1"));
+        assert_eq!(jobs[1].input, "b.png");
+        assert!(jobs[1].preset.as_deref().unwrap_or("").is_empty());
+    }
+}
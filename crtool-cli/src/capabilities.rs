@@ -0,0 +1,190 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool capabilities`: report what this build of crTool can do (optional features, signing
+//! algorithms, asset formats, bundled schemas) so orchestrating systems can adapt to it.
+
+use crate::Logger;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Optional features an orchestrator may ask about, and whether this build includes them.
+/// Features crTool does not have yet are listed too, so callers can rely on the keys.
+const OPTIONAL_FEATURES: &[(&str, bool)] = &[
+    ("hsm", cfg!(feature = "pkcs11")),
+    (
+        "keychain",
+        cfg!(all(feature = "keychain", target_os = "macos")),
+    ),
+    ("kms", SIGNING_ENABLED),
+    ("ffmpegThumbnails", false),
+    ("serverMode", cfg!(feature = "server")),
+    ("wasmPlugins", false),
+];
+
+/// Whether this binary was built with the `signing` feature. Verify-only builds (built with
+/// `--no-default-features`) contain no signing code and never handle private keys.
+pub const SIGNING_ENABLED: bool = cfg!(feature = "signing");
+
+/// Signing algorithms this build can sign with.
+#[cfg(feature = "signing")]
+fn signing_algorithms() -> Vec<&'static str> {
+    crtool::signing::SIGNING_ALGORITHMS.to_vec()
+}
+
+/// A verify-only build cannot sign with any algorithm.
+#[cfg(not(feature = "signing"))]
+fn signing_algorithms() -> Vec<&'static str> {
+    Vec::new()
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatCapability {
+    pub extension: &'static str,
+    pub mime: &'static str,
+    pub kind: &'static str,
+    pub can_sign: bool,
+    pub can_thumbnail: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaCapability {
+    pub name: &'static str,
+    pub path: String,
+    pub present: bool,
+    /// `$id` of the schema file, when present.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// `version` of the schema file, when it declares one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    pub version: &'static str,
+    /// `full`, or `verify-only` for builds without signing support.
+    pub build_mode: &'static str,
+    pub features: BTreeMap<&'static str, bool>,
+    pub signing_algorithms: Vec<&'static str>,
+    /// Hash backends built in, and the one `--hash-backend auto` picks on this machine.
+    pub hash_backends: Vec<crtool::hashing::HashBackend>,
+    pub default_hash_backend: crtool::hashing::HashBackend,
+    pub hash_acceleration: crtool::hashing::HashAcceleration,
+    pub formats: Vec<FormatCapability>,
+    pub schemas: Vec<SchemaCapability>,
+}
+
+fn schema_capability(name: &'static str, path: PathBuf) -> SchemaCapability {
+    let value: Option<serde_json::Value> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok());
+    let field = |key: &str| {
+        value
+            .as_ref()
+            .and_then(|v| v.get(key))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    SchemaCapability {
+        name,
+        present: value.is_some(),
+        id: field("$id"),
+        version: field("version"),
+        path: path.display().to_string(),
+    }
+}
+
+/// Collect the capabilities of this build.
+pub fn capabilities() -> Capabilities {
+    let schemas_dir = crtool::crjson_schema_path()
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION"),
+        build_mode: if SIGNING_ENABLED {
+            "full"
+        } else {
+            "verify-only"
+        },
+        features: OPTIONAL_FEATURES.iter().copied().collect(),
+        signing_algorithms: signing_algorithms(),
+        hash_backends: crtool::hashing::HashBackend::available(),
+        default_hash_backend: crtool::hashing::auto_backend(),
+        hash_acceleration: crtool::hashing::detect_acceleration(),
+        formats: crtool::SUPPORTED_ASSET_FORMATS
+            .iter()
+            .map(|f| FormatCapability {
+                extension: f.extension,
+                mime: f.mime,
+                kind: f.kind.name(),
+                can_sign: f.can_sign && SIGNING_ENABLED,
+                can_thumbnail: f.can_thumbnail,
+            })
+            .collect(),
+        schemas: vec![
+            schema_capability("crJSON", crtool::crjson_schema_path()),
+            schema_capability("testCase", schemas_dir.join("test-case.schema.json")),
+            schema_capability("batch", schemas_dir.join("batch.schema.json")),
+        ],
+    }
+}
+
+/// Log a human-readable capabilities summary.
+pub fn print_capabilities(caps: &Capabilities, logger: &mut Logger) {
+    logger.info(&format!(
+        "crTool {} ({} build)",
+        caps.version, caps.build_mode
+    ));
+    let features: Vec<String> = caps
+        .features
+        .iter()
+        .map(|(name, on)| format!("{name}: {}", if *on { "yes" } else { "no" }))
+        .collect();
+    logger.info(&format!("  Optional features: {}", features.join(", ")));
+    logger.info(&format!(
+        "  Signing algorithms: {}",
+        caps.signing_algorithms.join(", ")
+    ));
+    let backends: Vec<&str> = caps.hash_backends.iter().map(|b| b.name()).collect();
+    logger.info(&format!(
+        "  Hash backends: {} (auto: {}, {})",
+        backends.join(", "),
+        caps.default_hash_backend,
+        caps.hash_acceleration
+    ));
+    let readable: Vec<&str> = caps.formats.iter().map(|f| f.extension).collect();
+    let signable: Vec<&str> = caps
+        .formats
+        .iter()
+        .filter(|f| f.can_sign)
+        .map(|f| f.extension)
+        .collect();
+    logger.info(&format!("  Readable formats: {}", readable.join(", ")));
+    logger.info(&format!("  Signable formats: {}", signable.join(", ")));
+    for schema in &caps.schemas {
+        logger.info(&format!(
+            "  Schema {}: {}",
+            schema.name,
+            match (&schema.id, schema.present) {
+                (Some(id), _) => id.clone(),
+                (None, true) => schema.path.clone(),
+                (None, false) => format!("missing ({})", schema.path),
+            }
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool cbom`: content bill of materials of composited assets.
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use c2pa::Settings;
+use crtool::cbom::build_cbom;
+use crtool::hashing::HashAlgorithm;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Write the CBOM of every input. One input goes to `output` (a file, or a directory for
+/// `<name>_cbom.json`) or, without it, to stdout; several inputs need an output directory.
+pub fn write_cboms(
+    input_files: &[PathBuf],
+    settings: &Settings,
+    hash_algorithm: HashAlgorithm,
+    output: Option<&Path>,
+    logger: &mut Logger,
+) -> Result<()> {
+    if input_files.len() > 1 && !output.is_some_and(Path::is_dir) {
+        anyhow::bail!("--output must be an existing directory when writing several CBOMs");
+    }
+    for input_file in input_files {
+        let options = crtool::HashOptions {
+            algorithm: hash_algorithm,
+        };
+        let result = crtool::extract_crjson_manifest_hashed_with(input_file, settings, &options)
+            .with_context(|| format!("Failed to read {}", input_file.display()))?;
+        let cbom = build_cbom(&result);
+        let json = serde_json::to_string_pretty(&cbom).context("Failed to format CBOM")?;
+        let Some(output) = output else {
+            println!("{}", json);
+            continue;
+        };
+        let path = if output.is_dir() {
+            let stem = input_file
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("asset");
+            output.join(format!("{stem}_cbom.json"))
+        } else {
+            output.to_path_buf()
+        };
+        fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+        logger.info(&format!(
+            "📝 CBOM of {}: {} ({} ingredient(s))",
+            input_file.display(),
+            path.display(),
+            cbom.packages.len() - 1
+        ));
+    }
+    Ok(())
+}
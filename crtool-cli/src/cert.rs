@@ -0,0 +1,129 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool cert`: inspect signing certificates against the C2PA certificate profile and
+//! generate test credentials (see [`crtool::certs`]).
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use crtool::certs::{eku_name, CertificateInfo};
+use crtool::severity::Severity;
+use std::path::Path;
+
+fn print_info(info: &CertificateInfo, logger: &mut Logger) {
+    let or_dash = |v: &[String]| {
+        if v.is_empty() {
+            "—".to_string()
+        } else {
+            v.join(", ")
+        }
+    };
+    let ekus: Vec<String> = info
+        .extended_key_usage
+        .iter()
+        .map(|oid| match eku_name(oid) {
+            Some(name) => format!("{name} ({oid})"),
+            None => oid.clone(),
+        })
+        .collect();
+    logger.info(&format!("     Subject: {}", info.subject));
+    logger.info(&format!("     Issuer: {}", info.issuer));
+    logger.info(&format!("     Serial: {}", info.serial_number));
+    logger.info(&format!(
+        "     Valid: {} to {}{}",
+        info.not_before,
+        info.not_after,
+        if info.currently_valid {
+            ""
+        } else {
+            " (not valid now)"
+        }
+    ));
+    logger.info(&format!("     Key usage: {}", or_dash(&info.key_usage)));
+    logger.info(&format!("     Extended key usage: {}", or_dash(&ekus)));
+    logger.info(&format!(
+        "     Signing algorithm: {}",
+        info.signing_alg.as_deref().unwrap_or("—")
+    ));
+    logger.info(&format!("     SHA-256: {}", info.sha256));
+}
+
+/// `cert inspect`: print the signing certificate (the first certificate of `cert`) and the
+/// result of the C2PA certificate profile checks. Fails when a check fails at the error level.
+pub fn inspect(cert: &Path, json: bool, logger: &mut Logger) -> Result<()> {
+    let info = crtool::certs::inspect_certificate_file(cert)?;
+    logger.info(&format!("  📜 {}", cert.display()));
+    print_info(&info, logger);
+    if info.findings.is_empty() {
+        logger.info("     ✅ Conforms to the C2PA signing certificate profile");
+    }
+    for finding in &info.findings {
+        let icon = match finding.severity {
+            Severity::Error => "❌",
+            _ => "⚠️ ",
+        };
+        logger.info(&format!("     {icon} {}", finding.message));
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&info).context("Failed to format JSON report")?
+        );
+    }
+    if !info.is_conformant() {
+        anyhow::bail!(
+            "{} is not a conforming C2PA signing certificate",
+            cert.display()
+        );
+    }
+    Ok(())
+}
+
+/// `cert generate-test`: write a test CA and a signing certificate chain and key for `alg`
+/// to `output`.
+pub fn generate_test(
+    alg: &str,
+    output: &Path,
+    organization: &str,
+    json: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    let alg = crtool::signing::parse_signing_algorithm(alg)?;
+    let credentials = crtool::certs::generate_test_credentials_for(output, organization, alg)?;
+    logger.info(&format!(
+        "🔑 Generated {alg} test credentials for \"{organization}\" (not for production use)"
+    ));
+    logger.info(&format!(
+        "   CA certificate (trust anchor): {}",
+        credentials.ca_cert.display()
+    ));
+    logger.info(&format!(
+        "   Signing certificate chain: {}",
+        credentials.cert_chain.display()
+    ));
+    logger.info(&format!("   Private key: {}", credentials.key.display()));
+
+    if json {
+        let report = serde_json::json!({
+            "caCert": credentials.ca_cert,
+            "certChain": credentials.cert_chain,
+            "key": credentials.key,
+            "signingAlg": alg.to_string(),
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).context("Failed to format JSON report")?
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,272 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool compare-runs OLD NEW`: differences between two `extract --json` reports of the same
+//! library, for teams running recurring audits.
+//!
+//! Files are paired by asset hash, so renamed or moved files still match, and by input path when
+//! the content changed (e.g. an asset was re-signed).
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The parts of a per-file run report entry that are compared.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RunFile {
+    input: String,
+    status: String,
+    #[serde(default)]
+    asset_sha256: Option<String>,
+    #[serde(default)]
+    active_manifest: Option<String>,
+    #[serde(default)]
+    trust: Option<String>,
+    #[serde(default)]
+    valid: Option<bool>,
+}
+
+impl RunFile {
+    /// `invalid` for manifests with validation failures, otherwise the run status.
+    fn outcome(&self) -> &str {
+        if self.valid == Some(false) {
+            "invalid"
+        } else {
+            &self.status
+        }
+    }
+
+    fn passed(&self) -> bool {
+        matches!(self.outcome(), "extracted" | "filtered")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RunReport {
+    files: Vec<RunFile>,
+}
+
+/// One difference between the runs for a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChange {
+    /// Input path in the new run.
+    pub input: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_sha256: Option<String>,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Differences between two runs.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunComparison {
+    /// Files that passed in the old run and fail in the new one (`old`/`new`: outcome).
+    pub newly_failing: Vec<FileChange>,
+    /// Files that failed in the old run and pass in the new one.
+    pub newly_passing: Vec<FileChange>,
+    /// Files whose signer trust changed (`old`/`new`: trusted, untrusted, or unknown).
+    pub trust_changes: Vec<FileChange>,
+    /// Files whose active manifest changed (`old`/`new`: manifest label).
+    pub manifest_changes: Vec<FileChange>,
+    /// Inputs only in the new run.
+    pub added: Vec<String>,
+    /// Inputs only in the old run.
+    pub removed: Vec<String>,
+}
+
+fn compare(old: &[RunFile], new: &[RunFile]) -> RunComparison {
+    let mut comparison = RunComparison::default();
+    let mut matched = vec![false; old.len()];
+    for file in new {
+        let by_hash = file.asset_sha256.as_ref().and_then(|hash| {
+            (0..old.len()).find(|&i| !matched[i] && old[i].asset_sha256.as_ref() == Some(hash))
+        });
+        let Some(i) =
+            by_hash.or_else(|| (0..old.len()).find(|&i| !matched[i] && old[i].input == file.input))
+        else {
+            comparison.added.push(file.input.clone());
+            continue;
+        };
+        matched[i] = true;
+        let before = &old[i];
+        let change = |old: Option<&str>, new: Option<&str>| FileChange {
+            input: file.input.clone(),
+            asset_sha256: file.asset_sha256.clone(),
+            old: old.map(str::to_string),
+            new: new.map(str::to_string),
+        };
+        match (before.passed(), file.passed()) {
+            (true, false) => comparison
+                .newly_failing
+                .push(change(Some(before.outcome()), Some(file.outcome()))),
+            (false, true) => comparison
+                .newly_passing
+                .push(change(Some(before.outcome()), Some(file.outcome()))),
+            _ => {}
+        }
+        if before.trust.is_some() && file.trust.is_some() && before.trust != file.trust {
+            comparison
+                .trust_changes
+                .push(change(before.trust.as_deref(), file.trust.as_deref()));
+        }
+        if before.active_manifest != file.active_manifest {
+            comparison.manifest_changes.push(change(
+                before.active_manifest.as_deref(),
+                file.active_manifest.as_deref(),
+            ));
+        }
+    }
+    comparison.removed = old
+        .iter()
+        .zip(&matched)
+        .filter(|(_, matched)| !**matched)
+        .map(|(file, _)| file.input.clone())
+        .collect();
+    comparison
+}
+
+fn load_run(path: &Path) -> Result<Vec<RunFile>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read run report {}", path.display()))?;
+    let value = crtool::limits::parse_json(&text)
+        .with_context(|| format!("Invalid JSON in run report {}", path.display()))?;
+    let report: RunReport = serde_json::from_value(value).with_context(|| {
+        format!(
+            "{} is not an `extract --json` run report (no `files` list)",
+            path.display()
+        )
+    })?;
+    Ok(report.files)
+}
+
+fn log_changes(logger: &mut Logger, heading: &str, changes: &[FileChange]) {
+    if changes.is_empty() {
+        return;
+    }
+    logger.info(&format!("{heading} ({}):", changes.len()));
+    for change in changes {
+        logger.info(&format!(
+            "  • {}: {} → {}",
+            change.input,
+            change.old.as_deref().unwrap_or("—"),
+            change.new.as_deref().unwrap_or("—")
+        ));
+    }
+}
+
+/// Compare two run reports. Prints the differences (as JSON with `json`) and fails when any
+/// file newly fails.
+pub fn compare_run_files(
+    old_path: &Path,
+    new_path: &Path,
+    json: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    let comparison = compare(&load_run(old_path)?, &load_run(new_path)?);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&comparison).context("Failed to format comparison")?
+        );
+    } else {
+        logger.info(&format!(
+            "=== Comparing {} → {} ===",
+            old_path.display(),
+            new_path.display()
+        ));
+        log_changes(logger, "❌ Newly failing", &comparison.newly_failing);
+        log_changes(logger, "✅ Newly passing", &comparison.newly_passing);
+        log_changes(logger, "🔒 Trust changes", &comparison.trust_changes);
+        log_changes(logger, "📜 Manifest changes", &comparison.manifest_changes);
+        for (heading, inputs) in [
+            ("➕ Added", &comparison.added),
+            ("➖ Removed", &comparison.removed),
+        ] {
+            if !inputs.is_empty() {
+                logger.info(&format!("{heading} ({}):", inputs.len()));
+                for input in inputs {
+                    logger.info(&format!("  • {input}"));
+                }
+            }
+        }
+    }
+
+    if !comparison.newly_failing.is_empty() {
+        anyhow::bail!(
+            "{} file(s) newly fail compared to {}",
+            comparison.newly_failing.len(),
+            old_path.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn files(value: serde_json::Value) -> Vec<RunFile> {
+        serde_json::from_value::<RunReport>(value).unwrap().files
+    }
+
+    #[test]
+    fn test_compare_pairs_by_hash_then_path() {
+        let old = files(json!({"files": [
+            {"input": "a.jpg", "status": "extracted", "assetSha256": "aa",
+             "activeManifest": "urn:1", "trust": "trusted", "valid": true},
+            {"input": "b.jpg", "status": "extracted", "assetSha256": "bb",
+             "activeManifest": "urn:2", "trust": "trusted", "valid": true},
+            {"input": "c.jpg", "status": "error", "assetSha256": "cc"},
+            {"input": "gone.jpg", "status": "extracted", "assetSha256": "dd"}
+        ]}));
+        let new = files(json!({"files": [
+            // Renamed, same content
+            {"input": "renamed/a.jpg", "status": "extracted", "assetSha256": "aa",
+             "activeManifest": "urn:1", "trust": "untrusted", "valid": true},
+            // Re-signed: new content, same path
+            {"input": "b.jpg", "status": "extracted", "assetSha256": "b2",
+             "activeManifest": "urn:3", "trust": "trusted", "valid": false},
+            {"input": "c.jpg", "status": "extracted", "assetSha256": "cc",
+             "activeManifest": "urn:4", "trust": "unknown", "valid": true},
+            {"input": "new.jpg", "status": "error", "assetSha256": "ee"}
+        ]}));
+
+        let comparison = compare(&old, &new);
+        assert_eq!(comparison.newly_failing.len(), 1);
+        assert_eq!(comparison.newly_failing[0].input, "b.jpg");
+        assert_eq!(comparison.newly_failing[0].new.as_deref(), Some("invalid"));
+        assert_eq!(comparison.newly_passing[0].input, "c.jpg");
+        assert_eq!(
+            comparison.trust_changes,
+            vec![FileChange {
+                input: "renamed/a.jpg".to_string(),
+                asset_sha256: Some("aa".to_string()),
+                old: Some("trusted".to_string()),
+                new: Some("untrusted".to_string()),
+            }]
+        );
+        let manifest_changes: Vec<_> = comparison
+            .manifest_changes
+            .iter()
+            .map(|c| c.input.as_str())
+            .collect();
+        assert_eq!(manifest_changes, vec!["b.jpg", "c.jpg"]);
+        assert_eq!(comparison.added, vec!["new.jpg"]);
+        assert_eq!(comparison.removed, vec!["gone.jpg"]);
+    }
+}
@@ -0,0 +1,146 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool demo`: a guided end-to-end run (test certificates, manifest, signing, extraction,
+//! schema validation) that needs no input files, to check an installation and show new users
+//! what each step produces.
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use crtool::indicators::TrustIndicators;
+use std::fs;
+use std::path::Path;
+
+/// The sample image signed by the demo.
+const SAMPLE_IMAGE: &[u8] = include_bytes!("../../tests/fixtures/assets/Dog.jpg");
+
+const STEPS: usize = 5;
+
+fn sample_manifest() -> serde_json::Value {
+    serde_json::json!({
+        "title": "crTool demo: Dog.jpg",
+        "claim_generator_info": [{ "name": "crTool demo", "version": env!("CARGO_PKG_VERSION") }],
+        "assertions": [
+            {
+                "label": "c2pa.actions.v2",
+                "data": { "actions": [{
+                    "action": "c2pa.created",
+                    "digitalSourceType": "http://cv.iptc.org/newscodes/digitalsourcetype/digitalCapture"
+                }] }
+            },
+            {
+                "label": "stds.schema-org.CreativeWork",
+                "data": {
+                    "@context": "https://schema.org",
+                    "@type": "CreativeWork",
+                    "author": [{ "@type": "Person", "name": "crTool Demo" }]
+                }
+            }
+        ]
+    })
+}
+
+fn step(logger: &mut Logger, n: usize, title: &str) {
+    logger.info(&format!("\n{title} (step {n}/{STEPS})"));
+}
+
+fn produced(logger: &mut Logger, path: &Path, what: &str) {
+    logger.info(&format!("   📄 {} — {what}", path.display()));
+}
+
+/// Run every step into `output_dir`, printing the files each one produced. Fails at the first
+/// step that does not work, or when the extracted crJSON does not match the schema.
+pub fn run_demo(output_dir: &Path, logger: &mut Logger) -> Result<()> {
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create demo directory {:?}", output_dir))?;
+    logger.info(&format!("=== crTool demo in {} ===", output_dir.display()));
+
+    step(logger, 1, "🔑 Generating a test CA and signing certificate");
+    let credentials =
+        crtool::certs::generate_test_credentials(&output_dir.join("certs"), "crTool Demo")?;
+    produced(
+        logger,
+        &credentials.ca_cert,
+        "test CA certificate (trust anchor)",
+    );
+    produced(logger, &credentials.cert_chain, "signing certificate chain");
+    produced(logger, &credentials.key, "signing private key");
+
+    step(logger, 2, "📝 Writing a sample manifest definition");
+    let manifest_path = output_dir.join("manifest.json");
+    let manifest_json = serde_json::to_string_pretty(&sample_manifest())?;
+    fs::write(&manifest_path, &manifest_json)
+        .with_context(|| format!("Failed to write {:?}", manifest_path))?;
+    produced(
+        logger,
+        &manifest_path,
+        "title, generator, c2pa.created action and author",
+    );
+
+    step(logger, 3, "✍️ Signing the bundled sample image");
+    let input = output_dir.join("sample.jpg");
+    fs::write(&input, SAMPLE_IMAGE).with_context(|| format!("Failed to write {:?}", input))?;
+    let signed = output_dir.join("sample_signed.jpg");
+    let mut options = crtool::SignOptions::new(
+        manifest_json,
+        &credentials.cert_chain,
+        &credentials.key,
+        credentials.signing_alg,
+    );
+    options.allow_self_signed = true;
+    crtool::sign_asset(&input, &signed, &options)?;
+    produced(logger, &input, "unsigned input");
+    produced(logger, &signed, "signed with embedded Content Credentials");
+
+    step(logger, 4, "📤 Extracting the manifest store as crJSON");
+    let ca_pem = fs::read_to_string(&credentials.ca_cert)?;
+    let settings = crtool::build_trust_settings(&ca_pem, None, None)?;
+    let extraction = crtool::extract_crjson_manifest_with_settings(&signed, &settings)?;
+    let crjson_path = output_dir.join("sample_signed.json");
+    fs::write(&crjson_path, &extraction.manifest_json)
+        .with_context(|| format!("Failed to write {:?}", crjson_path))?;
+    produced(logger, &crjson_path, "JPEG Trust indicators (crJSON)");
+    let indicators = TrustIndicators::from_extraction(&extraction);
+    logger.info(&format!("   Active manifest: {}", extraction.active_label));
+    logger.info_trust(
+        "   Trust (demo CA as trust anchor): {trust}",
+        indicators.trust().as_str(),
+    );
+    let failures = indicators
+        .active_manifest()
+        .map_or(0, |m| m.failures().len());
+    if failures > 0 {
+        anyhow::bail!(
+            "The signed sample has {failures} validation failure(s); see {crjson_path:?}"
+        );
+    }
+
+    step(logger, 5, "✅ Validating the crJSON against the schema");
+    let schema = crtool::crjson_schema_path();
+    let validation = crtool::validate_json_value(&extraction.manifest_value, &schema)?;
+    if !validation.is_valid {
+        for error in &validation.errors {
+            logger.error(&format!("   ❌ {}: {}", error.instance_path, error.message));
+        }
+        anyhow::bail!(
+            "The extracted crJSON does not match the schema {:?}",
+            schema
+        );
+    }
+    logger.info(&format!("   Valid against {}", schema.display()));
+
+    logger.info(&format!(
+        "\n🎉 All {STEPS} steps succeeded. Try `crTool info {}` or open it in the GUI.",
+        signed.display()
+    ));
+    Ok(())
+}
@@ -0,0 +1,116 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool diff A B`: path-level differences between two crJSON / indicators documents, e.g.
+//! the same asset extracted by two crTool or c2pa-rs versions (see [`crtool::diff`]).
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use crtool::diff::{diff_indicators_with, DiffOptions, PathChange};
+use std::path::Path;
+
+fn load(path: &Path) -> Result<serde_json::Value> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    crtool::limits::parse_json(&text).with_context(|| format!("Invalid JSON in {}", path.display()))
+}
+
+/// A value as one line, shortened so large assertions do not flood the output.
+fn short(value: &Option<serde_json::Value>) -> String {
+    const MAX: usize = 80;
+    let Some(value) = value else {
+        return "—".to_string();
+    };
+    let text = value.to_string();
+    if text.chars().count() > MAX {
+        format!("{}…", text.chars().take(MAX).collect::<String>())
+    } else {
+        text
+    }
+}
+
+fn log_path_changes(logger: &mut Logger, indent: &str, changes: &[PathChange]) {
+    for change in changes {
+        logger.info(&format!(
+            "{indent}{} {}: {} → {}",
+            change.kind.symbol(),
+            change.path,
+            short(&change.old),
+            short(&change.new)
+        ));
+    }
+}
+
+/// Compare two indicators documents, leaving out the `ignore` pointers. Prints the differences
+/// (as JSON with `json`) and fails when there are any, like `diff`.
+pub fn diff_files(
+    a_path: &Path,
+    b_path: &Path,
+    ignore: &[String],
+    json: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    let options = DiffOptions {
+        ignore: ignore.to_vec(),
+    };
+    let diff = diff_indicators_with(&load(a_path)?, &load(b_path)?, &options);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&diff).context("Failed to format diff")?
+        );
+    } else {
+        logger.info(&format!(
+            "=== Diff {} → {} ===",
+            a_path.display(),
+            b_path.display()
+        ));
+        if diff.is_empty() {
+            logger.info("✅ No differences");
+        }
+        for manifest in &diff.manifests {
+            logger.info(&format!("📜 {} {}", manifest.kind.symbol(), manifest.label));
+            for assertion in &manifest.assertions {
+                logger.info(&format!(
+                    "  {} assertion {}",
+                    assertion.kind.symbol(),
+                    assertion.label
+                ));
+            }
+            if let Some(trust) = &manifest.trust {
+                logger.info(&format!("  🔒 trust: {} → {}", trust.old, trust.new));
+            }
+            for code in &manifest.new_failures {
+                logger.info(&format!("  ❌ new failure: {code}"));
+            }
+            for code in &manifest.resolved_failures {
+                logger.info(&format!("  ✅ resolved failure: {code}"));
+            }
+            log_path_changes(logger, "    ", &manifest.changes);
+        }
+        if !diff.changes.is_empty() {
+            logger.info("📄 Document:");
+            log_path_changes(logger, "    ", &diff.changes);
+        }
+    }
+
+    if !diff.is_empty() {
+        anyhow::bail!(
+            "{} difference(s) between {} and {}",
+            diff.change_count(),
+            a_path.display(),
+            b_path.display()
+        );
+    }
+    Ok(())
+}
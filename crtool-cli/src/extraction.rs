@@ -10,18 +10,36 @@ OF ANY KIND, either express or implied. See the License for the specific languag
 governing permissions and limitations under the License.
 */
 
+use crate::soft_binding::SoftBindingPlugin;
 use anyhow::{Context, Result};
 use c2pa::Settings;
+use crtool::badge::Badge;
+use crtool::expectations::Expectations;
+use crtool::filter::{FileFacts, FilterExpr};
+use crtool::graph_export::GraphFormat;
+use crtool::hashing::HashAlgorithm;
+use crtool::limits::LimitExceeded;
+use crtool::provenance::{
+    detect_ingredient_conflicts, IngredientConflict, IngredientCycle, ProvenanceGraph,
+};
+use crtool::removal::RemovalSign;
+use crtool::schema::BUNDLED_SCHEMA_SPEC;
+use crtool::soft_binding::{verify_soft_bindings, SoftBindingCheck, SoftBindingStatus};
+use crtool::trust::{TrustConfig, TrustLists};
+use crtool::unsupported::{detect_unsupported, unsupported_from_error, UnsupportedFeature};
 use crtool::{
-    build_trust_settings, extract_crjson_manifest_with_settings, C2PA_TRUST_ANCHORS_URL,
-    INTERIM_ALLOWED_LIST_URL, INTERIM_TRUST_ANCHORS_URL, INTERIM_TRUST_CONFIG_URL,
+    extract_crjson_manifest_with_settings, SchemaValidator, Severity, SeverityRules,
+    ValidationError, ValidationProfile, C2PA_TRUST_ANCHORS_URL, INTERIM_ALLOWED_LIST_URL,
+    INTERIM_TRUST_ANCHORS_URL, INTERIM_TRUST_CONFIG_URL,
 };
+use serde::Serialize;
 use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Fetch a URL and return the response body as a string.
-fn fetch_url(url: &str) -> Result<String> {
+pub fn fetch_url(url: &str) -> Result<String> {
     let client = reqwest::blocking::Client::builder()
         .user_agent("crTool/1.0")
         .build()
@@ -40,56 +58,250 @@ fn fetch_url(url: &str) -> Result<String> {
     Ok(body)
 }
 
-/// Build `Settings` for extraction.
-/// When `with_trust` is true, fetches and applies the C2PA and Content Credentials trust lists.
-/// Otherwise, trust verification is disabled so certificates are not reported as untrusted.
-pub fn extraction_settings(with_trust: bool) -> Result<Settings> {
-    if with_trust {
-        println!("Loading C2PA and Content Credentials trust lists...");
-        let c2pa_anchors = fetch_url(C2PA_TRUST_ANCHORS_URL)
-            .context("Failed to fetch official C2PA trust list")?;
-        let interim_anchors = fetch_url(INTERIM_TRUST_ANCHORS_URL)
-            .context("Failed to fetch interim trust anchors")?;
-        let trust_anchors = format!(
+/// Trust lists given on the command line with `--trust-anchors`, `--allowed-list` and
+/// `--trust-config`.
+#[derive(Debug, Clone, Default)]
+pub struct LocalTrustLists {
+    /// PEM files of trust anchors.
+    pub anchors: Vec<PathBuf>,
+    /// PEM bundle or SHA-256 hash list of explicitly allowed signing certificates.
+    pub allowed_list: Option<PathBuf>,
+    /// Trust configuration file (JSON or TOML, see [`TrustConfig`]).
+    pub config: Option<PathBuf>,
+}
+
+impl LocalTrustLists {
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty() && self.allowed_list.is_none() && self.config.is_none()
+    }
+
+    /// The trust policy of these options: the `--trust-config` file (without the default lists
+    /// when there is none) plus the `--trust-anchors` and `--allowed-list` files. `with_trust`
+    /// (`--trust`) always adds the default lists.
+    pub fn trust_config(&self, with_trust: bool) -> Result<TrustConfig> {
+        let mut config = match &self.config {
+            Some(path) => TrustConfig::load(path)?,
+            None => TrustConfig {
+                use_default_lists: false,
+                ..Default::default()
+            },
+        };
+        config.use_default_lists |= with_trust;
+        config.anchor_files.extend(self.anchors.iter().cloned());
+        if let Some(path) = &self.allowed_list {
+            config.add_allowed_list(path)?;
+        }
+        Ok(config)
+    }
+}
+
+/// Fetch the official C2PA trust list and the Content Credentials interim anchors, allowed list
+/// and EKU configuration.
+pub fn fetch_default_trust_lists() -> Result<TrustLists> {
+    let c2pa_anchors =
+        fetch_url(C2PA_TRUST_ANCHORS_URL).context("Failed to fetch official C2PA trust list")?;
+    let interim_anchors =
+        fetch_url(INTERIM_TRUST_ANCHORS_URL).context("Failed to fetch interim trust anchors")?;
+    let allowed_list =
+        fetch_url(INTERIM_ALLOWED_LIST_URL).context("Failed to fetch interim allowed list")?;
+    let trust_config =
+        fetch_url(INTERIM_TRUST_CONFIG_URL).context("Failed to fetch interim trust config")?;
+    Ok(TrustLists {
+        anchors: format!(
             "{}\n{}",
             c2pa_anchors.trim_end(),
             interim_anchors.trim_end()
-        );
-        let allowed_list =
-            fetch_url(INTERIM_ALLOWED_LIST_URL).context("Failed to fetch interim allowed list")?;
-        let trust_config =
-            fetch_url(INTERIM_TRUST_CONFIG_URL).context("Failed to fetch interim trust config")?;
-        println!("  Trust list validation enabled");
-        build_trust_settings(
-            &trust_anchors,
-            Some(allowed_list.trim()),
-            Some(trust_config.trim()),
-        )
+        ),
+        allowed_list: Some(allowed_list.trim().to_string()),
+        trust_config: Some(trust_config.trim().to_string()),
+    })
+}
+
+/// Build `Settings` for extraction.
+/// When `with_trust` is true (or the `--trust-config` file uses the default lists), fetches and
+/// applies the C2PA and Content Credentials trust lists; `local` lists are added to them (or
+/// used alone). With neither, trust verification is disabled so certificates are not reported
+/// as untrusted. `offline` turns a needed download into an error.
+pub fn extraction_settings(
+    with_trust: bool,
+    local: &LocalTrustLists,
+    quiet: bool,
+    offline: bool,
+) -> Result<Settings> {
+    if !with_trust && local.is_empty() {
+        return Ok(crtool::default_extraction_settings());
+    }
+
+    let config = local.trust_config(with_trust)?;
+    let defaults = if config.use_default_lists {
+        if offline {
+            anyhow::bail!(
+                "The trust configuration {:?} uses the default trust lists, which are downloaded; \
+                set useDefaultLists to false to use it with --offline",
+                local.config.as_deref().unwrap_or(Path::new(""))
+            );
+        }
+        if !quiet {
+            println!("Loading C2PA and Content Credentials trust lists...");
+        }
+        Some(fetch_default_trust_lists()?)
     } else {
-        Ok(crtool::default_extraction_settings())
+        None
+    };
+    if !quiet {
+        println!("  Trust list validation enabled");
+    }
+    Ok(config.settings(defaults.as_ref())?)
+}
+
+/// Per-run options for [`extract_manifest`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtractOptions<'a> {
+    /// Add a top-level `coseDetails` object describing each manifest's claim signature headers.
+    pub include_cose_details: bool,
+    /// Only write files whose facts match this `--where` expression.
+    pub filter: Option<&'a FilterExpr>,
+    /// Skip content hash verification (see [`crtool::extract_crjson_manifest_fast`]).
+    pub fast: bool,
+    /// Digest algorithm for the asset hash and the crJSON `asset_info` (`--hash-alg`).
+    pub hash_algorithm: HashAlgorithm,
+    /// Suppress per-file progress output.
+    pub quiet: bool,
+    /// Also write the provenance graph in each of these formats (`<name>_provenance.<ext>`).
+    pub graph_formats: &'a [GraphFormat],
+    /// `--expect-*` checks to run on each written manifest store.
+    pub expectations: Option<&'a Expectations>,
+    /// Resolver for the active manifest's soft-binding assertions (`--soft-binding-plugin`).
+    pub soft_binding_plugin: Option<&'a SoftBindingPlugin>,
+}
+
+/// Outcome of [`extract_manifest`] for one input file.
+#[derive(Debug, Clone)]
+pub struct Extracted {
+    /// Path of the written crJSON file, or `None` when excluded by `options.filter`.
+    pub output: Option<PathBuf>,
+    /// Facts about the file (trust, format, ...), as used by `--where`.
+    pub facts: FileFacts,
+    /// SHA-256 of the input, computed while the manifest was read (`None` with `--fast` or
+    /// another `--hash-alg`).
+    pub asset_sha256: Option<String>,
+    /// Size in bytes of the embedded JUMBF manifest store, when it could be read.
+    pub manifest_size: Option<u64>,
+    /// Features in the manifest store newer than this build understands.
+    pub unsupported: Vec<UnsupportedFeature>,
+    /// Ingredients sharing an instanceID/documentID but with different hashes.
+    pub conflicts: Vec<IngredientConflict>,
+    /// Chains of ingredient references that lead back to a manifest already on the chain.
+    pub cycles: Vec<IngredientCycle>,
+    /// Provenance graph files written next to the crJSON output.
+    pub graphs: Vec<PathBuf>,
+    /// Messages for `options.expectations` that do not hold.
+    pub unmet_expectations: Vec<String>,
+    /// Soft-binding checks of the active manifest (with `options.soft_binding_plugin`).
+    pub soft_bindings: Vec<SoftBindingCheck>,
+}
+
+impl Extracted {
+    /// Whether a soft-binding assertion did not match the asset or could not be checked.
+    pub fn soft_binding_failed(&self) -> bool {
+        self.soft_bindings.iter().any(|c| {
+            matches!(
+                c.status,
+                SoftBindingStatus::Mismatch | SoftBindingStatus::Error
+            )
+        })
     }
 }
 
 /// Extract a C2PA manifest from `input_path` and write it as crJSON to `output_path`.
-/// Returns the path of the written crJSON file.
 pub fn extract_manifest(
     input_path: &Path,
     output_path: &Path,
     settings: &Settings,
-) -> Result<PathBuf> {
+    options: ExtractOptions,
+) -> Result<Extracted> {
     if !input_path.exists() {
         anyhow::bail!("Input file does not exist: {:?}", input_path);
     }
 
-    println!("Extracting C2PA manifest (crJSON)...");
-    println!("  Input: {:?}", input_path);
+    let say = |msg: String| {
+        if !options.quiet {
+            println!("{msg}");
+        }
+    };
 
-    let extract_result = extract_crjson_manifest_with_settings(input_path, settings).context(
+    say("Extracting C2PA manifest (crJSON)...".to_string());
+    say(format!("  Input: {:?}", input_path));
+
+    let extract_result = if options.fast {
+        crtool::extract_crjson_manifest_fast(input_path, settings)
+    } else {
+        let hash_options = crtool::HashOptions {
+            algorithm: options.hash_algorithm,
+        };
+        crtool::extract_crjson_manifest_hashed_with(input_path, settings, &hash_options)
+    }
+    .context(
         "Failed to read C2PA data from input file. The file may not contain a C2PA manifest.",
     )?;
 
-    let active_label = &extract_result.active_label;
-    println!("  Active manifest label: {}", active_label);
+    say(format!(
+        "  Active manifest label: {}",
+        extract_result.active_label
+    ));
+    if options.fast {
+        say("  Validation: partial (content hashes not verified)".to_string());
+    }
+
+    let facts = FileFacts::from_extraction(&extract_result);
+    let asset_sha256 = extract_result
+        .asset_hash
+        .clone()
+        .filter(|_| extract_result.asset_hash_alg == Some(HashAlgorithm::Sha256));
+    let unsupported = detect_unsupported(&extract_result.manifest_value);
+    let conflicts = detect_ingredient_conflicts(&extract_result.manifest_value);
+    let graph = ProvenanceGraph::from_crjson(
+        &extract_result.manifest_value,
+        Some(&extract_result.active_label),
+    );
+    let cycles = graph.cycles();
+    let manifest_size = crtool::jumbf::load_manifest_store(input_path)
+        .ok()
+        .map(|store| store.len() as u64);
+
+    if let Some(filter) = options.filter {
+        if !filter.matches(&facts)? {
+            say("  Skipped: does not match --where".to_string());
+            return Ok(Extracted {
+                output: None,
+                facts,
+                asset_sha256,
+                manifest_size,
+                unsupported,
+                conflicts,
+                cycles,
+                graphs: Vec::new(),
+                unmet_expectations: Vec::new(),
+                soft_bindings: Vec::new(),
+            });
+        }
+    }
+
+    let unmet_expectations = options
+        .expectations
+        .map(|e| e.check(&extract_result.manifest_value, &facts))
+        .unwrap_or_default();
+    let soft_bindings = options
+        .soft_binding_plugin
+        .map(|plugin| {
+            verify_soft_bindings(
+                input_path,
+                &extract_result.manifest_value,
+                &extract_result.active_label,
+                plugin,
+            )
+        })
+        .unwrap_or_default();
 
     let mut json_value: JsonValue = extract_result.manifest_value;
     if !json_value.get("@context").is_some() {
@@ -101,6 +313,59 @@ pub fn extract_manifest(
         }
     }
 
+    if options.include_cose_details {
+        let labels: Vec<String> = json_value
+            .get("manifests")
+            .and_then(|m| m.as_array())
+            .map(|arr| arr.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|m| m.get("label").and_then(|l| l.as_str()).map(str::to_string))
+            .collect();
+        let details = crtool::cose::asset_cose_details(input_path, &labels)
+            .context("Failed to decode COSE signature details")?;
+        if let Some(obj) = json_value.as_object_mut() {
+            obj.insert("coseDetails".to_string(), details);
+        }
+    }
+
+    if !unsupported.is_empty() {
+        if let Some(obj) = json_value.as_object_mut() {
+            obj.insert(
+                "unsupportedFeatures".to_string(),
+                serde_json::to_value(&unsupported).context("Failed to serialize findings")?,
+            );
+        }
+    }
+
+    if !conflicts.is_empty() {
+        if let Some(obj) = json_value.as_object_mut() {
+            obj.insert(
+                "ingredientConflicts".to_string(),
+                serde_json::to_value(&conflicts).context("Failed to serialize conflicts")?,
+            );
+        }
+    }
+
+    if !cycles.is_empty() {
+        if let Some(obj) = json_value.as_object_mut() {
+            obj.insert(
+                "ingredientCycles".to_string(),
+                serde_json::to_value(&cycles).context("Failed to serialize cycles")?,
+            );
+        }
+    }
+
+    if !soft_bindings.is_empty() {
+        if let Some(obj) = json_value.as_object_mut() {
+            obj.insert(
+                "softBindingChecks".to_string(),
+                serde_json::to_value(&soft_bindings)
+                    .context("Failed to serialize soft-binding checks")?,
+            );
+        }
+    }
+
     const SUFFIX: &str = "_cr.json";
 
     let final_output_path = if output_path.is_dir() {
@@ -122,97 +387,540 @@ pub fn extract_manifest(
     fs::write(&final_output_path, pretty_json)
         .context("Failed to write manifest JSON to output file")?;
 
-    println!("✓ Successfully extracted C2PA manifest");
-    println!("  Output file: {:?}", final_output_path);
+    say("✓ Successfully extracted C2PA manifest".to_string());
+    say(format!("  Output file: {:?}", final_output_path));
 
-    Ok(final_output_path)
+    let mut graphs = Vec::new();
+    for format in options.graph_formats {
+        let path = provenance_graph_path(&final_output_path, *format)?;
+        fs::write(&path, graph.render(*format))
+            .with_context(|| format!("Failed to write provenance graph {:?}", path))?;
+        graphs.push(path);
+    }
+
+    Ok(Extracted {
+        output: Some(final_output_path),
+        facts,
+        asset_sha256,
+        manifest_size,
+        unsupported,
+        conflicts,
+        cycles,
+        graphs,
+        unmet_expectations,
+        soft_bindings,
+    })
 }
 
-/// Validate one or more JSON files against the crJSON schema.
-pub fn validate_json_files(
-    input_paths: &[PathBuf],
-    schema_path: &Path,
-    schema_label: &str,
-) -> Result<()> {
-    println!(
-        "=== Validating JSON files against {} schema ===\n",
-        schema_label
-    );
+/// Path of the provenance graph written next to a crJSON output
+/// (`<name>_cr.json` → `<name>_provenance.<ext>`).
+fn provenance_graph_path(crjson_path: &Path, format: GraphFormat) -> Result<PathBuf> {
+    let stem = crjson_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Invalid crJSON output filename")?;
+    let stem = stem.strip_suffix("_cr").unwrap_or(stem);
+    Ok(crjson_path.with_file_name(format!("{}_provenance.{}", stem, format.extension())))
+}
+
+/// Aggregate statistics for one extraction run.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionStats {
+    pub files: usize,
+    pub manifests_found: usize,
+    /// Files with no readable C2PA manifest (or that failed to extract).
+    pub manifests_missing: usize,
+    /// Missing manifests with remnants suggesting the credentials were stripped.
+    pub credentials_likely_removed: usize,
+    pub filtered_out: usize,
+    pub trusted: usize,
+    pub untrusted: usize,
+    pub trust_unknown: usize,
+    /// Average size of the embedded manifest store, in bytes.
+    pub average_manifest_bytes: Option<u64>,
+    /// Number of files per MIME type (or extension when the format is unknown).
+    pub formats: BTreeMap<String, usize>,
+    #[serde(skip)]
+    manifest_bytes_total: u64,
+    #[serde(skip)]
+    manifests_sized: u64,
+}
+
+impl ExtractionStats {
+    fn count_format(&mut self, input_path: &Path) {
+        let format = crtool::asset_format_for_path(input_path)
+            .map(|f| f.mime.to_string())
+            .or_else(|| {
+                input_path
+                    .extension()
+                    .map(|e| e.to_string_lossy().to_lowercase())
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+        *self.formats.entry(format).or_default() += 1;
+    }
+
+    /// Record a file whose manifest was read (whether or not it was written).
+    pub fn record_found(&mut self, input_path: &Path, extracted: &Extracted) {
+        self.files += 1;
+        self.manifests_found += 1;
+        if extracted.output.is_none() {
+            self.filtered_out += 1;
+        }
+        match extracted.facts.trust.as_str() {
+            "trusted" => self.trusted += 1,
+            "untrusted" => self.untrusted += 1,
+            _ => self.trust_unknown += 1,
+        }
+        if let Some(size) = extracted.manifest_size {
+            self.manifest_bytes_total += size;
+            self.manifests_sized += 1;
+            self.average_manifest_bytes = Some(self.manifest_bytes_total / self.manifests_sized);
+        }
+        self.count_format(input_path);
+    }
+
+    /// Record a file that had no manifest or failed to extract, with any removal remnants.
+    pub fn record_missing(&mut self, input_path: &Path, removal: &[RemovalSign]) {
+        self.files += 1;
+        self.manifests_missing += 1;
+        if !removal.is_empty() {
+            self.credentials_likely_removed += 1;
+        }
+        self.count_format(input_path);
+    }
+
+    /// Human-readable summary lines.
+    pub fn summary_lines(&self) -> Vec<String> {
+        let removed = if self.credentials_likely_removed > 0 {
+            format!(
+                " ({} with credentials likely removed)",
+                self.credentials_likely_removed
+            )
+        } else {
+            String::new()
+        };
+        let mut lines = vec![
+            format!(
+                "   Manifests: {} found, {} missing{}",
+                self.manifests_found, self.manifests_missing, removed
+            ),
+            format!(
+                "   Trust: {} trusted, {} untrusted, {} unknown",
+                self.trusted, self.untrusted, self.trust_unknown
+            ),
+        ];
+        if let Some(avg) = self.average_manifest_bytes {
+            lines.push(format!("   Average manifest size: {} bytes", avg));
+        }
+        if !self.formats.is_empty() {
+            let formats: Vec<String> = self
+                .formats
+                .iter()
+                .map(|(format, count)| format!("{format} ({count})"))
+                .collect();
+            lines.push(format!("   Formats: {}", formats.join(", ")));
+        }
+        lines
+    }
+}
+
+/// Per-file entry of the `--json` run report.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileReport {
+    pub input: String,
+    /// `extracted`, `filtered`, `credentialsRemoved`, `limitExceeded`, or `error`.
+    pub status: &'static str,
+    /// SHA-256 (hex) of the input file, so runs can be compared per asset (`compare-runs`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_manifest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust: Option<String>,
+    /// No validation failures other than an untrusted signer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The resource limit the file exceeded (`limitExceeded` only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_exceeded: Option<LimitExceeded>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unsupported_features: Vec<UnsupportedFeature>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ingredient_conflicts: Vec<IngredientConflict>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ingredient_cycles: Vec<IngredientCycle>,
+    /// Remnants suggesting the file's Content Credentials were stripped.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub credential_removal: Vec<RemovalSign>,
+    /// `--expect-*` checks that did not hold.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unmet_expectations: Vec<String>,
+    /// `--soft-binding-plugin` checks of the active manifest's soft bindings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub soft_bindings: Vec<SoftBindingCheck>,
+}
+
+impl FileReport {
+    pub fn from_result(input_path: &Path, result: &Result<Extracted>) -> Self {
+        let input = input_path.display().to_string();
+        // Hash again only when extraction did not (errors, `--fast`, another `--hash-alg`)
+        let asset_sha256 = result
+            .as_ref()
+            .ok()
+            .and_then(|extracted| extracted.asset_sha256.clone())
+            .or_else(|| {
+                crtool::hashing::hash_file(input_path, crtool::hashing::HashAlgorithm::Sha256).ok()
+            });
+        match result {
+            Ok(extracted) => FileReport {
+                input,
+                status: if extracted.output.is_some() {
+                    "extracted"
+                } else {
+                    "filtered"
+                },
+                asset_sha256,
+                output: extracted.output.as_ref().map(|p| p.display().to_string()),
+                active_manifest: Some(extracted.facts.active_label.clone()),
+                trust: Some(extracted.facts.trust.clone()),
+                valid: Some(extracted.facts.valid),
+                error: None,
+                limit_exceeded: None,
+                unsupported_features: extracted.unsupported.clone(),
+                ingredient_conflicts: extracted.conflicts.clone(),
+                ingredient_cycles: extracted.cycles.clone(),
+                credential_removal: Vec::new(),
+                unmet_expectations: extracted.unmet_expectations.clone(),
+                soft_bindings: extracted.soft_bindings.clone(),
+            },
+            Err(e) => {
+                let error = format!("{e:#}");
+                let limit_exceeded = crtool::limits::limit_exceeded(&**e).cloned();
+                FileReport {
+                    input,
+                    status: if limit_exceeded.is_some() {
+                        "limitExceeded"
+                    } else {
+                        "error"
+                    },
+                    limit_exceeded,
+                    asset_sha256,
+                    output: None,
+                    active_manifest: None,
+                    trust: None,
+                    valid: None,
+                    unsupported_features: unsupported_from_error(&error).into_iter().collect(),
+                    ingredient_conflicts: Vec::new(),
+                    ingredient_cycles: Vec::new(),
+                    credential_removal: Vec::new(),
+                    unmet_expectations: Vec::new(),
+                    soft_bindings: Vec::new(),
+                    error: Some(error),
+                }
+            }
+        }
+    }
+
+    /// Attach credential-removal findings to a failed file, marking it `credentialsRemoved`.
+    pub fn with_credential_removal(mut self, removal: Vec<RemovalSign>) -> Self {
+        if !removal.is_empty() {
+            self.status = "credentialsRemoved";
+        }
+        self.credential_removal = removal;
+        self
+    }
+}
+
+/// Machine-readable report of an extraction run, printed by `--json`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionRunReport {
+    pub mode: &'static str,
+    pub succeeded: u32,
+    pub failed: u32,
+    pub stats: ExtractionStats,
+    pub files: Vec<FileReport>,
+}
+
+/// Extract `input_path` and write an SVG badge summarizing its credential status to `output`
+/// (a file, or a directory for `<name>_badge.svg`). Returns the written path and the badge.
+pub fn write_badge(
+    input_path: &Path,
+    output: &Path,
+    settings: &Settings,
+) -> Result<(PathBuf, Badge)> {
+    let extraction = extract_crjson_manifest_with_settings(input_path, settings)?;
+    let badge = Badge::from_facts(&FileFacts::from_extraction(&extraction));
+
+    let badge_path = if output.is_dir() {
+        let stem = input_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .context("Invalid input filename")?;
+        output.join(format!("{}_badge.svg", stem))
+    } else {
+        output.to_path_buf()
+    };
+    if badge_path
+        .extension()
+        .is_some_and(|e| !e.eq_ignore_ascii_case("svg"))
+    {
+        anyhow::bail!(
+            "Badges are rendered as SVG; use an .svg output path instead of {:?}",
+            badge_path
+        );
+    }
+    if let Some(parent) = badge_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).context("Failed to create badge output directory")?;
+    }
+    fs::write(&badge_path, badge.render_svg()).context("Failed to write badge")?;
+
+    Ok((badge_path, badge))
+}
+
+/// Build an evidence report for `input_path` and write it next to the extracted crJSON
+/// (`<name>_cr.json` → `<name>_evidence.json`). Returns the path of the written report.
+pub fn write_evidence_report(
+    input_path: &Path,
+    crjson_path: &Path,
+    settings: &Settings,
+) -> Result<PathBuf> {
+    let report = crtool::evidence::build_evidence_report(input_path, settings)
+        .context("Failed to build evidence report")?;
+
+    let stem = crjson_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Invalid crJSON output filename")?;
+    let stem = stem.strip_suffix("_cr").unwrap_or(stem);
+    let report_path = crjson_path.with_file_name(format!("{}_evidence.json", stem));
 
-    if !schema_path.exists() {
-        anyhow::bail!("Schema file not found at: {:?}", schema_path);
+    let pretty_json =
+        serde_json::to_string_pretty(&report).context("Failed to format evidence report")?;
+    fs::write(&report_path, pretty_json).context("Failed to write evidence report")?;
+
+    Ok(report_path)
+}
+
+/// Name of the output manifest written by [`write_checksum_manifest`].
+pub const CHECKSUM_MANIFEST_NAME: &str = "SHA256SUMS";
+
+/// Compute SHA-256 checksums of `outputs` and write them to `SHA256SUMS` in `dir`, in the
+/// `sha256sum` text format (`<hex>  <path>`), so the set can be checked with `sha256sum -c`.
+/// Paths are written relative to `dir` when they are inside it.
+/// Returns the manifest path and the `(output, checksum)` pairs.
+pub fn write_checksum_manifest(
+    dir: &Path,
+    outputs: &[PathBuf],
+) -> Result<(PathBuf, Vec<(PathBuf, String)>)> {
+    let mut checksums = Vec::with_capacity(outputs.len());
+    let mut manifest = String::new();
+    for output in outputs {
+        let digest = crtool::hashing::hash_file(output, crtool::hashing::HashAlgorithm::Sha256)?;
+        let name = output.strip_prefix(dir).unwrap_or(output);
+        manifest.push_str(&format!("{}  {}\n", digest, name.display()));
+        checksums.push((output.clone(), digest));
     }
 
-    println!("Loading schema from: {:?}\n", schema_path);
-    let schema_content = fs::read_to_string(schema_path).context("Failed to read schema file")?;
+    let manifest_path = dir.join(CHECKSUM_MANIFEST_NAME);
+    fs::write(&manifest_path, manifest)
+        .with_context(|| format!("Failed to write checksum manifest {:?}", manifest_path))?;
+    Ok((manifest_path, checksums))
+}
+
+/// Context IRIs that mark a JSON file as a crJSON / indicators document.
+const INDICATORS_CONTEXT_MARKERS: &[&str] =
+    &["contentcredentials.org/crjson", "jpeg.org/jpegtrust"];
+
+/// Heuristic: does `@context` (a string, an array, or an object of IRIs) name a crJSON or
+/// JPEG Trust indicators context?
+pub fn is_indicators_document(value: &JsonValue) -> bool {
+    fn names_indicators(v: &JsonValue) -> bool {
+        match v {
+            JsonValue::String(s) => INDICATORS_CONTEXT_MARKERS.iter().any(|m| s.contains(m)),
+            JsonValue::Array(items) => items.iter().any(names_indicators),
+            JsonValue::Object(map) => map.values().any(names_indicators),
+            _ => false,
+        }
+    }
+    value.get("@context").is_some_and(names_indicators)
+}
+
+/// Options for [`validate_json_files`].
+#[derive(Debug, Clone, Default)]
+pub struct ValidateOptions {
+    /// Skip valid JSON whose `@context` is not an indicators context instead of failing it.
+    pub skip_non_indicators: bool,
+    /// Severity levels, suppressed rules and `--warnings-as-errors`.
+    pub rules: SeverityRules,
+}
+
+/// Per-directory validation counts.
+#[derive(Debug, Default)]
+struct DirectoryCounts {
+    valid: usize,
+    invalid: usize,
+    skipped: usize,
+}
 
-    let schema_json: JsonValue =
-        serde_json::from_str(&schema_content).context("Failed to parse schema JSON")?;
+/// Validate one or more JSON files against the crJSON schema, or against `schemas` in sequence
+/// (file paths, or `bundled` for the crJSON schema) when given, with a summary per directory
+/// when the inputs span more than one.
+pub fn validate_json_files(
+    input_paths: &[PathBuf],
+    schemas: &[String],
+    options: &ValidateOptions,
+) -> Result<()> {
+    let specs = if schemas.is_empty() {
+        vec![BUNDLED_SCHEMA_SPEC.to_string()]
+    } else {
+        schemas.to_vec()
+    };
+    if schemas.is_empty() {
+        println!("=== Validating JSON files against crJSON schema ===\n");
+    } else {
+        println!(
+            "=== Validating JSON files against {} schema(s): {} ===\n",
+            specs.len(),
+            specs.join(", ")
+        );
+    }
 
-    let compiled_schema = jsonschema::validator_for(&schema_json)
-        .map_err(|e| anyhow::anyhow!("Failed to compile JSON schema: {}", e))?;
+    let mut profile = ValidationProfile::new();
+    for spec in &specs {
+        let schema = if spec.eq_ignore_ascii_case(BUNDLED_SCHEMA_SPEC) {
+            let path = crtool::crjson_schema_path();
+            println!("Loading schema from: {:?}", path);
+            SchemaValidator::from_bundled()?
+        } else {
+            let path = Path::new(spec);
+            if !path.exists() {
+                anyhow::bail!("Schema file not found at: {:?}", path);
+            }
+            println!("Loading schema from: {:?}", path);
+            SchemaValidator::from_path(path)?
+        };
+        profile = profile.with_schema(spec.as_str(), schema);
+    }
+    // With one schema, findings need no label saying which schema reported them.
+    let label_findings = specs.len() > 1;
+    let compiled_schema = profile.with_rules(options.rules.clone());
 
-    println!("Schema compiled successfully\n");
+    println!("\nSchema compiled successfully\n");
 
     let mut total_files = 0;
     let mut valid_files = 0;
     let mut invalid_files = 0;
+    let mut skipped_files = 0;
+    let mut warning_count = 0;
     let mut error_details = Vec::new();
+    let mut by_directory: BTreeMap<PathBuf, DirectoryCounts> = BTreeMap::new();
 
     for input_path in input_paths {
         total_files += 1;
         println!("Validating: {:?}", input_path);
+        let counts = by_directory
+            .entry(
+                input_path
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default(),
+            )
+            .or_default();
 
         let json_content = match fs::read_to_string(input_path) {
             Ok(content) => content,
             Err(e) => {
                 println!("  ✗ ERROR: Failed to read file: {}\n", e);
                 invalid_files += 1;
+                counts.invalid += 1;
                 error_details.push((input_path.clone(), format!("Failed to read file: {}", e)));
                 continue;
             }
         };
 
-        let json_value: JsonValue = match serde_json::from_str(&json_content) {
+        let json_value = match crtool::limits::parse_json(&json_content) {
             Ok(value) => value,
             Err(e) => {
-                println!("  ✗ ERROR: Invalid JSON: {}\n", e);
+                let message = match crtool::limits::limit_exceeded(&e) {
+                    Some(limit) => limit.to_string(),
+                    None => format!("Invalid JSON: {}", e),
+                };
+                println!("  ✗ ERROR: {}\n", message);
                 invalid_files += 1;
-                error_details.push((input_path.clone(), format!("Invalid JSON: {}", e)));
+                counts.invalid += 1;
+                error_details.push((input_path.clone(), message));
                 continue;
             }
         };
 
-        let validation_result = compiled_schema.validate(&json_value);
-        match validation_result {
-            Ok(_) => {
-                println!("  ✓ Valid\n");
-                valid_files += 1;
+        if options.skip_non_indicators && !is_indicators_document(&json_value) {
+            println!("  - Skipped: @context is not a crJSON/indicators context\n");
+            skipped_files += 1;
+            counts.skipped += 1;
+            continue;
+        }
+
+        let mut result = compiled_schema.validate(&json_value);
+        result.errors.sort_by_key(|f| std::cmp::Reverse(f.severity));
+        if !label_findings {
+            result.errors.iter_mut().for_each(|f| f.schema = None);
+        }
+        warning_count += result.count(Severity::Warning);
+        if result.is_valid {
+            println!("  ✓ Valid");
+            for finding in &result.errors {
+                println!("{}", finding_line(finding));
             }
-            Err(errors) => {
-                println!("  ✗ Validation failed:");
-                let mut error_messages = Vec::new();
-                for error in errors {
-                    let instance_path = if error.instance_path.to_string().is_empty() {
-                        "root".to_string()
-                    } else {
-                        error.instance_path.to_string()
-                    };
-                    let message = format!("    - At {}: {}", instance_path, error);
-                    println!("{}", message);
-                    error_messages.push(message);
-                }
-                println!();
-                invalid_files += 1;
-                error_details.push((input_path.clone(), error_messages.join("\n")));
+            println!();
+            valid_files += 1;
+            counts.valid += 1;
+        } else {
+            println!("  ✗ Validation failed:");
+            let mut error_messages = Vec::new();
+            for finding in &result.errors {
+                let message = finding_line(finding);
+                println!("{}", message);
+                error_messages.push(message);
             }
+            println!();
+            invalid_files += 1;
+            counts.invalid += 1;
+            error_details.push((input_path.clone(), error_messages.join("\n")));
         }
     }
 
+    if by_directory.len() > 1 {
+        println!("=== Per-directory Summary ===");
+        for (dir, counts) in &by_directory {
+            let mut line = format!(
+                "  {:?}: {} valid, {} invalid",
+                dir, counts.valid, counts.invalid
+            );
+            if options.skip_non_indicators {
+                line.push_str(&format!(", {} skipped", counts.skipped));
+            }
+            println!("{}", line);
+        }
+        println!();
+    }
+
     println!("=== Validation Summary ===");
     println!("  Total files: {}", total_files);
     println!("  Valid: {}", valid_files);
     println!("  Invalid: {}", invalid_files);
+    if warning_count > 0 {
+        println!("  Warnings: {}", warning_count);
+    }
+    if options.skip_non_indicators {
+        println!("  Skipped (not indicators documents): {}", skipped_files);
+    }
 
     if invalid_files > 0 {
         println!("\n=== Files with Validation Errors ===");
@@ -228,6 +936,150 @@ pub fn validate_json_files(
     Ok(())
 }
 
+/// One finding of [`validate_json_files`], marked by severity: `-` for errors, `!` for
+/// warnings, `i` for notes.
+fn finding_line(finding: &ValidationError) -> String {
+    let marker = match finding.severity {
+        Severity::Error => "-",
+        Severity::Warning => "! warning:",
+        Severity::Info => "i info:",
+    };
+    match &finding.schema {
+        Some(schema) => format!(
+            "    {} At {}: {} [{}, {}]",
+            marker, finding.instance_path, finding.message, finding.rule, schema
+        ),
+        None => format!(
+            "    {} At {}: {} [{}]",
+            marker, finding.instance_path, finding.message, finding.rule
+        ),
+    }
+}
+
+/// Errors from validating `value` against one schema (empty when it is valid). Warnings and
+/// notes are left out.
+fn schema_errors(validator: &SchemaValidator, value: &JsonValue) -> Vec<String> {
+    validator
+        .validate(value)
+        .errors
+        .into_iter()
+        .filter(|error| error.severity == Severity::Error)
+        .map(|error| format!("At {}: {}", error.instance_path, error.message))
+        .collect()
+}
+
+/// A document that passes one schema but not the other.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaDivergence {
+    pub input: String,
+    /// Label of the schema the document passes.
+    pub passes: String,
+    /// Label of the schema the document fails.
+    pub fails: String,
+    pub errors: Vec<String>,
+}
+
+/// Result of validating the same documents against two schema versions.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaComparisonReport {
+    pub schemas: [String; 2],
+    pub total: usize,
+    pub both_valid: usize,
+    pub both_invalid: usize,
+    pub divergent: Vec<SchemaDivergence>,
+    /// Inputs that could not be read or parsed, with the reason.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unreadable: Vec<(String, String)>,
+}
+
+/// Validate each input against both schemas and record the documents whose outcome differs.
+pub fn compare_schema_versions(
+    input_paths: &[PathBuf],
+    schemas: [(String, PathBuf); 2],
+) -> Result<SchemaComparisonReport> {
+    let [(first_label, first_path), (second_label, second_path)] = schemas;
+    let first = SchemaValidator::from_path(&first_path)?;
+    let second = SchemaValidator::from_path(&second_path)?;
+
+    let mut report = SchemaComparisonReport {
+        schemas: [first_label, second_label],
+        total: input_paths.len(),
+        both_valid: 0,
+        both_invalid: 0,
+        divergent: Vec::new(),
+        unreadable: Vec::new(),
+    };
+    for input_path in input_paths {
+        let input = input_path.display().to_string();
+        let value = fs::read_to_string(input_path)
+            .map_err(|e| format!("Failed to read file: {}", e))
+            .and_then(|c| {
+                serde_json::from_str::<JsonValue>(&c).map_err(|e| format!("Invalid JSON: {}", e))
+            });
+        let value = match value {
+            Ok(v) => v,
+            Err(e) => {
+                report.unreadable.push((input, e));
+                continue;
+            }
+        };
+        let first_errors = schema_errors(&first, &value);
+        let second_errors = schema_errors(&second, &value);
+        match (first_errors.is_empty(), second_errors.is_empty()) {
+            (true, true) => report.both_valid += 1,
+            (false, false) => report.both_invalid += 1,
+            (true, false) => report.divergent.push(SchemaDivergence {
+                input,
+                passes: report.schemas[0].clone(),
+                fails: report.schemas[1].clone(),
+                errors: second_errors,
+            }),
+            (false, true) => report.divergent.push(SchemaDivergence {
+                input,
+                passes: report.schemas[1].clone(),
+                fails: report.schemas[0].clone(),
+                errors: first_errors,
+            }),
+        }
+    }
+    Ok(report)
+}
+
+/// Print a [`SchemaComparisonReport`] for humans.
+pub fn print_schema_comparison(report: &SchemaComparisonReport) {
+    let [first, second] = &report.schemas;
+    println!("=== Comparing schemas: {} vs {} ===\n", first, second);
+    for divergence in &report.divergent {
+        println!("{}", divergence.input);
+        println!("  ✓ {}  ✗ {}", divergence.passes, divergence.fails);
+        for error in &divergence.errors {
+            println!("    - {}", error);
+        }
+        println!();
+    }
+    for (input, error) in &report.unreadable {
+        println!("{}\n  ✗ ERROR: {}\n", input, error);
+    }
+    let only = |label: &str| {
+        report
+            .divergent
+            .iter()
+            .filter(|d| d.passes == label)
+            .count()
+    };
+    println!("=== Schema Comparison Summary ===");
+    println!("  Total files: {}", report.total);
+    println!("  Valid under both: {}", report.both_valid);
+    println!("  Invalid under both: {}", report.both_invalid);
+    println!("  Valid only under {}: {}", first, only(first));
+    println!("  Valid only under {}: {}", second, only(second));
+    if !report.unreadable.is_empty() {
+        println!("  Unreadable: {}", report.unreadable.len());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,8 +1093,8 @@ mod tests {
             .join("simple_manifest.json");
 
         if manifest_path.exists() {
-            let schema_path = crtool::crjson_schema_path();
-            let result = validate_json_files(&[manifest_path.clone()], &schema_path, "crJSON");
+            let result =
+                validate_json_files(&[manifest_path.clone()], &[], &ValidateOptions::default());
             assert!(result.is_err());
         }
     }
@@ -257,8 +1109,11 @@ mod tests {
         writeln!(file, "{{ invalid json }}").expect("Failed to write temp file");
         drop(file);
 
-        let schema_path = crtool::crjson_schema_path();
-        let result = validate_json_files(std::slice::from_ref(&temp_file), &schema_path, "crJSON");
+        let result = validate_json_files(
+            std::slice::from_ref(&temp_file),
+            &[],
+            &ValidateOptions::default(),
+        );
         assert!(result.is_err());
 
         let _ = fs::remove_file(temp_file);
@@ -267,8 +1122,40 @@ mod tests {
     #[test]
     fn test_validate_json_files_with_nonexistent_file() {
         let nonexistent = PathBuf::from("/nonexistent/file.json");
-        let schema_path = crtool::crjson_schema_path();
-        let result = validate_json_files(&[nonexistent], &schema_path, "crJSON");
+        let result = validate_json_files(&[nonexistent], &[], &ValidateOptions::default());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_is_indicators_document() {
+        let crjson =
+            serde_json::json!({"@context": ["https://contentcredentials.org/crjson/context/v1"]});
+        let jpt = serde_json::json!({"@context": "https://jpeg.org/jpegtrust/context/v1"});
+        let other = serde_json::json!({"@context": "https://schema.org", "manifests": []});
+        assert!(is_indicators_document(&crjson));
+        assert!(is_indicators_document(&jpt));
+        assert!(!is_indicators_document(&other));
+        assert!(!is_indicators_document(
+            &serde_json::json!({"name": "package"})
+        ));
+    }
+
+    #[test]
+    fn test_write_checksum_manifest() {
+        let dir = std::env::temp_dir().join("crtool_checksum_manifest_test");
+        fs::create_dir_all(&dir).expect("Failed to create temp dir");
+        let output = dir.join("a_cr.json");
+        fs::write(&output, b"abc").expect("Failed to write temp file");
+
+        let (manifest_path, checksums) =
+            write_checksum_manifest(&dir, std::slice::from_ref(&output)).unwrap();
+        let sha256_abc = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert_eq!(checksums, vec![(output, sha256_abc.to_string())]);
+        assert_eq!(
+            fs::read_to_string(&manifest_path).unwrap(),
+            format!("{}  a_cr.json\n", sha256_abc)
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
 }
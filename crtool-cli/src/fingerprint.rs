@@ -0,0 +1,75 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool fingerprint`: hash-only fingerprints (asset hash, manifest labels, signer certificate
+//! hashes) that can be shared without sharing content metadata.
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use c2pa::Settings;
+use crtool::fingerprint::{fingerprint_asset, AssetFingerprint};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fingerprint every input. Inputs without readable Content Credentials are reported and left
+/// out. The fingerprints (a JSON array without file paths) are written to `output` when given
+/// and printed to stdout with `json`.
+pub fn write_fingerprints(
+    input_files: &[PathBuf],
+    settings: &Settings,
+    output: Option<&Path>,
+    json: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    logger.info("=== Fingerprints ===");
+    let mut fingerprints: Vec<AssetFingerprint> = Vec::new();
+    let mut skipped = 0u32;
+    for input_file in input_files {
+        logger.info(&format!("  📄 {}", input_file.display()));
+        match fingerprint_asset(input_file, settings) {
+            Ok(fingerprint) => {
+                logger.info(&format!("     asset sha256: {}", fingerprint.asset_sha256));
+                for manifest in &fingerprint.manifests {
+                    let signer = manifest
+                        .signer_cert_sha256
+                        .as_deref()
+                        .or(manifest.error.as_deref())
+                        .unwrap_or("—");
+                    logger.info(&format!("     {} signer: {signer}", manifest.label));
+                }
+                fingerprints.push(fingerprint);
+            }
+            Err(e) => {
+                logger.error(&format!("     ⏭️  Skipped {}: {e:#}", input_file.display()));
+                skipped += 1;
+            }
+        }
+    }
+    logger.info(&format!(
+        "\n📊 Fingerprints: {} asset(s), {skipped} skipped without Content Credentials",
+        fingerprints.len()
+    ));
+
+    let report =
+        serde_json::to_string_pretty(&fingerprints).context("Failed to format fingerprints")?;
+    if let Some(path) = output {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).context("Failed to create fingerprint directory")?;
+        }
+        fs::write(path, &report).context("Failed to write fingerprints")?;
+        logger.info(&format!("   📝 Fingerprints: {}", path.display()));
+    }
+    if json {
+        println!("{}", report);
+    }
+    Ok(())
+}
@@ -0,0 +1,179 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool grep`: search the manifests of many assets for assertions matching an expression.
+//! Extracted manifest stores are cached on disk, so repeated searches of a corpus only read
+//! files that changed.
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use c2pa::Settings;
+use crtool::grep::{GrepMatch, GrepQuery};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Longest matched value printed per line; the `--json` report always has the full value.
+const MAX_VALUE_CHARS: usize = 200;
+
+/// Per-file entry of `crTool grep --json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrepFileResult {
+    pub input: String,
+    pub matches: Vec<GrepMatch>,
+}
+
+/// On-disk cache of extracted crJSON, keyed by the file's path, size and modification time
+/// (and the crTool version, so upgrades re-extract).
+pub struct ExtractionCache {
+    dir: Option<PathBuf>,
+}
+
+impl ExtractionCache {
+    /// Cache below `dir`, or no caching when `None`.
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
+    /// Default cache directory below the scratch directory.
+    pub fn default_dir() -> PathBuf {
+        crtool::scratch::scratch_dir().join("crtool-grep-cache")
+    }
+
+    fn entry_path(&self, input: &Path) -> Option<PathBuf> {
+        let dir = self.dir.as_ref()?;
+        let meta = fs::metadata(input).ok()?;
+        let modified = meta
+            .modified()
+            .ok()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?;
+        let path = fs::canonicalize(input).unwrap_or_else(|_| input.to_path_buf());
+        let key = format!(
+            "{}\n{}\n{}\n{}",
+            env!("CARGO_PKG_VERSION"),
+            path.display(),
+            meta.len(),
+            modified.as_nanos()
+        );
+        let hash =
+            crtool::hashing::hash_bytes(key.as_bytes(), crtool::hashing::HashAlgorithm::Sha256);
+        Some(dir.join(format!("{hash}.json")))
+    }
+
+    /// The crJSON manifest store of `input`, from the cache when it is current. Returns
+    /// `Ok(None)` for assets without Content Credentials.
+    pub fn manifest_store(
+        &self,
+        input: &Path,
+        settings: &Settings,
+    ) -> Result<Option<serde_json::Value>> {
+        let entry = self.entry_path(input);
+        if let Some(cached) = entry
+            .as_ref()
+            .and_then(|p| fs::read(p).ok())
+            .and_then(|data| serde_json::from_slice(&data).ok())
+        {
+            return Ok(Some(cached));
+        }
+        let crjson = match crtool::extract_crjson_manifest_fast(input, settings) {
+            Ok(result) => result.manifest_value,
+            Err(e) if e.is_no_manifest() => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if let Some(entry) = entry {
+            // A cache that cannot be written only costs a re-extraction next time
+            if let Some(parent) = entry.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            let _ = fs::write(&entry, serde_json::to_vec(&crjson)?);
+        }
+        Ok(Some(crjson))
+    }
+}
+
+fn short_value(value: &serde_json::Value) -> String {
+    let text = value.to_string();
+    if text.chars().count() > MAX_VALUE_CHARS {
+        let cut: String = text.chars().take(MAX_VALUE_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        text
+    }
+}
+
+/// Search every input and print the matching files with the manifest, assertion and JSON path
+/// of each match. Fails when nothing matched, like `grep`.
+pub fn grep_files(
+    input_files: &[PathBuf],
+    query: &GrepQuery,
+    cache: &ExtractionCache,
+    settings: &Settings,
+    json: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    let mut results: Vec<GrepFileResult> = Vec::new();
+    let mut without_credentials = 0u32;
+    let mut error_count = 0u32;
+    for input_file in input_files {
+        let crjson = match cache.manifest_store(input_file, settings) {
+            Ok(Some(crjson)) => crjson,
+            Ok(None) => {
+                without_credentials += 1;
+                continue;
+            }
+            Err(e) => {
+                logger.error(&format!("  ❌ {}: {e:#}", input_file.display()));
+                error_count += 1;
+                continue;
+            }
+        };
+        let matches = query
+            .search(&crjson)
+            .with_context(|| format!("Failed to search {}", input_file.display()))?;
+        if matches.is_empty() {
+            continue;
+        }
+        logger.info(&format!("📄 {}", input_file.display()));
+        for m in &matches {
+            logger.info(&format!(
+                "   {} {}{}: {}",
+                m.manifest,
+                m.assertion,
+                m.path.trim_start_matches('$'),
+                short_value(&m.value)
+            ));
+        }
+        results.push(GrepFileResult {
+            input: input_file.display().to_string(),
+            matches,
+        });
+    }
+
+    let match_count: usize = results.iter().map(|r| r.matches.len()).sum();
+    logger.info(&format!(
+        "\n🔎 {match_count} match(es) in {} of {} file(s) ({without_credentials} without Content Credentials, {error_count} unreadable)",
+        results.len(),
+        input_files.len()
+    ));
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&results).context("Failed to format grep results")?
+        );
+    }
+    if results.is_empty() {
+        anyhow::bail!("No manifest matched");
+    }
+    Ok(())
+}
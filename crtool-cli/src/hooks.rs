@@ -0,0 +1,206 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! External command hooks around signing (`--pre-sign-hook`, `--post-sign-hook`).
+//!
+//! Hooks run through the platform shell (`sh -c` / `cmd /C`) and receive the asset details in
+//! `CRTOOL_*` environment variables.
+
+use crate::jobs::{emit, say, OutputLine};
+use anyhow::{Context, Result};
+use crtool::scratch::ScratchDir;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Label of the soft-binding assertion added from pre-sign hook output.
+const SOFT_BINDING_LABEL: &str = "c2pa.soft-binding";
+
+/// External commands to run around signing.
+#[derive(Debug, Clone, Default)]
+pub struct SignHooks {
+    /// Runs on a working copy of the asset before it is hashed and signed.
+    pub pre_sign: Option<String>,
+    /// Runs once per successfully signed output (e.g. to upload it to a DAM/CDN).
+    pub post_sign: Option<String>,
+}
+
+/// JSON the pre-sign hook may print on stdout. Empty output means the hook only modified the file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreSignHookOutput {
+    /// Soft-binding assertion data (`alg`, `blocks`, and optional `pad`, `name`, `alg-params`, ...).
+    pub soft_binding: Option<JsonValue>,
+}
+
+/// Build a command that runs `cmd` through the platform shell.
+fn shell_command(cmd: &str) -> Command {
+    if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.args(["/C", cmd]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", cmd]);
+        c
+    }
+}
+
+/// Parse the hook's stdout. Blank output is allowed; anything else must be a JSON object.
+pub fn parse_pre_sign_output(stdout: &str) -> Result<PreSignHookOutput> {
+    if stdout.trim().is_empty() {
+        return Ok(PreSignHookOutput::default());
+    }
+    let output: PreSignHookOutput =
+        serde_json::from_str(stdout.trim()).context("Pre-sign hook output is not valid JSON")?;
+    if let Some(sb) = &output.soft_binding {
+        if sb.get("alg").and_then(|v| v.as_str()).is_none() {
+            anyhow::bail!("Pre-sign hook softBinding is missing the required 'alg' string");
+        }
+        if sb.get("blocks").and_then(|v| v.as_array()).is_none() {
+            anyhow::bail!("Pre-sign hook softBinding is missing the required 'blocks' array");
+        }
+    }
+    Ok(output)
+}
+
+/// Add a `c2pa.soft-binding` assertion with `data` to the manifest definition JSON.
+pub fn add_soft_binding_assertion(manifest_json: &str, data: &JsonValue) -> Result<String> {
+    let mut manifest: JsonValue =
+        serde_json::from_str(manifest_json).context("Failed to parse manifest JSON")?;
+    let obj = manifest
+        .as_object_mut()
+        .context("Manifest JSON must be an object")?;
+    let assertions = obj
+        .entry("assertions")
+        .or_insert_with(|| JsonValue::Array(Vec::new()))
+        .as_array_mut()
+        .context("Manifest 'assertions' must be an array")?;
+    assertions.push(serde_json::json!({
+        "label": SOFT_BINDING_LABEL,
+        "data": data,
+    }));
+    serde_json::to_string(&manifest).context("Failed to serialize manifest JSON")
+}
+
+/// A copy of the input asset for the pre-sign hook to modify; removed on drop.
+pub struct WorkingCopy {
+    _dir: ScratchDir,
+    pub path: PathBuf,
+}
+
+impl WorkingCopy {
+    /// Copy `input` into a fresh scratch directory (see `--temp-dir`), keeping its file name
+    /// (and so its extension).
+    pub fn new(input: &Path) -> Result<Self> {
+        let dir = ScratchDir::new("crtool-presign")
+            .context("Failed to create pre-sign working directory")?;
+        let path = dir.join(input.file_name().context("Input file has no filename")?);
+        fs::copy(input, &path).context("Failed to copy asset for pre-sign hook")?;
+        Ok(Self { _dir: dir, path })
+    }
+}
+
+/// Run the pre-sign hook on `asset` (a working copy of `original`), which it may modify in
+/// place. Environment: `CRTOOL_HOOK=pre-sign`, `CRTOOL_ASSET_PATH` (file to modify),
+/// `CRTOOL_INPUT_PATH` (original input), `CRTOOL_FORMAT` (MIME type, when known).
+pub fn run_pre_sign_hook(cmd: &str, asset: &Path, original: &Path) -> Result<PreSignHookOutput> {
+    say!("  Running pre-sign hook: {}", cmd);
+    let mut command = shell_command(cmd);
+    command
+        .env("CRTOOL_HOOK", "pre-sign")
+        .env("CRTOOL_ASSET_PATH", asset)
+        .env("CRTOOL_INPUT_PATH", original);
+    if let Some(format) = crtool::asset_format_for_path(original) {
+        command.env("CRTOOL_FORMAT", format.mime);
+    }
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run pre-sign hook: {}", cmd))?;
+    emit_lines(&output.stderr, OutputLine::Stderr);
+    if !output.status.success() {
+        anyhow::bail!("Pre-sign hook exited with {}", output.status);
+    }
+    parse_pre_sign_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Run the post-sign hook for a signed output. Environment: `CRTOOL_HOOK=post-sign`,
+/// `CRTOOL_OUTPUT_PATH`, `CRTOOL_MANIFEST_LABEL` (active manifest), `CRTOOL_SHA256` (hex digest
+/// of the output file), `CRTOOL_FORMAT` (MIME type, when known).
+pub fn run_post_sign_hook(cmd: &str, output_path: &Path) -> Result<()> {
+    say!("  Running post-sign hook: {}", cmd);
+    let manifest_label = c2pa::Reader::from_file(output_path)
+        .ok()
+        .and_then(|r| r.active_label().map(|l| l.to_string()))
+        .unwrap_or_default();
+    let sha256 = crtool::hashing::hash_file(output_path, crtool::hashing::HashAlgorithm::Sha256)?;
+
+    let mut command = shell_command(cmd);
+    command
+        .env("CRTOOL_HOOK", "post-sign")
+        .env("CRTOOL_OUTPUT_PATH", output_path)
+        .env("CRTOOL_MANIFEST_LABEL", manifest_label)
+        .env("CRTOOL_SHA256", sha256);
+    if let Some(format) = crtool::asset_format_for_path(output_path) {
+        command.env("CRTOOL_FORMAT", format.mime);
+    }
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run post-sign hook: {}", cmd))?;
+    emit_lines(&output.stdout, OutputLine::Stdout);
+    emit_lines(&output.stderr, OutputLine::Stderr);
+    if !output.status.success() {
+        anyhow::bail!("Post-sign hook exited with {}", output.status);
+    }
+    Ok(())
+}
+
+/// Pass a hook's output on with the rest of the signing's output, so that under `sign --jobs`
+/// it is written together with it.
+fn emit_lines(bytes: &[u8], line: fn(String) -> OutputLine) {
+    for text in String::from_utf8_lossy(bytes).lines() {
+        emit(line(text.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pre_sign_output() {
+        assert!(parse_pre_sign_output("  \n")
+            .unwrap()
+            .soft_binding
+            .is_none());
+
+        let out = parse_pre_sign_output(
+            r#"{"softBinding": {"alg": "com.example.wm", "blocks": [{"scope": {}, "value": "abc"}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(out.soft_binding.unwrap()["alg"], "com.example.wm");
+
+        assert!(parse_pre_sign_output(r#"{"softBinding": {"blocks": []}}"#).is_err());
+        assert!(parse_pre_sign_output("not json").is_err());
+    }
+
+    #[test]
+    fn test_add_soft_binding_assertion() {
+        let data = serde_json::json!({"alg": "com.example.wm", "blocks": []});
+        let json = add_soft_binding_assertion(r#"{"title": "x"}"#, &data).unwrap();
+        let manifest: JsonValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(manifest["assertions"][0]["label"], SOFT_BINDING_LABEL);
+        assert_eq!(manifest["assertions"][0]["data"], data);
+    }
+}
@@ -0,0 +1,106 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool info`: a short summary of each asset's Content Credentials.
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use c2pa::Settings;
+use crtool::filter::FileFacts;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Per-file entry of `crTool info --json`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InfoEntry {
+    pub input: String,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub facts: Option<FileFacts>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn print_facts(facts: &FileFacts, logger: &mut Logger) {
+    let or_dash = |v: &Option<String>| v.clone().unwrap_or_else(|| "—".to_string());
+    logger.info(&format!(
+        "     Format: {} ({})",
+        or_dash(&facts.format),
+        or_dash(&facts.kind)
+    ));
+    logger.info(&format!("     Active manifest: {}", facts.active_label));
+    logger.info(&format!(
+        "     Manifests: {} (chain depth {}), ingredients: {}",
+        facts.manifest_count, facts.chain_depth, facts.ingredient_count
+    ));
+    logger.info(&format!(
+        "     Signer: {} ({})",
+        or_dash(&facts.issuer),
+        or_dash(&facts.algorithm)
+    ));
+    logger.info(&format!("     Signed at: {}", or_dash(&facts.signed_at)));
+    logger.info_trust("     Trust: {trust}", &facts.trust);
+    let validity = if facts.valid {
+        "valid".to_string()
+    } else {
+        format!("invalid ({} failure(s))", facts.failure_count)
+    };
+    logger.info(&format!("     Validation: {validity}"));
+}
+
+/// Summarize every input. Assets whose manifest store cannot be read are reported and fail the
+/// run; with `json` the summaries are printed to stdout as a JSON array.
+pub fn print_info(
+    input_files: &[PathBuf],
+    settings: &Settings,
+    json: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    let mut entries = Vec::new();
+    for input_file in input_files {
+        logger.info(&format!("  📄 {}", input_file.display()));
+        let input = input_file.display().to_string();
+        let entry = match crtool::extract_crjson_manifest_with_settings(input_file, settings) {
+            Ok(extraction) => {
+                let facts = FileFacts::from_extraction(&extraction);
+                print_facts(&facts, logger);
+                InfoEntry {
+                    input,
+                    facts: Some(facts),
+                    error: None,
+                }
+            }
+            Err(e) => {
+                logger.error(&format!("     ❌ {}: {e:#}", input_file.display()));
+                InfoEntry {
+                    input,
+                    facts: None,
+                    error: Some(format!("{e:#}")),
+                }
+            }
+        };
+        entries.push(entry);
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).context("Failed to format info report")?
+        );
+    }
+
+    let unreadable = entries.iter().filter(|e| e.error.is_some()).count();
+    if unreadable > 0 {
+        anyhow::bail!("{unreadable} file(s) have no readable Content Credentials");
+    }
+    Ok(())
+}
@@ -0,0 +1,200 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `sign --jobs N`: sign several assets at the same time on worker threads.
+//!
+//! Each signing builds its own signer (see [`crtool::sign_asset`]), so workers share only the
+//! per-run [`crate::processing::SignSettings`], whose ingredient cache is thread-safe. PKCS#11
+//! and keychain keys cannot be opened from several threads at once, so `sign` refuses `--jobs`
+//! with them before starting any workers.
+//!
+//! Signing code prints its progress with [`say!`]. On a worker the lines are collected rather
+//! than printed, and written together when the task finishes, so the output of parallel
+//! signings does not interleave with each other or with the progress bar.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// A line of task output and the stream it belongs on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+impl OutputLine {
+    fn print(&self) {
+        match self {
+            OutputLine::Stdout(line) => println!("{line}"),
+            OutputLine::Stderr(line) => eprintln!("{line}"),
+        }
+    }
+}
+
+thread_local! {
+    /// Lines collected by [`capture`] on this thread, `None` when not capturing.
+    static CAPTURED: RefCell<Option<Vec<OutputLine>>> = const { RefCell::new(None) };
+}
+
+/// Print `line`, or keep it when the thread is inside [`capture`].
+pub fn emit(line: OutputLine) {
+    CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(lines) => lines.push(line),
+        None => line.print(),
+    });
+}
+
+/// `println!` for signing progress; see [`emit`].
+macro_rules! say {
+    ($($arg:tt)*) => {
+        $crate::jobs::emit($crate::jobs::OutputLine::Stdout(format!($($arg)*)))
+    };
+}
+
+pub(crate) use say;
+
+/// Run `f`, returning what it printed with [`emit`] instead of printing it.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Vec<OutputLine>) {
+    let outer = CAPTURED.with(|captured| captured.replace(Some(Vec::new())));
+    let result = f();
+    let lines = CAPTURED.with(|captured| captured.replace(outer));
+    (result, lines.unwrap_or_default())
+}
+
+/// Run `work` on each of `tasks` using `jobs` worker threads. `done` is called on the calling
+/// thread as each task finishes (in completion order) with the task's index, its result and how
+/// long it took; a progress bar on stderr counts the finished tasks. What the task printed with
+/// [`say!`] and what `done` prints are written above it.
+pub fn run_parallel<T, R>(
+    tasks: &[T],
+    jobs: usize,
+    show_progress: bool,
+    work: impl Fn(&T) -> R + Sync,
+    mut done: impl FnMut(usize, R, Duration),
+) where
+    T: Sync,
+    R: Send,
+{
+    let bar = if show_progress {
+        ProgressBar::new(tasks.len() as u64)
+    } else {
+        ProgressBar::hidden()
+    };
+    if let Ok(style) =
+        ProgressStyle::with_template("{bar:40} {pos}/{len} signed [{elapsed_precise}, ETA {eta}]")
+    {
+        bar.set_style(style);
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, tasks.len().max(1)) {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(task) = tasks.get(index) else {
+                    return;
+                };
+                let start = Instant::now();
+                let (result, output) = capture(|| work(task));
+                if sender
+                    .send((index, result, output, start.elapsed()))
+                    .is_err()
+                {
+                    return;
+                }
+            });
+        }
+        drop(sender);
+        for (index, result, output, elapsed) in receiver {
+            bar.suspend(|| {
+                output.iter().for_each(OutputLine::print);
+                done(index, result, elapsed)
+            });
+            bar.inc(1);
+        }
+    });
+    bar.finish_and_clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_parallel_runs_each_task_once() {
+        let tasks: Vec<usize> = (0..50).collect();
+        let mut seen = vec![0u32; tasks.len()];
+        run_parallel(
+            &tasks,
+            4,
+            false,
+            |n| n * 2,
+            |index, doubled, _| {
+                assert_eq!(doubled, tasks[index] * 2);
+                seen[index] += 1;
+            },
+        );
+        assert!(seen.iter().all(|&count| count == 1));
+
+        // What a task says is kept by the worker, not printed from it
+        let mut outputs = Vec::new();
+        run_parallel(
+            &tasks[..3],
+            2,
+            false,
+            |n| capture(|| say!("task {n}")).1,
+            |_, output, _| outputs.push(output),
+        );
+        outputs.sort_by_key(|output| format!("{output:?}"));
+        assert_eq!(
+            outputs,
+            (0..3)
+                .map(|n| vec![OutputLine::Stdout(format!("task {n}"))])
+                .collect::<Vec<_>>()
+        );
+
+        run_parallel(
+            &[] as &[usize],
+            4,
+            false,
+            |n| *n,
+            |_, _, _| panic!("no tasks"),
+        );
+    }
+    #[test]
+    fn test_capture_collects_output() {
+        let (value, lines) = capture(|| {
+            say!("first {}", 1);
+            emit(OutputLine::Stderr("warning".to_string()));
+            let (_, inner) = capture(|| say!("nested"));
+            assert_eq!(inner, vec![OutputLine::Stdout("nested".to_string())]);
+            42
+        });
+        assert_eq!(value, 42);
+        assert_eq!(
+            lines,
+            vec![
+                OutputLine::Stdout("first 1".to_string()),
+                OutputLine::Stderr("warning".to_string()),
+            ]
+        );
+        // Outside capture, nothing is kept
+        say!("printed");
+        assert!(CAPTURED.with(|captured| captured.borrow().is_none()));
+    }
+}
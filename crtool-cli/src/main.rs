@@ -10,20 +10,62 @@ OF ANY KIND, either express or implied. See the License for the specific languag
 governing permissions and limitations under the License.
 */
 
+mod assess;
 mod batch;
+#[cfg(feature = "signing")]
+mod batch_csv;
+mod capabilities;
+mod cbom;
+#[cfg(feature = "signing")]
+mod cert;
+mod compare_runs;
+#[cfg(feature = "signing")]
+mod demo;
+mod diff;
 mod extraction;
+mod fingerprint;
+mod grep;
+#[cfg(feature = "signing")]
+mod hooks;
+mod info;
+#[cfg(feature = "signing")]
+mod jobs;
+#[cfg(feature = "signing")]
 mod processing;
 mod profile;
+#[cfg(feature = "server")]
+mod serve;
+mod snapshot;
+mod soft_binding;
+mod strip;
+#[cfg(feature = "signing")]
 mod test_case;
+mod trust_profile;
+mod update;
+mod verify;
+#[cfg(feature = "signing")]
+mod watch;
 
 use anyhow::{Context, Result};
-use clap::Parser;
-use crtool::SUPPORTED_ASSET_EXTENSIONS;
-use extraction::{extract_manifest, extraction_settings, validate_json_files};
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use crtool::graph_export::GraphFormat;
+use crtool::severity::RuleSeverity;
+use crtool::soft_binding::SoftBindingStatus;
+use crtool::{MediaKind, SeverityRules};
+use extraction::{
+    compare_schema_versions, extract_manifest, extraction_settings, print_schema_comparison,
+    validate_json_files, write_badge, write_checksum_manifest, write_evidence_report,
+    ExtractOptions, ExtractionRunReport, ExtractionStats, FileReport, LocalTrustLists,
+    ValidateOptions,
+};
 use glob::glob;
+#[cfg(feature = "signing")]
+use processing::SignSettings;
 use profile::{run_profile_evaluation, ReportFormat};
+use std::ffi::OsString;
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
+#[cfg(feature = "signing")]
 use test_case::handle_create_test;
 
 // ─── Logger ──────────────────────────────────────────────────────────────────
@@ -31,11 +73,13 @@ use test_case::handle_create_test;
 /// Output manager: writes progress to stdout (unless quiet) and optionally to a log file.
 pub struct Logger {
     quiet: bool,
+    /// Send progress to stderr so stdout carries only the `--json` report.
+    json: bool,
     log_writer: Option<BufWriter<std::fs::File>>,
 }
 
 impl Logger {
-    pub fn new(quiet: bool, log_path: Option<&std::path::Path>) -> Result<Self> {
+    pub fn new(quiet: bool, json: bool, log_path: Option<&std::path::Path>) -> Result<Self> {
         let log_writer = if let Some(path) = log_path {
             let file = std::fs::File::create(path)
                 .with_context(|| format!("Failed to create log file: {}", path.display()))?;
@@ -44,13 +88,22 @@ impl Logger {
         } else {
             None
         };
-        Ok(Self { quiet, log_writer })
+        Ok(Self {
+            quiet,
+            json,
+            log_writer,
+        })
     }
 
-    /// Print informational message to stdout (suppressed by --quiet) and log file.
+    /// Print informational message to stdout (stderr with --json; suppressed by --quiet) and
+    /// log file.
     pub fn info(&mut self, msg: &str) {
         if !self.quiet {
-            println!("{msg}");
+            if self.json {
+                eprintln!("{msg}");
+            } else {
+                println!("{msg}");
+            }
         }
         if let Some(w) = &mut self.log_writer {
             let _ = writeln!(w, "{msg}");
@@ -64,6 +117,54 @@ impl Logger {
             let _ = writeln!(w, "ERROR: {msg}");
         }
     }
+
+    /// Like [`Logger::info`], with `{trust}` in `msg` replaced by the trust presentation of
+    /// `status`, colored when printing to a color terminal.
+    pub fn info_trust(&mut self, msg: &str, status: &str) {
+        let to_stderr = self.json;
+        if !self.quiet {
+            let line = trust_line(msg, status, color_terminal(to_stderr));
+            if to_stderr {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        }
+        if let Some(w) = &mut self.log_writer {
+            let _ = writeln!(w, "{}", trust_line(msg, status, false));
+        }
+    }
+
+    /// Like [`Logger::error`], with `{trust}` replaced as in [`Logger::info_trust`].
+    pub fn error_trust(&mut self, msg: &str, status: &str) {
+        eprintln!("{}", trust_line(msg, status, color_terminal(true)));
+        if let Some(w) = &mut self.log_writer {
+            let _ = writeln!(w, "ERROR: {}", trust_line(msg, status, false));
+        }
+    }
+}
+
+/// Whether stdout (or stderr) is a terminal and `NO_COLOR` is unset.
+fn color_terminal(stderr: bool) -> bool {
+    use std::io::IsTerminal;
+    std::env::var_os("NO_COLOR").is_none()
+        && if stderr {
+            std::io::stderr().is_terminal()
+        } else {
+            std::io::stdout().is_terminal()
+        }
+}
+
+fn trust_line(msg: &str, status: &str, color: bool) -> String {
+    let presentation = crtool::trust_presentation::trust_presentation(status);
+    let text = presentation.text();
+    let text = if color {
+        let [r, g, b] = presentation.rgb();
+        format!("\x1b[38;2;{r};{g};{b}m{text}\x1b[0m")
+    } else {
+        text
+    };
+    msg.replace("{trust}", &text)
 }
 
 // ─── CLI definition ───────────────────────────────────────────────────────────
@@ -72,38 +173,944 @@ impl Logger {
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    /// Path or glob pattern for test case JSON file(s) (C2PA validator test case schema).
-    /// Supports glob patterns (e.g., "test-cases/positive/tc-*.json", "test-cases/**/*.json").
-    /// Reads all signing configuration (manifest, cert, key, algorithm, TSA URL) from each file.
-    /// Use with -o to specify the output file or directory (directory required for multiple matches).
-    #[arg(short = 't', long = "create-test", value_name = "PATTERN")]
-    create_test: Option<String>,
-
-    /// Path(s) to input media asset(s). Supported: avi, avif, c2pa, dng, gif, heic, heif,
-    /// jpg/jpeg, m4a, mov, mp3, mp4, pdf, png, svg, tiff, wav, webp.
-    /// Supports glob patterns (e.g., "*.jpg", "images/*.png")
-    #[arg(value_name = "INPUT_FILE", required = false, num_args = 0..)]
-    input: Vec<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    /// Path to the output file or directory (not required in validate mode)
-    #[arg(short, long, value_name = "PATH")]
-    output: Option<PathBuf>,
+    /// Path to a batch JSON file — runs multiple commands in sequence
+    #[arg(short = 'b', long = "batch", value_name = "FILE")]
+    batch: Option<PathBuf>,
 
-    /// Extract manifest from input file to JSON (read-only mode; outputs crJSON)
-    #[arg(short, long, default_value = "false")]
-    extract: bool,
+    #[command(flatten)]
+    global: GlobalArgs,
+}
 
-    /// Validate JSON files against the crJSON schema
-    #[arg(short = 'v', long, default_value = "false")]
-    validate: bool,
+/// Options of every subcommand, accepted before or after it.
+#[derive(Args, Debug, Clone)]
+pub struct GlobalArgs {
+    /// Never use the network: skips the update check and refuses --trust (which downloads
+    /// trust lists). Also enabled by CRTOOL_OFFLINE=1
+    #[arg(long, default_value = "false", global = true)]
+    offline: bool,
+
+    /// Print machine-readable JSON to stdout (the run report of `extract`, or the results of
+    /// `info`, `verify`, `capabilities`, ...); progress output moves to stderr
+    #[arg(long, default_value = "false", global = true)]
+    json: bool,
+
+    /// Suppress progress output (errors are still shown on stderr)
+    #[arg(short = 'q', long = "quiet", default_value = "false", global = true)]
+    quiet: bool,
+
+    /// Write all progress output to a log file (in addition to stdout)
+    #[arg(short = 'l', long = "log", value_name = "FILE", global = true)]
+    log: Option<PathBuf>,
 
+    /// Directory for temporary files (e.g. --pre-sign-hook working copies), instead of
+    /// CRTOOL_TEMP_DIR or the system temp directory. Temporaries are removed when done
+    #[arg(long = "temp-dir", value_name = "DIR", global = true)]
+    temp_dir: Option<PathBuf>,
+
+    /// Refuse assets whose C2PA manifest store is larger than N MiB (default 128)
+    #[arg(long = "max-manifest-store-mb", value_name = "N", global = true)]
+    max_manifest_store_mb: Option<u64>,
+
+    /// Refuse crJSON and other JSON input nested deeper than N levels (default 128)
+    #[arg(long = "max-json-depth", value_name = "N", global = true)]
+    max_json_depth: Option<usize>,
+
+    /// Refuse JSON input containing a string longer than N MiB (default 32)
+    #[arg(long = "max-json-string-mb", value_name = "N", global = true)]
+    max_json_string_mb: Option<usize>,
+
+    /// Refuse JSON input containing an array of more than N elements (default 1000000)
+    #[arg(long = "max-json-array", value_name = "N", global = true)]
+    max_json_array: Option<usize>,
+
+    /// Refuse files downloaded from a URL (e.g. --ingredient-url) larger than N MiB
+    /// (default 1024)
+    #[arg(long = "max-download-mb", value_name = "N", global = true)]
+    max_download_mb: Option<u64>,
+
+    /// SHA-2 implementation for asset and output hashing: auto (pick from the CPU's SHA
+    /// instructions), sha2, or ring (builds with the `ring` feature)
+    #[arg(
+        long = "hash-backend",
+        value_name = "BACKEND",
+        global = true,
+        default_value = "auto"
+    )]
+    hash_backend: String,
+}
+
+impl GlobalArgs {
+    /// --offline, or CRTOOL_OFFLINE.
+    fn offline(&self) -> bool {
+        self.offline || crtool::update::offline_from_env()
+    }
+}
+
+/// How signers are trusted, for the commands that validate Content Credentials.
+#[derive(Args, Debug, Clone, Default)]
+pub struct TrustArgs {
     /// Enable trust list validation: load the official C2PA trust list and the Content
-    /// Credentials interim trust list for certificate validation during extract/read
+    /// Credentials interim trust list for certificate validation
     #[arg(long, default_value = "false")]
     trust: bool,
 
-    /// Path to the YAML asset profile for profile evaluation. When combined with --extract,
-    /// evaluates the extracted crJSON. When used alone, treats input files as crJSON indicators.
+    /// PEM file(s) of trust anchors (root or intermediate CA certificates) to validate signers
+    /// against, in addition to --trust lists when given (comma-separated or repeated)
+    #[arg(long = "trust-anchors", value_name = "FILE", value_delimiter = ',')]
+    trust_anchors: Vec<PathBuf>,
+
+    /// Signing certificates trusted without a chain to an anchor: a PEM bundle or a list of
+    /// base64 SHA-256 certificate hashes, one per line
+    #[arg(long = "allowed-list", value_name = "FILE")]
+    allowed_list: Option<PathBuf>,
+
+    /// Trust configuration file (JSON, or TOML with a `.toml` extension): trust anchor and
+    /// allowed certificate files, allowed certificate hashes, allowed EKUs and OCSP revocation
+    /// checks. Combined with --trust, --trust-anchors and --allowed-list
+    #[arg(
+        long = "trust-config",
+        value_name = "FILE",
+        env = "CRTOOL_TRUST_CONFIG"
+    )]
+    trust_config: Option<PathBuf>,
+
+    /// JSON file mapping trust statuses to the label, icon and color shown in summaries
+    /// (default: $CRTOOL_TRUST_PRESENTATION, else trust-presentation.json in the crTool
+    /// config directory)
+    #[arg(long = "trust-presentation", value_name = "FILE")]
+    trust_presentation: Option<PathBuf>,
+}
+
+impl TrustArgs {
+    fn local(&self) -> LocalTrustLists {
+        LocalTrustLists {
+            anchors: self.trust_anchors.clone(),
+            allowed_list: self.allowed_list.clone(),
+            config: self.trust_config.clone(),
+        }
+    }
+
+    /// Whether signers are checked against any trust list.
+    fn checks_trust(&self) -> bool {
+        self.trust || !self.local().is_empty()
+    }
+
+    /// The reader settings for these trust options.
+    fn settings(&self, global: &GlobalArgs) -> Result<c2pa::Settings> {
+        let offline = global.offline();
+        if offline && self.trust {
+            anyhow::bail!("--trust downloads trust lists and cannot be used with --offline");
+        }
+        extraction_settings(
+            self.trust,
+            &self.local(),
+            global.quiet || global.json,
+            offline,
+        )
+        .context("Failed to prepare extraction settings")
+    }
+}
+
+/// How asset inputs are expanded into the files to process.
+#[derive(Args, Debug, Clone, Default)]
+pub struct InputArgs {
+    /// Only process input files of the given media kind(s), applied after glob expansion.
+    /// Comma-separated: image, video, audio, document (e.g. `--kind image,video`).
+    /// Files of other kinds or unsupported formats are skipped instead of rejected.
+    #[arg(
+        long = "kind",
+        visible_alias = "only",
+        value_name = "KIND",
+        value_delimiter = ','
+    )]
+    kind: Vec<MediaKind>,
+
+    /// Accept directories as inputs and process every supported asset beneath them (respecting
+    /// --kind)
+    #[arg(short = 'r', long)]
+    recursive: bool,
+
+    /// Follow symbolic links when expanding inputs (default). Files reachable through several
+    /// links are still processed once
+    #[arg(long = "follow-symlinks", overrides_with = "no_follow_symlinks")]
+    follow_symlinks: bool,
+
+    /// Skip inputs that are symbolic links or are reached through a symlinked directory
+    #[arg(long = "no-follow-symlinks", overrides_with = "follow_symlinks")]
+    no_follow_symlinks: bool,
+}
+
+impl InputArgs {
+    /// --follow-symlinks and --no-follow-symlinks override each other; following is the default.
+    fn follow_symlinks(&self) -> bool {
+        self.follow_symlinks || !self.no_follow_symlinks
+    }
+
+    /// Expand `patterns` into the assets to process: globs, directories with --recursive,
+    /// files reached through several links once, and only the --kind kinds. Fails when nothing
+    /// is left or an input does not exist or is not a supported format.
+    fn expand(&self, patterns: &[String], logger: &mut Logger) -> Result<Vec<PathBuf>> {
+        let mut files =
+            expand_input_patterns(patterns).context("Failed to expand input file patterns")?;
+        if self.recursive {
+            files = expand_asset_directories(files, &self.kind)?;
+        }
+        let files = dedupe_and_report(files, self.follow_symlinks(), logger);
+        if files.is_empty() {
+            anyhow::bail!("No input files found matching the specified pattern(s)");
+        }
+
+        let total = files.len();
+        let files = filter_by_kind(files, &self.kind);
+        if files.is_empty() {
+            anyhow::bail!(
+                "None of the {total} input file(s) match --kind {}",
+                self.kind
+                    .iter()
+                    .map(|k| k.name())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+        }
+        if files.len() < total {
+            logger.info(&format!(
+                "🔎 --kind: skipped {} of {total} file(s)",
+                total - files.len()
+            ));
+        }
+
+        check_inputs_exist(&files)?;
+        let unsupported: Vec<_> = files
+            .iter()
+            .filter(|p| {
+                !crtool::is_supported_asset_path(p) && !crtool::documents::is_text_document_path(p)
+            })
+            .collect();
+        if !unsupported.is_empty() {
+            anyhow::bail!(
+                "Unsupported file format(s). The following file(s) have extensions not supported \
+                by C2PA: {:?}. Supported extensions: {}, and {} documents with a detached .c2pa \
+                store.",
+                unsupported.iter().map(|p| p.as_path()).collect::<Vec<_>>(),
+                crtool::supported_asset_extensions().join(", "),
+                crtool::documents::TEXT_DOCUMENT_FORMATS
+                    .iter()
+                    .map(|(ext, _)| *ext)
+                    .collect::<Vec<_>>()
+                    .join("/")
+            );
+        }
+
+        logger.info(&format!("🚀 Processing {} input file(s)", files.len()));
+        Ok(files)
+    }
+}
+
+/// Input assets of the commands that read Content Credentials.
+#[derive(Args, Debug, Clone)]
+pub struct AssetArgs {
+    /// Input media asset(s); glob patterns are supported
+    #[arg(value_name = "INPUT_FILE", required = true, num_args = 1..)]
+    input: Vec<String>,
+
+    #[command(flatten)]
+    inputs: InputArgs,
+
+    #[command(flatten)]
+    trust: TrustArgs,
+}
+
+impl AssetArgs {
+    /// The reader settings for the trust options and the expanded input files.
+    fn prepare(
+        &self,
+        global: &GlobalArgs,
+        logger: &mut Logger,
+    ) -> Result<(c2pa::Settings, Vec<PathBuf>)> {
+        let settings = self.trust.settings(global)?;
+        let files = self.inputs.expand(&self.input, logger)?;
+        Ok((settings, files))
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Sign assets using the manifest, certificate, key, algorithm, and TSA URL of C2PA
+    /// validator test case JSON file(s)
+    Sign(SignArgs),
+    /// Extract the manifest store of each asset to crJSON
+    Extract(ExtractArgs),
+    /// Validate JSON files against the crJSON schema
+    Validate(ValidateArgs),
+    /// Print a short summary of each asset's Content Credentials: format, manifests, signer,
+    /// signing time, trust, and validity
+    Info(AssetArgs),
+    /// Fully validate each asset's manifests (claim signatures, hash bindings, timestamps, and
+    /// signer trust against --trust/--trust-anchors/--allowed-list) and report the validation
+    /// codes per manifest
+    Verify {
+        #[command(flatten)]
+        assets: AssetArgs,
+
+        /// Write the JSON report to this file (stdout with --json)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Write hash-only fingerprints (asset SHA-256, manifest labels, signer certificate
+    /// SHA-256) for matching provenance with other organizations without sharing titles,
+    /// authors, thumbnails, or file paths
+    Fingerprint {
+        #[command(flatten)]
+        assets: AssetArgs,
+
+        /// Write the fingerprints (a JSON array) to this file
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Write a content bill of materials: the asset and every ingredient below it, with
+    /// titles, hashes, source types, licenses and trust status, as SPDX-like JSON
+    Cbom {
+        #[command(flatten)]
+        assets: AssetArgs,
+
+        /// Write the CBOM to this file (a directory for several inputs) instead of stdout
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Digest algorithm for the checksums: sha256, sha384, or sha512
+        #[arg(long = "hash-alg", value_name = "ALG", default_value = "sha256")]
+        hash_alg: crtool::hashing::HashAlgorithm,
+    },
+    /// Compare the crJSON of each input, normalized so that re-signing does not change it,
+    /// with the snapshot stored under the input's file stem (e.g. the signed testset assets
+    /// after a c2pa-rs upgrade). Exits non-zero when an input has no snapshot or its extraction
+    /// differs from it, printing the changed paths
+    Snapshot {
+        #[command(flatten)]
+        assets: AssetArgs,
+
+        /// Directory of the snapshots (`<stem>.json`)
+        #[arg(
+            long = "snapshots",
+            value_name = "DIR",
+            default_value = "tests/snapshots"
+        )]
+        snapshots: PathBuf,
+
+        /// Write missing snapshots and overwrite those that differ instead of failing, after
+        /// reviewing the changes
+        #[arg(long)]
+        update: bool,
+    },
+    /// Run a guided end-to-end demo: generate test certificates, write a sample manifest, sign
+    /// a bundled image, extract its crJSON and validate it against the schema, printing the
+    /// files each step produced
+    Demo {
+        /// Directory for the demo's files
+        #[arg(short, long, value_name = "DIR", default_value = "crtool-demo")]
+        output: PathBuf,
+    },
+    /// Sign and verify assets as they arrive in a directory (a hot folder), mirroring them into
+    /// an output directory. Processed files are logged so a restart skips them
+    Watch(WatchArgs),
+    /// Serve extraction, validation and signing over HTTP for internal web tooling:
+    /// `POST /extract` (multipart asset → crJSON), `POST /validate` (crJSON → validation
+    /// report), and `POST /sign` when started with --test-case. Needs the `server` feature
+    Serve(ServeArgs),
+    /// Search the manifests of many assets for assertions, or objects inside them, matching an
+    /// expression, e.g. `grep --assertion c2pa.actions --where 'softwareAgent contains
+    /// "Photoshop"' "library/**/*.jpg"`. Prints each matching file with the manifest,
+    /// assertion and JSON path of every match; exits non-zero when nothing matched
+    Grep(GrepArgs),
+    /// Compare two `extract --json` run reports of the same library: files that newly fail or
+    /// pass, trust transitions, and active manifest changes, paired by asset hash. Exits
+    /// non-zero when any file newly fails
+    CompareRuns {
+        /// Report of the earlier run
+        #[arg(value_name = "OLD")]
+        old: PathBuf,
+
+        /// Report of the later run
+        #[arg(value_name = "NEW")]
+        new: PathBuf,
+    },
+    /// Compare two crJSON / indicators documents, e.g. extractions of the same asset by two
+    /// versions: manifests and assertions (paired by label), signer trust, validation failures,
+    /// and every changed value as a JSON pointer. Exits non-zero when they differ
+    Diff {
+        /// The earlier document
+        #[arg(value_name = "A")]
+        a: PathBuf,
+
+        /// The later document
+        #[arg(value_name = "B")]
+        b: PathBuf,
+
+        /// JSON pointer to leave out, with everything beneath it (repeatable), e.g.
+        /// `/jsonGenerator`; `/manifests/*/...` applies to every manifest
+        #[arg(long = "ignore", value_name = "POINTER")]
+        ignore: Vec<String>,
+    },
+    /// Write a copy of an asset with its C2PA data removed: the embedded manifest store and
+    /// the XMP references to an embedded or remote manifest (JPEG and PNG). Useful for negative
+    /// test cases and checking "no manifest" handling
+    Strip {
+        /// Input media asset
+        #[arg(value_name = "INPUT_FILE")]
+        input: PathBuf,
+
+        /// Output file for the stripped asset
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+    },
+    /// Profiles: `profile evaluate --profile profile.json --indicators file.json` (JPEG Trust
+    /// Trust Profiles), `profile asset --profile profile.yml file_cr.json` (YAML asset profiles)
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+    /// Signing certificate helpers: `cert inspect cert.pem`, `cert generate-test --alg es256 -o
+    /// certs/`
+    Cert {
+        #[command(subcommand)]
+        action: CertAction,
+    },
+    /// Report what this build supports (optional features, signing algorithms, asset formats,
+    /// bundled schema versions). Combine with --json for machine-readable output
+    Capabilities,
+    /// Check the signed releases feed for a newer crTool. Combine with --json for
+    /// machine-readable output
+    SelfCheckUpdate,
+    /// Assess each asset against the JPEG Trust Trust Manifest given with --trust-manifest and
+    /// write a `<name>-conformance.json` report per asset. Exits non-zero when an asset does not
+    /// conform
+    Assess {
+        #[command(flatten)]
+        assets: AssetArgs,
+
+        /// Path to a JPEG Trust "Trust Manifest" JSON file of declared expectations
+        #[arg(long = "trust-manifest", value_name = "FILE", required = true)]
+        trust_manifest: PathBuf,
+
+        /// Directory for the reports (default: next to each asset)
+        #[arg(short, long, value_name = "DIR")]
+        output: Option<PathBuf>,
+    },
+    /// Write an SVG badge summarizing each input's credential status (trusted/untrusted,
+    /// issuer, signing date). Combine with --trust to check the signer against the published
+    /// trust lists
+    Badge {
+        #[command(flatten)]
+        assets: AssetArgs,
+
+        /// The badge (a .svg file), or a directory for `<name>_badge.svg` with several inputs
+        #[arg(short, long, value_name = "PATH", required = true)]
+        output: PathBuf,
+    },
+    /// Signing audit logs written with `sign --audit-log`: `audit-log verify signing.jsonl`
+    AuditLog {
+        #[command(subcommand)]
+        action: AuditLogAction,
+    },
+}
+
+impl Command {
+    /// Whether the command reads or signs assets or crJSON, and so needs the scratch directory,
+    /// resource limits, hashing backend and trust presentations set up first.
+    fn needs_setup(&self) -> bool {
+        !matches!(
+            self,
+            Command::CompareRuns { .. }
+                | Command::Diff { .. }
+                | Command::Strip { .. }
+                | Command::Profile {
+                    action: ProfileAction::Evaluate { .. }
+                }
+                | Command::Cert { .. }
+                | Command::Capabilities
+                | Command::SelfCheckUpdate
+                | Command::AuditLog { .. }
+        )
+    }
+
+    /// The trust options of the commands that validate Content Credentials.
+    fn trust_args(&self) -> Option<&TrustArgs> {
+        match self {
+            Command::Extract(args) => Some(&args.assets.trust),
+            Command::Grep(args) => Some(&args.assets.trust),
+            Command::Serve(args) => Some(&args.trust),
+            Command::Info(assets)
+            | Command::Verify { assets, .. }
+            | Command::Fingerprint { assets, .. }
+            | Command::Cbom { assets, .. }
+            | Command::Snapshot { assets, .. }
+            | Command::Assess { assets, .. }
+            | Command::Badge { assets, .. } => Some(&assets.trust),
+            _ => None,
+        }
+    }
+}
+
+/// Subcommands of `crTool profile`.
+#[derive(Subcommand, Debug)]
+pub enum ProfileAction {
+    /// Evaluate a JSON Trust Profile (a tree of all/any/not conditions over indicators)
+    /// against extracted crJSON, and print a trust report with the result of every condition.
+    /// Exits non-zero when an input does not satisfy the profile
+    Evaluate {
+        /// The JSON Trust Profile
+        #[arg(long, value_name = "FILE", required = true)]
+        profile: PathBuf,
+
+        /// Extracted crJSON / indicators file(s)
+        #[arg(long, value_name = "FILE", required = true, num_args = 1..)]
+        indicators: Vec<PathBuf>,
+
+        /// Write each report as `<name>-trust-report.json` to this directory
+        #[arg(short, long, value_name = "DIR")]
+        output: Option<PathBuf>,
+    },
+    /// Evaluate crJSON files against a YAML asset profile and write each report next to its
+    /// input as `<stem>-report.<ext>`
+    Asset {
+        /// crJSON file(s); glob patterns are supported
+        #[arg(value_name = "INPUT_FILE", required = true, num_args = 1..)]
+        input: Vec<String>,
+
+        /// The YAML asset profile
+        #[arg(long, value_name = "FILE", required = true)]
+        profile: PathBuf,
+
+        /// Output format for the report (json or yaml)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Json)]
+        report_format: ReportFormat,
+    },
+}
+
+/// Subcommands of `crTool audit-log`.
+#[derive(Subcommand, Debug)]
+pub enum AuditLogAction {
+    /// Check the hash chain of an audit log; exits non-zero if any entry was edited, removed
+    /// or reordered. Combine with --json for machine-readable output
+    Verify {
+        /// Audit log (JSON lines)
+        #[arg(value_name = "FILE")]
+        log: PathBuf,
+
+        /// Ed25519 public key (PEM) of the --audit-log-key the log was signed with
+        #[arg(
+            long = "public-key",
+            value_name = "PEM",
+            env = "CRTOOL_AUDIT_LOG_PUBLIC_KEY",
+            required = true
+        )]
+        public_key: PathBuf,
+    },
+}
+
+/// Subcommands of `crTool cert`.
+#[derive(Subcommand, Debug)]
+#[cfg_attr(not(feature = "signing"), allow(dead_code))]
+pub enum CertAction {
+    /// Show a signing certificate (the first certificate of a PEM file or chain): subject,
+    /// issuer, validity, key usage and EKUs, and the signing algorithm for its key, and check
+    /// it against the C2PA certificate profile. Exits non-zero when a check fails
+    Inspect {
+        /// PEM certificate or certificate chain
+        #[arg(value_name = "CERT_FILE")]
+        cert: PathBuf,
+    },
+    /// Generate a test CA (`test-ca.pem`) and a signing certificate chain and key issued by it
+    /// (`test-signer.pem`, `test-signer.key`) for producing test assets. Not for production
+    GenerateTest {
+        /// Key algorithm: es256, es384 or ed25519
+        #[arg(long, value_name = "ALG", default_value = "es256")]
+        alg: String,
+
+        /// Directory for the certificate and key files
+        #[arg(short, long, value_name = "DIR")]
+        output: PathBuf,
+
+        /// Organization named in the certificates' subjects
+        #[arg(long, value_name = "NAME", default_value = "crTool Test")]
+        organization: String,
+    },
+}
+
+/// Options of `crTool sign`.
+#[derive(Args, Debug, Clone)]
+#[cfg_attr(not(feature = "signing"), allow(dead_code))]
+pub struct SignArgs {
+    /// Path or glob pattern for test case JSON file(s), e.g. "test-cases/positive/tc-*.json"
+    #[arg(value_name = "TEST_CASE")]
+    test_case: String,
+
+    /// Asset(s) to sign instead of each test case's own asset (glob patterns supported)
+    #[arg(value_name = "INPUT_FILE", num_args = 0..)]
+    input: Vec<String>,
+
+    /// Output file, or directory when signing several assets
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        required_unless_present = "batch_csv"
+    )]
+    output: Option<PathBuf>,
+
+    /// Sign one asset per row of a CSV file with the columns `input`, `output`, `title`,
+    /// `author`, `preset`, using a single test case as the template. The template's manifest is
+    /// filled in per row; paths are relative to the CSV file (outputs to --output when given)
+    #[arg(long = "batch-csv", value_name = "FILE")]
+    batch_csv: Option<PathBuf>,
+
+    /// Sign this many assets at the same time, each on its own worker thread, with a progress
+    /// bar on stderr; the summary lists how long each asset took
+    #[arg(short = 'j', long, value_name = "N", default_value = "1")]
+    jobs: usize,
+
+    /// Reproduce each input's directory structure below SRCROOT inside --output, instead of
+    /// writing every output into the top level of --output. Useful when inputs live on
+    /// read-only media and names repeat across subdirectories
+    #[arg(long = "mirror-tree", value_name = "SRCROOT")]
+    mirror_tree: Option<PathBuf>,
+
+    #[command(flatten)]
+    inputs: InputArgs,
+
+    #[command(flatten)]
+    signer: SignerArgs,
+}
+
+/// Signing options of `crTool sign`, `watch` and `serve`.
+#[derive(Args, Debug, Clone, Default)]
+#[cfg_attr(not(feature = "signing"), allow(dead_code))]
+pub struct SignerArgs {
+    /// Shell command run on a working copy of each asset before it is hashed and signed
+    /// (e.g. to embed an invisible watermark). The copy's path is in `CRTOOL_ASSET_PATH`; the
+    /// hook may print `{"softBinding": {...}}` JSON to add a c2pa.soft-binding assertion.
+    #[arg(long = "pre-sign-hook", value_name = "CMD")]
+    pub pre_sign_hook: Option<String>,
+
+    /// Shell command run once per successfully signed output (e.g. to upload it). Receives
+    /// `CRTOOL_OUTPUT_PATH`, `CRTOOL_MANIFEST_LABEL`, and `CRTOOL_SHA256`. Hook failures are
+    /// reported separately from signing failures.
+    #[arg(long = "post-sign-hook", value_name = "CMD")]
+    pub post_sign_hook: Option<String>,
+
+    /// Append a record of every signing (operator, time, input/output SHA-256, algorithm,
+    /// certificate fingerprint) to this hash-chained JSONL audit log. Needs --audit-log-key
+    #[arg(long = "audit-log", value_name = "FILE")]
+    pub audit_log: Option<PathBuf>,
+
+    /// Ed25519 private key (PKCS#8 PEM) that signs each --audit-log entry
+    #[arg(
+        long = "audit-log-key",
+        value_name = "PEM",
+        env = "CRTOOL_AUDIT_LOG_KEY"
+    )]
+    pub audit_log_key: Option<PathBuf>,
+
+    /// Normalize titles in the manifest (including ingredient titles taken
+    /// from file names) to Unicode NFC, so names typed on different platforms match
+    #[arg(long = "normalize-unicode", default_value = "false")]
+    pub normalize_unicode: bool,
+
+    /// Redact an assertion of the parent ingredient's manifest (repeatable or comma-separated):
+    /// an assertion label such as `stds.exif`, resolved against the active manifest of the
+    /// `parentOf` file ingredient, or a full `self#jumbf=` URI. Added to the manifest's
+    /// `redactions`; the manifest should also record a c2pa.redacted action
+    #[arg(long = "redact", value_name = "ASSERTION", value_delimiter = ',')]
+    pub redact: Vec<String>,
+
+    /// Add an ingredient downloaded from an `https://` or `s3://` URL (repeatable), optionally
+    /// prefixed by its relationship: `parentOf=s3://bucket/parent.jpg` (default componentOf).
+    /// Downloads are cached in `CRTOOL_INGREDIENT_CACHE` (default: the temp directory)
+    #[arg(long = "ingredient-url", value_name = "[REL=]URL")]
+    pub ingredient_urls: Vec<String>,
+
+    /// Add a c2pa.metadata assertion generated from each input's EXIF, XMP and IPTC metadata
+    /// (camera, lens, exposure, title, creator, rights, keywords; never GPS). Properties the
+    /// manifest already has in a c2pa.metadata assertion are kept
+    #[arg(long = "metadata-from-asset", default_value = "false")]
+    pub metadata_from_asset: bool,
+
+    /// Private key to sign with instead of each test case's `signing_key`: a PEM file, or a
+    /// PKCS#11 URI of a key on an HSM, smart card or YubiKey, e.g.
+    /// `pkcs11:token=YubiKey%20PIV;object=c2pa?module-path=/usr/lib/libykcs11.so`
+    /// (needs the `pkcs11` feature)
+    #[arg(long, value_name = "KEY")]
+    pub key: Option<PathBuf>,
+
+    /// Sign with the private key of this label in the macOS keychain instead of a key file
+    /// (needs the `keychain` feature)
+    #[arg(long, value_name = "NAME", conflicts_with = "key")]
+    pub keychain: Option<String>,
+
+    /// Sign through a remote signing service (e.g. a KMS or enterprise signing service) instead
+    /// of a key file: the data to sign is POSTed to this URL, which returns the signature
+    #[arg(long = "signer-url", value_name = "URL", conflicts_with_all = ["key", "keychain"])]
+    pub signer_url: Option<String>,
+
+    /// Header sent with each request to --signer-url, e.g. `Authorization: Bearer $TOKEN`
+    /// (repeatable)
+    #[arg(
+        long = "signer-header",
+        value_name = "NAME: VALUE",
+        requires = "signer_url"
+    )]
+    pub signer_header: Vec<String>,
+
+    /// After signing, print a condensed provenance tree of each signed output:
+    /// manifest title, actions, and ingredients with their relationships
+    #[arg(long = "show-tree", default_value = "false")]
+    pub show_tree: bool,
+}
+
+/// Options of `crTool watch`.
+#[derive(Args, Debug, Clone)]
+#[cfg_attr(not(feature = "signing"), allow(dead_code))]
+pub struct WatchArgs {
+    /// Directory to watch for new assets, including its subdirectories
+    #[arg(long = "input-dir", value_name = "DIR")]
+    pub input_dir: PathBuf,
+
+    /// Directory the signed assets are written to, mirroring the input directory's layout
+    #[arg(long = "output-dir", value_name = "DIR")]
+    pub output_dir: PathBuf,
+
+    /// Test case JSON whose manifest and credentials every asset is signed with
+    #[arg(long = "test-case", visible_alias = "manifest", value_name = "FILE")]
+    pub test_case: PathBuf,
+
+    /// Sign a file once it has not changed for this many milliseconds
+    #[arg(long = "debounce-ms", value_name = "MS", default_value = "2000")]
+    pub debounce_ms: u64,
+
+    /// JSON-lines log of processed files (default: `.crtool-watch.jsonl` in the output
+    /// directory); files it records as signed with the same content are skipped
+    #[arg(long = "watch-log", value_name = "FILE")]
+    pub watch_log: Option<PathBuf>,
+
+    /// Sign the assets already in the input directory and exit instead of watching
+    #[arg(long, default_value = "false")]
+    pub once: bool,
+
+    #[command(flatten)]
+    pub signer: SignerArgs,
+}
+
+/// Largest `serve --max-upload-mb`: 64 GiB, so the limit in bytes cannot overflow.
+const MAX_UPLOAD_MB: u64 = 64 * 1024;
+
+/// Options of `crTool serve`.
+#[derive(Args, Debug, Clone)]
+#[cfg_attr(not(feature = "server"), allow(dead_code))]
+pub struct ServeArgs {
+    /// Port to listen on
+    #[arg(
+        long,
+        value_name = "PORT",
+        default_value = "8080",
+        env = "CRTOOL_SERVE_PORT"
+    )]
+    pub port: u16,
+
+    /// Address to listen on; use 0.0.0.0 to accept connections from other hosts
+    #[arg(
+        long,
+        value_name = "ADDR",
+        default_value = "127.0.0.1",
+        env = "CRTOOL_SERVE_BIND"
+    )]
+    pub bind: String,
+
+    /// Test case JSON whose manifest and credentials `POST /sign` signs with; without it the
+    /// endpoint is disabled
+    #[arg(
+        long = "test-case",
+        visible_alias = "manifest",
+        value_name = "FILE",
+        env = "CRTOOL_SERVE_TEST_CASE"
+    )]
+    pub test_case: Option<PathBuf>,
+
+    /// Largest request body accepted, in MiB (at most 65536)
+    #[arg(
+        long = "max-upload-mb",
+        value_name = "MIB",
+        default_value = "512",
+        env = "CRTOOL_SERVE_MAX_UPLOAD_MB",
+        value_parser = clap::value_parser!(u64).range(1..=MAX_UPLOAD_MB)
+    )]
+    pub max_upload_mb: u64,
+
+    /// Number of requests handled at the same time
+    #[arg(
+        long,
+        value_name = "N",
+        default_value = "4",
+        env = "CRTOOL_SERVE_WORKERS"
+    )]
+    pub workers: usize,
+
+    /// Seconds a request may take from arriving to being answered: a body that is still
+    /// uploading gets `408`, an extraction or signing that is still running `503`
+    #[arg(
+        long = "request-timeout",
+        value_name = "SECS",
+        default_value = "60",
+        env = "CRTOOL_SERVE_REQUEST_TIMEOUT",
+        value_parser = clap::value_parser!(u64).range(1..=86400)
+    )]
+    pub request_timeout: u64,
+
+    /// File of API keys, one `name:key` per line (`#` starts a comment); every endpoint but
+    /// `/health` and `/readyz` then needs one, sent as `Authorization: Bearer <key>` or
+    /// `X-API-Key: <key>`. Keys can also be given in $CRTOOL_SERVE_API_KEYS, comma-separated
+    #[arg(
+        long = "api-keys",
+        value_name = "FILE",
+        env = "CRTOOL_SERVE_API_KEYS_FILE"
+    )]
+    pub api_keys: Option<PathBuf>,
+
+    /// Requests a minute allowed per API key (per client address without keys); over it,
+    /// requests get `429` with `Retry-After`
+    #[arg(
+        long = "rate-limit",
+        value_name = "PER_MINUTE",
+        env = "CRTOOL_SERVE_RATE_LIMIT",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub rate_limit: Option<u32>,
+
+    /// Seconds `/readyz` fails after SIGTERM before the server stops taking requests, so load
+    /// balancers can route traffic elsewhere first
+    #[arg(
+        long = "drain-delay",
+        value_name = "SECS",
+        default_value = "0",
+        env = "CRTOOL_SERVE_DRAIN_DELAY"
+    )]
+    pub drain_delay: u64,
+
+    #[command(flatten)]
+    pub trust: TrustArgs,
+
+    #[command(flatten)]
+    pub signer: SignerArgs,
+}
+
+/// Options of `crTool grep`.
+#[derive(Args, Debug, Clone)]
+pub struct GrepArgs {
+    #[command(flatten)]
+    assets: AssetArgs,
+
+    /// Only search assertions with this label, any version or instance (`c2pa.actions` also
+    /// matches `c2pa.actions.v2__1`)
+    #[arg(long, value_name = "LABEL")]
+    pub assertion: Option<String>,
+
+    /// Report the objects inside the searched assertions that match this expression over
+    /// their keys, e.g. `softwareAgent contains "Photoshop" && action == "c2pa.edited"`.
+    /// Dotted names address nested keys (`softwareAgent.name`)
+    #[arg(long = "where", value_name = "EXPR")]
+    pub where_expr: Option<String>,
+
+    /// Directory for cached extractions (default: `crtool-grep-cache` in the temp directory)
+    #[arg(long = "cache-dir", value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Extract every file again instead of using or updating the cache
+    #[arg(long = "no-cache", default_value = "false")]
+    pub no_cache: bool,
+}
+
+/// Options of `crTool extract`.
+#[derive(Args, Debug, Clone)]
+pub struct ExtractArgs {
+    #[command(flatten)]
+    assets: AssetArgs,
+
+    /// Output file, or directory when extracting from several assets
+    #[arg(short, long, value_name = "PATH", required = true)]
+    output: PathBuf,
+
+    /// Reproduce each input's directory structure below SRCROOT inside --output, instead of
+    /// writing every output into the top level of --output. Useful when inputs live on
+    /// read-only media and names repeat across subdirectories
+    #[arg(long = "mirror-tree", value_name = "SRCROOT")]
+    mirror_tree: Option<PathBuf>,
+
+    /// Also write a forensic evidence report (`<name>_evidence.json`) containing
+    /// file hashes, certificate chains (PEM), timestamp tokens, raw signature bytes (base64),
+    /// and validation results
+    #[arg(long = "evidence-report", default_value = "false")]
+    pub evidence_report: bool,
+
+    /// Decode each claim signature's COSE_Sign1 structure (algorithm header,
+    /// header labels, x5chain and timestamp countersignature presence) into a top-level
+    /// `coseDetails` section of the output, keyed by manifest label
+    #[arg(long = "include-cose-details", default_value = "false")]
+    pub include_cose_details: bool,
+
+    /// Verify claim signatures and certificate chains but skip recomputing
+    /// content hashes. Much faster on large corpora; outputs are marked with a
+    /// `partialValidation` object and say nothing about whether the content was modified
+    #[arg(long, default_value = "false")]
+    pub fast: bool,
+
+    /// Also write the provenance graph of each manifest store as
+    /// `<name>_provenance.<ext>`. Comma-separated: dot, graphml, jsonld (JSON-LD mapped to
+    /// W3C PROV-O and schema.org)
+    #[arg(long = "graph", value_name = "FORMAT", value_delimiter = ',')]
+    pub graph: Vec<GraphFormat>,
+
+    /// Print SHA-256 checksums of every written output in the summary and write
+    /// them to a `SHA256SUMS` file in the output directory (checkable with `sha256sum -c`)
+    #[arg(long, default_value = "false")]
+    pub checksums: bool,
+
+    /// Digest algorithm for the asset hash in the crJSON `asset_info`: sha256, sha384, or
+    /// sha512
+    #[arg(long = "hash-alg", value_name = "ALG", default_value = "sha256")]
+    hash_alg: crtool::hashing::HashAlgorithm,
+
+    /// Only write outputs for files matching this expression over per-file
+    /// facts, e.g. `--where 'trust == "untrusted" && chain_depth > 2'`. Fields: path, format,
+    /// kind, active_label, manifest_count, chain_depth, ingredient_count, trust, valid,
+    /// failure_count, has_timestamp, issuer, algorithm
+    #[arg(long = "where", value_name = "EXPR")]
+    pub where_expr: Option<String>,
+
+    /// Fail the run unless the active manifest of every file has these
+    /// assertions (any instance; comma-separated or repeated, e.g. `c2pa.actions.v2`)
+    #[arg(long = "expect-assertion", value_name = "LABEL", value_delimiter = ',')]
+    pub expect_assertion: Vec<String>,
+
+    /// Fail the run unless every file's signing credential is trusted
+    /// (combine with --trust to check against the published trust lists)
+    #[arg(long = "expect-trusted", default_value = "false")]
+    pub expect_trusted: bool,
+
+    /// Fail the run unless the active manifest of every file has exactly N
+    /// ingredients
+    #[arg(long = "expect-ingredients", value_name = "N")]
+    pub expect_ingredients: Option<usize>,
+
+    /// Check the active manifest's c2pa.soft-binding assertions with this program, run as
+    /// `PROGRAM <alg> <asset>` and printing the watermark or fingerprint value it computes
+    /// (nothing for an unsupported algorithm). Fails the run when a value does not match
+    #[arg(long = "soft-binding-plugin", value_name = "PROGRAM")]
+    pub soft_binding_plugin: Option<PathBuf>,
+
+    /// Evaluate each extracted crJSON against this YAML asset profile and write
+    /// `<stem>-report.<ext>` next to it
     #[arg(long, value_name = "FILE")]
     profile: Option<PathBuf>,
 
@@ -111,21 +1118,237 @@ pub struct Cli {
     #[arg(long, value_enum, default_value_t = ReportFormat::Json)]
     report_format: ReportFormat,
 
-    /// Path to a batch JSON file — runs multiple commands in sequence
-    #[arg(short = 'b', long = "batch", value_name = "FILE")]
-    batch: Option<PathBuf>,
+    /// Assess each extracted crJSON against this JPEG Trust "Trust Manifest" and write
+    /// `<name>-conformance.json` next to it
+    #[arg(long = "trust-manifest", value_name = "FILE")]
+    trust_manifest: Option<PathBuf>,
+}
 
-    /// Suppress progress output (errors are still shown on stderr)
-    #[arg(short = 'q', long = "quiet", default_value = "false")]
-    quiet: bool,
+/// Options of `crTool validate`.
+#[derive(Args, Debug, Clone)]
+pub struct ValidateArgs {
+    /// JSON file(s); glob patterns are supported
+    #[arg(value_name = "INPUT_FILE", required = true, num_args = 1..)]
+    input: Vec<String>,
 
-    /// Write all progress output to a log file (in addition to stdout)
-    #[arg(short = 'l', long = "log", value_name = "FILE")]
-    log: Option<PathBuf>,
+    /// Accept directories as inputs and validate every `**/*.json` beneath them
+    #[arg(short = 'r', long)]
+    recursive: bool,
+
+    /// Skip JSON files whose `@context` is not a crJSON/indicators context
+    /// instead of counting them as failures
+    #[arg(long = "skip-non-indicators", default_value = "false")]
+    pub skip_non_indicators: bool,
+
+    /// Fail validation on warnings (e.g. missing recommended fields) as well as errors
+    #[arg(long = "warnings-as-errors", default_value = "false")]
+    pub warnings_as_errors: bool,
+
+    /// Validate against this schema instead of the bundled crJSON schema: a schema file path, or
+    /// `bundled`. Repeat to apply several schemas in sequence (e.g. `--schema bundled --schema
+    /// org-extension.json`); a file must pass all of them
+    #[arg(
+        long = "schema",
+        value_name = "SCHEMA",
+        conflicts_with = "compare_schemas"
+    )]
+    pub schemas: Vec<String>,
+
+    /// Drop findings of these rules: a failed schema keyword (e.g. `additionalProperties`)
+    /// or `recommended-field`. Comma-separated or repeated
+    #[arg(long = "suppress-rule", value_name = "RULE", value_delimiter = ',')]
+    pub suppress_rules: Vec<String>,
+
+    /// Report findings of RULE at LEVEL (error, warning or info), e.g.
+    /// `--severity required=warning`. Comma-separated or repeated
+    #[arg(long = "severity", value_name = "RULE=LEVEL", value_delimiter = ',')]
+    pub severity: Vec<RuleSeverity>,
+
+    /// Validate against two schema versions instead of the bundled crJSON
+    /// schema and report documents that pass one but not the other. Each is a schema file path
+    /// or `bundled` for the crJSON schema shipped with crTool
+    #[arg(
+        long = "compare-schemas",
+        value_name = "SCHEMA_A,SCHEMA_B",
+        value_delimiter = ',',
+        num_args = 2
+    )]
+    pub compare_schemas: Vec<String>,
+}
+
+/// A deprecated mode flag, rewritten by [`Cli::try_parse_args`] into the subcommand it stands
+/// for.
+#[derive(Debug, Clone, Copy)]
+pub struct LegacyAlias {
+    /// The flag as given, e.g. `--extract`.
+    flag: &'static str,
+    /// The subcommand it ran, e.g. `extract`.
+    subcommand: &'static str,
+}
+
+impl LegacyAlias {
+    pub fn warn(&self, logger: &mut Logger) {
+        logger.info(&format!(
+            "⚠️  {} is deprecated and will be removed in the next release; use `crTool {}` \
+            instead",
+            self.flag, self.subcommand
+        ));
+    }
+}
+
+/// The deprecated mode flags (short and long), the subcommand words each becomes, and whether
+/// its value is the subcommand's first argument. Checked in order, so the first one given wins
+/// as it did when they were flags.
+const LEGACY_MODE_FLAGS: &[(Option<&str>, &str, &[&str], bool)] = &[
+    (None, "--capabilities", &["capabilities"], false),
+    (None, "--verify-audit-log", &["audit-log", "verify"], true),
+    (None, "--self-check-update", &["self-check-update"], false),
+    (Some("-t"), "--create-test", &["sign"], true),
+    (Some("-v"), "--validate", &["validate"], false),
+    (None, "--verify", &["verify"], false),
+    (None, "--badge", &["badge"], false),
+    (Some("-e"), "--extract", &["extract"], false),
+];
+
+/// Rewrite a command line that uses a deprecated mode flag instead of a subcommand, e.g.
+/// `crTool --extract a.jpg -o out` into `crTool extract a.jpg -o out`. Without a mode flag,
+/// `--trust-manifest` alone stands for `assess` and `--profile` alone for `profile asset`.
+/// Command lines naming a subcommand are left alone.
+fn rewrite_legacy_args(argv: Vec<OsString>) -> (Vec<OsString>, Option<LegacyAlias>) {
+    let subcommands: Vec<String> = Cli::command()
+        .get_subcommands()
+        .flat_map(|sub| {
+            std::iter::once(sub.get_name().to_string())
+                .chain(sub.get_all_aliases().map(str::to_string))
+        })
+        .chain(["help".to_string()])
+        .collect();
+    let end = argv
+        .iter()
+        .position(|arg| arg == "--")
+        .unwrap_or(argv.len());
+    let words: Vec<&str> = argv[..end]
+        .iter()
+        .map(|arg| arg.to_str().unwrap_or_default())
+        .collect();
+    if words
+        .iter()
+        .skip(1)
+        .any(|word| subcommands.iter().any(|sub| sub == word))
+    {
+        return (argv, None);
+    }
+
+    let position = |long: &str, short: Option<&str>| {
+        words.iter().position(|word| {
+            *word == long
+                || Some(*word) == short
+                || word
+                    .strip_prefix(long)
+                    .is_some_and(|rest| rest.starts_with('='))
+        })
+    };
+    let rewrite = |index: usize, takes_value: bool, subcommand: &[&str]| {
+        let mut rest = argv.clone();
+        let flag = rest.remove(index);
+        let mut head: Vec<OsString> = vec![rest.remove(0)];
+        head.extend(subcommand.iter().map(OsString::from));
+        if takes_value {
+            match flag.to_str().and_then(|flag| flag.split_once('=')) {
+                Some((_, value)) => head.push(value.into()),
+                None if index <= rest.len() && index > 0 => head.push(rest.remove(index - 1)),
+                None => {}
+            }
+        }
+        head.extend(rest);
+        head
+    };
+
+    for (short, long, subcommand, takes_value) in LEGACY_MODE_FLAGS {
+        if let Some(index) = position(long, *short) {
+            let alias = LegacyAlias {
+                flag: *long,
+                subcommand: match *long {
+                    "--verify-audit-log" => "audit-log verify",
+                    _ => subcommand[0],
+                },
+            };
+            return (rewrite(index, *takes_value, subcommand), Some(alias));
+        }
+    }
+
+    let inferred = [
+        ("--trust-manifest", &["assess"][..], "assess"),
+        ("--profile", &["profile", "asset"][..], "profile asset"),
+    ];
+    for (option, subcommand, name) in inferred {
+        if position(option, None).is_some() {
+            let mut rewritten = vec![argv[0].clone()];
+            rewritten.extend(subcommand.iter().map(OsString::from));
+            rewritten.extend(argv[1..].iter().cloned());
+            let alias = LegacyAlias {
+                flag: match option {
+                    "--trust-manifest" => "--trust-manifest without a subcommand",
+                    _ => "--profile without a subcommand",
+                },
+                subcommand: name,
+            };
+            return (rewritten, Some(alias));
+        }
+    }
+    (argv, None)
+}
+
+impl Cli {
+    /// Parse `argv` (including the binary name), like [`Parser::try_parse_from`], after
+    /// rewriting a deprecated mode flag into its subcommand. Returns the flag that was
+    /// rewritten, so the caller can warn about it.
+    pub fn try_parse_args<I, T>(argv: I) -> Result<(Self, Option<LegacyAlias>), clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let (argv, alias) = rewrite_legacy_args(argv.into_iter().map(Into::into).collect());
+        Ok((Self::try_parse_from(argv)?, alias))
+    }
 }
 
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
+/// Select the hashing backend from --hash-backend and log what was detected.
+fn apply_hash_backend(choice: &str, logger: &mut Logger) -> Result<()> {
+    use crtool::hashing::{auto_backend, detect_acceleration, set_hash_backend, HashBackend};
+    let backend = if choice.eq_ignore_ascii_case("auto") {
+        auto_backend()
+    } else {
+        choice.parse::<HashBackend>()?
+    };
+    set_hash_backend(backend)?;
+    logger.info(&format!(
+        "#️⃣ Hashing: {backend} backend ({})",
+        detect_acceleration()
+    ));
+    Ok(())
+}
+
+/// Resource limits from the --max-* options, with the library defaults for the rest.
+fn extraction_limits(global: &GlobalArgs) -> crtool::limits::ExtractionLimits {
+    let defaults = crtool::limits::ExtractionLimits::DEFAULT;
+    crtool::limits::ExtractionLimits {
+        max_manifest_store_bytes: global
+            .max_manifest_store_mb
+            .map_or(defaults.max_manifest_store_bytes, |mb| mb * 1024 * 1024),
+        max_json_depth: global.max_json_depth.unwrap_or(defaults.max_json_depth),
+        max_string_len: global
+            .max_json_string_mb
+            .map_or(defaults.max_string_len, |mb| mb * 1024 * 1024),
+        max_array_len: global.max_json_array.unwrap_or(defaults.max_array_len),
+        max_download_bytes: global
+            .max_download_mb
+            .map_or(defaults.max_download_bytes, |mb| mb * 1024 * 1024),
+    }
+}
+
 /// Expand glob patterns and collect matching file paths.
 pub fn expand_input_patterns(patterns: &[String]) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
@@ -155,239 +1378,1345 @@ pub fn expand_input_patterns(patterns: &[String]) -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-// ─── Core execution ───────────────────────────────────────────────────────────
+/// Fail on the first input that does not exist.
+fn check_inputs_exist(files: &[PathBuf]) -> Result<()> {
+    if let Some(missing) = files.iter().find(|file| !file.exists()) {
+        anyhow::bail!("Input file does not exist: {:?}", missing);
+    }
+    Ok(())
+}
 
-/// Execute a parsed CLI command. Called from both normal mode and batch mode.
-pub fn run_cli(cli: Cli, logger: &mut Logger) -> Result<()> {
-    // Handle --create-test mode before anything else (no positional input required)
-    if let Some(test_case_pattern) = &cli.create_test {
-        let output = cli
-            .output
-            .context("--output is required when using --create-test mode")?;
+/// `--compare-schemas`: validate against both schema versions and fail when any document passes
+/// only one of them.
+fn compare_schemas(input_files: &[PathBuf], specs: &[String], json: bool) -> Result<()> {
+    let resolve = |spec: &str| -> Result<PathBuf> {
+        let path = if spec.eq_ignore_ascii_case("bundled") {
+            crtool::crjson_schema_path()
+        } else {
+            PathBuf::from(spec)
+        };
+        if !path.exists() {
+            anyhow::bail!("Schema file not found at: {:?}", path);
+        }
+        Ok(path)
+    };
+    let [first, second] = specs else {
+        anyhow::bail!("--compare-schemas takes exactly two schemas, e.g. bundled,draft.json");
+    };
+    let report = compare_schema_versions(
+        input_files,
+        [
+            (first.clone(), resolve(first)?),
+            (second.clone(), resolve(second)?),
+        ],
+    )?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).context("Failed to format comparison report")?
+        );
+    } else {
+        print_schema_comparison(&report);
+    }
+    if !report.divergent.is_empty() {
+        anyhow::bail!(
+            "{} file(s) pass one schema but not the other",
+            report.divergent.len()
+        );
+    }
+    if !report.unreadable.is_empty() {
+        anyhow::bail!("{} file(s) could not be read", report.unreadable.len());
+    }
+    Ok(())
+}
 
-        // Expand the pattern (or exact path) to a list of test case files
-        let test_case_files = expand_input_patterns(&[test_case_pattern.clone()])
-            .context("Failed to expand --create-test pattern")?;
+/// Replace each directory in `paths` with the `**/*.json` files beneath it.
+pub fn expand_json_directories(paths: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path);
+            continue;
+        }
+        let pattern = path.join("**").join("*.json");
+        let pattern = pattern.to_string_lossy();
+        let found: Vec<PathBuf> = glob(&pattern)
+            .context(format!("Invalid glob pattern: {}", pattern))?
+            .filter_map(|entry| entry.ok())
+            .filter(|p| p.is_file())
+            .collect();
+        if found.is_empty() {
+            anyhow::bail!("No JSON files found under directory: {:?}", path);
+        }
+        files.extend(found);
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
 
-        // Fast path: single test case, no input override — original behavior
-        if test_case_files.len() == 1 && cli.input.is_empty() {
-            return handle_create_test(&test_case_files[0], None, &output);
+/// Replace each directory in `paths` with the supported assets beneath it, keeping only the
+/// given kinds (all kinds when `kinds` is empty).
+pub fn expand_asset_directories(paths: Vec<PathBuf>, kinds: &[MediaKind]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path);
+            continue;
+        }
+        let found = crtool::walk_assets(&path, |_, format| {
+            kinds.is_empty() || kinds.contains(&format.kind)
+        })?;
+        if found.is_empty() {
+            anyhow::bail!("No supported assets found under directory: {:?}", path);
         }
+        files.extend(found);
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
 
-        let input_files = if cli.input.is_empty() {
-            vec![]
-        } else {
-            expand_input_patterns(&cli.input).context("Failed to expand input file patterns")?
+/// Keep only files whose format belongs to one of `kinds` (all files when `kinds` is empty).
+pub fn filter_by_kind(files: Vec<PathBuf>, kinds: &[MediaKind]) -> Vec<PathBuf> {
+    if kinds.is_empty() {
+        return files;
+    }
+    files
+        .into_iter()
+        .filter(|p| crtool::asset_format_for_path(p).is_some_and(|f| kinds.contains(&f.kind)))
+        .collect()
+}
+
+/// Outcome of [`dedupe_linked_inputs`].
+#[derive(Debug, Default)]
+pub struct LinkedInputs {
+    /// Files to process, in input order.
+    pub files: Vec<PathBuf>,
+    /// Inputs skipped because they are (or go through) a symlink and links are not followed.
+    pub skipped_symlinks: Vec<PathBuf>,
+    /// Inputs skipped as the same file as an earlier input: (skipped, kept).
+    pub duplicates: Vec<(PathBuf, PathBuf)>,
+}
+
+/// True if `path` or any of its parent directories is a symbolic link.
+fn is_via_symlink(path: &std::path::Path) -> bool {
+    path.ancestors()
+        .filter(|p| !p.as_os_str().is_empty())
+        .any(|p| {
+            p.symlink_metadata()
+                .is_ok_and(|m| m.file_type().is_symlink())
+        })
+}
+
+/// Identity of the file behind `path`: device and inode on Unix, canonical path elsewhere.
+#[cfg(unix)]
+fn file_identity(path: &std::path::Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path).ok().map(|m| (m.dev(), m.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(path: &std::path::Path) -> Option<PathBuf> {
+    std::fs::canonicalize(path).ok()
+}
+
+/// Drop inputs that resolve to a file already in the list (symlinks, hardlinks, or different
+/// spellings of the same path), and, when `follow_symlinks` is false, inputs reached through a
+/// symlink. The first occurrence of each file is kept.
+pub fn dedupe_linked_inputs(files: Vec<PathBuf>, follow_symlinks: bool) -> LinkedInputs {
+    let mut seen = std::collections::HashMap::new();
+    let mut result = LinkedInputs::default();
+    for file in files {
+        if !follow_symlinks && is_via_symlink(&file) {
+            result.skipped_symlinks.push(file);
+            continue;
+        }
+        let Some(id) = file_identity(&file) else {
+            // Missing files are reported by the caller's existence check.
+            result.files.push(file);
+            continue;
         };
+        match seen.get(&id) {
+            Some(kept) => result.duplicates.push((file, PathBuf::clone(kept))),
+            None => {
+                seen.insert(id, file.clone());
+                result.files.push(file);
+            }
+        }
+    }
+    result
+}
 
-        // Output must be a directory whenever multiple test cases or multiple inputs are involved
-        if (test_case_files.len() > 1 || input_files.len() > 1) && !output.is_dir() {
-            anyhow::bail!(
-                "Output must be a directory when creating test assets from multiple test cases or input files. Got: {:?}",
-                output
-            );
+/// Apply [`dedupe_linked_inputs`] and log what was skipped.
+fn dedupe_and_report(
+    files: Vec<PathBuf>,
+    follow_symlinks: bool,
+    logger: &mut Logger,
+) -> Vec<PathBuf> {
+    let linked = dedupe_linked_inputs(files, follow_symlinks);
+    if !linked.skipped_symlinks.is_empty() {
+        logger.info(&format!(
+            "🔗 --no-follow-symlinks: skipped {} symlinked file(s)",
+            linked.skipped_symlinks.len()
+        ));
+        for path in &linked.skipped_symlinks {
+            logger.info(&format!("     ↪ {}", path.display()));
         }
+    }
+    if !linked.duplicates.is_empty() {
+        logger.info(&format!(
+            "🔗 Skipped {} duplicate file(s) reachable through links",
+            linked.duplicates.len()
+        ));
+        for (skipped, kept) in &linked.duplicates {
+            logger.info(&format!(
+                "     ↪ {} (same file as {})",
+                skipped.display(),
+                kept.display()
+            ));
+        }
+    }
+    linked.files
+}
 
-        let mut success_count = 0u32;
-        let mut error_count = 0u32;
+/// Output directory for `input`: `output` itself, or with `--mirror-tree`, the directory under
+/// `output` matching the input's location relative to `src_root` (created if needed).
+pub fn mirrored_output_dir(
+    output: &std::path::Path,
+    src_root: Option<&std::path::Path>,
+    input: &std::path::Path,
+) -> Result<PathBuf> {
+    let Some(src_root) = src_root else {
+        return Ok(output.to_path_buf());
+    };
+    let root = std::fs::canonicalize(src_root)
+        .with_context(|| format!("--mirror-tree root does not exist: {:?}", src_root))?;
+    let input_abs = std::fs::canonicalize(input)
+        .with_context(|| format!("Input file does not exist: {:?}", input))?;
+    let relative = input_abs.strip_prefix(&root).map_err(|_| {
+        anyhow::anyhow!(
+            "Input {:?} is not under the --mirror-tree root {:?}",
+            input,
+            src_root
+        )
+    })?;
+    let dir = match relative.parent() {
+        Some(parent) => output.join(parent),
+        None => output.to_path_buf(),
+    };
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create output directory {:?}", dir))?;
+    Ok(dir)
+}
 
-        for test_case_path in &test_case_files {
-            if input_files.is_empty() {
-                logger.info(&format!(
-                    "  📄 Processing test case: {} ...",
-                    test_case_path.display()
-                ));
-                match handle_create_test(test_case_path, None, &output) {
-                    Ok(_) => {
-                        logger.info("     ✅ Done");
-                        success_count += 1;
-                    }
-                    Err(e) => {
-                        logger.error(&format!("     ❌ Error: {e}"));
-                        error_count += 1;
+/// Startup check of the scratch directory: warn when it is low on space. An explicit
+/// `--temp-dir` that cannot be created or queried is an error; the default is only warned about.
+fn check_temp_space(explicit: bool, logger: &mut Logger) -> Result<()> {
+    let dir = crtool::scratch::scratch_dir();
+    match crtool::scratch::check_scratch_space() {
+        Ok(free) if free < crtool::scratch::LOW_SCRATCH_SPACE => {
+            logger.error(&format!(
+                "⚠️  Temp directory {} has only {} MiB free; large assets may not fit \
+                (use --temp-dir to choose another)",
+                dir.display(),
+                free / (1024 * 1024)
+            ));
+            Ok(())
+        }
+        Ok(_) => Ok(()),
+        Err(e) if explicit => Err(anyhow::Error::from(e).context("Invalid --temp-dir")),
+        Err(e) => {
+            logger.error(&format!("⚠️  {e:#}"));
+            Ok(())
+        }
+    }
+}
+
+/// Read back a signed output and log its provenance tree (for `--show-tree`).
+#[cfg(feature = "signing")]
+fn print_provenance_tree(output_path: &std::path::Path, logger: &mut Logger) {
+    let settings = crtool::default_extraction_settings();
+    match crtool::extract_crjson_manifest_with_settings(output_path, &settings) {
+        Ok(result) => {
+            match crtool::provenance::build_tree(&result.manifest_value, &result.active_label) {
+                Some(tree) => {
+                    for line in tree.render_tree().lines() {
+                        logger.info(&format!("     {line}"));
                     }
-                }
-            } else {
-                for input_file in &input_files {
-                    logger.info(&format!("  📄 Processing: {} ...", input_file.display()));
-                    match handle_create_test(test_case_path, Some(input_file), &output) {
-                        Ok(_) => {
-                            logger.info("     ✅ Done");
-                            success_count += 1;
-                        }
-                        Err(e) => {
-                            logger.error(&format!("     ❌ Error: {e}"));
-                            error_count += 1;
-                        }
+                    for diagnostic in tree.diagnostics() {
+                        logger.info(&format!("     ⚠️  {diagnostic}"));
                     }
                 }
+                None => logger.error(&format!(
+                    "     ⚠️  Active manifest {} not found in {}",
+                    result.active_label,
+                    output_path.display()
+                )),
             }
         }
+        Err(e) => logger.error(&format!(
+            "     ⚠️  Could not read back {}: {e}",
+            output_path.display()
+        )),
+    }
+}
 
-        let total = success_count + error_count;
-        if total > 1 {
-            logger.info(&format!(
-                "\n📊 Test Asset Creation: {success_count} succeeded, {error_count} failed, {total} total"
+/// Run the post-sign hook (if configured) for a signed output. Returns false when the hook
+/// failed; the failure is logged but does not count as a signing failure.
+#[cfg(feature = "signing")]
+fn run_post_sign(
+    settings: &SignSettings,
+    output_path: &std::path::Path,
+    logger: &mut Logger,
+) -> bool {
+    let Some(cmd) = &settings.hooks.post_sign else {
+        return true;
+    };
+    match hooks::run_post_sign_hook(cmd, output_path) {
+        Ok(()) => true,
+        Err(e) => {
+            logger.error(&format!(
+                "     ⚠️  Post-sign hook failed for {}: {e}",
+                output_path.display()
             ));
+            false
+        }
+    }
+}
+
+/// `audit-log verify`: check the log's hash chain and signatures and fail if it is broken.
+fn verify_audit_log(
+    path: &std::path::Path,
+    public_key: &std::path::Path,
+    json: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    let key = crtool::audit_log::load_verifying_key(public_key)?;
+    let result = crtool::audit_log::verify_log(path, &key)?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&result).context("Failed to format verification")?
+        );
+    } else if result.is_intact() {
+        logger.info(&format!(
+            "✅ Audit log intact: {} entry(ies)",
+            result.verified_entries
+        ));
+        if let Some(head) = &result.head {
+            logger.info(&format!("   Head: {head}"));
         }
+    }
+    if let (Some(line), Some(reason)) = (result.broken_at_line, &result.reason) {
+        anyhow::bail!(
+            "Audit log {} is broken at line {line}: {reason} ({} entry(ies) verified before it)",
+            path.display(),
+            result.verified_entries
+        );
+    }
+    Ok(())
+}
+
+// ─── Core execution ───────────────────────────────────────────────────────────
+
+/// `sign TEMPLATE --batch-csv FILE`: sign one asset per CSV row.
+#[cfg(feature = "signing")]
+fn run_batch_csv_mode(
+    args: &SignArgs,
+    csv_path: &std::path::Path,
+    settings: &SignSettings,
+    logger: &mut Logger,
+) -> Result<()> {
+    let templates = expand_input_patterns(&[args.test_case.clone()])
+        .context("Failed to expand the test case pattern")?;
+    let [template] = templates.as_slice() else {
+        anyhow::bail!(
+            "--batch-csv needs exactly one test case template, got {}",
+            templates.len()
+        );
+    };
+
+    let mut signed = 0u32;
+    let mut hook_error_count = 0u32;
+    let failed = batch_csv::run_batch_csv(
+        csv_path,
+        template,
+        args.output.as_deref(),
+        settings,
+        logger,
+        |output_path, logger| {
+            signed += 1;
+            if args.signer.show_tree {
+                print_provenance_tree(output_path, logger);
+            }
+            if !run_post_sign(settings, output_path, logger) {
+                hook_error_count += 1;
+            }
+        },
+    )?;
 
-        if error_count > 0 {
-            anyhow::bail!("{error_count} file(s) failed to create test asset");
+    logger.info(&format!(
+        "\n📊 Batch CSV: {signed} signed, {failed} failed, {} total",
+        signed as usize + failed
+    ));
+    if failed > 0 {
+        anyhow::bail!("{failed} row(s) failed to sign");
+    }
+    if hook_error_count > 0 {
+        anyhow::bail!("{hook_error_count} post-sign hook run(s) failed");
+    }
+    Ok(())
+}
+
+/// Per-run signing settings from the signer options.
+#[cfg(feature = "signing")]
+fn sign_settings(signer: &SignerArgs) -> Result<SignSettings> {
+    let audit_log = match (&signer.audit_log, &signer.audit_log_key) {
+        (Some(log), Some(key)) => Some(
+            crtool::audit_log::AuditLog::open(log, key)
+                .context("Failed to load the audit log key")?,
+        ),
+        (Some(_), None) => {
+            anyhow::bail!(
+                "--audit-log needs --audit-log-key (or CRTOOL_AUDIT_LOG_KEY) to sign its entries"
+            )
         }
+        (None, _) => None,
+    };
+    Ok(SignSettings {
+        hooks: hooks::SignHooks {
+            pre_sign: signer.pre_sign_hook.clone(),
+            post_sign: signer.post_sign_hook.clone(),
+        },
+        audit_log,
+        normalize_unicode: signer.normalize_unicode,
+        redact: signer.redact.clone(),
+        ingredient_urls: signer.ingredient_urls.clone(),
+        metadata_from_asset: signer.metadata_from_asset,
+        key: signer.key.clone(),
+        keychain: signer.keychain.clone(),
+        remote_signer: signer
+            .signer_url
+            .as_ref()
+            .map(|url| crtool::signer::RemoteSigner::from_header_lines(url, &signer.signer_header))
+            .transpose()?,
+        ingredient_cache: Default::default(),
+    })
+}
 
+/// `sign`: sign assets from one or more test case files.
+#[cfg(feature = "signing")]
+fn run_sign(args: &SignArgs, quiet: bool, logger: &mut Logger) -> Result<()> {
+    let settings = sign_settings(&args.signer)?;
+    if let Some(csv_path) = &args.batch_csv {
+        return run_batch_csv_mode(args, csv_path, &settings, logger);
+    }
+    let Some(output) = args.output.as_deref() else {
+        anyhow::bail!("--output is required when signing");
+    };
+
+    // Expand the pattern (or exact path) to a list of test case files
+    let test_case_files = expand_input_patterns(&[args.test_case.clone()])
+        .context("Failed to expand the test case pattern")?;
+
+    // Fast path: single test case, no input override — original behavior
+    if test_case_files.len() == 1 && args.input.is_empty() {
+        let output_path = handle_create_test(&test_case_files[0], None, output, &settings)?;
+        if args.signer.show_tree {
+            print_provenance_tree(&output_path, logger);
+        }
+        if !run_post_sign(&settings, &output_path, logger) {
+            anyhow::bail!("Post-sign hook failed for {}", output_path.display());
+        }
         return Ok(());
     }
 
-    // All other modes require at least one input file
-    if cli.input.is_empty() {
+    let input_files = if args.input.is_empty() {
+        vec![]
+    } else {
+        let inputs = &args.inputs;
+        let mut files =
+            expand_input_patterns(&args.input).context("Failed to expand input file patterns")?;
+        if inputs.recursive {
+            files = expand_asset_directories(files, &inputs.kind)?;
+        }
+        filter_by_kind(
+            dedupe_and_report(files, inputs.follow_symlinks(), logger),
+            &inputs.kind,
+        )
+    };
+
+    // Output must be a directory whenever multiple test cases or multiple inputs are involved
+    if (test_case_files.len() > 1 || input_files.len() > 1 || args.mirror_tree.is_some())
+        && !output.is_dir()
+    {
         anyhow::bail!(
-            "No input files specified. Use --create-test to create a test asset from a test \
-            case JSON file, or provide input file(s) for extract/validate/profile modes."
+            "Output must be a directory when creating test assets from multiple test cases or input files, or with --mirror-tree. Got: {:?}",
+            output
         );
     }
 
-    let extraction_settings =
-        extraction_settings(cli.trust).context("Failed to prepare extraction settings")?;
-
-    let input_files =
-        expand_input_patterns(&cli.input).context("Failed to expand input file patterns")?;
+    // Each test case is signed once per input, or once with its own inputAsset without inputs
+    let tasks: Vec<(&PathBuf, Option<&PathBuf>)> = test_case_files
+        .iter()
+        .flat_map(|test_case_path| {
+            if input_files.is_empty() {
+                vec![(test_case_path, None)]
+            } else {
+                input_files
+                    .iter()
+                    .map(|input_file| (test_case_path, Some(input_file)))
+                    .collect()
+            }
+        })
+        .collect();
+    let task_name = |(test_case_path, input_file): &(&PathBuf, Option<&PathBuf>)| {
+        input_file.unwrap_or(test_case_path).display().to_string()
+    };
+    let sign = |(test_case_path, input_file): &(&PathBuf, Option<&PathBuf>)| match input_file {
+        None => handle_create_test(test_case_path, None, output, &settings),
+        Some(input_file) => mirrored_output_dir(output, args.mirror_tree.as_deref(), input_file)
+            .and_then(|out_dir| {
+                handle_create_test(test_case_path, Some(input_file), &out_dir, &settings)
+            }),
+    };
+
+    let mut success_count = 0u32;
+    let mut error_count = 0u32;
+    let mut hook_error_count = 0u32;
+    let mut timings: Vec<(String, std::time::Duration)> = Vec::new();
+    let mut finish = |name: String,
+                      result: Result<PathBuf>,
+                      elapsed: std::time::Duration,
+                      logger: &mut Logger| {
+        match result {
+            Ok(output_path) => {
+                logger.info(&format!("     ✅ Done in {:.2}s", elapsed.as_secs_f64()));
+                success_count += 1;
+                timings.push((name, elapsed));
+                if args.signer.show_tree {
+                    print_provenance_tree(&output_path, logger);
+                }
+                if !run_post_sign(&settings, &output_path, logger) {
+                    hook_error_count += 1;
+                }
+            }
+            Err(e) => {
+                logger.error(&format!("     ❌ Error: {e}"));
+                error_count += 1;
+            }
+        }
+    };
 
-    if input_files.is_empty() {
-        anyhow::bail!("No input files found matching the specified pattern(s)");
+    let job_count = args.jobs.max(1);
+    if job_count > 1 && tasks.len() > 1 {
+        for test_case_path in &test_case_files {
+            if !test_case::supports_parallel_signing(test_case_path, &settings)? {
+                anyhow::bail!(
+                    "--jobs {job_count} cannot be used with a PKCS#11 or keychain key ({}): the \
+                    token can only be opened from one thread at a time. Use --jobs 1.",
+                    test_case_path.display()
+                );
+            }
+        }
     }
 
-    for input_file in &input_files {
-        if !input_file.exists() {
-            anyhow::bail!("Input file does not exist: {:?}", input_file);
+    let started = std::time::Instant::now();
+    if job_count > 1 && tasks.len() > 1 {
+        logger.info(&format!(
+            "  ⚙️  Signing {} asset(s) with {} parallel jobs",
+            tasks.len(),
+            job_count.min(tasks.len())
+        ));
+        jobs::run_parallel(&tasks, job_count, !quiet, sign, |index, result, elapsed| {
+            let name = task_name(&tasks[index]);
+            logger.info(&format!("  📄 Signed: {name}"));
+            finish(name, result, elapsed, logger);
+        });
+    } else {
+        for task in &tasks {
+            let name = task_name(task);
+            match task.1 {
+                None => logger.info(&format!("  📄 Processing test case: {name} ...")),
+                Some(_) => logger.info(&format!("  📄 Processing: {name} ...")),
+            }
+            let start = std::time::Instant::now();
+            let result = sign(task);
+            finish(name, result, start.elapsed(), logger);
         }
     }
+    let wall_time = started.elapsed();
 
-    let standalone_eval = cli.profile.is_some() && !cli.extract && !cli.validate;
-    if !cli.validate && !standalone_eval {
-        let unsupported: Vec<_> = input_files
-            .iter()
-            .filter(|p| !crtool::is_supported_asset_path(p))
-            .collect();
-        if !unsupported.is_empty() {
-            anyhow::bail!(
-                "Unsupported file format(s). The following file(s) have extensions not supported \
-                by C2PA: {:?}. Supported extensions: {}.",
-                unsupported.iter().map(|p| p.as_path()).collect::<Vec<_>>(),
-                SUPPORTED_ASSET_EXTENSIONS.join(", ")
-            );
+    let total = success_count + error_count;
+    if total > 1 {
+        logger.info(&format!(
+            "\n📊 Test Asset Creation: {success_count} succeeded, {error_count} failed, {total} total"
+        ));
+        if settings.hooks.post_sign.is_some() {
+            logger.info(&format!(
+                "   Post-sign hook: {} succeeded, {hook_error_count} failed",
+                success_count - hook_error_count
+            ));
         }
+        print_timings(&timings, wall_time, job_count, logger);
+        let cache = &settings.ingredient_cache;
+        if cache.hits() > 0 {
+            logger.info(&format!(
+                "   File ingredients: {} loaded, {} reused from the ingredient cache",
+                cache.misses(),
+                cache.hits()
+            ));
+        }
+    }
+
+    if error_count > 0 {
+        anyhow::bail!("{error_count} file(s) failed to create test asset");
     }
+    if hook_error_count > 0 {
+        anyhow::bail!("{hook_error_count} post-sign hook run(s) failed");
+    }
+
+    Ok(())
+}
+
+/// Verify-only builds carry no signing code, so `sign` is rejected.
+#[cfg(not(feature = "signing"))]
+fn run_sign(_args: &SignArgs, _quiet: bool, _logger: &mut Logger) -> Result<()> {
+    anyhow::bail!(
+        "This is a verify-only build of crTool: signing (`sign`) is not available. Use a full \
+        build to create test assets."
+    )
+}
 
+/// Number of the slowest assets listed in the signing summary.
+#[cfg(feature = "signing")]
+const SLOWEST_LISTED: usize = 10;
+
+/// Summary of how long signing took: the run's wall time, the average per asset, and the
+/// slowest assets.
+#[cfg(feature = "signing")]
+fn print_timings(
+    timings: &[(String, std::time::Duration)],
+    wall_time: std::time::Duration,
+    jobs: usize,
+    logger: &mut Logger,
+) {
+    if timings.is_empty() {
+        return;
+    }
+    let total: std::time::Duration = timings.iter().map(|(_, elapsed)| *elapsed).sum();
     logger.info(&format!(
-        "🚀 Processing {} input file(s)",
-        input_files.len()
+        "   Time: {:.2}s wall, {:.2}s per asset on average ({} job(s))",
+        wall_time.as_secs_f64(),
+        total.as_secs_f64() / timings.len() as f64,
+        jobs
     ));
+    let mut slowest: Vec<_> = timings.iter().collect();
+    slowest.sort_by(|a, b| b.1.cmp(&a.1));
+    for (name, elapsed) in slowest.iter().take(SLOWEST_LISTED) {
+        logger.info(&format!("     {:>8.2}s  {name}", elapsed.as_secs_f64()));
+    }
+    if slowest.len() > SLOWEST_LISTED {
+        logger.info(&format!(
+            "     ... and {} faster asset(s)",
+            slowest.len() - SLOWEST_LISTED
+        ));
+    }
+}
 
-    // ── Validate mode ─────────────────────────────────────────────────────────
-    if cli.validate {
-        let schema_path = crtool::crjson_schema_path();
-        return validate_json_files(&input_files, &schema_path, "crJSON");
+#[cfg(feature = "signing")]
+fn run_demo(dir: &std::path::Path, logger: &mut Logger) -> Result<()> {
+    demo::run_demo(dir, logger)
+}
+
+/// Verify-only builds cannot sign, so there is no demo.
+#[cfg(not(feature = "signing"))]
+fn run_demo(_dir: &std::path::Path, _logger: &mut Logger) -> Result<()> {
+    anyhow::bail!(
+        "This is a verify-only build of crTool: the demo signs a sample image and needs a \
+        full build."
+    )
+}
+
+#[cfg(feature = "signing")]
+fn run_cert(action: &CertAction, json: bool, logger: &mut Logger) -> Result<()> {
+    match action {
+        CertAction::Inspect { cert } => cert::inspect(cert, json, logger),
+        CertAction::GenerateTest {
+            alg,
+            output,
+            organization,
+        } => cert::generate_test(alg, output, organization, json, logger),
     }
+}
 
-    // ── Standalone profile evaluation mode: --profile without --extract ───────
-    if standalone_eval {
-        let profile_path = cli.profile.as_ref().unwrap();
-        let mut success_count = 0u32;
-        let mut error_count = 0u32;
+/// Verify-only builds have no certificate tools.
+#[cfg(not(feature = "signing"))]
+fn run_cert(_action: &CertAction, _json: bool, _logger: &mut Logger) -> Result<()> {
+    anyhow::bail!("This is a verify-only build of crTool: the certificate tools need a full build.")
+}
 
-        logger.info("=== Profile Evaluation ===");
+/// `watch`: sign assets arriving in a hot folder.
+/// `watch`: sign assets arriving in a hot folder.
+#[cfg(feature = "signing")]
+fn run_watch_mode(args: &WatchArgs, logger: &mut Logger) -> Result<()> {
+    let settings = sign_settings(&args.signer)?;
+    watch::run_watch(args, &settings, logger, |output_path, logger| {
+        if args.signer.show_tree {
+            print_provenance_tree(output_path, logger);
+        }
+        run_post_sign(&settings, output_path, logger);
+    })
+}
 
-        for input_file in &input_files {
-            logger.info(&format!("  📄 Processing: {} ...", input_file.display()));
-            match run_profile_evaluation(input_file, profile_path, cli.report_format) {
-                Ok(_) => {
-                    logger.info("     ✅ Done");
-                    success_count += 1;
-                }
-                Err(e) => {
-                    logger.error(&format!("     ❌ Error: {e}"));
-                    error_count += 1;
-                }
+/// Verify-only builds cannot sign, so there is no watch mode.
+#[cfg(not(feature = "signing"))]
+fn run_watch_mode(_args: &WatchArgs, _logger: &mut Logger) -> Result<()> {
+    anyhow::bail!(
+        "This is a verify-only build of crTool: `watch` signs assets and needs a full build."
+    )
+}
+
+/// `serve`: answer extract, validate and sign requests over HTTP.
+#[cfg(feature = "server")]
+fn run_serve_mode(args: &ServeArgs, global: &GlobalArgs, logger: &mut Logger) -> Result<()> {
+    let default_lists = args.trust.checks_trust()
+        && args
+            .trust
+            .local()
+            .trust_config(args.trust.trust)?
+            .use_default_lists;
+    let state = serve::ServeState::new(args.trust.settings(global)?)?
+        .with_trust(args.trust.local(), default_lists);
+    let state = match &args.test_case {
+        #[cfg(feature = "signing")]
+        Some(test_case) => state.with_signing(test_case, sign_settings(&args.signer)?)?,
+        #[cfg(not(feature = "signing"))]
+        Some(_) => anyhow::bail!(
+            "This is a verify-only build of crTool: `serve --test-case` signs assets and needs a \
+            full build."
+        ),
+        None => state,
+    };
+    serve::run_server(args, state, logger)
+}
+
+/// Builds without the `server` feature have no HTTP server.
+#[cfg(not(feature = "server"))]
+fn run_serve_mode(_args: &ServeArgs, _global: &GlobalArgs, _logger: &mut Logger) -> Result<()> {
+    anyhow::bail!(
+        "This build of crTool has no HTTP server; rebuild with `--features server` to use `serve`."
+    )
+}
+
+/// Expand the JSON inputs of `validate` and `profile asset`: globs, and with `recursive`, every
+/// `**/*.json` beneath directories.
+fn expand_json_inputs(
+    patterns: &[String],
+    recursive: bool,
+    logger: &mut Logger,
+) -> Result<Vec<PathBuf>> {
+    let files = expand_input_patterns(patterns).context("Failed to expand input file patterns")?;
+    let files = dedupe_and_report(files, true, logger);
+    let files = if recursive {
+        expand_json_directories(files)?
+    } else {
+        files
+    };
+    if files.is_empty() {
+        anyhow::bail!("No input files found matching the specified pattern(s)");
+    }
+    check_inputs_exist(&files)?;
+    logger.info(&format!("🚀 Processing {} input file(s)", files.len()));
+    Ok(files)
+}
+
+/// `validate`: check JSON files against the crJSON schema (or compare two schema versions).
+fn run_validate(args: &ValidateArgs, json: bool, logger: &mut Logger) -> Result<()> {
+    let files = expand_json_inputs(&args.input, args.recursive, logger)?;
+    if !args.compare_schemas.is_empty() {
+        return compare_schemas(&files, &args.compare_schemas, json);
+    }
+    let mut rules = SeverityRules::new().warnings_as_errors(args.warnings_as_errors);
+    for level in &args.severity {
+        rules = rules.with_level(level.rule.clone(), level.severity);
+    }
+    for rule in &args.suppress_rules {
+        rules = rules.suppress(rule.clone());
+    }
+    let options = ValidateOptions {
+        skip_non_indicators: args.skip_non_indicators,
+        rules,
+    };
+    validate_json_files(&files, &args.schemas, &options)
+}
+
+/// `profile asset`: evaluate crJSON files against a YAML asset profile.
+fn run_profile_asset(
+    input: &[String],
+    profile: &std::path::Path,
+    report_format: ReportFormat,
+    logger: &mut Logger,
+) -> Result<()> {
+    let files = expand_json_inputs(input, false, logger)?;
+    let mut success_count = 0u32;
+    let mut error_count = 0u32;
+
+    logger.info("=== Profile Evaluation ===");
+
+    for input_file in &files {
+        logger.info(&format!("  📄 Processing: {} ...", input_file.display()));
+        match run_profile_evaluation(input_file, profile, report_format) {
+            Ok(_) => {
+                logger.info("     ✅ Done");
+                success_count += 1;
+            }
+            Err(e) => {
+                logger.error(&format!("     ❌ Error: {e}"));
+                error_count += 1;
             }
         }
+    }
 
-        logger.info(&format!(
-            "\n📊 Evaluation Summary: {success_count} succeeded, {error_count} failed, {} total",
-            input_files.len()
-        ));
+    logger.info(&format!(
+        "\n📊 Evaluation Summary: {success_count} succeeded, {error_count} failed, {} total",
+        files.len()
+    ));
 
-        if error_count > 0 {
-            anyhow::bail!("{error_count} file(s) failed evaluation");
-        }
+    if error_count > 0 {
+        anyhow::bail!("{error_count} file(s) failed evaluation");
+    }
+    Ok(())
+}
 
-        return Ok(());
+/// `grep`: search the manifests of many assets.
+fn run_grep(args: &GrepArgs, global: &GlobalArgs, logger: &mut Logger) -> Result<()> {
+    let (settings, files) = args.assets.prepare(global, logger)?;
+    let query =
+        crtool::grep::GrepQuery::new(args.assertion.as_deref(), args.where_expr.as_deref())?;
+    let cache = grep::ExtractionCache::new((!args.no_cache).then(|| {
+        args.cache_dir
+            .clone()
+            .unwrap_or_else(grep::ExtractionCache::default_dir)
+    }));
+    grep::grep_files(&files, &query, &cache, &settings, global.json, logger)
+}
+
+/// `assess`: assess each asset against a JPEG Trust Trust Manifest.
+fn run_assess(
+    assets: &AssetArgs,
+    trust_manifest: &std::path::Path,
+    output: Option<&std::path::Path>,
+    global: &GlobalArgs,
+    logger: &mut Logger,
+) -> Result<()> {
+    let tm = crtool::trust_manifest::load_trust_manifest(trust_manifest)?;
+    let (settings, files) = assets.prepare(global, logger)?;
+    let mut conforming = 0u32;
+    let mut nonconforming = 0u32;
+    let mut error_count = 0u32;
+
+    logger.info("=== Trust Manifest Assessment ===");
+
+    for input_file in &files {
+        logger.info(&format!("  📄 Processing: {} ...", input_file.display()));
+        match assess::assess_asset(input_file, &tm, output, &settings, logger) {
+            Ok(report) if report.conforms => {
+                logger.info("     ✅ Conforms");
+                conforming += 1;
+            }
+            Ok(report) => {
+                logger.info(&format!(
+                    "     ❌ Does not conform ({} clause(s) failed)",
+                    report.failed
+                ));
+                nonconforming += 1;
+            }
+            Err(e) => {
+                logger.error(&format!("     ❌ Error: {e}"));
+                error_count += 1;
+            }
+        }
     }
 
-    // ── Extract mode ──────────────────────────────────────────────────────────
-    if cli.extract {
-        let output = cli
-            .output
-            .context("--output is required when using --extract mode")?;
+    logger.info(&format!(
+        "\n📊 Assessment Summary: {conforming} conform, {nonconforming} do not conform, \
+        {error_count} failed, {} total",
+        files.len()
+    ));
 
-        if input_files.len() > 1 && !output.is_dir() {
-            anyhow::bail!(
-                "Output must be a directory when extracting from multiple input files. Got: {:?}",
-                output
-            );
+    if error_count > 0 {
+        anyhow::bail!("{error_count} file(s) failed assessment");
+    }
+    if nonconforming > 0 {
+        anyhow::bail!("{nonconforming} file(s) do not conform to the trust manifest");
+    }
+    Ok(())
+}
+
+/// `badge`: write an SVG credential-status badge per input.
+fn run_badge(
+    assets: &AssetArgs,
+    output: &std::path::Path,
+    global: &GlobalArgs,
+    logger: &mut Logger,
+) -> Result<()> {
+    let (settings, files) = assets.prepare(global, logger)?;
+    if files.len() > 1 {
+        std::fs::create_dir_all(output).context("Failed to create badge output directory")?;
+    }
+    let mut error_count = 0u32;
+    for input_file in &files {
+        logger.info(&format!("  📄 Processing: {} ...", input_file.display()));
+        match write_badge(input_file, output, &settings) {
+            Ok((path, badge)) => logger.info(&format!(
+                "     🏷️  {} ({}): {}",
+                badge.status.label(),
+                badge.detail(),
+                path.display()
+            )),
+            Err(e) => {
+                logger.error(&format!("     ❌ Error: {e:#}"));
+                error_count += 1;
+            }
         }
+    }
+    if error_count > 0 {
+        anyhow::bail!("{error_count} badge(s) could not be written");
+    }
+    Ok(())
+}
 
-        let mut success_count = 0u32;
-        let mut error_count = 0u32;
+/// `extract`: write the crJSON of each asset, with the optional reports and checks.
+fn run_extract(args: &ExtractArgs, global: &GlobalArgs, logger: &mut Logger) -> Result<()> {
+    let trust_manifest = args
+        .trust_manifest
+        .as_deref()
+        .map(crtool::trust_manifest::load_trust_manifest)
+        .transpose()?;
+    let (settings, files) = args.assets.prepare(global, logger)?;
+    let output = &args.output;
+
+    if (files.len() > 1 || args.mirror_tree.is_some()) && !output.is_dir() {
+        anyhow::bail!(
+            "Output must be a directory when extracting from multiple input files or with --mirror-tree. Got: {:?}",
+            output
+        );
+    }
+
+    let filter = args
+        .where_expr
+        .as_deref()
+        .map(crtool::filter::FilterExpr::parse)
+        .transpose()
+        .context("Invalid --where expression")?;
+    let expectations = crtool::expectations::Expectations {
+        assertions: args.expect_assertion.clone(),
+        trusted: args.expect_trusted,
+        ingredients: args.expect_ingredients,
+    };
+    let soft_binding_plugin = args
+        .soft_binding_plugin
+        .clone()
+        .map(soft_binding::SoftBindingPlugin::new);
+    let options = ExtractOptions {
+        include_cose_details: args.include_cose_details,
+        filter: filter.as_ref(),
+        fast: args.fast,
+        hash_algorithm: args.hash_alg,
+        quiet: global.quiet || global.json,
+        graph_formats: &args.graph,
+        expectations: Some(&expectations).filter(|e| !e.is_empty()),
+        soft_binding_plugin: soft_binding_plugin.as_ref(),
+    };
+    if args.fast {
+        logger.info("⚡ Fast mode: content hashes are not verified; results are partial");
+    }
 
-        for input_file in &input_files {
-            logger.info(&format!("  📄 Processing: {} ...", input_file.display()));
-            match extract_manifest(input_file, &output, &extraction_settings) {
-                Ok(crjson_path) => {
-                    logger.info("     ✅ Done");
-                    success_count += 1;
-                    if let Some(profile_path) = &cli.profile {
-                        if let Err(e) =
-                            run_profile_evaluation(&crjson_path, profile_path, cli.report_format)
-                        {
+    let mut success_count = 0u32;
+    let mut error_count = 0u32;
+    let mut filtered_count = 0u32;
+    let mut unmet_count = 0u32;
+    let mut soft_binding_count = 0u32;
+    let mut evidence_error_count = 0u32;
+    let mut limit_count = 0u32;
+    let mut written_outputs: Vec<PathBuf> = Vec::new();
+    let mut stats = ExtractionStats::default();
+    let mut file_reports = Vec::new();
+
+    for input_file in &files {
+        logger.info(&format!("  📄 Processing: {} ...", input_file.display()));
+        let result = mirrored_output_dir(output, args.mirror_tree.as_deref(), input_file)
+            .and_then(|out_dir| extract_manifest(input_file, &out_dir, &settings, options));
+        let mut removal = Vec::new();
+        match &result {
+            Ok(extracted) => {
+                stats.record_found(input_file, extracted);
+                for feature in &extracted.unsupported {
+                    logger.info(&format!("     ⚠️  {feature}"));
+                }
+                for conflict in &extracted.conflicts {
+                    logger.info(&format!("     ⚠️  {conflict}"));
+                }
+                for cycle in &extracted.cycles {
+                    logger.info(&format!("     ⚠️  {cycle}"));
+                }
+                for unmet in &extracted.unmet_expectations {
+                    logger.error(&format!(
+                        "     ❌ Expectation not met for {}: {unmet}",
+                        input_file.display()
+                    ));
+                }
+                if !extracted.unmet_expectations.is_empty() {
+                    unmet_count += 1;
+                }
+                for check in &extracted.soft_bindings {
+                    match check.status {
+                        SoftBindingStatus::Match => logger.info(&format!("     ✅ {check}")),
+                        SoftBindingStatus::Unsupported => logger.info(&format!("     ⚠️  {check}")),
+                        _ => logger.error(&format!("     ❌ {check}")),
+                    }
+                }
+                if extracted.soft_binding_failed() {
+                    soft_binding_count += 1;
+                }
+            }
+            Err(e) => {
+                if let Some(limit) = crtool::limits::limit_exceeded(&**e) {
+                    logger.error(&format!("     🛑 {limit}"));
+                    limit_count += 1;
+                } else {
+                    if let Some(feature) =
+                        crtool::unsupported::unsupported_from_error(&format!("{e:#}"))
+                    {
+                        logger.error(&format!("     ⚠️  {feature}"));
+                    }
+                    removal =
+                        crtool::removal::detect_credential_removal(input_file).unwrap_or_default();
+                    if !removal.is_empty() {
+                        logger.error("     ⚠️  Credentials likely removed:");
+                        for sign in &removal {
+                            logger.error(&format!("        • {sign}"));
+                        }
+                    }
+                    stats.record_missing(input_file, &removal);
+                }
+            }
+        }
+        if global.json {
+            file_reports.push(
+                FileReport::from_result(input_file, &result).with_credential_removal(removal),
+            );
+        }
+        match result.map(|extracted| (extracted.output, extracted.graphs)) {
+            Ok((None, _)) => {
+                logger.info("     ⏭️  Skipped (does not match --where)");
+                filtered_count += 1;
+            }
+            Ok((Some(crjson_path), graphs)) => {
+                logger.info("     ✅ Done");
+                success_count += 1;
+                written_outputs.push(crjson_path.clone());
+                for graph_path in graphs {
+                    logger.info(&format!(
+                        "     🕸️  Provenance graph: {}",
+                        graph_path.display()
+                    ));
+                    written_outputs.push(graph_path);
+                }
+                if args.evidence_report {
+                    match write_evidence_report(input_file, &crjson_path, &settings) {
+                        Ok(report_path) => {
+                            logger.info(&format!(
+                                "     🧾 Evidence report: {}",
+                                report_path.display()
+                            ));
+                            written_outputs.push(report_path);
+                        }
+                        Err(e) => {
                             logger.error(&format!(
-                                "     ⚠️  Profile evaluation failed for {}: {e}",
-                                crjson_path.display()
+                                "     ❌ Evidence report failed for {}: {e}",
+                                input_file.display()
                             ));
+                            evidence_error_count += 1;
                         }
                     }
                 }
-                Err(e) => {
-                    logger.error(&format!("     ❌ Error: {e}"));
-                    error_count += 1;
+                if let Some(tm) = &trust_manifest {
+                    match assess::assess_crjson_file(&crjson_path, tm, logger) {
+                        Ok(report) if !report.conforms => logger.info(&format!(
+                            "     ⚠️  Does not conform to trust manifest ({} clause(s) failed)",
+                            report.failed
+                        )),
+                        Ok(_) => {}
+                        Err(e) => logger.error(&format!(
+                            "     ⚠️  Trust manifest assessment failed for {}: {e}",
+                            crjson_path.display()
+                        )),
+                    }
+                }
+                if let Some(profile_path) = &args.profile {
+                    if let Err(e) =
+                        run_profile_evaluation(&crjson_path, profile_path, args.report_format)
+                    {
+                        logger.error(&format!(
+                            "     ⚠️  Profile evaluation failed for {}: {e}",
+                            crjson_path.display()
+                        ));
+                    }
                 }
             }
+            Err(e) => {
+                logger.error(&format!("     ❌ Error: {e}"));
+                error_count += 1;
+            }
         }
+    }
 
+    logger.info(&format!(
+        "\n📊 Extraction Summary: {success_count} succeeded, {error_count} failed, {} total",
+        files.len()
+    ));
+    if filter.is_some() {
         logger.info(&format!(
-            "\n📊 Extraction Summary: {success_count} succeeded, {error_count} failed, {} total",
-            input_files.len()
+            "   --where: {filtered_count} file(s) filtered out"
         ));
-
-        if error_count > 0 {
-            anyhow::bail!("{error_count} file(s) failed to extract");
+    }
+    if !expectations.is_empty() {
+        logger.info(&format!(
+            "   --expect: {unmet_count} file(s) did not meet expectations"
+        ));
+    }
+    if soft_binding_plugin.is_some() {
+        logger.info(&format!(
+            "   --soft-binding-plugin: {soft_binding_count} file(s) with a soft binding that \
+            did not match or could not be checked"
+        ));
+    }
+    if args.evidence_report && evidence_error_count > 0 {
+        logger.info(&format!(
+            "   --evidence-report: {evidence_error_count} report(s) could not be written"
+        ));
+    }
+    if limit_count > 0 {
+        logger.info(&format!(
+            "   --max-*: {limit_count} file(s) refused for exceeding a resource limit"
+        ));
+    }
+    if args.fast {
+        logger.info(
+            "   --fast: content hashes were not verified (partial validation), except for ISO \
+            BMFF assets (MP4, MOV, HEIF, AVIF), which are always read in full",
+        );
+    }
+    if files.len() > 1 {
+        for line in stats.summary_lines() {
+            logger.info(&line);
         }
+    }
+    if args.checksums && !written_outputs.is_empty() {
+        let dir = if output.is_dir() {
+            output.to_path_buf()
+        } else {
+            output
+                .parent()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("."))
+        };
+        let (manifest_path, checksums) = write_checksum_manifest(&dir, &written_outputs)
+            .context("Failed to checksum extracted outputs")?;
+        logger.info("   Output checksums (SHA-256):");
+        for (path, digest) in &checksums {
+            logger.info(&format!("     {digest}  {}", path.display()));
+        }
+        logger.info(&format!(
+            "   🔐 Checksum manifest: {}",
+            manifest_path.display()
+        ));
+    }
 
-        return Ok(());
+    if global.json {
+        let report = ExtractionRunReport {
+            mode: "extract",
+            succeeded: success_count,
+            failed: error_count,
+            stats,
+            files: file_reports,
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).context("Failed to format JSON report")?
+        );
     }
 
-    anyhow::bail!(
-        "No operation specified. Use --create-test FILE to create a test asset, \
-        --extract to extract a manifest, --validate to validate JSON files, or \
-        --batch FILE to run a batch of commands."
-    );
+    if error_count > 0 {
+        anyhow::bail!("{error_count} file(s) failed to extract");
+    }
+    if unmet_count > 0 {
+        anyhow::bail!("{unmet_count} file(s) did not meet expectations");
+    }
+    if soft_binding_count > 0 {
+        anyhow::bail!("{soft_binding_count} file(s) failed soft-binding verification");
+    }
+    if evidence_error_count > 0 {
+        anyhow::bail!("{evidence_error_count} evidence report(s) could not be written");
+    }
+
+    Ok(())
+}
+
+/// Scratch directory, resource limits, hashing backend and trust presentations, for the commands
+/// that read or sign assets.
+fn prepare_environment(
+    global: &GlobalArgs,
+    trust_presentation: Option<&std::path::Path>,
+    logger: &mut Logger,
+) -> Result<()> {
+    if let Some(dir) = &global.temp_dir {
+        crtool::scratch::set_scratch_dir(dir);
+    }
+    check_temp_space(global.temp_dir.is_some(), logger)?;
+    crtool::limits::set_extraction_limits(extraction_limits(global));
+    apply_hash_backend(&global.hash_backend, logger)?;
+    crtool::trust_presentation::set_trust_presentations(match trust_presentation {
+        Some(path) => crtool::trust_presentation::TrustPresentations::load(path)?,
+        None => crtool::trust_presentation::TrustPresentations::load_default()?,
+    });
+    Ok(())
+}
+
+/// Execute a parsed CLI command. Called from both normal mode and batch mode.
+pub fn run_cli(cli: Cli, logger: &mut Logger) -> Result<()> {
+    let Some(command) = &cli.command else {
+        anyhow::bail!(
+            "No operation specified. Use a subcommand (sign, extract, validate, info, verify, \
+            fingerprint, cbom, grep, badge, assess, compare-runs, diff, snapshot, profile; see \
+            --help), or --batch FILE to run a batch of commands."
+        );
+    };
+    let global = &cli.global;
+    if command.needs_setup() {
+        let trust_presentation = command
+            .trust_args()
+            .and_then(|trust| trust.trust_presentation.as_deref());
+        prepare_environment(global, trust_presentation, logger)?;
+    }
+
+    match command {
+        Command::Sign(args) => run_sign(args, global.quiet, logger),
+        Command::Extract(args) => run_extract(args, global, logger),
+        Command::Validate(args) => run_validate(args, global.json, logger),
+        Command::Info(assets) => {
+            let (settings, files) = assets.prepare(global, logger)?;
+            info::print_info(&files, &settings, global.json, logger)
+        }
+        Command::Verify { assets, output } => {
+            let (settings, files) = assets.prepare(global, logger)?;
+            verify::verify_assets(
+                &files,
+                &settings,
+                assets.trust.checks_trust(),
+                output.as_deref(),
+                global.json,
+                logger,
+            )
+        }
+        Command::Fingerprint { assets, output } => {
+            let (settings, files) = assets.prepare(global, logger)?;
+            fingerprint::write_fingerprints(
+                &files,
+                &settings,
+                output.as_deref(),
+                global.json,
+                logger,
+            )
+        }
+        Command::Cbom {
+            assets,
+            output,
+            hash_alg,
+        } => {
+            let (settings, files) = assets.prepare(global, logger)?;
+            cbom::write_cboms(&files, &settings, *hash_alg, output.as_deref(), logger)
+        }
+        Command::Snapshot {
+            assets,
+            snapshots,
+            update,
+        } => {
+            let (settings, files) = assets.prepare(global, logger)?;
+            snapshot::check_snapshots(&files, &settings, snapshots, *update, logger)
+        }
+        Command::Demo { output } => run_demo(output, logger),
+        Command::Watch(args) => run_watch_mode(args, logger),
+        Command::Serve(args) => run_serve_mode(args, global, logger),
+        Command::Grep(args) => run_grep(args, global, logger),
+        Command::CompareRuns { old, new } => {
+            compare_runs::compare_run_files(old, new, global.json, logger)
+        }
+        Command::Diff { a, b, ignore } => diff::diff_files(a, b, ignore, global.json, logger),
+        Command::Strip { input, output } => strip::strip_asset(input, output, global.json, logger),
+        Command::Profile {
+            action:
+                ProfileAction::Evaluate {
+                    profile,
+                    indicators,
+                    output,
+                },
+        } => trust_profile::evaluate_files(
+            profile,
+            indicators,
+            output.as_deref(),
+            global.json,
+            logger,
+        ),
+        Command::Profile {
+            action:
+                ProfileAction::Asset {
+                    input,
+                    profile,
+                    report_format,
+                },
+        } => run_profile_asset(input, profile, *report_format, logger),
+        Command::Cert { action } => run_cert(action, global.json, logger),
+        Command::Capabilities => {
+            let caps = capabilities::capabilities();
+            if global.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&caps).context("Failed to format capabilities")?
+                );
+            } else {
+                capabilities::print_capabilities(&caps, logger);
+            }
+            Ok(())
+        }
+        Command::SelfCheckUpdate => {
+            update::self_check_update(global.offline(), global.json, logger)
+        }
+        Command::Assess {
+            assets,
+            trust_manifest,
+            output,
+        } => run_assess(assets, trust_manifest, output.as_deref(), global, logger),
+        Command::Badge { assets, output } => run_badge(assets, output, global, logger),
+        Command::AuditLog {
+            action: AuditLogAction::Verify { log, public_key },
+        } => verify_audit_log(log, public_key, global.json, logger),
+    }
 }
 
 // ─── Entry point ──────────────────────────────────────────────────────────────
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let (cli, legacy_alias) = Cli::try_parse_args(std::env::args_os()).unwrap_or_else(|e| e.exit());
 
-    let mut logger = Logger::new(cli.quiet, cli.log.as_deref())?;
+    let global = &cli.global;
+    let mut logger = Logger::new(global.quiet, global.json, global.log.as_deref())?;
+    if let Some(alias) = legacy_alias {
+        alias.warn(&mut logger);
+    }
 
     // ── Batch mode ────────────────────────────────────────────────────────────
     if let Some(batch_path) = &cli.batch.clone() {
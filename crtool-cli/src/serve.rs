@@ -0,0 +1,1402 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool serve`: a small HTTP server exposing extraction, validation and (optionally) signing
+//! to internal web tooling, built with the `server` feature.
+//!
+//! | Endpoint         | Request                                    | Response                 |
+//! | ---------------- | ------------------------------------------ | ------------------------ |
+//! | `GET /health`    |                                            | Status and version       |
+//! | `GET /readyz`    |                                            | Readiness checks         |
+//! | `POST /extract`  | `multipart/form-data` with an `asset` file | crJSON of the asset      |
+//! | `POST /validate` | crJSON document                            | Schema validation report |
+//! | `POST /sign`     | `multipart/form-data` with an `asset` file | The signed asset         |
+//!
+//! Uploaded assets are streamed to a scratch file as they arrive rather than held in memory, and
+//! a body is refused as soon as it grows past `--max-upload-mb`. Each request has until
+//! `--request-timeout` to arrive and be answered.
+//!
+//! With API keys configured (`--api-keys`, [`API_KEYS_ENV`]), every endpoint but `/health` and
+//! `/readyz` needs one, and `--rate-limit` applies per key (per client address without keys).
+//! Each request is logged as one JSON line ([`AccessLogEntry`]).
+//!
+//! On SIGTERM (or Ctrl-C) the server drains: `/readyz` answers `503` for `--drain-delay`, so load
+//! balancers stop sending traffic, then no new requests are taken and the server exits once the
+//! requests in flight are answered.
+
+use crate::extraction::LocalTrustLists;
+use crate::{Logger, ServeArgs};
+use anyhow::{Context, Result};
+use crtool::formats::AssetFormat;
+use crtool::scratch::ScratchDir;
+use crtool::{SchemaValidator, Settings};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Largest form field other than the asset (`format`, `manifest`), kept in memory.
+const MAX_FORM_FIELD_BYTES: usize = 4 * 1024 * 1024;
+
+/// Largest header block of a form part.
+const MAX_PART_HEADER_BYTES: usize = 16 * 1024;
+
+/// Bytes read from the connection at a time.
+const READ_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Environment variable with API keys, as comma-separated `name:key` entries, in addition to
+/// those of `--api-keys`.
+pub const API_KEYS_ENV: &str = "CRTOOL_SERVE_API_KEYS";
+
+/// Shortest API key accepted.
+const MIN_API_KEY_LEN: usize = 16;
+
+/// Clients tracked by `--rate-limit` before those idle for a minute are forgotten.
+const MAX_RATE_LIMIT_CLIENTS: usize = 10_000;
+
+/// A response before it is sent.
+#[derive(Debug)]
+struct Reply {
+    status: u16,
+    content_type: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl Reply {
+    fn json(status: u16, value: &impl Serialize) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            headers: Vec::new(),
+            body: serde_json::to_vec_pretty(value).unwrap_or_default(),
+        }
+    }
+
+    fn with_header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::json(status, &json!({ "error": message.into() }))
+    }
+
+    /// `413` for resource limit violations, else `status`.
+    fn from_error(status: u16, error: &anyhow::Error) -> Self {
+        match crtool::limits::limit_exceeded(&**error) {
+            Some(limit) => Self::error(413, limit.to_string()),
+            None => Self::error(status, format!("{error:#}")),
+        }
+    }
+
+    fn internal(error: anyhow::Error) -> Self {
+        Self::from_error(500, &error)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ValidationErrorReport {
+    instance_path: String,
+    message: String,
+    severity: crtool::Severity,
+    rule: String,
+}
+
+#[derive(Serialize)]
+struct ValidationReport {
+    valid: bool,
+    errors: Vec<ValidationErrorReport>,
+}
+
+/// Limits applied to every request.
+#[derive(Debug, Clone, Copy)]
+struct RequestLimits {
+    /// Largest request body, in bytes.
+    max_body_bytes: u64,
+    /// Time from receiving a request to answering it.
+    timeout: Duration,
+    /// Requests processed at once, including timed out ones that are still finishing.
+    max_in_flight: usize,
+}
+
+/// Error of a [`BodyReader`] whose body grew past its limit.
+#[derive(Debug)]
+struct BodyTooLarge(u64);
+
+impl std::fmt::Display for BodyTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Request body is over {} bytes", self.0)
+    }
+}
+
+impl std::error::Error for BodyTooLarge {}
+
+/// A request body that fails reads once more than `max_bytes` arrived or the deadline passed.
+struct BodyReader<R> {
+    inner: R,
+    max_bytes: u64,
+    remaining: u64,
+    deadline: Instant,
+}
+
+impl<R: Read> BodyReader<R> {
+    fn new(inner: R, max_bytes: u64, deadline: Instant) -> Self {
+        Self {
+            inner,
+            max_bytes,
+            remaining: max_bytes,
+            deadline,
+        }
+    }
+}
+
+impl<R: Read> Read for BodyReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if Instant::now() > self.deadline {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        let n = self.inner.read(buf)?;
+        if n as u64 > self.remaining {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                BodyTooLarge(self.max_bytes),
+            ));
+        }
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/// The reply to a failed read of a request body.
+fn read_error(error: io::Error) -> Reply {
+    if error.kind() == io::ErrorKind::TimedOut {
+        return Reply::error(
+            408,
+            "The request body did not arrive within --request-timeout",
+        );
+    }
+    match error
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<BodyTooLarge>())
+    {
+        Some(too_large) => Reply::error(413, too_large.to_string()),
+        None => Reply::error(400, format!("Failed to read request body: {error}")),
+    }
+}
+
+/// Read a whole request body into memory, for endpoints that take a JSON document.
+fn read_body(body: &mut impl Read) -> Result<Vec<u8>, Reply> {
+    let mut data = Vec::new();
+    body.read_to_end(&mut data).map_err(read_error)?;
+    Ok(data)
+}
+
+/// Position of `needle` in `haystack` at or after `from`.
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|i| i + from)
+}
+
+/// The boundary of a `multipart/form-data` content type.
+fn multipart_boundary(content_type: &str) -> Option<&str> {
+    let (mime, params) = content_type.split_once(';')?;
+    if !mime.trim().eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params.split(';').find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("boundary")
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// The headers of one field of a `multipart/form-data` body.
+#[derive(Debug)]
+struct PartHeaders {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+}
+
+fn parse_part_headers(headers: &str) -> Result<PartHeaders> {
+    let (mut name, mut filename, mut content_type) = (None, None, None);
+    for (field, value) in headers
+        .split("\r\n")
+        .filter_map(|line| line.split_once(':'))
+    {
+        if field.trim().eq_ignore_ascii_case("content-disposition") {
+            for (key, value) in value.split(';').skip(1).filter_map(|p| p.split_once('=')) {
+                let value = value.trim().trim_matches('"').to_string();
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "name" => name = Some(value),
+                    "filename" => filename = Some(value),
+                    _ => {}
+                }
+            }
+        } else if field.trim().eq_ignore_ascii_case("content-type") {
+            content_type = Some(value.trim().to_string());
+        }
+    }
+    Ok(PartHeaders {
+        name: name.context("Multipart part has no name")?,
+        filename,
+        content_type,
+    })
+}
+
+/// The `asset` file of a form, written to the form's scratch directory.
+#[derive(Debug)]
+struct UploadedAsset {
+    path: PathBuf,
+    filename: Option<String>,
+    content_type: Option<String>,
+}
+
+/// A `multipart/form-data` body: the `asset` file on disk, the other fields in memory.
+#[derive(Debug)]
+struct Form {
+    scratch: ScratchDir,
+    asset: Option<UploadedAsset>,
+    fields: Vec<(String, Vec<u8>)>,
+}
+
+impl Form {
+    fn field(&self, name: &str) -> Option<&[u8]> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, data)| data.as_slice())
+    }
+
+    fn asset(&self) -> Result<&UploadedAsset, Reply> {
+        self.asset
+            .as_ref()
+            .ok_or_else(|| Reply::error(400, "The form has no `asset` field"))
+    }
+
+    /// Format of the uploaded asset: the `format` field (extension or MIME type), else the file
+    /// name's extension, else the part's content type.
+    fn asset_format(&self) -> Result<&'static AssetFormat, Reply> {
+        let asset = self.asset()?;
+        let format = match self.field("format") {
+            Some(format) => crtool::asset_format_for_type(&String::from_utf8_lossy(format)),
+            None => asset
+                .filename
+                .as_deref()
+                .and_then(crtool::asset_format_for_path)
+                .or_else(|| {
+                    asset
+                        .content_type
+                        .as_deref()
+                        .and_then(crtool::asset_format_for_type)
+                }),
+        };
+        format.ok_or_else(|| {
+            Reply::error(
+                415,
+                "Unknown or unsupported asset format; name the file with its extension or add a \
+                `format` field",
+            )
+        })
+    }
+}
+
+/// A `multipart/form-data` body read as it arrives, holding about one chunk in memory.
+struct FormInput<R> {
+    reader: R,
+    buf: Vec<u8>,
+}
+
+fn malformed(message: &str) -> Reply {
+    Reply::error(400, message)
+}
+
+impl<R: Read> FormInput<R> {
+    /// Append the next chunk of the body to the buffer; false at the end of the body.
+    fn fill(&mut self) -> Result<bool, Reply> {
+        let start = self.buf.len();
+        self.buf.resize(start + READ_CHUNK_BYTES, 0);
+        let read = loop {
+            match self.reader.read(&mut self.buf[start..]) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        self.buf.truncate(start + *read.as_ref().unwrap_or(&0));
+        Ok(read.map_err(read_error)? > 0)
+    }
+
+    /// Read until the buffer holds at least `len` bytes or the body ends.
+    fn fill_to(&mut self, len: usize) -> Result<(), Reply> {
+        while self.buf.len() < len && self.fill()? {}
+        Ok(())
+    }
+
+    fn consume(&mut self, len: usize) {
+        self.buf.drain(..len);
+    }
+
+    /// Position of `needle` in the buffer, reading on while at most `max_before` bytes precede
+    /// it. `None` when the body ends first.
+    fn find(&mut self, needle: &[u8], max_before: usize) -> Result<Option<usize>, Reply> {
+        loop {
+            if let Some(pos) = find(&self.buf, needle, 0) {
+                return Ok(Some(pos));
+            }
+            if self.buf.len() > max_before + needle.len() {
+                return Err(malformed("Multipart part headers are too large"));
+            }
+            if !self.fill()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Copy the body up to `delimiter` into `sink`, failing with `413` past `max_bytes`. The
+    /// delimiter stays in the buffer.
+    fn copy_until(
+        &mut self,
+        delimiter: &[u8],
+        sink: &mut impl Write,
+        max_bytes: Option<usize>,
+    ) -> Result<(), Reply> {
+        let mut written = 0;
+        loop {
+            let found = find(&self.buf, delimiter, 0);
+            // Without the delimiter, the buffer's tail may be the start of it
+            let len = found.unwrap_or_else(|| self.buf.len().saturating_sub(delimiter.len() - 1));
+            written += len;
+            if max_bytes.is_some_and(|max| written > max) {
+                return Err(Reply::error(
+                    413,
+                    format!("Form field is over {} bytes", max_bytes.unwrap_or_default()),
+                ));
+            }
+            sink.write_all(&self.buf[..len])
+                .context("Failed to write the upload to the temp directory")
+                .map_err(Reply::internal)?;
+            self.consume(len);
+            if found.is_some() {
+                return Ok(());
+            }
+            if !self.fill()? {
+                return Err(malformed("Multipart body ends before its closing boundary"));
+            }
+        }
+    }
+}
+
+/// Read a `multipart/form-data` body (RFC 7578), writing the `asset` file to a scratch
+/// directory as it arrives.
+fn read_form(body: impl Read, boundary: &str) -> Result<Form, Reply> {
+    let delimiter = format!("--{boundary}").into_bytes();
+    let next_delimiter = format!("\r\n--{boundary}").into_bytes();
+    let mut input = FormInput {
+        reader: body,
+        buf: Vec::new(),
+    };
+    let mut form = Form {
+        scratch: ScratchDir::new("crtool-serve").map_err(|e| Reply::internal(e.into()))?,
+        asset: None,
+        fields: Vec::new(),
+    };
+
+    let start = input
+        .find(&delimiter, MAX_PART_HEADER_BYTES)?
+        .ok_or_else(|| malformed("Multipart body has no boundary"))?;
+    input.consume(start + delimiter.len());
+    loop {
+        input.fill_to(2)?;
+        if input.buf.starts_with(b"--") {
+            return Ok(form);
+        }
+        if !input.buf.starts_with(b"\r\n") {
+            return Err(malformed("Malformed multipart boundary line"));
+        }
+        input.consume(2);
+        let header_end = input
+            .find(b"\r\n\r\n", MAX_PART_HEADER_BYTES)?
+            .ok_or_else(|| malformed("Multipart part has no headers"))?;
+        let headers = std::str::from_utf8(&input.buf[..header_end])
+            .map_err(|_| malformed("Multipart headers are not UTF-8"))?;
+        let part = parse_part_headers(headers).map_err(|e| Reply::from_error(400, &e))?;
+        input.consume(header_end + 4);
+
+        if part.name == "asset" && form.asset.is_none() {
+            let path = form.scratch.join("upload");
+            let mut file = std::fs::File::create(&path)
+                .context("Failed to write the upload to the temp directory")
+                .map_err(Reply::internal)?;
+            input.copy_until(&next_delimiter, &mut file, None)?;
+            form.asset = Some(UploadedAsset {
+                path,
+                filename: part.filename,
+                content_type: part.content_type,
+            });
+        } else {
+            let mut data = Vec::new();
+            input.copy_until(&next_delimiter, &mut data, Some(MAX_FORM_FIELD_BYTES))?;
+            form.fields.push((part.name, data));
+        }
+        input.consume(next_delimiter.len());
+    }
+}
+
+/// A request body, read as far as its endpoint needs before the endpoint runs.
+#[derive(Debug)]
+enum Body {
+    Empty,
+    Json(Vec<u8>),
+    Form(Form),
+}
+
+/// Read the body of a `method` `path` request: a form for `/extract` and `/sign`, the whole
+/// document for `/validate`, nothing otherwise.
+fn read_request_body(
+    method: &str,
+    path: &str,
+    content_type: Option<&str>,
+    mut body: impl Read,
+) -> Result<Body, Reply> {
+    match (method, path) {
+        ("POST", "/extract" | "/sign") => {
+            let boundary = content_type.and_then(multipart_boundary).ok_or_else(|| {
+                Reply::error(
+                    415,
+                    "Expected a multipart/form-data body with the asset in an `asset` field",
+                )
+            })?;
+            Ok(Body::Form(read_form(body, boundary)?))
+        }
+        ("POST", "/validate") => Ok(Body::Json(read_body(&mut body)?)),
+        _ => Ok(Body::Empty),
+    }
+}
+
+#[cfg(feature = "signing")]
+struct SignEndpoint {
+    test_case: crate::test_case::TestCase,
+    base_dir: std::path::PathBuf,
+    sign_settings: crate::processing::SignSettings,
+}
+
+/// What the server needs to answer requests; shared by all worker threads.
+pub struct ServeState {
+    settings: Settings,
+    schema: SchemaValidator,
+    /// Local trust files, re-read by `/readyz`.
+    trust: LocalTrustLists,
+    /// Whether the published trust lists were loaded at startup.
+    default_trust_lists: bool,
+    /// Set on SIGTERM: `/readyz` fails so no new traffic is routed here.
+    draining: AtomicBool,
+    #[cfg(feature = "signing")]
+    sign: Option<SignEndpoint>,
+}
+
+/// Count the trust anchors of `trust`, reading every file as extraction does.
+fn check_trust_files(trust: &LocalTrustLists) -> Result<usize> {
+    let config = trust.trust_config(false)?;
+    config.settings(None)?;
+    let mut anchors = 0;
+    for path in &config.anchor_files {
+        let pem = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read trust anchors {:?}", path))?;
+        anchors += crtool::trust::pem_certificates(&pem).len();
+    }
+    Ok(anchors)
+}
+
+impl ServeState {
+    /// Extraction with `settings` and validation against the bundled crJSON schema; `/sign` is
+    /// disabled until [`ServeState::with_signing`].
+    pub fn new(settings: Settings) -> Result<Self> {
+        Ok(Self {
+            settings,
+            schema: SchemaValidator::from_bundled().context("Failed to load crJSON schema")?,
+            trust: LocalTrustLists::default(),
+            default_trust_lists: false,
+            draining: AtomicBool::new(false),
+            #[cfg(feature = "signing")]
+            sign: None,
+        })
+    }
+
+    /// Report the trust configuration `settings` were built from on `/readyz`: `trust` is
+    /// re-read on every check, `default_lists` tells whether the published lists were loaded.
+    pub fn with_trust(mut self, trust: LocalTrustLists, default_lists: bool) -> Self {
+        self.trust = trust;
+        self.default_trust_lists = default_lists;
+        self
+    }
+
+    /// Start draining: `/readyz` fails from now on.
+    fn drain(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// `200` when the schema is compiled, the trust files load and the server is not draining,
+    /// else `503`, with the result of each check.
+    fn readiness(&self) -> Reply {
+        // Compiled at startup; a server without it does not start
+        let schema = json!({ "ok": true });
+        let trust = if self.trust.is_empty() {
+            Ok(0)
+        } else {
+            check_trust_files(&self.trust)
+        };
+        let trust_ok = trust.is_ok();
+        let trust = match trust {
+            Ok(anchors) => json!({
+                "ok": true,
+                "anchors": anchors,
+                "defaultLists": self.default_trust_lists,
+            }),
+            Err(e) => json!({ "ok": false, "error": format!("{e:#}") }),
+        };
+        let draining = self.is_draining();
+        let ready = trust_ok && !draining;
+        Reply::json(
+            if ready { 200 } else { 503 },
+            &json!({
+                "ready": ready,
+                "draining": draining,
+                "schema": schema,
+                "trust": trust,
+            }),
+        )
+    }
+
+    /// Enable `/sign` with the manifest and credentials of a test case.
+    #[cfg(feature = "signing")]
+    pub fn with_signing(
+        mut self,
+        test_case_path: &std::path::Path,
+        sign_settings: crate::processing::SignSettings,
+    ) -> Result<Self> {
+        self.sign = Some(SignEndpoint {
+            test_case: crate::test_case::load_test_case(test_case_path)?,
+            base_dir: test_case_path
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_default(),
+            sign_settings,
+        });
+        Ok(self)
+    }
+
+    #[cfg(feature = "signing")]
+    fn can_sign(&self) -> bool {
+        self.sign.is_some()
+    }
+
+    #[cfg(not(feature = "signing"))]
+    fn can_sign(&self) -> bool {
+        false
+    }
+
+    fn extract(&self, form: Form) -> Result<Reply, Reply> {
+        let asset = form.asset()?;
+        let format = form.asset_format()?;
+        let file = std::fs::File::open(&asset.path)
+            .context("Failed to read the upload from the temp directory")
+            .map_err(Reply::internal)?;
+        let result = crtool::extract_crjson_manifest_from_stream(file, format.mime, &self.settings)
+            .map_err(|e| {
+                if e.is_no_manifest() {
+                    Reply::error(422, "The asset has no C2PA manifest")
+                } else {
+                    Reply::from_error(400, &e.into())
+                }
+            })?;
+        Ok(Reply::json(200, &result.manifest_value))
+    }
+
+    fn validate(&self, body: &[u8]) -> Result<Reply, Reply> {
+        let text = std::str::from_utf8(body)
+            .map_err(|_| Reply::error(400, "The request body is not UTF-8 JSON"))?;
+        let value =
+            crtool::limits::parse_json(text).map_err(|e| Reply::from_error(400, &e.into()))?;
+        let result = self.schema.validate(&value);
+        Ok(Reply::json(
+            200,
+            &ValidationReport {
+                valid: result.is_valid,
+                errors: result
+                    .errors
+                    .into_iter()
+                    .map(|e| ValidationErrorReport {
+                        instance_path: e.instance_path,
+                        message: e.message,
+                        severity: e.severity,
+                        rule: e.rule,
+                    })
+                    .collect(),
+            },
+        ))
+    }
+
+    #[cfg(feature = "signing")]
+    fn sign(&self, form: Form) -> Result<Reply, Reply> {
+        use std::path::Path;
+
+        let Some(endpoint) = &self.sign else {
+            return Err(Reply::error(
+                404,
+                "Signing is not enabled; start the server with --test-case",
+            ));
+        };
+        let asset = form.asset()?;
+        let format = form.asset_format()?;
+        if !format.can_sign {
+            return Err(Reply::error(
+                415,
+                format!("Signing is not supported for {}", format.mime),
+            ));
+        }
+        let mut test_case = endpoint.test_case.clone();
+        if let Some(manifest) = form.field("manifest") {
+            test_case.manifest = std::str::from_utf8(manifest)
+                .context("The manifest is not UTF-8 JSON")
+                .and_then(|text| Ok(crtool::limits::parse_json(text)?))
+                .map_err(|e| Reply::from_error(400, &e))?;
+        }
+
+        // Keep the uploaded file name for {{filename}} placeholders, with the format's extension
+        let stem = asset
+            .filename
+            .as_deref()
+            .and_then(|name| Path::new(name).file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "asset".to_string());
+        let input = form.scratch.join(format!("{stem}.{}", format.extension));
+        let output_dir = form.scratch.join("signed");
+        std::fs::rename(&asset.path, &input)
+            .and_then(|_| std::fs::create_dir(&output_dir))
+            .context("Failed to prepare the upload in the temp directory")
+            .map_err(Reply::internal)?;
+        let output = crate::test_case::sign_test_case(
+            &test_case,
+            &endpoint.base_dir,
+            &input,
+            &output_dir,
+            &endpoint.sign_settings,
+        )
+        .map_err(|e| Reply::from_error(400, &e))?;
+        let signed = std::fs::read(&output)
+            .context("Failed to read the signed asset")
+            .map_err(Reply::internal)?;
+        Ok(Reply {
+            status: 200,
+            content_type: format.mime,
+            headers: Vec::new(),
+            body: signed,
+        })
+    }
+
+    #[cfg(not(feature = "signing"))]
+    fn sign(&self, _form: Form) -> Result<Reply, Reply> {
+        Err(Reply::error(
+            404,
+            "This is a verify-only build of crTool: signing is not available",
+        ))
+    }
+
+    fn route(&self, method: &str, path: &str, body: Body) -> Reply {
+        let result = match (method, path, body) {
+            ("GET", "/health", _) => Ok(Reply::json(
+                200,
+                &json!({
+                    "status": "ok",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "sign": self.can_sign(),
+                }),
+            )),
+            ("GET", "/readyz", _) => Ok(self.readiness()),
+            ("POST", "/extract", Body::Form(form)) => self.extract(form),
+            ("POST", "/validate", Body::Json(body)) => self.validate(&body),
+            ("POST", "/sign", Body::Form(form)) => self.sign(form),
+            (_, "/health" | "/readyz" | "/extract" | "/validate" | "/sign", _) => Err(
+                Reply::error(405, format!("{method} is not allowed on {path}")),
+            ),
+            _ => Err(Reply::error(404, format!("No endpoint {path}"))),
+        };
+        result.unwrap_or_else(|reply| reply)
+    }
+}
+
+/// API keys accepted by the server, with the name each is logged as.
+pub struct ApiKeys {
+    keys: Vec<(String, String)>,
+}
+
+impl ApiKeys {
+    /// Parse `name:key` entries; a bare key is named `key-<n>`, counting from 1.
+    fn parse<'a>(entries: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut keys = Vec::new();
+        for entry in entries.into_iter().map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, key) = match entry.split_once(':') {
+                Some((name, key)) => (name.trim().to_string(), key.trim()),
+                None => (format!("key-{}", keys.len() + 1), entry),
+            };
+            if key.len() < MIN_API_KEY_LEN {
+                anyhow::bail!(
+                    "API key `{name}` is shorter than {MIN_API_KEY_LEN} characters; generate one \
+                    with e.g. `openssl rand -hex 32`"
+                );
+            }
+            keys.push((name, key.to_string()));
+        }
+        Ok(Self { keys })
+    }
+
+    /// The keys of `file` (one entry per line, `#` starts a comment line) and [`API_KEYS_ENV`].
+    /// `None` when neither names a key, i.e. the server is open.
+    pub fn load(file: Option<&Path>) -> Result<Option<Self>> {
+        let mut entries = Vec::new();
+        if let Some(file) = file {
+            let text = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read API keys from {:?}", file))?;
+            entries.extend(
+                text.lines()
+                    .filter(|line| !line.trim_start().starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+        if let Some(env) = std::env::var_os(API_KEYS_ENV) {
+            let env = env
+                .into_string()
+                .map_err(|_| anyhow::anyhow!("{API_KEYS_ENV} is not UTF-8"))?;
+            entries.extend(env.split(',').map(str::to_string));
+        }
+        let keys = Self::parse(entries.iter().map(String::as_str))?;
+        Ok((!keys.keys.is_empty()).then_some(keys))
+    }
+
+    /// Name of the key `presented`. Every key is compared in full, so the time taken does not
+    /// tell how much of a key matched.
+    fn find(&self, presented: &str) -> Option<&str> {
+        let mut found = None;
+        for (name, key) in &self.keys {
+            if constant_time_eq(key.as_bytes(), presented.as_bytes()) {
+                found = Some(name.as_str());
+            }
+        }
+        found
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Token buckets of `per_minute` requests a minute, refilled continuously, one per client.
+struct RateLimiter {
+    per_minute: u32,
+    buckets: Mutex<HashMap<String, (f64, Instant)>>,
+}
+
+impl RateLimiter {
+    fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a request from `client`'s bucket at `now`, or the seconds until one is available.
+    fn take(&self, client: &str, now: Instant) -> Result<(), u64> {
+        let capacity = f64::from(self.per_minute);
+        let per_second = capacity / 60.0;
+        let Ok(mut buckets) = self.buckets.lock() else {
+            return Ok(());
+        };
+        // Forget clients whose buckets have refilled, so many addresses cannot grow the map
+        if buckets.len() >= MAX_RATE_LIMIT_CLIENTS {
+            let full = Duration::from_secs(60);
+            buckets.retain(|_, (_, updated)| now.saturating_duration_since(*updated) < full);
+        }
+        let (tokens, updated) = buckets.entry(client.to_string()).or_insert((capacity, now));
+        *tokens = (*tokens + now.saturating_duration_since(*updated).as_secs_f64() * per_second)
+            .min(capacity);
+        *updated = now;
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - *tokens) * 60.0 / capacity).ceil() as u64)
+        }
+    }
+}
+
+/// Who may use the server, and how often.
+struct AccessControl {
+    keys: Option<ApiKeys>,
+    limiter: Option<RateLimiter>,
+}
+
+impl AccessControl {
+    /// Check a request for `path` from `client` with its `Authorization` and `X-API-Key`
+    /// headers. Returns the name of the key it was made with.
+    fn admit(
+        &self,
+        path: &str,
+        client: &str,
+        authorization: Option<&str>,
+        api_key: Option<&str>,
+        now: Instant,
+    ) -> Result<Option<String>, Reply> {
+        if matches!(path, "/health" | "/readyz") {
+            return Ok(None);
+        }
+        let key_name =
+            match &self.keys {
+                None => None,
+                Some(keys) => {
+                    let presented = authorization
+                        .and_then(|value| value.strip_prefix("Bearer "))
+                        .or(api_key)
+                        .map(str::trim);
+                    match presented.and_then(|key| keys.find(key)) {
+                        Some(name) => Some(name.to_string()),
+                        None => return Err(Reply::error(
+                            401,
+                            "A valid API key is required: send `Authorization: Bearer <key>` or \
+                            `X-API-Key: <key>`",
+                        )
+                        .with_header("WWW-Authenticate", "Bearer")),
+                    }
+                }
+            };
+        if let Some(limiter) = &self.limiter {
+            let bucket = key_name.as_deref().unwrap_or(client);
+            if let Err(retry_after) = limiter.take(bucket, now) {
+                return Err(Reply::error(
+                    429,
+                    format!(
+                        "More than {} requests a minute; retry in {retry_after} s",
+                        limiter.per_minute
+                    ),
+                )
+                .with_header("Retry-After", retry_after.to_string()));
+            }
+        }
+        Ok(key_name)
+    }
+}
+
+/// One line of the access log.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessLogEntry {
+    pub timestamp: String,
+    /// Address of the client.
+    pub client: String,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub duration_ms: u64,
+    /// `Content-Length` of the request, when sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_bytes: Option<usize>,
+    pub response_bytes: usize,
+    /// Name of the API key the request was made with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// A request counted against [`RequestLimits::max_in_flight`] until dropped.
+struct InFlight(Arc<AtomicUsize>);
+
+impl InFlight {
+    fn acquire(count: &Arc<AtomicUsize>, max: usize) -> Option<Self> {
+        if count.fetch_add(1, Ordering::SeqCst) >= max {
+            count.fetch_sub(1, Ordering::SeqCst);
+            return None;
+        }
+        Some(Self(count.clone()))
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Read the request's body and answer it with `state` before `deadline`. The endpoint runs on
+/// its own thread: when it overruns, the client gets `503` and the thread keeps its in-flight
+/// slot until it finishes.
+fn handle(
+    state: &Arc<ServeState>,
+    request: &mut tiny_http::Request,
+    method: &str,
+    path: &str,
+    limits: RequestLimits,
+    slot: InFlight,
+) -> Reply {
+    let deadline = Instant::now() + limits.timeout;
+    let content_type = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Content-Type"))
+        .map(|header| header.value.as_str().to_string());
+    if request
+        .body_length()
+        .is_some_and(|len| len as u64 > limits.max_body_bytes)
+    {
+        return Reply::error(413, BodyTooLarge(limits.max_body_bytes).to_string());
+    }
+    let body = BodyReader::new(request.as_reader(), limits.max_body_bytes, deadline);
+    let body = match read_request_body(method, path, content_type.as_deref(), body) {
+        Ok(body) => body,
+        Err(reply) => return reply,
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let state = state.clone();
+    let (method, path) = (method.to_string(), path.to_string());
+    std::thread::spawn(move || {
+        let _slot = slot;
+        let _ = sender.send(state.route(&method, &path, body));
+    });
+    match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        Ok(reply) => reply,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            Reply::error(503, "The request was not answered within --request-timeout")
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Reply::error(500, "The request failed unexpectedly")
+        }
+    }
+}
+
+/// Everything the worker threads share.
+struct Server<'l> {
+    state: Arc<ServeState>,
+    limits: RequestLimits,
+    in_flight: Arc<AtomicUsize>,
+    access: AccessControl,
+    logger: Mutex<&'l mut Logger>,
+}
+
+fn header<'r>(request: &'r tiny_http::Request, name: &'static str) -> Option<&'r str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+fn serve_request(server: &Server, mut request: tiny_http::Request) {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let client = request
+        .remote_addr()
+        .map_or_else(String::new, |addr| addr.ip().to_string());
+    let request_bytes = request.body_length();
+    let admitted = server.access.admit(
+        &path,
+        &client,
+        header(&request, "Authorization"),
+        header(&request, "X-API-Key"),
+        started,
+    );
+    let (reply, api_key) = match admitted {
+        Ok(api_key) => {
+            let reply = match InFlight::acquire(&server.in_flight, server.limits.max_in_flight) {
+                Some(slot) => handle(
+                    &server.state,
+                    &mut request,
+                    &method,
+                    &path,
+                    server.limits,
+                    slot,
+                ),
+                None => Reply::error(503, "The server is busy; retry later"),
+            };
+            (reply, api_key)
+        }
+        Err(reply) => (reply, None),
+    };
+
+    let status = reply.status;
+    let response_bytes = reply.body.len();
+    let mut response = tiny_http::Response::from_data(reply.body).with_status_code(status);
+    for (name, value) in
+        std::iter::once(("Content-Type", reply.content_type.to_string())).chain(reply.headers)
+    {
+        if let Ok(header) = tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+            response = response.with_header(header);
+        }
+    }
+    let sent = request.respond(response);
+    let entry = AccessLogEntry {
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        client,
+        method,
+        path,
+        status,
+        duration_ms: started.elapsed().as_millis() as u64,
+        request_bytes,
+        response_bytes,
+        api_key,
+    };
+    if let Ok(mut logger) = server.logger.lock() {
+        if let Err(e) = sent {
+            logger.error(&format!(
+                "  {} {}: failed to respond: {e}",
+                entry.method, entry.path
+            ));
+        }
+        if let Ok(line) = serde_json::to_string(&entry) {
+            logger.info(&line);
+        }
+    }
+}
+
+/// Listen on `--bind`:`--port` and answer requests with `state` until SIGTERM or Ctrl-C, then
+/// drain (see the module documentation).
+pub fn run_server(args: &ServeArgs, state: ServeState, logger: &mut Logger) -> Result<()> {
+    let keys = ApiKeys::load(args.api_keys.as_deref())?;
+    let state = Arc::new(state);
+    let stop = AtomicBool::new(false);
+    {
+        let state = state.clone();
+        ctrlc::set_handler(move || state.drain())
+            .context("Failed to install the SIGTERM handler")?;
+    }
+
+    let address = format!("{}:{}", args.bind, args.port);
+    let server = tiny_http::Server::http(&address)
+        .map_err(|e| anyhow::anyhow!("Failed to listen on {address}: {e}"))?;
+    logger.info(&format!(
+        "🚀 crTool HTTP server listening on http://{address} (/sign {})",
+        if state.can_sign() {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    ));
+    match &keys {
+        Some(keys) => logger.info(&format!(
+            "🔑 {} API key(s) accepted; every endpoint but /health and /readyz needs one",
+            keys.keys.len()
+        )),
+        None => logger.info(&format!(
+            "⚠️  No API keys configured (--api-keys, {API_KEYS_ENV}): the server is open to \
+            anyone who can reach it"
+        )),
+    }
+
+    let workers = args.workers.max(1);
+    let timeout = Duration::from_secs(args.request_timeout);
+    let shared = Server {
+        state,
+        limits: RequestLimits {
+            max_body_bytes: args.max_upload_mb * 1024 * 1024,
+            timeout,
+            max_in_flight: workers,
+        },
+        in_flight: Arc::new(AtomicUsize::new(0)),
+        access: AccessControl {
+            keys,
+            limiter: args.rate_limit.map(RateLimiter::new),
+        },
+        logger: Mutex::new(logger),
+    };
+    let log = |message: &str| {
+        if let Ok(mut logger) = shared.logger.lock() {
+            logger.info(message);
+        }
+    };
+    std::thread::scope(|scope| {
+        // Turn a drain into a stop once --drain-delay has passed
+        scope.spawn(|| {
+            while !stop.load(Ordering::SeqCst) {
+                if shared.state.is_draining() {
+                    log(&format!(
+                        "🛑 Draining: /readyz fails for {} s, then in-flight requests finish",
+                        args.drain_delay
+                    ));
+                    std::thread::sleep(Duration::from_secs(args.drain_delay));
+                    stop.store(true, Ordering::SeqCst);
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        });
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while !stop.load(Ordering::SeqCst) {
+                    match server.recv_timeout(Duration::from_millis(100)) {
+                        Ok(Some(request)) => serve_request(&shared, request),
+                        Ok(None) => {}
+                        Err(e) => {
+                            if let Ok(mut logger) = shared.logger.lock() {
+                                logger.error(&format!("❌ HTTP server: {e}"));
+                            }
+                            stop.store(true, Ordering::SeqCst);
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    // Requests that overran --request-timeout still hold a slot until their work is done
+    let deadline = Instant::now() + timeout;
+    while shared.in_flight.load(Ordering::SeqCst) > 0 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    log("✅ Stopped after answering the requests in flight");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDARY: &str = "----crtool-boundary";
+
+    fn form(fields: &[(&str, Option<&str>, &[u8])]) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (name, filename, data) in fields {
+            body.extend_from_slice(format!("--{BOUNDARY}\r\n").as_bytes());
+            let filename = filename.map_or(String::new(), |f| format!("; filename=\"{f}\""));
+            body.extend_from_slice(
+                format!("Content-Disposition: form-data; name=\"{name}\"{filename}\r\n\r\n")
+                    .as_bytes(),
+            );
+            body.extend_from_slice(data);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{BOUNDARY}--\r\n").as_bytes());
+        body
+    }
+
+    fn content_type() -> String {
+        format!("multipart/form-data; boundary=\"{BOUNDARY}\"")
+    }
+
+    /// Hands out at most 7 bytes per read, so boundaries straddle reads.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(7).min(self.0.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    fn request(state: &ServeState, method: &str, path: &str, body: &[u8]) -> Reply {
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let body = BodyReader::new(body, 64 * 1024 * 1024, deadline);
+        match read_request_body(method, path, Some(content_type().as_str()), body) {
+            Ok(body) => state.route(method, path, body),
+            Err(reply) => reply,
+        }
+    }
+
+    #[test]
+    fn test_read_form() {
+        assert_eq!(multipart_boundary(&content_type()), Some(BOUNDARY));
+        assert_eq!(multipart_boundary("application/json"), None);
+
+        let asset_data = b"\r\n--not-the-boundary\r\n\xff".repeat(10_000);
+        let body = form(&[
+            ("asset", Some("photo.jpg"), &asset_data),
+            ("format", None, b"png"),
+        ]);
+        let parsed = read_form(Trickle(&body), BOUNDARY).unwrap();
+        let asset = parsed.asset().unwrap();
+        assert_eq!(asset.filename.as_deref(), Some("photo.jpg"));
+        assert_eq!(std::fs::read(&asset.path).unwrap(), asset_data);
+        assert_eq!(parsed.field("format"), Some(&b"png"[..]));
+        assert_eq!(parsed.asset_format().unwrap().extension, "png");
+
+        let body = form(&[("asset", Some("photo.jpg"), b"data")]);
+        let parsed = read_form(&body[..], BOUNDARY).unwrap();
+        assert_eq!(parsed.asset_format().unwrap().extension, "jpg");
+
+        let reply = read_form(&body[..body.len() - 10], BOUNDARY).unwrap_err();
+        assert_eq!(reply.status, 400);
+        assert_eq!(
+            read_form(&b"no boundary here"[..], BOUNDARY)
+                .unwrap_err()
+                .status,
+            400
+        );
+
+        let too_long = vec![b'x'; MAX_FORM_FIELD_BYTES + 1];
+        let body = form(&[("manifest", None, &too_long)]);
+        assert_eq!(read_form(&body[..], BOUNDARY).unwrap_err().status, 413);
+    }
+
+    #[test]
+    fn test_body_limits() {
+        let body = form(&[("asset", Some("photo.jpg"), &[0u8; 4096])]);
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let reply = read_form(BodyReader::new(&body[..], 1024, deadline), BOUNDARY).unwrap_err();
+        assert_eq!(reply.status, 413);
+
+        let past = Instant::now() - Duration::from_secs(1);
+        let reply = read_form(BodyReader::new(&body[..], 1 << 20, past), BOUNDARY).unwrap_err();
+        assert_eq!(reply.status, 408);
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let first = InFlight::acquire(&count, 1).unwrap();
+        assert!(InFlight::acquire(&count, 1).is_none());
+        drop(first);
+        assert!(InFlight::acquire(&count, 1).is_some());
+    }
+
+    #[test]
+    fn test_access_control() {
+        let keys = ApiKeys::parse(["ci:0123456789abcdef0123", "fedcba9876543210fedc"]).unwrap();
+        assert_eq!(keys.find("0123456789abcdef0123"), Some("ci"));
+        assert_eq!(keys.find("fedcba9876543210fedc"), Some("key-2"));
+        assert_eq!(keys.find("0123456789abcdef012"), None);
+        assert!(ApiKeys::parse(["short"]).is_err());
+
+        let access = AccessControl {
+            keys: Some(keys),
+            limiter: Some(RateLimiter::new(2)),
+        };
+        let now = Instant::now();
+        let admit = |path, authorization, api_key, now| {
+            access.admit(path, "10.0.0.1", authorization, api_key, now)
+        };
+        assert_eq!(admit("/health", None, None, now).unwrap(), None);
+        assert_eq!(admit("/readyz", None, None, now).unwrap(), None);
+        let denied = admit("/extract", None, None, now).unwrap_err();
+        assert_eq!(denied.status, 401);
+        assert!(denied
+            .headers
+            .contains(&("WWW-Authenticate", "Bearer".to_string())));
+        let wrong = Some("Bearer fedcba9876543210fedX");
+        assert_eq!(admit("/extract", wrong, None, now).unwrap_err().status, 401);
+
+        let bearer = Some("Bearer 0123456789abcdef0123");
+        assert_eq!(
+            admit("/extract", bearer, None, now).unwrap().as_deref(),
+            Some("ci")
+        );
+        assert_eq!(
+            admit("/validate", None, Some("0123456789abcdef0123"), now)
+                .unwrap()
+                .as_deref(),
+            Some("ci")
+        );
+        // the bucket of 2 is empty; the other key has its own
+        let limited = admit("/extract", bearer, None, now).unwrap_err();
+        assert_eq!(limited.status, 429);
+        assert!(limited.headers.contains(&("Retry-After", "30".to_string())));
+        assert!(admit("/extract", Some("Bearer fedcba9876543210fedc"), None, now).is_ok());
+        // a request comes back every 30 seconds
+        let later = now + Duration::from_secs(31);
+        assert!(admit("/extract", bearer, None, later).is_ok());
+    }
+
+    #[test]
+    fn test_max_upload_mb_is_bounded() {
+        let parse = |mb: &str| {
+            crate::Cli::try_parse_args(["crTool", "serve", "--max-upload-mb", mb])
+                .map(|_| ())
+                .map_err(|e| e.kind())
+        };
+        assert!(parse("65536").is_ok());
+        assert_eq!(
+            parse("17592186044416"),
+            Err(clap::error::ErrorKind::ValueValidation)
+        );
+        assert_eq!(parse("0"), Err(clap::error::ErrorKind::ValueValidation));
+    }
+
+    #[test]
+    fn test_route() {
+        let state = ServeState::new(crtool::default_extraction_settings()).unwrap();
+
+        let health = request(&state, "GET", "/health", b"");
+        assert_eq!(health.status, 200);
+        let health: serde_json::Value = serde_json::from_slice(&health.body).unwrap();
+        assert_eq!(health["sign"], false);
+
+        let invalid = request(&state, "POST", "/validate", b"{}");
+        assert_eq!(invalid.status, 200);
+        let invalid: serde_json::Value = serde_json::from_slice(&invalid.body).unwrap();
+        assert_eq!(invalid["valid"], false);
+        assert!(!invalid["errors"].as_array().unwrap().is_empty());
+        assert_eq!(
+            request(&state, "POST", "/validate", b"{not json").status,
+            400
+        );
+
+        let dog = std::fs::read(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../tests/fixtures/assets/Dog.jpg"),
+        )
+        .unwrap();
+        let unsigned = request(
+            &state,
+            "POST",
+            "/extract",
+            &form(&[("asset", Some("Dog.jpg"), &dog)]),
+        );
+        assert_eq!(unsigned.status, 422);
+        let no_asset = request(
+            &state,
+            "POST",
+            "/extract",
+            &form(&[("format", None, b"jpg")]),
+        );
+        assert_eq!(no_asset.status, 400);
+        let not_a_form =
+            read_request_body("POST", "/extract", Some("image/jpeg"), &dog[..]).unwrap_err();
+        assert_eq!(not_a_form.status, 415);
+
+        let ready = request(&state, "GET", "/readyz", b"");
+        assert_eq!(ready.status, 200);
+        state.drain();
+        let draining = request(&state, "GET", "/readyz", b"");
+        assert_eq!(draining.status, 503);
+        let draining: serde_json::Value = serde_json::from_slice(&draining.body).unwrap();
+        assert_eq!(draining["draining"], true);
+        assert_eq!(draining["trust"]["ok"], true);
+
+        assert_eq!(request(&state, "POST", "/sign", &form(&[])).status, 404);
+        assert_eq!(request(&state, "GET", "/extract", b"").status, 405);
+        assert_eq!(request(&state, "GET", "/nothing", b"").status, 404);
+    }
+}
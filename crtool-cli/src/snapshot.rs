@@ -0,0 +1,101 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool snapshot`: compare the normalized crJSON of each input with the snapshot stored
+//! under its file stem (see [`crtool::snapshot`]), e.g. for the signed testset assets before
+//! and after a c2pa-rs upgrade.
+
+use crate::Logger;
+use anyhow::Result;
+use c2pa::Settings;
+use crtool::snapshot::{SnapshotOutcome, SnapshotStore};
+use std::path::{Path, PathBuf};
+
+/// Check every input against its snapshot in `dir` (writing missing and changed ones with
+/// `update`). Fails when an input cannot be extracted, has no snapshot, or differs from it.
+pub fn check_snapshots(
+    input_files: &[PathBuf],
+    settings: &Settings,
+    dir: &Path,
+    update: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    let store = SnapshotStore::new(dir).update(update);
+    logger.info(&format!("=== Snapshots in {} ===", dir.display()));
+    let (mut matched, mut created, mut updated, mut changed, mut missing, mut failed) =
+        (0, 0, 0, 0, 0, 0);
+    for input_file in input_files {
+        let name = input_file
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let outcome = crtool::extract_crjson_manifest_with_settings(input_file, settings)
+            .map_err(anyhow::Error::from)
+            .and_then(|extraction| Ok(store.check(&name, &extraction.manifest_value)?));
+        match outcome {
+            Ok(SnapshotOutcome::Matched) => {
+                logger.info(&format!("  ✅ {name}"));
+                matched += 1;
+            }
+            Ok(SnapshotOutcome::Created) => {
+                logger.info(&format!("  🆕 {name}: snapshot written"));
+                created += 1;
+            }
+            Ok(SnapshotOutcome::Missing) => {
+                logger.error(&format!(
+                    "  ❌ {name}: no snapshot ({})",
+                    store.path(&name).display()
+                ));
+                missing += 1;
+            }
+            Ok(SnapshotOutcome::Updated(diff)) => {
+                logger.info(&format!(
+                    "  📝 {name}: snapshot updated ({} change(s))",
+                    diff.change_count()
+                ));
+                updated += 1;
+            }
+            Ok(SnapshotOutcome::Changed(diff)) => {
+                logger.error(&format!("  ❌ {name}: extraction changed"));
+                for line in diff.to_string().lines() {
+                    logger.error(&format!("       {line}"));
+                }
+                changed += 1;
+            }
+            Err(e) => {
+                logger.error(&format!("  ❌ {}: {e:#}", input_file.display()));
+                failed += 1;
+            }
+        }
+    }
+    logger.info(&format!(
+        "\n📊 Snapshots: {matched} matched, {created} new, {updated} updated, {changed} changed, \
+        {missing} missing, {failed} failed"
+    ));
+
+    if changed > 0 {
+        anyhow::bail!(
+            "{changed} extraction(s) differ from their snapshots; review the changes and rerun \
+            with --update to accept them"
+        );
+    }
+    if missing > 0 {
+        anyhow::bail!(
+            "{missing} input(s) have no snapshot; rerun with --update to write them, then review \
+            and commit them"
+        );
+    }
+    if failed > 0 {
+        anyhow::bail!("{failed} input(s) could not be extracted");
+    }
+    Ok(())
+}
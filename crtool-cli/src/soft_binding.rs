@@ -0,0 +1,70 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `--soft-binding-plugin`: soft-binding values computed by an external program (see
+//! [`crtool::soft_binding`]).
+//!
+//! The program is run as `<program> <alg> <asset path>` for each soft-binding assertion of an
+//! extracted asset's active manifest, with `CRTOOL_SOFT_BINDING_ALG`, `CRTOOL_ASSET_PATH` and
+//! `CRTOOL_MANIFEST_LABEL` set. It prints the watermark or fingerprint value on stdout, or
+//! nothing when it does not support the algorithm; a non-zero exit is reported as an error.
+
+use crtool::soft_binding::{SoftBinding, SoftBindingResolver};
+use crtool::CrtoolError;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// A soft-binding resolver program.
+#[derive(Debug, Clone)]
+pub struct SoftBindingPlugin {
+    program: PathBuf,
+    name: String,
+}
+
+impl SoftBindingPlugin {
+    pub fn new(program: PathBuf) -> Self {
+        let name = program.display().to_string();
+        Self { program, name }
+    }
+}
+
+impl SoftBindingResolver for SoftBindingPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resolve(&self, asset: &Path, binding: &SoftBinding) -> crtool::Result<Option<String>> {
+        let output = Command::new(&self.program)
+            .arg(&binding.alg)
+            .arg(asset)
+            .env("CRTOOL_SOFT_BINDING_ALG", &binding.alg)
+            .env("CRTOOL_ASSET_PATH", asset)
+            .env("CRTOOL_MANIFEST_LABEL", &binding.manifest_label)
+            .stdin(Stdio::null())
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|e| {
+                CrtoolError::Message(format!(
+                    "Failed to run soft-binding plugin {}: {e}",
+                    self.name
+                ))
+            })?;
+        if !output.status.success() {
+            return Err(CrtoolError::Message(format!(
+                "Soft-binding plugin {} exited with {}",
+                self.name, output.status
+            )));
+        }
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(Some(value).filter(|v| !v.is_empty()))
+    }
+}
@@ -0,0 +1,80 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool strip INPUT -o OUTPUT`: write a copy of an asset without its C2PA manifest store
+//! and manifest references (see [`crtool::strip`]).
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use crtool::strip::InUseDetection;
+use std::path::Path;
+
+/// Whether `output` is the existing file `input`, i.e. the asset is stripped in place.
+fn is_same_file(input: &Path, output: &Path) -> bool {
+    match (input.canonicalize(), output.canonicalize()) {
+        (Ok(input), Ok(output)) => input == output,
+        _ => false,
+    }
+}
+
+/// Strip `input` into `output`, then confirm that no manifest can be read from the output.
+pub fn strip_asset(input: &Path, output: &Path, json: bool, logger: &mut Logger) -> Result<()> {
+    if is_same_file(input, output) {
+        match crtool::strip::IN_USE_DETECTION {
+            InUseDetection::Complete => {}
+            InUseDetection::CurrentUser => logger.info(
+                "ℹ️  Stripping in place: processes of other users that have the file open are not \
+                detected",
+            ),
+            InUseDetection::LockingProcesses => logger.info(
+                "ℹ️  Stripping in place: in-use detection is best-effort on this platform; only \
+                processes that lock the file are detected",
+            ),
+        }
+    }
+    let stripped = crtool::remove_c2pa(input, output)
+        .with_context(|| format!("Failed to strip {}", input.display()))?;
+    if stripped.is_empty() {
+        logger.info(&format!(
+            "⚠️  No C2PA data found in {}; wrote an unchanged copy",
+            input.display()
+        ));
+    } else {
+        logger.info(&format!(
+            "✂️  Removed {} manifest store block(s) ({} bytes) from {}",
+            stripped.removed_blocks,
+            stripped.removed_bytes,
+            input.display()
+        ));
+        for reference in &stripped.references {
+            logger.info(&format!("   Removed XMP reference: {reference}"));
+        }
+    }
+    match crtool::extract_crjson_manifest(output) {
+        Err(e) if e.is_no_manifest() => {}
+        Err(e) => anyhow::bail!("Failed to check {}: {e}", output.display()),
+        Ok(_) => anyhow::bail!(
+            "{} still has a readable C2PA manifest (e.g. in a sidecar or a format-specific \
+            location crTool does not strip)",
+            output.display()
+        ),
+    }
+    logger.info(&format!("✅ Wrote {}", output.display()));
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&stripped).context("Failed to format JSON report")?
+        );
+    }
+    Ok(())
+}
@@ -10,17 +10,18 @@ OF ANY KIND, either express or implied. See the License for the specific languag
 governing permissions and limitations under the License.
 */
 
+use crate::jobs::say;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::processing::{
-    detect_signing_algorithm, parse_signing_algorithm, process_single_file, ProcessingConfig,
-};
+use crate::processing::SignSettings;
+use crate::processing::{process_single_file, ProcessingConfig};
+use crtool::signing::{detect_signing_algorithm, parse_signing_algorithm};
 
 /// A C2PA validator test case loaded from a JSON file.
 /// Follows the schema defined in `INTERNAL/schemas/test-case.schema.json`.
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TestCase {
     pub test_id: String,
@@ -37,22 +38,21 @@ pub struct TestCase {
 }
 
 /// Handle the `--create-test` mode: read a test case JSON file and produce a signed asset.
+/// Returns the path of the signed output file.
 /// If `input_override` is provided, it takes precedence over the `inputAsset` field in the
 /// test case JSON. If neither is present, an error is returned.
 pub fn handle_create_test(
     test_case_path: &Path,
     input_override: Option<&Path>,
     output: &Path,
-) -> Result<()> {
-    println!(
+    settings: &SignSettings,
+) -> Result<PathBuf> {
+    say!(
         "=== Creating test asset from test case: {:?} ===",
         test_case_path
     );
 
-    let json_str =
-        fs::read_to_string(test_case_path).context("Failed to read test case JSON file")?;
-    let test_case: TestCase = serde_json::from_str(&json_str)
-        .context("Failed to parse test case JSON (does it match the test case schema?)")?;
+    let test_case = load_test_case(test_case_path)?;
 
     // All paths in the test case are resolved relative to the test case file's directory
     let base_dir = test_case_path
@@ -71,53 +71,115 @@ pub fn handle_create_test(
             no input file was provided on the command line."
         )
     };
-    let cert = base_dir.join(&test_case.signing_cert);
-    let key = base_dir.join(
+
+    let output_path = sign_test_case(&test_case, &base_dir, &input_asset, output, settings)?;
+
+    say!("\n✓ Test asset created successfully");
+    say!("  Output: {:?}", output);
+    Ok(output_path)
+}
+
+/// Read and parse a test case JSON file.
+pub fn load_test_case(test_case_path: &Path) -> Result<TestCase> {
+    let json_str =
+        fs::read_to_string(test_case_path).context("Failed to read test case JSON file")?;
+    serde_json::from_str(&json_str)
+        .context("Failed to parse test case JSON (does it match the test case schema?)")
+}
+
+/// The key `test_case` is signed with: `--key` overrides the test case's key, which is resolved
+/// against `base_dir` unless it is a PKCS#11 URI.
+fn signing_key(test_case: &TestCase, base_dir: &Path, settings: &SignSettings) -> PathBuf {
+    if let Some(key) = &settings.key {
+        return key.clone();
+    }
+    let key = Path::new(
         test_case
             .signing_key
             .as_deref()
             .unwrap_or(&test_case.signing_cert),
     );
+    if crtool::signer::is_pkcs11_uri(key) {
+        key.to_path_buf()
+    } else {
+        base_dir.join(key)
+    }
+}
+
+/// Whether the test case at `test_case_path` may be signed on several threads at once, i.e. it
+/// is not signed with a PKCS#11 or keychain key (see [`crtool::signer::supports_parallel_signing`]).
+pub fn supports_parallel_signing(test_case_path: &Path, settings: &SignSettings) -> Result<bool> {
+    let test_case = load_test_case(test_case_path)?;
+    let base_dir = test_case_path.parent().unwrap_or(Path::new("."));
+    // Only the key, keychain and remote signer choose the backend
+    let mut options = crtool::SignOptions::new(
+        "{}",
+        base_dir.join(&test_case.signing_cert),
+        signing_key(&test_case, base_dir, settings),
+        crtool::SigningAlg::Es256,
+    );
+    options.keychain = settings.keychain.clone();
+    options.remote_signer = settings.remote_signer.clone();
+    Ok(crtool::signer::supports_parallel_signing(&options))
+}
+
+/// Sign `input_asset` with the test case's manifest and credentials, resolving the cert, key,
+/// and file ingredients against `base_dir`. Returns the path of the signed output file.
+pub fn sign_test_case(
+    test_case: &TestCase,
+    base_dir: &Path,
+    input_asset: &Path,
+    output: &Path,
+    settings: &SignSettings,
+) -> Result<PathBuf> {
+    let cert = base_dir.join(&test_case.signing_cert);
+    let key = signing_key(test_case, base_dir, settings);
+
+    // Placeholders ({{filename}}, {{uuid}}, ...) give each signed asset its own values
+    let mut manifest = test_case.manifest.clone();
+    let template = crtool::template::TemplateContext::for_input(input_asset);
+    crtool::template::expand_placeholders(&mut manifest, &template)
+        .context("Failed to expand manifest placeholders")?;
+
+    // A pinned label / vendor prefix is passed through to the SDK, so reject malformed ones here
+    crtool::labels::validate_manifest_naming(&manifest)
+        .context("Invalid manifest label or vendor prefix in test case")?;
 
     // Serialize the manifest object back to JSON string for the builder
-    let manifest_json = serde_json::to_string(&test_case.manifest)
-        .context("Failed to serialize manifest from test case")?;
+    let manifest_json =
+        serde_json::to_string(&manifest).context("Failed to serialize manifest from test case")?;
 
     // Determine signing algorithm from manifest.alg, or auto-detect from certificate
-    let signing_alg = if let Some(alg_str) = test_case.manifest.get("alg").and_then(|v| v.as_str())
-    {
+    let signing_alg = if let Some(alg_str) = manifest.get("alg").and_then(|v| v.as_str()) {
         parse_signing_algorithm(alg_str)?
     } else {
-        println!("No alg in manifest — auto-detecting signing algorithm from certificate...");
+        say!("No alg in manifest — auto-detecting signing algorithm from certificate...");
         let detected = detect_signing_algorithm(&cert)?;
-        println!("  Detected: {:?}", detected);
+        say!("  Detected: {:?}", detected);
         detected
     };
 
-    println!("  Test ID:   {}", test_case.test_id);
+    say!("  Test ID:   {}", test_case.test_id);
     if let Some(title) = &test_case.title {
-        println!("  Title:     {}", title);
+        say!("  Title:     {}", title);
     }
-    println!("  Input:     {:?}", input_asset);
-    println!("  Cert:      {:?}", cert);
-    println!("  Algorithm: {:?}", signing_alg);
+    say!("  Input:     {:?}", input_asset);
+    say!("  Cert:      {:?}", cert);
+    say!("  Algorithm: {:?}", signing_alg);
     if let Some(tsa) = &test_case.tsa_url {
-        println!("  TSA URL:   {}", tsa);
+        say!("  TSA URL:   {}", tsa);
     }
 
     let config = ProcessingConfig {
         manifest_json: &manifest_json,
-        ingredients_base_dir: &base_dir,
+        ingredients_base_dir: base_dir,
         cert: &cert,
         key: &key,
         signing_alg,
         tsa_url: test_case.tsa_url.clone(),
         allow_self_signed: true, // test certs are typically self-signed
+        settings,
     };
 
-    process_single_file(&input_asset, output, &config)?;
-
-    println!("\n✓ Test asset created successfully");
-    println!("  Output: {:?}", output);
-    Ok(())
+    process_single_file(input_asset, output, &config)
 }
@@ -0,0 +1,138 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool profile evaluate`: JPEG Trust Profile evaluation of extracted crJSON, with a trust
+//! report per input (see [`crtool::profiles`]).
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use crtool::profiles::{
+    evaluate_indicators, load_trust_profile, ConditionResult, Operator, TrustReport,
+};
+use crtool::trust_manifest::ClauseStatus;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Log a condition and the conditions below it, indented by depth.
+fn log_condition(logger: &mut Logger, condition: &ConditionResult, depth: usize) {
+    let mark = match condition.status {
+        ClauseStatus::Pass => "✓",
+        ClauseStatus::Fail => "✗",
+        ClauseStatus::NotApplicable => "–",
+    };
+    let what = match (condition.operator, &condition.indicator) {
+        (Operator::Test, Some(indicator)) => format!("{} ({})", condition.id, indicator),
+        (Operator::All, _) => format!("{} (all of)", condition.id),
+        (Operator::Any, _) => format!("{} (any of)", condition.id),
+        _ => format!("{} (not)", condition.id),
+    };
+    let indent = "  ".repeat(depth + 1);
+    match &condition.message {
+        Some(message) => logger.info(&format!("{indent}{mark} {what}: {message}")),
+        None => logger.info(&format!("{indent}{mark} {what}")),
+    }
+    for child in &condition.conditions {
+        log_condition(logger, child, depth + 1);
+    }
+}
+
+/// Write `report` as `<stem>-trust-report.json` in `dir`.
+fn write_report(report: &TrustReport, stem: &str, dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(dir).context("Failed to create output directory")?;
+    let path = dir.join(format!("{}-trust-report.json", stem));
+    let json = serde_json::to_string_pretty(report).context("Failed to format trust report")?;
+    fs::write(&path, json).context("Failed to write trust report")?;
+    Ok(path)
+}
+
+/// Evaluate the Trust Profile at `profile_path` against every indicators file. Prints each
+/// report (as a JSON array of reports with `json`), writes them to `output` when given, and
+/// fails when an input does not satisfy the profile or cannot be read.
+pub fn evaluate_files(
+    profile_path: &Path,
+    indicators: &[PathBuf],
+    output: Option<&Path>,
+    json: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    let profile = load_trust_profile(profile_path)?;
+    logger.info(&format!(
+        "=== Trust Profile: {} ===",
+        profile
+            .name
+            .as_deref()
+            .unwrap_or(&profile_path.to_string_lossy())
+    ));
+
+    let mut reports = Vec::new();
+    let mut error_count = 0u32;
+    for path in indicators {
+        logger.info(&format!("  📄 {}", path.display()));
+        let value = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))
+            .and_then(|text| {
+                crtool::limits::parse_json(&text)
+                    .with_context(|| format!("Invalid JSON in {}", path.display()))
+            });
+        let value = match value {
+            Ok(value) => value,
+            Err(e) => {
+                logger.error(&format!("     ❌ Error: {e:#}"));
+                error_count += 1;
+                continue;
+            }
+        };
+        let report = evaluate_indicators(&profile, &value, &path.to_string_lossy());
+        log_condition(logger, &report.result, 1);
+        logger.info(&format!(
+            "     {} ({} passed, {} failed, {} not applicable)",
+            if report.satisfied {
+                "✅ Satisfied"
+            } else {
+                "❌ Not satisfied"
+            },
+            report.passed,
+            report.failed,
+            report.not_applicable
+        ));
+        if let Some(dir) = output {
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .context("Indicators path has no filename")?;
+            let written = write_report(&report, stem.trim_end_matches("_cr"), dir)?;
+            logger.info(&format!("     Report: {}", written.display()));
+        }
+        reports.push(report);
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&reports).context("Failed to format trust reports")?
+        );
+    }
+
+    let unsatisfied = reports.iter().filter(|r| !r.satisfied).count();
+    logger.info(&format!(
+        "\n📊 Trust Profile Summary: {} satisfied, {unsatisfied} not satisfied, {error_count} \
+        error(s)",
+        reports.len() - unsatisfied
+    ));
+    if error_count > 0 {
+        anyhow::bail!("{error_count} indicators file(s) could not be read");
+    }
+    if unsatisfied > 0 {
+        anyhow::bail!("{unsatisfied} input(s) do not satisfy the trust profile");
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool self-check-update`: look for a newer crTool release in the signed releases feed.
+
+use crate::extraction::fetch_url;
+use crate::Logger;
+use anyhow::{Context, Result};
+use crtool::update::{check_feed, UpdateFeedConfig, UpdateStatus};
+
+/// Fetch and verify the releases feed, then report whether an update is available.
+pub fn self_check_update(offline: bool, json: bool, logger: &mut Logger) -> Result<()> {
+    if offline {
+        anyhow::bail!("Update check skipped: --offline (or CRTOOL_OFFLINE) is set");
+    }
+    let config = UpdateFeedConfig::from_env()?;
+
+    logger.info(&format!("Checking for updates at {} ...", config.url));
+    let feed = fetch_url(&config.url).context("Failed to fetch the releases feed")?;
+    let signature =
+        fetch_url(&config.signature_url()).context("Failed to fetch the feed signature")?;
+    let status = check_feed(
+        env!("CARGO_PKG_VERSION"),
+        feed.as_bytes(),
+        &signature,
+        &config.public_key,
+    )?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&status).context("Failed to format update status")?
+        );
+        return Ok(());
+    }
+    match &status {
+        UpdateStatus::UpToDate { current } => {
+            logger.info(&format!("✅ crTool {current} is up to date"));
+        }
+        UpdateStatus::Available { current, latest } => {
+            logger.info(&format!(
+                "⬆️  crTool {} is available (you have {current})",
+                latest.version
+            ));
+            logger.info(&format!("   {}", latest.url));
+            if let Some(notes) = &latest.notes {
+                logger.info(&format!("   {notes}"));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,141 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `--verify`: full validation of each asset's manifest store (claim signatures, hash bindings,
+//! timestamps, and signer trust) with a per-manifest report of validation codes.
+
+use crate::Logger;
+use anyhow::{Context, Result};
+use c2pa::Settings;
+use crtool::verify::VerificationReport;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Per-file entry of the `--verify` report.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedFile {
+    pub input: String,
+    pub passed: bool,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub report: Option<VerificationReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Machine-readable report of a `--verify` run.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyRunReport {
+    pub mode: &'static str,
+    /// Whether an untrusted signer fails verification (a trust list was given).
+    pub trust_required: bool,
+    pub passed: u32,
+    pub failed: u32,
+    pub files: Vec<VerifiedFile>,
+}
+
+/// Verify every input. An asset passes when no manifest reports a validation failure and, when
+/// `require_trusted` is set, its signer is not untrusted. The report is written to `output`
+/// when given and printed to stdout with `json`.
+pub fn verify_assets(
+    input_files: &[PathBuf],
+    settings: &Settings,
+    require_trusted: bool,
+    output: Option<&Path>,
+    json: bool,
+    logger: &mut Logger,
+) -> Result<()> {
+    logger.info("=== Verification ===");
+    let mut files = Vec::new();
+    for input_file in input_files {
+        logger.info(&format!("  📄 Verifying: {} ...", input_file.display()));
+        let input = input_file.display().to_string();
+        let file = match crtool::extract_crjson_manifest_with_settings(input_file, settings) {
+            Ok(extraction) => {
+                let report = VerificationReport::from_extraction(&extraction);
+                let passed = report.passed(require_trusted);
+                let failures = report.failures();
+                if passed {
+                    logger.info_trust(
+                        &format!(
+                            "     ✅ Verified ({} manifest(s), trust: {{trust}})",
+                            report.manifests.len()
+                        ),
+                        &report.trust,
+                    );
+                } else {
+                    logger.error_trust(
+                        &format!(
+                            "     ❌ Verification failed for {} (trust: {{trust}})",
+                            input_file.display()
+                        ),
+                        &report.trust,
+                    );
+                    for failure in &failures {
+                        logger.error(&format!("        • {failure}"));
+                    }
+                }
+                VerifiedFile {
+                    input,
+                    passed,
+                    report: Some(report),
+                    error: None,
+                }
+            }
+            Err(e) => {
+                logger.error(&format!("     ❌ Error: {e:#}"));
+                VerifiedFile {
+                    input,
+                    passed: false,
+                    report: None,
+                    error: Some(format!("{e:#}")),
+                }
+            }
+        };
+        files.push(file);
+    }
+
+    let passed = files.iter().filter(|f| f.passed).count() as u32;
+    let report = VerifyRunReport {
+        mode: "verify",
+        trust_required: require_trusted,
+        passed,
+        failed: files.len() as u32 - passed,
+        files,
+    };
+    logger.info(&format!(
+        "\n📊 Verification Summary: {} passed, {} failed, {} total",
+        report.passed,
+        report.failed,
+        input_files.len()
+    ));
+
+    let report_json =
+        serde_json::to_string_pretty(&report).context("Failed to format verification report")?;
+    if let Some(path) = output {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).context("Failed to create report directory")?;
+        }
+        fs::write(path, &report_json).context("Failed to write verification report")?;
+        logger.info(&format!("   📝 Report: {}", path.display()));
+    }
+    if json {
+        println!("{}", report_json);
+    }
+
+    if report.failed > 0 {
+        anyhow::bail!("{} file(s) failed verification", report.failed);
+    }
+    Ok(())
+}
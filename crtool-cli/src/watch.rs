@@ -0,0 +1,291 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `crTool watch`: sign and verify assets as they arrive in a hot folder, for DAM-ingest style
+//! workflows. Every processed file is appended to a JSON-lines log, so a restarted watcher
+//! skips files it already signed.
+
+use crate::processing::SignSettings;
+use crate::test_case::handle_create_test;
+use crate::{Logger, WatchArgs};
+use anyhow::{Context, Result};
+use crtool::hashing::{hash_file, HashAlgorithm};
+use crtool::verify::VerificationReport;
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// File name of the processed-files log in the output directory.
+pub const DEFAULT_LOG_NAME: &str = ".crtool-watch.jsonl";
+
+/// Suffixes of files still being written by browsers and copy tools.
+const PARTIAL_SUFFIXES: &[&str] = &[".tmp", ".part", ".partial", ".crdownload", ".download"];
+
+/// One line of the processed-files log.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessedEntry {
+    pub timestamp: String,
+    pub input: String,
+    pub input_sha256: String,
+    /// `signed` or `failed`.
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Whether the signed output verified when read back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The processed-files log and the `(input, SHA-256)` pairs it records as signed.
+struct ProcessedLog {
+    path: PathBuf,
+    signed: HashSet<(String, String)>,
+}
+
+impl ProcessedLog {
+    fn open(path: PathBuf) -> Result<Self> {
+        let mut signed = HashSet::new();
+        if path.exists() {
+            let text = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read watch log {:?}", path))?;
+            for (i, line) in text
+                .lines()
+                .enumerate()
+                .filter(|(_, l)| !l.trim().is_empty())
+            {
+                let entry: ProcessedEntry = serde_json::from_str(line)
+                    .with_context(|| format!("Invalid line {} in watch log {:?}", i + 1, path))?;
+                if entry.status == "signed" {
+                    signed.insert((entry.input, entry.input_sha256));
+                }
+            }
+        }
+        Ok(Self { path, signed })
+    }
+
+    fn append(&mut self, entry: ProcessedEntry) -> Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open watch log {:?}", self.path))?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)
+            .with_context(|| format!("Failed to write watch log {:?}", self.path))?;
+        if entry.status == "signed" {
+            self.signed.insert((entry.input, entry.input_sha256));
+        }
+        Ok(())
+    }
+}
+
+/// Whether `path` is an asset to sign rather than a hidden or partially written file.
+fn is_candidate(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    !name.starts_with('.')
+        && !PARTIAL_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+        && crtool::asset_format_for_path(path).is_some()
+        && path.is_file()
+}
+
+/// Read a signed output back and report whether it verifies.
+fn verify_output(output: &Path, logger: &mut Logger) -> bool {
+    match crtool::extract_crjson_manifest(output) {
+        Ok(result) => {
+            let report = VerificationReport::from_extraction(&result);
+            if report.passed(false) {
+                logger.info("     ✅ Verified");
+            }
+            for failure in report.failures() {
+                logger.error(&format!("     ❌ {failure}"));
+            }
+            report.passed(false)
+        }
+        Err(e) => {
+            logger.error(&format!(
+                "     ❌ Could not read back {}: {e:#}",
+                output.display()
+            ));
+            false
+        }
+    }
+}
+
+struct HotFolder<'a> {
+    input_dir: PathBuf,
+    output_dir: PathBuf,
+    args: &'a WatchArgs,
+    settings: &'a SignSettings,
+    log: ProcessedLog,
+    /// Content already handled in this run, including failures, so an unchanged failing file
+    /// is not retried on every event.
+    seen: HashSet<(String, String)>,
+    signed: usize,
+    failed: usize,
+}
+
+impl HotFolder<'_> {
+    fn process(
+        &mut self,
+        input: &Path,
+        logger: &mut Logger,
+        on_signed: &mut impl FnMut(&Path, &mut Logger),
+    ) -> Result<()> {
+        // Event paths are not always canonical (e.g. /var vs /private/var on macOS).
+        let input = &fs::canonicalize(input).unwrap_or_else(|_| input.to_path_buf());
+        let sha256 = hash_file(input, HashAlgorithm::Sha256)?;
+        let key = (input.display().to_string(), sha256.clone());
+        if self.log.signed.contains(&key) || !self.seen.insert(key) {
+            return Ok(());
+        }
+        let relative = match input.strip_prefix(&self.input_dir) {
+            Ok(relative) => relative,
+            Err(_) => Path::new(input.file_name().context("Input has no file name")?),
+        };
+        let output = self.output_dir.join(relative);
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent).context("Failed to create output directory")?;
+        }
+
+        logger.info(&format!("  📥 {} → {}", input.display(), output.display()));
+        let mut entry = ProcessedEntry {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            input: input.display().to_string(),
+            input_sha256: sha256,
+            status: "signed".to_string(),
+            output: None,
+            verified: None,
+            error: None,
+        };
+        match handle_create_test(&self.args.test_case, Some(input), &output, self.settings) {
+            Ok(output_path) => {
+                entry.verified = Some(verify_output(&output_path, logger));
+                entry.output = Some(output_path.display().to_string());
+                self.signed += 1;
+                on_signed(&output_path, logger);
+            }
+            Err(e) => {
+                logger.error(&format!("     ❌ Error: {e:#}"));
+                entry.status = "failed".to_string();
+                entry.error = Some(format!("{e:#}"));
+                self.failed += 1;
+            }
+        }
+        self.log.append(entry)
+    }
+}
+
+/// Sign the assets already in the input directory, then (unless `--once`) every asset that
+/// arrives there, once it has not changed for the debounce interval. Calls `on_signed` for each
+/// signed output. Runs until interrupted; with `--once`, fails when any file failed.
+pub fn run_watch(
+    args: &WatchArgs,
+    settings: &SignSettings,
+    logger: &mut Logger,
+    mut on_signed: impl FnMut(&Path, &mut Logger),
+) -> Result<()> {
+    let input_dir = fs::canonicalize(&args.input_dir)
+        .with_context(|| format!("Input directory {:?} does not exist", args.input_dir))?;
+    fs::create_dir_all(&args.output_dir).context("Failed to create output directory")?;
+    let output_dir = fs::canonicalize(&args.output_dir)?;
+    if output_dir.starts_with(&input_dir) {
+        anyhow::bail!("The output directory must not be inside the watched input directory");
+    }
+    let log_path = args
+        .watch_log
+        .clone()
+        .unwrap_or_else(|| output_dir.join(DEFAULT_LOG_NAME));
+    let mut folder = HotFolder {
+        input_dir: input_dir.clone(),
+        output_dir,
+        args,
+        settings,
+        log: ProcessedLog::open(log_path)?,
+        seen: HashSet::new(),
+        signed: 0,
+        failed: 0,
+    };
+
+    // Start watching before the initial scan so files arriving meanwhile are not missed.
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to start file watcher")?;
+    if !args.once {
+        watcher
+            .watch(&input_dir, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {:?}", input_dir))?;
+    }
+
+    let existing = crtool::walk_assets(&input_dir, |path, _| is_candidate(path))?;
+    logger.info(&format!(
+        "👀 {} asset(s) in {}",
+        existing.len(),
+        input_dir.display()
+    ));
+    for path in existing {
+        folder.process(&path, logger, &mut on_signed)?;
+    }
+    if args.once {
+        logger.info(&format!(
+            "\n📊 Watch: {} signed, {} failed",
+            folder.signed, folder.failed
+        ));
+        if folder.failed > 0 {
+            anyhow::bail!("{} file(s) failed to sign", folder.failed);
+        }
+        return Ok(());
+    }
+
+    logger.info(&format!(
+        "👀 Watching {} (Ctrl-C to stop)",
+        input_dir.display()
+    ));
+    let debounce = Duration::from_millis(args.debounce_ms);
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+    loop {
+        match rx.recv_timeout(Duration::from_millis(250)) {
+            Ok(Ok(event)) => {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    for path in event.paths {
+                        pending.insert(path, Instant::now());
+                    }
+                }
+            }
+            Ok(Err(e)) => logger.error(&format!("⚠️  File watcher: {e}")),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => anyhow::bail!("The file watcher stopped"),
+        }
+
+        let ready: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, changed)| changed.elapsed() >= debounce)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in ready {
+            pending.remove(&path);
+            if is_candidate(&path) {
+                // A file that cannot be read (e.g. removed meanwhile) must not stop the watcher.
+                if let Err(e) = folder.process(&path, logger, &mut on_signed) {
+                    logger.error(&format!("  ❌ {}: {e:#}", path.display()));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+/*
+ * C API of crtool: extract C2PA manifests as crJSON and validate crJSON documents.
+ *
+ * Every function returning a CrtoolResult allocates it; release it with crtool_free_result.
+ * Strings are UTF-8 and NUL-terminated. Outputs are JSON text.
+ */
+
+#ifndef CRTOOL_H
+#define CRTOOL_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Status codes of CrtoolResult.status. */
+#define CRTOOL_OK 0
+#define CRTOOL_ERROR_INVALID_ARGUMENT 1 /* null, non-UTF-8 or (for validation) non-JSON argument */
+#define CRTOOL_ERROR_NO_MANIFEST 2      /* the asset carries no C2PA manifest */
+#define CRTOOL_ERROR_LIMIT_EXCEEDED 3   /* manifest store or JSON over a resource limit */
+#define CRTOOL_ERROR_FAILED 4           /* any other failure */
+#define CRTOOL_ERROR_PANIC 5            /* internal error; the message is in `error` */
+
+/* Fields are never reordered or removed. */
+typedef struct CrtoolResult {
+    int32_t status; /* CRTOOL_OK or a CRTOOL_ERROR_* code */
+    char *json;     /* JSON output when status is CRTOOL_OK, else NULL */
+    char *error;    /* error message otherwise, else NULL */
+} CrtoolResult;
+
+/*
+ * Extract the manifest store of the asset at `path` as crJSON. `trust_config` is the path of a
+ * trust configuration (JSON or TOML) or NULL. Output:
+ * {"activeLabel": "...", "assetSha256": "...", "crjson": {...}}
+ */
+CrtoolResult *crtool_extract_path(const char *path, const char *trust_config);
+
+/*
+ * Validate crJSON text against the bundled crJSON schema, or the schema file at `schema_path`
+ * when it is not NULL. A document that fails the schema still returns CRTOOL_OK. Output:
+ * {"valid": false, "errors": [{"instancePath": "...", "message": "...", "severity": "error",
+ * "rule": "required"}]}. Only findings of severity "error" make the document invalid.
+ */
+CrtoolResult *crtool_validate_json(const char *json, const char *schema_path);
+
+/* Release a result and its strings. NULL is ignored. */
+void crtool_free_result(CrtoolResult *result);
+
+/* Library version, e.g. "0.3.0". Static; do not free. */
+const char *crtool_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CRTOOL_H */
@@ -0,0 +1,332 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! C API for linking crtool's extraction and validation into desktop applications (C, C++,
+//! Swift) instead of spawning the CLI. The declarations are in `include/crtool.h`.
+//!
+//! Every call returns a heap-allocated [`CrtoolResult`] that the caller releases with
+//! [`crtool_free_result`]. Strings are UTF-8 and NUL-terminated; outputs are JSON. Panics are
+//! caught and reported as [`CRTOOL_ERROR_PANIC`] rather than unwinding into the caller.
+
+use crtool::SchemaValidator;
+use serde::Serialize;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+use std::sync::OnceLock;
+
+/// The call succeeded; `json` holds its output.
+pub const CRTOOL_OK: i32 = 0;
+/// A required argument was null, or an argument was not UTF-8 (or not JSON, for validation).
+pub const CRTOOL_ERROR_INVALID_ARGUMENT: i32 = 1;
+/// The asset carries no C2PA manifest.
+pub const CRTOOL_ERROR_NO_MANIFEST: i32 = 2;
+/// The manifest store or JSON document is over a resource limit.
+pub const CRTOOL_ERROR_LIMIT_EXCEEDED: i32 = 3;
+/// Any other failure, e.g. an unreadable file or a manifest that could not be decoded.
+pub const CRTOOL_ERROR_FAILED: i32 = 4;
+/// crtool panicked; the message is in `error`.
+pub const CRTOOL_ERROR_PANIC: i32 = 5;
+
+/// Outcome of a call. The layout is part of the ABI: fields are never reordered or removed.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CrtoolResult {
+    /// [`CRTOOL_OK`] or one of the `CRTOOL_ERROR_*` codes.
+    pub status: i32,
+    /// JSON output when `status` is [`CRTOOL_OK`], else null.
+    pub json: *mut c_char,
+    /// Error message when `status` is not [`CRTOOL_OK`], else null.
+    pub error: *mut c_char,
+}
+
+struct Failure {
+    status: i32,
+    message: String,
+}
+
+impl Failure {
+    fn invalid_argument(message: impl Into<String>) -> Self {
+        Self {
+            status: CRTOOL_ERROR_INVALID_ARGUMENT,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<crtool::CrtoolError> for Failure {
+    fn from(error: crtool::CrtoolError) -> Self {
+        let status = if error.is_no_manifest() {
+            CRTOOL_ERROR_NO_MANIFEST
+        } else if error.limit_exceeded().is_some() {
+            CRTOOL_ERROR_LIMIT_EXCEEDED
+        } else {
+            CRTOOL_ERROR_FAILED
+        };
+        Self {
+            status,
+            message: format!("{:#}", error),
+        }
+    }
+}
+
+fn c_string(text: String) -> *mut c_char {
+    CString::new(text.replace('\0', ""))
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Run `call`, catching panics, and box its outcome for the caller.
+fn into_result(call: impl FnOnce() -> Result<String, Failure>) -> *mut CrtoolResult {
+    let outcome = catch_unwind(AssertUnwindSafe(call)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(Failure {
+            status: CRTOOL_ERROR_PANIC,
+            message: format!("crtool panicked: {}", message),
+        })
+    });
+    let result = match outcome {
+        Ok(json) => CrtoolResult {
+            status: CRTOOL_OK,
+            json: c_string(json),
+            error: ptr::null_mut(),
+        },
+        Err(failure) => CrtoolResult {
+            status: failure.status,
+            json: ptr::null_mut(),
+            error: c_string(failure.message),
+        },
+    };
+    Box::into_raw(Box::new(result))
+}
+
+/// The string behind `arg`, or `None` when it is null.
+///
+/// # Safety
+///
+/// `arg` must be null or point to a NUL-terminated string that outlives the call.
+unsafe fn str_arg<'a>(arg: *const c_char, name: &str) -> Result<Option<&'a str>, Failure> {
+    if arg.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(arg)
+        .to_str()
+        .map(Some)
+        .map_err(|_| Failure::invalid_argument(format!("{} is not UTF-8", name)))
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, Failure> {
+    serde_json::to_string(value).map_err(|e| Failure::from(crtool::CrtoolError::from(e)))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExtractOutput {
+    active_label: String,
+    asset_sha256: Option<String>,
+    crjson: serde_json::Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaError {
+    instance_path: String,
+    message: String,
+    severity: crtool::Severity,
+    rule: String,
+}
+
+#[derive(Serialize)]
+struct SchemaValidation {
+    valid: bool,
+    errors: Vec<SchemaError>,
+}
+
+/// Extract the C2PA manifest store of the asset at `path` as crJSON. `trust_config` is the path
+/// of a trust configuration (JSON or TOML, as saved by the GUI) or null; without one, signing
+/// credentials report as untrusted. On success, `json` is
+/// `{"activeLabel": ..., "assetSha256": ..., "crjson": {...}}`.
+///
+/// # Safety
+///
+/// `path` and `trust_config` must be null or NUL-terminated strings valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn crtool_extract_path(
+    path: *const c_char,
+    trust_config: *const c_char,
+) -> *mut CrtoolResult {
+    into_result(|| {
+        let path = str_arg(path, "path")?
+            .ok_or_else(|| Failure::invalid_argument("path must not be null"))?;
+        let settings = match str_arg(trust_config, "trust_config")? {
+            Some(config) => crtool::trust::TrustConfig::load(Path::new(config))?.settings(None)?,
+            None => crtool::default_extraction_settings(),
+        };
+        let result = crtool::extract_crjson_manifest_hashed(path, &settings)?;
+        to_json(&ExtractOutput {
+            active_label: result.active_label,
+            asset_sha256: result.asset_hash,
+            crjson: result.manifest_value,
+        })
+    })
+}
+
+/// Validate a crJSON document (`json`, JSON text) against the crJSON schema compiled into the
+/// library, or the schema file at `schema_path` when it is not null. On success, `json` is
+/// `{"valid": ..., "errors": [{"instancePath": ..., "message": ...}]}`; a document that fails
+/// the schema is still [`CRTOOL_OK`].
+///
+/// # Safety
+///
+/// `json` and `schema_path` must be null or NUL-terminated strings valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn crtool_validate_json(
+    json: *const c_char,
+    schema_path: *const c_char,
+) -> *mut CrtoolResult {
+    into_result(|| {
+        let text = str_arg(json, "json")?
+            .ok_or_else(|| Failure::invalid_argument("json must not be null"))?;
+        let value = crtool::limits::parse_json(text).map_err(|e| {
+            let failure = Failure::from(e);
+            if failure.status == CRTOOL_ERROR_FAILED {
+                Failure::invalid_argument(format!("Invalid JSON: {}", failure.message))
+            } else {
+                failure
+            }
+        })?;
+        let result = match str_arg(schema_path, "schema_path")? {
+            Some(schema) => SchemaValidator::from_path(schema)?.validate(&value),
+            None => {
+                // The bundled schema is compiled on first use and shared by later calls.
+                static BUNDLED: OnceLock<SchemaValidator> = OnceLock::new();
+                let bundled = match BUNDLED.get() {
+                    Some(validator) => validator,
+                    None => {
+                        let validator = SchemaValidator::from_bundled()?;
+                        BUNDLED.get_or_init(|| validator)
+                    }
+                };
+                bundled.validate(&value)
+            }
+        };
+        to_json(&SchemaValidation {
+            valid: result.is_valid,
+            errors: result
+                .errors
+                .into_iter()
+                .map(|e| SchemaError {
+                    instance_path: e.instance_path,
+                    message: e.message,
+                    severity: e.severity,
+                    rule: e.rule,
+                })
+                .collect(),
+        })
+    })
+}
+
+/// Release a result returned by this library, including its strings. Null is ignored.
+///
+/// # Safety
+///
+/// `result` must be null or a result from this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn crtool_free_result(result: *mut CrtoolResult) {
+    if result.is_null() {
+        return;
+    }
+    let result = Box::from_raw(result);
+    for text in [result.json, result.error] {
+        if !text.is_null() {
+            drop(CString::from_raw(text));
+        }
+    }
+}
+
+/// Version of the library, e.g. `"0.3.0"`. The string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn crtool_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Status, JSON output and error of `result`, which is freed.
+    fn take(result: *mut CrtoolResult) -> (i32, Option<String>, Option<String>) {
+        let text = |ptr: *mut c_char| {
+            (!ptr.is_null()).then(|| {
+                unsafe { CStr::from_ptr(ptr) }
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        };
+        let (status, json, error) = unsafe { ((*result).status, (*result).json, (*result).error) };
+        let taken = (status, text(json), text(error));
+        unsafe { crtool_free_result(result) };
+        taken
+    }
+
+    #[test]
+    fn test_validate_json() {
+        let json = CString::new("{}").unwrap();
+        let (status, output, error) =
+            take(unsafe { crtool_validate_json(json.as_ptr(), ptr::null()) });
+        assert_eq!(status, CRTOOL_OK);
+        assert!(error.is_none());
+        let output: serde_json::Value = serde_json::from_str(&output.unwrap()).unwrap();
+        assert_eq!(output["valid"], false);
+        assert!(!output["errors"].as_array().unwrap().is_empty());
+
+        let broken = CString::new("{").unwrap();
+        let (status, output, error) =
+            take(unsafe { crtool_validate_json(broken.as_ptr(), ptr::null()) });
+        assert_eq!(status, CRTOOL_ERROR_INVALID_ARGUMENT);
+        assert!(output.is_none());
+        assert!(error.unwrap().starts_with("Invalid JSON"));
+
+        let (status, _, _) = take(unsafe { crtool_validate_json(ptr::null(), ptr::null()) });
+        assert_eq!(status, CRTOOL_ERROR_INVALID_ARGUMENT);
+    }
+
+    #[test]
+    fn test_extract_path() {
+        let (status, _, _) = take(unsafe { crtool_extract_path(ptr::null(), ptr::null()) });
+        assert_eq!(status, CRTOOL_ERROR_INVALID_ARGUMENT);
+
+        let unsigned = CString::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../tests/fixtures/assets/Dog.jpg"
+        ))
+        .unwrap();
+        let (status, output, error) =
+            take(unsafe { crtool_extract_path(unsigned.as_ptr(), ptr::null()) });
+        assert_eq!(status, CRTOOL_ERROR_NO_MANIFEST);
+        assert!(output.is_none());
+        assert!(error.is_some());
+
+        let missing = CString::new("/nonexistent/crtool-ffi.jpg").unwrap();
+        let (status, _, _) = take(unsafe { crtool_extract_path(missing.as_ptr(), ptr::null()) });
+        assert_eq!(status, CRTOOL_ERROR_FAILED);
+
+        unsafe { crtool_free_result(ptr::null_mut()) };
+        let version = unsafe { CStr::from_ptr(crtool_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
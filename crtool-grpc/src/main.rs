@@ -0,0 +1,444 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! gRPC service exposing crTool's Extract, Validate, Verify, and Sign operations
+//! (`proto/crtool.proto`), for pipelines that standardize on gRPC.
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use crtool::formats::AssetFormat;
+use crtool::trust::TrustConfig;
+use crtool::{SchemaValidator, Settings};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status, Streaming};
+
+mod pb {
+    tonic::include_proto!("crtool.v1");
+}
+
+use pb::crtool_server::{Crtool, CrtoolServer};
+use pb::{
+    asset_upload, sign_upload, AssetChunk, AssetInfo, AssetUpload, ExtractResponse, SignInfo,
+    SignUpload, ValidateRequest, ValidateResponse, VerifyResponse,
+};
+
+/// Size of the chunks the signed asset is streamed back in.
+const RESPONSE_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Address to listen on
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:50051")]
+    listen: SocketAddr,
+
+    /// Trust configuration JSON (`anchorFiles`, `allowedFiles`) used by Extract and Verify.
+    /// Only the listed PEM files are trusted; the published default lists are not fetched
+    #[arg(long = "trust-config", value_name = "FILE")]
+    trust_config: Option<PathBuf>,
+
+    /// Largest asset accepted by an upload, in MiB
+    #[arg(long = "max-asset-mb", value_name = "MIB", default_value_t = 512)]
+    max_asset_mb: usize,
+
+    /// Largest C2PA manifest store decoded from an asset, in MiB
+    #[arg(
+        long = "max-manifest-store-mb",
+        value_name = "MIB",
+        default_value_t = 128
+    )]
+    max_manifest_store_mb: u64,
+
+    /// Deepest JSON nesting accepted in crJSON documents
+    #[arg(long = "max-json-depth", value_name = "N", default_value_t = 128)]
+    max_json_depth: usize,
+
+    /// PEM certificate chain the Sign RPC signs with, signing certificate first. Without it,
+    /// Sign is refused
+    #[arg(long = "sign-cert", value_name = "FILE", requires = "sign_key")]
+    sign_cert: Option<PathBuf>,
+
+    /// Private key for --sign-cert: a PEM file or a `pkcs11:` URI
+    #[arg(long = "sign-key", value_name = "KEY", requires = "sign_cert")]
+    sign_key: Option<PathBuf>,
+
+    /// Signing algorithm (es256, es384, es512, ps256, ps384, ps512, ed25519). Detected from
+    /// --sign-cert when omitted
+    #[arg(long = "sign-alg", value_name = "ALG", requires = "sign_cert")]
+    sign_alg: Option<String>,
+
+    /// RFC 3161 time-stamping authority the Sign RPC countersigns with
+    #[arg(long = "tsa-url", value_name = "URL", requires = "sign_cert")]
+    tsa_url: Option<String>,
+}
+
+impl Cli {
+    /// Signing credentials for the Sign RPC, if --sign-cert and --sign-key were given. The
+    /// manifest definition of each request is filled in by [`sign`].
+    fn sign_options(&self) -> Result<Option<crtool::SignOptions>> {
+        let (Some(cert), Some(key)) = (&self.sign_cert, &self.sign_key) else {
+            return Ok(None);
+        };
+        let signing_alg = match &self.sign_alg {
+            Some(alg) => crtool::signing::parse_signing_algorithm(alg)?,
+            None => crtool::signing::detect_signing_algorithm(cert)?,
+        };
+        // Fail at start-up rather than on the first request
+        if !crtool::signer::is_pkcs11_uri(key) {
+            crtool::keys::check_key_pair(cert, key, signing_alg)?;
+        }
+        let mut options = crtool::SignOptions::new(String::new(), cert, key, signing_alg);
+        options.tsa_url = self.tsa_url.clone();
+        Ok(Some(options))
+    }
+}
+
+/// One message of an upload stream: the asset info (first) or a chunk of asset bytes.
+enum Part<I> {
+    Info(I),
+    Chunk(Vec<u8>),
+}
+
+trait Upload {
+    type Info;
+    fn into_part(self) -> Option<Part<Self::Info>>;
+}
+
+impl Upload for AssetUpload {
+    type Info = AssetInfo;
+    fn into_part(self) -> Option<Part<AssetInfo>> {
+        Some(match self.part? {
+            asset_upload::Part::Info(info) => Part::Info(info),
+            asset_upload::Part::Chunk(chunk) => Part::Chunk(chunk),
+        })
+    }
+}
+
+impl Upload for SignUpload {
+    type Info = SignInfo;
+    fn into_part(self) -> Option<Part<SignInfo>> {
+        Some(match self.part? {
+            sign_upload::Part::Info(info) => Part::Info(info),
+            sign_upload::Part::Chunk(chunk) => Part::Chunk(chunk),
+        })
+    }
+}
+
+/// Read an upload stream into memory, rejecting it as soon as it grows past `max_bytes`.
+async fn collect_upload<M: Upload>(
+    mut stream: Streaming<M>,
+    max_bytes: usize,
+) -> Result<(M::Info, Vec<u8>), Status> {
+    let info = match stream.message().await?.and_then(Upload::into_part) {
+        Some(Part::Info(info)) => info,
+        _ => {
+            return Err(Status::invalid_argument(
+                "the first message of an upload must carry the asset info",
+            ))
+        }
+    };
+    let mut data = Vec::new();
+    while let Some(message) = stream.message().await? {
+        match message.into_part() {
+            Some(Part::Chunk(chunk)) => {
+                if data.len() + chunk.len() > max_bytes {
+                    return Err(Status::resource_exhausted(format!(
+                        "asset is larger than the {} byte limit",
+                        max_bytes
+                    )));
+                }
+                data.extend_from_slice(&chunk);
+            }
+            _ => {
+                return Err(Status::invalid_argument(
+                    "the asset info may only be sent once, as the first message",
+                ))
+            }
+        }
+    }
+    Ok((info, data))
+}
+
+/// Look up a supported format by extension or MIME type.
+fn resolve_format(format: &str) -> Result<&'static AssetFormat, Status> {
+    crtool::asset_format_for_type(format)
+        .ok_or_else(|| Status::invalid_argument(format!("unsupported asset format '{}'", format)))
+}
+
+/// Run blocking SDK work off the async runtime, mapping errors to `status` (resource limit
+/// violations to `RESOURCE_EXHAUSTED`).
+async fn blocking<T, F>(status: fn(String) -> Status, f: F) -> Result<T, Status>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .map_err(|e| match crtool::limits::limit_exceeded(&*e) {
+            Some(limit) => Status::resource_exhausted(limit.to_string()),
+            None => status(format!("{:#}", e)),
+        })
+}
+
+fn extract(
+    data: &[u8],
+    format: &AssetFormat,
+    settings: &Settings,
+) -> Result<crtool::ManifestExtractionResult> {
+    crtool::extract_crjson_manifest_from_bytes(data, format.mime, settings)
+        .context("Failed to read C2PA data from the asset")
+}
+
+/// Sign `data` with the manifest definition of `info` and the server's credentials (`options`).
+fn sign(
+    info: SignInfo,
+    data: Vec<u8>,
+    format: &AssetFormat,
+    options: &crtool::SignOptions,
+) -> Result<Vec<u8>> {
+    // `file_path` ingredients would be read from the server's own disk
+    let manifest: serde_json::Value =
+        serde_json::from_str(&info.manifest_json).context("Invalid manifest definition")?;
+    let ingredients = manifest.get("ingredients").and_then(|v| v.as_array());
+    if ingredients.is_some_and(|list| list.iter().any(|i| i.get("file_path").is_some())) {
+        anyhow::bail!("Ingredients with a file_path cannot be signed over gRPC");
+    }
+    let options = crtool::SignOptions {
+        manifest_json: info.manifest_json,
+        ..options.clone()
+    };
+    let mut source = std::io::Cursor::new(data);
+    let mut dest = std::io::Cursor::new(Vec::new());
+    crtool::sign_stream(format.mime, &mut source, &mut dest, &options)?;
+    Ok(dest.into_inner())
+}
+
+struct CrtoolService {
+    settings: Arc<Settings>,
+    schema: Arc<SchemaValidator>,
+    max_asset_bytes: usize,
+    /// Credentials for Sign, from --sign-cert / --sign-key; Sign is refused without them.
+    signing: Option<Arc<Signing>>,
+}
+
+/// The server's signing credentials.
+struct Signing {
+    options: crtool::SignOptions,
+    /// Held while signing with a key that cannot be used from several threads at once (see
+    /// [`crtool::signer::supports_parallel_signing`]).
+    exclusive: Option<std::sync::Mutex<()>>,
+}
+
+impl Signing {
+    fn new(options: crtool::SignOptions) -> Self {
+        let exclusive =
+            (!crtool::signer::supports_parallel_signing(&options)).then(Default::default);
+        Self { options, exclusive }
+    }
+}
+
+#[tonic::async_trait]
+impl Crtool for CrtoolService {
+    async fn extract(
+        &self,
+        request: Request<Streaming<AssetUpload>>,
+    ) -> Result<Response<ExtractResponse>, Status> {
+        let (info, data) = collect_upload(request.into_inner(), self.max_asset_bytes).await?;
+        let format = resolve_format(&info.format)?;
+        let settings = self.settings.clone();
+        let result = blocking(Status::invalid_argument, move || {
+            extract(&data, format, &settings)
+        })
+        .await?;
+        Ok(Response::new(ExtractResponse {
+            active_label: result.active_label,
+            crjson: result.manifest_json,
+        }))
+    }
+
+    async fn validate(
+        &self,
+        request: Request<ValidateRequest>,
+    ) -> Result<Response<ValidateResponse>, Status> {
+        let value = crtool::limits::parse_json(&request.into_inner().crjson).map_err(|e| {
+            match crtool::limits::limit_exceeded(&e) {
+                Some(limit) => Status::resource_exhausted(limit.to_string()),
+                None => Status::invalid_argument(format!("invalid JSON: {:#}", e)),
+            }
+        })?;
+        let schema = Arc::clone(&self.schema);
+        let result = blocking(Status::internal, move || Ok(schema.validate(&value))).await?;
+        Ok(Response::new(ValidateResponse {
+            valid: result.is_valid,
+            errors: result
+                .errors
+                .into_iter()
+                .map(|e| pb::ValidationError {
+                    instance_path: e.instance_path,
+                    message: e.message,
+                    severity: e.severity.to_string(),
+                    rule: e.rule,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn verify(
+        &self,
+        request: Request<Streaming<AssetUpload>>,
+    ) -> Result<Response<VerifyResponse>, Status> {
+        let (info, data) = collect_upload(request.into_inner(), self.max_asset_bytes).await?;
+        let format = resolve_format(&info.format)?;
+        let settings = self.settings.clone();
+        let result = blocking(Status::invalid_argument, move || {
+            extract(&data, format, &settings)
+        })
+        .await?;
+        let facts = crtool::filter::FileFacts::from_extraction(&result);
+        Ok(Response::new(VerifyResponse {
+            failure_codes: crtool::filter::failure_codes(
+                &result.manifest_value,
+                &result.active_label,
+            ),
+            active_label: facts.active_label,
+            trust: facts.trust,
+            valid: facts.valid,
+            manifest_count: facts.manifest_count as u32,
+        }))
+    }
+
+    type SignStream = Pin<Box<dyn Stream<Item = Result<AssetChunk, Status>> + Send>>;
+
+    async fn sign(
+        &self,
+        request: Request<Streaming<SignUpload>>,
+    ) -> Result<Response<Self::SignStream>, Status> {
+        let Some(signing) = self.signing.clone() else {
+            return Err(Status::failed_precondition(
+                "signing is not configured; start the service with --sign-cert and --sign-key",
+            ));
+        };
+        let (info, data) = collect_upload(request.into_inner(), self.max_asset_bytes).await?;
+        let format = resolve_format(&info.format)?;
+        if !format.can_sign {
+            return Err(Status::invalid_argument(format!(
+                "signing is not supported for {}",
+                format.mime
+            )));
+        }
+        let signed = blocking(Status::invalid_argument, move || {
+            let _guard = signing
+                .exclusive
+                .as_ref()
+                .map(|lock| lock.lock().unwrap_or_else(|e| e.into_inner()));
+            sign(info, data, format, &signing.options)
+        })
+        .await?;
+        let chunks: Vec<Result<AssetChunk, Status>> = signed
+            .chunks(RESPONSE_CHUNK_SIZE)
+            .map(|c| Ok(AssetChunk { chunk: c.to_vec() }))
+            .collect();
+        Ok(Response::new(Box::pin(tokio_stream::iter(chunks))))
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let settings = match &cli.trust_config {
+        Some(path) => TrustConfig::load(path)?
+            .settings(None)
+            .context("Failed to apply trust configuration")?,
+        None => crtool::default_extraction_settings(),
+    };
+    crtool::limits::set_extraction_limits(crtool::limits::ExtractionLimits {
+        max_manifest_store_bytes: cli.max_manifest_store_mb * 1024 * 1024,
+        max_json_depth: cli.max_json_depth,
+        ..crtool::limits::ExtractionLimits::DEFAULT
+    });
+    let service = CrtoolService {
+        settings: Arc::new(settings),
+        schema: Arc::new(SchemaValidator::from_bundled().context("Failed to load crJSON schema")?),
+        max_asset_bytes: cli.max_asset_mb * 1024 * 1024,
+        signing: cli
+            .sign_options()
+            .context("Invalid signing credentials")?
+            .map(|options| Arc::new(Signing::new(options))),
+    };
+
+    println!("🚀 crTool gRPC service listening on {}", cli.listen);
+    tonic::transport::Server::builder()
+        .add_service(CrtoolServer::new(service))
+        .serve_with_shutdown(cli.listen, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .context("gRPC server failed")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_format() {
+        assert_eq!(resolve_format("jpg").unwrap().mime, "image/jpeg");
+        assert_eq!(resolve_format(".PNG").unwrap().extension, "png");
+        assert_eq!(resolve_format("image/webp").unwrap().extension, "webp");
+        assert!(resolve_format("text/plain").is_err());
+    }
+
+    #[test]
+    fn test_signing_credentials_are_server_config() {
+        let cli = Cli::try_parse_from(["crtool-grpc"]).unwrap();
+        assert!(cli.sign_options().unwrap().is_none());
+        assert!(Cli::try_parse_from(["crtool-grpc", "--sign-key", "key.pem"]).is_err());
+
+        let certs = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../tests/fixtures/certs");
+        let cert = certs.join("ed25519.pub");
+        let key = certs.join("ed25519.pem");
+        let cli = Cli::try_parse_from([
+            "crtool-grpc".as_ref(),
+            "--sign-cert".as_ref(),
+            cert.as_os_str(),
+            "--sign-key".as_ref(),
+            key.as_os_str(),
+        ])
+        .unwrap();
+        let options = cli.sign_options().unwrap().unwrap();
+        assert_eq!(options.signing_alg, crtool::SigningAlg::Ed25519);
+    }
+
+    #[test]
+    fn test_sign_refuses_file_path_ingredients() {
+        let options = crtool::SignOptions::new(
+            String::new(),
+            "cert.pem",
+            "key.pem",
+            crtool::SigningAlg::Es256,
+        );
+        let info = SignInfo {
+            format: "jpg".into(),
+            manifest_json: r#"{"ingredients": [{"file_path": "/etc/passwd"}]}"#.into(),
+        };
+        let format = resolve_format("jpg").unwrap();
+        let err = sign(info, Vec::new(), format, &options).unwrap_err();
+        assert!(err.to_string().contains("file_path"), "{err}");
+    }
+}
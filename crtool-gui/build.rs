@@ -1,4 +1,5 @@
-// Link macOS Application Services; compile Cocoa handler for open document (drop-on-icon).
+// Link macOS Application Services; compile Cocoa handlers for open document (drop-on-icon)
+// and file drag-out.
 fn main() {
     let target = std::env::var("TARGET").unwrap_or_default();
     if target.contains("apple-darwin") {
@@ -8,5 +9,8 @@ fn main() {
         cc::Build::new()
             .file("macos/open_document_handler.m")
             .compile("open_document_handler");
+        cc::Build::new()
+            .file("macos/drag_out.m")
+            .compile("drag_out");
     }
 }
@@ -0,0 +1,41 @@
+// Start a native file drag (NSDraggingSession) from the key window's content view so the
+// manifest JSON written to a temp file can be dropped on Finder or other apps.
+// Called from Rust on the main thread while the mouse drag event is current.
+
+#import <Cocoa/Cocoa.h>
+
+@interface CrtoolDragSource : NSObject <NSDraggingSource>
+@end
+
+@implementation CrtoolDragSource
+- (NSDragOperation)draggingSession:(NSDraggingSession *)session
+    sourceOperationMaskForDraggingContext:(NSDraggingContext)context {
+    (void)session;
+    (void)context;
+    return NSDragOperationCopy;
+}
+@end
+
+static CrtoolDragSource *s_dragSource = nil;
+
+// Returns 0 when a drag session was started.
+int crtool_macos_begin_file_drag(const char *path) {
+    if (!path) return -1;
+
+    NSWindow *window = [NSApp keyWindow];
+    NSView *view = [window contentView];
+    NSEvent *event = [NSApp currentEvent];
+    if (!view || !event) return -1;
+
+    NSURL *url = [NSURL fileURLWithPath:[NSString stringWithUTF8String:path]];
+    if (!url) return -1;
+
+    NSDraggingItem *item = [[NSDraggingItem alloc] initWithPasteboardWriter:url];
+    NSImage *icon = [[NSWorkspace sharedWorkspace] iconForFile:[url path]];
+    NSPoint location = [view convertPoint:[event locationInWindow] fromView:nil];
+    [item setDraggingFrame:NSMakeRect(location.x - 16, location.y - 16, 32, 32) contents:icon];
+
+    if (s_dragSource == nil) s_dragSource = [[CrtoolDragSource alloc] init];
+    [view beginDraggingSessionWithItems:@[ item ] event:event source:s_dragSource];
+    return 0;
+}
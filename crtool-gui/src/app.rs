@@ -12,23 +12,31 @@ governing permissions and limitations under the License.
 
 //! Main application: dock state, menu bar, and central panel (welcome or DockArea).
 
-use crate::document::{self, DocumentTab};
+#[cfg(feature = "capture")]
+use crate::capture::{self, CaptureSettings, CaptureState};
+use crate::controller::AppController;
+use crate::document::{DocumentCommand, DocumentPanel, DocumentTab};
+use crate::mini_mode;
+use crate::report_export::{DocumentReport, ReportFormat};
+use crate::shortcuts;
 use crate::tab_viewer::CrtoolTabViewer;
+use crate::tree_view::{self, TreeViewSettings};
+use crate::trust_prefs::{self, TrustPrefsState};
+use crate::update_check::{self, UpdateCheckState};
 use crate::util;
-use crtool::{crjson_schema_path, is_supported_asset_path, ManifestExtractionResult, Settings};
+use crtool::graph_export::GraphFormat;
+use crtool::provenance::ProvenanceGraph;
+use crtool::schema::BUNDLED_SCHEMA_SPEC;
+use crtool::trust::{TrustConfig, TrustLists};
+use crtool::ManifestExtractionResult;
 use eframe::egui;
 use egui_dock::{DockArea, DockState, Style};
 use egui_twemoji::EmojiLabel;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Run Save As dialog and write manifest JSON; returns true if user picked a path (and write succeeded or we tried).
 fn save_manifest_as(tab: &DocumentTab, manifest: &ManifestExtractionResult) -> bool {
-    let default_name = tab
-        .file_path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .map(|s| format!("{}-manifest.json", s))
-        .unwrap_or_else(|| "manifest.json".to_string());
+    let default_name = util::manifest_export_file_name(&tab.document.file_path);
     if let Some(save_path) = rfd::FileDialog::new()
         .set_file_name(&default_name)
         .add_filter("JSON", &["json"])
@@ -43,59 +51,181 @@ fn save_manifest_as(tab: &DocumentTab, manifest: &ManifestExtractionResult) -> b
     }
 }
 
-/// Keyboard shortcuts for menu actions (Cmd on macOS, Ctrl on Windows/Linux).
-mod shortcuts {
-    use egui::{Key, KeyboardShortcut, Modifiers};
+/// Run Save dialog and write the manifest store's provenance graph in `format`.
+fn export_provenance_graph(
+    tab: &DocumentTab,
+    manifest: &ManifestExtractionResult,
+    format: GraphFormat,
+) {
+    let default_name = util::provenance_graph_file_name(&tab.document.file_path, format);
+    if let Some(save_path) = rfd::FileDialog::new()
+        .set_file_name(&default_name)
+        .add_filter(format.label(), &[format.extension()])
+        .save_file()
+    {
+        let graph =
+            ProvenanceGraph::from_crjson(&manifest.manifest_value, Some(&manifest.active_label));
+        if let Err(e) = std::fs::write(&save_path, graph.render(format)) {
+            eprintln!("Failed to save provenance graph: {}", e);
+        }
+    }
+}
 
-    pub const OPEN: KeyboardShortcut = KeyboardShortcut {
-        modifiers: Modifiers::COMMAND,
-        logical_key: Key::O,
-    };
-    pub const CLOSE: KeyboardShortcut = KeyboardShortcut {
-        modifiers: Modifiers::COMMAND,
-        logical_key: Key::W,
-    };
-    pub const CLOSE_ALL: KeyboardShortcut = KeyboardShortcut {
-        modifiers: Modifiers::COMMAND.plus(Modifiers::SHIFT),
-        logical_key: Key::W,
-    };
-    pub const SAVE_AS: KeyboardShortcut = KeyboardShortcut {
-        modifiers: Modifiers::COMMAND.plus(Modifiers::SHIFT),
-        logical_key: Key::S,
-    };
-    pub const COPY: KeyboardShortcut = KeyboardShortcut {
-        modifiers: Modifiers::COMMAND,
-        logical_key: Key::C,
-    };
-    pub const SELECT_ALL: KeyboardShortcut = KeyboardShortcut {
-        modifiers: Modifiers::COMMAND,
-        logical_key: Key::A,
+/// Run Save dialog and write the content bill of materials of the manifest's asset.
+fn export_cbom(tab: &DocumentTab, manifest: &ManifestExtractionResult) {
+    let default_name = util::cbom_file_name(&tab.document.file_path);
+    if let Some(save_path) = rfd::FileDialog::new()
+        .set_file_name(&default_name)
+        .add_filter("JSON", &["json"])
+        .save_file()
+    {
+        let cbom = crtool::cbom::build_cbom(manifest);
+        let written = serde_json::to_string_pretty(&cbom)
+            .map_err(std::io::Error::other)
+            .and_then(|json| std::fs::write(&save_path, json));
+        if let Err(e) = written {
+            eprintln!("Failed to save CBOM: {}", e);
+        }
+    }
+}
+
+/// Run Save dialog and write the validation report and indicators of the tab's document.
+fn export_report(tab: &DocumentTab, format: ReportFormat) {
+    let Some(report) = DocumentReport::new(&tab.document) else {
+        return;
     };
+    let default_name = util::report_file_name(&tab.document.file_path, format);
+    if let Some(save_path) = rfd::FileDialog::new()
+        .set_file_name(&default_name)
+        .add_filter(format.label(), &[format.extension()])
+        .save_file()
+    {
+        if let Err(e) = std::fs::write(&save_path, report.render(format)) {
+            eprintln!("Failed to save report: {}", e);
+        }
+    }
+}
+
+/// Open dialog with one filter for all supported formats, then one per media kind.
+fn open_assets_dialog() -> rfd::FileDialog {
+    let mut dialog = rfd::FileDialog::new().add_filter(
+        "C2PA-supported files",
+        &crtool::supported_asset_extensions(),
+    );
+    for kind in crtool::MediaKind::ALL {
+        dialog = dialog.add_filter(kind.label(), &crtool::extensions_for_kind(kind));
+    }
+    dialog
+}
+
+/// A window opened from the menus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AppWindow {
+    KeyboardShortcuts,
+    UpdateCheck,
+    TreeSettings,
+    TrustPreferences,
+    #[cfg(feature = "capture")]
+    Capture,
+}
+
+/// State changes requested by input (dropped files, shortcuts) or the menus. Rendering only
+/// collects commands; [`CrtoolApp::apply`] carries them out, so the UI can be driven and
+/// inspected without a window.
+#[derive(Clone)]
+pub(crate) enum AppCommand {
+    /// Open files as new tabs (unsupported paths are ignored).
+    OpenFiles(Vec<PathBuf>),
+    /// Show the Open dialog and open the picked files.
+    OpenDialog,
+    /// Close the focused tab, or the first tab when none is focused.
+    Close,
+    CloseAll,
+    /// Save the manifest of the focused tab (or the first tab with one) as JSON.
+    SaveAs,
+    /// Export the focused tab's provenance graph.
+    ExportGraph(GraphFormat),
+    /// Export the focused tab's content bill of materials.
+    ExportCbom,
+    /// Export the focused tab's validation report and indicators.
+    ExportReport(ReportFormat),
+    Copy,
+    /// View command for the focused tab.
+    Document(DocumentCommand),
+    OpenWindow(AppWindow),
+    /// Look for a sidecar or remote manifest for the open file at this path, which has none
+    /// embedded.
+    FindManifest(PathBuf),
+    /// Apply the trust policy edited in Preferences → Trust Anchors.
+    ApplyTrustPrefs,
+    /// Pick a schema file and validate documents against it after the current schemas.
+    AddSchema,
+    /// Validate documents against these schemas (file paths, or `bundled`), in order.
+    SetSchemas(Vec<String>),
+    SetMiniMode(bool),
 }
 
-/// Main app state: multi-document dock, schema path, and extraction settings (trust config).
+/// Main app: the controller (document loading and trust settings), the multi-document dock, and
+/// window state.
 pub(crate) struct CrtoolApp {
+    pub(crate) controller: AppController,
     /// Multi-document dock state (tabs can be undocked into separate windows).
     pub(crate) dock_state: DockState<DocumentTab>,
-    /// Schema path for validation (shared).
-    pub(crate) schema_path: PathBuf,
-    /// Settings used for manifest extraction (trust lists or verify_trust disabled).
-    pub(crate) extraction_settings: Settings,
+    trust_prefs: TrustPrefsState,
+    show_trust_prefs: bool,
+    /// Why the last change in Preferences → Validation Schemas was refused.
+    schema_error: Option<String>,
+    /// Whether the Help → Keyboard Shortcuts window is open.
+    show_shortcuts: bool,
+    /// Help → Check for Updates: window visibility and background check result.
+    show_update_check: bool,
+    update_check: UpdateCheckState,
+    /// Manifest Data tree settings (View → Tree Settings).
+    tree_settings: TreeViewSettings,
+    show_tree_settings: bool,
+    /// View → Mini Mode: compact always-on-top summary of the most recently opened file.
+    mini_mode: bool,
+    /// File → Capture & Sign: settings, background capture state, and window visibility.
+    #[cfg(feature = "capture")]
+    capture_settings: CaptureSettings,
+    #[cfg(feature = "capture")]
+    capture_state: CaptureState,
+    #[cfg(feature = "capture")]
+    show_capture: bool,
 }
 
 impl CrtoolApp {
     pub(crate) fn new() -> Self {
-        Self::new_with_optional_files(Vec::new(), util::gui_extraction_settings())
+        Self::new_with_optional_files(
+            Vec::new(),
+            util::load_trust_config(),
+            util::fetch_default_trust_lists(),
+        )
     }
 
     pub(crate) fn new_with_optional_files(
         initial_files: Vec<PathBuf>,
-        extraction_settings: Settings,
+        trust_config: TrustConfig,
+        default_lists: Option<TrustLists>,
     ) -> Self {
         let mut app = Self {
+            controller: AppController::new(trust_config, default_lists),
             dock_state: DockState::new(Vec::new()),
-            schema_path: crjson_schema_path(),
-            extraction_settings,
+            trust_prefs: TrustPrefsState::default(),
+            show_trust_prefs: false,
+            schema_error: None,
+            show_shortcuts: false,
+            show_update_check: false,
+            update_check: UpdateCheckState::default(),
+            tree_settings: TreeViewSettings::default(),
+            show_tree_settings: false,
+            mini_mode: false,
+            #[cfg(feature = "capture")]
+            capture_settings: CaptureSettings::default(),
+            #[cfg(feature = "capture")]
+            capture_state: CaptureState::default(),
+            #[cfg(feature = "capture")]
+            show_capture: false,
         };
         app.add_documents(initial_files);
         app
@@ -103,17 +233,78 @@ impl CrtoolApp {
 
     /// Open one or more files as new tabs (focus goes to the last opened).
     pub(crate) fn add_documents(&mut self, paths: Vec<PathBuf>) {
-        let schema_path = self.schema_path.clone();
-        let settings = self.extraction_settings.clone();
-        for path in paths {
-            if !path.is_file() || !is_supported_asset_path(&path) {
-                continue;
-            }
-            let tab = document::load_document(path, &schema_path, &settings);
+        for document in self.controller.open(paths) {
+            let tab = DocumentTab::new(document, &self.controller.extraction_settings);
             self.dock_state.push_to_focused_leaf(tab);
         }
     }
 
+    /// Start in mini mode (`--mini`); the window itself is sized by the caller.
+    pub(crate) fn with_mini_mode(mut self, mini: bool) -> Self {
+        self.mini_mode = mini;
+        self
+    }
+
+    /// Switch between mini mode and the full window.
+    fn set_mini_mode(&mut self, ctx: &egui::Context, mini: bool) {
+        self.mini_mode = mini;
+        mini_mode::apply_window_mode(ctx, mini);
+    }
+
+    /// Tab of the most recently opened file, if it is still open.
+    fn last_opened_tab(&self) -> Option<&DocumentTab> {
+        let path = self.controller.last_opened.as_ref()?;
+        self.dock_state
+            .iter_all_tabs()
+            .map(|(_, tab)| tab)
+            .find(|tab| &tab.document.file_path == path)
+    }
+
+    /// Apply the trust policy being edited in Preferences → Trust Anchors: rebuild the extraction
+    /// settings, save the policy, and re-read open documents so their trust badges update.
+    fn apply_trust_prefs(&mut self) {
+        let config = self.trust_prefs.draft.clone();
+        match self
+            .controller
+            .set_trust_config(config, &util::trust_config_path())
+        {
+            Ok(save_error) => self.trust_prefs.error = save_error,
+            Err(e) => {
+                self.trust_prefs.error = Some(format!("{:#}", e));
+                return;
+            }
+        }
+        let settings = &self.controller.extraction_settings;
+        for (_, tab) in self.dock_state.iter_all_tabs_mut() {
+            *tab = DocumentTab::new(self.controller.reload(&tab.document), settings);
+        }
+    }
+
+    /// Validate against `schemas` (Preferences → Validation Schemas) and re-read open documents
+    /// so their validation results update. Keeps the current schemas when one cannot be loaded.
+    fn set_schemas(&mut self, schemas: Vec<String>) {
+        if let Err(e) = self.controller.set_schemas(schemas) {
+            self.schema_error = Some(format!("{:#}", e));
+            return;
+        }
+        self.schema_error = None;
+        let settings = &self.controller.extraction_settings;
+        for (_, tab) in self.dock_state.iter_all_tabs_mut() {
+            *tab = DocumentTab::new(self.controller.reload(&tab.document), settings);
+        }
+    }
+
+    /// Replace the tab of `path` with the document read from its sidecar or remote manifest (or
+    /// with the reason none was found).
+    fn find_manifest(&mut self, path: &Path) {
+        let settings = &self.controller.extraction_settings;
+        for (_, tab) in self.dock_state.iter_all_tabs_mut() {
+            if tab.document.file_path == path {
+                *tab = DocumentTab::new(self.controller.find_manifest(&tab.document), settings);
+            }
+        }
+    }
+
     /// Returns the location of the currently focused tab for Close / Save As. None if no tabs.
     pub(crate) fn focused_tab_location(
         &self,
@@ -131,92 +322,328 @@ impl CrtoolApp {
                 Some((surface, node_index, leaf.active))
             })
     }
-}
 
-impl Default for CrtoolApp {
-    fn default() -> Self {
-        Self::new()
+    /// Apply a view command to the focused tab, if any.
+    fn command_focused_tab(&mut self, command: DocumentCommand) {
+        if let Some((_, tab)) = self.dock_state.find_active_focused() {
+            tab.apply_command(command);
+        }
+    }
+    /// Carry out a command collected from input or the last frame.
+    pub(crate) fn apply(&mut self, ctx: &egui::Context, command: AppCommand) {
+        match command {
+            AppCommand::OpenFiles(paths) => self.add_documents(paths),
+            AppCommand::OpenDialog => {
+                if let Some(paths) = open_assets_dialog().pick_files() {
+                    self.add_documents(paths);
+                }
+            }
+            AppCommand::Close => {
+                let loc = self
+                    .focused_tab_location()
+                    .or_else(|| self.dock_state.find_tab_from(|_| true));
+                if let Some(loc) = loc {
+                    self.dock_state.remove_tab(loc);
+                }
+            }
+            AppCommand::CloseAll => self.dock_state.retain_tabs(|_| false),
+            AppCommand::SaveAs => {
+                let mut did_save = false;
+                if let Some((_, tab)) = self.dock_state.find_active_focused() {
+                    if let Ok(ref manifest) = tab.document.extraction_result {
+                        did_save = save_manifest_as(tab, manifest);
+                    }
+                }
+                if !did_save {
+                    for (_, tab) in self.dock_state.iter_all_tabs_mut() {
+                        if let Ok(ref manifest) = tab.document.extraction_result {
+                            save_manifest_as(tab, manifest);
+                            break;
+                        }
+                    }
+                }
+            }
+            AppCommand::ExportGraph(format) => {
+                if let Some((_, tab)) = self.dock_state.find_active_focused() {
+                    if let Ok(ref manifest) = tab.document.extraction_result {
+                        export_provenance_graph(tab, manifest, format);
+                    }
+                }
+            }
+            AppCommand::ExportCbom => {
+                if let Some((_, tab)) = self.dock_state.find_active_focused() {
+                    if let Ok(ref manifest) = tab.document.extraction_result {
+                        export_cbom(tab, manifest);
+                    }
+                }
+            }
+            AppCommand::ExportReport(format) => {
+                if let Some((_, tab)) = self.dock_state.find_active_focused() {
+                    export_report(tab, format);
+                }
+            }
+            AppCommand::Copy => ctx.copy_text(util::get_selected_text(ctx)),
+            AppCommand::Document(command) => self.command_focused_tab(command),
+            AppCommand::OpenWindow(window) => match window {
+                AppWindow::KeyboardShortcuts => self.show_shortcuts = true,
+                AppWindow::UpdateCheck => {
+                    self.show_update_check = true;
+                    update_check::spawn_update_check(self.update_check.clone(), ctx.clone());
+                }
+                AppWindow::TreeSettings => self.show_tree_settings = true,
+                AppWindow::TrustPreferences => {
+                    self.trust_prefs = TrustPrefsState::edit(&self.controller.trust_config);
+                    self.show_trust_prefs = true;
+                }
+                #[cfg(feature = "capture")]
+                AppWindow::Capture => self.show_capture = true,
+            },
+            AppCommand::FindManifest(path) => self.find_manifest(&path),
+            AppCommand::ApplyTrustPrefs => self.apply_trust_prefs(),
+            AppCommand::AddSchema => {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("JSON Schema", &["json"])
+                    .pick_file()
+                {
+                    let mut schemas = self.controller.schemas.clone();
+                    schemas.push(path.to_string_lossy().to_string());
+                    self.set_schemas(schemas);
+                }
+            }
+            AppCommand::SetSchemas(schemas) => self.set_schemas(schemas),
+            AppCommand::SetMiniMode(mini) => self.set_mini_mode(ctx, mini),
+        }
     }
-}
 
-impl eframe::App for CrtoolApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    /// Commands from this frame's input: opened or dropped files, and keyboard shortcuts
+    /// (shortcuts are ignored in mini mode).
+    pub(crate) fn input_commands(&self, ctx: &egui::Context) -> Vec<AppCommand> {
         let mut paths_to_open: Vec<PathBuf> = Vec::new();
 
         #[cfg(target_os = "macos")]
         paths_to_open.extend(
             crate::macos_open_document::drain_pending_files()
                 .into_iter()
-                .filter(|p| p.is_file() && is_supported_asset_path(p)),
+                .filter(|p| AppController::can_open(p)),
         );
 
         let dropped = ctx.input(|i| i.raw.dropped_files.clone());
         for file in dropped {
-            if let Some(path) = file.path.filter(|p| is_supported_asset_path(p)) {
+            if let Some(path) = file.path.filter(|p| AppController::can_open(p)) {
                 paths_to_open.push(path);
             }
         }
 
+        #[cfg(feature = "capture")]
+        paths_to_open.extend(capture::take_signed(&self.capture_state));
+
+        let mut commands = Vec::new();
         if !paths_to_open.is_empty() {
-            self.add_documents(paths_to_open);
+            commands.push(AppCommand::OpenFiles(paths_to_open));
+        }
+        if self.mini_mode {
+            return commands;
         }
 
-        // Handle keyboard shortcuts (check more specific before less specific).
-        // We avoid calling ctx inside input_mut to prevent deadlock; copy is deferred.
-        let mut trigger_copy = false;
+        // Check more specific shortcuts before less specific ones.
         ctx.input_mut(|i| {
             if i.consume_shortcut(&shortcuts::OPEN) {
-                if let Some(paths) = rfd::FileDialog::new()
-                    .add_filter("C2PA-supported files", crtool::SUPPORTED_ASSET_EXTENSIONS)
-                    .pick_files()
-                {
-                    self.add_documents(paths);
-                }
+                commands.push(AppCommand::OpenDialog);
             }
             if i.consume_shortcut(&shortcuts::CLOSE_ALL) {
-                self.dock_state.retain_tabs(|_| false);
+                commands.push(AppCommand::CloseAll);
             } else if i.consume_shortcut(&shortcuts::CLOSE) {
-                if let Some(loc) = self.focused_tab_location() {
-                    self.dock_state.remove_tab(loc);
-                }
+                commands.push(AppCommand::Close);
             }
             if i.consume_shortcut(&shortcuts::SAVE_AS) {
-                if let Some((_, tab)) = self.dock_state.find_active_focused() {
-                    if let Ok(ref manifest) = tab.extraction_result {
-                        let default_name = tab
-                            .file_path
-                            .file_stem()
-                            .and_then(|s| s.to_str())
-                            .map(|s| format!("{}-manifest.json", s))
-                            .unwrap_or_else(|| "manifest.json".to_string());
-                        if let Some(save_path) = rfd::FileDialog::new()
-                            .set_file_name(&default_name)
-                            .add_filter("JSON", &["json"])
-                            .save_file()
-                        {
-                            if let Err(e) = std::fs::write(&save_path, &manifest.manifest_json) {
-                                eprintln!("Failed to save file: {}", e);
-                            }
-                        }
-                    }
-                }
+                commands.push(AppCommand::SaveAs);
             }
             if i.consume_shortcut(&shortcuts::COPY) {
-                trigger_copy = true;
+                commands.push(AppCommand::Copy);
             }
             // Select All: consumed for consistency; no-op (egui handles text selection where applicable)
             let _ = i.consume_shortcut(&shortcuts::SELECT_ALL);
+            let command = if i.consume_shortcut(&shortcuts::TOGGLE_RAW_JSON) {
+                Some(DocumentCommand::ToggleRawJson)
+            } else if i.consume_shortcut(&shortcuts::FOCUS_LEFT) {
+                Some(DocumentCommand::FocusPanel(DocumentPanel::ManifestData))
+            } else if i.consume_shortcut(&shortcuts::FOCUS_RIGHT) {
+                Some(DocumentCommand::FocusPanel(DocumentPanel::Tree))
+            } else if i.consume_shortcut(&shortcuts::FIND) {
+                Some(DocumentCommand::Find)
+            } else {
+                None
+            };
+            commands.extend(command.map(AppCommand::Document));
+        });
+        commands
+    }
+
+    /// Process input, draw one frame, and apply the commands it produced.
+    pub(crate) fn run_frame(&mut self, ctx: &egui::Context) {
+        for command in self.input_commands(ctx) {
+            self.apply(ctx, command);
+        }
+        for command in self.show(ctx) {
+            self.apply(ctx, command);
+        }
+    }
+
+    /// Draw the UI. Only view state (window visibility, tab layout, panel state) changes here;
+    /// everything else is returned as commands.
+    pub(crate) fn show(&mut self, ctx: &egui::Context) -> Vec<AppCommand> {
+        let mut commands = Vec::new();
+
+        if self.mini_mode {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                if mini_mode::show_mini_summary(ui, self.last_opened_tab()) {
+                    commands.push(AppCommand::SetMiniMode(false));
+                }
+            });
+            return commands;
+        }
+
+        let has_manifest = self
+            .dock_state
+            .find_active_focused()
+            .is_some_and(|(_, tab)| tab.document.extraction_result.is_ok());
+        self.show_menu_bar(ctx, has_manifest, &mut commands);
+
+        shortcuts::show_shortcuts_window(ctx, &mut self.show_shortcuts);
+        update_check::show_update_window(ctx, &self.update_check, &mut self.show_update_check);
+        tree_view::show_tree_settings_window(
+            ctx,
+            &mut self.tree_settings,
+            &mut self.show_tree_settings,
+        );
+        #[cfg(feature = "capture")]
+        capture::show_capture_window(
+            ctx,
+            &mut self.capture_settings,
+            &self.capture_state,
+            &mut self.show_capture,
+        );
+        if trust_prefs::show_trust_preferences_window(
+            ctx,
+            &mut self.trust_prefs,
+            self.controller.default_lists.as_ref(),
+            &mut self.show_trust_prefs,
+        ) {
+            commands.push(AppCommand::ApplyTrustPrefs);
+        }
+
+        let has_any_tabs = self.dock_state.iter_all_tabs().next().is_some();
+        let mut tab_viewer = CrtoolTabViewer {
+            tree_settings: self.tree_settings,
+            commands: Vec::new(),
+        };
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("C2PA Content Credential Tool");
+            ui.separator();
+
+            if !has_any_tabs {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(80.0);
+                    EmojiLabel::new(
+                        egui::RichText::new(
+                            "👆 Open one or more C2PA-supported files (image, video, audio, or PDF) to extract manifests. \
+                             You can drag files onto this window or use Open below.",
+                        )
+                        .size(16.0),
+                    )
+                    .show(ui);
+                    ui.add_space(20.0);
+                    if ui
+                        .button(egui::RichText::new("📂 Select File(s)...").size(16.0))
+                        .clicked()
+                    {
+                        commands.push(AppCommand::OpenDialog);
+                    }
+                });
+            } else {
+                let style = Style::from_egui(ui.style().as_ref());
+                DockArea::new(&mut self.dock_state)
+                    .style(style)
+                    .show_inside(ui, &mut tab_viewer);
+            }
         });
-        if trigger_copy {
-            ctx.copy_text(util::get_selected_text(ctx));
+        commands.append(&mut tab_viewer.commands);
+        commands
+    }
+
+    /// Preferences → Validation Schemas: the schemas documents are validated against, in order,
+    /// with buttons to remove one, add a schema file, or go back to the bundled schema.
+    fn show_schema_menu(&self, ui: &mut egui::Ui, commands: &mut Vec<AppCommand>) {
+        let schemas = &self.controller.schemas;
+        let is_bundled = |spec: &str| spec.eq_ignore_ascii_case(BUNDLED_SCHEMA_SPEC);
+        for (index, schema) in schemas.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let label = if is_bundled(schema) {
+                    "Bundled crJSON schema".to_string()
+                } else {
+                    Path::new(schema)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| schema.clone())
+                };
+                ui.label(format!("{}. {}", index + 1, label))
+                    .on_hover_text(schema);
+                // The last schema cannot be removed: documents are always validated.
+                if ui
+                    .add_enabled(schemas.len() > 1, egui::Button::new("✖"))
+                    .on_hover_text("Stop validating against this schema")
+                    .clicked()
+                {
+                    let mut remaining = schemas.clone();
+                    remaining.remove(index);
+                    commands.push(AppCommand::SetSchemas(remaining));
+                    ui.close();
+                }
+            });
+        }
+        ui.separator();
+        if ui.button("➕ Add Schema...").clicked() {
+            commands.push(AppCommand::AddSchema);
+            ui.close();
+        }
+        if !schemas.iter().any(|s| is_bundled(s)) && ui.button("➕ Add Bundled Schema").clicked() {
+            let mut with_bundled = vec![BUNDLED_SCHEMA_SPEC.to_string()];
+            with_bundled.extend(schemas.iter().cloned());
+            commands.push(AppCommand::SetSchemas(with_bundled));
+            ui.close();
+        }
+        if ui.button("↺ Bundled Schema Only").clicked() {
+            commands.push(AppCommand::SetSchemas(
+                vec![BUNDLED_SCHEMA_SPEC.to_string()],
+            ));
+            ui.close();
         }
+        if let Some(error) = &self.schema_error {
+            ui.separator();
+            ui.colored_label(egui::Color32::from_rgb(200, 60, 60), error);
+        }
+    }
 
+    /// Menu bar; `has_manifest` enables the focused-tab exports.
+    fn show_menu_bar(
+        &self,
+        ctx: &egui::Context,
+        has_manifest: bool,
+        commands: &mut Vec<AppCommand>,
+    ) {
         let open_shortcut = ctx.format_shortcut(&shortcuts::OPEN);
         let close_shortcut = ctx.format_shortcut(&shortcuts::CLOSE);
         let close_all_shortcut = ctx.format_shortcut(&shortcuts::CLOSE_ALL);
         let save_as_shortcut = ctx.format_shortcut(&shortcuts::SAVE_AS);
         let copy_shortcut = ctx.format_shortcut(&shortcuts::COPY);
         let select_all_shortcut = ctx.format_shortcut(&shortcuts::SELECT_ALL);
+        let raw_json_shortcut = ctx.format_shortcut(&shortcuts::TOGGLE_RAW_JSON);
+        let focus_left_shortcut = ctx.format_shortcut(&shortcuts::FOCUS_LEFT);
+        let focus_right_shortcut = ctx.format_shortcut(&shortcuts::FOCUS_RIGHT);
+        let find_shortcut = ctx.format_shortcut(&shortcuts::FIND);
+
+        let has_tabs = self.dock_state.iter_all_tabs().next().is_some();
 
         egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
@@ -225,25 +652,20 @@ impl eframe::App for CrtoolApp {
                         .button(format!("📂 Open...\t{}", open_shortcut))
                         .clicked()
                     {
-                        if let Some(paths) = rfd::FileDialog::new()
-                            .add_filter("C2PA-supported files", crtool::SUPPORTED_ASSET_EXTENSIONS)
-                            .pick_files()
-                        {
-                            self.add_documents(paths);
-                        }
+                        commands.push(AppCommand::OpenDialog);
                         ui.close();
                     }
 
-                    let has_tabs = self.dock_state.iter_all_tabs().next().is_some();
-                    let focused = self.focused_tab_location();
+                    #[cfg(feature = "capture")]
+                    if ui.button("📷 Capture & Sign...").clicked() {
+                        commands.push(AppCommand::OpenWindow(AppWindow::Capture));
+                        ui.close();
+                    }
 
                     // Close: enabled when any tab exists; close focused tab or first tab
                     ui.add_enabled_ui(has_tabs, |ui| {
                         if ui.button(format!("❌ Close\t{}", close_shortcut)).clicked() {
-                            let loc = focused.or_else(|| self.dock_state.find_tab_from(|_| true));
-                            if let Some(loc) = loc {
-                                self.dock_state.remove_tab(loc);
-                            }
+                            commands.push(AppCommand::Close);
                             ui.close();
                         }
                     });
@@ -253,7 +675,7 @@ impl eframe::App for CrtoolApp {
                             .button(format!("❌ Close All\t{}", close_all_shortcut))
                             .clicked()
                         {
-                            self.dock_state.retain_tabs(|_| false);
+                            commands.push(AppCommand::CloseAll);
                             ui.close();
                         }
                     });
@@ -266,20 +688,34 @@ impl eframe::App for CrtoolApp {
                             .button(format!("💾 Save As...\t{}", save_as_shortcut))
                             .clicked()
                         {
-                            let mut did_save = false;
-                            if let Some((_, tab)) = self.dock_state.find_active_focused() {
-                                if let Ok(ref manifest) = tab.extraction_result {
-                                    did_save = save_manifest_as(tab, manifest);
+                            commands.push(AppCommand::SaveAs);
+                            ui.close();
+                        }
+                    });
+
+                    // Export Provenance Graph: uses the focused tab's manifest
+                    ui.add_enabled_ui(has_manifest, |ui| {
+                        ui.menu_button("🕸 Export Provenance Graph", |ui| {
+                            for format in GraphFormat::ALL {
+                                if ui.button(format!("{}...", format.label())).clicked() {
+                                    commands.push(AppCommand::ExportGraph(format));
+                                    ui.close();
                                 }
                             }
-                            if !did_save {
-                                for (_, tab) in self.dock_state.iter_all_tabs_mut() {
-                                    if let Ok(ref manifest) = tab.extraction_result {
-                                        save_manifest_as(tab, manifest);
-                                        break;
-                                    }
+                        });
+                        ui.menu_button("📄 Export Report", |ui| {
+                            for format in ReportFormat::ALL {
+                                if ui.button(format!("{}...", format.label())).clicked() {
+                                    commands.push(AppCommand::ExportReport(format));
+                                    ui.close();
                                 }
                             }
+                        });
+                        if ui
+                            .button("📜 Export Content Bill of Materials...")
+                            .clicked()
+                        {
+                            commands.push(AppCommand::ExportCbom);
                             ui.close();
                         }
                     });
@@ -287,7 +723,7 @@ impl eframe::App for CrtoolApp {
 
                 ui.menu_button("Edit", |ui| {
                     if ui.button(format!("📋 Copy\t{}", copy_shortcut)).clicked() {
-                        ctx.copy_text(util::get_selected_text(ctx));
+                        commands.push(AppCommand::Copy);
                         ui.close();
                     }
                     ui.separator();
@@ -297,47 +733,92 @@ impl eframe::App for CrtoolApp {
                     {
                         ui.close();
                     }
+                    ui.separator();
+                    ui.add_enabled_ui(has_tabs, |ui| {
+                        if ui
+                            .button(format!("🔍 Find...\t{}", find_shortcut))
+                            .clicked()
+                        {
+                            commands.push(AppCommand::Document(DocumentCommand::Find));
+                            ui.close();
+                        }
+                    });
                 });
-            });
-        });
-
-        let has_any_tabs = self.dock_state.iter_all_tabs().next().is_some();
-        let mut tab_viewer = CrtoolTabViewer;
-
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("C2PA Content Credential Tool");
-            ui.separator();
 
-            if !has_any_tabs {
-                ui.vertical_centered(|ui| {
-                    ui.add_space(80.0);
-                    EmojiLabel::new(
-                        egui::RichText::new(
-                            "👆 Open one or more C2PA-supported files (image, video, audio, or PDF) to extract manifests. \
-                             You can drag files onto this window or use Open below.",
-                        )
-                        .size(16.0),
-                    )
-                    .show(ui);
-                    ui.add_space(20.0);
-                    if ui
-                        .button(egui::RichText::new("📂 Select File(s)...").size(16.0))
-                        .clicked()
-                    {
-                        if let Some(paths) = rfd::FileDialog::new()
-                            .add_filter("C2PA-supported files", crtool::SUPPORTED_ASSET_EXTENSIONS)
-                            .pick_files()
+                ui.menu_button("View", |ui| {
+                    ui.add_enabled_ui(has_tabs, |ui| {
+                        if ui
+                            .button(format!("📋 Toggle Raw JSON\t{}", raw_json_shortcut))
+                            .clicked()
+                        {
+                            commands.push(AppCommand::Document(DocumentCommand::ToggleRawJson));
+                            ui.close();
+                        }
+                        ui.separator();
+                        if ui
+                            .button(format!("📊 Manifest Data Panel\t{}", focus_left_shortcut))
+                            .clicked()
+                        {
+                            commands.push(AppCommand::Document(DocumentCommand::FocusPanel(
+                                DocumentPanel::ManifestData,
+                            )));
+                            ui.close();
+                        }
+                        if ui
+                            .button(format!("🌳 Tree Panel\t{}", focus_right_shortcut))
+                            .clicked()
                         {
-                            self.add_documents(paths);
+                            commands.push(AppCommand::Document(DocumentCommand::FocusPanel(
+                                DocumentPanel::Tree,
+                            )));
+                            ui.close();
                         }
+                    });
+                    ui.separator();
+                    if ui.button("⚙ Tree Settings...").clicked() {
+                        commands.push(AppCommand::OpenWindow(AppWindow::TreeSettings));
+                        ui.close();
+                    }
+                    ui.separator();
+                    if ui.button("🗗 Mini Mode").clicked() {
+                        commands.push(AppCommand::SetMiniMode(true));
+                        ui.close();
                     }
                 });
-            } else {
-                let style = Style::from_egui(ui.style().as_ref());
-                DockArea::new(&mut self.dock_state)
-                    .style(style)
-                    .show_inside(ui, &mut tab_viewer);
-            }
+
+                ui.menu_button("Preferences", |ui| {
+                    if ui.button("🔒 Trust Anchors...").clicked() {
+                        commands.push(AppCommand::OpenWindow(AppWindow::TrustPreferences));
+                        ui.close();
+                    }
+                    ui.menu_button("📐 Validation Schemas", |ui| {
+                        self.show_schema_menu(ui, commands);
+                    });
+                });
+
+                ui.menu_button("Help", |ui| {
+                    if ui.button("⌨ Keyboard Shortcuts").clicked() {
+                        commands.push(AppCommand::OpenWindow(AppWindow::KeyboardShortcuts));
+                        ui.close();
+                    }
+                    if ui.button("⬆ Check for Updates...").clicked() {
+                        commands.push(AppCommand::OpenWindow(AppWindow::UpdateCheck));
+                        ui.close();
+                    }
+                });
+            });
         });
     }
 }
+
+impl Default for CrtoolApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl eframe::App for CrtoolApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.run_frame(ctx);
+    }
+}
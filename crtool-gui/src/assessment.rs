@@ -0,0 +1,126 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Trust Manifest assessment section: load a JPEG Trust Trust Manifest and show the
+//! per-clause conformance of the document's indicators.
+
+use crtool::trust_manifest::{
+    assess_extraction, load_trust_manifest, ClauseStatus, ConformanceReport,
+};
+use crtool::ManifestExtractionResult;
+use eframe::egui;
+use egui_twemoji::EmojiLabel;
+use std::path::PathBuf;
+
+/// Loaded Trust Manifest and its assessment result for one document.
+#[derive(Clone)]
+pub(crate) struct TrustAssessment {
+    pub(crate) trust_manifest_path: PathBuf,
+    pub(crate) result: Result<ConformanceReport, String>,
+}
+
+fn run_assessment(path: PathBuf, manifest: &ManifestExtractionResult) -> TrustAssessment {
+    let result = load_trust_manifest(&path)
+        .map(|tm| assess_extraction(&tm, manifest))
+        .map_err(|e| format!("{:#}", e));
+    TrustAssessment {
+        trust_manifest_path: path,
+        result,
+    }
+}
+
+fn show_report(ui: &mut egui::Ui, report: &ConformanceReport) {
+    let (text, color) = if report.conforms {
+        ("✅ Conforms", egui::Color32::from_rgb(0, 100, 0))
+    } else {
+        (
+            "❌ Does not conform",
+            egui::Color32::from_rgb(255, 100, 100),
+        )
+    };
+    EmojiLabel::new(
+        egui::RichText::new(format!(
+            "{} — {} passed, {} failed, {} not applicable",
+            text, report.passed, report.failed, report.not_applicable
+        ))
+        .size(15.0)
+        .color(color),
+    )
+    .show(ui);
+
+    egui::Grid::new("trust_assessment_clauses")
+        .striped(true)
+        .num_columns(3)
+        .show(ui, |ui| {
+            for clause in &report.clauses {
+                let (mark, color) = match clause.status {
+                    ClauseStatus::Pass => ("✓", egui::Color32::from_rgb(0, 100, 0)),
+                    ClauseStatus::Fail => ("✗", egui::Color32::from_rgb(255, 100, 100)),
+                    ClauseStatus::NotApplicable => ("–", egui::Color32::from_rgb(64, 64, 64)),
+                };
+                ui.colored_label(color, mark);
+                ui.label(&clause.id)
+                    .on_hover_text(clause.description.as_deref().unwrap_or(&clause.indicator));
+                ui.label(clause.message.as_deref().unwrap_or(""));
+                ui.end_row();
+            }
+        });
+}
+
+/// Renders the assessment section with a button to load (or reload) a Trust Manifest.
+pub(crate) fn show_trust_assessment(
+    ui: &mut egui::Ui,
+    assessment: &mut Option<TrustAssessment>,
+    manifest: &ManifestExtractionResult,
+) {
+    egui::CollapsingHeader::new(
+        egui::RichText::new("🧪 Trust Manifest assessment")
+            .size(15.0)
+            .color(egui::Color32::from_rgb(100, 120, 140)),
+    )
+    .id_salt("trust_assessment")
+    .default_open(assessment.is_some())
+    .show(ui, |ui| {
+        ui.horizontal(|ui| {
+            if ui.button("📂 Load Trust Manifest...").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .pick_file()
+                {
+                    *assessment = Some(run_assessment(path, manifest));
+                }
+            }
+            if let Some(current) = assessment.as_ref() {
+                if ui.button("🔄 Re-run").clicked() {
+                    *assessment = Some(run_assessment(
+                        current.trust_manifest_path.clone(),
+                        manifest,
+                    ));
+                }
+            }
+        });
+        let Some(current) = assessment.as_ref() else {
+            return;
+        };
+        ui.label(
+            egui::RichText::new(current.trust_manifest_path.display().to_string())
+                .size(12.0)
+                .color(egui::Color32::from_rgb(64, 64, 64)),
+        );
+        match &current.result {
+            Ok(report) => show_report(ui, report),
+            Err(e) => {
+                ui.colored_label(egui::Color32::from_rgb(230, 80, 80), e);
+            }
+        }
+    });
+}
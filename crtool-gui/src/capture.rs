@@ -0,0 +1,280 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! File → Capture & Sign (optional `capture` feature): take a photo with an external capture
+//! command (gPhoto2 by default), sign it with a test case via [`crtool::sign_asset`], and open
+//! the signed result, on a background thread.
+
+use eframe::egui;
+use egui_twemoji::EmojiLabel;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+/// Environment variable overriding the default capture command.
+pub(crate) const CAPTURE_COMMAND_ENV: &str = "CRTOOL_CAPTURE_COMMAND";
+
+/// Path the capture command must write the photo to, passed in its environment.
+const CAPTURE_PATH_ENV: &str = "CRTOOL_CAPTURE_PATH";
+
+const DEFAULT_CAPTURE_COMMAND: &str =
+    "gphoto2 --capture-image-and-download --force-overwrite --filename \"$CRTOOL_CAPTURE_PATH\"";
+
+/// What to run for a capture, edited in the Capture & Sign window.
+#[derive(Clone)]
+pub(crate) struct CaptureSettings {
+    /// Shell command that captures one photo to `$CRTOOL_CAPTURE_PATH`.
+    pub(crate) command: String,
+    /// Test case JSON (the signing profile) applied to the captured photo.
+    pub(crate) test_case: String,
+    /// Where captured and signed photos are written.
+    pub(crate) output_dir: String,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            command: std::env::var(CAPTURE_COMMAND_ENV)
+                .unwrap_or_else(|_| DEFAULT_CAPTURE_COMMAND.to_string()),
+            test_case: String::new(),
+            output_dir: std::env::temp_dir()
+                .join("crtool-captures")
+                .display()
+                .to_string(),
+        }
+    }
+}
+
+/// Progress of a capture.
+#[derive(Clone)]
+pub(crate) enum CaptureProgress {
+    Capturing,
+    Signing,
+    Done(Result<PathBuf, String>),
+    /// The signed photo has been handed to the app to open.
+    Opened(PathBuf),
+}
+
+/// Shared between the UI and the background capture; `None` until a capture is started.
+pub(crate) type CaptureState = Arc<Mutex<Option<CaptureProgress>>>;
+
+fn set(state: &CaptureState, progress: CaptureProgress) {
+    if let Ok(mut s) = state.lock() {
+        *s = Some(progress);
+    }
+}
+
+/// Build a command that runs `cmd` through the platform shell.
+fn shell_command(cmd: &str) -> Command {
+    if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.args(["/C", cmd]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", cmd]);
+        c
+    }
+}
+
+fn failure(what: &str, output: &std::process::Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    format!("{} failed ({}): {}", what, output.status, stderr.trim())
+}
+
+fn run_capture(settings: &CaptureSettings, state: &CaptureState) -> Result<PathBuf, String> {
+    if settings.test_case.trim().is_empty() {
+        return Err("Choose a test case to sign the capture with.".to_string());
+    }
+    let dir = Path::new(&settings.output_dir);
+    std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {:?}: {}", dir, e))?;
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let captured = dir.join(format!("capture-{}.jpg", stamp));
+    let signed = dir.join(format!("capture-{}-signed.jpg", stamp));
+
+    let output = shell_command(&settings.command)
+        .env(CAPTURE_PATH_ENV, &captured)
+        .output()
+        .map_err(|e| format!("Failed to run capture command: {}", e))?;
+    if !output.status.success() {
+        return Err(failure("Capture command", &output));
+    }
+    if !captured.is_file() {
+        return Err(format!(
+            "Capture command did not write a photo to {}",
+            captured.display()
+        ));
+    }
+
+    set(state, CaptureProgress::Signing);
+    sign_capture(Path::new(&settings.test_case), &captured, &signed)
+        .map_err(|e| format!("Signing failed: {:#}", e))?;
+    Ok(signed)
+}
+
+/// The parts of a test case JSON (`INTERNAL/schemas/test-case.schema.json`) used for signing.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestCase {
+    manifest: serde_json::Value,
+    signing_cert: String,
+    signing_key: Option<String>,
+    tsa_url: Option<String>,
+}
+
+/// Sign `captured` with the manifest and credentials of the test case at `test_case_path`,
+/// resolving its paths against the test case's directory as `crTool sign` does.
+fn sign_capture(test_case_path: &Path, captured: &Path, signed: &Path) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let json = std::fs::read_to_string(test_case_path)
+        .with_context(|| format!("Cannot read {:?}", test_case_path))?;
+    let test_case: TestCase = serde_json::from_str(&json).context("Invalid test case JSON")?;
+    let base_dir = test_case_path.parent().unwrap_or(Path::new("."));
+
+    let mut manifest = test_case.manifest;
+    let template = crtool::template::TemplateContext::for_input(captured);
+    crtool::template::expand_placeholders(&mut manifest, &template)?;
+
+    let cert = base_dir.join(&test_case.signing_cert);
+    let key = Path::new(
+        test_case
+            .signing_key
+            .as_deref()
+            .unwrap_or(&test_case.signing_cert),
+    );
+    let key = if crtool::signer::is_pkcs11_uri(key) {
+        key.to_path_buf()
+    } else {
+        base_dir.join(key)
+    };
+    let signing_alg = match manifest.get("alg").and_then(|v| v.as_str()) {
+        Some(alg) => crtool::signing::parse_signing_algorithm(alg)?,
+        None => crtool::signing::detect_signing_algorithm(&cert)?,
+    };
+
+    let mut options = crtool::SignOptions::new(manifest.to_string(), cert, key, signing_alg);
+    options.ingredients_base_dir = base_dir.to_path_buf();
+    options.tsa_url = test_case.tsa_url;
+    options.allow_self_signed = true; // test certs are typically self-signed
+    crtool::sign_asset(captured, signed, &options)?;
+    Ok(())
+}
+
+/// Start a capture on a background thread, repainting when it finishes.
+pub(crate) fn spawn_capture(state: CaptureState, settings: CaptureSettings, ctx: egui::Context) {
+    set(&state, CaptureProgress::Capturing);
+    std::thread::spawn(move || {
+        let result = run_capture(&settings, &state);
+        set(&state, CaptureProgress::Done(result));
+        ctx.request_repaint();
+    });
+}
+
+/// The signed photo of a finished capture, once; later calls return None.
+pub(crate) fn take_signed(state: &CaptureState) -> Option<PathBuf> {
+    let mut s = state.lock().ok()?;
+    match s.take() {
+        Some(CaptureProgress::Done(Ok(path))) => {
+            *s = Some(CaptureProgress::Opened(path.clone()));
+            Some(path)
+        }
+        other => {
+            *s = other;
+            None
+        }
+    }
+}
+
+/// File → Capture & Sign window.
+pub(crate) fn show_capture_window(
+    ctx: &egui::Context,
+    settings: &mut CaptureSettings,
+    state: &CaptureState,
+    open: &mut bool,
+) {
+    let current = state.lock().ok().and_then(|s| s.clone());
+    let busy = matches!(
+        current,
+        Some(CaptureProgress::Capturing | CaptureProgress::Signing)
+    );
+    egui::Window::new("Capture & Sign")
+        .open(open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(520.0)
+        .show(ctx, |ui| {
+            egui::Grid::new("capture_settings")
+                .num_columns(2)
+                .spacing([16.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("Capture command");
+                    ui.text_edit_singleline(&mut settings.command)
+                        .on_hover_text(
+                            "Runs through the shell; must write the photo to $CRTOOL_CAPTURE_PATH",
+                        );
+                    ui.end_row();
+
+                    ui.label("Test case");
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut settings.test_case);
+                        if ui.button("📂").clicked() {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .add_filter("Test case JSON", &["json"])
+                                .pick_file()
+                            {
+                                settings.test_case = path.display().to_string();
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Output folder");
+                    ui.text_edit_singleline(&mut settings.output_dir);
+                    ui.end_row();
+                });
+            ui.separator();
+            ui.add_enabled_ui(!busy, |ui| {
+                if ui.button("📷 Capture").clicked() {
+                    spawn_capture(state.clone(), settings.clone(), ctx.clone());
+                }
+            });
+            match current {
+                None => {}
+                Some(CaptureProgress::Capturing) => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Capturing...");
+                    });
+                }
+                Some(CaptureProgress::Signing) => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Signing...");
+                    });
+                }
+                Some(CaptureProgress::Done(Ok(path)) | CaptureProgress::Opened(path)) => {
+                    EmojiLabel::new(format!("✅ Signed: {}", path.display())).show(ui);
+                }
+                Some(CaptureProgress::Done(Err(e))) => {
+                    EmojiLabel::new(
+                        egui::RichText::new(format!("❌ {}", e))
+                            .color(egui::Color32::from_rgb(230, 80, 80)),
+                    )
+                    .show(ui);
+                }
+            }
+        });
+}
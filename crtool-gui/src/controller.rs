@@ -0,0 +1,444 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Application state without egui types: which files can be opened, loading (extraction and
+//! schema validation), the search for a sidecar or remote manifest when a file has none, and the
+//! trust settings and validation schemas documents are read with. The view layer (app.rs,
+//! document.rs) renders what this produces and owns the tab layout.
+
+use crate::util;
+use crtool::removal::{detect_credential_removal, RemovalSign};
+use crtool::schema::BUNDLED_SCHEMA_SPEC;
+use crtool::trust::{TrustConfig, TrustLists};
+use crtool::unsupported::{detect_unsupported, unsupported_from_error, UnsupportedFeature};
+use crtool::{
+    asset_format_for_path, extract_crjson_manifest_hashed, extract_crjson_manifest_with_store,
+    is_no_manifest_error, is_supported_asset_path, AssetFormat, ManifestExtractionResult, Settings,
+    ValidationProfile, ValidationResult,
+};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Timeout for fetching a remote manifest referenced by the asset's XMP.
+const REMOTE_MANIFEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Basic facts about a file that carries no Content Credentials, shown instead of an error.
+#[derive(Clone)]
+pub(crate) struct AssetInfo {
+    pub(crate) format: Option<&'static AssetFormat>,
+    pub(crate) size_bytes: u64,
+    /// Pixel width and height, for image formats crTool can decode
+    pub(crate) dimensions: Option<(u32, u32)>,
+    pub(crate) sha256: String,
+}
+
+impl AssetInfo {
+    fn read(path: &Path) -> Self {
+        AssetInfo {
+            format: asset_format_for_path(path),
+            size_bytes: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+            dimensions: image::image_dimensions(path).ok(),
+            sha256: crtool::hashing::hash_file(path, crtool::hashing::HashAlgorithm::Sha256)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// One file as read from disk: extraction and validation results and the banners derived from
+/// them.
+#[derive(Clone)]
+pub(crate) struct LoadedDocument {
+    /// Loaded file path
+    pub(crate) file_path: PathBuf,
+    /// Extraction result (Ok with manifest or Err with message)
+    pub(crate) extraction_result: Result<ManifestExtractionResult, String>,
+    /// Validation result when extraction succeeded
+    pub(crate) validation_result: Option<ValidationResult>,
+    /// Features newer than this build understands (from the manifest or the read error)
+    pub(crate) unsupported: Vec<UnsupportedFeature>,
+    /// Remnants suggesting the credentials were stripped (only when no manifest was read)
+    pub(crate) credential_removal: Vec<RemovalSign>,
+    /// Set when the file carries no manifest at all (rather than one that failed to read)
+    pub(crate) no_manifest: Option<AssetInfo>,
+    /// Outcome of the last sidecar/remote manifest search, when it found nothing usable
+    pub(crate) manifest_search: Option<String>,
+}
+
+/// Load one document from disk. Uses security-scoped access on macOS when needed.
+/// Uses the given Settings for extraction so trust validation is applied consistently (no thread-local reliance).
+pub(crate) fn load_document(
+    file_path: PathBuf,
+    validation: &Result<ValidationProfile, String>,
+    extraction_settings: &Settings,
+) -> LoadedDocument {
+    let mut credential_removal = Vec::new();
+    let mut no_manifest = None;
+    let extract = || {
+        extract_crjson_manifest_hashed(&file_path, extraction_settings).map_err(|e| {
+            // A file refused for exceeding a resource limit is not a stripped one
+            if let Some(limit) = crtool::limits::limit_exceeded(&e) {
+                return limit.to_string();
+            }
+            credential_removal = detect_credential_removal(&file_path).unwrap_or_default();
+            if is_no_manifest_error(&e) {
+                no_manifest = Some(AssetInfo::read(&file_path));
+            }
+            e.to_string()
+        })
+    };
+    let result = {
+        #[cfg(target_os = "macos")]
+        {
+            crate::security_scoped::with_security_scoped_access(&file_path, extract)
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            extract()
+        }
+    };
+
+    let mut document = document_from_result(file_path, result, validation);
+    document.credential_removal = credential_removal;
+    document.no_manifest = no_manifest;
+    document
+}
+
+/// Document for an extraction outcome: validation of the manifest against the schemas (or the
+/// reason they could not be loaded), or the unsupported features named by the error.
+fn document_from_result(
+    file_path: PathBuf,
+    result: Result<ManifestExtractionResult, String>,
+    validation: &Result<ValidationProfile, String>,
+) -> LoadedDocument {
+    let (extraction_result, validation_result) = match result {
+        Ok(extract_result) => {
+            let file = file_path.to_string_lossy().to_string();
+            let validation = match validation {
+                Ok(profile) => ValidationResult {
+                    file_path: file,
+                    ..profile.validate(&extract_result.manifest_value)
+                },
+                Err(e) => ValidationResult::from_findings(
+                    file,
+                    vec![crtool::ValidationError {
+                        instance_path: "schema".to_string(),
+                        message: e.clone(),
+                        severity: crtool::Severity::Error,
+                        rule: "schema".to_string(),
+                        schema: None,
+                    }],
+                ),
+            };
+            (Ok(extract_result), Some(validation))
+        }
+        Err(e) => (Err(e), None),
+    };
+
+    let unsupported = match &extraction_result {
+        Ok(extract_result) => detect_unsupported(&extract_result.manifest_value),
+        Err(e) => unsupported_from_error(e).into_iter().collect(),
+    };
+
+    LoadedDocument {
+        file_path,
+        extraction_result,
+        validation_result,
+        unsupported,
+        credential_removal: Vec::new(),
+        no_manifest: None,
+        manifest_search: None,
+    }
+}
+
+/// Where a manifest for an asset without an embedded one was found.
+enum ManifestSource {
+    /// `.c2pa` file next to the asset
+    Sidecar(PathBuf),
+    /// URL from the asset's XMP `dcterms:provenance`
+    Remote(String),
+}
+
+impl std::fmt::Display for ManifestSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestSource::Sidecar(path) => write!(f, "sidecar {}", path.display()),
+            ManifestSource::Remote(url) => write!(f, "remote manifest {}", url),
+        }
+    }
+}
+
+/// The `.c2pa` sidecar crTool looks for next to `path`: the detached store of a text document,
+/// or `photo.c2pa` for `photo.jpg`, as c2patool names sidecars.
+fn sidecar_path(path: &Path) -> PathBuf {
+    if crtool::documents::is_text_document_path(path) {
+        crtool::documents::detached_store_path(path)
+    } else {
+        path.with_extension("c2pa")
+    }
+}
+
+/// Sidecar or remote manifest reference for `path`; the sidecar wins when both exist.
+fn find_manifest_source(path: &Path) -> Option<ManifestSource> {
+    let sidecar = sidecar_path(path);
+    if sidecar != path && sidecar.is_file() {
+        return Some(ManifestSource::Sidecar(sidecar));
+    }
+    let data = std::fs::read(path).ok()?;
+    crtool::removal::remote_manifest_url(&data).map(ManifestSource::Remote)
+}
+
+fn fetch_remote_manifest(url: &str) -> anyhow::Result<Vec<u8>> {
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("crTool-gui/1.0")
+        .timeout(REMOTE_MANIFEST_TIMEOUT)
+        .build()?;
+    let response = client.get(url).send()?.error_for_status()?;
+    Ok(response.bytes()?.to_vec())
+}
+
+/// Loads documents with the current trust settings and remembers what was opened last.
+pub(crate) struct AppController {
+    /// Schemas documents are validated against, in order (Preferences → Validation Schemas):
+    /// schema file paths, or `bundled` for the crJSON schema.
+    pub(crate) schemas: Vec<String>,
+    /// `schemas` compiled once for every document, or why they could not be loaded.
+    pub(crate) validation: Result<ValidationProfile, String>,
+    /// Settings used for manifest extraction (trust lists or verify_trust disabled).
+    pub(crate) extraction_settings: Settings,
+    /// User trust policy that `extraction_settings` was built from (Preferences → Trust Anchors).
+    pub(crate) trust_config: TrustConfig,
+    /// Default trust lists fetched at startup, if they were reachable.
+    pub(crate) default_lists: Option<TrustLists>,
+    /// Most recently opened (or dropped) file, summarized in mini mode.
+    pub(crate) last_opened: Option<PathBuf>,
+}
+
+impl AppController {
+    pub(crate) fn new(trust_config: TrustConfig, default_lists: Option<TrustLists>) -> Self {
+        Self {
+            schemas: vec![BUNDLED_SCHEMA_SPEC.to_string()],
+            validation: ValidationProfile::from_specs(&[BUNDLED_SCHEMA_SPEC])
+                .map_err(|e| e.to_string()),
+            extraction_settings: util::gui_extraction_settings(
+                &trust_config,
+                default_lists.as_ref(),
+            ),
+            trust_config,
+            default_lists,
+            last_opened: None,
+        }
+    }
+
+    /// Whether `path` can be opened: an existing file in a C2PA-supported format.
+    pub(crate) fn can_open(path: &Path) -> bool {
+        path.is_file() && is_supported_asset_path(path)
+    }
+
+    /// Load every openable path, in order; others are skipped.
+    pub(crate) fn open(&mut self, paths: Vec<PathBuf>) -> Vec<LoadedDocument> {
+        let mut documents = Vec::new();
+        for path in paths {
+            if !Self::can_open(&path) {
+                continue;
+            }
+            self.last_opened = Some(path.clone());
+            documents.push(load_document(
+                path,
+                &self.validation,
+                &self.extraction_settings,
+            ));
+        }
+        documents
+    }
+
+    /// Read a document again with the current settings.
+    pub(crate) fn reload(&self, document: &LoadedDocument) -> LoadedDocument {
+        load_document(
+            document.file_path.clone(),
+            &self.validation,
+            &self.extraction_settings,
+        )
+    }
+
+    /// Look for a manifest of a document that has none embedded: a `.c2pa` sidecar next to it,
+    /// then a remote manifest its XMP points to. Returns the document read with that manifest,
+    /// or the same document with `manifest_search` explaining what was (not) found.
+    pub(crate) fn find_manifest(&self, document: &LoadedDocument) -> LoadedDocument {
+        let path = &document.file_path;
+        let Some(source) = find_manifest_source(path) else {
+            let mut unchanged = document.clone();
+            unchanged.manifest_search = Some(format!(
+                "No sidecar ({}) or remote manifest reference found.",
+                sidecar_path(path).display()
+            ));
+            return unchanged;
+        };
+        let store = match &source {
+            ManifestSource::Sidecar(sidecar) => {
+                crtool::jumbf::read_store_file(sidecar).map_err(Into::into)
+            }
+            ManifestSource::Remote(url) => fetch_remote_manifest(url),
+        };
+        let result = store.and_then(|store| {
+            Ok(extract_crjson_manifest_with_store(
+                path,
+                &store,
+                &self.extraction_settings,
+            )?)
+        });
+        match result {
+            Ok(manifest) => document_from_result(path.clone(), Ok(manifest), &self.validation),
+            Err(e) => {
+                let mut unchanged = document.clone();
+                unchanged.manifest_search = Some(format!(
+                    "Found {}, but it could not be read: {:#}",
+                    source, e
+                ));
+                unchanged
+            }
+        }
+    }
+
+    /// Switch to a new trust policy and save it to `save_path`. Fails without changing anything
+    /// when the policy's trust lists cannot be used; returns the save error (the policy is
+    /// applied either way). Open documents must be reloaded to pick up the change.
+    pub(crate) fn set_trust_config(
+        &mut self,
+        config: TrustConfig,
+        save_path: &Path,
+    ) -> anyhow::Result<Option<String>> {
+        let settings = config.settings(self.default_lists.as_ref())?;
+        let save_error = config
+            .save(save_path)
+            .err()
+            .map(|e| format!("Applied, but not saved: {:#}", e));
+        self.extraction_settings = settings;
+        self.trust_config = config;
+        Ok(save_error)
+    }
+
+    /// Validate documents against `schemas` (file paths, or `bundled`), applied in order. Fails
+    /// without changing anything when a schema cannot be loaded. Open documents must be reloaded
+    /// to pick up the change.
+    pub(crate) fn set_schemas(&mut self, schemas: Vec<String>) -> anyhow::Result<()> {
+        if schemas.is_empty() {
+            anyhow::bail!("At least one validation schema is required");
+        }
+        self.validation = Ok(ValidationProfile::from_specs(&schemas)?);
+        self.schemas = schemas;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../tests/fixtures/assets")
+            .join(name)
+    }
+
+    #[test]
+    fn test_open_skips_unsupported_and_reports_errors() {
+        let mut controller = AppController::new(TrustConfig::default(), None);
+        let documents = controller.open(vec![
+            sample("../minimal_valid_crjson.json"),
+            sample("missing.jpg"),
+            sample("PXL_20260208_202351558.jpg"),
+            sample("Dog.png"),
+        ]);
+        assert_eq!(documents.len(), 2);
+        assert_eq!(controller.last_opened, Some(sample("Dog.png")));
+
+        let signed = &documents[0];
+        assert!(signed.extraction_result.is_ok());
+        assert!(signed.validation_result.is_some());
+
+        // No manifest: an error state without validation
+        let unsigned = &documents[1];
+        assert!(unsigned.extraction_result.is_err());
+        assert!(unsigned.validation_result.is_none());
+
+        let reloaded = controller.reload(signed);
+        assert_eq!(reloaded.file_path, signed.file_path);
+        assert!(reloaded.extraction_result.is_ok());
+    }
+
+    #[test]
+    fn test_no_manifest_info_and_sidecar_search() {
+        let controller = AppController::new(TrustConfig::default(), None);
+        let dir = std::env::temp_dir().join(format!("crtool-gui-sidecar-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let asset = dir.join("Dog.jpg");
+        std::fs::copy(sample("Dog.jpg"), &asset).unwrap();
+
+        let unsigned = load_document(
+            asset.clone(),
+            &controller.validation,
+            &controller.extraction_settings,
+        );
+        let info = unsigned.no_manifest.as_ref().expect("no manifest detected");
+        assert_eq!(info.format.map(|f| f.extension), Some("jpg"));
+        assert_eq!(info.sha256.len(), 64);
+        assert!(info.dimensions.is_some());
+
+        let not_found = controller.find_manifest(&unsigned);
+        assert!(not_found.extraction_result.is_err());
+        assert!(not_found
+            .manifest_search
+            .as_deref()
+            .is_some_and(|m| m.contains("Dog.c2pa")));
+
+        // A sidecar store is read (its hash binding is for another asset, so it won't validate)
+        let store =
+            crtool::jumbf::load_manifest_store(sample("PXL_20260208_202351558.jpg")).unwrap();
+        std::fs::write(dir.join("Dog.c2pa"), store).unwrap();
+        let found = controller.find_manifest(&unsigned);
+        assert!(found.extraction_result.is_ok());
+        assert!(found.no_manifest.is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_set_schemas_validates_against_each() {
+        let mut controller = AppController::new(TrustConfig::default(), None);
+        let dir = std::env::temp_dir().join(format!("crtool-gui-schemas-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let extension = dir.join("org.schema.json");
+        std::fs::write(&extension, r#"{"required": ["orgReviewer"]}"#).unwrap();
+        let extension = extension.to_string_lossy().to_string();
+
+        let documents = controller.open(vec![sample("PXL_20260208_202351558.jpg")]);
+        controller
+            .set_schemas(vec![BUNDLED_SCHEMA_SPEC.to_string(), extension.clone()])
+            .unwrap();
+        let reloaded = controller.reload(&documents[0]);
+        let validation = reloaded.validation_result.expect("validated");
+        assert!(!validation.is_valid);
+        assert!(validation
+            .errors
+            .iter()
+            .any(|e| e.schema.as_deref() == Some(extension.as_str())));
+
+        assert!(controller.set_schemas(Vec::new()).is_err());
+        assert!(controller
+            .set_schemas(vec![dir.join("missing.json").to_string_lossy().to_string()])
+            .is_err());
+        assert_eq!(controller.schemas.len(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -12,91 +12,433 @@ governing permissions and limitations under the License.
 
 //! Document tab state and UI: one loaded file per tab (manifest, validation, tree, raw JSON).
 
+use crate::app::AppCommand;
+use crate::assessment::{show_trust_assessment, TrustAssessment};
+use crate::controller::{AssetInfo, LoadedDocument};
+use crate::drag_out;
 use crate::manifest_ui::{
     display_manifest_ingredient_tree, get_claim_type, get_generator_name,
     get_signature_issued_info, get_timestamp_info, get_trust_status, get_validation_failures,
     ValidationFailureEntry,
 };
+use crate::references::{collect_references, show_external_references, ReachabilityMap};
+use crate::thumbnails::{ThumbnailCache, PREVIEW_MAX_SIZE};
+use crate::tree_view::{show_large_arrays, LazyManifestTree, PreparedTree, TreeViewSettings};
 use crate::util;
-use crtool::{
-    extract_crjson_manifest_with_settings, validate_json_value, ManifestExtractionResult, Settings,
-    ValidationResult,
-};
+use crtool::removal::RemovalSign;
+use crtool::unsupported::UnsupportedFeature;
+use crtool::{claim_thumbnail_uri, ManifestExtractionResult, Settings, Severity, ValidationError};
 use eframe::egui;
 use egui_code_editor::{CodeEditor, ColorTheme};
 use egui_json_tree::{DefaultExpand, JsonTree};
 use egui_twemoji::EmojiLabel;
-use std::path::{Path, PathBuf};
 
 /// Width of the draggable resize handle between the two columns (px).
 const RESIZE_HANDLE_WIDTH: f32 = 6.0;
 /// Minimum fraction of total width for each column (so neither collapses).
 const MIN_PANEL_RATIO: f32 = 0.15;
 const MAX_PANEL_RATIO: f32 = 0.85;
+/// Manifests larger than this (bytes) use the read-only virtualized raw JSON view.
+const LARGE_RAW_JSON_BYTES: usize = 1024 * 1024;
+/// Scroll step for ↑/↓ in a focused panel (px).
+const KEY_SCROLL_STEP: f32 = 40.0;
+
+/// The two side-by-side panels of a document tab.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DocumentPanel {
+    ManifestData,
+    Tree,
+}
+
+/// View commands sent to the focused tab from keyboard shortcuts and the menus.
+#[derive(Clone, Copy)]
+pub(crate) enum DocumentCommand {
+    ToggleRawJson,
+    FocusPanel(DocumentPanel),
+    Find,
+}
+
+/// Cached raw JSON view: an editor buffer for normal documents, or line offsets for the
+/// virtualized view of large ones. The manifest JSON does not change once a tab is loaded.
+#[derive(Clone)]
+enum RawJsonView {
+    Editor(String),
+    Lines(Vec<usize>),
+}
+
+impl RawJsonView {
+    fn new(json: &str) -> Self {
+        if json.len() > LARGE_RAW_JSON_BYTES {
+            let starts = std::iter::once(0)
+                .chain(json.match_indices('\n').map(|(i, _)| i + 1))
+                .collect();
+            RawJsonView::Lines(starts)
+        } else {
+            RawJsonView::Editor(json.to_string())
+        }
+    }
+}
+
+/// Read-only raw JSON for large documents: only the visible lines are laid out.
+fn show_raw_json_lines(ui: &mut egui::Ui, json: &str, line_starts: &[usize]) {
+    ui.label(
+        egui::RichText::new(format!(
+            "Large document ({} lines): read-only view. Use File → Save As... to export.",
+            line_starts.len()
+        ))
+        .size(12.0)
+        .color(egui::Color32::from_rgb(100, 100, 100)),
+    );
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    egui::ScrollArea::both()
+        .id_salt("raw_json_lines")
+        .auto_shrink([false, false])
+        .show_rows(ui, row_height, line_starts.len(), |ui, rows| {
+            for i in rows {
+                let end = line_starts.get(i + 1).copied().unwrap_or(json.len());
+                let line = json[line_starts[i]..end].trim_end_matches(['\n', '\r']);
+                ui.monospace(line);
+            }
+        });
+}
 
 /// Per-document state for each tab in the dock.
 #[derive(Clone)]
 pub(crate) struct DocumentTab {
-    /// Loaded file path
-    pub(crate) file_path: PathBuf,
-    /// Extraction result (Ok with manifest or Err with message)
-    pub(crate) extraction_result: Result<ManifestExtractionResult, String>,
-    /// Validation result when extraction succeeded
-    pub(crate) validation_result: Option<ValidationResult>,
+    /// The file as loaded by the controller
+    pub(crate) document: LoadedDocument,
     /// Whether to show the raw JSON view
     show_raw_json: bool,
-    /// Buffer for raw JSON view (refreshed from manifest each frame)
-    raw_json_buffer: String,
+    /// Raw JSON view state, built the first time the raw view is shown
+    raw_json: Option<RawJsonView>,
     /// Split ratio for left/right panels (0..1)
     split_ratio: f32,
+    /// Reachability of URIs from external-reference / asset-ref assertions (filled on demand)
+    reference_reachability: ReachabilityMap,
+    /// Ingredient thumbnail textures, decoded on demand
+    thumbnails: ThumbnailCache,
+    /// Trust Manifest assessment, once the user has loaded one
+    trust_assessment: Option<TrustAssessment>,
+    /// Panel that receives keyboard scrolling (set by Cmd/Ctrl+1/2)
+    focused_panel: Option<DocumentPanel>,
+    /// Search text for the manifest data panel; `Some` while the search bar is open
+    search: Option<String>,
+    /// Move keyboard focus to the search field on the next frame
+    focus_search: bool,
+    /// Manifest value as shown in the Manifest Data tree (built on first display)
+    prepared_tree: Option<PreparedTree>,
+    /// Per-manifest Manifest Data list used instead for very large stores
+    lazy_tree: Option<LazyManifestTree>,
+    /// Tree settings the Manifest Data tree was last expanded with
+    applied_tree_settings: Option<TreeViewSettings>,
 }
 
-/// Load one document from disk and return a DocumentTab. Uses security-scoped access on macOS when needed.
-/// Uses the given Settings for extraction so trust validation is applied consistently (no thread-local reliance).
-pub(crate) fn load_document(
-    file_path: PathBuf,
-    schema_path: &Path,
-    extraction_settings: &Settings,
-) -> DocumentTab {
-    let extract = || {
-        extract_crjson_manifest_with_settings(&file_path, extraction_settings)
-            .map_err(|e| e.to_string())
-    };
-    let result = {
-        #[cfg(target_os = "macos")]
-        {
-            crate::security_scoped::with_security_scoped_access(&file_path, extract)
+impl DocumentTab {
+    /// Tab for a loaded document. `extraction_settings` are used to read ingredient thumbnails.
+    pub(crate) fn new(document: LoadedDocument, extraction_settings: &Settings) -> Self {
+        let thumbnails =
+            ThumbnailCache::new(document.file_path.clone(), extraction_settings.clone());
+        DocumentTab {
+            document,
+            show_raw_json: false,
+            raw_json: None,
+            split_ratio: 0.5,
+            reference_reachability: ReachabilityMap::default(),
+            thumbnails,
+            trust_assessment: None,
+            focused_panel: None,
+            search: None,
+            focus_search: false,
+            prepared_tree: None,
+            lazy_tree: None,
+            applied_tree_settings: None,
         }
-        #[cfg(not(target_os = "macos"))]
-        {
-            extract()
+    }
+
+    pub(crate) fn apply_command(&mut self, command: DocumentCommand) {
+        match command {
+            DocumentCommand::ToggleRawJson => self.show_raw_json = !self.show_raw_json,
+            DocumentCommand::FocusPanel(panel) => {
+                self.show_raw_json = false;
+                self.focused_panel = Some(panel);
+            }
+            DocumentCommand::Find => {
+                self.show_raw_json = false;
+                self.search.get_or_insert_with(String::new);
+                self.focus_search = true;
+                self.focused_panel = Some(DocumentPanel::ManifestData);
+            }
         }
-    };
+    }
+}
 
-    let (extraction_result, validation_result) = match result {
-        Ok(extract_result) => {
-            let validation = validate_json_value(&extract_result.manifest_value, schema_path)
-                .unwrap_or_else(|e| ValidationResult {
-                    file_path: file_path.to_string_lossy().to_string(),
-                    is_valid: false,
-                    errors: vec![crtool::ValidationError {
-                        instance_path: "schema".to_string(),
-                        message: e.to_string(),
-                    }],
-                });
-            (Ok(extract_result), Some(validation))
+/// Panel heading, highlighted when the panel has keyboard focus.
+fn show_panel_heading(ui: &mut egui::Ui, text: &str, focused: bool) {
+    let mut text = egui::RichText::new(text).size(16.0);
+    if focused {
+        text = text
+            .color(egui::Color32::from_rgb(70, 130, 220))
+            .underline();
+    }
+    EmojiLabel::new(text).show(ui);
+}
+
+/// Scroll the enclosing scroll area with ↑/↓ and Page Up/Page Down (unless a text field has focus).
+fn scroll_with_keys(ui: &mut egui::Ui, page_height: f32) {
+    if ui.ctx().wants_keyboard_input() {
+        return;
+    }
+    let delta = ui.input(|i| {
+        let mut delta = 0.0;
+        if i.key_pressed(egui::Key::ArrowDown) {
+            delta -= KEY_SCROLL_STEP;
+        }
+        if i.key_pressed(egui::Key::ArrowUp) {
+            delta += KEY_SCROLL_STEP;
+        }
+        if i.key_pressed(egui::Key::PageDown) {
+            delta -= page_height;
         }
-        Err(e) => (Err(e), None),
+        if i.key_pressed(egui::Key::PageUp) {
+            delta += page_height;
+        }
+        delta
+    });
+    if delta != 0.0 {
+        ui.scroll_with_delta(egui::vec2(0.0, delta));
+    }
+}
+
+/// Search bar for the manifest data panel. Returns true when the search text changed.
+fn show_search_bar(ui: &mut egui::Ui, search: &mut Option<String>, focus: &mut bool) -> bool {
+    let Some(text) = search.as_mut() else {
+        return false;
     };
+    let mut changed = false;
+    let mut close = false;
+    ui.horizontal(|ui| {
+        EmojiLabel::new(egui::RichText::new("🔍 Search:").size(15.0)).show(ui);
+        let response = ui.add(
+            egui::TextEdit::singleline(text)
+                .hint_text("key or value in manifest data")
+                .desired_width(280.0),
+        );
+        if *focus {
+            response.request_focus();
+            *focus = false;
+        }
+        changed = response.changed();
+        if response.has_focus() && ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+            close = true;
+        }
+        if ui.button("✖").on_hover_text("Close search").clicked() {
+            close = true;
+        }
+    });
+    if close {
+        *search = None;
+        changed = true;
+    }
+    changed
+}
 
-    DocumentTab {
-        file_path,
-        extraction_result,
-        validation_result,
-        show_raw_json: false,
-        raw_json_buffer: String::new(),
-        split_ratio: 0.5,
+/// Banner listing unsupported features (future claim/assertion versions, unknown algorithms).
+fn show_unsupported_banner(ui: &mut egui::Ui, features: &[UnsupportedFeature]) {
+    if features.is_empty() {
+        return;
     }
+    egui::Frame::group(ui.style())
+        .fill(egui::Color32::from_rgb(255, 243, 205))
+        .show(ui, |ui| {
+            EmojiLabel::new(
+                egui::RichText::new(format!(
+                    "⚠️ Contains {} feature(s) this version of crTool does not support",
+                    features.len()
+                ))
+                .size(15.0)
+                .color(egui::Color32::from_rgb(133, 100, 4)),
+            )
+            .show(ui);
+            for feature in features {
+                let location = feature
+                    .manifest
+                    .as_deref()
+                    .map(|m| format!(" in {}", m))
+                    .unwrap_or_default();
+                ui.label(
+                    egui::RichText::new(format!("• {}{}", feature, location))
+                        .color(egui::Color32::from_rgb(90, 70, 10)),
+                );
+            }
+        });
+    ui.add_space(4.0);
+}
+
+/// Banner shown instead of a bare read error when credentials look stripped from the file.
+fn show_credential_removal_banner(ui: &mut egui::Ui, signs: &[RemovalSign]) {
+    if signs.is_empty() {
+        return;
+    }
+    egui::Frame::group(ui.style())
+        .fill(egui::Color32::from_rgb(255, 228, 225))
+        .show(ui, |ui| {
+            EmojiLabel::new(
+                egui::RichText::new("🧹 Content Credentials likely removed")
+                    .size(15.0)
+                    .color(egui::Color32::from_rgb(150, 40, 40)),
+            )
+            .show(ui);
+            ui.label(
+                egui::RichText::new(
+                    "No manifest could be read, but the file still carries traces of one:",
+                )
+                .color(egui::Color32::from_rgb(110, 40, 40)),
+            );
+            for sign in signs {
+                ui.label(
+                    egui::RichText::new(format!("• {}", sign))
+                        .color(egui::Color32::from_rgb(110, 40, 40)),
+                );
+            }
+        });
+    ui.add_space(4.0);
+}
+
+/// Panel shown instead of an error when the file simply has no Content Credentials: basic
+/// asset facts and a button to look for a sidecar or remote manifest. Returns true when the
+/// search is requested.
+fn show_no_credentials_panel(
+    ui: &mut egui::Ui,
+    info: &AssetInfo,
+    search_result: Option<&str>,
+) -> bool {
+    let mut search = false;
+    egui::Frame::group(ui.style())
+        .fill(egui::Color32::from_rgb(235, 240, 248))
+        .show(ui, |ui| {
+            EmojiLabel::new(
+                egui::RichText::new("ℹ️ No Content Credentials")
+                    .size(15.0)
+                    .color(egui::Color32::from_rgb(40, 70, 120)),
+            )
+            .show(ui);
+            ui.label(
+                egui::RichText::new("This file does not contain an embedded C2PA manifest.")
+                    .color(egui::Color32::from_rgb(64, 64, 64)),
+            );
+            ui.add_space(4.0);
+            egui::Grid::new("no_credentials_info")
+                .num_columns(2)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    let format = info
+                        .format
+                        .map(|f| format!("{} ({})", f.extension.to_uppercase(), f.mime))
+                        .unwrap_or_else(|| "Unknown".to_string());
+                    ui.label("Format:");
+                    ui.label(format);
+                    ui.end_row();
+                    if let Some((width, height)) = info.dimensions {
+                        ui.label("Dimensions:");
+                        ui.label(format!("{} × {} px", width, height));
+                        ui.end_row();
+                    }
+                    ui.label("Size:");
+                    ui.label(format!("{} bytes", info.size_bytes));
+                    ui.end_row();
+                    ui.label("SHA-256:");
+                    ui.label(egui::RichText::new(&info.sha256).monospace());
+                    ui.end_row();
+                });
+            ui.add_space(4.0);
+            if ui
+                .button("🔍 Search for a sidecar or remote manifest")
+                .on_hover_text(
+                    "Look for a .c2pa file next to this one, then a remote manifest referenced by its XMP",
+                )
+                .clicked()
+            {
+                search = true;
+            }
+            if let Some(message) = search_result {
+                ui.label(
+                    egui::RichText::new(message).color(egui::Color32::from_rgb(133, 100, 4)),
+                );
+            }
+        });
+    search
+}
+
+/// Collapsible preview of the asset next to the active manifest's claim thumbnail, to check
+/// that the credentials describe what is on screen.
+fn show_preview(
+    ui: &mut egui::Ui,
+    thumbnails: &mut ThumbnailCache,
+    manifest: &ManifestExtractionResult,
+) {
+    egui::CollapsingHeader::new("🖼 Preview")
+        .id_salt("asset_preview")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.horizontal_top(|ui| {
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new("Asset").strong());
+                    thumbnails.show_asset(ui);
+                });
+                ui.add_space(16.0);
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new("Claim thumbnail").strong());
+                    match claim_thumbnail_uri(&manifest.manifest_value, &manifest.active_label) {
+                        Some(uri) => thumbnails.show_resource(
+                            ui,
+                            &manifest.active_label,
+                            &uri,
+                            PREVIEW_MAX_SIZE,
+                        ),
+                        None => {
+                            ui.label(
+                                egui::RichText::new("None in the active manifest")
+                                    .size(12.0)
+                                    .color(egui::Color32::from_rgb(64, 64, 64)),
+                            );
+                        }
+                    }
+                });
+            });
+        });
+}
+
+/// Renders one schema validation finding, with an icon and color for its severity.
+fn show_schema_finding(ui: &mut egui::Ui, finding: &ValidationError) {
+    let (icon, label, color) = match finding.severity {
+        Severity::Error => ("❌", "Error", egui::Color32::from_rgb(255, 150, 150)),
+        Severity::Warning => ("⚠️", "Warning", egui::Color32::from_rgb(230, 170, 60)),
+        Severity::Info => ("ℹ️", "Info", egui::Color32::from_rgb(120, 150, 190)),
+    };
+    ui.group(|ui| {
+        EmojiLabel::new(
+            egui::RichText::new(format!("📍 Path: {}", finding.instance_path))
+                .size(14.0)
+                .color(egui::Color32::from_rgb(255, 200, 100)),
+        )
+        .show(ui);
+        let source: Vec<&str> = [Some(finding.rule.as_str()), finding.schema.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|s| !s.is_empty())
+            .collect();
+        let rule = if source.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", source.join(", "))
+        };
+        EmojiLabel::new(
+            egui::RichText::new(format!("{} {}: {}{}", icon, label, finding.message, rule))
+                .size(14.0)
+                .color(color),
+        )
+        .show(ui);
+    });
 }
 
 /// Renders one validation failure entry (code, optional explanation, url, source).
@@ -136,17 +478,30 @@ fn show_validation_failure_entry(ui: &mut egui::Ui, entry: &ValidationFailureEnt
 }
 
 /// Renders one document tab: manifest info, validation, raw JSON toggle, and manifest/tree panels.
-pub(crate) fn show_document_tab_ui(ui: &mut egui::Ui, tab: &mut DocumentTab) {
-    let manifest = match &tab.extraction_result {
-        Ok(m) => m.clone(),
-        Err(e) => {
+/// Returns a command for the app when the tab asks for one (a manifest search).
+pub(crate) fn show_document_tab_ui(
+    ui: &mut egui::Ui,
+    tab: &mut DocumentTab,
+    tree_settings: &TreeViewSettings,
+) -> Option<AppCommand> {
+    show_unsupported_banner(ui, &tab.document.unsupported);
+    show_credential_removal_banner(ui, &tab.document.credential_removal);
+
+    let manifest = match (&tab.document.extraction_result, &tab.document.no_manifest) {
+        (Ok(m), _) => m,
+        (Err(_), Some(info)) => {
+            let search = tab.document.manifest_search.as_deref();
+            return show_no_credentials_panel(ui, info, search)
+                .then(|| AppCommand::FindManifest(tab.document.file_path.clone()));
+        }
+        (Err(e), None) => {
             EmojiLabel::new(
                 egui::RichText::new(format!("❌ Error: {}", e))
                     .size(15.0)
                     .color(egui::Color32::from_rgb(230, 80, 80)),
             )
             .show(ui);
-            return;
+            return None;
         }
     };
 
@@ -211,81 +566,97 @@ pub(crate) fn show_document_tab_ui(ui: &mut egui::Ui, tab: &mut DocumentTab) {
 
     if let Some(trust_status) = get_trust_status(&manifest.manifest_value, &manifest.active_label) {
         ui.horizontal(|ui| {
-            let (icon, color, text) = match trust_status.as_str() {
-                "signingCredential.trusted" => {
-                    ("🔒", egui::Color32::from_rgb(0, 100, 0), "Trusted")
-                }
-                "signingCredential.untrusted" => {
-                    ("🚫", egui::Color32::from_rgb(255, 100, 100), "Untrusted")
-                }
-                _ => (
-                    "⚠️",
-                    egui::Color32::from_rgb(64, 64, 64),
-                    trust_status.as_str(),
-                ),
+            let (presentation, color) = util::trust_style(&trust_status);
+            let response = EmojiLabel::new(
+                egui::RichText::new(format!(
+                    "{} Trust Status: {}",
+                    presentation.icon, presentation.label
+                ))
+                .size(15.0)
+                .color(color),
+            )
+            .show(ui);
+            let tooltip = match &presentation.description {
+                Some(description) => format!("{description}\n\nStatus: {trust_status}"),
+                None => format!("Status: {trust_status}"),
             };
+            response.on_hover_text(tooltip);
+        });
+    }
+
+    if let Some(ref asset_hash) = manifest.asset_hash {
+        ui.horizontal(|ui| {
             EmojiLabel::new(
-                egui::RichText::new(format!("{} Trust Status: {}", icon, text))
+                egui::RichText::new("#️⃣ Asset SHA-256:")
                     .size(15.0)
-                    .color(color),
+                    .color(egui::Color32::from_rgb(100, 120, 140)),
             )
             .show(ui);
+            ui.label(egui::RichText::new(asset_hash).monospace());
         });
     }
 
+    let references = collect_references(&manifest.manifest_value, &manifest.active_label);
+    show_external_references(ui, &references, &tab.reference_reachability);
+    show_trust_assessment(ui, &mut tab.trust_assessment, manifest);
+    show_preview(ui, &mut tab.thumbnails, manifest);
+
     ui.separator();
 
-    if let Some(ref validation) = tab.validation_result {
+    if let Some(ref validation) = tab.document.validation_result {
         let manifest_failures =
             get_validation_failures(&manifest.manifest_value, &manifest.active_label);
-        let has_schema_errors = !validation.errors.is_empty();
+        let has_schema_findings = !validation.errors.is_empty();
         let has_manifest_failures = !manifest_failures.is_empty();
+        let schema_errors = validation.count(Severity::Error);
+        let warnings = validation.count(Severity::Warning);
+        let warning_note = if warnings > 0 {
+            format!(" ({} warning(s))", warnings)
+        } else {
+            String::new()
+        };
 
         if validation.is_valid && !has_manifest_failures {
             EmojiLabel::new(
-                egui::RichText::new("✅ Manifest is valid!")
+                egui::RichText::new(format!("✅ Manifest is valid!{}", warning_note))
                     .size(15.0)
                     .color(egui::Color32::from_rgb(0, 100, 0)),
             )
             .show(ui);
         } else {
-            let total_errors = validation.errors.len() + manifest_failures.len();
+            let total_errors = schema_errors + manifest_failures.len();
             EmojiLabel::new(
-                egui::RichText::new(format!("❌ Validation failed ({} error(s))", total_errors))
-                    .size(15.0)
-                    .color(egui::Color32::from_rgb(255, 100, 100)),
+                egui::RichText::new(format!(
+                    "❌ Validation failed ({} error(s)){}",
+                    total_errors, warning_note
+                ))
+                .size(15.0)
+                .color(egui::Color32::from_rgb(255, 100, 100)),
             )
             .show(ui);
+        }
 
+        if has_schema_findings || has_manifest_failures {
             ui.separator();
 
             egui::ScrollArea::vertical()
                 .id_salt("validation_errors")
                 .max_height(200.0)
                 .show(ui, |ui| {
-                    if has_schema_errors {
-                        EmojiLabel::new(
-                            egui::RichText::new("⚠️  Schema validation errors:").size(16.0),
-                        )
-                        .show(ui);
-                        for error in &validation.errors {
-                            ui.group(|ui| {
-                                EmojiLabel::new(
-                                    egui::RichText::new(format!(
-                                        "📍 Path: {}",
-                                        error.instance_path
-                                    ))
-                                    .size(14.0)
-                                    .color(egui::Color32::from_rgb(255, 200, 100)),
-                                )
-                                .show(ui);
-                                EmojiLabel::new(
-                                    egui::RichText::new(format!("❌ Error: {}", error.message))
-                                        .size(14.0)
-                                        .color(egui::Color32::from_rgb(255, 150, 150)),
-                                )
-                                .show(ui);
-                            });
+                    if has_schema_findings {
+                        let heading = if schema_errors > 0 {
+                            "⚠️  Schema validation errors:"
+                        } else if warnings > 0 {
+                            "⚠️  Schema validation warnings:"
+                        } else {
+                            "ℹ️  Schema validation notes:"
+                        };
+                        EmojiLabel::new(egui::RichText::new(heading).size(16.0)).show(ui);
+                        // Errors first, then warnings, then notes.
+                        let mut findings: Vec<_> = validation.errors.iter().collect();
+                        findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+                        for finding in findings {
+                            show_schema_finding(ui, finding);
                         }
                         if has_manifest_failures {
                             ui.add_space(8.0);
@@ -315,22 +686,39 @@ pub(crate) fn show_document_tab_ui(ui: &mut egui::Ui, tab: &mut DocumentTab) {
             egui::RichText::new("Show Raw JSON (replaces tree and manifest data)").size(15.0),
         )
         .show(ui);
+        ui.add_space(12.0);
+        drag_out::show_drag_handle(ui, &tab.document.file_path, &manifest.manifest_json);
     });
 
+    let search_changed = if tab.show_raw_json {
+        false
+    } else {
+        show_search_bar(ui, &mut tab.search, &mut tab.focus_search)
+    };
+
     if tab.show_raw_json {
         ui.separator();
         EmojiLabel::new(egui::RichText::new("📋 Raw JSON:").size(17.0)).show(ui);
 
-        tab.raw_json_buffer = manifest.manifest_json.clone();
-        let mut editor = CodeEditor::default()
-            .id_source("raw_json")
-            .with_rows(28)
-            .with_ui_fontsize(ui)
-            .with_theme(ColorTheme::AYU)
-            .with_syntax(util::json_syntax())
-            .with_numlines(false)
-            .vscroll(true);
-        editor.show(ui, &mut tab.raw_json_buffer);
+        let json = &manifest.manifest_json;
+        match tab.raw_json.get_or_insert_with(|| RawJsonView::new(json)) {
+            RawJsonView::Editor(buffer) => {
+                // The view is read-only: undo any edit.
+                if buffer != json {
+                    buffer.clone_from(json);
+                }
+                let mut editor = CodeEditor::default()
+                    .id_source("raw_json")
+                    .with_rows(28)
+                    .with_ui_fontsize(ui)
+                    .with_theme(ColorTheme::AYU)
+                    .with_syntax(util::json_syntax())
+                    .with_numlines(false)
+                    .vscroll(true);
+                editor.show(ui, buffer);
+            }
+            RawJsonView::Lines(line_starts) => show_raw_json_lines(ui, json, line_starts),
+        }
     } else {
         ui.separator();
         let fill_height = ui.available_height();
@@ -338,6 +726,24 @@ pub(crate) fn show_document_tab_ui(ui: &mut egui::Ui, tab: &mut DocumentTab) {
         let content_width = (total_width - RESIZE_HANDLE_WIDTH).max(0.0);
         let left_width = content_width * tab.split_ratio;
         let right_width = content_width - left_width;
+        let page_height = (fill_height - 40.0).max(KEY_SCROLL_STEP);
+        let search = tab.search.clone().unwrap_or_default();
+        let lazy = tree_settings.is_lazy(manifest.manifest_json.len());
+        if lazy {
+            if !matches!(&tab.lazy_tree, Some(t) if !t.is_stale(tree_settings)) {
+                tab.lazy_tree = Some(LazyManifestTree::new(
+                    &manifest.manifest_json,
+                    tree_settings.large_array_len,
+                ));
+            }
+        } else if !matches!(&tab.prepared_tree, Some(p) if !p.is_stale(tree_settings)) {
+            tab.prepared_tree = Some(PreparedTree::new(
+                &manifest.manifest_value,
+                tree_settings.large_array_len,
+            ));
+        }
+        let settings_changed = tab.applied_tree_settings.as_ref() != Some(tree_settings);
+        tab.applied_tree_settings = Some(*tree_settings);
 
         ui.horizontal(|ui| {
             let left_response = ui.allocate_ui_with_layout(
@@ -345,14 +751,41 @@ pub(crate) fn show_document_tab_ui(ui: &mut egui::Ui, tab: &mut DocumentTab) {
                 egui::Layout::top_down(egui::Align::Min),
                 |ui| {
                     ui.set_min_size(egui::vec2(left_width, fill_height));
-                    EmojiLabel::new(egui::RichText::new("📊 Manifest Data").size(16.0)).show(ui);
+                    let focused = tab.focused_panel == Some(DocumentPanel::ManifestData);
+                    show_panel_heading(ui, "📊 Manifest Data", focused);
                     egui::ScrollArea::vertical()
                         .id_salt("manifest_data")
                         .show(ui, |ui| {
                             ui.set_min_width((left_width - 16.0).max(0.0));
-                            JsonTree::new("manifest-data-tree", &manifest.manifest_value)
-                                .default_expand(DefaultExpand::ToLevel(2))
+                            if focused {
+                                scroll_with_keys(ui, page_height);
+                            }
+                            if let Some(lazy_tree) = tab.lazy_tree.as_mut().filter(|_| lazy) {
+                                lazy_tree.show(
+                                    ui,
+                                    &manifest.manifest_value,
+                                    tree_settings,
+                                    &manifest.active_label,
+                                    &search,
+                                    search_changed || settings_changed,
+                                );
+                                return;
+                            }
+                            let Some(prepared) = tab.prepared_tree.as_ref() else {
+                                return;
+                            };
+                            let expand = if search.is_empty() {
+                                tree_settings.default_expand(prepared.node_count)
+                            } else {
+                                DefaultExpand::SearchResults(&search)
+                            };
+                            let response = JsonTree::new("manifest-data-tree", &prepared.value)
+                                .default_expand(expand)
                                 .show(ui);
+                            if search_changed || settings_changed {
+                                response.reset_expanded(ui);
+                            }
+                            show_large_arrays(ui, &manifest.manifest_value, &prepared.large_arrays);
                         });
                 },
             );
@@ -391,21 +824,24 @@ pub(crate) fn show_document_tab_ui(ui: &mut egui::Ui, tab: &mut DocumentTab) {
                 egui::Layout::top_down(egui::Align::Min),
                 |ui| {
                     ui.set_min_size(egui::vec2(right_width, fill_height));
-                    EmojiLabel::new(
-                        egui::RichText::new("🌳 Manifest & Ingredients Tree").size(16.0),
-                    )
-                    .show(ui);
+                    let focused = tab.focused_panel == Some(DocumentPanel::Tree);
+                    show_panel_heading(ui, "🌳 Manifest & Ingredients Tree", focused);
                     egui::ScrollArea::vertical()
                         .id_salt("tree_view")
                         .show(ui, |ui| {
+                            if focused {
+                                scroll_with_keys(ui, page_height);
+                            }
                             display_manifest_ingredient_tree(
                                 ui,
                                 &manifest.manifest_value,
                                 &manifest.active_label,
+                                &mut tab.thumbnails,
                             );
                         });
                 },
             );
         });
     }
+    None
 }
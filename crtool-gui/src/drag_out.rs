@@ -0,0 +1,82 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Drag-out export of the manifest JSON. eframe has no drag-source support, so the JSON is
+//! written to a temp file and handed to the platform: a native file drag on macOS; elsewhere
+//! the file is revealed in the file manager so it can be dragged from there.
+
+use crate::util;
+use eframe::egui;
+use std::path::{Path, PathBuf};
+
+/// Temp subdirectory holding files written for drag-out.
+const DRAG_OUT_DIR: &str = "crtool-drag-out";
+
+/// Write `json` to `<scratch>/crtool-drag-out/<asset stem>-manifest.json`. The file must outlive
+/// the drag, so it is left in place (and overwritten by the next drag of the same asset).
+fn write_drag_file(asset_path: &Path, json: &str) -> std::io::Result<PathBuf> {
+    let dir = crtool::scratch::scratch_dir().join(DRAG_OUT_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(util::manifest_export_file_name(asset_path));
+    std::fs::write(&path, json)?;
+    Ok(path)
+}
+
+#[cfg(target_os = "macos")]
+fn begin_file_drag(path: &Path) -> Result<(), String> {
+    extern "C" {
+        fn crtool_macos_begin_file_drag(path: *const std::ffi::c_char) -> i32;
+    }
+    let c_path =
+        std::ffi::CString::new(path.to_string_lossy().as_bytes()).map_err(|e| e.to_string())?;
+    match unsafe { crtool_macos_begin_file_drag(c_path.as_ptr()) } {
+        0 => Ok(()),
+        code => Err(format!("Could not start drag session ({})", code)),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn begin_file_drag(path: &Path) -> Result<(), String> {
+    let result = if cfg!(windows) {
+        std::process::Command::new("explorer")
+            .arg(format!("/select,{}", path.display()))
+            .spawn()
+    } else {
+        std::process::Command::new("xdg-open")
+            .arg(path.parent().unwrap_or(path))
+            .spawn()
+    };
+    result.map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// A drag handle that exports `json` as a file when dragged out of the window.
+pub(crate) fn show_drag_handle(ui: &mut egui::Ui, asset_path: &Path, json: &str) {
+    let hover = if cfg!(target_os = "macos") {
+        "Drag to Finder or another app to export the manifest JSON"
+    } else {
+        "Drag to export the manifest JSON (opens the exported file's folder)"
+    };
+    let response = ui
+        .add(egui::Button::new("⇱ Drag JSON").sense(egui::Sense::drag()))
+        .on_hover_text(hover);
+    if response.hovered() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+    }
+    if response.drag_started() {
+        let result = write_drag_file(asset_path, json)
+            .map_err(|e| e.to_string())
+            .and_then(|path| begin_file_drag(&path));
+        if let Err(e) = result {
+            eprintln!("Drag-out failed: {}", e);
+        }
+    }
+}
@@ -14,9 +14,24 @@ governing permissions and limitations under the License.
 #![allow(unexpected_cfgs)]
 
 mod app;
+mod assessment;
+#[cfg(feature = "capture")]
+mod capture;
+mod controller;
 mod document;
+mod drag_out;
 mod manifest_ui;
+mod mini_mode;
+mod references;
+mod report_export;
+mod shortcuts;
 mod tab_viewer;
+mod thumbnails;
+mod tree_view;
+mod trust_prefs;
+#[cfg(test)]
+mod ui_tests;
+mod update_check;
 mod util;
 
 #[cfg(target_os = "macos")]
@@ -31,11 +46,21 @@ fn main() -> Result<(), eframe::Error> {
     #[cfg(target_os = "macos")]
     macos_open_document::install_handler();
 
+    // --mini: start as a small always-on-top summary window (View → Mini Mode)
+    let mini = std::env::args().skip(1).any(|arg| arg == "--mini");
+    let viewport = eframe::egui::ViewportBuilder::default().with_drag_and_drop(true);
+    let viewport = if mini {
+        viewport
+            .with_inner_size(mini_mode::MINI_SIZE)
+            .with_min_inner_size(mini_mode::MINI_SIZE)
+            .with_always_on_top()
+    } else {
+        viewport
+            .with_inner_size(mini_mode::FULL_SIZE)
+            .with_min_inner_size(mini_mode::FULL_MIN_SIZE)
+    };
     let options = eframe::NativeOptions {
-        viewport: eframe::egui::ViewportBuilder::default()
-            .with_inner_size([1200.0, 800.0])
-            .with_min_inner_size([800.0, 600.0])
-            .with_drag_and_drop(true),
+        viewport,
         ..Default::default()
     };
 
@@ -46,22 +71,24 @@ fn main() -> Result<(), eframe::Error> {
             egui_extras::install_image_loaders(&cc.egui_ctx);
             #[cfg(target_os = "macos")]
             macos_open_document::install_cocoa_handler();
-            let extraction_settings = util::gui_extraction_settings();
+            util::load_trust_presentations();
+            let trust_config = util::load_trust_config();
+            let default_lists = util::fetch_default_trust_lists();
 
             let mut initial_files: Vec<PathBuf> = std::env::args()
                 .skip(1)
                 .filter_map(|arg| {
                     let path = arg_to_path(&arg);
-                    (path.is_file() && crtool::is_supported_asset_path(&path)).then_some(path)
+                    controller::AppController::can_open(&path).then_some(path)
                 })
                 .collect();
             #[cfg(target_os = "macos")]
             initial_files.extend(macos_open_document::drain_pending_files());
 
-            Ok(Box::new(CrtoolApp::new_with_optional_files(
-                initial_files,
-                extraction_settings,
-            )))
+            Ok(Box::new(
+                CrtoolApp::new_with_optional_files(initial_files, trust_config, default_lists)
+                    .with_mini_mode(mini),
+            ))
         }),
     )
 }
@@ -0,0 +1,115 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! View → Mini Mode (or `--mini`): a small always-on-top window showing only the credential
+//! summary (trust status, signer, source type) of the most recently opened file, to keep beside
+//! an editor while reviewing a folder.
+
+use crate::document::DocumentTab;
+use crate::manifest_ui::manifest_digital_source_type;
+use crate::util;
+use crtool::badge::{Badge, BadgeStatus};
+use crtool::filter::FileFacts;
+use crtool::provenance::find_manifest;
+use eframe::egui;
+use egui_twemoji::EmojiLabel;
+
+/// Window size in mini mode.
+pub(crate) const MINI_SIZE: [f32; 2] = [340.0, 130.0];
+/// Window size and minimum size when returning to the full window.
+pub(crate) const FULL_SIZE: [f32; 2] = [1200.0, 800.0];
+pub(crate) const FULL_MIN_SIZE: [f32; 2] = [800.0, 600.0];
+
+/// Resize the native window and set its level for mini (always on top) or full mode.
+pub(crate) fn apply_window_mode(ctx: &egui::Context, mini: bool) {
+    let (size, min_size, level) = if mini {
+        (MINI_SIZE, MINI_SIZE, egui::WindowLevel::AlwaysOnTop)
+    } else {
+        (FULL_SIZE, FULL_MIN_SIZE, egui::WindowLevel::Normal)
+    };
+    ctx.send_viewport_cmd(egui::ViewportCommand::MinInnerSize(min_size.into()));
+    ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size.into()));
+    ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+}
+
+/// Icon and color of `status`; the trust statuses follow the configured trust presentation.
+fn status_style(status: BadgeStatus) -> (String, egui::Color32) {
+    let trust = match status {
+        BadgeStatus::Trusted => "trusted",
+        BadgeStatus::Untrusted => "untrusted",
+        BadgeStatus::Unverified => "unknown",
+        BadgeStatus::Invalid => return ("❌".to_string(), egui::Color32::from_rgb(200, 40, 40)),
+    };
+    let (presentation, color) = util::trust_style(trust);
+    (presentation.icon, color)
+}
+
+/// Draw the summary of `tab`. Returns true when the user asks for the full window.
+pub(crate) fn show_mini_summary(ui: &mut egui::Ui, tab: Option<&DocumentTab>) -> bool {
+    let mut expand = false;
+    ui.horizontal(|ui| {
+        let name = tab
+            .and_then(|t| t.document.file_path.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "No file".to_string());
+        ui.add(egui::Label::new(egui::RichText::new(name).strong()).truncate());
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui
+                .small_button("⛶")
+                .on_hover_text("Full window (View → Mini Mode)")
+                .clicked()
+            {
+                expand = true;
+            }
+        });
+    });
+    ui.separator();
+
+    let Some(tab) = tab else {
+        EmojiLabel::new("👆 Drop a C2PA-supported file here.").show(ui);
+        return expand;
+    };
+    let manifest = match &tab.document.extraction_result {
+        Ok(manifest) => manifest,
+        Err(_) if tab.document.no_manifest.is_some() => {
+            EmojiLabel::new("ℹ️ No Content Credentials").show(ui);
+            return expand;
+        }
+        Err(e) => {
+            ui.add(
+                egui::Label::new(
+                    egui::RichText::new(e).color(egui::Color32::from_rgb(200, 40, 40)),
+                )
+                .truncate(),
+            );
+            return expand;
+        }
+    };
+
+    let badge = Badge::from_facts(&FileFacts::from_extraction(manifest));
+    let (icon, color) = status_style(badge.status);
+    EmojiLabel::new(
+        egui::RichText::new(format!("{} {}", icon, badge.status.label()))
+            .size(15.0)
+            .color(color),
+    )
+    .show(ui);
+
+    let signer = badge.issuer.as_deref().unwrap_or("—");
+    ui.add(egui::Label::new(format!("Signer: {}", signer)).truncate());
+
+    let source_type = find_manifest(&manifest.manifest_value, &manifest.active_label)
+        .and_then(manifest_digital_source_type)
+        .unwrap_or_else(|| "—".to_string());
+    ui.add(egui::Label::new(format!("Source type: {}", source_type)).truncate());
+    expand
+}
@@ -0,0 +1,278 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! External-reference and asset-ref assertions: list referenced URIs as links with their
+//! hash/identifier details, and optionally check reachability in the background.
+
+use crtool::provenance::find_manifest;
+use eframe::egui;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Assertion label prefixes whose payload references content outside the asset.
+const REFERENCE_ASSERTION_PREFIXES: &[&str] = &["c2pa.external-reference", "c2pa.asset-ref"];
+
+/// Timeout for a single reachability request.
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A URI referenced by an external-reference or asset-ref assertion.
+#[derive(Debug, Clone)]
+pub(crate) struct ReferencedUri {
+    /// Assertion label the reference came from (e.g. "c2pa.asset-ref").
+    pub(crate) assertion: String,
+    pub(crate) uri: String,
+    /// Other fields of the reference (description, alg, hash, dc:format, ...), in payload order.
+    pub(crate) details: Vec<(String, String)>,
+}
+
+impl ReferencedUri {
+    /// Only http(s) URIs are opened in the browser and checked for reachability.
+    pub(crate) fn is_web(&self) -> bool {
+        let lower = self.uri.to_ascii_lowercase();
+        lower.starts_with("https://") || lower.starts_with("http://")
+    }
+}
+
+/// Result of a reachability check for one URI.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Reachability {
+    Checking,
+    Reachable(u16),
+    Unreachable(String),
+}
+
+/// Reachability results keyed by URI, shared with the background check thread.
+pub(crate) type ReachabilityMap = Arc<Mutex<HashMap<String, Reachability>>>;
+
+fn is_reference_assertion_label(key: &str) -> bool {
+    REFERENCE_ASSERTION_PREFIXES
+        .iter()
+        .any(|prefix| key.starts_with(prefix))
+}
+
+fn value_to_display(v: &serde_json::Value) -> String {
+    match v {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Build a reference from a URI-bearing object (`url` or `uri`) plus extra detail fields.
+fn reference_from_object(
+    assertion: &str,
+    obj: &serde_json::Map<String, serde_json::Value>,
+    mut details: Vec<(String, String)>,
+) -> Option<ReferencedUri> {
+    let uri = obj
+        .get("url")
+        .or_else(|| obj.get("uri"))
+        .and_then(|v| v.as_str())?
+        .to_string();
+    details.extend(
+        obj.iter()
+            .filter(|(k, _)| k.as_str() != "url" && k.as_str() != "uri")
+            .map(|(k, v)| (k.clone(), value_to_display(v))),
+    );
+    Some(ReferencedUri {
+        assertion: assertion.to_string(),
+        uri,
+        details,
+    })
+}
+
+/// References from one assertion payload. external-reference carries a single `location`
+/// (ext-uri map or plain string); asset-ref carries a `references` array of
+/// `{ reference: { uri, ... }, description }`.
+fn references_from_assertion(label: &str, payload: &serde_json::Value) -> Vec<ReferencedUri> {
+    let mut out = Vec::new();
+    match payload.get("location") {
+        Some(serde_json::Value::String(s)) => out.push(ReferencedUri {
+            assertion: label.to_string(),
+            uri: s.clone(),
+            details: Vec::new(),
+        }),
+        Some(serde_json::Value::Object(obj)) => {
+            out.extend(reference_from_object(label, obj, Vec::new()));
+        }
+        _ => {}
+    }
+    if let Some(refs) = payload.get("references").and_then(|v| v.as_array()) {
+        for entry in refs {
+            let Some(reference) = entry.get("reference").and_then(|v| v.as_object()) else {
+                continue;
+            };
+            let details = entry
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter(|(k, _)| k.as_str() != "reference")
+                .map(|(k, v)| (k.clone(), value_to_display(v)))
+                .collect();
+            out.extend(reference_from_object(label, reference, details));
+        }
+    }
+    out
+}
+
+/// Collect referenced URIs from the active manifest's external-reference and asset-ref assertions.
+pub(crate) fn collect_references(
+    manifest_value: &serde_json::Value,
+    active_label: &str,
+) -> Vec<ReferencedUri> {
+    let Some(assertions) = find_manifest(manifest_value, active_label)
+        .and_then(|m| m.get("assertions"))
+        .and_then(|v| v.as_object())
+    else {
+        return Vec::new();
+    };
+    assertions
+        .iter()
+        .filter(|(key, _)| is_reference_assertion_label(key))
+        .flat_map(|(key, payload)| references_from_assertion(key, payload))
+        .collect()
+}
+
+fn check_uri(client: &reqwest::blocking::Client, uri: &str) -> Reachability {
+    // Some servers reject HEAD; fall back to GET before reporting the URI as unreachable.
+    let response = client
+        .head(uri)
+        .send()
+        .and_then(|r| match r.status().as_u16() {
+            405 | 501 => client.get(uri).send(),
+            _ => Ok(r),
+        });
+    match response {
+        Ok(r) if r.status().is_success() || r.status().is_redirection() => {
+            Reachability::Reachable(r.status().as_u16())
+        }
+        Ok(r) => Reachability::Unreachable(format!("HTTP {}", r.status())),
+        Err(e) => Reachability::Unreachable(e.to_string()),
+    }
+}
+
+/// Check each http(s) URI on a background thread, recording results in `map` and repainting
+/// as each one completes.
+pub(crate) fn spawn_reachability_checks(
+    uris: Vec<String>,
+    map: ReachabilityMap,
+    ctx: egui::Context,
+) {
+    if let Ok(mut m) = map.lock() {
+        for uri in &uris {
+            m.insert(uri.clone(), Reachability::Checking);
+        }
+    }
+    std::thread::spawn(move || {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent("crTool/1.0")
+            .timeout(REACHABILITY_TIMEOUT)
+            .build();
+        for uri in uris {
+            let result = match &client {
+                Ok(client) => check_uri(client, &uri),
+                Err(e) => Reachability::Unreachable(e.to_string()),
+            };
+            if let Ok(mut m) = map.lock() {
+                m.insert(uri, result);
+            }
+            ctx.request_repaint();
+        }
+    });
+}
+
+/// Renders the external references section: clickable links, details, and reachability.
+pub(crate) fn show_external_references(
+    ui: &mut egui::Ui,
+    references: &[ReferencedUri],
+    reachability: &ReachabilityMap,
+) {
+    if references.is_empty() {
+        return;
+    }
+    let detail_color = egui::Color32::from_rgb(64, 64, 64);
+
+    egui::CollapsingHeader::new(
+        egui::RichText::new(format!("🔗 External references ({})", references.len()))
+            .size(15.0)
+            .color(egui::Color32::from_rgb(100, 120, 140)),
+    )
+    .id_salt("external_references")
+    .default_open(true)
+    .show(ui, |ui| {
+        let web_uris: Vec<String> = references
+            .iter()
+            .filter(|r| r.is_web())
+            .map(|r| r.uri.clone())
+            .collect();
+        let results = reachability.lock().map(|m| m.clone()).unwrap_or_default();
+        let checking = results.values().any(|r| *r == Reachability::Checking);
+        if !web_uris.is_empty()
+            && ui
+                .add_enabled(!checking, egui::Button::new("🌐 Check reachability"))
+                .on_hover_text("Send a request to each http(s) URI to see whether it resolves")
+                .clicked()
+        {
+            spawn_reachability_checks(web_uris, reachability.clone(), ui.ctx().clone());
+        }
+
+        for reference in references {
+            ui.group(|ui| {
+                ui.label(
+                    egui::RichText::new(&reference.assertion)
+                        .size(12.0)
+                        .color(detail_color),
+                );
+                if reference.is_web() {
+                    ui.hyperlink_to(&reference.uri, &reference.uri);
+                } else if ui
+                    .add(
+                        egui::Label::new(egui::RichText::new(&reference.uri).monospace())
+                            .sense(egui::Sense::click()),
+                    )
+                    .on_hover_text("Click to copy")
+                    .clicked()
+                {
+                    ui.ctx().copy_text(reference.uri.clone());
+                }
+                match results.get(&reference.uri) {
+                    Some(Reachability::Checking) => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Checking…");
+                        });
+                    }
+                    Some(Reachability::Reachable(status)) => {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(0, 100, 0),
+                            format!("Reachable (HTTP {})", status),
+                        );
+                    }
+                    Some(Reachability::Unreachable(reason)) => {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 100, 100),
+                            format!("Unreachable: {}", reason),
+                        );
+                    }
+                    None => {}
+                }
+                for (key, value) in &reference.details {
+                    ui.label(
+                        egui::RichText::new(format!("{}: {}", key, value))
+                            .size(12.0)
+                            .color(detail_color),
+                    );
+                }
+            });
+        }
+    });
+}
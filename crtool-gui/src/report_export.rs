@@ -0,0 +1,398 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! File → Export Report: the validation result and the crJSON (JPEG Trust) indicators of a
+//! document as JSON, CSV, or a self-contained HTML page, for attaching to tickets.
+
+use crate::controller::LoadedDocument;
+use crate::manifest_ui::{get_trust_status, get_validation_failures, ValidationFailureEntry};
+use crtool::{ManifestExtractionResult, Severity, ValidationResult};
+use serde_json::Value as JsonValue;
+use std::fmt::Write as _;
+
+/// File formats of an exported report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ReportFormat {
+    Json,
+    Csv,
+    Html,
+}
+
+impl ReportFormat {
+    pub(crate) const ALL: [ReportFormat; 3] =
+        [ReportFormat::Json, ReportFormat::Csv, ReportFormat::Html];
+
+    /// Menu and file dialog label.
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "JSON",
+            ReportFormat::Csv => "CSV",
+            ReportFormat::Html => "HTML",
+        }
+    }
+
+    pub(crate) fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Csv => "csv",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+/// Validation result and indicators of one document with a manifest.
+pub(crate) struct DocumentReport<'a> {
+    file: String,
+    manifest: &'a ManifestExtractionResult,
+    schema: Option<&'a ValidationResult>,
+    failures: Vec<ValidationFailureEntry>,
+    trust: Option<String>,
+}
+
+impl<'a> DocumentReport<'a> {
+    /// Report for `document`; `None` when it has no manifest.
+    pub(crate) fn new(document: &'a LoadedDocument) -> Option<Self> {
+        let manifest = document.extraction_result.as_ref().ok()?;
+        Some(DocumentReport {
+            file: document.file_path.display().to_string(),
+            manifest,
+            schema: document.validation_result.as_ref(),
+            failures: get_validation_failures(&manifest.manifest_value, &manifest.active_label),
+            trust: get_trust_status(&manifest.manifest_value, &manifest.active_label),
+        })
+    }
+
+    fn schema_valid(&self) -> Option<bool> {
+        self.schema.map(|s| s.is_valid)
+    }
+
+    /// Whether the document passed both schema and manifest validation.
+    fn passed(&self) -> bool {
+        self.schema_valid() != Some(false) && self.failures.is_empty()
+    }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        let or_dash = |v: Option<String>| v.unwrap_or_else(|| "—".to_string());
+        vec![
+            ("File", self.file.clone()),
+            ("Active manifest", self.manifest.active_label.clone()),
+            ("Asset SHA-256", or_dash(self.manifest.asset_hash.clone())),
+            ("Trust", or_dash(self.trust.clone())),
+            (
+                "Schema",
+                match self.schema_valid() {
+                    Some(true) => "valid".to_string(),
+                    Some(false) => "invalid".to_string(),
+                    None => "not checked".to_string(),
+                },
+            ),
+            (
+                "Result",
+                if self.passed() { "passed" } else { "failed" }.to_string(),
+            ),
+        ]
+    }
+
+    pub(crate) fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Csv => self.to_csv(),
+            ReportFormat::Html => self.to_html(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let report = serde_json::json!({
+            "file": self.file,
+            "activeManifest": self.manifest.active_label,
+            "assetSha256": self.manifest.asset_hash,
+            "trust": self.trust,
+            "passed": self.passed(),
+            "schemaValid": self.schema_valid(),
+            "schemaErrors": self.schema.map(|s| &s.errors),
+            "validationFailures": self.failures.iter().map(|f| serde_json::json!({
+                "code": f.code,
+                "explanation": f.explanation,
+                "url": f.url,
+                "source": f.source,
+            })).collect::<Vec<_>>(),
+            "indicators": self.manifest.manifest_value,
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+
+    /// One row per fact: `section,key,value`. Sections are `summary`, `schemaError` (or
+    /// `schemaWarning`, `schemaInfo`), `validationFailure`, and `indicator` (one row per scalar,
+    /// keyed by JSON pointer).
+    fn to_csv(&self) -> String {
+        let mut out = String::from("section,key,value\n");
+        let mut row = |section: &str, key: &str, value: &str| {
+            let _ = writeln!(
+                out,
+                "{},{},{}",
+                csv_field(section),
+                csv_field(key),
+                csv_field(value)
+            );
+        };
+        for (key, value) in self.summary() {
+            row("summary", key, &value);
+        }
+        for error in self.schema.map(|s| s.errors.as_slice()).unwrap_or_default() {
+            let section = match error.severity {
+                Severity::Error => "schemaError",
+                Severity::Warning => "schemaWarning",
+                Severity::Info => "schemaInfo",
+            };
+            row(section, &error.instance_path, &error.message);
+        }
+        for failure in &self.failures {
+            let mut detail = failure.explanation.clone().unwrap_or_default();
+            if let Some(source) = &failure.source {
+                detail = format!("{source}: {detail}");
+            }
+            row("validationFailure", &failure.code, &detail);
+        }
+        let mut leaves = Vec::new();
+        json_leaves(&self.manifest.manifest_value, String::new(), &mut leaves);
+        for (pointer, value) in leaves {
+            row("indicator", &pointer, &value);
+        }
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let mut out = String::new();
+        let title = format!("Content Credentials report: {}", self.file);
+        let _ = write!(
+            out,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+            html_escape(&title),
+            HTML_STYLE,
+            html_escape(&title)
+        );
+
+        out.push_str("<table class=\"summary\">\n");
+        for (key, value) in self.summary() {
+            let class = match (key, value.as_str()) {
+                ("Result", "passed") => " class=\"pass\"",
+                ("Result", _) => " class=\"fail\"",
+                _ => "",
+            };
+            let _ = writeln!(
+                out,
+                "<tr><th>{}</th><td{class}>{}</td></tr>",
+                key,
+                html_escape(&value)
+            );
+        }
+        out.push_str("</table>\n<h2>Validation</h2>\n");
+
+        let schema_errors = self.schema.map(|s| s.errors.as_slice()).unwrap_or_default();
+        if schema_errors.is_empty() && self.failures.is_empty() {
+            out.push_str("<p class=\"pass\">No validation failures.</p>\n");
+        } else {
+            out.push_str("<ul>\n");
+            for error in schema_errors {
+                let _ = writeln!(
+                    out,
+                    "<li class=\"{}\">Schema {}: <code>{}</code> {}</li>",
+                    error.severity,
+                    error.severity,
+                    html_escape(&error.instance_path),
+                    html_escape(&error.message)
+                );
+            }
+            for failure in &self.failures {
+                let _ = writeln!(
+                    out,
+                    "<li><code>{}</code> {}{}</li>",
+                    html_escape(&failure.code),
+                    failure
+                        .source
+                        .as_deref()
+                        .map(|s| format!("({}) ", html_escape(s)))
+                        .unwrap_or_default(),
+                    html_escape(failure.explanation.as_deref().unwrap_or_default())
+                );
+            }
+            out.push_str("</ul>\n");
+        }
+
+        if let Some(tree) = crtool::provenance::build_tree(
+            &self.manifest.manifest_value,
+            &self.manifest.active_label,
+        ) {
+            let _ = write!(
+                out,
+                "<h2>Provenance</h2>\n<pre>{}</pre>\n",
+                html_escape(&tree.render_tree())
+            );
+        }
+
+        out.push_str("<h2>Indicators</h2>\n<ul class=\"tree\">\n");
+        html_tree_children(&self.manifest.manifest_value, 0, &mut out);
+        let _ = write!(
+            out,
+            "</ul>\n<p class=\"generated\">Generated by crTool {}</p>\n</body>\n</html>\n",
+            env!("CARGO_PKG_VERSION")
+        );
+        out
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+table.summary{border-collapse:collapse}\
+table.summary th,table.summary td{border:1px solid #ccc;padding:4px 8px;text-align:left}\
+.pass{color:#060}.fail{color:#b00}\
+li.error{color:#b00}li.warning{color:#a60}li.info{color:#555}\
+ul.tree,ul.tree ul{list-style:none;padding-left:1.2em}\
+.key{color:#555}.value{font-family:monospace}\
+pre{background:#f4f4f4;padding:1em}\
+.generated{color:#888;font-size:small}";
+
+/// Quote a CSV field when it contains a separator, quote, or line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Scalar values of `value` with their JSON pointers.
+fn json_leaves(value: &JsonValue, pointer: String, out: &mut Vec<(String, String)>) {
+    match value {
+        JsonValue::Object(map) => {
+            for (k, v) in map {
+                let escaped = k.replace('~', "~0").replace('/', "~1");
+                json_leaves(v, format!("{pointer}/{escaped}"), out);
+            }
+        }
+        JsonValue::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                json_leaves(v, format!("{pointer}/{i}"), out);
+            }
+        }
+        JsonValue::String(s) => out.push((pointer, s.clone())),
+        other => out.push((pointer, other.to_string())),
+    }
+}
+
+/// List items for the members of an object or array; containers become collapsible
+/// `<details>` (open for the first two levels).
+fn html_tree_children(value: &JsonValue, depth: usize, out: &mut String) {
+    let entries: Vec<(String, &JsonValue)> = match value {
+        JsonValue::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        JsonValue::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("[{i}]"), v))
+            .collect(),
+        _ => return,
+    };
+    for (key, child) in entries {
+        match child {
+            JsonValue::Object(_) | JsonValue::Array(_) => {
+                let count = match child {
+                    JsonValue::Object(m) => m.len(),
+                    JsonValue::Array(a) => a.len(),
+                    _ => 0,
+                };
+                let _ = writeln!(
+                    out,
+                    "<li><details{}><summary><span class=\"key\">{}</span> ({count})</summary><ul>",
+                    if depth < 2 { " open" } else { "" },
+                    html_escape(&key)
+                );
+                html_tree_children(child, depth + 1, out);
+                out.push_str("</ul></details></li>\n");
+            }
+            scalar => {
+                let text = match scalar {
+                    JsonValue::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let _ = writeln!(
+                    out,
+                    "<li><span class=\"key\">{}</span>: <span class=\"value\">{}</span></li>",
+                    html_escape(&key),
+                    html_escape(&text)
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn document() -> LoadedDocument {
+        let crjson = serde_json::json!({
+            "manifests": [{
+                "label": "urn:c2pa:a",
+                "claim.v2": { "dc:title": "a <b>.jpg" },
+                "validationResults": { "failure": [
+                    { "code": "assertion.dataHash.mismatch", "explanation": "hash, mismatch" }
+                ] }
+            }]
+        });
+        LoadedDocument {
+            file_path: PathBuf::from("a.jpg"),
+            extraction_result: Ok(ManifestExtractionResult {
+                input_path: "a.jpg".into(),
+                active_label: "urn:c2pa:a".into(),
+                asset_hash: None,
+                asset_hash_alg: None,
+                manifest_json: crjson.to_string(),
+                manifest_value: crjson,
+            }),
+            validation_result: None,
+            unsupported: Vec::new(),
+            credential_removal: Vec::new(),
+            no_manifest: None,
+            manifest_search: None,
+        }
+    }
+
+    #[test]
+    fn test_report_formats() {
+        let document = document();
+        let report = DocumentReport::new(&document).unwrap();
+
+        let json: JsonValue = serde_json::from_str(&report.render(ReportFormat::Json)).unwrap();
+        assert_eq!(json["passed"], false);
+        assert_eq!(
+            json["validationFailures"][0]["code"],
+            "assertion.dataHash.mismatch"
+        );
+        assert_eq!(json["indicators"]["manifests"][0]["label"], "urn:c2pa:a");
+
+        let csv = report.render(ReportFormat::Csv);
+        assert!(csv.contains("validationFailure,assertion.dataHash.mismatch,\"hash, mismatch\""));
+        assert!(csv.contains("indicator,/manifests/0/claim.v2/dc:title,a <b>.jpg"));
+
+        let html = report.render(ReportFormat::Html);
+        assert!(html.contains("a &lt;b&gt;.jpg"));
+        assert!(html.contains("<td class=\"fail\">failed</td>"));
+        assert!(!html.contains("<b>"));
+    }
+}
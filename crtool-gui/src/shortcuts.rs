@@ -0,0 +1,99 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Keyboard shortcuts (Cmd on macOS, Ctrl on Windows/Linux) and the Help → Keyboard Shortcuts
+//! window. Every shortcut is listed in [`ALL`] so the menus and the help window stay in sync.
+
+use eframe::egui;
+use egui::{Key, KeyboardShortcut, Modifiers};
+
+pub(crate) const OPEN: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND,
+    logical_key: Key::O,
+};
+pub(crate) const CLOSE: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND,
+    logical_key: Key::W,
+};
+pub(crate) const CLOSE_ALL: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND.plus(Modifiers::SHIFT),
+    logical_key: Key::W,
+};
+pub(crate) const SAVE_AS: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND.plus(Modifiers::SHIFT),
+    logical_key: Key::S,
+};
+pub(crate) const COPY: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND,
+    logical_key: Key::C,
+};
+pub(crate) const SELECT_ALL: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND,
+    logical_key: Key::A,
+};
+pub(crate) const TOGGLE_RAW_JSON: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND,
+    logical_key: Key::R,
+};
+pub(crate) const FOCUS_LEFT: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND,
+    logical_key: Key::Num1,
+};
+pub(crate) const FOCUS_RIGHT: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND,
+    logical_key: Key::Num2,
+};
+pub(crate) const FIND: KeyboardShortcut = KeyboardShortcut {
+    modifiers: Modifiers::COMMAND,
+    logical_key: Key::F,
+};
+
+/// All shortcuts with what they do, in the order shown in the help window.
+pub(crate) const ALL: &[(KeyboardShortcut, &str)] = &[
+    (OPEN, "Open files"),
+    (CLOSE, "Close the focused tab"),
+    (CLOSE_ALL, "Close all tabs"),
+    (SAVE_AS, "Save the focused tab's manifest as JSON"),
+    (COPY, "Copy selected text"),
+    (SELECT_ALL, "Select all"),
+    (TOGGLE_RAW_JSON, "Toggle Raw JSON / tree view"),
+    (FOCUS_LEFT, "Focus the Manifest Data panel"),
+    (FOCUS_RIGHT, "Focus the Manifest & Ingredients Tree panel"),
+    (FIND, "Search the manifest data"),
+];
+
+/// Help → Keyboard Shortcuts window.
+pub(crate) fn show_shortcuts_window(ctx: &egui::Context, open: &mut bool) {
+    egui::Window::new("Keyboard Shortcuts")
+        .open(open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("keyboard_shortcuts")
+                .num_columns(2)
+                .spacing([24.0, 6.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for (shortcut, description) in ALL {
+                        ui.monospace(ctx.format_shortcut(shortcut));
+                        ui.label(*description);
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(6.0);
+            ui.label(
+                egui::RichText::new("With a panel focused, ↑/↓ and Page Up/Page Down scroll it.")
+                    .size(12.0)
+                    .color(egui::Color32::from_rgb(100, 100, 100)),
+            );
+        });
+}
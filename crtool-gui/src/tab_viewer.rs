@@ -12,27 +12,35 @@ governing permissions and limitations under the License.
 
 //! egui_dock TabViewer: tab title and content for each document.
 
+use crate::app::AppCommand;
 use crate::document::{self, DocumentTab};
+use crate::tree_view::TreeViewSettings;
 use eframe::egui;
 use egui_dock::TabViewer;
 
 /// TabViewer for the dock: shows document title and content per tab.
-pub(crate) struct CrtoolTabViewer;
+pub(crate) struct CrtoolTabViewer {
+    pub(crate) tree_settings: TreeViewSettings,
+    /// Commands requested by tab content this frame
+    pub(crate) commands: Vec<AppCommand>,
+}
 
 impl TabViewer for CrtoolTabViewer {
     type Tab = DocumentTab;
 
     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
         let name = tab
+            .document
             .file_path
             .file_name()
             .and_then(|n| n.to_str())
             .map(|s| s.to_string())
-            .unwrap_or_else(|| tab.file_path.to_string_lossy().into_owned());
+            .unwrap_or_else(|| tab.document.file_path.to_string_lossy().into_owned());
         name.into()
     }
 
     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
-        document::show_document_tab_ui(ui, tab);
+        self.commands
+            .extend(document::show_document_tab_ui(ui, tab, &self.tree_settings));
     }
 }
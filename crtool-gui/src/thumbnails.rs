@@ -0,0 +1,297 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Ingredient and claim thumbnails, and the preview of the asset itself: decoded on demand in the
+//! background and cached as textures keyed by resource URI, so a large tree does not re-read or
+//! re-decode images every frame.
+//!
+//! Each document reads its manifest store once, on a loader thread that owns its
+//! [`crtool::ResourceReader`] and serves every thumbnail of the document from it. Decoding runs
+//! on a small worker pool shared by all documents, so scrolling through hundreds of ingredients
+//! does not start hundreds of threads.
+
+use crtool::Settings;
+use eframe::egui;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Longest edge of a displayed thumbnail (px).
+const THUMBNAIL_MAX_SIZE: f32 = 96.0;
+/// Longest edge of the asset and claim thumbnail in the preview pane (px).
+pub(crate) const PREVIEW_MAX_SIZE: f32 = 160.0;
+/// Cache key of the asset's own preview (resource URIs all start with `self#jumbf=`).
+const ASSET_KEY: &str = "asset";
+
+#[derive(Clone)]
+enum ThumbnailState {
+    Loading,
+    Ready(egui::TextureHandle),
+    Failed(String),
+}
+
+/// Decoded images waiting to be uploaded as textures on the UI thread.
+type DecodedQueue = Arc<Mutex<Vec<(String, Result<egui::ColorImage, String>)>>>;
+
+/// Largest number of threads decoding thumbnails at once, across all documents.
+const MAX_DECODE_WORKERS: usize = 4;
+
+type DecodeJob = Box<dyn FnOnce() + Send>;
+
+/// Run `job` on the shared decode pool, starting its workers on first use.
+fn decode_in_pool(job: DecodeJob) {
+    static POOL: OnceLock<Mutex<mpsc::Sender<DecodeJob>>> = OnceLock::new();
+    let pool = POOL.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<DecodeJob>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(MAX_DECODE_WORKERS);
+        for _ in 0..workers {
+            let receiver = receiver.clone();
+            std::thread::spawn(move || loop {
+                let job = match receiver.lock() {
+                    Ok(receiver) => receiver.recv(),
+                    Err(_) => return,
+                };
+                match job {
+                    Ok(job) => job(),
+                    Err(_) => return,
+                }
+            });
+        }
+        Mutex::new(sender)
+    });
+    if let Ok(sender) = pool.lock() {
+        let _ = sender.send(job);
+    }
+}
+
+/// A thumbnail for a document's loader thread to read: its cache key, manifest and URI.
+struct ResourceRequest {
+    key: String,
+    manifest_label: String,
+    uri: String,
+    max_size: f32,
+}
+
+/// Per-document thumbnail cache.
+#[derive(Clone)]
+pub(crate) struct ThumbnailCache {
+    file_path: PathBuf,
+    settings: Settings,
+    /// Texture (or load state) per absolute resource URI.
+    entries: HashMap<String, ThumbnailState>,
+    decoded: DecodedQueue,
+    /// Requests to the document's loader thread, started with the first thumbnail.
+    resources: Option<mpsc::Sender<ResourceRequest>>,
+}
+
+fn decode_thumbnail(bytes: &[u8], max_size: f32) -> Result<egui::ColorImage, String> {
+    let image = image::load_from_memory(bytes).map_err(|e| e.to_string())?;
+    // Thumbnails are shown small; downscale large ones before uploading.
+    let max = (max_size * 2.0) as u32;
+    let image = if image.width() > max || image.height() > max {
+        image.thumbnail(max, max)
+    } else {
+        image
+    };
+    let rgba = image.to_rgba8();
+    let size = [rgba.width() as usize, rgba.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        rgba.as_flat_samples().as_slice(),
+    ))
+}
+
+impl ThumbnailCache {
+    pub(crate) fn new(file_path: PathBuf, settings: Settings) -> Self {
+        Self {
+            file_path,
+            settings,
+            entries: HashMap::new(),
+            decoded: DecodedQueue::default(),
+            resources: None,
+        }
+    }
+
+    /// Move finished decodes into textures (must run on the UI thread).
+    fn upload_decoded(&mut self, ctx: &egui::Context) {
+        let finished: Vec<_> = match self.decoded.lock() {
+            Ok(mut queue) => queue.drain(..).collect(),
+            Err(_) => return,
+        };
+        for (uri, result) in finished {
+            let state = match result {
+                Ok(image) => ThumbnailState::Ready(ctx.load_texture(
+                    format!("thumbnail:{}", uri),
+                    image,
+                    egui::TextureOptions::LINEAR,
+                )),
+                Err(e) => ThumbnailState::Failed(e),
+            };
+            self.entries.insert(uri, state);
+        }
+    }
+
+    /// Read `key` with `load` and decode it on the decode pool.
+    fn decode_later<F>(
+        decoded: &DecodedQueue,
+        ctx: &egui::Context,
+        key: String,
+        max_size: f32,
+        load: F,
+    ) where
+        F: FnOnce() -> Result<Vec<u8>, String> + Send + 'static,
+    {
+        let decoded = decoded.clone();
+        let ctx = ctx.clone();
+        decode_in_pool(Box::new(move || {
+            let result = load().and_then(|bytes| decode_thumbnail(&bytes, max_size));
+            if let Ok(mut queue) = decoded.lock() {
+                queue.push((key, result));
+            }
+            ctx.request_repaint();
+        }));
+    }
+
+    /// Start the loader thread: it reads the manifest store once and then serves every
+    /// requested resource from it, handing the bytes to the decode pool.
+    fn start_loader(&self, ctx: &egui::Context) -> mpsc::Sender<ResourceRequest> {
+        let (sender, receiver) = mpsc::channel::<ResourceRequest>();
+        let file_path = self.file_path.clone();
+        let settings = self.settings.clone();
+        let decoded = self.decoded.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let reader = crtool::ResourceReader::open(&file_path, &settings);
+            // Ends when the cache (and every clone of it) is dropped
+            for request in receiver {
+                let bytes = match &reader {
+                    Ok(reader) => reader
+                        .read(&request.manifest_label, &request.uri)
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                Self::decode_later(&decoded, &ctx, request.key, request.max_size, move || bytes);
+            }
+        });
+        sender
+    }
+
+    /// Draw the cached image `key` at most `max_size` px. Returns true when it has not been
+    /// loaded yet and its area is visible, i.e. loading should start.
+    fn show_cached(&mut self, ui: &mut egui::Ui, key: &str, max_size: f32) -> bool {
+        self.upload_decoded(ui.ctx());
+        match self.entries.get(key) {
+            Some(ThumbnailState::Ready(texture)) => {
+                let size = texture.size_vec2();
+                let scale = (max_size / size.x.max(size.y)).min(1.0);
+                ui.image((texture.id(), size * scale));
+                false
+            }
+            Some(ThumbnailState::Loading) => {
+                ui.spinner();
+                false
+            }
+            Some(ThumbnailState::Failed(e)) => {
+                ui.label(
+                    egui::RichText::new("Thumbnail unavailable")
+                        .size(12.0)
+                        .color(egui::Color32::from_rgb(64, 64, 64)),
+                )
+                .on_hover_text(e.as_str());
+                false
+            }
+            None => {
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(max_size, max_size), egui::Sense::hover());
+                ui.is_rect_visible(rect)
+            }
+        }
+    }
+
+    /// Show the thumbnail `uri` (relative to `manifest_label`). Decoding starts the first time
+    /// the thumbnail's area is actually visible; later frames reuse the cached texture.
+    pub(crate) fn show(&mut self, ui: &mut egui::Ui, manifest_label: &str, uri: &str) {
+        self.show_resource(ui, manifest_label, uri, THUMBNAIL_MAX_SIZE);
+    }
+
+    /// [`Self::show`] with a size limit other than the tree's.
+    pub(crate) fn show_resource(
+        &mut self,
+        ui: &mut egui::Ui,
+        manifest_label: &str,
+        uri: &str,
+        max_size: f32,
+    ) {
+        let key = crtool::resolve_resource_uri(manifest_label, uri);
+        if self.show_cached(ui, &key, max_size) {
+            self.entries.insert(key.clone(), ThumbnailState::Loading);
+            if self.resources.is_none() {
+                self.resources = Some(self.start_loader(ui.ctx()));
+            }
+            let request = ResourceRequest {
+                key,
+                manifest_label: manifest_label.to_string(),
+                uri: uri.to_string(),
+                max_size,
+            };
+            if let Some(Err(mpsc::SendError(request))) =
+                self.resources.as_ref().map(|r| r.send(request))
+            {
+                self.entries.insert(
+                    request.key,
+                    ThumbnailState::Failed("Thumbnail loader stopped".to_string()),
+                );
+            }
+        }
+    }
+
+    /// Show the asset itself, decoded from the file, for formats crTool can render.
+    pub(crate) fn show_asset(&mut self, ui: &mut egui::Ui) {
+        let can_render =
+            crtool::asset_format_for_path(&self.file_path).is_some_and(|f| f.can_thumbnail);
+        if !can_render {
+            ui.label(
+                egui::RichText::new("No preview for this format")
+                    .size(12.0)
+                    .color(egui::Color32::from_rgb(64, 64, 64)),
+            );
+            return;
+        }
+        if self.show_cached(ui, ASSET_KEY, PREVIEW_MAX_SIZE) {
+            self.entries
+                .insert(ASSET_KEY.to_string(), ThumbnailState::Loading);
+            let file_path = self.file_path.clone();
+            Self::decode_later(
+                &self.decoded,
+                ui.ctx(),
+                ASSET_KEY.to_string(),
+                PREVIEW_MAX_SIZE,
+                move || std::fs::read(&file_path).map_err(|e| e.to_string()),
+            );
+        }
+    }
+}
+
+/// Thumbnail resource URI of an ingredient (`thumbnail.url`, `thumbnail.identifier`, or a string).
+pub(crate) fn ingredient_thumbnail_uri(ingredient: &serde_json::Value) -> Option<&str> {
+    let thumbnail = ingredient.get("thumbnail")?;
+    thumbnail.as_str().or_else(|| {
+        thumbnail
+            .get("url")
+            .or_else(|| thumbnail.get("identifier"))
+            .and_then(|v| v.as_str())
+    })
+}
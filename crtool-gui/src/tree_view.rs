@@ -0,0 +1,457 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Manifest Data tree settings and guards for huge manifests: a configurable default expand
+//! depth, a node count above which the tree starts collapsed, a virtualized list for very
+//! large arrays (which are shortened to a preview in the tree itself), and a per-manifest list
+//! for very large stores whose manifest trees are only built when expanded.
+
+use eframe::egui;
+use egui_json_tree::{DefaultExpand, JsonTree};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::collections::BTreeMap;
+
+/// Items of a large array kept in the tree as a preview.
+const PREVIEW_ITEMS: usize = 10;
+/// Longest compact JSON shown for one row of a large array.
+const MAX_ROW_CHARS: usize = 240;
+
+/// View → Tree Settings.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TreeViewSettings {
+    /// Levels expanded when a document is opened.
+    pub(crate) expand_depth: u8,
+    /// Documents with more JSON nodes than this start fully collapsed.
+    pub(crate) collapse_above_nodes: usize,
+    /// Arrays longer than this are shown in the virtualized Large arrays list.
+    pub(crate) large_array_len: usize,
+    /// Manifest stores whose crJSON is larger than this (KiB) list their manifests and build
+    /// each manifest's tree only when it is expanded.
+    pub(crate) lazy_above_kib: usize,
+}
+
+impl Default for TreeViewSettings {
+    fn default() -> Self {
+        Self {
+            expand_depth: 2,
+            collapse_above_nodes: 20_000,
+            large_array_len: 500,
+            lazy_above_kib: 1024,
+        }
+    }
+}
+
+impl TreeViewSettings {
+    /// Default expansion for a document with `node_count` nodes.
+    pub(crate) fn default_expand(&self, node_count: usize) -> DefaultExpand<'static> {
+        if node_count > self.collapse_above_nodes {
+            DefaultExpand::None
+        } else {
+            DefaultExpand::ToLevel(self.expand_depth)
+        }
+    }
+
+    /// Whether a store with `json_len` bytes of crJSON is shown as a lazy manifest list.
+    pub(crate) fn is_lazy(&self, json_len: usize) -> bool {
+        json_len > self.lazy_above_kib * 1024
+    }
+}
+
+/// An array too large for the tree, by JSON pointer.
+#[derive(Clone)]
+pub(crate) struct LargeArray {
+    pub(crate) pointer: String,
+    pub(crate) len: usize,
+}
+
+/// The manifest value as shown in the tree, with large arrays shortened.
+#[derive(Clone)]
+pub(crate) struct PreparedTree {
+    /// `large_array_len` this was prepared with.
+    large_array_len: usize,
+    pub(crate) node_count: usize,
+    pub(crate) value: serde_json::Value,
+    pub(crate) large_arrays: Vec<LargeArray>,
+}
+
+impl PreparedTree {
+    pub(crate) fn new(value: &serde_json::Value, large_array_len: usize) -> Self {
+        Self::from_owned(value.clone(), large_array_len)
+    }
+
+    /// Prepare `value` itself rather than a copy of it.
+    fn from_owned(mut shown: serde_json::Value, large_array_len: usize) -> Self {
+        let mut prepared = Self {
+            large_array_len,
+            node_count: 0,
+            value: serde_json::Value::Null,
+            large_arrays: Vec::new(),
+        };
+        prepared.shorten(&mut shown, String::new());
+        prepared.value = shown;
+        prepared
+    }
+
+    /// True when it must be rebuilt for changed settings.
+    pub(crate) fn is_stale(&self, settings: &TreeViewSettings) -> bool {
+        self.large_array_len != settings.large_array_len
+    }
+
+    fn shorten(&mut self, value: &mut serde_json::Value, pointer: String) {
+        self.node_count += 1;
+        match value {
+            serde_json::Value::Array(items) => {
+                if items.len() > self.large_array_len {
+                    // Count the hidden items so the collapse threshold still sees their weight.
+                    self.node_count += items.iter().map(count_nodes).sum::<usize>();
+                    self.large_arrays.push(LargeArray {
+                        pointer: pointer.clone(),
+                        len: items.len(),
+                    });
+                    let more = items.len() - PREVIEW_ITEMS;
+                    items.truncate(PREVIEW_ITEMS);
+                    items.push(serde_json::Value::String(format!(
+                        "… {} more items (see Large arrays below the tree)",
+                        more
+                    )));
+                    return;
+                }
+                for (i, item) in items.iter_mut().enumerate() {
+                    self.shorten(item, format!("{}/{}", pointer, i));
+                }
+            }
+            serde_json::Value::Object(map) => {
+                for (key, item) in map.iter_mut() {
+                    let escaped = key.replace('~', "~0").replace('/', "~1");
+                    self.shorten(item, format!("{}/{}", pointer, escaped));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// One manifest of a [`LazyManifestTree`].
+struct LazyManifest {
+    label: String,
+    title: Option<String>,
+    /// The manifest's crJSON, parsed the first time it is expanded.
+    raw: Box<RawValue>,
+    /// Built the first time the manifest is expanded.
+    tree: Option<PreparedTree>,
+}
+
+/// The fields of a manifest shown in its heading (see [`crtool::provenance::manifest_title`]),
+/// read without building the rest of the manifest.
+#[derive(Deserialize)]
+struct ManifestHeading {
+    label: Option<String>,
+    title: Option<String>,
+    #[serde(rename = "claim.v2")]
+    claim_v2: Option<ClaimTitle>,
+    claim: Option<ClaimTitle>,
+}
+
+#[derive(Deserialize)]
+struct ClaimTitle {
+    #[serde(rename = "dc:title")]
+    dc_title: Option<String>,
+    title: Option<String>,
+}
+
+impl ManifestHeading {
+    fn title(self) -> Option<String> {
+        self.claim_v2
+            .or(self.claim)
+            .and_then(|c| c.dc_title.or(c.title))
+            .or(self.title)
+    }
+}
+
+impl LazyManifest {
+    fn new(raw: Box<RawValue>) -> Self {
+        let heading = serde_json::from_str::<ManifestHeading>(raw.get()).ok();
+        Self {
+            label: heading
+                .as_ref()
+                .and_then(|h| h.label.clone())
+                .unwrap_or_else(|| "(no label)".to_string()),
+            title: heading.and_then(ManifestHeading::title),
+            raw,
+            tree: None,
+        }
+    }
+}
+
+/// Manifest Data of a very large store: the document's other top-level fields as one tree, and
+/// its `manifests` as a list whose entries are kept as raw JSON and parsed, prepared and rendered
+/// only when expanded, so opening an asset with hundreds of ingredient manifests does not build
+/// one huge tree.
+pub(crate) struct LazyManifestTree {
+    /// `large_array_len` this was prepared with.
+    large_array_len: usize,
+    /// The document without its `manifests` array.
+    header: PreparedTree,
+    manifests: Vec<LazyManifest>,
+}
+
+impl LazyManifestTree {
+    /// Split the crJSON text `json` into its top-level fields and its raw manifests.
+    pub(crate) fn new(json: &str, large_array_len: usize) -> Self {
+        let mut fields: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
+        let mut manifests = Vec::new();
+        let document: BTreeMap<String, Box<RawValue>> =
+            serde_json::from_str(json).unwrap_or_default();
+        for (key, raw) in document {
+            if key == "manifests" {
+                let raw: Vec<Box<RawValue>> = serde_json::from_str(raw.get()).unwrap_or_default();
+                manifests = raw.into_iter().map(LazyManifest::new).collect();
+            } else if let Ok(value) = serde_json::from_str(raw.get()) {
+                fields.insert(key, value);
+            }
+        }
+        Self {
+            large_array_len,
+            header: PreparedTree::from_owned(serde_json::Value::Object(fields), large_array_len),
+            manifests,
+        }
+    }
+
+    /// True when it must be rebuilt for changed settings.
+    pub(crate) fn is_stale(&self, settings: &TreeViewSettings) -> bool {
+        self.large_array_len != settings.large_array_len
+    }
+
+    /// Number of manifest trees built so far.
+    pub(crate) fn loaded_count(&self) -> usize {
+        self.manifests.iter().filter(|m| m.tree.is_some()).count()
+    }
+
+    /// Show the header tree and the manifest list. `value` is the full document; `search`
+    /// applies to the header and to expanded manifests. `reset` re-applies default expansion.
+    pub(crate) fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        value: &serde_json::Value,
+        settings: &TreeViewSettings,
+        active_label: &str,
+        search: &str,
+        reset: bool,
+    ) {
+        let expand = |node_count: usize| {
+            if search.is_empty() {
+                settings.default_expand(node_count)
+            } else {
+                DefaultExpand::SearchResults(search)
+            }
+        };
+        let response = JsonTree::new("manifest-data-tree", &self.header.value)
+            .default_expand(expand(self.header.node_count))
+            .show(ui);
+        if reset {
+            response.reset_expanded(ui);
+        }
+        show_large_arrays(ui, value, &self.header.large_arrays);
+
+        ui.separator();
+        ui.label(
+            egui::RichText::new(format!(
+                "manifests ({}, {} loaded; each loads when expanded)",
+                self.manifests.len(),
+                self.loaded_count()
+            ))
+            .strong(),
+        );
+        let large_array_len = self.large_array_len;
+        for (i, manifest) in self.manifests.iter_mut().enumerate() {
+            let active = manifest.label == active_label;
+            let heading = format!(
+                "{}[{}] {}{}",
+                if active { "⭐ " } else { "" },
+                i,
+                manifest.label,
+                manifest
+                    .title
+                    .as_deref()
+                    .map(|t| format!(" — {t}"))
+                    .unwrap_or_default()
+            );
+            egui::CollapsingHeader::new(heading)
+                .id_salt(("lazy_manifest", i))
+                .default_open(false)
+                .show(ui, |ui| {
+                    let Some(item) = value.get("manifests").and_then(|m| m.get(i)) else {
+                        return;
+                    };
+                    let tree = manifest.tree.get_or_insert_with(|| {
+                        let parsed = serde_json::from_str(manifest.raw.get()).unwrap_or_default();
+                        PreparedTree::from_owned(parsed, large_array_len)
+                    });
+                    let response = JsonTree::new(("manifest-data-tree", i), &tree.value)
+                        .default_expand(expand(tree.node_count))
+                        .show(ui);
+                    if reset {
+                        response.reset_expanded(ui);
+                    }
+                    // Large array pointers are relative to this manifest
+                    show_large_arrays(ui, item, &tree.large_arrays);
+                });
+        }
+    }
+}
+
+/// Number of JSON values in `value`, counting containers and leaves.
+fn count_nodes(value: &serde_json::Value) -> usize {
+    let mut count = 0;
+    let mut stack = vec![value];
+    while let Some(v) = stack.pop() {
+        count += 1;
+        match v {
+            serde_json::Value::Array(items) => stack.extend(items),
+            serde_json::Value::Object(map) => stack.extend(map.values()),
+            _ => {}
+        }
+    }
+    count
+}
+
+/// Virtualized list of each large array: only the visible rows are formatted and drawn.
+pub(crate) fn show_large_arrays(
+    ui: &mut egui::Ui,
+    value: &serde_json::Value,
+    large_arrays: &[LargeArray],
+) {
+    if large_arrays.is_empty() {
+        return;
+    }
+    ui.separator();
+    ui.label(egui::RichText::new("Large arrays").size(15.0).strong());
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    for array in large_arrays {
+        let Some(items) = value.pointer(&array.pointer).and_then(|v| v.as_array()) else {
+            continue;
+        };
+        let title = if array.pointer.is_empty() {
+            "/".to_string()
+        } else {
+            array.pointer.clone()
+        };
+        egui::CollapsingHeader::new(format!("{} ({} items)", title, array.len))
+            .id_salt(("large_array", &array.pointer))
+            .default_open(false)
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .id_salt(("large_array_rows", &array.pointer))
+                    .max_height(300.0)
+                    .show_rows(ui, row_height, items.len(), |ui, rows| {
+                        for i in rows {
+                            let mut text = serde_json::to_string(&items[i]).unwrap_or_default();
+                            if text.len() > MAX_ROW_CHARS {
+                                let cut = (0..=MAX_ROW_CHARS)
+                                    .rev()
+                                    .find(|&n| text.is_char_boundary(n))
+                                    .unwrap_or(0);
+                                text.truncate(cut);
+                                text.push('…');
+                            }
+                            ui.monospace(format!("[{}] {}", i, text));
+                        }
+                    });
+            });
+    }
+}
+
+/// View → Tree Settings window.
+pub(crate) fn show_tree_settings_window(
+    ctx: &egui::Context,
+    settings: &mut TreeViewSettings,
+    open: &mut bool,
+) {
+    egui::Window::new("Tree Settings")
+        .open(open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("tree_settings")
+                .num_columns(2)
+                .spacing([16.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label("Default expand depth");
+                    ui.add(egui::DragValue::new(&mut settings.expand_depth).range(0..=10));
+                    ui.end_row();
+
+                    ui.label("Start collapsed above (nodes)");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.collapse_above_nodes)
+                            .range(100..=1_000_000)
+                            .speed(100),
+                    );
+                    ui.end_row();
+
+                    ui.label("Large array length");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.large_array_len)
+                            .range(PREVIEW_ITEMS + 1..=100_000)
+                            .speed(10),
+                    );
+                    ui.end_row();
+
+                    ui.label("Load manifests on expand above (KiB)");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.lazy_above_kib)
+                            .range(64..=1_048_576)
+                            .speed(64),
+                    );
+                    ui.end_row();
+                });
+            ui.add_space(6.0);
+            if ui.button("Restore Defaults").clicked() {
+                *settings = TreeViewSettings::default();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lazy_tree_keeps_manifests_raw_until_expanded() {
+        let json = serde_json::json!({
+            "@context": "https://c2pa.org/crjson",
+            "manifests": [
+                {"label": "urn:c2pa:a", "claim.v2": {"dc:title": "a.jpg"}},
+                {"label": "urn:c2pa:b", "title": "b.jpg"},
+                {"assertions": {}}
+            ]
+        })
+        .to_string();
+        let tree = LazyManifestTree::new(&json, 100);
+        assert!(tree.header.value.get("manifests").is_none());
+        assert_eq!(tree.header.value["@context"], "https://c2pa.org/crjson");
+        let headings: Vec<_> = tree
+            .manifests
+            .iter()
+            .map(|m| (m.label.as_str(), m.title.as_deref()))
+            .collect();
+        assert_eq!(
+            headings,
+            vec![
+                ("urn:c2pa:a", Some("a.jpg")),
+                ("urn:c2pa:b", Some("b.jpg")),
+                ("(no label)", None)
+            ]
+        );
+        assert_eq!(tree.loaded_count(), 0);
+    }
+}
@@ -0,0 +1,247 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Preferences → Trust Anchors: edit the trust policy (default lists, user PEM files, allowed
+//! certificate hashes and EKUs, OCSP) used for the Trusted / Untrusted badge.
+
+use crtool::trust::{certificate_fingerprints, pem_certificates, TrustConfig, TrustLists};
+use eframe::egui;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Edits in progress in the Trust Anchors window; applied to the app only on **Apply**.
+#[derive(Default)]
+pub(crate) struct TrustPrefsState {
+    pub(crate) draft: TrustConfig,
+    /// SHA-256 fingerprints per added file, or why the file could not be read.
+    fingerprints: BTreeMap<PathBuf, Result<Vec<String>, String>>,
+    /// `draft.allowed_hashes` being edited, one per line.
+    hashes_text: String,
+    /// `draft.allowed_ekus` being edited, one per line.
+    ekus_text: String,
+    /// Error from the last Apply, shown until the next one.
+    pub(crate) error: Option<String>,
+}
+
+impl TrustPrefsState {
+    /// Start editing a copy of `config`.
+    pub(crate) fn edit(config: &TrustConfig) -> Self {
+        let mut state = Self {
+            draft: config.clone(),
+            hashes_text: config.allowed_hashes.join("\n"),
+            ekus_text: config.allowed_ekus.join("\n"),
+            ..Default::default()
+        };
+        for path in config.anchor_files.iter().chain(&config.allowed_files) {
+            state.read_fingerprints(path);
+        }
+        state
+    }
+
+    fn read_fingerprints(&mut self, path: &Path) {
+        let result = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|pem| {
+                let fingerprints = certificate_fingerprints(&pem);
+                if fingerprints.is_empty() {
+                    Err("no PEM certificates found".to_string())
+                } else {
+                    Ok(fingerprints)
+                }
+            });
+        self.fingerprints.insert(path.to_path_buf(), result);
+    }
+}
+
+/// Non-empty trimmed lines of a multi-line text field.
+fn text_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn pick_pem_files() -> Vec<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter("PEM certificates", &["pem", "crt", "cer"])
+        .pick_files()
+        .unwrap_or_default()
+}
+
+/// One list of PEM files with their fingerprints, plus Add / Remove controls.
+fn file_list_ui(
+    ui: &mut egui::Ui,
+    id: &str,
+    files: &mut Vec<PathBuf>,
+    fingerprints: &BTreeMap<PathBuf, Result<Vec<String>, String>>,
+) -> Vec<PathBuf> {
+    let mut remove = None;
+    if files.is_empty() {
+        ui.weak("None");
+    }
+    for (i, path) in files.iter().enumerate() {
+        ui.horizontal(|ui| {
+            if ui.small_button("🗑").on_hover_text("Remove").clicked() {
+                remove = Some(i);
+            }
+            ui.label(path.display().to_string());
+        });
+        match fingerprints.get(path) {
+            Some(Ok(list)) => {
+                for fp in list {
+                    ui.indent((id, i), |ui| {
+                        ui.monospace(egui::RichText::new(fp).size(11.0));
+                    });
+                }
+            }
+            Some(Err(e)) => {
+                ui.indent((id, i), |ui| {
+                    ui.colored_label(egui::Color32::RED, e);
+                });
+            }
+            None => {}
+        }
+    }
+    if let Some(i) = remove {
+        files.remove(i);
+    }
+    let mut added = Vec::new();
+    if ui.button("➕ Add PEM...").clicked() {
+        for path in pick_pem_files() {
+            if !files.contains(&path) {
+                files.push(path.clone());
+                added.push(path);
+            }
+        }
+    }
+    added
+}
+
+/// Preferences → Trust Anchors window. Returns true when the user clicked **Apply**.
+pub(crate) fn show_trust_preferences_window(
+    ctx: &egui::Context,
+    state: &mut TrustPrefsState,
+    default_lists: Option<&TrustLists>,
+    open: &mut bool,
+) -> bool {
+    let mut applied = false;
+    egui::Window::new("Trust Anchors")
+        .open(open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(560.0)
+        .show(ctx, |ui| {
+            ui.checkbox(
+                &mut state.draft.use_default_lists,
+                "Trust the C2PA and Content Credentials default lists",
+            );
+            ui.indent("default_lists", |ui| match default_lists {
+                Some(lists) => {
+                    ui.weak(format!(
+                        "{} anchor certificate(s) loaded",
+                        pem_certificates(&lists.anchors).len()
+                    ));
+                }
+                None => {
+                    ui.weak("Not available (the lists could not be fetched at startup)");
+                }
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .max_height(360.0)
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new("Trust anchors").strong());
+                    ui.weak("Root or intermediate CA certificates; signers chaining to them are trusted.");
+                    let added = file_list_ui(
+                        ui,
+                        "anchor_files",
+                        &mut state.draft.anchor_files,
+                        &state.fingerprints,
+                    );
+                    for path in added {
+                        state.read_fingerprints(&path);
+                    }
+                    ui.add_space(8.0);
+
+                    ui.label(egui::RichText::new("Allowed certificates").strong());
+                    ui.weak("Signing certificates trusted as-is, without a chain to an anchor.");
+                    let added = file_list_ui(
+                        ui,
+                        "allowed_files",
+                        &mut state.draft.allowed_files,
+                        &state.fingerprints,
+                    );
+                    for path in added {
+                        state.read_fingerprints(&path);
+                    }
+                    ui.add_space(8.0);
+
+                    ui.label(egui::RichText::new("Allowed certificate hashes").strong());
+                    ui.weak("Base64 SHA-256 hashes of signing certificates, one per line.");
+                    if ui
+                        .add(
+                            egui::TextEdit::multiline(&mut state.hashes_text)
+                                .code_editor()
+                                .desired_rows(2)
+                                .desired_width(f32::INFINITY),
+                        )
+                        .changed()
+                    {
+                        state.draft.allowed_hashes = text_lines(&state.hashes_text);
+                    }
+                    ui.add_space(8.0);
+
+                    ui.label(egui::RichText::new("Allowed EKUs").strong());
+                    ui.weak("Extended key usage OIDs accepted for signing certificates, one per line.");
+                    if ui
+                        .add(
+                            egui::TextEdit::multiline(&mut state.ekus_text)
+                                .code_editor()
+                                .desired_rows(2)
+                                .desired_width(f32::INFINITY),
+                        )
+                        .changed()
+                    {
+                        state.draft.allowed_ekus = text_lines(&state.ekus_text);
+                    }
+                    ui.add_space(8.0);
+
+                    ui.checkbox(
+                        &mut state.draft.revocation.ocsp_fetch,
+                        "Check revocation by fetching OCSP responses (needs network access)",
+                    );
+                });
+
+            if let Some(error) = &state.error {
+                ui.separator();
+                ui.colored_label(egui::Color32::RED, error);
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Apply")
+                    .on_hover_text("Save and re-check open documents")
+                    .clicked()
+                {
+                    applied = true;
+                }
+                if ui.button("Restore Defaults").clicked() {
+                    state.draft = TrustConfig::default();
+                    state.hashes_text.clear();
+                    state.ekus_text.clear();
+                }
+            });
+        });
+    applied
+}
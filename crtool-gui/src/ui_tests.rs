@@ -0,0 +1,239 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Headless UI tests: `CrtoolApp` is driven through egui_kittest, which renders frames without
+//! a window and exposes the UI through AccessKit so widgets can be found by their labels.
+
+use crate::app::{AppCommand, CrtoolApp};
+use crate::document::{DocumentCommand, DocumentTab};
+use crtool::trust::TrustConfig;
+use crtool::{Severity, ValidationError, ValidationResult};
+use eframe::egui;
+use egui_kittest::kittest::Queryable;
+use egui_kittest::Harness;
+use std::path::PathBuf;
+
+/// Signed sample (a Pixel camera capture with Content Credentials).
+const SIGNED_SAMPLE: &str = "PXL_20260208_202351558.jpg";
+
+/// Frames rendered after each change. Fixed rather than `Harness::run`, because panels that load
+/// in the background (thumbnails) keep requesting repaints.
+const FRAMES: usize = 4;
+
+fn sample(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/fixtures/assets")
+        .join(name)
+}
+
+/// Harness around an app with default trust settings (no network access) and `files` open.
+fn harness(files: Vec<PathBuf>) -> Harness<'static, CrtoolApp> {
+    let app = CrtoolApp::new_with_optional_files(files, TrustConfig::default(), None);
+    let mut harness = Harness::builder()
+        .with_size(egui::vec2(1200.0, 800.0))
+        .build_state(|ctx, app: &mut CrtoolApp| app.run_frame(ctx), app);
+    harness.run_steps(FRAMES);
+    harness
+}
+
+fn apply(harness: &mut Harness<'_, CrtoolApp>, command: AppCommand) {
+    let ctx = harness.ctx.clone();
+    harness.state_mut().apply(&ctx, command);
+    harness.run_steps(FRAMES);
+}
+
+fn tab_count(harness: &Harness<'_, CrtoolApp>) -> usize {
+    harness.state().dock_state.iter_all_tabs().count()
+}
+
+fn first_tab(harness: &mut Harness<'_, CrtoolApp>) -> &mut DocumentTab {
+    let (_, tab) = harness
+        .state_mut()
+        .dock_state
+        .iter_all_tabs_mut()
+        .next()
+        .expect("a tab is open");
+    tab
+}
+
+/// Replace the tab's validation outcome: the given schema errors, and no `validationResults`
+/// failures in the manifest.
+fn set_validation(tab: &mut DocumentTab, errors: Vec<ValidationError>) {
+    let manifest = tab
+        .document
+        .extraction_result
+        .as_mut()
+        .expect("manifest was read");
+    if let Some(value) = manifest.manifest_value.as_object_mut() {
+        value.remove("validationResults");
+    }
+    if let Some(manifests) = manifest
+        .manifest_value
+        .get_mut("manifests")
+        .and_then(|m| m.as_array_mut())
+    {
+        for m in manifests.iter_mut().filter_map(|m| m.as_object_mut()) {
+            m.remove("validationResults");
+            m.remove("ingredientDeltas");
+        }
+    }
+    tab.document.validation_result = Some(ValidationResult::from_findings(
+        tab.document.file_path.to_string_lossy().to_string(),
+        errors,
+    ));
+}
+
+#[test]
+fn test_welcome_then_open_and_close() {
+    let mut harness = harness(Vec::new());
+    harness.get_by_label_contains("Open one or more C2PA-supported files");
+    assert_eq!(tab_count(&harness), 0);
+
+    // Dropped files open as tabs; unsupported ones are ignored
+    harness.input_mut().dropped_files.extend(
+        [
+            sample(SIGNED_SAMPLE),
+            sample("../minimal_valid_crjson.json"),
+        ]
+        .map(|path| egui::DroppedFile {
+            path: Some(path),
+            ..Default::default()
+        }),
+    );
+    harness.run_steps(FRAMES);
+    assert_eq!(tab_count(&harness), 1);
+    assert!(harness
+        .query_by_label_contains("Open one or more C2PA-supported files")
+        .is_none());
+
+    apply(&mut harness, AppCommand::OpenFiles(vec![sample("Dog.png")]));
+    assert_eq!(tab_count(&harness), 2);
+    apply(&mut harness, AppCommand::Close);
+    assert_eq!(tab_count(&harness), 1);
+    apply(&mut harness, AppCommand::CloseAll);
+    assert_eq!(tab_count(&harness), 0);
+    harness.get_by_label_contains("Open one or more C2PA-supported files");
+}
+
+#[test]
+fn test_samples_render_tree_or_error() {
+    let mut harness = harness(vec![sample(SIGNED_SAMPLE)]);
+    harness.get_by_label_contains("Active Manifest:");
+    harness.get_by_label_contains("Manifest Data");
+    harness.get_by_label_contains("Manifest & Ingredients Tree");
+
+    // The unsigned samples show the no-credentials panel rather than an error
+    for unsigned in ["Dog.jpg", "Dog.png", "Dog.webp"] {
+        apply(&mut harness, AppCommand::CloseAll);
+        apply(&mut harness, AppCommand::OpenFiles(vec![sample(unsigned)]));
+        harness.get_by_label_contains("No Content Credentials");
+        harness.get_by_label_contains("SHA-256:");
+        assert!(harness.query_by_label_contains("Error:").is_none());
+        assert!(
+            harness
+                .query_by_label_contains("Active Manifest:")
+                .is_none(),
+            "{unsigned} should not show a manifest"
+        );
+    }
+}
+
+#[test]
+fn test_no_credentials_manifest_search() {
+    let mut harness = harness(vec![sample("Dog.png")]);
+    harness
+        .get_by_label_contains("Search for a sidecar or remote manifest")
+        .click();
+    harness.run_steps(FRAMES);
+    // The fixtures have neither a Dog.c2pa sidecar nor a remote reference
+    harness.get_by_label_contains("No sidecar");
+    harness.get_by_label_contains("No Content Credentials");
+}
+
+#[test]
+fn test_validation_banner_states() {
+    let mut harness = harness(vec![sample(SIGNED_SAMPLE)]);
+
+    set_validation(first_tab(&mut harness), Vec::new());
+    harness.run_steps(FRAMES);
+    harness.get_by_label_contains("Manifest is valid!");
+    assert!(harness
+        .query_by_label_contains("Validation failed")
+        .is_none());
+
+    set_validation(
+        first_tab(&mut harness),
+        vec![ValidationError {
+            instance_path: "/manifests/0/claim".to_string(),
+            message: "missing required property".to_string(),
+            severity: Severity::Error,
+            rule: "required".to_string(),
+            schema: None,
+        }],
+    );
+    harness.run_steps(FRAMES);
+    harness.get_by_label_contains("Validation failed (1 error(s))");
+    harness.get_by_label_contains("Schema validation errors:");
+    harness.get_by_label_contains("/manifests/0/claim");
+    assert!(harness
+        .query_by_label_contains("Manifest is valid!")
+        .is_none());
+
+    set_validation(
+        first_tab(&mut harness),
+        vec![ValidationError {
+            instance_path: "/manifests/0/claim.v2".to_string(),
+            message: "Missing recommended field \"dc:title\"".to_string(),
+            severity: Severity::Warning,
+            rule: "recommended-field".to_string(),
+            schema: None,
+        }],
+    );
+    harness.run_steps(FRAMES);
+    harness.get_by_label_contains("Manifest is valid! (1 warning(s))");
+    harness.get_by_label_contains("Schema validation warnings:");
+    harness.get_by_label_contains("Warning: Missing recommended field");
+}
+
+#[test]
+fn test_preview_pane() {
+    let mut harness = harness(vec![sample(SIGNED_SAMPLE)]);
+    assert!(harness.query_by_label_contains("Claim thumbnail").is_none());
+
+    harness.get_by_label("🖼 Preview").click();
+    harness.run_steps(FRAMES);
+    harness.get_by_label("Asset");
+    harness.get_by_label("Claim thumbnail");
+}
+
+#[test]
+fn test_raw_json_toggle() {
+    let mut harness = harness(vec![sample(SIGNED_SAMPLE)]);
+    harness.get_by_label_contains("Manifest & Ingredients Tree");
+    assert!(harness.query_by_label_contains("Raw JSON:").is_none());
+
+    apply(
+        &mut harness,
+        AppCommand::Document(DocumentCommand::ToggleRawJson),
+    );
+    harness.get_by_label_contains("Raw JSON:");
+    assert!(harness
+        .query_by_label_contains("Manifest & Ingredients Tree")
+        .is_none());
+
+    apply(
+        &mut harness,
+        AppCommand::Document(DocumentCommand::ToggleRawJson),
+    );
+    harness.get_by_label_contains("Manifest & Ingredients Tree");
+    assert!(harness.query_by_label_contains("Raw JSON:").is_none());
+}
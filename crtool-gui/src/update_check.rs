@@ -0,0 +1,115 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Help → Check for Updates: fetch and verify the signed releases feed on a background thread.
+
+use crtool::update::{check_feed, offline_from_env, UpdateFeedConfig, UpdateStatus};
+use eframe::egui;
+use egui_twemoji::EmojiLabel;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Progress of the update check.
+#[derive(Clone)]
+pub(crate) enum UpdateCheck {
+    Checking,
+    Done(Result<UpdateStatus, String>),
+}
+
+/// Shared between the UI and the background check; `None` until a check is started.
+pub(crate) type UpdateCheckState = Arc<Mutex<Option<UpdateCheck>>>;
+
+fn run_check() -> Result<UpdateStatus, String> {
+    if offline_from_env() {
+        return Err("Offline mode (CRTOOL_OFFLINE) is set; update check skipped.".to_string());
+    }
+    let config = UpdateFeedConfig::from_env().map_err(|e| format!("{:#}", e))?;
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("crTool-gui/1.0")
+        .timeout(UPDATE_CHECK_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let fetch = |url: &str| -> Result<String, String> {
+        client
+            .get(url)
+            .send()
+            .and_then(|r| r.error_for_status())
+            .and_then(|r| r.text())
+            .map_err(|e| format!("Failed to fetch {}: {}", url, e))
+    };
+    let feed = fetch(&config.url)?;
+    let signature = fetch(&config.signature_url())?;
+    check_feed(
+        env!("CARGO_PKG_VERSION"),
+        feed.as_bytes(),
+        &signature,
+        &config.public_key,
+    )
+    .map_err(|e| format!("{:#}", e))
+}
+
+/// Start a check on a background thread, repainting when it finishes.
+pub(crate) fn spawn_update_check(state: UpdateCheckState, ctx: egui::Context) {
+    if let Ok(mut s) = state.lock() {
+        *s = Some(UpdateCheck::Checking);
+    }
+    std::thread::spawn(move || {
+        let result = run_check();
+        if let Ok(mut s) = state.lock() {
+            *s = Some(UpdateCheck::Done(result));
+        }
+        ctx.request_repaint();
+    });
+}
+
+/// Window showing the state of the update check.
+pub(crate) fn show_update_window(ctx: &egui::Context, state: &UpdateCheckState, open: &mut bool) {
+    let current = state.lock().ok().and_then(|s| s.clone());
+    egui::Window::new("Check for Updates")
+        .open(open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| match current {
+            None | Some(UpdateCheck::Checking) => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Checking for updates...");
+                });
+            }
+            Some(UpdateCheck::Done(Ok(UpdateStatus::UpToDate { current }))) => {
+                EmojiLabel::new(format!("✅ crTool {} is up to date.", current)).show(ui);
+            }
+            Some(UpdateCheck::Done(Ok(UpdateStatus::Available { current, latest }))) => {
+                EmojiLabel::new(
+                    egui::RichText::new(format!(
+                        "⬆️ crTool {} is available (you have {}).",
+                        latest.version, current
+                    ))
+                    .size(15.0),
+                )
+                .show(ui);
+                if let Some(notes) = &latest.notes {
+                    ui.label(notes);
+                }
+                ui.hyperlink_to("Download", &latest.url);
+            }
+            Some(UpdateCheck::Done(Err(e))) => {
+                EmojiLabel::new(
+                    egui::RichText::new(format!("❌ {}", e))
+                        .color(egui::Color32::from_rgb(230, 80, 80)),
+                )
+                .show(ui);
+            }
+        });
+}
@@ -10,15 +10,22 @@ OF ANY KIND, either express or implied. See the License for the specific languag
 governing permissions and limitations under the License.
 */
 
+use crate::report_export::ReportFormat;
+use crtool::graph_export::GraphFormat;
+use crtool::trust::{TrustConfig, TrustLists};
+use crtool::trust_presentation::{trust_presentation, TrustPresentation, TrustPresentations};
 use crtool::Settings;
 use crtool::{
-    build_trust_settings, default_extraction_settings, C2PA_TRUST_ANCHORS_URL,
-    INTERIM_ALLOWED_LIST_URL, INTERIM_TRUST_ANCHORS_URL, INTERIM_TRUST_CONFIG_URL,
+    default_extraction_settings, C2PA_TRUST_ANCHORS_URL, INTERIM_ALLOWED_LIST_URL,
+    INTERIM_TRUST_ANCHORS_URL, INTERIM_TRUST_CONFIG_URL,
 };
 use eframe::egui;
 use egui_code_editor::Syntax;
 use std::collections::BTreeSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the location of the GUI's trust configuration file.
+pub(crate) const TRUST_CONFIG_ENV: &str = "CRTOOL_TRUST_CONFIG";
 
 /// Syntax definition for JSON (keywords true/false/null) for the code editor.
 pub(crate) fn json_syntax() -> Syntax {
@@ -34,6 +41,42 @@ pub(crate) fn json_syntax() -> Syntax {
     }
 }
 
+/// Default file name for an exported manifest: `<asset stem>-manifest.json`.
+pub(crate) fn manifest_export_file_name(asset_path: &Path) -> String {
+    asset_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(|s| format!("{}-manifest.json", s))
+        .unwrap_or_else(|| "manifest.json".to_string())
+}
+
+/// Default file name for an exported provenance graph: `<asset stem>-provenance.<ext>`.
+pub(crate) fn provenance_graph_file_name(asset_path: &Path, format: GraphFormat) -> String {
+    let stem = asset_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("manifest");
+    format!("{}-provenance.{}", stem, format.extension())
+}
+
+/// Default file name for an exported content bill of materials: `<asset stem>-cbom.json`.
+pub(crate) fn cbom_file_name(asset_path: &Path) -> String {
+    let stem = asset_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("manifest");
+    format!("{}-cbom.json", stem)
+}
+
+/// Default file name for an exported validation report: `<asset stem>-report.<ext>`.
+pub(crate) fn report_file_name(asset_path: &Path, format: ReportFormat) -> String {
+    let stem = asset_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("manifest");
+    format!("{}-report.{}", stem, format.extension())
+}
+
 /// Convert a command-line argument to a file path. Handles macOS `file://` URLs
 /// that the system may pass when opening via "Open With" or drop-on-icon.
 pub(crate) fn arg_to_path(arg: &str) -> PathBuf {
@@ -46,11 +89,11 @@ pub(crate) fn arg_to_path(arg: &str) -> PathBuf {
     PathBuf::from(arg)
 }
 
-/// Build Settings for GUI extraction: trust lists when fetch succeeds, otherwise default settings.
-/// Trust verification stays enabled so claimSignature always shows trusted or untrusted.
-/// If the interim Content Credentials list fails (e.g. 404), the C2PA official list is still used
-/// so that certificates chaining to C2PA anchors show as trusted.
-pub(crate) fn gui_extraction_settings() -> Settings {
+/// Fetch the default trust lists: the official C2PA list plus, when reachable, the Content
+/// Credentials interim list. If the interim list fails (e.g. 404), the C2PA official list is still
+/// used so that certificates chaining to C2PA anchors show as trusted. Returns None when the C2PA
+/// list itself is unavailable.
+pub(crate) fn fetch_default_trust_lists() -> Option<TrustLists> {
     let client = match reqwest::blocking::Client::builder()
         .user_agent("crTool-gui/1.0")
         .build()
@@ -58,7 +101,7 @@ pub(crate) fn gui_extraction_settings() -> Settings {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Trust lists: failed to create HTTP client: {}", e);
-            return default_extraction_settings();
+            return None;
         }
     };
     let fetch = |url: &str| -> Option<String> {
@@ -70,15 +113,11 @@ pub(crate) fn gui_extraction_settings() -> Settings {
             .map_err(|e| eprintln!("Trust lists: failed to fetch {}: {}", url, e))
             .ok()
     };
-    let c2pa_anchors = match fetch(C2PA_TRUST_ANCHORS_URL) {
-        Some(s) => s,
-        None => {
-            eprintln!("Trust lists: C2PA list unavailable; certificates will show as untrusted.");
-            return default_extraction_settings();
-        }
+    let Some(c2pa_anchors) = fetch(C2PA_TRUST_ANCHORS_URL) else {
+        eprintln!("Trust lists: C2PA list unavailable; only user trust anchors will be used.");
+        return None;
     };
-    let interim_anchors = fetch(INTERIM_TRUST_ANCHORS_URL);
-    let trust_anchors = match &interim_anchors {
+    let anchors = match fetch(INTERIM_TRUST_ANCHORS_URL) {
         Some(s) => format!("{}\n{}", c2pa_anchors.trim_end(), s.trim_end()),
         None => {
             eprintln!(
@@ -88,19 +127,61 @@ pub(crate) fn gui_extraction_settings() -> Settings {
             c2pa_anchors
         }
     };
-    let allowed_list = fetch(INTERIM_ALLOWED_LIST_URL);
-    let trust_config = fetch(INTERIM_TRUST_CONFIG_URL);
-    match build_trust_settings(
-        &trust_anchors,
-        allowed_list.as_deref().map(|s| s.trim()),
-        trust_config.as_deref().map(|s| s.trim()),
-    ) {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("Trust lists: failed to build settings: {}", e);
-            default_extraction_settings()
-        }
+    Some(TrustLists {
+        anchors,
+        allowed_list: fetch(INTERIM_ALLOWED_LIST_URL).map(|s| s.trim().to_string()),
+        trust_config: fetch(INTERIM_TRUST_CONFIG_URL).map(|s| s.trim().to_string()),
+    })
+}
+
+/// Where the GUI keeps its trust configuration: `CRTOOL_TRUST_CONFIG` if set, otherwise
+/// `trust.json` in the platform's per-user config directory.
+pub(crate) fn trust_config_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(TRUST_CONFIG_ENV) {
+        return PathBuf::from(path);
     }
+    crtool::trust::user_config_dir().join("trust.json")
+}
+
+/// The saved trust configuration, or the default (published lists only) when none is saved yet.
+pub(crate) fn load_trust_config() -> TrustConfig {
+    let path = trust_config_path();
+    if !path.exists() {
+        return TrustConfig::default();
+    }
+    TrustConfig::load(&path).unwrap_or_else(|e| {
+        eprintln!("Trust configuration: {:#}; using defaults.", e);
+        TrustConfig::default()
+    })
+}
+
+/// Load the trust presentation file (see [`crtool::trust_presentation`]) for all trust labels,
+/// keeping the built-in mappings when it is missing or invalid.
+pub(crate) fn load_trust_presentations() {
+    match TrustPresentations::load_default() {
+        Ok(presentations) => crtool::trust_presentation::set_trust_presentations(presentations),
+        Err(e) => eprintln!("Trust presentation: {:#}; using defaults.", e),
+    }
+}
+
+/// Presentation of a trust status with its color for egui.
+pub(crate) fn trust_style(status: &str) -> (TrustPresentation, egui::Color32) {
+    let presentation = trust_presentation(status);
+    let [r, g, b] = presentation.rgb();
+    (presentation, egui::Color32::from_rgb(r, g, b))
+}
+
+/// Build Settings for GUI extraction from the user's trust configuration and the fetched default
+/// lists, falling back to default settings if the configuration cannot be applied.
+/// Trust verification stays enabled so claimSignature always shows trusted or untrusted.
+pub(crate) fn gui_extraction_settings(
+    config: &TrustConfig,
+    default_lists: Option<&TrustLists>,
+) -> Settings {
+    config.settings(default_lists).unwrap_or_else(|e| {
+        eprintln!("Trust lists: failed to build settings: {:#}", e);
+        default_extraction_settings()
+    })
 }
 
 /// Helper to get selected text from the context (for Edit → Copy).
@@ -0,0 +1,202 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Node.js bindings (napi-rs) for the crtool extraction and validation API. Results are plain
+//! JS objects; crJSON documents are returned as parsed objects, not strings.
+//!
+//! Extraction and verification read the asset and check signatures, so they run on the libuv
+//! thread pool and return Promises. Schema validation is synchronous.
+
+use crtool::SchemaValidator;
+use napi::bindgen_prelude::AsyncTask;
+use napi::{Env, Error, Result, Task};
+use napi_derive::napi;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+fn to_napi(e: crtool::CrtoolError) -> Error {
+    Error::from_reason(format!("{:#}", e))
+}
+
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct ExtractOptions {
+    /// Path to a trust configuration JSON file (`anchorFiles`, `allowedFiles`), as saved by the
+    /// crTool GUI. Without it, signing credentials report as untrusted.
+    pub trust_config: Option<String>,
+    /// Verify claim signatures and certificate chains but skip recomputing content hashes.
+    pub fast: Option<bool>,
+}
+
+impl ExtractOptions {
+    fn extract(&self, path: &Path) -> crtool::Result<crtool::ManifestExtractionResult> {
+        let settings = match &self.trust_config {
+            Some(config) => crtool::trust::TrustConfig::load(Path::new(config))?.settings(None)?,
+            None => crtool::default_extraction_settings(),
+        };
+        if self.fast.unwrap_or(false) {
+            crtool::extract_crjson_manifest_fast(path, &settings)
+        } else {
+            crtool::extract_crjson_manifest_with_settings(path, &settings)
+        }
+    }
+}
+
+#[napi(object)]
+pub struct ExtractResult {
+    pub active_label: String,
+    /// The crJSON document.
+    pub crjson: serde_json::Value,
+}
+
+#[napi(object)]
+pub struct VerifyResult {
+    pub active_label: String,
+    /// `trusted`, `untrusted`, or `unknown`.
+    pub trust: String,
+    /// No validation failures other than an untrusted signer.
+    pub valid: bool,
+    pub failure_codes: Vec<String>,
+    pub manifest_count: u32,
+}
+
+#[napi(object)]
+pub struct SchemaError {
+    pub instance_path: String,
+    pub message: String,
+    /// `"error"`, `"warning"` or `"info"`; only errors make the document invalid.
+    pub severity: String,
+    /// Failed schema keyword (e.g. `"required"`) or `"recommended-field"`.
+    pub rule: String,
+}
+
+#[napi(object)]
+pub struct SchemaValidation {
+    pub valid: bool,
+    pub errors: Vec<SchemaError>,
+}
+
+pub struct ExtractTask {
+    path: PathBuf,
+    options: ExtractOptions,
+}
+
+impl Task for ExtractTask {
+    type Output = ExtractResult;
+    type JsValue = ExtractResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let result = self.options.extract(&self.path).map_err(to_napi)?;
+        Ok(ExtractResult {
+            active_label: result.active_label,
+            crjson: result.manifest_value,
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+pub struct VerifyTask {
+    path: PathBuf,
+    options: ExtractOptions,
+}
+
+impl Task for VerifyTask {
+    type Output = VerifyResult;
+    type JsValue = VerifyResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let result = self.options.extract(&self.path).map_err(to_napi)?;
+        let facts = crtool::filter::FileFacts::from_extraction(&result);
+        Ok(VerifyResult {
+            failure_codes: crtool::filter::failure_codes(
+                &result.manifest_value,
+                &result.active_label,
+            ),
+            active_label: facts.active_label,
+            trust: facts.trust,
+            valid: facts.valid,
+            manifest_count: facts.manifest_count as u32,
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Extract the C2PA manifest store of the asset at `path` as crJSON.
+#[napi(ts_return_type = "Promise<ExtractResult>")]
+pub fn extract_manifest(path: String, options: Option<ExtractOptions>) -> AsyncTask<ExtractTask> {
+    AsyncTask::new(ExtractTask {
+        path: PathBuf::from(path),
+        options: options.unwrap_or_default(),
+    })
+}
+
+/// Check the active manifest of the asset at `path`: signer trust and validation status.
+#[napi(ts_return_type = "Promise<VerifyResult>")]
+pub fn verify_asset(path: String, options: Option<ExtractOptions>) -> AsyncTask<VerifyTask> {
+    AsyncTask::new(VerifyTask {
+        path: PathBuf::from(path),
+        options: options.unwrap_or_default(),
+    })
+}
+
+/// Validate a crJSON document (object) against the bundled crJSON schema, or `schemaPath`.
+#[napi]
+pub fn validate_crjson(
+    crjson: serde_json::Value,
+    schema_path: Option<String>,
+) -> Result<SchemaValidation> {
+    // The bundled schema is compiled on first use and shared by later calls.
+    static BUNDLED: OnceLock<SchemaValidator> = OnceLock::new();
+    let result = match schema_path {
+        Some(path) => SchemaValidator::from_path(path)
+            .map_err(to_napi)?
+            .validate(&crjson),
+        None => {
+            let bundled = match BUNDLED.get() {
+                Some(validator) => validator,
+                None => {
+                    let validator = SchemaValidator::from_bundled().map_err(to_napi)?;
+                    BUNDLED.get_or_init(|| validator)
+                }
+            };
+            bundled.validate(&crjson)
+        }
+    };
+    Ok(SchemaValidation {
+        valid: result.is_valid,
+        errors: result
+            .errors
+            .into_iter()
+            .map(|e| SchemaError {
+                instance_path: e.instance_path,
+                message: e.message,
+                severity: e.severity.to_string(),
+                rule: e.rule,
+            })
+            .collect(),
+    })
+}
+
+/// File extensions of the asset formats crTool can read.
+#[napi]
+pub fn supported_extensions() -> Vec<String> {
+    crtool::supported_asset_extensions()
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,132 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! WebAssembly bindings (wasm-bindgen) for the crtool extraction and validation API, for
+//! browser-based JPEG Trust inspectors. There is no file system: assets are passed as bytes and
+//! trust anchors as PEM text. Results are plain JS objects.
+
+use crtool::SchemaValidator;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use wasm_bindgen::prelude::*;
+
+fn to_js(e: crtool::CrtoolError) -> JsError {
+    JsError::new(&format!("{:#}", e))
+}
+
+/// Convert a result to a JS object (maps as objects, not `Map`s).
+fn to_object(value: &impl Serialize) -> Result<JsValue, JsError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct ExtractOptions {
+    /// PEM bundle of trust anchors. Without it, signing credentials report as untrusted.
+    trust_anchors: Option<String>,
+    /// PEM bundle or SHA-256 hash list of signing certificates trusted without an anchor.
+    allowed_list: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExtractResult {
+    active_label: String,
+    /// SHA-256 of the asset bytes.
+    asset_sha256: Option<String>,
+    crjson: serde_json::Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaError {
+    instance_path: String,
+    message: String,
+    severity: crtool::Severity,
+    rule: String,
+}
+
+#[derive(Serialize)]
+struct SchemaValidation {
+    valid: bool,
+    errors: Vec<SchemaError>,
+}
+
+/// Extract the C2PA manifest store of an asset as crJSON. `format` is a file extension or MIME
+/// type (`"jpg"`, `"image/jpeg"`); `options` may set `trustAnchors` and `allowedList`.
+/// Returns `{ activeLabel, assetSha256, crjson }`.
+#[wasm_bindgen]
+pub fn extract(asset: &[u8], format: &str, options: JsValue) -> Result<JsValue, JsError> {
+    let options: ExtractOptions = if options.is_undefined() || options.is_null() {
+        ExtractOptions::default()
+    } else {
+        serde_wasm_bindgen::from_value(options).map_err(|e| JsError::new(&e.to_string()))?
+    };
+    let settings = match &options.trust_anchors {
+        Some(anchors) => {
+            crtool::build_trust_settings(anchors, options.allowed_list.as_deref(), None)
+                .map_err(to_js)?
+        }
+        None => crtool::default_extraction_settings(),
+    };
+    let result =
+        crtool::extract_crjson_manifest_from_bytes(asset, format, &settings).map_err(to_js)?;
+    to_object(&ExtractResult {
+        active_label: result.active_label,
+        asset_sha256: result.asset_hash,
+        crjson: result.manifest_value,
+    })
+}
+
+/// Validate a crJSON document (an object or JSON text) against the crJSON schema bundled in the
+/// module. Returns `{ valid, errors: [{ instancePath, message }] }`.
+#[wasm_bindgen]
+pub fn validate(crjson: JsValue) -> Result<JsValue, JsError> {
+    let value = match crjson.as_string() {
+        Some(text) => crtool::limits::parse_json(&text).map_err(to_js)?,
+        None => serde_wasm_bindgen::from_value(crjson).map_err(|e| JsError::new(&e.to_string()))?,
+    };
+    // The schema is compiled on first use and shared by later calls.
+    static BUNDLED: OnceLock<SchemaValidator> = OnceLock::new();
+    let validator = match BUNDLED.get() {
+        Some(validator) => validator,
+        None => {
+            let validator = SchemaValidator::from_bundled().map_err(to_js)?;
+            BUNDLED.get_or_init(|| validator)
+        }
+    };
+    let result = validator.validate(&value);
+    to_object(&SchemaValidation {
+        valid: result.is_valid,
+        errors: result
+            .errors
+            .into_iter()
+            .map(|e| SchemaError {
+                instance_path: e.instance_path,
+                message: e.message,
+                severity: e.severity,
+                rule: e.rule,
+            })
+            .collect(),
+    })
+}
+
+/// File extensions of the asset formats crTool can read.
+#[wasm_bindgen(js_name = supportedExtensions)]
+pub fn supported_extensions() -> Vec<String> {
+    crtool::supported_asset_extensions()
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
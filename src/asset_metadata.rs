@@ -0,0 +1,434 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! A `c2pa.metadata` assertion generated from the EXIF, XMP and IPTC metadata already in an
+//! asset (requires the `signing` feature), so camera and rights metadata need not be copied
+//! into the manifest JSON by hand.
+//!
+//! Properties use their XMP names (`tiff:Make`, `exif:FNumber`, `dc:creator`, ...). XMP wins
+//! over IPTC-IIM when both carry a property. GPS coordinates are never copied: add them to the
+//! manifest explicitly when an asset's location may be disclosed.
+
+use crate::error::{Context, Result};
+use exif::{In, Tag, Value};
+use serde_json::{json, Map, Value as JsonValue};
+use std::fs;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// Label of the metadata assertion.
+pub const METADATA_LABEL: &str = "c2pa.metadata";
+
+/// Largest prefix of an asset scanned for XMP and IPTC (metadata sits near the start).
+const SCAN_LIMIT: u64 = 64 * 1024 * 1024;
+
+/// JSON-LD context of the generated assertion.
+fn context() -> JsonValue {
+    json!({
+        "dc": "http://purl.org/dc/elements/1.1/",
+        "exif": "http://ns.adobe.com/exif/1.0/",
+        "exifEX": "http://cipa.jp/exif/1.0/",
+        "photoshop": "http://ns.adobe.com/photoshop/1.0/",
+        "tiff": "http://ns.adobe.com/tiff/1.0/",
+        "xmp": "http://ns.adobe.com/xap/1.0/",
+    })
+}
+
+/// How a property value is represented.
+#[derive(Clone, Copy)]
+enum Shape {
+    Text,
+    /// Language alternative: `{"x-default": value}`.
+    LangAlt,
+    /// Ordered or unordered array.
+    List,
+}
+
+/// XMP properties copied from the XMP packet and, when absent there, from IPTC-IIM
+/// `(record 2 dataset)`.
+const XMP_PROPERTIES: &[(&str, Shape, Option<u8>)] = &[
+    ("dc:title", Shape::LangAlt, Some(5)),
+    ("dc:creator", Shape::List, Some(80)),
+    ("dc:description", Shape::LangAlt, Some(120)),
+    ("dc:rights", Shape::LangAlt, Some(116)),
+    ("dc:subject", Shape::List, Some(25)),
+    ("photoshop:Headline", Shape::Text, Some(105)),
+    ("photoshop:Credit", Shape::Text, Some(110)),
+    ("photoshop:City", Shape::Text, Some(90)),
+    ("photoshop:Country", Shape::Text, Some(101)),
+    ("photoshop:DateCreated", Shape::Text, Some(55)),
+    ("xmp:CreatorTool", Shape::Text, None),
+];
+
+/// EXIF tags copied, with their XMP names.
+const EXIF_TAGS: &[(Tag, &str)] = &[
+    (Tag::Make, "tiff:Make"),
+    (Tag::Model, "tiff:Model"),
+    (Tag::Orientation, "tiff:Orientation"),
+    (Tag::LensMake, "exifEX:LensMake"),
+    (Tag::LensModel, "exifEX:LensModel"),
+    (
+        Tag::PhotographicSensitivity,
+        "exifEX:PhotographicSensitivity",
+    ),
+    (Tag::DateTimeOriginal, "exif:DateTimeOriginal"),
+    (Tag::ExposureTime, "exif:ExposureTime"),
+    (Tag::FNumber, "exif:FNumber"),
+    (Tag::FocalLength, "exif:FocalLength"),
+    (Tag::ExposureBiasValue, "exif:ExposureBiasValue"),
+    (Tag::ColorSpace, "exif:ColorSpace"),
+    (Tag::DigitalZoomRatio, "exif:DigitalZoomRatio"),
+    (Tag::PixelXDimension, "exif:PixelXDimension"),
+    (Tag::PixelYDimension, "exif:PixelYDimension"),
+];
+
+/// The metadata properties of the asset at `path`, by XMP name; empty when it has none.
+pub fn read_asset_metadata(path: &Path) -> Result<Map<String, JsonValue>> {
+    let mut properties = Map::new();
+    let file = fs::File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+
+    // Formats the EXIF reader does not understand simply contribute no EXIF properties.
+    if let Ok(exif) = exif::Reader::new().read_from_container(&mut BufReader::new(&file)) {
+        for (tag, name) in EXIF_TAGS {
+            if let Some(value) = exif.get_field(*tag, In::PRIMARY).and_then(exif_value) {
+                properties.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    let mut bytes = Vec::new();
+    fs::File::open(path)
+        .and_then(|f| f.take(SCAN_LIMIT).read_to_end(&mut bytes))
+        .with_context(|| format!("Failed to read {:?}", path))?;
+    let xmp = xmp_packet(&bytes);
+    let iptc = iptc_datasets(&bytes);
+    for (name, shape, dataset) in XMP_PROPERTIES {
+        let mut values = xmp.map(|xmp| xmp_values(xmp, name)).unwrap_or_default();
+        if values.is_empty() {
+            values = dataset
+                .map(|dataset| {
+                    iptc.iter()
+                        .filter(|(d, _)| *d == dataset)
+                        .map(|(_, v)| iptc_value(dataset, v))
+                        .collect()
+                })
+                .unwrap_or_default();
+        }
+        let Some(first) = values.first().cloned() else {
+            continue;
+        };
+        let value = match shape {
+            Shape::Text => json!(first),
+            Shape::LangAlt => json!({ "x-default": first }),
+            Shape::List => json!(values),
+        };
+        properties.insert(name.to_string(), value);
+    }
+    Ok(properties)
+}
+
+/// The `c2pa.metadata` assertion (`{"label", "data"}`) for the asset at `path`, or `None` when
+/// it has no metadata crTool copies.
+pub fn metadata_assertion(path: &Path) -> Result<Option<JsonValue>> {
+    let mut data = read_asset_metadata(path)?;
+    if data.is_empty() {
+        return Ok(None);
+    }
+    data.insert("@context".to_string(), context());
+    Ok(Some(json!({ "label": METADATA_LABEL, "data": data })))
+}
+
+/// Add the metadata of the asset at `path` to `manifest`. Properties already in a
+/// `c2pa.metadata` assertion of the manifest are kept. Returns the number of properties added.
+pub fn add_metadata_from_asset(manifest: &mut JsonValue, path: &Path) -> Result<usize> {
+    let properties = read_asset_metadata(path)?;
+    if properties.is_empty() {
+        return Ok(0);
+    }
+    let assertions = manifest
+        .as_object_mut()
+        .context("Manifest JSON must be an object")?
+        .entry("assertions")
+        .or_insert_with(|| json!([]))
+        .as_array_mut()
+        .context("Manifest 'assertions' must be an array")?;
+    let index = match assertions
+        .iter()
+        .position(|a| a.get("label").and_then(|l| l.as_str()) == Some(METADATA_LABEL))
+    {
+        Some(index) => index,
+        None => {
+            assertions.push(json!({ "label": METADATA_LABEL, "data": { "@context": context() } }));
+            assertions.len() - 1
+        }
+    };
+    let data = assertions[index]
+        .get_mut("data")
+        .and_then(|d| d.as_object_mut())
+        .context("c2pa.metadata assertion 'data' must be an object")?;
+    let mut added = 0;
+    for (name, value) in properties {
+        if !data.contains_key(&name) {
+            data.insert(name, value);
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+/// JSON value of an EXIF field: text, an integer, a number, or a rational written `n/d` for
+/// exposure times.
+fn exif_value(field: &exif::Field) -> Option<JsonValue> {
+    match &field.value {
+        Value::Ascii(parts) => {
+            let text = String::from_utf8_lossy(parts.first()?).trim().to_string();
+            if text.is_empty() {
+                return None;
+            }
+            // EXIF dates are `YYYY:MM:DD HH:MM:SS`; XMP dates are ISO 8601.
+            if field.tag == Tag::DateTimeOriginal && text.len() == 19 {
+                return Some(json!(format!(
+                    "{}-{}-{}T{}",
+                    &text[0..4],
+                    &text[5..7],
+                    &text[8..10],
+                    &text[11..]
+                )));
+            }
+            Some(json!(text))
+        }
+        Value::Rational(values) => {
+            let value = values.first()?;
+            if field.tag == Tag::ExposureTime {
+                return Some(json!(format!("{}/{}", value.num, value.denom)));
+            }
+            (value.denom != 0).then(|| json!(value.to_f64()))
+        }
+        Value::SRational(values) => {
+            let value = values.first()?;
+            (value.denom != 0).then(|| json!(value.to_f64()))
+        }
+        _ => field.value.get_uint(0).map(|v| json!(v)),
+    }
+}
+
+/// The XMP packet embedded in `bytes`, if any.
+fn xmp_packet(bytes: &[u8]) -> Option<&str> {
+    let start = find(bytes, b"<x:xmpmeta")?;
+    let end = start + find(&bytes[start..], b"</x:xmpmeta>")?;
+    std::str::from_utf8(&bytes[start..end]).ok()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Values of an XMP property, in attribute (`dc:title="..."`) or element form, where an
+/// element holding an `rdf:Alt`, `rdf:Seq` or `rdf:Bag` gives one value per `rdf:li`.
+fn xmp_values(xmp: &str, name: &str) -> Vec<String> {
+    let attribute = format!(" {}=\"", name);
+    if let Some(start) = xmp.find(&attribute).map(|i| i + attribute.len()) {
+        if let Some(len) = xmp[start..].find('"') {
+            return vec![unescape(&xmp[start..start + len])];
+        }
+    }
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let Some(start) = xmp.find(&open).map(|i| i + open.len()) else {
+        return Vec::new();
+    };
+    let Some(len) = xmp[start..].find(&close) else {
+        return Vec::new();
+    };
+    let content = &xmp[start..start + len];
+    if !content.contains("<rdf:li") {
+        let text = unescape(content.trim());
+        return if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![text]
+        };
+    }
+    content
+        .split("<rdf:li")
+        .skip(1)
+        .filter_map(|item| {
+            let text = &item[item.find('>')? + 1..];
+            Some(unescape(text[..text.find("</rdf:li>")?].trim()))
+        })
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// IPTC-IIM application record (record 2) datasets of a JPEG, from its Photoshop APP13
+/// segment: `(dataset, bytes)` in file order.
+fn iptc_datasets(bytes: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    let mut datasets = Vec::new();
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return datasets;
+    }
+    let mut pos = 2;
+    while pos + 4 <= bytes.len() && bytes[pos] == 0xFF {
+        let marker = bytes[pos + 1];
+        let len = u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]) as usize;
+        // Start of scan: no metadata segments follow.
+        if marker == 0xDA || len < 2 || pos + 2 + len > bytes.len() {
+            break;
+        }
+        let segment = &bytes[pos + 4..pos + 2 + len];
+        if marker == 0xED {
+            if let Some(resources) = segment.strip_prefix(b"Photoshop 3.0\0") {
+                if let Some(iim) = photoshop_resource(resources, 0x0404) {
+                    datasets.extend(iim_datasets(iim));
+                }
+            }
+        }
+        pos += 2 + len;
+    }
+    datasets
+}
+
+/// Data of the Photoshop image resource `id` in a sequence of `8BIM` resource blocks.
+fn photoshop_resource(mut blocks: &[u8], id: u16) -> Option<&[u8]> {
+    while blocks.len() >= 12 && blocks.starts_with(b"8BIM") {
+        let block_id = u16::from_be_bytes([blocks[4], blocks[5]]);
+        // Pascal string name, padded to an even length.
+        let name_len = blocks[6] as usize;
+        let name_end = 6 + (name_len + 2) / 2 * 2;
+        let size_bytes = blocks.get(name_end..name_end + 4)?;
+        let size = u32::from_be_bytes(size_bytes.try_into().ok()?) as usize;
+        let data = blocks.get(name_end + 4..name_end + 4 + size)?;
+        if block_id == id {
+            return Some(data);
+        }
+        blocks = blocks.get(name_end + 4 + (size + 1) / 2 * 2..)?;
+    }
+    None
+}
+
+/// Record 2 datasets of IPTC-IIM data.
+fn iim_datasets(mut iim: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    let mut datasets = Vec::new();
+    while iim.len() >= 5 && iim[0] == 0x1C {
+        let (record, dataset) = (iim[1], iim[2]);
+        let len = u16::from_be_bytes([iim[3], iim[4]]) as usize;
+        // Extended (over 32 KiB) datasets are not text properties.
+        if len & 0x8000 != 0 || iim.len() < 5 + len {
+            break;
+        }
+        if record == 2 {
+            datasets.push((dataset, iim[5..5 + len].to_vec()));
+        }
+        iim = &iim[5 + len..];
+    }
+    datasets
+}
+
+/// Text of an IIM dataset (UTF-8, else Latin-1); dates (`YYYYMMDD`) become ISO 8601.
+fn iptc_value(dataset: u8, bytes: &[u8]) -> String {
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text.trim().to_string(),
+        Err(_) => bytes
+            .iter()
+            .map(|&b| b as char)
+            .collect::<String>()
+            .trim()
+            .to_string(),
+    };
+    if dataset == 55 && text.len() == 8 && text.bytes().all(|b| b.is_ascii_digit()) {
+        return format!("{}-{}-{}", &text[0..4], &text[4..6], &text[6..8]);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A JPEG with an XMP packet and a Photoshop APP13 segment, but no EXIF.
+    fn jpeg_with_metadata() -> Vec<u8> {
+        let xmp = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF><rdf:Description
+             xmp:CreatorTool="Darkroom 2.1">
+            <dc:title><rdf:Alt><rdf:li xml:lang="x-default">Harbor &amp; boats</rdf:li></rdf:Alt></dc:title>
+            <dc:subject><rdf:Bag><rdf:li>harbor</rdf:li><rdf:li>boats</rdf:li></rdf:Bag></dc:subject>
+            </rdf:Description></rdf:RDF></x:xmpmeta>"#;
+        let mut app1 = b"http://ns.adobe.com/xap/1.0/\0".to_vec();
+        app1.extend(xmp.as_bytes());
+
+        let mut iim = Vec::new();
+        for (dataset, value) in [(5u8, "IIM title"), (80, "Ada Lovelace"), (55, "20240107")] {
+            iim.extend([0x1C, 2, dataset]);
+            iim.extend((value.len() as u16).to_be_bytes());
+            iim.extend(value.as_bytes());
+        }
+        let mut app13 = b"Photoshop 3.0\08BIM\x04\x04\0\0".to_vec();
+        app13.extend((iim.len() as u32).to_be_bytes());
+        app13.extend(&iim);
+
+        let mut jpeg = vec![0xFF, 0xD8];
+        for (marker, payload) in [(0xE1u8, app1), (0xED, app13)] {
+            jpeg.extend([0xFF, marker]);
+            jpeg.extend(((payload.len() + 2) as u16).to_be_bytes());
+            jpeg.extend(payload);
+        }
+        jpeg.extend([0xFF, 0xDA, 0x00, 0x02, 0xFF, 0xD9]);
+        jpeg
+    }
+
+    #[test]
+    fn test_read_asset_metadata() {
+        let path =
+            std::env::temp_dir().join(format!("crtool-asset-metadata-{}.jpg", std::process::id()));
+        fs::write(&path, jpeg_with_metadata()).unwrap();
+        let properties = read_asset_metadata(&path).unwrap();
+        // XMP wins over IIM; IIM fills in what XMP lacks.
+        assert_eq!(
+            properties["dc:title"],
+            json!({ "x-default": "Harbor & boats" })
+        );
+        assert_eq!(properties["dc:subject"], json!(["harbor", "boats"]));
+        assert_eq!(properties["xmp:CreatorTool"], "Darkroom 2.1");
+        assert_eq!(properties["dc:creator"], json!(["Ada Lovelace"]));
+        assert_eq!(properties["photoshop:DateCreated"], "2024-01-07");
+
+        let mut manifest = json!({
+            "assertions": [{ "label": "c2pa.metadata", "data": { "dc:title": { "x-default": "Mine" } } }]
+        });
+        assert_eq!(add_metadata_from_asset(&mut manifest, &path).unwrap(), 4);
+        let data = &manifest["assertions"][0]["data"];
+        assert_eq!(data["dc:title"]["x-default"], "Mine");
+        assert_eq!(data["dc:creator"], json!(["Ada Lovelace"]));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_metadata_assertion_from_exif() {
+        let photo = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/assets/PXL_20260208_202351558.jpg");
+        let assertion = metadata_assertion(&photo).unwrap().unwrap();
+        assert_eq!(assertion["label"], METADATA_LABEL);
+        assert_eq!(assertion["data"]["tiff:Make"], "Google");
+        assert_eq!(assertion["data"]["tiff:Model"], "Pixel 10");
+        assert!(assertion["data"]["@context"]["tiff"].is_string());
+
+        let dog = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/assets/Dog.jpg");
+        assert!(metadata_assertion(&dog).unwrap().is_none());
+    }
+}
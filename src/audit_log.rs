@@ -0,0 +1,422 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Append-only audit log of signing operations, stored as hash-chained JSONL.
+//!
+//! Each line is one [`AuditEntry`]. Its `hash` is the SHA-256 of the entry's compact JSON
+//! (without the `hash` and `signature` fields), and its `prevHash` is the `hash` of the line
+//! before it (64 zeros for the first line). Editing, removing, or reordering any line breaks the
+//! chain from that point on, which [`verify_log`] reports.
+//!
+//! A hash chain alone can be recomputed by anyone who can write the file, so each entry's `hash`
+//! is also signed with an Ed25519 key held by the signing service ([`AuditLog`]), and
+//! [`verify_log`] checks every signature against the matching public key.
+
+use crate::error::{format_err, Context, Result};
+use crate::hashing::{hash_bytes, HashAlgorithm};
+use base64::Engine;
+use ed25519_dalek::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// `prevHash` of the first entry in a log.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// What was signed, by whom, and with which key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningEvent {
+    /// RFC 3339 UTC time the output was written.
+    pub timestamp: String,
+    /// Account that ran the tool (`USER` / `USERNAME`), when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+    /// crTool version that performed the signing.
+    pub tool_version: String,
+    pub input: String,
+    pub input_sha256: String,
+    pub output: String,
+    pub output_sha256: String,
+    /// Signing algorithm, e.g. `es256`.
+    pub algorithm: String,
+    /// SHA-256 of the DER signing certificate.
+    pub cert_fingerprint: String,
+}
+
+impl SigningEvent {
+    /// Operator name from the environment (`USER`, then `USERNAME`).
+    pub fn operator_from_env() -> Option<String> {
+        std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok()
+            .filter(|u| !u.trim().is_empty())
+    }
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// Position in the log, starting at 1.
+    pub seq: u64,
+    pub prev_hash: String,
+    #[serde(flatten)]
+    pub event: SigningEvent,
+    pub hash: String,
+    /// Base64 Ed25519 signature over `hash` (its hex characters), by the log's key.
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// SHA-256 over the compact JSON of `entry` without its `hash` and `signature` fields.
+fn entry_hash(entry: &serde_json::Value) -> Result<String> {
+    let mut unhashed = entry.clone();
+    if let Some(obj) = unhashed.as_object_mut() {
+        obj.remove("hash");
+        obj.remove("signature");
+    }
+    // Re-serializing a parsed line reproduces the bytes written by `append_event`.
+    let bytes = serde_json::to_vec(&unhashed).context("Failed to serialize audit entry")?;
+    Ok(hash_bytes(&bytes, HashAlgorithm::Sha256))
+}
+
+/// Ed25519 private key that signs audit log entries, from a PKCS#8 PEM file
+/// (`openssl genpkey -algorithm ed25519`).
+pub fn load_signing_key(path: &Path) -> Result<SigningKey> {
+    let pem = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read audit log key {:?}", path))?;
+    SigningKey::from_pkcs8_pem(&pem)
+        .map_err(|e| format_err!("{:?} is not an Ed25519 PKCS#8 PEM private key: {e}", path))
+}
+
+/// Ed25519 public key that audit log signatures are checked against, from a PEM file
+/// (`openssl pkey -in key.pem -pubout`).
+pub fn load_verifying_key(path: &Path) -> Result<VerifyingKey> {
+    let pem = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read audit log public key {:?}", path))?;
+    VerifyingKey::from_public_key_pem(&pem)
+        .map_err(|e| format_err!("{:?} is not an Ed25519 PEM public key: {e}", path))
+}
+
+/// The last non-empty line of the log open in `file`.
+fn last_entry(file: &fs::File, path: &Path) -> Result<Option<AuditEntry>> {
+    let mut last = None;
+    for line in BufReader::new(file).lines() {
+        let line = line.with_context(|| format!("Failed to read audit log {:?}", path))?;
+        if !line.trim().is_empty() {
+            last = Some(line);
+        }
+    }
+    last.map(|line| {
+        serde_json::from_str(&line)
+            .with_context(|| format!("Last line of audit log {:?} is not an audit entry", path))
+    })
+    .transpose()
+}
+
+/// An audit log and the key its entries are signed with.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+    key: SigningKey,
+}
+
+impl AuditLog {
+    /// The log at `path` (created on the first append), signed with the PEM key at `key_path`.
+    pub fn open(path: &Path, key_path: &Path) -> Result<Self> {
+        Ok(Self::with_key(path, load_signing_key(key_path)?))
+    }
+
+    pub fn with_key(path: &Path, key: SigningKey) -> Self {
+        AuditLog {
+            path: path.to_path_buf(),
+            key,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Public key that verifies this log's signatures.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.key.verifying_key()
+    }
+
+    /// Append `event`, chained to the current last entry and signed. The log file is locked
+    /// exclusively from reading the last entry until the new one is written and synced, so
+    /// concurrent signings, in this process (`sign --jobs`) or in others sharing the log, never
+    /// chain two entries to the same predecessor.
+    #[cfg(feature = "fs")]
+    pub fn append(&self, event: SigningEvent) -> Result<AuditEntry> {
+        use fs2::FileExt;
+        use std::io::{Seek, Write};
+
+        let path = &self.path;
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)
+            .with_context(|| format!("Failed to open audit log {:?} for appending", path))?;
+        file.lock_exclusive()
+            .with_context(|| format!("Failed to lock audit log {:?}", path))?;
+        file.rewind()
+            .with_context(|| format!("Failed to read audit log {:?}", path))?;
+
+        let (seq, prev_hash) = match last_entry(&file, path)? {
+            Some(last) => (last.seq + 1, last.hash),
+            None => (1, GENESIS_HASH.to_string()),
+        };
+        let mut entry = AuditEntry {
+            seq,
+            prev_hash,
+            event,
+            hash: String::new(),
+            signature: String::new(),
+        };
+        entry.hash = entry_hash(&serde_json::to_value(&entry)?)?;
+        entry.signature = base64::engine::general_purpose::STANDARD
+            .encode(self.key.sign(entry.hash.as_bytes()).to_bytes());
+
+        let mut line = serde_json::to_string(&entry).context("Failed to serialize audit entry")?;
+        line.push('\n');
+        file.write_all(line.as_bytes())
+            .and_then(|_| file.sync_data())
+            .with_context(|| format!("Failed to append to audit log {:?}", path))?;
+        // Dropping the file releases the lock
+        Ok(entry)
+    }
+}
+
+/// Result of checking an audit log's hash chain.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditVerification {
+    /// Entries whose hash and chain link checked out before the first break.
+    pub verified_entries: u64,
+    /// 1-based line number of the first broken entry, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken_at_line: Option<usize>,
+    /// Why that line breaks the chain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// `hash` of the last verified entry (the log head), for anchoring elsewhere.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+}
+
+impl AuditVerification {
+    pub fn is_intact(&self) -> bool {
+        self.broken_at_line.is_none()
+    }
+}
+
+/// Check one log line against the expected sequence number, previous hash and the log's key.
+/// Returns the entry's hash, or why the line breaks the chain.
+fn check_line(
+    line: &str,
+    expected_seq: u64,
+    prev_hash: &str,
+    key: &VerifyingKey,
+) -> std::result::Result<String, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(line).map_err(|e| format!("not valid JSON: {e}"))?;
+    let entry: AuditEntry =
+        serde_json::from_value(value.clone()).map_err(|e| format!("not an audit entry: {e}"))?;
+    if entry.seq != expected_seq {
+        return Err(format!(
+            "sequence {} where {expected_seq} was expected",
+            entry.seq
+        ));
+    }
+    if entry.prev_hash != prev_hash {
+        return Err("prevHash does not match the previous entry".to_string());
+    }
+    if entry_hash(&value).map_err(|e| e.to_string())? != entry.hash {
+        return Err("hash does not match the entry contents".to_string());
+    }
+    if entry.signature.is_empty() {
+        return Err("entry is not signed".to_string());
+    }
+    let signature = base64::engine::general_purpose::STANDARD
+        .decode(&entry.signature)
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or("signature is not a base64 Ed25519 signature")?;
+    key.verify(entry.hash.as_bytes(), &signature)
+        .map_err(|_| "signature does not verify with the log's public key".to_string())?;
+    Ok(entry.hash)
+}
+
+/// Walk the log and check every entry's hash, signature, sequence number, and link to its
+/// predecessor. Stops at the first broken entry; everything after it is untrustworthy.
+pub fn verify_log(path: &Path, key: &VerifyingKey) -> Result<AuditVerification> {
+    let file =
+        fs::File::open(path).with_context(|| format!("Failed to open audit log {:?}", path))?;
+    let mut result = AuditVerification::default();
+    let mut prev_hash = GENESIS_HASH.to_string();
+
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read audit log {:?}", path))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match check_line(&line, result.verified_entries + 1, &prev_hash, key) {
+            Ok(hash) => {
+                prev_hash = hash;
+                result.verified_entries += 1;
+            }
+            Err(reason) => {
+                result.broken_at_line = Some(i + 1);
+                result.reason = Some(reason);
+                break;
+            }
+        }
+    }
+
+    if result.verified_entries > 0 {
+        result.head = Some(prev_hash);
+    }
+    Ok(result)
+}
+
+#[cfg(all(test, feature = "fs"))]
+mod tests {
+    use super::*;
+
+    fn event(output: &str) -> SigningEvent {
+        SigningEvent {
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            operator: Some("ada".to_string()),
+            tool_version: "0.0.0".to_string(),
+            input: "in.jpg".to_string(),
+            input_sha256: "aa".to_string(),
+            output: output.to_string(),
+            output_sha256: "bb".to_string(),
+            algorithm: "es256".to_string(),
+            cert_fingerprint: "cc".to_string(),
+        }
+    }
+
+    fn log(name: &str) -> AuditLog {
+        let path = std::env::temp_dir().join(name);
+        let _ = fs::remove_file(&path);
+        AuditLog::with_key(&path, SigningKey::from_bytes(&[3u8; 32]))
+    }
+
+    #[test]
+    fn test_append_and_verify_chain() {
+        let log = log("crtool_audit_log_chain.jsonl");
+        let path = log.path();
+        let key = log.verifying_key();
+
+        let first = log.append(event("a.jpg")).unwrap();
+        let second = log.append(event("b.jpg")).unwrap();
+        assert_eq!(first.seq, 1);
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.seq, 2);
+        assert_eq!(second.prev_hash, first.hash);
+
+        let result = verify_log(path, &key).unwrap();
+        assert!(result.is_intact());
+        assert_eq!(result.verified_entries, 2);
+        assert_eq!(result.head.as_deref(), Some(second.hash.as_str()));
+
+        // Rewriting the first entry's output breaks it, even though the JSON is still valid
+        let text = fs::read_to_string(path).unwrap();
+        fs::write(path, text.replacen("a.jpg", "z.jpg", 1)).unwrap();
+        let result = verify_log(path, &key).unwrap();
+        let _ = fs::remove_file(path);
+        assert_eq!(result.broken_at_line, Some(1));
+        assert_eq!(result.verified_entries, 0);
+    }
+
+    #[test]
+    fn test_removed_entry_breaks_chain() {
+        let log = log("crtool_audit_log_removed.jsonl");
+        let path = log.path();
+        for name in ["a.jpg", "b.jpg", "c.jpg"] {
+            log.append(event(name)).unwrap();
+        }
+        let text = fs::read_to_string(path).unwrap();
+        let kept: Vec<&str> = text
+            .lines()
+            .enumerate()
+            .filter(|(i, _)| *i != 1)
+            .map(|(_, l)| l)
+            .collect();
+        fs::write(path, kept.join("\n")).unwrap();
+
+        let result = verify_log(path, &log.verifying_key()).unwrap();
+        let _ = fs::remove_file(path);
+        assert_eq!(result.verified_entries, 1);
+        assert_eq!(result.broken_at_line, Some(2));
+    }
+
+    /// A chain rewritten and rehashed by someone without the key fails on its signatures.
+    #[test]
+    fn test_rehashed_chain_needs_the_key() {
+        let log = log("crtool_audit_log_forged.jsonl");
+        let path = log.path();
+        log.append(event("a.jpg")).unwrap();
+
+        let forger = AuditLog::with_key(path, SigningKey::from_bytes(&[9u8; 32]));
+        forger.append(event("forged.jpg")).unwrap();
+        let result = verify_log(path, &log.verifying_key()).unwrap();
+        assert_eq!(result.verified_entries, 1);
+        assert_eq!(result.broken_at_line, Some(2));
+        assert!(result.reason.unwrap().contains("signature"));
+
+        // An unsigned entry with a valid hash fails too
+        let mut entry: serde_json::Value =
+            serde_json::from_str(fs::read_to_string(path).unwrap().lines().next().unwrap())
+                .unwrap();
+        entry.as_object_mut().unwrap().remove("signature");
+        fs::write(path, serde_json::to_string(&entry).unwrap()).unwrap();
+        let result = verify_log(path, &log.verifying_key()).unwrap();
+        let _ = fs::remove_file(path);
+        assert_eq!(result.reason.as_deref(), Some("entry is not signed"));
+    }
+
+    /// Appends from several threads, each with its own file handle, form one unbroken chain.
+    #[test]
+    fn test_concurrent_appends_chain() {
+        let log = log("crtool_audit_log_concurrent.jsonl");
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let log = log.clone();
+                s.spawn(move || {
+                    for i in 0..5 {
+                        log.append(event(&format!("{t}-{i}.jpg"))).unwrap();
+                    }
+                });
+            }
+        });
+        let result = verify_log(log.path(), &log.verifying_key()).unwrap();
+        let _ = fs::remove_file(log.path());
+        assert!(result.is_intact(), "{:?}", result.reason);
+        assert_eq!(result.verified_entries, 20);
+    }
+
+    #[test]
+    fn test_load_pem_keys() {
+        let certs = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/certs");
+        let key = load_signing_key(&certs.join("audit_log.pem")).unwrap();
+        let public = load_verifying_key(&certs.join("audit_log_public.pem")).unwrap();
+        assert_eq!(key.verifying_key(), public);
+        assert!(load_signing_key(&certs.join("audit_log_public.pem")).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Small SVG badges summarizing an asset's Content Credentials (status, issuer, signing date),
+//! for showing next to images on review sites.
+
+use crate::filter::FileFacts;
+
+/// Overall credential status shown on a badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeStatus {
+    /// Valid, and the signer is on the trust list.
+    Trusted,
+    /// Valid, but the signer is not on the trust list.
+    Untrusted,
+    /// Valid; trust was not checked.
+    Unverified,
+    /// The manifest has validation failures.
+    Invalid,
+}
+
+impl BadgeStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            BadgeStatus::Trusted => "Trusted",
+            BadgeStatus::Untrusted => "Untrusted signer",
+            BadgeStatus::Unverified => "Trust not checked",
+            BadgeStatus::Invalid => "Invalid",
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            BadgeStatus::Trusted => "✓",
+            BadgeStatus::Untrusted | BadgeStatus::Unverified => "!",
+            BadgeStatus::Invalid => "✗",
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            BadgeStatus::Trusted => "#2e7d32",
+            BadgeStatus::Untrusted => "#ef6c00",
+            BadgeStatus::Unverified => "#616161",
+            BadgeStatus::Invalid => "#c62828",
+        }
+    }
+}
+
+/// What a badge shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    pub status: BadgeStatus,
+    pub issuer: Option<String>,
+    /// RFC 3339 time of the signature's timestamp; the badge shows its date.
+    pub signed_at: Option<String>,
+}
+
+/// Longest issuer name shown before it is cut short.
+const MAX_ISSUER_CHARS: usize = 40;
+
+impl Badge {
+    /// Summarize the verification facts of an extracted asset.
+    pub fn from_facts(facts: &FileFacts) -> Self {
+        let status = if !facts.valid {
+            BadgeStatus::Invalid
+        } else {
+            match facts.trust.as_str() {
+                "trusted" => BadgeStatus::Trusted,
+                "untrusted" => BadgeStatus::Untrusted,
+                _ => BadgeStatus::Unverified,
+            }
+        };
+        Self {
+            status,
+            issuer: facts.issuer.clone(),
+            signed_at: facts.signed_at.clone(),
+        }
+    }
+
+    /// Second line of the badge: issuer and signing date.
+    pub fn detail(&self) -> String {
+        let issuer = match &self.issuer {
+            Some(issuer) if issuer.chars().count() > MAX_ISSUER_CHARS => {
+                let cut: String = issuer.chars().take(MAX_ISSUER_CHARS - 1).collect();
+                format!("{}…", cut.trim_end())
+            }
+            Some(issuer) => issuer.clone(),
+            None => "Unknown issuer".to_string(),
+        };
+        let date = match &self.signed_at {
+            Some(time) => time.get(..10).unwrap_or(time).to_string(),
+            None => "no timestamp".to_string(),
+        };
+        format!("{} · {}", issuer, date)
+    }
+
+    /// Render the badge as a standalone SVG document.
+    pub fn render_svg(&self) -> String {
+        let title = format!(
+            "{} Content Credentials · {}",
+            self.status.symbol(),
+            self.status.label()
+        );
+        let detail = self.detail();
+        // Approximate advance of an 11px sans-serif glyph; enough to size the box.
+        let text_width = |s: &str| s.chars().count() * 7;
+        let width = 16 + text_width(&title).max(text_width(&detail));
+        let color = self.status.color();
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="40" viewBox="0 0 {width} 40" role="img" aria-label="{aria}">
+  <title>{aria}</title>
+  <rect x="0.5" y="0.5" width="{inner}" height="39" rx="4" fill="#ffffff" stroke="{color}"/>
+  <path d="M4.5 0.5h{band}a4 4 0 0 1 4 4v13.5h-{inner}v-13.5a4 4 0 0 1 4-4z" fill="{color}"/>
+  <g font-family="Verdana,DejaVu Sans,sans-serif" font-size="11">
+    <text x="8" y="13" fill="#ffffff" font-weight="bold">{title}</text>
+    <text x="8" y="32" fill="#212121">{detail}</text>
+  </g>
+</svg>
+"##,
+            width = width,
+            inner = width - 1,
+            band = width - 9,
+            color = color,
+            aria = xml_escape(&format!("{} — {}", title, detail)),
+            title = xml_escape(&title),
+            detail = xml_escape(&detail),
+        )
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge_status_from_facts() {
+        let facts = |trust: &str, valid: bool| FileFacts {
+            trust: trust.to_string(),
+            valid,
+            ..Default::default()
+        };
+        let status = |f: FileFacts| Badge::from_facts(&f).status;
+        assert_eq!(status(facts("trusted", true)), BadgeStatus::Trusted);
+        assert_eq!(status(facts("untrusted", true)), BadgeStatus::Untrusted);
+        assert_eq!(status(facts("unknown", true)), BadgeStatus::Unverified);
+        assert_eq!(status(facts("trusted", false)), BadgeStatus::Invalid);
+    }
+
+    #[test]
+    fn test_render_svg_escapes_and_summarizes() {
+        let badge = Badge {
+            status: BadgeStatus::Trusted,
+            issuer: Some("Smith & <Sons>".to_string()),
+            signed_at: Some("2025-03-04T05:06:07+00:00".to_string()),
+        };
+        assert_eq!(badge.detail(), "Smith & <Sons> · 2025-03-04");
+        let svg = badge.render_svg();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("Smith &amp; &lt;Sons&gt; · 2025-03-04"));
+        assert!(svg.contains("Content Credentials · Trusted"));
+        assert!(!svg.contains("<Sons>"));
+
+        let unsigned = Badge {
+            status: BadgeStatus::Invalid,
+            issuer: Some("X".repeat(60)),
+            signed_at: None,
+        };
+        let detail = unsigned.detail();
+        assert!(detail.ends_with("… · no timestamp"));
+        assert_eq!(
+            detail.chars().count(),
+            MAX_ISSUER_CHARS + " · no timestamp".chars().count()
+        );
+    }
+}
@@ -0,0 +1,454 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Content bill of materials (CBOM): every ingredient of an asset, recursively, with its title,
+//! hashes, source type, license and trust status, in an SPDX-like JSON layout (`packages` plus
+//! `relationships`) for legal and compliance reviews of derivative works.
+
+use crate::filter::{status_codes, trust_status};
+use crate::provenance::{
+    find_manifest, ingredient_assertions, ingredient_manifest_label, ingredient_title,
+    manifest_title, MAX_PROVENANCE_DEPTH, MAX_PROVENANCE_NODES,
+};
+use crate::ManifestExtractionResult;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Version of the CBOM layout written by [`build_cbom`].
+pub const CBOM_VERSION: &str = "1.0";
+
+/// SPDX's value for information that is absent.
+pub const NOASSERTION: &str = "NOASSERTION";
+
+/// Assertions whose fields may carry license and copyright information.
+const METADATA_ASSERTIONS: &[&str] = &[
+    "stds.schema-org.CreativeWork",
+    "c2pa.metadata",
+    "stds.iptc",
+    "cawg.metadata",
+];
+
+/// Metadata fields naming a license (or usage terms), in order of preference.
+const LICENSE_FIELDS: &[&str] = &[
+    "license",
+    "xmpRights:WebStatement",
+    "xmpRights:UsageTerms",
+    "usageInfo",
+    "Iptc4xmpExt:LicensorURL",
+];
+
+/// Metadata fields holding a copyright notice or holder, in order of preference.
+const COPYRIGHT_FIELDS: &[&str] = &["copyrightNotice", "dc:rights", "copyrightHolder"];
+
+/// A content bill of materials for one asset.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cbom {
+    pub cbom_version: &'static str,
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: &'static str,
+    /// File name of the asset.
+    pub name: String,
+    pub creation_info: CreationInfo,
+    /// SPDX ID of the package describing the asset itself.
+    pub document_describes: Vec<String>,
+    /// The asset (first) and every ingredient below it.
+    pub packages: Vec<CbomPackage>,
+    pub relationships: Vec<CbomRelationship>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreationInfo {
+    /// RFC 3339 time at which the CBOM was generated.
+    pub created: String,
+    pub creators: Vec<String>,
+}
+
+/// The asset or one ingredient.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CbomPackage {
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    /// Title of the ingredient (or of the asset's active manifest, else its file name).
+    pub name: String,
+    /// Levels below the asset (0 for the asset itself).
+    pub depth: usize,
+    /// MIME type, when declared.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Label of the package's own manifest, when it has Content Credentials.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_label: Option<String>,
+    pub checksums: Vec<CbomChecksum>,
+    /// How the package was used by its parent (`parentOf`, `componentOf`, `inputTo`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<String>,
+    /// IPTC digital source type declared by the package's manifest actions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digital_source_type: Option<String>,
+    /// License or usage terms from metadata assertions, or `NOASSERTION`.
+    pub license_declared: String,
+    /// Copyright notice or holder from metadata assertions, or `NOASSERTION`.
+    pub copyright_text: String,
+    /// `trusted`, `untrusted` or `unknown`; `noCredentials` without a manifest.
+    pub trust_status: String,
+    /// Validation failure codes of the package's manifest.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub validation_failures: Vec<String>,
+}
+
+/// A hash recorded for a package.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CbomChecksum {
+    /// Hash algorithm (e.g. `sha256`).
+    pub algorithm: String,
+    /// Digest as recorded: hex for the asset hash, base64 for C2PA hashed URIs.
+    pub value: String,
+    /// What was hashed: `content`, `manifest`, `claimSignature`, or `thumbnail`.
+    pub covers: String,
+}
+
+/// A parent/ingredient edge.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CbomRelationship {
+    pub spdx_element_id: String,
+    /// `CONTAINS` for components, `DESCENDANT_OF` for parents, `GENERATED_FROM` for inputs.
+    pub relationship_type: &'static str,
+    pub related_spdx_element: String,
+    /// The C2PA ingredient relationship the type was derived from.
+    pub c2pa_relationship: String,
+}
+
+/// Text of a metadata value: strings as-is; objects by `name`, `@value` or `x-default`; arrays
+/// by their first element.
+fn metadata_text(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) if !s.is_empty() => Some(s.clone()),
+        JsonValue::Array(items) => items.iter().find_map(metadata_text),
+        JsonValue::Object(map) => ["name", "@value", "x-default", "@id", "url"]
+            .iter()
+            .find_map(|k| map.get(*k).and_then(metadata_text)),
+        _ => None,
+    }
+}
+
+/// First of `fields` found in the manifest's metadata assertions.
+fn metadata_field(manifest: &JsonValue, fields: &[&str]) -> Option<String> {
+    let assertions = manifest.get("assertions")?.as_object()?;
+    let metadata: Vec<&JsonValue> = assertions
+        .iter()
+        .filter(|(k, _)| METADATA_ASSERTIONS.iter().any(|m| k.starts_with(m)))
+        .map(|(_, v)| v)
+        .collect();
+    fields.iter().find_map(|field| {
+        metadata
+            .iter()
+            .find_map(|a| a.get(*field).and_then(metadata_text))
+    })
+}
+
+/// Digital source type of the first action in the manifest that declares one.
+fn digital_source_type(manifest: &JsonValue) -> Option<String> {
+    let assertions = manifest.get("assertions")?.as_object()?;
+    assertions
+        .iter()
+        .filter(|(k, _)| k.starts_with("c2pa.actions"))
+        .filter_map(|(_, v)| v.get("actions").and_then(|a| a.as_array()))
+        .flatten()
+        .find_map(|a| a.get("digitalSourceType").and_then(|v| v.as_str()))
+        .map(str::to_string)
+}
+
+/// Hashes an ingredient assertion records (its manifest, signature, content and thumbnail).
+fn ingredient_checksums(ingredient: &JsonValue) -> Vec<CbomChecksum> {
+    let default_alg = ingredient
+        .get("alg")
+        .and_then(|v| v.as_str())
+        .unwrap_or("sha256");
+    let mut checksums: Vec<CbomChecksum> = [
+        ("activeManifest", "manifest"),
+        ("c2pa_manifest", "manifest"),
+        ("claimSignature", "claimSignature"),
+        ("data", "content"),
+        ("thumbnail", "thumbnail"),
+    ]
+    .iter()
+    .filter_map(|(key, covers)| {
+        let uri = ingredient.get(*key)?;
+        Some(CbomChecksum {
+            algorithm: uri
+                .get("alg")
+                .and_then(|v| v.as_str())
+                .unwrap_or(default_alg)
+                .to_string(),
+            value: uri.get("hash")?.as_str()?.to_string(),
+            covers: covers.to_string(),
+        })
+    })
+    .collect();
+    if let Some(hash) = ingredient.get("hash").and_then(|v| v.as_str()) {
+        checksums.push(CbomChecksum {
+            algorithm: default_alg.to_string(),
+            value: hash.to_string(),
+            covers: "content".to_string(),
+        });
+    }
+    checksums
+}
+
+fn relationship_type(relationship: &str) -> &'static str {
+    match relationship {
+        "parentOf" => "DESCENDANT_OF",
+        "inputTo" => "GENERATED_FROM",
+        _ => "CONTAINS",
+    }
+}
+
+/// Fill license, copyright, source type and trust from the package's own manifest.
+fn describe_manifest(package: &mut CbomPackage, manifest: Option<&JsonValue>) {
+    match manifest {
+        Some(m) => {
+            package.license_declared =
+                metadata_field(m, LICENSE_FIELDS).unwrap_or_else(|| NOASSERTION.to_string());
+            package.copyright_text =
+                metadata_field(m, COPYRIGHT_FIELDS).unwrap_or_else(|| NOASSERTION.to_string());
+            package.digital_source_type = digital_source_type(m);
+            package.trust_status = trust_status(m).to_string();
+            package.validation_failures = status_codes(m, "failure")
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+        }
+        None => package.trust_status = "noCredentials".to_string(),
+    }
+}
+
+fn new_package(spdx_id: String, name: String, depth: usize) -> CbomPackage {
+    CbomPackage {
+        spdx_id,
+        name,
+        depth,
+        format: None,
+        manifest_label: None,
+        checksums: Vec::new(),
+        relationship: None,
+        digital_source_type: None,
+        license_declared: NOASSERTION.to_string(),
+        copyright_text: NOASSERTION.to_string(),
+        trust_status: String::new(),
+        validation_failures: Vec::new(),
+    }
+}
+
+/// Add the ingredients of manifest `label` (the package `parent_id`) and, recursively, theirs.
+fn add_ingredients(
+    crjson: &JsonValue,
+    label: &str,
+    parent_id: &str,
+    depth: usize,
+    path: &mut HashSet<String>,
+    cbom: &mut Cbom,
+) {
+    let Some(manifest) = find_manifest(crjson, label) else {
+        return;
+    };
+    path.insert(label.to_string());
+    for ingredient in ingredient_assertions(manifest) {
+        let spdx_id = format!("SPDXRef-Ingredient-{}", cbom.packages.len());
+        let mut package = new_package(spdx_id.clone(), ingredient_title(ingredient), depth);
+        let relationship = ingredient
+            .get("relationship")
+            .and_then(|v| v.as_str())
+            .unwrap_or("componentOf")
+            .to_string();
+        package.format = ingredient
+            .get("format")
+            .or_else(|| ingredient.get("dc:format"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        package.checksums = ingredient_checksums(ingredient);
+        package.relationship = Some(relationship.clone());
+        let child_label = ingredient_manifest_label(ingredient);
+        package.manifest_label = child_label.map(str::to_string);
+        describe_manifest(
+            &mut package,
+            child_label.and_then(|l| find_manifest(crjson, l)),
+        );
+        cbom.relationships.push(CbomRelationship {
+            spdx_element_id: parent_id.to_string(),
+            relationship_type: relationship_type(&relationship),
+            related_spdx_element: spdx_id.clone(),
+            c2pa_relationship: relationship,
+        });
+        cbom.packages.push(package);
+
+        if let Some(child) = child_label {
+            let expand = !path.contains(child)
+                && path.len() < MAX_PROVENANCE_DEPTH
+                && cbom.packages.len() < MAX_PROVENANCE_NODES;
+            if expand {
+                add_ingredients(crjson, child, &spdx_id, depth + 1, path, cbom);
+            }
+        }
+    }
+    path.remove(label);
+}
+
+/// Build the CBOM of an extracted asset: the asset itself, then every ingredient of its active
+/// manifest, recursively through the ingredients' own manifests. Cycles and chains deeper than
+/// [`MAX_PROVENANCE_DEPTH`] are not expanded.
+pub fn build_cbom(result: &ManifestExtractionResult) -> Cbom {
+    let crjson = &result.manifest_value;
+    let file_name = Path::new(&result.input_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| result.input_path.clone());
+    let active = find_manifest(crjson, &result.active_label);
+
+    let asset_id = "SPDXRef-Asset".to_string();
+    let mut asset = new_package(
+        asset_id.clone(),
+        active
+            .and_then(manifest_title)
+            .map(str::to_string)
+            .unwrap_or_else(|| file_name.clone()),
+        0,
+    );
+    asset.format = crate::asset_format_for_path(&result.input_path).map(|f| f.mime.to_string());
+    asset.manifest_label = Some(result.active_label.clone());
+    asset.checksums = result
+        .asset_hash
+        .iter()
+        .map(|hash| CbomChecksum {
+            algorithm: result.asset_hash_alg.unwrap_or_default().name().to_string(),
+            value: hash.clone(),
+            covers: "content".to_string(),
+        })
+        .collect();
+    describe_manifest(&mut asset, active);
+
+    let mut cbom = Cbom {
+        cbom_version: CBOM_VERSION,
+        spdx_id: "SPDXRef-DOCUMENT",
+        name: file_name,
+        creation_info: CreationInfo {
+            created: chrono::Utc::now().to_rfc3339(),
+            creators: vec![format!("Tool: crTool-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        document_describes: vec![asset_id.clone()],
+        packages: vec![asset],
+        relationships: Vec::new(),
+    };
+    add_ingredients(
+        crjson,
+        &result.active_label,
+        &asset_id,
+        1,
+        &mut HashSet::new(),
+        &mut cbom,
+    );
+    cbom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::HashAlgorithm;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_cbom() {
+        let crjson = json!({
+            "manifests": [
+                {
+                    "label": "urn:c2pa:root",
+                    "claim.v2": { "dc:title": "Composite" },
+                    "assertions": {
+                        "c2pa.ingredient.v3": {
+                            "title": "background.jpg", "relationship": "parentOf", "format": "image/jpeg",
+                            "activeManifest": { "url": "self#jumbf=/c2pa/urn:c2pa:bg", "hash": "bWFuaWZlc3Q=" }
+                        },
+                        "c2pa.ingredient.v3__1": {
+                            "title": "logo.png", "relationship": "componentOf",
+                            "data": { "url": "self#jumbf=x", "hash": "Y29udGVudA==", "alg": "sha384" }
+                        },
+                        "stds.schema-org.CreativeWork": { "license": "https://example.com/license" }
+                    },
+                    "validationResults": { "success": [ { "code": "signingCredential.trusted" } ] }
+                },
+                {
+                    "label": "urn:c2pa:bg",
+                    "assertions": {
+                        "c2pa.actions.v2": { "actions": [ { "action": "c2pa.created",
+                            "digitalSourceType": "http://cv.iptc.org/newscodes/digitalsourcetype/digitalCapture" } ] },
+                        "c2pa.metadata": { "dc:rights": { "x-default": "© Example Photo" } },
+                        "c2pa.ingredient.v3": { "title": "raw.dng", "relationship": "parentOf",
+                            "activeManifest": { "url": "self#jumbf=/c2pa/urn:c2pa:root" } }
+                    },
+                    "validationResults": { "failure": [ { "code": "signingCredential.untrusted" } ] }
+                }
+            ]
+        });
+        let result = ManifestExtractionResult {
+            input_path: "out/composite.jpg".into(),
+            active_label: "urn:c2pa:root".into(),
+            asset_hash: Some("abcd".into()),
+            asset_hash_alg: Some(HashAlgorithm::Sha384),
+            manifest_json: crjson.to_string(),
+            manifest_value: crjson,
+        };
+        let cbom = build_cbom(&result);
+
+        assert_eq!(cbom.name, "composite.jpg");
+        let names: Vec<&str> = cbom.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Composite", "background.jpg", "raw.dng", "logo.png"]
+        );
+
+        let asset = &cbom.packages[0];
+        assert_eq!(asset.license_declared, "https://example.com/license");
+        assert_eq!(asset.trust_status, "trusted");
+        assert_eq!(asset.checksums[0].value, "abcd");
+        assert_eq!(asset.checksums[0].algorithm, "sha384");
+
+        let background = &cbom.packages[1];
+        assert_eq!(background.copyright_text, "© Example Photo");
+        assert_eq!(background.license_declared, NOASSERTION);
+        assert_eq!(background.trust_status, "untrusted");
+        assert!(background
+            .digital_source_type
+            .as_deref()
+            .is_some_and(|t| t.ends_with("digitalCapture")));
+        assert_eq!(background.checksums[0].covers, "manifest");
+
+        // raw.dng points back at the root manifest: listed, but not expanded again
+        assert_eq!(cbom.packages[2].depth, 2);
+        assert_eq!(cbom.packages.len(), 4);
+
+        let logo = &cbom.packages[3];
+        assert_eq!(logo.trust_status, "noCredentials");
+        assert_eq!(logo.checksums[0].algorithm, "sha384");
+        assert_eq!(cbom.relationships[0].relationship_type, "DESCENDANT_OF");
+        assert_eq!(
+            cbom.relationships[2].related_spdx_element,
+            "SPDXRef-Ingredient-3"
+        );
+        assert_eq!(cbom.relationships[2].relationship_type, "CONTAINS");
+    }
+}
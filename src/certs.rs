@@ -0,0 +1,479 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Signing certificates: inspection against the C2PA certificate profile, and test
+//! credentials (a throwaway certificate authority and a C2PA signing certificate issued by it)
+//! for trying out signing without an external `openssl` recipe. Never use the test credentials
+//! for production content.
+
+use crate::error::{bail, format_err, Context, Result};
+use crate::hashing::{hash_bytes, HashAlgorithm};
+use crate::severity::Severity;
+use c2pa::SigningAlg;
+use chrono::Datelike;
+use rcgen::{
+    date_time_ymd, BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair, KeyUsagePurpose, SignatureAlgorithm, PKCS_ECDSA_P256_SHA256, PKCS_ECDSA_P384_SHA384,
+    PKCS_ED25519,
+};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use x509_parser::certificate::X509Certificate;
+use x509_parser::extensions::{ExtendedKeyUsage, KeyUsage};
+use x509_parser::prelude::FromDer;
+use x509_parser::public_key::PublicKey;
+use x509_parser::time::ASN1Time;
+use x509_parser::x509::X509Version;
+
+/// EKU OIDs that make a certificate usable for signing C2PA claims.
+const SIGNING_EKUS: &[&str] = &[
+    "1.3.6.1.5.5.7.3.4",
+    "1.3.6.1.5.5.7.3.36",
+    "1.3.6.1.4.1.62558.2.1",
+    "1.3.6.1.4.1.311.76.59.1.9",
+];
+
+/// `id-kp-timeStamping` and `id-kp-OCSPSigning`: each must be the only EKU of its certificate.
+const EXCLUSIVE_EKUS: &[&str] = &["1.3.6.1.5.5.7.3.8", "1.3.6.1.5.5.7.3.9"];
+
+/// `anyExtendedKeyUsage`, not allowed in a C2PA signing certificate.
+const ANY_EKU: &str = "2.5.29.37.0";
+
+/// Certificate signature algorithms allowed by the C2PA certificate profile.
+const SIGNATURE_ALGORITHMS: &[&str] = &[
+    "1.2.840.10045.4.3.2",
+    "1.2.840.10045.4.3.3",
+    "1.2.840.10045.4.3.4",
+    "1.2.840.113549.1.1.10",
+    "1.2.840.113549.1.1.11",
+    "1.2.840.113549.1.1.12",
+    "1.2.840.113549.1.1.13",
+    "1.3.101.112",
+];
+
+/// Smallest RSA modulus, in bits, accepted for signing.
+const MIN_RSA_BITS: usize = 2048;
+
+/// Name of a well-known EKU OID, e.g. `emailProtection` for `1.3.6.1.5.5.7.3.4`.
+pub fn eku_name(oid: &str) -> Option<&'static str> {
+    Some(match oid {
+        "2.5.29.37.0" => "anyExtendedKeyUsage",
+        "1.3.6.1.5.5.7.3.1" => "serverAuth",
+        "1.3.6.1.5.5.7.3.2" => "clientAuth",
+        "1.3.6.1.5.5.7.3.3" => "codeSigning",
+        "1.3.6.1.5.5.7.3.4" => "emailProtection",
+        "1.3.6.1.5.5.7.3.8" => "timeStamping",
+        "1.3.6.1.5.5.7.3.9" => "OCSPSigning",
+        "1.3.6.1.5.5.7.3.36" => "documentSigning",
+        "1.3.6.1.4.1.62558.2.1" => "c2pa-kp-claimSigning",
+        "1.3.6.1.4.1.311.76.59.1.9" => "Microsoft C2PA signing",
+        _ => return None,
+    })
+}
+
+/// A C2PA certificate profile check that a certificate does not pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CertificateFinding {
+    /// `error` when validators reject signatures made with the certificate.
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl std::fmt::Display for CertificateFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// What [`inspect_certificate`] found in a certificate.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    /// Serial number as colon-separated hex.
+    pub serial_number: String,
+    /// Start of the validity period (RFC 3339).
+    pub not_before: String,
+    /// End of the validity period (RFC 3339).
+    pub not_after: String,
+    /// Whether the current time is within the validity period.
+    pub currently_valid: bool,
+    /// Whether the basic constraints mark it as a CA certificate.
+    pub is_ca: bool,
+    /// Key usage bits, e.g. `digitalSignature`.
+    pub key_usage: Vec<String>,
+    /// Extended key usage OIDs in dot notation (see [`eku_name`]).
+    pub extended_key_usage: Vec<String>,
+    /// Signing algorithm for the public key, e.g. `es256`; `None` when C2PA has none for it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_alg: Option<String>,
+    /// SHA-256 of the DER certificate, as in `allowedHashes` trust lists.
+    pub sha256: String,
+    /// Failed C2PA certificate profile checks; empty for a conforming signing certificate.
+    pub findings: Vec<CertificateFinding>,
+}
+
+impl CertificateInfo {
+    /// True when no check failed at the `error` level.
+    pub fn is_conformant(&self) -> bool {
+        !self.findings.iter().any(|f| f.severity == Severity::Error)
+    }
+}
+
+fn rfc3339(time: ASN1Time) -> String {
+    chrono::DateTime::from_timestamp(time.timestamp(), 0)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_else(|| time.to_string())
+}
+
+fn key_usage_names(usage: &KeyUsage) -> Vec<String> {
+    [
+        (usage.digital_signature(), "digitalSignature"),
+        (usage.non_repudiation(), "nonRepudiation"),
+        (usage.key_encipherment(), "keyEncipherment"),
+        (usage.data_encipherment(), "dataEncipherment"),
+        (usage.key_agreement(), "keyAgreement"),
+        (usage.key_cert_sign(), "keyCertSign"),
+        (usage.crl_sign(), "cRLSign"),
+        (usage.encipher_only(), "encipherOnly"),
+        (usage.decipher_only(), "decipherOnly"),
+    ]
+    .into_iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, name)| name.to_string())
+    .collect()
+}
+
+fn eku_oids(eku: &ExtendedKeyUsage) -> Vec<String> {
+    let mut oids: Vec<String> = [
+        (eku.any, ANY_EKU),
+        (eku.server_auth, "1.3.6.1.5.5.7.3.1"),
+        (eku.client_auth, "1.3.6.1.5.5.7.3.2"),
+        (eku.code_signing, "1.3.6.1.5.5.7.3.3"),
+        (eku.email_protection, "1.3.6.1.5.5.7.3.4"),
+        (eku.time_stamping, "1.3.6.1.5.5.7.3.8"),
+        (eku.ocsp_signing, "1.3.6.1.5.5.7.3.9"),
+    ]
+    .into_iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, oid)| oid.to_string())
+    .collect();
+    oids.extend(eku.other.iter().map(|oid| oid.to_id_string()));
+    oids
+}
+
+/// Inspect a DER certificate and check it against the C2PA signing certificate profile:
+/// X.509 v3, an allowed signature algorithm and key, not a CA, not self-signed, a critical key
+/// usage with `digitalSignature`, and an EKU permitted for claim signing.
+pub fn inspect_certificate(der: &[u8]) -> Result<CertificateInfo> {
+    let (_, cert) = X509Certificate::from_der(der)
+        .map_err(|e| format_err!("Failed to parse X.509 certificate: {}", e))?;
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let mut error = |message: String| errors.push(message);
+
+    if cert.version() != X509Version::V3 {
+        error(format!(
+            "X.509 version {} (must be v3)",
+            cert.version().0 + 1
+        ));
+    }
+    let signature_oid = cert.signature_algorithm.algorithm.to_id_string();
+    if !SIGNATURE_ALGORITHMS.contains(&signature_oid.as_str()) {
+        error(format!(
+            "Certificate signature algorithm {signature_oid} is not allowed"
+        ));
+    }
+    let signing_alg = match crate::keys::certificate_signing_algorithm(cert.public_key()) {
+        Ok(alg) => Some(alg.to_string()),
+        Err(e) => {
+            error(e.to_string());
+            None
+        }
+    };
+    if let Ok(PublicKey::RSA(rsa)) = cert.public_key().parsed() {
+        if rsa.key_size() < MIN_RSA_BITS {
+            error(format!(
+                "{}-bit RSA key (at least {MIN_RSA_BITS} bits required)",
+                rsa.key_size()
+            ));
+        }
+    }
+    let validity = cert.validity();
+    if !validity.is_valid() {
+        error("Not valid at the current time".to_string());
+    }
+    let is_ca = matches!(cert.basic_constraints(), Ok(Some(bc)) if bc.value.ca);
+    if is_ca {
+        error("CA certificate (basic constraints cA is true)".to_string());
+    }
+    if cert.subject() == cert.issuer() {
+        error("Self-signed; sign with a certificate issued by a CA".to_string());
+    }
+
+    let key_usage = match cert.key_usage() {
+        Ok(Some(ku)) => {
+            if !ku.value.digital_signature() {
+                error("Key usage does not include digitalSignature".to_string());
+            }
+            if ku.value.key_cert_sign() {
+                error("Key usage includes keyCertSign".to_string());
+            }
+            if !ku.critical {
+                warnings.push("Key usage extension is not marked critical".to_string());
+            }
+            key_usage_names(ku.value)
+        }
+        _ => {
+            error("No key usage extension".to_string());
+            Vec::new()
+        }
+    };
+
+    let extended_key_usage = match cert.extended_key_usage() {
+        Ok(Some(eku)) => eku_oids(eku.value),
+        _ => Vec::new(),
+    };
+    if extended_key_usage.is_empty() {
+        error("No extended key usage".to_string());
+    } else if extended_key_usage.iter().any(|oid| oid == ANY_EKU) {
+        error("Extended key usage includes anyExtendedKeyUsage".to_string());
+    } else if extended_key_usage.len() > 1
+        && extended_key_usage
+            .iter()
+            .any(|oid| EXCLUSIVE_EKUS.contains(&oid.as_str()))
+    {
+        error("timeStamping and OCSPSigning must be the only extended key usage".to_string());
+    } else if !extended_key_usage
+        .iter()
+        .any(|oid| SIGNING_EKUS.contains(&oid.as_str()))
+    {
+        error(
+            "No extended key usage for claim signing (emailProtection, documentSigning or \
+            c2pa-kp-claimSigning)"
+                .to_string(),
+        );
+    }
+
+    let findings = errors
+        .into_iter()
+        .map(|message| CertificateFinding {
+            severity: Severity::Error,
+            message,
+        })
+        .chain(warnings.into_iter().map(|message| CertificateFinding {
+            severity: Severity::Warning,
+            message,
+        }))
+        .collect();
+
+    Ok(CertificateInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        serial_number: cert.raw_serial_as_string(),
+        not_before: rfc3339(validity.not_before),
+        not_after: rfc3339(validity.not_after),
+        currently_valid: validity.is_valid(),
+        is_ca,
+        key_usage,
+        extended_key_usage,
+        signing_alg,
+        sha256: hash_bytes(der, HashAlgorithm::Sha256),
+        findings,
+    })
+}
+
+/// Inspect the first certificate of a PEM file (the signing certificate of a chain) with
+/// [`inspect_certificate`]. When the file holds a chain, also checks that the next certificate
+/// issued it.
+pub fn inspect_certificate_file(path: &Path) -> Result<CertificateInfo> {
+    let pem = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read certificate file {:?}", path))?;
+    let certs = crate::trust::pem_certificates(&pem);
+    let Some(first) = certs.first() else {
+        bail!("No PEM certificates found in {:?}", path);
+    };
+    let mut info = inspect_certificate(first)?;
+    if let Some(next) = certs.get(1) {
+        let next = inspect_certificate(next)?;
+        if next.subject != info.issuer {
+            info.findings.push(CertificateFinding {
+                severity: Severity::Warning,
+                message: format!(
+                    "The next certificate in the file ({}) is not the issuer ({})",
+                    next.subject, info.issuer
+                ),
+            });
+        }
+    }
+    Ok(info)
+}
+
+/// Files written by [`generate_test_credentials`].
+#[derive(Debug, Clone)]
+pub struct TestCredentials {
+    /// PEM certificate of the test CA; use it as a trust anchor to see the signer as trusted.
+    pub ca_cert: PathBuf,
+    /// PEM chain of the signing certificate followed by the CA certificate.
+    pub cert_chain: PathBuf,
+    /// PEM (PKCS#8) private key of the signing certificate.
+    pub key: PathBuf,
+    /// Signing algorithm of the key.
+    pub signing_alg: SigningAlg,
+}
+
+fn certificate_params(common_name: &str, organization: &str) -> Result<CertificateParams> {
+    let mut params = CertificateParams::new(Vec::<String>::new())
+        .context("Failed to create certificate parameters")?;
+    params
+        .distinguished_name
+        .push(DnType::CommonName, common_name);
+    params
+        .distinguished_name
+        .push(DnType::OrganizationName, organization);
+    let year = chrono::Utc::now().year();
+    params.not_before = date_time_ymd(year - 1, 1, 1);
+    params.not_after = date_time_ymd(year + 5, 1, 1);
+    Ok(params)
+}
+
+/// Generate an Ed25519 test CA and a signing certificate issued by it for `organization`, and
+/// write them to `dir` as `test-ca.pem`, `test-signer.pem` (chain) and `test-signer.key`.
+pub fn generate_test_credentials(dir: &Path, organization: &str) -> Result<TestCredentials> {
+    generate_test_credentials_for(dir, organization, SigningAlg::Ed25519)
+}
+
+/// Key generation parameters for `alg`. RSA and P-521 keys cannot be generated.
+fn key_algorithm(alg: SigningAlg) -> Result<&'static SignatureAlgorithm> {
+    match alg {
+        SigningAlg::Es256 => Ok(&PKCS_ECDSA_P256_SHA256),
+        SigningAlg::Es384 => Ok(&PKCS_ECDSA_P384_SHA384),
+        SigningAlg::Ed25519 => Ok(&PKCS_ED25519),
+        other => bail!(
+            "Cannot generate {} test keys; use es256, es384 or ed25519",
+            other
+        ),
+    }
+}
+
+/// [`generate_test_credentials`] with `alg` keys (es256, es384 or ed25519) for the CA and the
+/// signing certificate. The CA is self-signed; the signing certificate is not, since C2PA
+/// validators reject self-signed signing certificates.
+pub fn generate_test_credentials_for(
+    dir: &Path,
+    organization: &str,
+    alg: SigningAlg,
+) -> Result<TestCredentials> {
+    let key_alg = key_algorithm(alg)?;
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create directory {:?}", dir))?;
+
+    let ca_key = KeyPair::generate_for(key_alg).context("Failed to generate CA key")?;
+    let mut ca_params = certificate_params(&format!("{organization} Test Root CA"), organization)?;
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    ca_params.key_usages = vec![
+        KeyUsagePurpose::KeyCertSign,
+        KeyUsagePurpose::CrlSign,
+        KeyUsagePurpose::DigitalSignature,
+    ];
+    let ca = ca_params
+        .self_signed(&ca_key)
+        .context("Failed to create CA certificate")?;
+
+    let key = KeyPair::generate_for(key_alg).context("Failed to generate signing key")?;
+    let mut params = certificate_params(&format!("{organization} Test Signer"), organization)?;
+    params.is_ca = IsCa::ExplicitNoCa;
+    params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+    // C2PA signing certificates need one of the permitted EKUs; email protection is the
+    // most widely accepted.
+    params.extended_key_usages = vec![ExtendedKeyUsagePurpose::EmailProtection];
+    params.use_authority_key_identifier_extension = true;
+    let cert = params
+        .signed_by(&key, &ca, &ca_key)
+        .context("Failed to issue signing certificate")?;
+
+    let credentials = TestCredentials {
+        ca_cert: dir.join("test-ca.pem"),
+        cert_chain: dir.join("test-signer.pem"),
+        key: dir.join("test-signer.key"),
+        signing_alg: alg,
+    };
+    let write = |path: &Path, contents: String| {
+        fs::write(path, contents).with_context(|| format!("Failed to write {:?}", path))
+    };
+    write(&credentials.ca_cert, ca.pem())?;
+    write(
+        &credentials.cert_chain,
+        format!("{}{}", cert.pem(), ca.pem()),
+    )?;
+    write(&credentials.key, key.serialize_pem())?;
+    Ok(credentials)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_test_credentials() {
+        let dir = std::env::temp_dir().join(format!("crtool-test-certs-{}", std::process::id()));
+        let credentials = generate_test_credentials(&dir, "crTool Tests").unwrap();
+
+        let chain = fs::read_to_string(&credentials.cert_chain).unwrap();
+        assert_eq!(crate::trust::pem_certificates(&chain).len(), 2);
+        let ca = fs::read_to_string(&credentials.ca_cert).unwrap();
+        assert_eq!(crate::trust::pem_certificates(&ca).len(), 1);
+        assert!(fs::read_to_string(&credentials.key)
+            .unwrap()
+            .contains("PRIVATE KEY"));
+        assert_eq!(
+            crate::signing::detect_signing_algorithm(&credentials.cert_chain).unwrap(),
+            c2pa::SigningAlg::Ed25519
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_inspect_test_credentials() {
+        let dir = std::env::temp_dir().join(format!("crtool-test-inspect-{}", std::process::id()));
+        let credentials =
+            generate_test_credentials_for(&dir, "crTool Tests", SigningAlg::Es256).unwrap();
+
+        let info = inspect_certificate_file(&credentials.cert_chain).unwrap();
+        assert!(info.is_conformant(), "{:?}", info.findings);
+        assert!(info.findings.is_empty(), "{:?}", info.findings);
+        assert_eq!(info.signing_alg.as_deref(), Some("es256"));
+        assert!(info.subject.contains("crTool Tests Test Signer"));
+        assert!(info.issuer.contains("crTool Tests Test Root CA"));
+        assert!(info.currently_valid);
+        assert!(!info.is_ca);
+        assert_eq!(info.key_usage, vec!["digitalSignature"]);
+        assert_eq!(info.extended_key_usage, vec!["1.3.6.1.5.5.7.3.4"]);
+        assert_eq!(
+            eku_name(&info.extended_key_usage[0]),
+            Some("emailProtection")
+        );
+
+        // the CA certificate is self-signed and cannot sign claims
+        let ca = inspect_certificate_file(&credentials.ca_cert).unwrap();
+        assert!(ca.is_ca);
+        assert!(!ca.is_conformant());
+        assert!(ca
+            .findings
+            .iter()
+            .any(|f| f.message.contains("Self-signed")));
+
+        assert!(generate_test_credentials_for(&dir, "crTool Tests", SigningAlg::Ps256).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,373 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Decoding of the COSE_Sign1 claim signature (RFC 9052) used by C2PA manifests.
+
+use crate::error::{bail, format_err, Context, Result};
+use base64::Engine;
+use ciborium::value::Value as CborValue;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// COSE header label for the algorithm identifier.
+const HEADER_ALG: i128 = 1;
+/// COSE header label for the X.509 certificate chain (RFC 9360).
+const HEADER_X5CHAIN: i128 = 33;
+/// C2PA timestamp countersignature header labels (v1 and v2).
+const HEADER_SIG_TST: &str = "sigTst";
+const HEADER_SIG_TST2: &str = "sigTst2";
+/// CBOR tag for COSE_Sign1.
+const COSE_SIGN1_TAG: u64 = 18;
+
+/// Decoded fields of a COSE_Sign1 claim signature.
+#[derive(Debug, Clone, Default)]
+pub struct CoseSign1Info {
+    /// COSE algorithm identifier from the protected header (e.g. -7 for ES256).
+    pub algorithm_id: Option<i64>,
+    /// DER-encoded certificates from the x5chain header, signing certificate first.
+    pub certificate_chain: Vec<Vec<u8>>,
+    /// Header label the timestamp tokens were found under ("sigTst" or "sigTst2").
+    pub timestamp_header: Option<String>,
+    /// DER-encoded RFC 3161 timestamp tokens from the timestamp countersignature header.
+    pub timestamp_tokens: Vec<Vec<u8>>,
+    /// Raw signature bytes.
+    pub signature: Vec<u8>,
+    /// Labels present in the protected header, in encoded order.
+    pub protected_header_labels: Vec<String>,
+    /// Labels present in the unprotected header, in encoded order.
+    pub unprotected_header_labels: Vec<String>,
+    /// Header bucket x5chain was found in ("protected" or "unprotected").
+    pub x5chain_location: Option<&'static str>,
+    /// True when the payload element is nil (detached payload, as C2PA requires).
+    pub payload_detached: bool,
+}
+
+/// Header conformance details of one claim signature, as emitted by `--include-cose-details`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoseDetails {
+    pub algorithm_id: Option<i64>,
+    pub algorithm: Option<&'static str>,
+    pub protected_headers: Vec<String>,
+    pub unprotected_headers: Vec<String>,
+    pub x5chain_present: bool,
+    pub x5chain_location: Option<&'static str>,
+    pub certificate_count: usize,
+    pub timestamp_present: bool,
+    pub timestamp_header: Option<String>,
+    pub timestamp_token_count: usize,
+    pub payload_detached: bool,
+    pub signature_length: usize,
+}
+
+impl From<&CoseSign1Info> for CoseDetails {
+    fn from(info: &CoseSign1Info) -> Self {
+        Self {
+            algorithm_id: info.algorithm_id,
+            algorithm: info.algorithm_name(),
+            protected_headers: info.protected_header_labels.clone(),
+            unprotected_headers: info.unprotected_header_labels.clone(),
+            x5chain_present: !info.certificate_chain.is_empty(),
+            x5chain_location: info.x5chain_location,
+            certificate_count: info.certificate_chain.len(),
+            timestamp_present: !info.timestamp_tokens.is_empty(),
+            timestamp_header: info.timestamp_header.clone(),
+            timestamp_token_count: info.timestamp_tokens.len(),
+            payload_detached: info.payload_detached,
+            signature_length: info.signature.len(),
+        }
+    }
+}
+
+impl CoseSign1Info {
+    /// Algorithm name for the COSE identifier (e.g. "ES256"), when known.
+    pub fn algorithm_name(&self) -> Option<&'static str> {
+        self.algorithm_id.and_then(cose_algorithm_name)
+    }
+}
+
+/// Map a COSE algorithm identifier to the C2PA algorithm name.
+pub fn cose_algorithm_name(id: i64) -> Option<&'static str> {
+    Some(match id {
+        -7 => "ES256",
+        -35 => "ES384",
+        -36 => "ES512",
+        -37 => "PS256",
+        -38 => "PS384",
+        -39 => "PS512",
+        -8 => "Ed25519",
+        _ => return None,
+    })
+}
+
+fn as_int(v: &CborValue) -> Option<i128> {
+    match v {
+        CborValue::Integer(i) => Some(i128::from(*i)),
+        _ => None,
+    }
+}
+
+/// Display name for a header label: well-known integer labels by name, others as-is.
+fn header_label_name(label: &CborValue) -> String {
+    match label {
+        CborValue::Integer(i) => match i128::from(*i) {
+            1 => "alg".to_string(),
+            2 => "crit".to_string(),
+            3 => "content type".to_string(),
+            4 => "kid".to_string(),
+            HEADER_X5CHAIN => "x5chain".to_string(),
+            n => n.to_string(),
+        },
+        CborValue::Text(t) => t.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Look up a header value by integer label.
+fn header_int<'a>(map: &'a [(CborValue, CborValue)], label: i128) -> Option<&'a CborValue> {
+    map.iter()
+        .find(|(k, _)| as_int(k) == Some(label))
+        .map(|(_, v)| v)
+}
+
+/// Look up a header value by text label.
+fn header_text<'a>(map: &'a [(CborValue, CborValue)], label: &str) -> Option<&'a CborValue> {
+    map.iter()
+        .find(|(k, _)| matches!(k, CborValue::Text(t) if t == label))
+        .map(|(_, v)| v)
+}
+
+/// x5chain is either a single bstr or an array of bstr.
+fn certificates_from_x5chain(v: &CborValue) -> Vec<Vec<u8>> {
+    match v {
+        CborValue::Bytes(b) => vec![b.clone()],
+        CborValue::Array(items) => items
+            .iter()
+            .filter_map(|i| match i {
+                CborValue::Bytes(b) => Some(b.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Tokens from a sigTst/sigTst2 value: `{ "tstTokens": [ { "val": bstr }, ... ] }`.
+fn timestamp_tokens(v: &CborValue) -> Vec<Vec<u8>> {
+    let CborValue::Map(map) = v else {
+        return Vec::new();
+    };
+    let Some(CborValue::Array(tokens)) = header_text(map, "tstTokens") else {
+        return Vec::new();
+    };
+    tokens
+        .iter()
+        .filter_map(|t| match t {
+            CborValue::Map(m) => match header_text(m, "val") {
+                Some(CborValue::Bytes(b)) => Some(b.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Decode the protected header bstr into a map (empty bstr means an empty map).
+fn decode_protected(bytes: &[u8]) -> Result<Vec<(CborValue, CborValue)>> {
+    if bytes.is_empty() {
+        return Ok(Vec::new());
+    }
+    match ciborium::de::from_reader::<CborValue, _>(bytes)
+        .map_err(|e| format_err!("Invalid COSE protected header: {}", e))?
+    {
+        CborValue::Map(m) => Ok(m),
+        _ => bail!("COSE protected header is not a CBOR map"),
+    }
+}
+
+/// Decode a COSE_Sign1 structure (tagged or untagged).
+pub fn parse_cose_sign1(bytes: &[u8]) -> Result<CoseSign1Info> {
+    let value: CborValue = ciborium::de::from_reader(bytes)
+        .map_err(|e| format_err!("Invalid COSE_Sign1 CBOR: {}", e))?;
+    let value = match value {
+        CborValue::Tag(COSE_SIGN1_TAG, inner) => *inner,
+        CborValue::Tag(tag, _) => {
+            bail!("Unexpected CBOR tag {} (expected COSE_Sign1)", tag)
+        }
+        other => other,
+    };
+    let CborValue::Array(parts) = value else {
+        bail!("COSE_Sign1 is not a CBOR array");
+    };
+    if parts.len() != 4 {
+        bail!("COSE_Sign1 must have 4 elements, found {}", parts.len());
+    }
+
+    let protected = match &parts[0] {
+        CborValue::Bytes(b) => decode_protected(b)?,
+        _ => bail!("COSE_Sign1 protected header is not a bstr"),
+    };
+    let unprotected = match &parts[1] {
+        CborValue::Map(m) => m.clone(),
+        _ => bail!("COSE_Sign1 unprotected header is not a map"),
+    };
+    let signature = match &parts[3] {
+        CborValue::Bytes(b) => b.clone(),
+        _ => bail!("COSE_Sign1 signature is not a bstr"),
+    };
+
+    let algorithm_id = header_int(&protected, HEADER_ALG)
+        .and_then(as_int)
+        .and_then(|i| i64::try_from(i).ok());
+
+    let (x5chain_location, x5chain) = match header_int(&protected, HEADER_X5CHAIN) {
+        Some(v) => (Some("protected"), Some(v)),
+        None => match header_int(&unprotected, HEADER_X5CHAIN) {
+            Some(v) => (Some("unprotected"), Some(v)),
+            None => (None, None),
+        },
+    };
+    let certificate_chain = x5chain.map(certificates_from_x5chain).unwrap_or_default();
+
+    let (timestamp_header, timestamp_tokens) = [HEADER_SIG_TST2, HEADER_SIG_TST]
+        .iter()
+        .find_map(|label| {
+            header_text(&unprotected, label).map(|v| (Some(label.to_string()), timestamp_tokens(v)))
+        })
+        .unwrap_or((None, Vec::new()));
+
+    Ok(CoseSign1Info {
+        algorithm_id,
+        certificate_chain,
+        timestamp_header,
+        timestamp_tokens,
+        signature,
+        protected_header_labels: protected
+            .iter()
+            .map(|(k, _)| header_label_name(k))
+            .collect(),
+        unprotected_header_labels: unprotected
+            .iter()
+            .map(|(k, _)| header_label_name(k))
+            .collect(),
+        x5chain_location,
+        payload_detached: matches!(parts[2], CborValue::Null),
+    })
+}
+
+/// PEM-encode a DER certificate.
+pub fn der_to_pem(label: &str, der: &[u8]) -> String {
+    let b64 = base64::engine::general_purpose::STANDARD.encode(der);
+    let mut pem = format!("-----BEGIN {}-----\n", label);
+    for chunk in b64.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {}-----\n", label));
+    pem
+}
+
+/// Read and decode the claim signature of `manifest_label` from a raw manifest store.
+pub fn manifest_cose_info(store: &[u8], manifest_label: &str) -> Result<CoseSign1Info> {
+    let parsed = crate::jumbf::parse_manifest_store(store)?;
+    let bytes = crate::jumbf::manifest_signature_bytes(&parsed, manifest_label)
+        .with_context(|| format!("No claim signature box for manifest {}", manifest_label))?;
+    parse_cose_sign1(bytes)
+}
+
+/// COSE details for each manifest in an asset's store, keyed by manifest label. Manifests whose
+/// signature cannot be decoded get `{ "error": ... }` instead.
+pub fn asset_cose_details<P: AsRef<Path>>(
+    path: P,
+    manifest_labels: &[String],
+) -> Result<serde_json::Value> {
+    let store = crate::jumbf::load_manifest_store(path)?;
+    let details: BTreeMap<&str, serde_json::Value> = manifest_labels
+        .iter()
+        .map(|label| {
+            let value = match manifest_cose_info(&store, label) {
+                Ok(info) => serde_json::to_value(CoseDetails::from(&info))
+                    .unwrap_or(serde_json::Value::Null),
+                Err(e) => serde_json::json!({ "error": e.to_string() }),
+            };
+            (label.as_str(), value)
+        })
+        .collect();
+    serde_json::to_value(details).context("Failed to serialize COSE details")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(v: &CborValue) -> Vec<u8> {
+        let mut out = Vec::new();
+        ciborium::ser::into_writer(v, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_parse_cose_sign1() {
+        let protected = encode(&CborValue::Map(vec![
+            (
+                CborValue::Integer(1.into()),
+                CborValue::Integer((-7).into()),
+            ),
+            (
+                CborValue::Integer(33.into()),
+                CborValue::Array(vec![CborValue::Bytes(vec![1, 2, 3])]),
+            ),
+        ]));
+        let unprotected = CborValue::Map(vec![(
+            CborValue::Text("sigTst2".into()),
+            CborValue::Map(vec![(
+                CborValue::Text("tstTokens".into()),
+                CborValue::Array(vec![CborValue::Map(vec![(
+                    CborValue::Text("val".into()),
+                    CborValue::Bytes(vec![9, 9]),
+                )])]),
+            )]),
+        )]);
+        let sign1 = CborValue::Tag(
+            COSE_SIGN1_TAG,
+            Box::new(CborValue::Array(vec![
+                CborValue::Bytes(protected),
+                unprotected,
+                CborValue::Null,
+                CborValue::Bytes(vec![0xaa, 0xbb]),
+            ])),
+        );
+
+        let info = parse_cose_sign1(&encode(&sign1)).unwrap();
+        assert_eq!(info.algorithm_name(), Some("ES256"));
+        assert_eq!(info.certificate_chain, vec![vec![1, 2, 3]]);
+        assert_eq!(info.timestamp_header.as_deref(), Some("sigTst2"));
+        assert_eq!(info.timestamp_tokens, vec![vec![9, 9]]);
+        assert_eq!(info.signature, vec![0xaa, 0xbb]);
+        assert_eq!(info.protected_header_labels, vec!["alg", "x5chain"]);
+        assert_eq!(info.unprotected_header_labels, vec!["sigTst2"]);
+        assert_eq!(info.x5chain_location, Some("protected"));
+        assert!(info.payload_detached);
+
+        let details = CoseDetails::from(&info);
+        assert!(details.x5chain_present && details.timestamp_present);
+        assert_eq!(details.certificate_count, 1);
+    }
+
+    #[test]
+    fn test_der_to_pem_wraps_lines() {
+        let pem = der_to_pem("CERTIFICATE", &[0u8; 100]);
+        assert!(pem.starts_with("-----BEGIN CERTIFICATE-----\n"));
+        assert!(pem.lines().all(|l| l.len() <= 64));
+    }
+}
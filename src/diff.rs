@@ -0,0 +1,526 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Differences between two crJSON / indicators documents, e.g. the outputs of two extractions of
+//! the same test set.
+//!
+//! Manifests are paired by label and assertions by label, so reordering does not show as a
+//! change. For each manifest the diff lists added, removed and changed assertions, the change of
+//! signer trust and of validation failure codes, and every changed value as a JSON pointer
+//! relative to the manifest. Changes outside the manifests (e.g. `jsonGenerator`) are listed
+//! with pointers relative to the document.
+
+use crate::filter::{status_codes, trust_status};
+use serde::Serialize;
+use serde_json::Value;
+
+/// How a value differs between the two documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Only in the second document.
+    Added,
+    /// Only in the first document.
+    Removed,
+    /// In both, with different values.
+    Changed,
+}
+
+impl ChangeKind {
+    /// `+`, `-`, or `~`, as printed by the CLI.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "+",
+            ChangeKind::Removed => "-",
+            ChangeKind::Changed => "~",
+        }
+    }
+}
+
+/// One value that differs, at a JSON pointer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PathChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<Value>,
+}
+
+/// An assertion added, removed or changed in a manifest present in both documents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssertionChange {
+    pub label: String,
+    pub kind: ChangeKind,
+}
+
+/// A change in a manifest's signer trust (`trusted`, `untrusted`, or `unknown`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrustChange {
+    pub old: String,
+    pub new: String,
+}
+
+/// Differences in one manifest, paired by label.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestDiff {
+    pub label: String,
+    /// `added` or `removed` for a manifest in only one document; the other fields are then
+    /// empty.
+    pub kind: ChangeKind,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assertions: Vec<AssertionChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust: Option<TrustChange>,
+    /// Validation failure codes only in the second document.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub new_failures: Vec<String>,
+    /// Validation failure codes only in the first document.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub resolved_failures: Vec<String>,
+    /// Changed values, with pointers relative to the manifest.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<PathChange>,
+}
+
+/// Differences between two indicators documents (see [`diff_indicators`]).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IndicatorsDiff {
+    /// Manifests added, removed, or changed, in the order of the first document (then added
+    /// ones).
+    pub manifests: Vec<ManifestDiff>,
+    /// Changed values outside `manifests`, with pointers relative to the document.
+    pub changes: Vec<PathChange>,
+}
+
+impl IndicatorsDiff {
+    /// Whether the documents are the same (apart from ignored paths).
+    pub fn is_empty(&self) -> bool {
+        self.manifests.is_empty() && self.changes.is_empty()
+    }
+
+    /// Number of path-level changes, counting an added or removed manifest as one.
+    pub fn change_count(&self) -> usize {
+        self.changes.len()
+            + self
+                .manifests
+                .iter()
+                .map(|m| match m.kind {
+                    ChangeKind::Changed => m.changes.len(),
+                    _ => 1,
+                })
+                .sum::<usize>()
+    }
+}
+
+fn write_change(
+    f: &mut std::fmt::Formatter<'_>,
+    indent: &str,
+    change: &PathChange,
+) -> std::fmt::Result {
+    let show = |value: &Option<Value>| value.as_ref().map_or("—".to_string(), Value::to_string);
+    writeln!(
+        f,
+        "{indent}{} {}: {} → {}",
+        change.kind.symbol(),
+        change.path,
+        show(&change.old),
+        show(&change.new)
+    )
+}
+
+/// One line per change, manifests first, e.g. for test failure messages.
+impl std::fmt::Display for IndicatorsDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for manifest in &self.manifests {
+            writeln!(f, "{} manifest {}", manifest.kind.symbol(), manifest.label)?;
+            for assertion in &manifest.assertions {
+                writeln!(
+                    f,
+                    "  {} assertion {}",
+                    assertion.kind.symbol(),
+                    assertion.label
+                )?;
+            }
+            if let Some(trust) = &manifest.trust {
+                writeln!(f, "  trust: {} → {}", trust.old, trust.new)?;
+            }
+            for code in &manifest.new_failures {
+                writeln!(f, "  new failure: {code}")?;
+            }
+            for code in &manifest.resolved_failures {
+                writeln!(f, "  resolved failure: {code}")?;
+            }
+            for change in &manifest.changes {
+                write_change(f, "    ", change)?;
+            }
+        }
+        for change in &self.changes {
+            write_change(f, "", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Options for [`diff_indicators_with`].
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// JSON pointers to leave out, with everything beneath them (e.g. `/jsonGenerator` for the
+    /// generation time). Pointers are relative to the document, or to each manifest when they
+    /// start with `/manifests/*`.
+    pub ignore: Vec<String>,
+}
+
+impl DiffOptions {
+    fn ignored(&self, path: &str) -> bool {
+        self.ignore.iter().any(|ignore| {
+            let ignore = ignore.trim_end_matches('/');
+            path == ignore
+                || path
+                    .strip_prefix(ignore)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+
+    /// Pointers to ignore inside each manifest.
+    fn for_manifests(&self) -> DiffOptions {
+        DiffOptions {
+            ignore: self
+                .ignore
+                .iter()
+                .filter_map(|i| i.strip_prefix("/manifests/*"))
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// Differences between indicators documents `a` (before) and `b` (after).
+pub fn diff_indicators(a: &Value, b: &Value) -> IndicatorsDiff {
+    diff_indicators_with(a, b, &DiffOptions::default())
+}
+
+/// [`diff_indicators`], leaving out the paths in `options`.
+pub fn diff_indicators_with(a: &Value, b: &Value, options: &DiffOptions) -> IndicatorsDiff {
+    let mut diff = IndicatorsDiff::default();
+
+    let without_manifests = |value: &Value| match value {
+        Value::Object(map) => {
+            let mut map = map.clone();
+            map.remove("manifests");
+            Value::Object(map)
+        }
+        other => other.clone(),
+    };
+    diff_values(
+        &without_manifests(a),
+        &without_manifests(b),
+        String::new(),
+        options,
+        &mut diff.changes,
+    );
+
+    let manifest_options = options.for_manifests();
+    let old = keyed(a.get("manifests"));
+    let new = keyed(b.get("manifests"));
+    for (label, before) in &old {
+        let Some((_, after)) = new.iter().find(|(l, _)| l == label) else {
+            diff.manifests
+                .push(manifest_only(label, ChangeKind::Removed));
+            continue;
+        };
+        let manifest = diff_manifest(label, before, after, &manifest_options);
+        if manifest.assertions.is_empty()
+            && manifest.trust.is_none()
+            && manifest.new_failures.is_empty()
+            && manifest.resolved_failures.is_empty()
+            && manifest.changes.is_empty()
+        {
+            continue;
+        }
+        diff.manifests.push(manifest);
+    }
+    for (label, _) in &new {
+        if !old.iter().any(|(l, _)| l == label) {
+            diff.manifests.push(manifest_only(label, ChangeKind::Added));
+        }
+    }
+    diff
+}
+
+fn manifest_only(label: &str, kind: ChangeKind) -> ManifestDiff {
+    ManifestDiff {
+        label: label.to_string(),
+        kind,
+        assertions: Vec::new(),
+        trust: None,
+        new_failures: Vec::new(),
+        resolved_failures: Vec::new(),
+        changes: Vec::new(),
+    }
+}
+
+fn diff_manifest(
+    label: &str,
+    before: &Value,
+    after: &Value,
+    options: &DiffOptions,
+) -> ManifestDiff {
+    let mut manifest = manifest_only(label, ChangeKind::Changed);
+
+    let old_assertions = keyed(before.get("assertions"));
+    let new_assertions = keyed(after.get("assertions"));
+    for (label, value) in &old_assertions {
+        match new_assertions.iter().find(|(l, _)| l == label) {
+            None => manifest.assertions.push(AssertionChange {
+                label: label.clone(),
+                kind: ChangeKind::Removed,
+            }),
+            Some((_, new)) if new != value => manifest.assertions.push(AssertionChange {
+                label: label.clone(),
+                kind: ChangeKind::Changed,
+            }),
+            Some(_) => {}
+        }
+    }
+    for (label, _) in &new_assertions {
+        if !old_assertions.iter().any(|(l, _)| l == label) {
+            manifest.assertions.push(AssertionChange {
+                label: label.clone(),
+                kind: ChangeKind::Added,
+            });
+        }
+    }
+
+    let (old_trust, new_trust) = (trust_status(before), trust_status(after));
+    if old_trust != new_trust {
+        manifest.trust = Some(TrustChange {
+            old: old_trust.to_string(),
+            new: new_trust.to_string(),
+        });
+    }
+    let old_failures = status_codes(before, "failure");
+    let new_failures = status_codes(after, "failure");
+    manifest.new_failures = new_failures
+        .iter()
+        .filter(|code| !old_failures.contains(code))
+        .map(|code| code.to_string())
+        .collect();
+    manifest.resolved_failures = old_failures
+        .iter()
+        .filter(|code| !new_failures.contains(code))
+        .map(|code| code.to_string())
+        .collect();
+
+    diff_values(before, after, String::new(), options, &mut manifest.changes);
+    manifest
+}
+
+/// Entries of a manifest or assertion collection with their labels: an object keyed by label,
+/// or an array of objects with a `label` (the index when there is none).
+fn keyed(collection: Option<&Value>) -> Vec<(String, &Value)> {
+    match collection {
+        Some(Value::Object(map)) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let label = item
+                    .get("label")
+                    .and_then(|l| l.as_str())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| index.to_string());
+                (label, item)
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Escape a key as a JSON pointer token.
+fn pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Append the changes from `a` to `b` below `path`. Objects are compared key by key and arrays
+/// index by index; any other difference replaces the whole value.
+fn diff_values(
+    a: &Value,
+    b: &Value,
+    path: String,
+    options: &DiffOptions,
+    out: &mut Vec<PathChange>,
+) {
+    if options.ignored(&path) {
+        return;
+    }
+    match (a, b) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, before) in old {
+                let child = format!("{}/{}", path, pointer_token(key));
+                match new.get(key) {
+                    Some(after) => diff_values(before, after, child, options, out),
+                    None if !options.ignored(&child) => out.push(PathChange {
+                        path: child,
+                        kind: ChangeKind::Removed,
+                        old: Some(before.clone()),
+                        new: None,
+                    }),
+                    None => {}
+                }
+            }
+            for (key, after) in new {
+                let child = format!("{}/{}", path, pointer_token(key));
+                if !old.contains_key(key) && !options.ignored(&child) {
+                    out.push(PathChange {
+                        path: child,
+                        kind: ChangeKind::Added,
+                        old: None,
+                        new: Some(after.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                let child = format!("{}/{}", path, index);
+                match (old.get(index), new.get(index)) {
+                    (Some(before), Some(after)) => diff_values(before, after, child, options, out),
+                    (Some(before), None) => out.push(PathChange {
+                        path: child,
+                        kind: ChangeKind::Removed,
+                        old: Some(before.clone()),
+                        new: None,
+                    }),
+                    (None, Some(after)) => out.push(PathChange {
+                        path: child,
+                        kind: ChangeKind::Added,
+                        old: None,
+                        new: Some(after.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if a != b => out.push(PathChange {
+            path,
+            kind: ChangeKind::Changed,
+            old: Some(a.clone()),
+            new: Some(b.clone()),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document(title: &str, failures: Value, extra_assertion: bool) -> Value {
+        let mut assertions = json!({
+            "c2pa.actions.v2": {"actions": [{"action": "c2pa.created"}]}
+        });
+        if extra_assertion {
+            assertions["c2pa.hash.data"] = json!({"alg": "sha256"});
+        }
+        json!({
+            "@context": ["https://contentcredentials.org/crjson/context/v1"],
+            "jsonGenerator": {"name": "crTool", "date": title},
+            "manifests": [
+                {
+                    "label": "urn:c2pa:ingredient",
+                    "assertions": {}
+                },
+                {
+                    "label": "urn:c2pa:active",
+                    "claim.v2": {"dc:title": title},
+                    "assertions": assertions,
+                    "validationResults": {"success": [], "failure": failures}
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_identical_documents() {
+        let a = document("a.jpg", json!([]), false);
+        assert!(diff_indicators(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn test_manifest_assertion_and_trust_changes() {
+        let a = document("a.jpg", json!([]), true);
+        let mut b = document(
+            "b.jpg",
+            json!([{"code": "signingCredential.untrusted"}]),
+            false,
+        );
+        // Manifest order does not matter
+        b["manifests"].as_array_mut().unwrap().reverse();
+        b["manifests"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({"label": "urn:c2pa:new", "assertions": {}}));
+
+        let diff = diff_indicators(&a, &b);
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].path, "/jsonGenerator/date");
+
+        assert_eq!(diff.manifests.len(), 2);
+        let active = &diff.manifests[0];
+        assert_eq!(active.label, "urn:c2pa:active");
+        assert_eq!(active.kind, ChangeKind::Changed);
+        assert_eq!(
+            active.assertions,
+            [AssertionChange {
+                label: "c2pa.hash.data".to_string(),
+                kind: ChangeKind::Removed
+            }]
+        );
+        assert_eq!(
+            active.trust,
+            Some(TrustChange {
+                old: "unknown".to_string(),
+                new: "untrusted".to_string()
+            })
+        );
+        assert_eq!(active.new_failures, ["signingCredential.untrusted"]);
+        let mut paths: Vec<&str> = active.changes.iter().map(|c| c.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "/assertions/c2pa.hash.data",
+                "/claim.v2/dc:title",
+                "/validationResults/failure/0"
+            ]
+        );
+        assert_eq!(diff.manifests[1].kind, ChangeKind::Added);
+        assert_eq!(diff.change_count(), 5);
+
+        let options = DiffOptions {
+            ignore: vec![
+                "/jsonGenerator".to_string(),
+                "/manifests/*/claim.v2".to_string(),
+            ],
+        };
+        let diff = diff_indicators_with(&a, &b, &options);
+        assert!(diff.changes.is_empty());
+        assert!(diff.manifests[0]
+            .changes
+            .iter()
+            .all(|c| !c.path.starts_with("/claim.v2")));
+    }
+}
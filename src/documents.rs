@@ -0,0 +1,102 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Text and JSON documents that accompany media (captions, transcripts, metadata).
+//!
+//! c2pa-rs cannot embed a manifest store in these formats, so they are signed into a detached
+//! `.c2pa` store next to the document (`caption.json` → `caption.json.c2pa`). The store name
+//! keeps the document's extension, so `intro.json` and `intro.txt` in one directory get a store
+//! each. The store is bound to the document's bytes by a `c2pa.hash.data` assertion, so any edit
+//! to the document shows up as a data hash mismatch.
+
+use crate::error::{bail, Result};
+use crate::{ManifestExtractionResult, Settings};
+use std::path::{Path, PathBuf};
+
+/// Text document formats signed with a detached store: (extension, MIME type).
+pub const TEXT_DOCUMENT_FORMATS: &[(&str, &str)] =
+    &[("json", "application/json"), ("txt", "text/plain")];
+
+/// MIME type of a text document path, from its extension (case-insensitive).
+pub fn text_document_mime<P: AsRef<Path>>(path: P) -> Option<&'static str> {
+    let ext = path.as_ref().extension()?.to_str()?.to_lowercase();
+    TEXT_DOCUMENT_FORMATS
+        .iter()
+        .find(|(e, _)| *e == ext)
+        .map(|(_, mime)| *mime)
+}
+
+/// Whether a path is a text document signed with a detached store.
+pub fn is_text_document_path<P: AsRef<Path>>(path: P) -> bool {
+    text_document_mime(path).is_some()
+}
+
+/// Path of the detached manifest store for a document: its full file name plus `.c2pa`.
+pub fn detached_store_path<P: AsRef<Path>>(document: P) -> PathBuf {
+    let mut name = document.as_ref().as_os_str().to_os_string();
+    name.push(".c2pa");
+    PathBuf::from(name)
+}
+
+/// Read the detached manifest store of a document.
+pub fn load_detached_store<P: AsRef<Path>>(document: P) -> Result<Vec<u8>> {
+    let store_path = detached_store_path(document.as_ref());
+    if !store_path.is_file() {
+        bail!(
+            "No detached manifest store for {:?} (expected {:?})",
+            document.as_ref(),
+            store_path
+        );
+    }
+    crate::jumbf::read_store_file(&store_path)
+}
+
+/// Extract the crJSON manifest of a text document from its detached store, validating the
+/// store's data hash against the document's bytes.
+pub fn extract_document_manifest<P: AsRef<Path>>(
+    document: P,
+    settings: &Settings,
+) -> Result<ManifestExtractionResult> {
+    let document = document.as_ref();
+    if !is_text_document_path(document) {
+        bail!("Not a text document: {:?}", document);
+    }
+    let store = load_detached_store(document)?;
+    crate::extract_crjson_manifest_with_store(document, &store, settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_document_paths() {
+        assert_eq!(
+            text_document_mime("captions/intro.JSON"),
+            Some("application/json")
+        );
+        assert_eq!(text_document_mime("transcript.txt"), Some("text/plain"));
+        assert!(!is_text_document_path("photo.jpg"));
+        assert!(!is_text_document_path("README"));
+        assert_eq!(
+            detached_store_path("captions/intro.json"),
+            PathBuf::from("captions/intro.json.c2pa")
+        );
+        assert_ne!(
+            detached_store_path("captions/intro.json"),
+            detached_store_path("captions/intro.txt")
+        );
+
+        let err = load_detached_store("no/such/caption.txt").unwrap_err();
+        assert!(err.to_string().contains("caption.txt.c2pa"), "{err}");
+    }
+}
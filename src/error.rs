@@ -0,0 +1,234 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Error type of the library.
+//!
+//! Every public function returns [`CrtoolError`], so callers can branch on the cases they handle
+//! differently: an asset without a manifest ([`CrtoolError::NoManifest`]), a missing or
+//! unreadable file ([`CrtoolError::FileNotFound`], [`CrtoolError::Io`]), a schema that cannot be
+//! used ([`CrtoolError::InvalidSchema`]) or a refused input ([`CrtoolError::LimitExceeded`]).
+//! Errors are often wrapped in a [`CrtoolError::Context`] describing what was being done; match
+//! on [`CrtoolError::root`] to see the cause.
+
+use crate::limits::LimitExceeded;
+use std::error::Error as StdError;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// Result type of the library.
+pub type Result<T, E = CrtoolError> = std::result::Result<T, E>;
+
+/// Errors returned by the library.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum CrtoolError {
+    /// The asset carries no C2PA manifest.
+    #[error(transparent)]
+    NoManifest(c2pa::Error),
+    /// An input file does not exist.
+    #[error("Input file does not exist: {0:?}")]
+    FileNotFound(PathBuf),
+    /// A file could not be read or written.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A JSON schema could not be found or compiled.
+    #[error("{0}")]
+    InvalidSchema(String),
+    /// An input was refused because it is over a resource limit (see [`crate::limits`]).
+    #[error(transparent)]
+    LimitExceeded(#[from] LimitExceeded),
+    /// Any other error from the C2PA SDK, e.g. a manifest that could not be decoded.
+    #[error(transparent)]
+    C2pa(c2pa::Error),
+    /// JSON that could not be parsed or serialized.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Any other failure, e.g. an invalid argument.
+    #[error("{0}")]
+    Message(String),
+    /// An error from another library.
+    #[error(transparent)]
+    Other(Box<dyn StdError + Send + Sync>),
+    /// `inner`, with a description of what was being done. It is part of the message, so the
+    /// message reads like `Failed to read C2PA data from "a.jpg": <cause>`.
+    #[error("{context}: {inner}")]
+    Context {
+        context: String,
+        inner: Box<CrtoolError>,
+    },
+}
+
+impl CrtoolError {
+    /// The error beneath any [`CrtoolError::Context`] wrappers.
+    pub fn root(&self) -> &CrtoolError {
+        match self {
+            Self::Context { inner, .. } => inner.root(),
+            other => other,
+        }
+    }
+
+    /// Whether the asset carries no C2PA manifest (as opposed to one that could not be read).
+    pub fn is_no_manifest(&self) -> bool {
+        matches!(self.root(), Self::NoManifest(_))
+    }
+
+    /// The limit violation behind this error, if it was caused by one.
+    pub fn limit_exceeded(&self) -> Option<&LimitExceeded> {
+        match self.root() {
+            Self::LimitExceeded(limit) => Some(limit),
+            _ => None,
+        }
+    }
+
+    /// Convert any error, keeping its kind when it is one the library classifies.
+    pub(crate) fn from_error<E: StdError + Send + Sync + 'static>(error: E) -> Self {
+        let error: Box<dyn StdError + Send + Sync> = Box::new(error);
+        let error = match error.downcast::<CrtoolError>() {
+            Ok(error) => return *error,
+            Err(error) => error,
+        };
+        let error = match error.downcast::<c2pa::Error>() {
+            Ok(error) => return Self::from(*error),
+            Err(error) => error,
+        };
+        let error = match error.downcast::<std::io::Error>() {
+            Ok(error) => return Self::Io(*error),
+            Err(error) => error,
+        };
+        let error = match error.downcast::<serde_json::Error>() {
+            Ok(error) => return Self::Json(*error),
+            Err(error) => error,
+        };
+        match error.downcast::<LimitExceeded>() {
+            Ok(error) => Self::LimitExceeded(*error),
+            Err(error) => Self::Other(error),
+        }
+    }
+}
+
+impl From<c2pa::Error> for CrtoolError {
+    fn from(error: c2pa::Error) -> Self {
+        match error {
+            c2pa::Error::JumbfNotFound | c2pa::Error::ProvenanceMissing => Self::NoManifest(error),
+            error => Self::C2pa(error),
+        }
+    }
+}
+
+impl From<String> for CrtoolError {
+    fn from(message: String) -> Self {
+        Self::Message(message)
+    }
+}
+
+impl From<&str> for CrtoolError {
+    fn from(message: &str) -> Self {
+        Self::Message(message.to_string())
+    }
+}
+
+/// Whether `error`, or an error in its source chain, means the asset has no C2PA manifest.
+/// For callers holding an error that wraps a [`CrtoolError`], e.g. an `anyhow::Error`
+/// (`is_no_manifest_error(&*e)`).
+pub fn is_no_manifest_error(error: &(dyn StdError + 'static)) -> bool {
+    std::iter::successors(Some(error), |&e| e.source()).any(|cause| {
+        cause
+            .downcast_ref::<CrtoolError>()
+            .is_some_and(CrtoolError::is_no_manifest)
+            || matches!(
+                cause.downcast_ref::<c2pa::Error>(),
+                Some(c2pa::Error::JumbfNotFound | c2pa::Error::ProvenanceMissing)
+            )
+    })
+}
+
+/// Add a description of what was being done to an error, like `anyhow::Context`.
+pub(crate) trait Context<T> {
+    fn context<C: Display>(self, context: C) -> Result<T>;
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> Result<T>;
+}
+
+impl<T, E: StdError + Send + Sync + 'static> Context<T> for std::result::Result<T, E> {
+    fn context<C: Display>(self, context: C) -> Result<T> {
+        self.map_err(|e| CrtoolError::Context {
+            context: context.to_string(),
+            inner: Box::new(CrtoolError::from_error(e)),
+        })
+    }
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> Result<T> {
+        self.map_err(|e| CrtoolError::Context {
+            context: context().to_string(),
+            inner: Box::new(CrtoolError::from_error(e)),
+        })
+    }
+}
+
+impl<T> Context<T> for Option<T> {
+    fn context<C: Display>(self, context: C) -> Result<T> {
+        self.ok_or_else(|| CrtoolError::Message(context.to_string()))
+    }
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> Result<T> {
+        self.ok_or_else(|| CrtoolError::Message(context().to_string()))
+    }
+}
+
+/// A [`CrtoolError::Message`] from format arguments.
+macro_rules! format_err {
+    ($($arg:tt)*) => {
+        $crate::error::CrtoolError::Message(format!($($arg)*))
+    };
+}
+
+/// Return early with a [`CrtoolError::Message`] from format arguments.
+macro_rules! bail {
+    ($($arg:tt)*) => {
+        return Err($crate::error::format_err!($($arg)*))
+    };
+}
+
+pub(crate) use bail;
+pub(crate) use format_err;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_manifest() -> Result<()> {
+        Err(c2pa::Error::JumbfNotFound)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_context_keeps_kind() {
+        let err = no_manifest()
+            .context("Failed to read C2PA data from \"a.jpg\"")
+            .context("Extraction failed")
+            .unwrap_err();
+        assert!(err.is_no_manifest());
+        assert!(is_no_manifest_error(&err));
+        assert!(err
+            .to_string()
+            .starts_with("Extraction failed: Failed to read C2PA data from \"a.jpg\": "));
+
+        let err = std::fs::read("/nonexistent/crtool.jpg")
+            .context("Failed to read asset")
+            .unwrap_err();
+        assert!(matches!(err.root(), CrtoolError::Io(_)));
+        assert!(!err.is_no_manifest());
+
+        let err: Result<()> = None.context("No active C2PA manifest found");
+        assert!(matches!(err.unwrap_err(), CrtoolError::Message(_)));
+    }
+}
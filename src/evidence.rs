@@ -0,0 +1,192 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Evidence report: a self-contained record of an asset's Content Credentials for
+//! legal/affidavit workflows.
+//!
+//! Combines the asset's file hashes, the raw claim signature and certificate chain of every
+//! manifest, RFC 3161 timestamp tokens, and the c2pa-rs validation results in one JSON document.
+
+use crate::cose::{der_to_pem, manifest_cose_info};
+use crate::error::{format_err, Context, Result};
+use crate::hashing::{hash_reader_multi, HashAlgorithm};
+use crate::{extract_crjson_manifest_with_settings, Settings};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Top-level evidence report for one asset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenceReport {
+    /// Tool that produced the report.
+    pub generator: EvidenceGenerator,
+    /// RFC 3339 time at which the report was generated.
+    pub generated_at: String,
+    /// The examined asset file.
+    pub asset: EvidenceAsset,
+    /// Label of the active manifest.
+    pub active_manifest: String,
+    /// One entry per manifest in the store, in crJSON order.
+    pub manifests: Vec<ManifestEvidence>,
+    /// Validation results as reported in crJSON (`validationResults`).
+    pub validation_results: Option<serde_json::Value>,
+}
+
+/// Name and version of the generating tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceGenerator {
+    pub name: String,
+    pub version: String,
+}
+
+/// File identity of the examined asset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenceAsset {
+    pub path: String,
+    pub size_bytes: u64,
+    /// Hex digests keyed by algorithm name ("sha256", "sha384", "sha512").
+    pub hashes: BTreeMap<String, String>,
+}
+
+/// Signature evidence for a single manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEvidence {
+    pub label: String,
+    /// Signature algorithm (e.g. "ES256") from the COSE protected header.
+    pub signature_algorithm: Option<String>,
+    /// Raw COSE signature bytes, base64.
+    pub signature_base64: Option<String>,
+    /// Signer certificate chain as PEM blocks, signing certificate first.
+    pub certificate_chain_pem: Vec<String>,
+    /// Certificate details as reported in crJSON (`signature.certificateInfo`).
+    pub certificate_info: Option<serde_json::Value>,
+    /// Timestamp details, when the signature carries a timestamp.
+    pub timestamp: Option<TimestampEvidence>,
+    /// Set when the raw signature could not be read from the manifest store.
+    pub error: Option<String>,
+}
+
+/// RFC 3161 timestamp evidence for a manifest signature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimestampEvidence {
+    /// COSE header the tokens were found under ("sigTst" or "sigTst2").
+    pub header: Option<String>,
+    /// DER-encoded timestamp tokens, base64.
+    pub tokens_base64: Vec<String>,
+    /// Timestamp details as reported in crJSON (`signature.timeStampInfo`).
+    pub info: Option<serde_json::Value>,
+}
+
+/// Build an evidence report for `input_path` using the given extraction settings.
+pub fn build_evidence_report<P: AsRef<Path>>(
+    input_path: P,
+    settings: &Settings,
+) -> Result<EvidenceReport> {
+    let input_path = input_path.as_ref();
+    let extraction = extract_crjson_manifest_with_settings(input_path, settings)?;
+
+    let file = fs::File::open(input_path)
+        .with_context(|| format!("Failed to open input file: {:?}", input_path))?;
+    let size_bytes = file
+        .metadata()
+        .context("Failed to read file metadata")?
+        .len();
+    let digests = hash_reader_multi(std::io::BufReader::new(file), &HashAlgorithm::ALL)?;
+    let hashes = HashAlgorithm::ALL
+        .iter()
+        .map(|a| a.name().to_string())
+        .zip(digests)
+        .collect();
+
+    let store = crate::jumbf::load_manifest_store(input_path);
+    let b64 = base64::engine::general_purpose::STANDARD;
+
+    let manifests = extraction
+        .manifest_value
+        .get("manifests")
+        .and_then(|m| m.as_array())
+        .map(|arr| arr.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|m| {
+            let label = m.get("label")?.as_str()?.to_string();
+            let sig_json = m.get("signature");
+            let certificate_info = sig_json.and_then(|s| s.get("certificateInfo")).cloned();
+            let timestamp_info = sig_json.and_then(|s| s.get("timeStampInfo")).cloned();
+
+            let cose = store
+                .as_ref()
+                .map_err(|e| format_err!("{}", e))
+                .and_then(|s| manifest_cose_info(s, &label));
+
+            let mut evidence = ManifestEvidence {
+                label,
+                signature_algorithm: None,
+                signature_base64: None,
+                certificate_chain_pem: Vec::new(),
+                certificate_info,
+                timestamp: timestamp_info.clone().map(|info| TimestampEvidence {
+                    header: None,
+                    tokens_base64: Vec::new(),
+                    info: Some(info),
+                }),
+                error: None,
+            };
+            match cose {
+                Ok(info) => {
+                    evidence.signature_algorithm = info.algorithm_name().map(str::to_string);
+                    evidence.signature_base64 = Some(b64.encode(&info.signature));
+                    evidence.certificate_chain_pem = info
+                        .certificate_chain
+                        .iter()
+                        .map(|der| der_to_pem("CERTIFICATE", der))
+                        .collect();
+                    if !info.timestamp_tokens.is_empty() {
+                        evidence.timestamp = Some(TimestampEvidence {
+                            header: info.timestamp_header.clone(),
+                            tokens_base64: info
+                                .timestamp_tokens
+                                .iter()
+                                .map(|t| b64.encode(t))
+                                .collect(),
+                            info: timestamp_info,
+                        });
+                    }
+                }
+                Err(e) => evidence.error = Some(e.to_string()),
+            }
+            Some(evidence)
+        })
+        .collect();
+
+    Ok(EvidenceReport {
+        generator: EvidenceGenerator {
+            name: "crTool".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        asset: EvidenceAsset {
+            path: input_path.to_string_lossy().to_string(),
+            size_bytes,
+            hashes,
+        },
+        active_manifest: extraction.active_label,
+        manifests,
+        validation_results: extraction.manifest_value.get("validationResults").cloned(),
+    })
+}
@@ -0,0 +1,137 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `--expect-*` checks: simple expectations about the active manifest (assertions present,
+//! signer trusted, number of ingredients), for CI pipelines that want to confirm what their
+//! signing service produced without writing a profile.
+
+use crate::filter::FileFacts;
+use crate::provenance::find_manifest;
+use serde_json::Value as JsonValue;
+
+/// Expectations checked against each extracted manifest store.
+#[derive(Debug, Clone, Default)]
+pub struct Expectations {
+    /// Assertion labels that must be present in the active manifest (any instance, so
+    /// `c2pa.actions.v2` also matches `c2pa.actions.v2__1`).
+    pub assertions: Vec<String>,
+    /// The active manifest's signing credential must be trusted.
+    pub trusted: bool,
+    /// Exact number of ingredients of the active manifest.
+    pub ingredients: Option<usize>,
+}
+
+/// Whether `manifest` has an assertion with `label` or an instance of it (`label__N`).
+pub fn has_assertion(manifest: &JsonValue, label: &str) -> bool {
+    manifest
+        .get("assertions")
+        .and_then(|a| a.as_object())
+        .is_some_and(|a| {
+            a.keys().any(|k| {
+                k == label
+                    || k.strip_prefix(label)
+                        .and_then(|rest| rest.strip_prefix("__"))
+                        .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()))
+            })
+        })
+}
+
+impl Expectations {
+    /// True when no expectation is set.
+    pub fn is_empty(&self) -> bool {
+        self.assertions.is_empty() && !self.trusted && self.ingredients.is_none()
+    }
+
+    /// Unmet expectations of one file, as messages; empty when every expectation holds.
+    pub fn check(&self, crjson: &JsonValue, facts: &FileFacts) -> Vec<String> {
+        let mut unmet = Vec::new();
+        let active = find_manifest(crjson, &facts.active_label);
+        for label in &self.assertions {
+            if !active.is_some_and(|m| has_assertion(m, label)) {
+                unmet.push(format!("assertion {} is missing", label));
+            }
+        }
+        if self.trusted && facts.trust != "trusted" {
+            unmet.push(format!("signer is {}, expected trusted", facts.trust));
+        }
+        if let Some(expected) = self.ingredients {
+            if facts.ingredient_count != expected {
+                unmet.push(format!(
+                    "{} ingredient(s), expected {}",
+                    facts.ingredient_count, expected
+                ));
+            }
+        }
+        unmet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store() -> JsonValue {
+        json!({"manifests": [{
+            "label": "urn:c2pa:a",
+            "assertions": {
+                "c2pa.actions.v2__1": {"actions": []},
+                "c2pa.hash.data": {},
+                "c2pa.ingredient.v3": {"title": "parent.jpg"}
+            }
+        }]})
+    }
+
+    fn facts(trust: &str) -> FileFacts {
+        FileFacts {
+            active_label: "urn:c2pa:a".to_string(),
+            ingredient_count: 1,
+            trust: trust.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_has_assertion_matches_instances() {
+        let manifest = &store()["manifests"][0];
+        assert!(has_assertion(manifest, "c2pa.actions.v2"));
+        assert!(has_assertion(manifest, "c2pa.hash.data"));
+        assert!(!has_assertion(manifest, "c2pa.actions"));
+        assert!(!has_assertion(manifest, "c2pa.hash"));
+    }
+
+    #[test]
+    fn test_check_reports_unmet_expectations() {
+        let met = Expectations {
+            assertions: vec!["c2pa.actions.v2".to_string()],
+            trusted: true,
+            ingredients: Some(1),
+        };
+        assert!(met.check(&store(), &facts("trusted")).is_empty());
+
+        let unmet = Expectations {
+            assertions: vec!["c2pa.thumbnail.claim".to_string()],
+            trusted: true,
+            ingredients: Some(2),
+        };
+        let messages = unmet.check(&store(), &facts("untrusted"));
+        assert_eq!(
+            messages,
+            vec![
+                "assertion c2pa.thumbnail.claim is missing",
+                "signer is untrusted, expected trusted",
+                "1 ingredient(s), expected 2",
+            ]
+        );
+        assert!(Expectations::default().is_empty());
+    }
+}
@@ -0,0 +1,548 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `--where` filter expressions over per-file facts, e.g.
+//! `trust == "untrusted" && chain_depth > 2`.
+//!
+//! Grammar: `||`, `&&`, `!`, parentheses, and comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`,
+//! `contains`) between fields and literals (numbers, `"strings"` or `'strings'`, `true`,
+//! `false`). A bare field is true when it is a true boolean, a non-zero number, or a non-empty
+//! string. `a contains b` is a case-sensitive substring test on the text of both sides.
+
+use crate::error::{bail, format_err, Result};
+use crate::indicators::active_manifest_indicator;
+use crate::provenance::{chain_depth, find_manifest};
+use crate::ManifestExtractionResult;
+use serde::Serialize;
+use std::fmt;
+
+/// Field names available in filter expressions, with a short description.
+pub const FILTER_FIELDS: &[(&str, &str)] = &[
+    ("path", "input file path"),
+    ("format", "MIME type of the asset"),
+    ("kind", "media kind: image, video, audio, document"),
+    ("active_label", "label of the active manifest"),
+    ("manifest_count", "number of manifests in the store"),
+    (
+        "chain_depth",
+        "longest ingredient chain below the active manifest",
+    ),
+    ("ingredient_count", "ingredients of the active manifest"),
+    ("trust", "trusted, untrusted, or unknown"),
+    (
+        "valid",
+        "true when the active manifest has no validation failures",
+    ),
+    (
+        "failure_count",
+        "validation failure codes on the active manifest",
+    ),
+    (
+        "has_timestamp",
+        "true when the claim signature has a timestamp",
+    ),
+    ("issuer", "signing certificate issuer"),
+    (
+        "signed_at",
+        "time of the claim signature's timestamp (RFC 3339)",
+    ),
+    ("algorithm", "claim signature algorithm"),
+];
+
+/// A value in a filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Num(f64),
+    Str(String),
+    Bool(bool),
+    Null,
+}
+
+impl FilterValue {
+    fn truthy(&self) -> bool {
+        match self {
+            FilterValue::Num(n) => *n != 0.0,
+            FilterValue::Str(s) => !s.is_empty(),
+            FilterValue::Bool(b) => *b,
+            FilterValue::Null => false,
+        }
+    }
+}
+
+impl FilterValue {
+    /// Text of the value for `contains`: strings unquoted, other values as displayed.
+    fn text(&self) -> String {
+        match self {
+            FilterValue::Str(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterValue::Num(n) => write!(f, "{}", n),
+            FilterValue::Str(s) => write!(f, "{:?}", s),
+            FilterValue::Bool(b) => write!(f, "{}", b),
+            FilterValue::Null => write!(f, "null"),
+        }
+    }
+}
+
+/// Something filter expressions can be evaluated against: a source of named field values.
+pub trait FilterFields {
+    /// Value of a named field, or `None` when it has no such field.
+    fn field(&self, name: &str) -> Option<FilterValue>;
+}
+
+/// Facts about one file that filter expressions are evaluated against.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileFacts {
+    pub path: String,
+    pub format: Option<String>,
+    pub kind: Option<String>,
+    pub active_label: String,
+    pub manifest_count: usize,
+    pub chain_depth: usize,
+    pub ingredient_count: usize,
+    pub trust: String,
+    pub valid: bool,
+    pub failure_count: usize,
+    pub has_timestamp: bool,
+    pub issuer: Option<String>,
+    /// Time of the claim signature's timestamp, when it has one.
+    pub signed_at: Option<String>,
+    pub algorithm: Option<String>,
+}
+
+/// Status codes of one kind (`success`, `failure`, ...) in a manifest's validation results.
+pub(crate) fn status_codes<'a>(manifest: &'a serde_json::Value, key: &str) -> Vec<&'a str> {
+    manifest
+        .get("validationResults")
+        .and_then(|vr| vr.get(key))
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|e| e.get("code").and_then(|c| c.as_str()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Trust in a manifest's signing credential according to its validation results: `trusted`,
+/// `untrusted`, or `unknown` (no trust list was checked).
+pub fn trust_status(manifest: &serde_json::Value) -> &'static str {
+    if status_codes(manifest, "failure").contains(&"signingCredential.untrusted") {
+        "untrusted"
+    } else if status_codes(manifest, "success").contains(&"signingCredential.trusted") {
+        "trusted"
+    } else {
+        "unknown"
+    }
+}
+
+/// Failure status codes in the validation results of manifest `label`.
+pub fn failure_codes(crjson: &serde_json::Value, label: &str) -> Vec<String> {
+    find_manifest(crjson, label)
+        .map(|m| {
+            status_codes(m, "failure")
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl FileFacts {
+    /// Derive facts from an extraction result.
+    pub fn from_extraction(result: &ManifestExtractionResult) -> Self {
+        let crjson = &result.manifest_value;
+        let format = crate::asset_format_for_path(&result.input_path);
+        let active = active_manifest_indicator(crjson, &result.active_label);
+        let failures: Vec<&str> = active
+            .iter()
+            .flat_map(|m| m.validation.failure.iter().map(|e| e.code.as_str()))
+            .collect();
+        let signature = active.as_ref().and_then(|m| m.signature.as_ref());
+
+        FileFacts {
+            path: result.input_path.clone(),
+            format: format.map(|f| f.mime.to_string()),
+            kind: format.map(|f| f.kind.name().to_string()),
+            active_label: result.active_label.clone(),
+            manifest_count: crjson
+                .get("manifests")
+                .and_then(|m| m.as_array())
+                .map_or(0, |m| m.len()),
+            chain_depth: chain_depth(crjson, &result.active_label),
+            ingredient_count: active.as_ref().map_or(0, |m| m.ingredients.len()),
+            trust: active
+                .as_ref()
+                .map_or("unknown", |m| m.trust.as_str())
+                .to_string(),
+            valid: failures.iter().all(|c| *c == "signingCredential.untrusted"),
+            failure_count: failures.len(),
+            has_timestamp: signature.is_some_and(|s| s.has_timestamp),
+            issuer: signature.and_then(|s| s.issuer.clone()),
+            signed_at: signature.and_then(|s| s.timestamp.clone()),
+            algorithm: signature.and_then(|s| s.algorithm.clone()),
+        }
+    }
+}
+
+impl FilterFields for FileFacts {
+    /// Value of a named field (see [`FILTER_FIELDS`]).
+    fn field(&self, name: &str) -> Option<FilterValue> {
+        let opt = |v: &Option<String>| v.clone().map_or(FilterValue::Null, FilterValue::Str);
+        Some(match name {
+            "path" => FilterValue::Str(self.path.clone()),
+            "format" => opt(&self.format),
+            "kind" => opt(&self.kind),
+            "active_label" => FilterValue::Str(self.active_label.clone()),
+            "manifest_count" => FilterValue::Num(self.manifest_count as f64),
+            "chain_depth" => FilterValue::Num(self.chain_depth as f64),
+            "ingredient_count" => FilterValue::Num(self.ingredient_count as f64),
+            "trust" => FilterValue::Str(self.trust.clone()),
+            "valid" => FilterValue::Bool(self.valid),
+            "failure_count" => FilterValue::Num(self.failure_count as f64),
+            "has_timestamp" => FilterValue::Bool(self.has_timestamp),
+            "issuer" => opt(&self.issuer),
+            "signed_at" => opt(&self.signed_at),
+            "algorithm" => opt(&self.algorithm),
+            _ => return None,
+        })
+    }
+}
+
+/// Comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+/// A parsed filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    Field(String),
+    Literal(FilterValue),
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    Cmp(Box<FilterExpr>, CmpOp, Box<FilterExpr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Num(f64),
+    Str(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    const OPS: &[&str] = &["&&", "||", "==", "!=", "<=", ">=", "<", ">", "!"];
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let start = i + 1;
+            let end = chars[start..]
+                .iter()
+                .position(|ch| *ch == c)
+                .map(|p| start + p)
+                .ok_or_else(|| format_err!("Unterminated string starting at offset {}", i))?;
+            tokens.push(Token::Str(chars[start..end].iter().collect()));
+            i = end + 1;
+        } else if c.is_ascii_digit()
+            || (c == '-' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit()))
+        {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let n = text
+                .parse()
+                .map_err(|_| format_err!("Invalid number '{}'", text))?;
+            tokens.push(Token::Num(n));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            // Dots allow nested JSON keys (`softwareAgent.name`) in open expressions
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            let rest: String = chars[i..].iter().take(2).collect();
+            let op = OPS
+                .iter()
+                .find(|op| rest.starts_with(**op))
+                .ok_or_else(|| format_err!("Unexpected character '{}' at offset {}", c, i))?;
+            tokens.push(Token::Op(op));
+            i += op.len();
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Accept any identifier as a field instead of only [`FILTER_FIELDS`].
+    open_fields: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat_op(&mut self, op: &'static str) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<FilterExpr> {
+        let mut lhs = self.and()?;
+        while self.eat_op("||") {
+            lhs = FilterExpr::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<FilterExpr> {
+        let mut lhs = self.unary()?;
+        while self.eat_op("&&") {
+            lhs = FilterExpr::And(Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<FilterExpr> {
+        if self.eat_op("!") {
+            return Ok(FilterExpr::Not(Box::new(self.unary()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<FilterExpr> {
+        let lhs = self.primary()?;
+        let op = match self.peek() {
+            Some(Token::Op("==")) => CmpOp::Eq,
+            Some(Token::Op("!=")) => CmpOp::Ne,
+            Some(Token::Op("<")) => CmpOp::Lt,
+            Some(Token::Op("<=")) => CmpOp::Le,
+            Some(Token::Op(">")) => CmpOp::Gt,
+            Some(Token::Op(">=")) => CmpOp::Ge,
+            Some(Token::Ident(id)) if id == "contains" => CmpOp::Contains,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        let rhs = self.primary()?;
+        Ok(FilterExpr::Cmp(Box::new(lhs), op, Box::new(rhs)))
+    }
+
+    fn primary(&mut self) -> Result<FilterExpr> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| format_err!("Unexpected end of expression"))?;
+        self.pos += 1;
+        Ok(match token {
+            Token::LParen => {
+                let inner = self.or()?;
+                if self.peek() != Some(&Token::RParen) {
+                    bail!("Expected ')'");
+                }
+                self.pos += 1;
+                inner
+            }
+            Token::Num(n) => FilterExpr::Literal(FilterValue::Num(n)),
+            Token::Str(s) => FilterExpr::Literal(FilterValue::Str(s)),
+            Token::Ident(id) => match id.as_str() {
+                "true" => FilterExpr::Literal(FilterValue::Bool(true)),
+                "false" => FilterExpr::Literal(FilterValue::Bool(false)),
+                "null" => FilterExpr::Literal(FilterValue::Null),
+                _ if self.open_fields || FILTER_FIELDS.iter().any(|(name, _)| *name == id) => {
+                    FilterExpr::Field(id)
+                }
+                _ => bail!(
+                    "Unknown field '{}'. Available fields: {}",
+                    id,
+                    FILTER_FIELDS
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            },
+            other => bail!("Unexpected token {:?}", other),
+        })
+    }
+}
+
+impl FilterExpr {
+    /// Parse an expression; unknown field names are rejected here rather than at evaluation.
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, false)
+    }
+
+    /// Parse an expression over arbitrary field names, such as the JSON keys searched by
+    /// [`crate::grep`]. Dotted names (`softwareAgent.name`) address nested keys.
+    pub fn parse_open(input: &str) -> Result<Self> {
+        Self::parse_with(input, true)
+    }
+
+    fn parse_with(input: &str, open_fields: bool) -> Result<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(input)?,
+            pos: 0,
+            open_fields,
+        };
+        let expr = parser.or()?;
+        if parser.pos != parser.tokens.len() {
+            bail!(
+                "Unexpected {:?} after end of expression",
+                parser.tokens[parser.pos]
+            );
+        }
+        Ok(expr)
+    }
+
+    fn value<F: FilterFields + ?Sized>(&self, facts: &F) -> Result<FilterValue> {
+        Ok(match self {
+            FilterExpr::Field(name) => facts.field(name).unwrap_or(FilterValue::Null),
+            FilterExpr::Literal(v) => v.clone(),
+            other => FilterValue::Bool(other.matches(facts)?),
+        })
+    }
+
+    /// Evaluate the expression for one file (or other [`FilterFields`] source).
+    pub fn matches<F: FilterFields + ?Sized>(&self, facts: &F) -> Result<bool> {
+        Ok(match self {
+            FilterExpr::Not(e) => !e.matches(facts)?,
+            FilterExpr::And(a, b) => a.matches(facts)? && b.matches(facts)?,
+            FilterExpr::Or(a, b) => a.matches(facts)? || b.matches(facts)?,
+            FilterExpr::Cmp(lhs, op, rhs) => {
+                let (l, r) = (lhs.value(facts)?, rhs.value(facts)?);
+                match op {
+                    CmpOp::Eq => l == r,
+                    CmpOp::Ne => l != r,
+                    CmpOp::Contains => {
+                        !matches!(l, FilterValue::Null)
+                            && !matches!(r, FilterValue::Null)
+                            && l.text().contains(&r.text())
+                    }
+                    _ => {
+                        let ordering = match (&l, &r) {
+                            (FilterValue::Num(a), FilterValue::Num(b)) => a.partial_cmp(b),
+                            (FilterValue::Str(a), FilterValue::Str(b)) => Some(a.cmp(b)),
+                            (FilterValue::Null, _) | (_, FilterValue::Null) => None,
+                            _ => bail!("Cannot order {} and {}", l, r),
+                        };
+                        match ordering {
+                            None => false,
+                            Some(o) => match op {
+                                CmpOp::Lt => o.is_lt(),
+                                CmpOp::Le => o.is_le(),
+                                CmpOp::Gt => o.is_gt(),
+                                _ => o.is_ge(),
+                            },
+                        }
+                    }
+                }
+            }
+            FilterExpr::Field(_) | FilterExpr::Literal(_) => self.value(facts)?.truthy(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts() -> FileFacts {
+        FileFacts {
+            path: "a.jpg".into(),
+            trust: "untrusted".into(),
+            chain_depth: 3,
+            valid: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_expressions() {
+        let f = facts();
+        let eval = |s: &str| FilterExpr::parse(s).unwrap().matches(&f).unwrap();
+        assert!(eval(r#"trust == "untrusted" && chain_depth > 2"#));
+        assert!(!eval("chain_depth <= 2"));
+        assert!(eval("valid && !has_timestamp"));
+        assert!(eval(
+            "(trust == 'trusted' || chain_depth >= 3) && path != \"b.jpg\""
+        ));
+        assert!(!eval("issuer == \"x\""));
+        assert!(!eval("issuer > \"a\""));
+        assert!(eval("path contains \".jp\" && !(issuer contains \"\")"));
+        assert!(FilterExpr::parse("softwareAgent contains 'x'").is_err());
+        assert!(FilterExpr::parse_open("softwareAgent.name contains 'x'").is_ok());
+    }
+
+    #[test]
+    fn test_filter_parse_errors() {
+        assert!(FilterExpr::parse("nope == 1").is_err());
+        assert!(FilterExpr::parse("trust ==").is_err());
+        assert!(FilterExpr::parse("(valid").is_err());
+        assert!(FilterExpr::parse("valid valid").is_err());
+        assert!(FilterExpr::parse("trust == \"x").is_err());
+    }
+
+    #[test]
+    fn test_chain_depth_handles_cycles() {
+        let crjson = serde_json::json!({
+            "manifests": [
+                { "label": "urn:c2pa:a", "assertions": { "c2pa.ingredient.v3": { "activeManifest": { "url": "self#jumbf=/c2pa/urn:c2pa:b" } } } },
+                { "label": "urn:c2pa:b", "assertions": { "c2pa.ingredient.v3": { "activeManifest": "urn:c2pa:a" } } }
+            ]
+        });
+        // a -> b is one link; b's reference back to a is a cycle and does not extend the chain
+        assert_eq!(chain_depth(&crjson, "urn:c2pa:a"), 1);
+    }
+}
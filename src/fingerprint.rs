@@ -0,0 +1,145 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Hash-only fingerprints of an asset's Content Credentials, for matching provenance across
+//! organizations without sharing content metadata.
+//!
+//! A fingerprint holds the asset's SHA-256, the label of every manifest in its store, and the
+//! SHA-256 of each manifest's signing certificate. Titles, authors, assertions, thumbnails, and
+//! file paths are left out.
+
+use crate::error::{format_err, Result};
+use crate::hashing::{hash_bytes, hash_file, HashAlgorithm};
+use crate::{extract_crjson_manifest_with_settings, Settings};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Fingerprint of one manifest in the store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestFingerprint {
+    pub label: String,
+    pub is_active: bool,
+    /// SHA-256 (lowercase hex) of the DER signing certificate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer_cert_sha256: Option<String>,
+    /// Set when the claim signature could not be read from the manifest store.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Fingerprint of one asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetFingerprint {
+    /// SHA-256 (lowercase hex) of the whole asset file.
+    pub asset_sha256: String,
+    pub active_manifest: String,
+    /// Every manifest in the store, in crJSON order.
+    pub manifests: Vec<ManifestFingerprint>,
+}
+
+impl AssetFingerprint {
+    /// Build a fingerprint from an asset hash, its extracted crJSON, and its raw manifest store.
+    pub fn from_parts(
+        asset_sha256: String,
+        crjson: &serde_json::Value,
+        active_label: &str,
+        store: Result<&[u8]>,
+    ) -> Self {
+        let manifests = crjson
+            .get("manifests")
+            .and_then(|m| m.as_array())
+            .map(|arr| arr.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|m| m.get("label")?.as_str())
+            .map(|label| {
+                let signer = store
+                    .as_ref()
+                    .map_err(|e| format_err!("{}", e))
+                    .and_then(|s| crate::cose::manifest_cose_info(s, label))
+                    .and_then(|info| {
+                        info.certificate_chain
+                            .first()
+                            .map(|der| hash_bytes(der, HashAlgorithm::Sha256))
+                            .ok_or_else(|| format_err!("Claim signature has no x5chain"))
+                    });
+                let (signer_cert_sha256, error) = match signer {
+                    Ok(fingerprint) => (Some(fingerprint), None),
+                    Err(e) => (None, Some(e.to_string())),
+                };
+                ManifestFingerprint {
+                    label: label.to_string(),
+                    is_active: label == active_label,
+                    signer_cert_sha256,
+                    error,
+                }
+            })
+            .collect();
+        Self {
+            asset_sha256,
+            active_manifest: active_label.to_string(),
+            manifests,
+        }
+    }
+}
+
+/// Fingerprint the asset at `path`. Fails when the asset has no readable manifest store.
+pub fn fingerprint_asset<P: AsRef<Path>>(path: P, settings: &Settings) -> Result<AssetFingerprint> {
+    let path = path.as_ref();
+    let extraction = extract_crjson_manifest_with_settings(path, settings)?;
+    let asset_sha256 = hash_file(path, HashAlgorithm::Sha256)?;
+    let store = crate::jumbf::load_manifest_store(path);
+    Ok(AssetFingerprint::from_parts(
+        asset_sha256,
+        &extraction.manifest_value,
+        &extraction.active_label,
+        store.as_deref().map_err(|e| format_err!("{}", e)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fingerprint_leaves_out_metadata() {
+        let crjson = json!({
+            "manifests": [
+                {"label": "urn:c2pa:ingredient", "title": "Holiday.jpg"},
+                {
+                    "label": "urn:c2pa:active",
+                    "title": "Secret draft.jpg",
+                    "assertions": {"stds.schema-org.CreativeWork": {"author": [{"name": "A. Person"}]}}
+                }
+            ]
+        });
+        let fingerprint = AssetFingerprint::from_parts(
+            "ab".repeat(32),
+            &crjson,
+            "urn:c2pa:active",
+            Err(format_err!("no store")),
+        );
+        assert_eq!(fingerprint.manifests.len(), 2);
+        assert!(fingerprint.manifests[1].is_active);
+        assert!(fingerprint.manifests[0].signer_cert_sha256.is_none());
+        assert!(fingerprint.manifests[0].error.is_some());
+
+        let text = serde_json::to_string(&fingerprint).unwrap();
+        assert!(text.contains("\"assetSha256\""));
+        for private in ["Holiday", "Secret draft", "A. Person", "title", "author"] {
+            assert!(!text.contains(private), "{private} leaked: {text}");
+        }
+    }
+}
@@ -0,0 +1,280 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Asset formats supported by c2pa-rs, grouped by media kind.
+
+use crate::error::{bail, Context, CrtoolError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Broad media category of an asset format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Image,
+    Video,
+    Audio,
+    Document,
+}
+
+impl MediaKind {
+    /// All kinds, in display order.
+    pub const ALL: [MediaKind; 4] = [
+        MediaKind::Image,
+        MediaKind::Video,
+        MediaKind::Audio,
+        MediaKind::Document,
+    ];
+
+    /// Lowercase singular name (e.g. "image").
+    pub fn name(&self) -> &'static str {
+        match self {
+            MediaKind::Image => "image",
+            MediaKind::Video => "video",
+            MediaKind::Audio => "audio",
+            MediaKind::Document => "document",
+        }
+    }
+
+    /// Plural label for UI (e.g. file dialog filters).
+    pub fn label(&self) -> &'static str {
+        match self {
+            MediaKind::Image => "Images",
+            MediaKind::Video => "Video",
+            MediaKind::Audio => "Audio",
+            MediaKind::Document => "Documents",
+        }
+    }
+}
+
+impl std::fmt::Display for MediaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for MediaKind {
+    type Err = CrtoolError;
+
+    /// Accepts singular or plural names, case-insensitive ("image", "Images", "docs").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "image" | "images" => Ok(MediaKind::Image),
+            "video" | "videos" => Ok(MediaKind::Video),
+            "audio" | "audios" => Ok(MediaKind::Audio),
+            "document" | "documents" | "doc" | "docs" => Ok(MediaKind::Document),
+            other => bail!(
+                "Unknown media kind '{}'. Expected one of: image, video, audio, document",
+                other
+            ),
+        }
+    }
+}
+
+/// One supported asset format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetFormat {
+    pub kind: MediaKind,
+    /// Lowercase file extension without the dot.
+    pub extension: &'static str,
+    /// MIME type as understood by c2pa-rs.
+    pub mime: &'static str,
+    /// Whether crTool can decode the format to render a thumbnail.
+    pub can_thumbnail: bool,
+    /// Whether c2pa-rs can embed (sign) a manifest into the format, not just read one.
+    pub can_sign: bool,
+}
+
+impl AssetFormat {
+    /// Whether the format is ISO BMFF based (MP4, MOV, M4A, HEIF, AVIF), whose hard binding
+    /// (`c2pa.hash.bmff`) is checked against the box structure of the asset itself.
+    pub fn is_bmff(&self) -> bool {
+        matches!(
+            self.mime,
+            "video/mp4"
+                | "video/quicktime"
+                | "audio/mp4"
+                | "image/heic"
+                | "image/heif"
+                | "image/avif"
+        )
+    }
+}
+
+const fn format(
+    kind: MediaKind,
+    extension: &'static str,
+    mime: &'static str,
+    can_thumbnail: bool,
+    can_sign: bool,
+) -> AssetFormat {
+    AssetFormat {
+        kind,
+        extension,
+        mime,
+        can_thumbnail,
+        can_sign,
+    }
+}
+
+/// Asset formats supported by c2pa-rs for reading/embedding C2PA manifests.
+/// Matches the formats listed in c2pa-rs [supported-formats](https://github.com/contentauth/c2pa-rs/blob/main/docs/supported-formats.md).
+pub const SUPPORTED_ASSET_FORMATS: &[AssetFormat] = &[
+    format(MediaKind::Video, "avi", "video/msvideo", false, true),
+    format(MediaKind::Image, "avif", "image/avif", false, true),
+    format(
+        MediaKind::Document,
+        "c2pa",
+        "application/c2pa",
+        false,
+        false,
+    ),
+    format(MediaKind::Image, "dng", "image/x-adobe-dng", false, true),
+    format(MediaKind::Image, "gif", "image/gif", true, true),
+    format(MediaKind::Image, "heic", "image/heic", false, true),
+    format(MediaKind::Image, "heif", "image/heif", false, true),
+    format(MediaKind::Image, "jpg", "image/jpeg", true, true),
+    format(MediaKind::Image, "jpeg", "image/jpeg", true, true),
+    format(MediaKind::Audio, "m4a", "audio/mp4", false, true),
+    format(MediaKind::Video, "mov", "video/quicktime", false, true),
+    format(MediaKind::Audio, "mp3", "audio/mpeg", false, true),
+    format(MediaKind::Video, "mp4", "video/mp4", false, true),
+    format(MediaKind::Document, "pdf", "application/pdf", false, false),
+    format(MediaKind::Image, "png", "image/png", true, true),
+    format(MediaKind::Image, "svg", "image/svg+xml", false, true),
+    format(MediaKind::Image, "tif", "image/tiff", true, true),
+    format(MediaKind::Image, "tiff", "image/tiff", true, true),
+    format(MediaKind::Audio, "wav", "audio/wav", false, true),
+    format(MediaKind::Image, "webp", "image/webp", true, true),
+];
+
+/// All supported file extensions, in table order.
+pub fn supported_asset_extensions() -> Vec<&'static str> {
+    SUPPORTED_ASSET_FORMATS
+        .iter()
+        .map(|f| f.extension)
+        .collect()
+}
+
+/// Supported file extensions of the given media kind.
+pub fn extensions_for_kind(kind: MediaKind) -> Vec<&'static str> {
+    SUPPORTED_ASSET_FORMATS
+        .iter()
+        .filter(|f| f.kind == kind)
+        .map(|f| f.extension)
+        .collect()
+}
+
+/// Look up a format by extension (case-insensitive, with or without a leading dot).
+pub fn asset_format_for_extension(extension: &str) -> Option<&'static AssetFormat> {
+    let ext = extension.trim_start_matches('.').to_lowercase();
+    SUPPORTED_ASSET_FORMATS.iter().find(|f| f.extension == ext)
+}
+
+/// Look up a format by extension or MIME type (case-insensitive), e.g. `"jpg"` or `"image/jpeg"`.
+pub fn asset_format_for_type(format: &str) -> Option<&'static AssetFormat> {
+    let format = format.trim();
+    asset_format_for_extension(format).or_else(|| {
+        SUPPORTED_ASSET_FORMATS
+            .iter()
+            .find(|f| f.mime.eq_ignore_ascii_case(format))
+    })
+}
+
+/// Look up the format of a file path from its extension.
+pub fn asset_format_for_path<P: AsRef<Path>>(path: P) -> Option<&'static AssetFormat> {
+    path.as_ref()
+        .extension()
+        .and_then(|e| e.to_str())
+        .and_then(asset_format_for_extension)
+}
+
+/// Every supported asset under `dir`, recursively, for which `filter` returns true. Symlinked
+/// directories are not followed. Paths are returned sorted.
+pub fn walk_assets<P, F>(dir: P, mut filter: F) -> Result<Vec<PathBuf>>
+where
+    P: AsRef<Path>,
+    F: FnMut(&Path, &AssetFormat) -> bool,
+{
+    let mut assets = Vec::new();
+    let mut pending = vec![dir.as_ref().to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory {}", dir.display()))?;
+        for entry in entries {
+            let entry = entry.with_context(|| format!("Failed to read {}", dir.display()))?;
+            let path = entry.path();
+            let file_type = entry
+                .file_type()
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if let Some(format) = asset_format_for_path(&path) {
+                if path.is_file() && filter(&path, format) {
+                    assets.push(path);
+                }
+            }
+        }
+    }
+    assets.sort();
+    Ok(assets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lookup_and_kinds() {
+        let jpg = asset_format_for_path("photo.JPG").unwrap();
+        assert_eq!(jpg.kind, MediaKind::Image);
+        assert_eq!(jpg.mime, "image/jpeg");
+        assert!(asset_format_for_extension(".mp4").is_some());
+        assert!(asset_format_for_path("notes.txt").is_none());
+        assert!(asset_format_for_path("clip.mp4").unwrap().is_bmff());
+        assert!(asset_format_for_path("photo.avif").unwrap().is_bmff());
+        assert!(!jpg.is_bmff());
+        assert_eq!(asset_format_for_type("IMAGE/PNG").unwrap().extension, "png");
+        assert_eq!(asset_format_for_type(" webp ").unwrap().mime, "image/webp");
+
+        assert!(extensions_for_kind(MediaKind::Audio).contains(&"wav"));
+        assert!(!extensions_for_kind(MediaKind::Image).contains(&"mp4"));
+        assert_eq!("Images".parse::<MediaKind>().unwrap(), MediaKind::Image);
+        assert!("spreadsheet".parse::<MediaKind>().is_err());
+    }
+
+    #[test]
+    fn test_walk_assets_recurses_and_filters() {
+        let root = std::env::temp_dir().join(format!("crtool-walk-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("album/raw")).unwrap();
+        for file in ["a.jpg", "notes.txt", "album/b.PNG", "album/raw/c.mp4"] {
+            std::fs::write(root.join(file), b"").unwrap();
+        }
+
+        let all = walk_assets(&root, |_, _| true).unwrap();
+        assert_eq!(
+            all,
+            vec![
+                root.join("a.jpg"),
+                root.join("album/b.PNG"),
+                root.join("album/raw/c.mp4")
+            ]
+        );
+        let images = walk_assets(&root, |_, f| f.kind == MediaKind::Image).unwrap();
+        assert_eq!(images.len(), 2);
+        assert!(walk_assets(root.join("missing"), |_, _| true).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,342 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Machine-readable exports of a manifest store's provenance graph: Graphviz DOT, GraphML (for
+//! graph databases and tools such as Gephi or Neo4j), and JSON-LD mapped to W3C PROV-O and
+//! schema.org (for knowledge-graph pipelines).
+//!
+//! The graph itself is [`crate::provenance::ProvenanceGraph`], re-exported here.
+
+use crate::error::{bail, CrtoolError};
+pub use crate::provenance::{GraphEdge, GraphNode, GraphNodeKind, ProvenanceGraph};
+use serde_json::{json, Value as JsonValue};
+
+/// Export format for [`ProvenanceGraph::render`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphFormat {
+    Dot,
+    GraphMl,
+    JsonLd,
+}
+
+impl GraphFormat {
+    pub const ALL: [GraphFormat; 3] = [GraphFormat::Dot, GraphFormat::GraphMl, GraphFormat::JsonLd];
+
+    /// Lowercase name, as accepted by `FromStr`.
+    pub fn name(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::GraphMl => "graphml",
+            GraphFormat::JsonLd => "jsonld",
+        }
+    }
+
+    /// File extension without the dot.
+    pub fn extension(self) -> &'static str {
+        self.name()
+    }
+
+    /// Human-readable label for menus and file dialogs.
+    pub fn label(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "Graphviz DOT",
+            GraphFormat::GraphMl => "GraphML",
+            GraphFormat::JsonLd => "JSON-LD (PROV-O)",
+        }
+    }
+}
+
+impl std::fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for GraphFormat {
+    type Err = CrtoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(GraphFormat::Dot),
+            "graphml" => Ok(GraphFormat::GraphMl),
+            "jsonld" | "json-ld" => Ok(GraphFormat::JsonLd),
+            other => bail!(
+                "Unknown graph format '{}'. Expected one of: dot, graphml, jsonld",
+                other
+            ),
+        }
+    }
+}
+
+impl ProvenanceGraph {
+    /// Serialize the graph in `format`.
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::GraphMl => self.to_graphml(),
+            GraphFormat::JsonLd => {
+                serde_json::to_string_pretty(&self.to_jsonld()).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Graphviz DOT: manifests are boxes (bold for the active one), manifest-less ingredients
+    /// ellipses, and missing manifests dashed boxes. Edges are labelled with the relationship.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph provenance {\n  rankdir=LR;\n");
+        for node in &self.nodes {
+            let label = match &node.title {
+                Some(title) if node.kind == GraphNodeKind::Ingredient => title.clone(),
+                Some(title) => format!("{}\n{}", title, node.id),
+                None => node.id.clone(),
+            };
+            let style = match node.kind {
+                GraphNodeKind::Manifest if node.active => "shape=box, style=bold",
+                GraphNodeKind::Manifest => "shape=box",
+                GraphNodeKind::MissingManifest => "shape=box, style=dashed",
+                GraphNodeKind::Ingredient => "shape=ellipse",
+            };
+            out.push_str(&format!(
+                "  \"{}\" [label=\"{}\", {}];\n",
+                dot_escape(&node.id),
+                dot_escape(&label),
+                style
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\"",
+                dot_escape(&edge.source),
+                dot_escape(&edge.target)
+            ));
+            if let Some(rel) = &edge.relationship {
+                out.push_str(&format!(" [label=\"{}\"]", dot_escape(rel)));
+            }
+            out.push_str(";\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// GraphML with node attributes `kind`, `title`, `actions` (comma-separated), `active`, and
+    /// edge attributes `relationship` and `ingredientTitle`.
+    pub fn to_graphml(&self) -> String {
+        const KEYS: [(&str, &str, &str); 6] = [
+            ("kind", "node", "string"),
+            ("title", "node", "string"),
+            ("actions", "node", "string"),
+            ("active", "node", "boolean"),
+            ("relationship", "edge", "string"),
+            ("ingredientTitle", "edge", "string"),
+        ];
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        );
+        for (id, domain, ty) in KEYS {
+            out.push_str(&format!(
+                "  <key id=\"{id}\" for=\"{domain}\" attr.name=\"{id}\" attr.type=\"{ty}\"/>\n"
+            ));
+        }
+        out.push_str("  <graph id=\"provenance\" edgedefault=\"directed\">\n");
+        let data = |out: &mut String, key: &str, value: &str| {
+            out.push_str(&format!(
+                "      <data key=\"{}\">{}</data>\n",
+                key,
+                xml_escape(value)
+            ));
+        };
+        for node in &self.nodes {
+            out.push_str(&format!("    <node id=\"{}\">\n", xml_escape(&node.id)));
+            data(&mut out, "kind", node.kind.name());
+            if let Some(title) = &node.title {
+                data(&mut out, "title", title);
+            }
+            if !node.actions.is_empty() {
+                data(&mut out, "actions", &node.actions.join(","));
+            }
+            data(
+                &mut out,
+                "active",
+                if node.active { "true" } else { "false" },
+            );
+            out.push_str("    </node>\n");
+        }
+        for (i, edge) in self.edges.iter().enumerate() {
+            out.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+                i,
+                xml_escape(&edge.source),
+                xml_escape(&edge.target)
+            ));
+            if let Some(rel) = &edge.relationship {
+                data(&mut out, "relationship", rel);
+            }
+            if let Some(title) = &edge.title {
+                data(&mut out, "ingredientTitle", title);
+            }
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// JSON-LD `@graph` in which every node is a `prov:Entity` / `schema:CreativeWork`.
+    /// Actions become `prov:wasGeneratedBy` activities, and each ingredient edge becomes
+    /// `prov:wasDerivedFrom` plus a `prov:qualifiedDerivation` whose `prov:hadRole` is the
+    /// ingredient relationship.
+    pub fn to_jsonld(&self) -> JsonValue {
+        let nodes: Vec<JsonValue> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let kind = if node.active {
+                    "activeManifest"
+                } else {
+                    node.kind.name()
+                };
+                let mut entity = json!({
+                    "@id": node.id,
+                    "@type": ["prov:Entity", "schema:CreativeWork"],
+                    "schema:additionalType": kind,
+                });
+                let obj = entity.as_object_mut().expect("object literal");
+                if node.kind != GraphNodeKind::Ingredient {
+                    obj.insert("schema:identifier".to_string(), json!(node.id));
+                }
+                if let Some(title) = &node.title {
+                    obj.insert("schema:name".to_string(), json!(title));
+                }
+                if !node.actions.is_empty() {
+                    let activities: Vec<JsonValue> = node
+                        .actions
+                        .iter()
+                        .map(|a| json!({ "@type": "prov:Activity", "rdfs:label": a }))
+                        .collect();
+                    obj.insert("prov:wasGeneratedBy".to_string(), json!(activities));
+                }
+                let edges: Vec<&GraphEdge> =
+                    self.edges.iter().filter(|e| e.source == node.id).collect();
+                if !edges.is_empty() {
+                    let derived: Vec<JsonValue> =
+                        edges.iter().map(|e| json!({ "@id": e.target })).collect();
+                    let qualified: Vec<JsonValue> = edges
+                        .iter()
+                        .map(|e| {
+                            let mut d = json!({
+                                "@type": "prov:Derivation",
+                                "prov:entity": { "@id": e.target },
+                            });
+                            if let Some(rel) = &e.relationship {
+                                d["prov:hadRole"] = json!(rel);
+                            }
+                            d
+                        })
+                        .collect();
+                    obj.insert("prov:wasDerivedFrom".to_string(), json!(derived));
+                    obj.insert("prov:qualifiedDerivation".to_string(), json!(qualified));
+                }
+                entity
+            })
+            .collect();
+        json!({
+            "@context": {
+                "prov": "http://www.w3.org/ns/prov#",
+                "schema": "https://schema.org/",
+                "rdfs": "http://www.w3.org/2000/01/rdf-schema#"
+            },
+            "@graph": nodes
+        })
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crjson() -> JsonValue {
+        serde_json::json!({
+            "manifests": [
+                {
+                    "label": "urn:c2pa:a",
+                    "claim.v2": { "dc:title": "final.jpg" },
+                    "assertions": {
+                        "c2pa.actions.v2": { "actions": [{ "action": "c2pa.edited" }] },
+                        "c2pa.ingredient.v3": {
+                            "title": "source.jpg", "relationship": "parentOf",
+                            "activeManifest": { "url": "self#jumbf=/c2pa/urn:c2pa:b/c2pa.claim" }
+                        },
+                        "c2pa.ingredient.v3__1": { "title": "logo & \"mark\".png", "relationship": "componentOf" },
+                        "c2pa.ingredient.v3__2": { "title": "gone.jpg", "activeManifest": "urn:c2pa:z" }
+                    }
+                },
+                { "label": "urn:c2pa:b", "claim.v2": { "dc:title": "source.jpg" }, "assertions": {} }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let graph = ProvenanceGraph::from_crjson(&crjson(), Some("urn:c2pa:a"));
+
+        let dot = graph.render(GraphFormat::Dot);
+        assert!(dot.starts_with("digraph provenance {"));
+        assert!(dot.contains("\"urn:c2pa:a\" -> \"urn:c2pa:b\" [label=\"parentOf\"];"));
+        assert!(dot.contains("logo & \\\"mark\\\".png"));
+
+        let graphml = graph.render(GraphFormat::GraphMl);
+        assert!(graphml.contains("<edge id=\"e0\" source=\"urn:c2pa:a\" target=\"urn:c2pa:b\">"));
+        assert!(graphml.contains("logo &amp; &quot;mark&quot;.png"));
+        assert_eq!(graphml.matches("<node ").count(), 4);
+
+        let jsonld: JsonValue = serde_json::from_str(&graph.render(GraphFormat::JsonLd)).unwrap();
+        let active = &jsonld["@graph"][0];
+        assert_eq!(active["schema:name"], "final.jpg");
+        assert_eq!(active["schema:additionalType"], "activeManifest");
+        assert_eq!(active["prov:wasDerivedFrom"][0]["@id"], "urn:c2pa:b");
+        assert_eq!(
+            active["prov:qualifiedDerivation"][0]["prov:hadRole"],
+            "parentOf"
+        );
+        assert_eq!(
+            active["prov:wasGeneratedBy"][0]["rdfs:label"],
+            "c2pa.edited"
+        );
+    }
+
+    #[test]
+    fn test_graph_format_from_str() {
+        assert_eq!(
+            "JSON-LD".parse::<GraphFormat>().unwrap(),
+            GraphFormat::JsonLd
+        );
+        assert_eq!(
+            "graphml".parse::<GraphFormat>().unwrap(),
+            GraphFormat::GraphMl
+        );
+        assert!("svg".parse::<GraphFormat>().is_err());
+    }
+}
@@ -0,0 +1,232 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Manifest content search (`crTool grep`): find assertions, and the JSON objects inside them,
+//! that match a [`FilterExpr`] over their keys, e.g.
+//! `--assertion c2pa.actions --where 'softwareAgent contains "Photoshop"'`.
+
+use crate::error::{bail, Context, Result};
+use crate::filter::{FilterExpr, FilterFields, FilterValue};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+/// One assertion, or object inside an assertion, that matched a [`GrepQuery`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrepMatch {
+    /// Label of the manifest holding the assertion.
+    pub manifest: String,
+    /// Assertion label as stored (e.g. `c2pa.actions.v2`).
+    pub assertion: String,
+    /// JSON path of the matching object within the assertion (`$` for the assertion itself).
+    pub path: String,
+    /// The matching object.
+    pub value: JsonValue,
+}
+
+/// What to look for in a manifest store.
+#[derive(Debug, Clone, Default)]
+pub struct GrepQuery {
+    /// Only search assertions with this label, any version or instance (`c2pa.actions`
+    /// matches `c2pa.actions.v2` and `c2pa.actions__1`). All assertions when `None`.
+    pub assertion: Option<String>,
+    /// Objects must match this expression over their keys. Without one, each selected
+    /// assertion matches as a whole.
+    pub filter: Option<FilterExpr>,
+}
+
+impl GrepQuery {
+    /// Build a query from an assertion label and a `--where` expression.
+    pub fn new(assertion: Option<&str>, where_expr: Option<&str>) -> Result<Self> {
+        if assertion.is_none() && where_expr.is_none() {
+            bail!("Give an assertion label, a --where expression, or both");
+        }
+        Ok(Self {
+            assertion: assertion.map(str::to_string),
+            filter: where_expr
+                .map(FilterExpr::parse_open)
+                .transpose()
+                .context("Invalid --where expression")?,
+        })
+    }
+
+    /// Search every manifest of a crJSON manifest store.
+    pub fn search(&self, crjson: &JsonValue) -> Result<Vec<GrepMatch>> {
+        let mut matches = Vec::new();
+        let manifests = crjson.get("manifests").and_then(|m| m.as_array());
+        for manifest in manifests.into_iter().flatten() {
+            let label = manifest
+                .get("label")
+                .and_then(|l| l.as_str())
+                .unwrap_or_default();
+            let assertions = manifest.get("assertions").and_then(|a| a.as_object());
+            for (key, value) in assertions.into_iter().flatten() {
+                if self
+                    .assertion
+                    .as_deref()
+                    .is_some_and(|wanted| !assertion_label_matches(key, wanted))
+                {
+                    continue;
+                }
+                let mut found = |path: String, value: &JsonValue| {
+                    matches.push(GrepMatch {
+                        manifest: label.to_string(),
+                        assertion: key.clone(),
+                        path,
+                        value: value.clone(),
+                    })
+                };
+                match &self.filter {
+                    None => found("$".to_string(), value),
+                    Some(filter) => search_objects(filter, value, "$".to_string(), &mut found)?,
+                }
+            }
+        }
+        Ok(matches)
+    }
+}
+
+/// Whether assertion `key` is `label`, a versioned form of it (`label.vN`), or an instance of
+/// either (`__N`).
+pub fn assertion_label_matches(key: &str, label: &str) -> bool {
+    let all_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let base = match key.rsplit_once("__") {
+        Some((base, n)) if all_digits(n) => base,
+        _ => key,
+    };
+    base == label
+        || base
+            .strip_prefix(label)
+            .and_then(|rest| rest.strip_prefix(".v"))
+            .is_some_and(all_digits)
+}
+
+/// Visit every object in `value` (including `value` itself) and report those matching `filter`.
+fn search_objects(
+    filter: &FilterExpr,
+    value: &JsonValue,
+    path: String,
+    found: &mut impl FnMut(String, &JsonValue),
+) -> Result<()> {
+    match value {
+        JsonValue::Object(map) => {
+            if filter.matches(&JsonFields(value))? {
+                found(path.clone(), value);
+            }
+            for (k, v) in map {
+                search_objects(filter, v, format!("{path}.{k}"), found)?;
+            }
+        }
+        JsonValue::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                search_objects(filter, v, format!("{path}[{i}]"), found)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Fields of a JSON object for filter evaluation; dotted names address nested keys. Objects
+/// and arrays compare as their compact JSON text, so `contains` searches inside them.
+struct JsonFields<'a>(&'a JsonValue);
+
+impl FilterFields for JsonFields<'_> {
+    fn field(&self, name: &str) -> Option<FilterValue> {
+        let mut cur = self.0;
+        for key in name.split('.') {
+            cur = cur.get(key)?;
+        }
+        Some(match cur {
+            JsonValue::Null => FilterValue::Null,
+            JsonValue::Bool(b) => FilterValue::Bool(*b),
+            JsonValue::Number(n) => n.as_f64().map_or(FilterValue::Null, FilterValue::Num),
+            JsonValue::String(s) => FilterValue::Str(s.clone()),
+            other => FilterValue::Str(other.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store() -> JsonValue {
+        json!({
+            "manifests": [{
+                "label": "urn:c2pa:a",
+                "assertions": {
+                    "c2pa.actions.v2": { "actions": [
+                        { "action": "c2pa.created", "softwareAgent": { "name": "Adobe Photoshop 25.0" } },
+                        { "action": "c2pa.edited", "softwareAgent": "GIMP" }
+                    ] },
+                    "c2pa.actions.v2__1": { "actions": [ { "action": "c2pa.opened" } ] },
+                    "stds.schema-org.CreativeWork": { "author": [ { "name": "Photoshop Fan" } ] }
+                }
+            }]
+        })
+    }
+
+    #[test]
+    fn test_assertion_label_matches() {
+        assert!(assertion_label_matches("c2pa.actions.v2", "c2pa.actions"));
+        assert!(assertion_label_matches(
+            "c2pa.actions.v2__3",
+            "c2pa.actions"
+        ));
+        assert!(assertion_label_matches("c2pa.actions__1", "c2pa.actions"));
+        assert!(assertion_label_matches(
+            "c2pa.actions.v2",
+            "c2pa.actions.v2"
+        ));
+        assert!(!assertion_label_matches("c2pa.actions.v2", "c2pa.action"));
+        assert!(!assertion_label_matches("c2pa.actions.vx", "c2pa.actions"));
+    }
+
+    #[test]
+    fn test_grep_search() {
+        let crjson = store();
+        let query = GrepQuery::new(
+            Some("c2pa.actions"),
+            Some(r#"softwareAgent contains "Photoshop""#),
+        )
+        .unwrap();
+        let matches = query.search(&crjson).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].assertion, "c2pa.actions.v2");
+        assert_eq!(matches[0].path, "$.actions[0]");
+
+        // Nested keys, and every assertion when no label is given
+        let query = GrepQuery::new(None, Some("name contains 'Photoshop'")).unwrap();
+        let paths: Vec<String> = query
+            .search(&crjson)
+            .unwrap()
+            .into_iter()
+            .map(|m| format!("{} {}", m.assertion, m.path))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "c2pa.actions.v2 $.actions[0].softwareAgent",
+                "stds.schema-org.CreativeWork $.author[0]"
+            ]
+        );
+        let query = GrepQuery::new(None, Some("softwareAgent.name == 'GIMP'")).unwrap();
+        assert!(query.search(&crjson).unwrap().is_empty());
+
+        // A label alone matches each instance of the assertion
+        let query = GrepQuery::new(Some("c2pa.actions"), None).unwrap();
+        assert_eq!(query.search(&crjson).unwrap().len(), 2);
+        assert!(GrepQuery::new(None, None).is_err());
+    }
+}
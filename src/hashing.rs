@@ -0,0 +1,510 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! File and buffer hashing helpers (SHA-2 family) shared by reports and the CLI.
+//!
+//! Digests are computed by a process-wide [`HashBackend`]: the pure-Rust `sha2` crate (which
+//! uses the SHA-NI / ARMv8 SHA instructions when the CPU has them) or, in builds with the
+//! `ring` feature, ring's assembly implementations. By default the backend is picked from the
+//! CPU features found at runtime ([`auto_backend`]); [`set_hash_backend`] overrides it.
+
+use crate::error::{bail, format_err, Context, CrtoolError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Digest algorithms supported for asset and output hashing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// All supported algorithms, in increasing digest size.
+    pub const ALL: [HashAlgorithm; 3] = [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha384,
+        HashAlgorithm::Sha512,
+    ];
+
+    /// Lowercase algorithm name as used in reports (e.g. "sha256").
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha384 => "sha384",
+            HashAlgorithm::Sha512 => "sha512",
+        }
+    }
+}
+
+impl std::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for HashAlgorithm {
+    type Err = CrtoolError;
+
+    /// Parse "sha256", "SHA-384", etc.
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.replace('-', "");
+        Self::ALL
+            .into_iter()
+            .find(|a| a.name().eq_ignore_ascii_case(&normalized))
+            .ok_or_else(|| {
+                format_err!("Unknown hash algorithm '{s}' (expected sha256, sha384 or sha512)")
+            })
+    }
+}
+
+/// Implementation used to compute digests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashBackend {
+    /// The `sha2` crate; uses SHA-NI (x86_64) or the ARMv8 SHA extensions when present.
+    Sha2,
+    /// ring's assembly implementations (builds with the `ring` feature only).
+    Ring,
+}
+
+impl HashBackend {
+    /// All backends, whether or not this build includes them.
+    pub const ALL: [HashBackend; 2] = [HashBackend::Sha2, HashBackend::Ring];
+
+    /// Lowercase backend name (e.g. "sha2").
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashBackend::Sha2 => "sha2",
+            HashBackend::Ring => "ring",
+        }
+    }
+
+    /// Whether this build includes the backend.
+    pub fn is_available(&self) -> bool {
+        match self {
+            HashBackend::Sha2 => true,
+            HashBackend::Ring => cfg!(feature = "ring"),
+        }
+    }
+
+    /// Backends included in this build.
+    pub fn available() -> Vec<HashBackend> {
+        Self::ALL.into_iter().filter(|b| b.is_available()).collect()
+    }
+}
+
+impl std::fmt::Display for HashBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for HashBackend {
+    type Err = CrtoolError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|b| b.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format_err!("Unknown hash backend '{s}' (expected sha2 or ring)"))
+    }
+}
+
+/// SHA instruction set extensions detected on the running CPU.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashAcceleration {
+    /// x86_64 SHA extensions (SHA-1/SHA-256).
+    pub sha_ni: bool,
+    /// ARMv8 SHA-256 instructions.
+    pub arm_sha2: bool,
+    /// ARMv8.2 SHA-512 instructions.
+    pub arm_sha512: bool,
+}
+
+impl HashAcceleration {
+    /// Whether any SHA instructions are available.
+    pub fn any(&self) -> bool {
+        self.sha_ni || self.arm_sha2 || self.arm_sha512
+    }
+}
+
+impl std::fmt::Display for HashAcceleration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut found = Vec::new();
+        if self.sha_ni {
+            found.push("SHA-NI");
+        }
+        if self.arm_sha2 {
+            found.push("ARMv8 SHA2");
+        }
+        if self.arm_sha512 {
+            found.push("ARMv8 SHA512");
+        }
+        if found.is_empty() {
+            f.write_str("no SHA instructions")
+        } else {
+            f.write_str(&found.join(", "))
+        }
+    }
+}
+
+/// Detect the SHA instructions of the running CPU.
+pub fn detect_acceleration() -> HashAcceleration {
+    #[allow(unused_mut)]
+    let mut accel = HashAcceleration::default();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        accel.sha_ni = std::arch::is_x86_feature_detected!("sha");
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        accel.arm_sha2 = std::arch::is_aarch64_feature_detected!("sha2");
+        accel.arm_sha512 = std::arch::is_aarch64_feature_detected!("sha3");
+    }
+    accel
+}
+
+/// Backend to use when none was chosen: `sha2` when the CPU has SHA instructions (which it
+/// then uses directly), otherwise ring's vectorized assembly if built in, otherwise `sha2`.
+pub fn auto_backend() -> HashBackend {
+    if !detect_acceleration().any() && HashBackend::Ring.is_available() {
+        HashBackend::Ring
+    } else {
+        HashBackend::Sha2
+    }
+}
+
+static BACKEND: RwLock<Option<HashBackend>> = RwLock::new(None);
+
+/// Use `backend` for all later hashing in this process. Fails if this build does not include it.
+pub fn set_hash_backend(backend: HashBackend) -> Result<()> {
+    if !backend.is_available() {
+        bail!(
+            "Hash backend '{backend}' is not available in this build (rebuild with the `{backend}` feature)"
+        );
+    }
+    *BACKEND.write().unwrap_or_else(|e| e.into_inner()) = Some(backend);
+    Ok(())
+}
+
+/// The backend in use: the one set with [`set_hash_backend`], else [`auto_backend`].
+pub fn hash_backend() -> HashBackend {
+    let chosen = *BACKEND.read().unwrap_or_else(|e| e.into_inner());
+    chosen.unwrap_or_else(|| {
+        let backend = auto_backend();
+        *BACKEND.write().unwrap_or_else(|e| e.into_inner()) = Some(backend);
+        backend
+    })
+}
+
+/// Incremental digest computed with the current [`hash_backend`].
+enum Digester {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    #[cfg(feature = "ring")]
+    Ring(ring::digest::Context),
+}
+
+impl Digester {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        #[cfg(feature = "ring")]
+        if hash_backend() == HashBackend::Ring {
+            let alg = match algorithm {
+                HashAlgorithm::Sha256 => &ring::digest::SHA256,
+                HashAlgorithm::Sha384 => &ring::digest::SHA384,
+                HashAlgorithm::Sha512 => &ring::digest::SHA512,
+            };
+            return Digester::Ring(ring::digest::Context::new(alg));
+        }
+        match algorithm {
+            HashAlgorithm::Sha256 => Digester::Sha256(Sha256::new()),
+            HashAlgorithm::Sha384 => Digester::Sha384(Sha384::new()),
+            HashAlgorithm::Sha512 => Digester::Sha512(Sha512::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Digester::Sha256(h) => h.update(data),
+            Digester::Sha384(h) => h.update(data),
+            Digester::Sha512(h) => h.update(data),
+            #[cfg(feature = "ring")]
+            Digester::Ring(h) => h.update(data),
+        }
+    }
+
+    /// Hex digest.
+    fn finish(self) -> String {
+        match self {
+            Digester::Sha256(h) => to_hex(&h.finalize()),
+            Digester::Sha384(h) => to_hex(&h.finalize()),
+            Digester::Sha512(h) => to_hex(&h.finalize()),
+            #[cfg(feature = "ring")]
+            Digester::Ring(h) => to_hex(h.finish().as_ref()),
+        }
+    }
+}
+
+/// Lowercase hex encoding of a digest.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a hex digest as produced by [`to_hex`]. Returns `None` for invalid input.
+pub fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Hash everything readable from `reader` with each of the given algorithms in a single pass.
+/// Returns hex digests in the same order as `algorithms`.
+pub fn hash_reader_multi<R: Read>(
+    mut reader: R,
+    algorithms: &[HashAlgorithm],
+) -> Result<Vec<String>> {
+    let mut digesters: Vec<(HashAlgorithm, Digester)> = Vec::new();
+    for &alg in algorithms {
+        if !digesters.iter().any(|(a, _)| *a == alg) {
+            digesters.push((alg, Digester::new(alg)));
+        }
+    }
+
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .context("Failed to read data for hashing")?;
+        if n == 0 {
+            break;
+        }
+        for (_, h) in digesters.iter_mut() {
+            h.update(&buf[..n]);
+        }
+    }
+
+    let digests: Vec<(HashAlgorithm, String)> = digesters
+        .into_iter()
+        .map(|(alg, h)| (alg, h.finish()))
+        .collect();
+    Ok(algorithms
+        .iter()
+        .map(|alg| {
+            digests
+                .iter()
+                .find(|(a, _)| a == alg)
+                .map(|(_, d)| d.clone())
+                .unwrap_or_default()
+        })
+        .collect())
+}
+
+/// Hash a byte slice and return the hex digest.
+pub fn hash_bytes(data: &[u8], algorithm: HashAlgorithm) -> String {
+    let mut h = Digester::new(algorithm);
+    h.update(data);
+    h.finish()
+}
+
+/// Hash a file on disk and return the hex digest.
+pub fn hash_file<P: AsRef<Path>>(path: P, algorithm: HashAlgorithm) -> Result<String> {
+    let path = path.as_ref();
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open file for hashing: {:?}", path))?;
+    let mut digests = hash_reader_multi(std::io::BufReader::new(file), &[algorithm])?;
+    Ok(digests.remove(0))
+}
+
+/// Stream wrapper that computes the digest (SHA-256 by default) of the whole stream while another
+/// consumer (such as the c2pa `Reader`) reads it. A read that continues the hashed prefix extends
+/// it; reads elsewhere pass through unhashed. [`HashingReader::finish`] then reads and hashes
+/// only what the consumer left uncovered, so a stream read front to back is read from disk once.
+pub struct HashingReader<R> {
+    inner: R,
+    /// Current position in `inner`.
+    pos: u64,
+    /// Length of the prefix hashed so far.
+    hashed: u64,
+    hasher: Digester,
+}
+
+impl<R: Read + Seek> HashingReader<R> {
+    /// Wrap `inner`, which must be positioned at its start, hashing with SHA-256.
+    pub fn new(inner: R) -> Self {
+        Self::with_algorithm(inner, HashAlgorithm::Sha256)
+    }
+
+    /// Wrap `inner`, which must be positioned at its start, hashing with `algorithm`.
+    pub fn with_algorithm(inner: R, algorithm: HashAlgorithm) -> Self {
+        Self {
+            inner,
+            pos: 0,
+            hashed: 0,
+            hasher: Digester::new(algorithm),
+        }
+    }
+
+    /// Number of bytes hashed so far (the prefix covered by the consumer's reads).
+    pub fn hashed_len(&self) -> u64 {
+        self.hashed
+    }
+
+    /// Hash the rest of the stream and return the inner stream with the hex digest.
+    pub fn finish(mut self) -> Result<(R, String)> {
+        self.inner
+            .seek(SeekFrom::Start(self.hashed))
+            .context("Failed to seek for hashing")?;
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let n = self
+                .inner
+                .read(&mut buf)
+                .context("Failed to read data for hashing")?;
+            if n == 0 {
+                break;
+            }
+            self.hasher.update(&buf[..n]);
+        }
+        Ok((self.inner, self.hasher.finish()))
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        let end = self.pos + n as u64;
+        if self.pos <= self.hashed && end > self.hashed {
+            let start = (self.hashed - self.pos) as usize;
+            self.hasher.update(&buf[start..n]);
+            self.hashed = end;
+        }
+        self.pos = end;
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for HashingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_bytes_known_vectors() {
+        assert_eq!(
+            hash_bytes(b"abc", HashAlgorithm::Sha256),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let multi = hash_reader_multi(&b"abc"[..], &HashAlgorithm::ALL).unwrap();
+        assert_eq!(multi[0], hash_bytes(b"abc", HashAlgorithm::Sha256));
+        assert_eq!(multi[1], hash_bytes(b"abc", HashAlgorithm::Sha384));
+        assert_eq!(multi[2], hash_bytes(b"abc", HashAlgorithm::Sha512));
+    }
+
+    #[test]
+    fn test_hashing_reader_covers_skipped_ranges() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let expected = hash_bytes(&data, HashAlgorithm::Sha256);
+
+        // Read front to back: everything is hashed during the reads
+        let mut reader = HashingReader::new(std::io::Cursor::new(data.clone()));
+        std::io::copy(&mut reader, &mut std::io::sink()).unwrap();
+        assert_eq!(reader.hashed_len(), data.len() as u64);
+        assert_eq!(reader.finish().unwrap().1, expected);
+
+        // Out-of-order reads and re-reads: finish fills the gaps
+        let mut reader = HashingReader::new(std::io::Cursor::new(data.clone()));
+        let mut buf = [0u8; 1000];
+        reader.read_exact(&mut buf).unwrap();
+        reader.seek(SeekFrom::Start(500)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        reader.seek(SeekFrom::Start(50_000)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(reader.hashed_len(), 1500);
+        assert_eq!(reader.finish().unwrap().1, expected);
+
+        let mut reader =
+            HashingReader::with_algorithm(std::io::Cursor::new(&data), HashAlgorithm::Sha512);
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(
+            reader.finish().unwrap().1,
+            hash_bytes(&data, HashAlgorithm::Sha512)
+        );
+    }
+
+    #[test]
+    fn test_from_hex_round_trip() {
+        let bytes = [0x00, 0x7f, 0xab, 0xff];
+        assert_eq!(from_hex(&to_hex(&bytes)).unwrap(), bytes);
+        assert!(from_hex("abc").is_none());
+        assert!(from_hex("zz").is_none());
+    }
+
+    #[test]
+    fn test_hash_algorithm_from_str() {
+        assert_eq!(
+            "sha384".parse::<HashAlgorithm>().unwrap(),
+            HashAlgorithm::Sha384
+        );
+        assert_eq!(
+            "SHA-512".parse::<HashAlgorithm>().unwrap(),
+            HashAlgorithm::Sha512
+        );
+        assert_eq!(HashAlgorithm::default(), HashAlgorithm::Sha256);
+        assert!("md5".parse::<HashAlgorithm>().is_err());
+    }
+
+    #[test]
+    fn test_hash_backends() {
+        assert!(HashBackend::Sha2.is_available());
+        assert_eq!(HashBackend::Ring.is_available(), cfg!(feature = "ring"));
+        assert_eq!("RING".parse::<HashBackend>().unwrap(), HashBackend::Ring);
+        assert!("md5".parse::<HashBackend>().is_err());
+        assert!(auto_backend().is_available());
+        assert_eq!(
+            set_hash_backend(HashBackend::Ring).is_ok(),
+            cfg!(feature = "ring")
+        );
+
+        // Every available backend produces the same digests
+        for backend in HashBackend::available() {
+            set_hash_backend(backend).unwrap();
+            assert_eq!(hash_backend(), backend);
+            assert_eq!(
+                hash_bytes(b"abc", HashAlgorithm::Sha256),
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            );
+            let multi = hash_reader_multi(&b"abc"[..], &HashAlgorithm::ALL).unwrap();
+            assert!(multi[2].starts_with("ddaf35a193617aba"));
+        }
+        set_hash_backend(auto_backend()).unwrap();
+    }
+}
@@ -0,0 +1,391 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! CAWG identity assertions (`cawg.identity`) added while signing (requires the `signing`
+//! feature).
+//!
+//! A manifest definition may carry an `identity` block next to its assertions:
+//!
+//! ```json
+//! "identity": {
+//!   "credential_type": "ica",
+//!   "issuer": "did:web:identity.example.com",
+//!   "cert": "issuer.pem", "key": "issuer.key",
+//!   "verified_identities": [{
+//!     "type": "cawg.social_media", "username": "jdoe", "uri": "https://social.example/jdoe",
+//!     "provider": { "id": "https://social.example", "name": "Example Social" }
+//!   }],
+//!   "referenced_assertions": ["c2pa.actions.v2"],
+//!   "roles": ["cawg.creator"]
+//! }
+//! ```
+//!
+//! `x509` credentials sign the identity assertion with an X.509 certificate (the signing
+//! certificate unless `cert`/`key` are given). `ica` credentials embed an identity claims
+//! aggregation credential: a W3C verifiable credential from `issuer` listing the
+//! `verified_identities`, signed with the issuer's `cert`/`key`. crTool acts as the issuer, so
+//! validators report the issuer as unverifiable unless they trust it; that is enough for
+//! test assets.
+
+use crate::error::{bail, format_err, Context, Result};
+use crate::signing::SignOptions;
+use c2pa::crypto::raw_signature::{signer_from_cert_chain_and_private_key, RawSigner};
+use c2pa::identity::builder::{
+    CredentialHolder, IdentityAssertionBuilder, IdentityAssertionSigner, IdentityBuilderError,
+};
+use c2pa::identity::x509::X509CredentialHolder;
+use c2pa::identity::SignerPayload;
+use c2pa::SigningAlg;
+use ciborium::Value as CborValue;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Key of the identity block in a manifest definition.
+pub const IDENTITY_KEY: &str = "identity";
+
+/// `sig_type` of identity claims aggregation credentials.
+const ICA_SIG_TYPE: &str = "cawg.identity_claims_aggregation";
+
+/// How the identity assertion is signed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum IdentityCredentialType {
+    /// X.509 certificate (`cawg.x509.cose`).
+    #[default]
+    #[serde(rename = "x509", alias = "cawg.x509.cose")]
+    X509,
+    /// Identity claims aggregation credential (`cawg.identity_claims_aggregation`).
+    #[serde(rename = "ica", alias = "cawg.identity_claims_aggregation")]
+    Ica,
+}
+
+/// The `identity` block of a manifest definition.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IdentityConfig {
+    #[serde(default)]
+    pub credential_type: IdentityCredentialType,
+    /// PEM certificate chain of the credential (`x509`: defaults to the signing certificate;
+    /// `ica`: the issuer's, required). Relative paths are resolved like file ingredients.
+    pub cert: Option<PathBuf>,
+    /// PEM (PKCS#8) private key for `cert`.
+    pub key: Option<PathBuf>,
+    /// Signing algorithm of `key`; detected from `cert` when absent.
+    pub alg: Option<String>,
+    /// Issuer of an `ica` credential, usually a `did:web:` DID.
+    pub issuer: Option<String>,
+    /// Subject (`credentialSubject.id`) of an `ica` credential.
+    pub subject: Option<String>,
+    /// Identities the `ica` issuer vouches for: objects with a `type` (e.g.
+    /// `cawg.social_media`, `cawg.document_verification`), a `provider` and optionally
+    /// `name`, `username`, `address`, `uri` and `verifiedAt` (default: now).
+    #[serde(default)]
+    pub verified_identities: Vec<JsonValue>,
+    /// Labels of assertions the identity assertion binds to, besides the hard binding.
+    #[serde(default)]
+    pub referenced_assertions: Vec<String>,
+    /// Roles of the named actor, e.g. `cawg.creator`.
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl IdentityConfig {
+    /// The identity block of a manifest definition, if any.
+    pub fn from_manifest(manifest: &JsonValue) -> Result<Option<Self>> {
+        let Some(block) = manifest.get(IDENTITY_KEY) else {
+            return Ok(None);
+        };
+        let config: IdentityConfig =
+            serde_json::from_value(block.clone()).context("Invalid identity block in manifest")?;
+        config.check()?;
+        Ok(Some(config))
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.cert.is_some() != self.key.is_some() {
+            bail!("identity: cert and key must be given together");
+        }
+        if self.credential_type == IdentityCredentialType::Ica {
+            if self.issuer.is_none() {
+                bail!("identity: an ica credential needs an issuer");
+            }
+            if self.cert.is_none() {
+                bail!("identity: an ica credential needs the issuer's cert and key");
+            }
+            if self.verified_identities.is_empty() {
+                bail!("identity: an ica credential needs verified_identities");
+            }
+            for (i, identity) in self.verified_identities.iter().enumerate() {
+                if identity.get("type").and_then(|t| t.as_str()).is_none() {
+                    bail!("identity: verified_identities[{i}] has no type");
+                }
+                if identity
+                    .get("provider")
+                    .and_then(|p| p.as_object())
+                    .is_none()
+                {
+                    bail!("identity: verified_identities[{i}] has no provider object");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The identity claims aggregation credential vouching for `c2pa_asset` (the serialized
+    /// signer payload), as JSON.
+    pub fn ica_credential(&self, c2pa_asset: JsonValue) -> JsonValue {
+        let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let identities: Vec<JsonValue> = self
+            .verified_identities
+            .iter()
+            .map(|identity| {
+                let mut identity = identity.clone();
+                if let Some(obj) = identity.as_object_mut() {
+                    obj.entry("verifiedAt")
+                        .or_insert_with(|| JsonValue::from(now.clone()));
+                }
+                identity
+            })
+            .collect();
+        let mut subject = serde_json::json!({
+            "verifiedIdentities": identities,
+            "c2paAsset": c2pa_asset,
+        });
+        if let Some(id) = &self.subject {
+            subject["id"] = JsonValue::from(id.as_str());
+        }
+        serde_json::json!({
+            "@context": [
+                "https://www.w3.org/ns/credentials/v2",
+                "https://cawg.io/identity/1.1/ica/context/"
+            ],
+            "type": ["VerifiableCredential", "IdentityClaimsAggregationCredential"],
+            "issuer": self.issuer,
+            "validFrom": now,
+            "credentialSubject": subject,
+        })
+    }
+}
+
+/// Remove the identity block from a manifest definition before it goes to the SDK.
+pub fn strip_identity(manifest: &mut JsonValue) {
+    if let Some(obj) = manifest.as_object_mut() {
+        obj.remove(IDENTITY_KEY);
+    }
+}
+
+fn resolve(base: &Path, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    }
+}
+
+fn raw_signer(
+    cert: &Path,
+    key: &Path,
+    alg: SigningAlg,
+    tsa_url: Option<String>,
+) -> Result<Box<dyn RawSigner + Send + Sync>> {
+    let cert_chain =
+        fs::read(cert).with_context(|| format!("Failed to read certificate {:?}", cert))?;
+    let private_key = fs::read(key).with_context(|| format!("Failed to read key {:?}", key))?;
+    signer_from_cert_chain_and_private_key(&cert_chain, &private_key, alg, tsa_url)
+        .map_err(|e| format_err!("Failed to create signer for {:?}: {}", cert, e))
+}
+
+/// Signer for `options` that adds the identity assertion described by `config`.
+pub fn identity_signer(
+    config: &IdentityConfig,
+    options: &SignOptions,
+) -> Result<Box<dyn c2pa::Signer>> {
+    let tsa_url = options
+        .tsa_url
+        .clone()
+        .filter(|_| !options.allow_self_signed);
+    let claim_signer = raw_signer(&options.cert, &options.key, options.signing_alg, tsa_url)?;
+
+    let (cert, key) = match (&config.cert, &config.key) {
+        (Some(cert), Some(key)) => (
+            resolve(&options.ingredients_base_dir, cert),
+            resolve(&options.ingredients_base_dir, key),
+        ),
+        _ => (options.cert.clone(), options.key.clone()),
+    };
+    let alg = match &config.alg {
+        Some(alg) => crate::signing::parse_signing_algorithm(alg)?,
+        None if config.cert.is_some() => crate::signing::detect_signing_algorithm(&cert)?,
+        None => options.signing_alg,
+    };
+    let credential_signer = raw_signer(&cert, &key, alg, None)?;
+
+    let mut builder = match config.credential_type {
+        IdentityCredentialType::X509 => IdentityAssertionBuilder::for_credential_holder(
+            X509CredentialHolder::from_raw_signer(credential_signer),
+        ),
+        IdentityCredentialType::Ica => {
+            IdentityAssertionBuilder::for_credential_holder(IcaCredentialHolder {
+                config: config.clone(),
+                signer: credential_signer,
+            })
+        }
+    };
+    let referenced: Vec<&str> = config
+        .referenced_assertions
+        .iter()
+        .map(String::as_str)
+        .collect();
+    builder.add_referenced_assertions(&referenced);
+    let roles: Vec<&str> = config.roles.iter().map(String::as_str).collect();
+    builder.add_roles(&roles);
+
+    let mut signer = IdentityAssertionSigner::new(claim_signer);
+    signer.add_identity_assertion(builder);
+    Ok(Box::new(signer))
+}
+
+/// Signs identity claims aggregation credentials as their issuer.
+struct IcaCredentialHolder {
+    config: IdentityConfig,
+    signer: Box<dyn RawSigner + Send + Sync>,
+}
+
+impl CredentialHolder for IcaCredentialHolder {
+    fn sig_type(&self) -> &'static str {
+        ICA_SIG_TYPE
+    }
+
+    fn reserve_size(&self) -> usize {
+        let identities = serde_json::to_vec(&self.config.verified_identities)
+            .map(|v| v.len())
+            .unwrap_or(0);
+        16 * 1024 + 2 * identities
+    }
+
+    fn sign(&self, signer_payload: &SignerPayload) -> Result<Vec<u8>, IdentityBuilderError> {
+        let internal = |e: String| IdentityBuilderError::InternalError(e);
+        let c2pa_asset =
+            serde_json::to_value(signer_payload).map_err(|e| internal(e.to_string()))?;
+        let credential = serde_json::to_vec(&self.config.ica_credential(c2pa_asset))
+            .map_err(|e| internal(e.to_string()))?;
+        cose_sign1(&*self.signer, "application/vc", &credential)
+            .map_err(|e| internal(e.to_string()))
+    }
+}
+
+/// COSE algorithm identifier of a signing algorithm.
+fn cose_alg(alg: SigningAlg) -> i64 {
+    match alg {
+        SigningAlg::Es256 => -7,
+        SigningAlg::Es384 => -35,
+        SigningAlg::Es512 => -36,
+        SigningAlg::Ps256 => -37,
+        SigningAlg::Ps384 => -38,
+        SigningAlg::Ps512 => -39,
+        SigningAlg::Ed25519 => -8,
+    }
+}
+
+fn cbor(value: &CborValue) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    ciborium::into_writer(value, &mut out).context("Failed to encode CBOR")?;
+    Ok(out)
+}
+
+/// Untagged COSE_Sign1 of `payload` with the algorithm and content type in the protected
+/// header.
+fn cose_sign1(signer: &dyn RawSigner, content_type: &str, payload: &[u8]) -> Result<Vec<u8>> {
+    let protected = cbor(&CborValue::Map(vec![
+        (CborValue::from(1), CborValue::from(cose_alg(signer.alg()))),
+        (CborValue::from(3), CborValue::from(content_type)),
+    ]))?;
+    let to_be_signed = cbor(&CborValue::Array(vec![
+        CborValue::from("Signature1"),
+        CborValue::Bytes(protected.clone()),
+        CborValue::Bytes(Vec::new()),
+        CborValue::Bytes(payload.to_vec()),
+    ]))?;
+    let signature = signer
+        .sign(&to_be_signed)
+        .map_err(|e| format_err!("Failed to sign credential: {}", e))?;
+    cbor(&CborValue::Array(vec![
+        CborValue::Bytes(protected),
+        CborValue::Map(Vec::new()),
+        CborValue::Bytes(payload.to_vec()),
+        CborValue::Bytes(signature),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_config() {
+        let manifest = serde_json::json!({ "title": "no identity" });
+        assert_eq!(IdentityConfig::from_manifest(&manifest).unwrap(), None);
+
+        let manifest = serde_json::json!({
+            "title": "x",
+            "identity": { "roles": ["cawg.creator"] }
+        });
+        let config = IdentityConfig::from_manifest(&manifest).unwrap().unwrap();
+        assert_eq!(config.credential_type, IdentityCredentialType::X509);
+        assert_eq!(config.roles, vec!["cawg.creator"]);
+
+        // An ica credential needs an issuer, its key, and identities with type and provider
+        let ica = |identity: JsonValue| {
+            serde_json::json!({ "identity": {
+                "credential_type": "cawg.identity_claims_aggregation",
+                "issuer": "did:web:identity.example.com",
+                "cert": "issuer.pem", "key": "issuer.key",
+                "verified_identities": [identity]
+            } })
+        };
+        let config = IdentityConfig::from_manifest(&ica(serde_json::json!({
+            "type": "cawg.social_media", "username": "jdoe",
+            "provider": { "id": "https://social.example", "name": "Example Social" }
+        })))
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.credential_type, IdentityCredentialType::Ica);
+        assert!(IdentityConfig::from_manifest(&ica(
+            serde_json::json!({ "type": "cawg.social_media" })
+        ))
+        .is_err());
+        assert!(IdentityConfig::from_manifest(
+            &serde_json::json!({ "identity": { "credential_type": "ica" } })
+        )
+        .is_err());
+        assert!(IdentityConfig::from_manifest(
+            &serde_json::json!({ "identity": { "cert": "only-a-cert.pem" } })
+        )
+        .is_err());
+
+        let vc = config.ica_credential(serde_json::json!({ "sig_type": ICA_SIG_TYPE }));
+        assert_eq!(vc["issuer"], "did:web:identity.example.com");
+        assert_eq!(vc["type"][1], "IdentityClaimsAggregationCredential");
+        let identity = &vc["credentialSubject"]["verifiedIdentities"][0];
+        assert_eq!(identity["username"], "jdoe");
+        assert!(identity["verifiedAt"].is_string());
+        assert_eq!(
+            vc["credentialSubject"]["c2paAsset"]["sig_type"],
+            ICA_SIG_TYPE
+        );
+
+        let mut manifest = ica(JsonValue::Null);
+        strip_identity(&mut manifest);
+        assert!(manifest.get(IDENTITY_KEY).is_none());
+    }
+}
@@ -0,0 +1,791 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Typed model of the JPEG Trust indicators in a crJSON manifest store.
+//!
+//! crJSON from different c2pa-rs versions spells the same fact differently (`claim.v2` vs
+//! `claim`, `instanceID` vs `instance_id`, `validationResults` vs `validation_results`, signer
+//! names as strings or distinguished-name objects). [`TrustIndicators`] reads all of them once,
+//! so the GUI and CLI reports work on plain structs instead of probing JSON keys.
+
+use crate::hashing::HashAlgorithm;
+use crate::provenance::{
+    ingredient_fingerprint, ingredient_manifest_label, ingredient_title, manifest_actions,
+};
+use crate::ManifestExtractionResult;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value as JsonValue;
+
+/// Trust in a signing credential.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum TrustStatus {
+    /// The certificate chains to a trust anchor or is on an allowed list.
+    Trusted,
+    /// A trust list was checked and the certificate is not on it.
+    Untrusted,
+    /// No trust list was checked.
+    #[default]
+    Unknown,
+    /// An organization's own `status.trust` value (legacy crJSON).
+    Other(String),
+}
+
+impl TrustStatus {
+    /// `trusted`, `untrusted`, `unknown`, or the custom value.
+    pub fn as_str(&self) -> &str {
+        match self {
+            TrustStatus::Trusted => "trusted",
+            TrustStatus::Untrusted => "untrusted",
+            TrustStatus::Unknown => "unknown",
+            TrustStatus::Other(s) => s,
+        }
+    }
+
+    /// Trust according to validation status codes (`signingCredential.*`).
+    pub fn from_validation(status: &ValidationStatus) -> Self {
+        if status.has_failure("signingCredential.untrusted") {
+            TrustStatus::Untrusted
+        } else if status.has_success("signingCredential.trusted") {
+            TrustStatus::Trusted
+        } else {
+            TrustStatus::Unknown
+        }
+    }
+}
+
+impl From<&str> for TrustStatus {
+    /// Accepts both crTool's names and the C2PA status codes.
+    fn from(s: &str) -> Self {
+        match s {
+            "trusted" | "signingCredential.trusted" => TrustStatus::Trusted,
+            "untrusted" | "signingCredential.untrusted" => TrustStatus::Untrusted,
+            "unknown" | "" => TrustStatus::Unknown,
+            other => TrustStatus::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for TrustStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for TrustStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TrustStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(TrustStatus::from(
+            String::deserialize(deserializer)?.as_str(),
+        ))
+    }
+}
+
+/// One validation status entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusEntry {
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+}
+
+/// Validation status codes of a manifest, by kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationStatus {
+    #[serde(default)]
+    pub success: Vec<StatusEntry>,
+    #[serde(default)]
+    pub informational: Vec<StatusEntry>,
+    #[serde(default)]
+    pub failure: Vec<StatusEntry>,
+}
+
+impl ValidationStatus {
+    /// Read `{ success, informational, failure }` arrays; entries without a code are skipped.
+    pub fn from_json(value: &JsonValue) -> Self {
+        let entries = |key: &str| -> Vec<StatusEntry> {
+            value
+                .get(key)
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|e| {
+                    Some(StatusEntry {
+                        code: e.get("code")?.as_str()?.to_string(),
+                        url: str_at(e, &["url"]),
+                        explanation: str_at(e, &["explanation"]),
+                    })
+                })
+                .collect()
+        };
+        Self {
+            success: entries("success"),
+            informational: entries("informational"),
+            failure: entries("failure"),
+        }
+    }
+
+    pub fn has_success(&self, code: &str) -> bool {
+        self.success.iter().any(|e| e.code == code)
+    }
+
+    pub fn has_failure(&self, code: &str) -> bool {
+        self.failure.iter().any(|e| e.code == code)
+    }
+
+    /// Failures other than an untrusted signer (which is reported as [`TrustStatus`]).
+    pub fn failures_excluding_trust(&self) -> impl Iterator<Item = &StatusEntry> {
+        self.failure
+            .iter()
+            .filter(|e| e.code != "signingCredential.untrusted")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.success.is_empty() && self.informational.is_empty() && self.failure.is_empty()
+    }
+}
+
+/// Validation results an ingredient contributed to the manifest that uses it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngredientDelta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingredient_assertion_uri: Option<String>,
+    pub validation: ValidationStatus,
+}
+
+/// One `claim_generator_info` entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratorInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl std::fmt::Display for GeneratorInfo {
+    /// `name version`, `—` without a name.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.name.as_deref().unwrap_or("—");
+        match self.version.as_deref().filter(|v| !v.is_empty()) {
+            Some(version) => write!(f, "{name} {version}"),
+            None => f.write_str(name),
+        }
+    }
+}
+
+/// The claim signature.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureIndicator {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+    /// Certificate issuer (organization, else common name).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// Certificate subject (common name, else organization).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Time of the signature's timestamp, when it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    /// Time authority that issued the timestamp (common name, else organization).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_authority: Option<String>,
+    /// Whether the signature carries a timestamp (`timeStampInfo`).
+    pub has_timestamp: bool,
+    /// Signing time: the timestamp, else the legacy top-level `signature.timestamp`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_at: Option<String>,
+}
+
+impl SignatureIndicator {
+    /// Read a crJSON `signature` object.
+    pub fn from_json(sig: &JsonValue) -> Self {
+        let cert = sig.get("certificateInfo");
+        let ts = sig.get("timeStampInfo").filter(|t| t.is_object());
+        let timestamp = ts.and_then(|t| str_at(t, &["timestamp"]));
+        Self {
+            algorithm: str_at(sig, &["algorithm"]),
+            issuer: cert
+                .and_then(|c| c.get("issuer"))
+                .and_then(|i| {
+                    i.as_str()
+                        .map(str::to_string)
+                        .or_else(|| first_str(i, ORG_FIRST))
+                })
+                .or_else(|| str_at(sig, &["issuer"])),
+            subject: cert
+                .and_then(|c| c.get("subject"))
+                .or_else(|| sig.get("subject"))
+                .and_then(|s| first_str(s, CN_FIRST)),
+            timestamp_authority: ts
+                .and_then(|t| t.get("certificateInfo"))
+                .and_then(|c| c.get("issuer"))
+                .and_then(|i| first_str(i, CN_FIRST)),
+            has_timestamp: ts.is_some(),
+            signed_at: timestamp.clone().or_else(|| str_at(sig, &["timestamp"])),
+            timestamp,
+        }
+    }
+}
+
+/// An ingredient assertion of a manifest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngredientIndicator {
+    /// Assertion label (`c2pa.ingredient.v3__1`, ...).
+    pub assertion_label: String,
+    /// Title, else an identifier or file path.
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+    /// Label of the ingredient's own manifest in the store.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_label: Option<String>,
+    /// Hash of the ingredient's manifest or content reference.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// Validation of the ingredient's manifest as recorded in the assertion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<ValidationStatus>,
+    /// Trust from [`IngredientIndicator::validation`], when recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trust: Option<TrustStatus>,
+}
+
+impl IngredientIndicator {
+    /// Read an ingredient assertion payload stored under `assertion_label`.
+    pub fn from_json(assertion_label: &str, ingredient: &JsonValue) -> Self {
+        let validation = ingredient
+            .get("validationResults")
+            .or_else(|| ingredient.get("validation_results"))
+            .and_then(|vr| {
+                vr.get("activeManifest")
+                    .or_else(|| vr.get("active_manifest"))
+            })
+            .map(ValidationStatus::from_json);
+        let trust = validation
+            .as_ref()
+            .map(TrustStatus::from_validation)
+            .filter(|t| *t != TrustStatus::Unknown);
+        Self {
+            assertion_label: assertion_label.to_string(),
+            title: ingredient_title(ingredient),
+            relationship: str_at(ingredient, &["relationship"]),
+            format: first_str(ingredient, &["format", "dc:format"]),
+            instance_id: first_str(ingredient, &["instanceID", "instance_id"]),
+            document_id: first_str(ingredient, &["documentID", "document_id"]),
+            manifest_label: ingredient_manifest_label(ingredient).map(str::to_string),
+            hash: ingredient_fingerprint(ingredient).map(str::to_string),
+            validation,
+            trust,
+        }
+    }
+}
+
+/// One manifest of the store.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestIndicator {
+    pub label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+    /// `claim.v2` or `claim`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Free-text `claim_generator` of v1 claims.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim_generator: Option<String>,
+    #[serde(default)]
+    pub generator_info: Vec<GeneratorInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<SignatureIndicator>,
+    /// Digital source type URI of the `c2pa.created` action.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digital_source_type: Option<String>,
+    #[serde(default)]
+    pub actions: Vec<String>,
+    #[serde(default)]
+    pub assertion_labels: Vec<String>,
+    #[serde(default)]
+    pub ingredients: Vec<IngredientIndicator>,
+    pub validation: ValidationStatus,
+    #[serde(default)]
+    pub ingredient_deltas: Vec<IngredientDelta>,
+    pub trust: TrustStatus,
+}
+
+impl ManifestIndicator {
+    /// Read one entry of the crJSON `manifests` array.
+    pub fn from_json(manifest: &JsonValue) -> Self {
+        let (claim_version, claim) = match (manifest.get("claim.v2"), manifest.get("claim")) {
+            (Some(c), _) => (Some("claim.v2"), Some(c)),
+            (None, Some(c)) => (Some("claim"), Some(c)),
+            (None, None) => (None, None),
+        };
+        let validation = manifest
+            .get("validationResults")
+            .or_else(|| manifest.get("validation_results"))
+            .map(ValidationStatus::from_json)
+            .unwrap_or_default();
+        let trust = match TrustStatus::from_validation(&validation) {
+            TrustStatus::Unknown => manifest
+                .get("status")
+                .and_then(|s| s.get("trust"))
+                .and_then(|t| t.as_str())
+                .map_or(TrustStatus::Unknown, TrustStatus::from),
+            trust => trust,
+        };
+        let assertions = manifest.get("assertions").and_then(|a| a.as_object());
+        let ingredients = assertions
+            .into_iter()
+            .flatten()
+            .filter(|(k, _)| is_ingredient_label(k))
+            .map(|(k, v)| IngredientIndicator::from_json(k, v))
+            .collect();
+
+        Self {
+            label: str_at(manifest, &["label"]).unwrap_or_default(),
+            instance_id: claim.and_then(|c| first_str(c, &["instanceID", "instance_id"])),
+            claim_version: claim_version.map(str::to_string),
+            title: crate::provenance::manifest_title(manifest).map(str::to_string),
+            claim_generator: claim
+                .and_then(|c| first_str(c, &["claim_generator", "claimGenerator"])),
+            generator_info: claim
+                .and_then(|c| c.get("claim_generator_info"))
+                .or_else(|| manifest.get("claim_generator_info"))
+                .map(generator_info)
+                .unwrap_or_default(),
+            signature: manifest
+                .get("signature")
+                .filter(|s| s.is_object())
+                .map(SignatureIndicator::from_json),
+            digital_source_type: digital_source_type(manifest),
+            actions: manifest_actions(manifest),
+            assertion_labels: assertions
+                .map(|a| a.keys().cloned().collect())
+                .unwrap_or_default(),
+            ingredients,
+            ingredient_deltas: manifest
+                .get("ingredientDeltas")
+                .map(ingredient_deltas)
+                .unwrap_or_default(),
+            validation,
+            trust,
+        }
+    }
+
+    /// Name of the app or device that made the manifest: the first `claim_generator_info`
+    /// name, else the v1 `claim_generator`.
+    pub fn generator_name(&self) -> Option<&str> {
+        self.generator_info
+            .iter()
+            .find_map(|g| g.name.as_deref())
+            .or(self.claim_generator.as_deref())
+    }
+
+    /// Validation failures of this manifest and its ingredient deltas, except an untrusted
+    /// signer, each with the ingredient assertion it came from (`None` for the manifest's own).
+    pub fn failures(&self) -> Vec<(Option<&str>, &StatusEntry)> {
+        let own = self
+            .validation
+            .failures_excluding_trust()
+            .map(|e| (None, e));
+        let deltas = self.ingredient_deltas.iter().flat_map(|d| {
+            d.validation
+                .failures_excluding_trust()
+                .map(|e| (d.ingredient_assertion_uri.as_deref(), e))
+        });
+        own.chain(deltas).collect()
+    }
+}
+
+/// Trust indicators of a whole manifest store.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustIndicators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_hash_alg: Option<HashAlgorithm>,
+    pub active_label: String,
+    pub manifests: Vec<ManifestIndicator>,
+}
+
+impl TrustIndicators {
+    /// Indicators of an extraction result.
+    pub fn from_extraction(result: &ManifestExtractionResult) -> Self {
+        Self {
+            input_path: Some(result.input_path.clone()),
+            asset_hash: result.asset_hash.clone(),
+            asset_hash_alg: result.asset_hash_alg,
+            ..Self::from_crjson(&result.manifest_value, &result.active_label)
+        }
+    }
+
+    /// Indicators of a crJSON document whose active manifest is `active_label`.
+    pub fn from_crjson(crjson: &JsonValue, active_label: &str) -> Self {
+        let manifests = match crjson.get("manifests").and_then(|m| m.as_array()) {
+            Some(manifests) => manifests
+                .iter()
+                .map(|m| {
+                    let mut indicator = ManifestIndicator::from_json(m);
+                    if indicator.label == active_label {
+                        apply_legacy_validation(&mut indicator, crjson, m);
+                    }
+                    indicator
+                })
+                .collect(),
+            None => active_manifest_indicator(crjson, active_label)
+                .into_iter()
+                .collect(),
+        };
+        Self {
+            input_path: None,
+            asset_hash: None,
+            asset_hash_alg: None,
+            active_label: active_label.to_string(),
+            manifests,
+        }
+    }
+
+    /// Manifest by label or claim instanceID.
+    pub fn manifest(&self, label: &str) -> Option<&ManifestIndicator> {
+        self.manifests
+            .iter()
+            .find(|m| m.label == label || m.instance_id.as_deref() == Some(label))
+    }
+
+    /// The active manifest (the only manifest of a legacy single-manifest document).
+    pub fn active_manifest(&self) -> Option<&ManifestIndicator> {
+        self.manifest(&self.active_label)
+            .or_else(|| self.manifests.first().filter(|m| m.label.is_empty()))
+    }
+
+    /// Trust of the active manifest's signer.
+    pub fn trust(&self) -> TrustStatus {
+        self.active_manifest()
+            .map(|m| m.trust.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// The crJSON object of the active manifest: the `manifests` entry with label or instanceID
+/// `active_label`, or the document itself for legacy single-manifest crJSON.
+pub fn active_manifest_json<'a>(
+    crjson: &'a JsonValue,
+    active_label: &str,
+) -> Option<&'a JsonValue> {
+    crate::provenance::find_manifest(crjson, active_label).or_else(|| {
+        (crjson.get("manifests").is_none()
+            && (crjson.get("claim_generator_info").is_some() || crjson.get("title").is_some()))
+        .then_some(crjson)
+    })
+}
+
+/// Indicators of the active manifest only, without converting the rest of the store.
+pub fn active_manifest_indicator(
+    crjson: &JsonValue,
+    active_label: &str,
+) -> Option<ManifestIndicator> {
+    let manifest = active_manifest_json(crjson, active_label)?;
+    let mut indicator = ManifestIndicator::from_json(manifest);
+    apply_legacy_validation(&mut indicator, crjson, manifest);
+    Some(indicator)
+}
+
+/// Older crJSON keeps the active manifest's results at document level
+/// (`validationResults.activeManifest` and `.ingredientDeltas`).
+fn apply_legacy_validation(
+    indicator: &mut ManifestIndicator,
+    crjson: &JsonValue,
+    manifest: &JsonValue,
+) {
+    let Some(doc) = crjson
+        .get("validationResults")
+        .filter(|_| !std::ptr::eq(crjson, manifest))
+    else {
+        return;
+    };
+    let has_own =
+        manifest.get("validationResults").is_some() || manifest.get("validation_results").is_some();
+    if !has_own {
+        if let Some(active) = doc.get("activeManifest") {
+            indicator.validation = ValidationStatus::from_json(active);
+            if indicator.trust == TrustStatus::Unknown {
+                indicator.trust = TrustStatus::from_validation(&indicator.validation);
+            }
+        }
+    }
+    if manifest.get("ingredientDeltas").is_none() {
+        if let Some(deltas) = doc.get("ingredientDeltas") {
+            indicator.ingredient_deltas = ingredient_deltas(deltas);
+        }
+    }
+}
+
+fn ingredient_deltas(deltas: &JsonValue) -> Vec<IngredientDelta> {
+    deltas
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|d| {
+            Some(IngredientDelta {
+                ingredient_assertion_uri: str_at(d, &["ingredientAssertionURI"]),
+                validation: ValidationStatus::from_json(d.get("validationDeltas")?),
+            })
+        })
+        .collect()
+}
+
+/// Ingredient assertion labels (`c2pa.ingredient`, `.v2`, `.v3`, `__N` instances), excluding
+/// ingredient thumbnails.
+fn is_ingredient_label(key: &str) -> bool {
+    (key == "c2pa.ingredient"
+        || key.starts_with("c2pa.ingredient.")
+        || key.starts_with("c2pa.ingredient__"))
+        && !key.contains("thumbnail")
+}
+
+fn generator_info(value: &JsonValue) -> Vec<GeneratorInfo> {
+    let one = |v: &JsonValue| match v.as_str() {
+        Some(name) => Some(GeneratorInfo {
+            name: Some(name.to_string()),
+            version: None,
+        }),
+        None => v.is_object().then(|| GeneratorInfo {
+            name: first_str(v, &["name", "title"]),
+            version: str_at(v, &["version"]),
+        }),
+    };
+    match value.as_array() {
+        Some(entries) => entries.iter().filter_map(one).collect(),
+        None => one(value).into_iter().collect(),
+    }
+}
+
+/// `digitalSourceType` of the `c2pa.created` action, from the `c2pa.actions(.v2)` assertion
+/// (an object keyed by label, or a legacy array of `{label, data}`; also inside the claim).
+pub fn digital_source_type(manifest: &JsonValue) -> Option<String> {
+    let from_actions = |actions: &JsonValue| {
+        actions
+            .as_array()?
+            .iter()
+            .find(|a| a.get("action").and_then(|v| v.as_str()) == Some("c2pa.created"))
+            .and_then(|a| str_at(a, &["digitalSourceType"]))
+    };
+    let from_assertions = |assertions: &JsonValue| {
+        const LABELS: [&str; 2] = ["c2pa.actions.v2", "c2pa.actions"];
+        if let Some(obj) = assertions.as_object() {
+            return LABELS
+                .iter()
+                .find_map(|l| from_actions(obj.get(*l)?.get("actions")?));
+        }
+        assertions.as_array()?.iter().find_map(|a| {
+            let label = a.get("label")?.as_str()?;
+            if !LABELS.contains(&label) {
+                return None;
+            }
+            from_actions(a.get("data")?.get("actions")?)
+        })
+    };
+    manifest
+        .get("assertions")
+        .and_then(from_assertions)
+        .or_else(|| {
+            manifest
+                .get("claim.v2")
+                .or_else(|| manifest.get("claim"))
+                .and_then(|c| c.get("assertions"))
+                .and_then(from_assertions)
+        })
+}
+
+/// Distinguished-name attributes naming an organization or person, in order of preference.
+const ORG_FIRST: &[&str] = &["O", "o", "CN", "cn"];
+const CN_FIRST: &[&str] = &["CN", "cn", "O", "o"];
+
+fn str_at(value: &JsonValue, keys: &[&str]) -> Option<String> {
+    let mut cur = value;
+    for k in keys {
+        cur = cur.get(k)?;
+    }
+    cur.as_str().map(str::to_string)
+}
+
+fn first_str(value: &JsonValue, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|k| value.get(*k).and_then(|v| v.as_str()))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store() -> JsonValue {
+        json!({
+            "manifests": [
+                {
+                    "label": "urn:c2pa:active",
+                    "claim.v2": {
+                        "instanceID": "xmp:iid:active",
+                        "dc:title": "composite.jpg",
+                        "claim_generator_info": { "name": "Photo Editor", "version": "2.1" }
+                    },
+                    "signature": {
+                        "algorithm": "Es256",
+                        "certificateInfo": {
+                            "issuer": { "O": "Example CA", "CN": "Example Issuing CA" },
+                            "subject": { "CN": "Example Signer", "O": "Example Corp" }
+                        },
+                        "timeStampInfo": {
+                            "timestamp": "2025-01-02T03:04:05Z",
+                            "certificateInfo": { "issuer": { "CN": "Example TSA" } }
+                        }
+                    },
+                    "assertions": {
+                        "c2pa.actions.v2": { "actions": [ {
+                            "action": "c2pa.created",
+                            "digitalSourceType": "http://cv.iptc.org/newscodes/digitalsourcetype/digitalCapture"
+                        } ] },
+                        "c2pa.ingredient.v3": {
+                            "dc:title": "background.jpg",
+                            "relationship": "componentOf",
+                            "instance_id": "xmp:iid:bg",
+                            "activeManifest": { "url": "self#jumbf=/c2pa/urn:c2pa:bg/c2pa.claim", "hash": "abc" },
+                            "validationResults": { "activeManifest": {
+                                "failure": [ { "code": "signingCredential.untrusted" } ]
+                            } }
+                        },
+                        "c2pa.thumbnail.ingredient.jpeg": {}
+                    },
+                    "validationResults": {
+                        "success": [ { "code": "signingCredential.trusted" } ],
+                        "failure": [ { "code": "assertion.dataHash.mismatch", "explanation": "hash" } ]
+                    },
+                    "ingredientDeltas": [ {
+                        "ingredientAssertionURI": "self#jumbf=c2pa.assertions/c2pa.ingredient.v3",
+                        "validationDeltas": { "failure": [ { "code": "claimSignature.mismatch" } ] }
+                    } ]
+                },
+                {
+                    "label": "urn:c2pa:bg",
+                    "claim": { "claim_generator": "Old Tool/1.0" },
+                    "signature": { "issuer": "Legacy CA", "timestamp": "2020-01-01T00:00:00Z" },
+                    "status": { "trust": "com.example.reviewed" }
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_trust_indicators() {
+        let indicators = TrustIndicators::from_crjson(&store(), "urn:c2pa:active");
+        let active = indicators.active_manifest().unwrap();
+        assert_eq!(active.claim_version.as_deref(), Some("claim.v2"));
+        assert_eq!(active.title.as_deref(), Some("composite.jpg"));
+        assert_eq!(active.generator_name(), Some("Photo Editor"));
+        assert_eq!(active.generator_info[0].to_string(), "Photo Editor 2.1");
+        assert_eq!(indicators.trust(), TrustStatus::Trusted);
+
+        let sig = active.signature.as_ref().unwrap();
+        assert_eq!(sig.issuer.as_deref(), Some("Example CA"));
+        assert_eq!(sig.subject.as_deref(), Some("Example Signer"));
+        assert_eq!(sig.timestamp_authority.as_deref(), Some("Example TSA"));
+        assert!(sig.has_timestamp);
+        assert!(active
+            .digital_source_type
+            .as_deref()
+            .unwrap()
+            .ends_with("digitalCapture"));
+
+        assert_eq!(active.ingredients.len(), 1);
+        let ingredient = &active.ingredients[0];
+        assert_eq!(ingredient.title, "background.jpg");
+        assert_eq!(ingredient.instance_id.as_deref(), Some("xmp:iid:bg"));
+        assert_eq!(ingredient.manifest_label.as_deref(), Some("urn:c2pa:bg"));
+        assert_eq!(ingredient.trust, Some(TrustStatus::Untrusted));
+
+        let failures: Vec<(Option<&str>, &str)> = active
+            .failures()
+            .into_iter()
+            .map(|(source, e)| (source, e.code.as_str()))
+            .collect();
+        assert_eq!(
+            failures,
+            [
+                (None, "assertion.dataHash.mismatch"),
+                (
+                    Some("self#jumbf=c2pa.assertions/c2pa.ingredient.v3"),
+                    "claimSignature.mismatch"
+                )
+            ]
+        );
+
+        // Lookup by instanceID, and legacy v1 fields
+        assert!(indicators.manifest("xmp:iid:active").is_some());
+        let legacy = indicators.manifest("urn:c2pa:bg").unwrap();
+        assert_eq!(legacy.generator_name(), Some("Old Tool/1.0"));
+        assert_eq!(legacy.trust.as_str(), "com.example.reviewed");
+        let sig = legacy.signature.as_ref().unwrap();
+        assert_eq!(sig.issuer.as_deref(), Some("Legacy CA"));
+        assert!(!sig.has_timestamp);
+        assert_eq!(sig.signed_at.as_deref(), Some("2020-01-01T00:00:00Z"));
+
+        let text = serde_json::to_string(&indicators).unwrap();
+        let back: TrustIndicators = serde_json::from_str(&text).unwrap();
+        assert_eq!(back, indicators);
+    }
+
+    #[test]
+    fn test_legacy_document_validation() {
+        let crjson = json!({
+            "manifests": [ { "label": "urn:c2pa:a", "claim": {} } ],
+            "validationResults": {
+                "activeManifest": { "failure": [
+                    { "code": "signingCredential.untrusted" },
+                    { "code": "assertion.hashedURI.mismatch" }
+                ] }
+            }
+        });
+        let active = active_manifest_indicator(&crjson, "urn:c2pa:a").unwrap();
+        assert_eq!(active.trust, TrustStatus::Untrusted);
+        assert_eq!(active.failures().len(), 1);
+
+        // A single manifest without a `manifests` list
+        let single = json!({ "title": "photo.jpg", "status": { "trust": "trusted" } });
+        let indicators = TrustIndicators::from_crjson(&single, "");
+        assert_eq!(indicators.trust(), TrustStatus::Trusted);
+    }
+}
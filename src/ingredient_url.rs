@@ -0,0 +1,379 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Ingredients referenced by URL: the `file_path` of a manifest's ingredient entry may be an
+//! `https://` URL or an `s3://bucket/key` object instead of a local file. It is downloaded once
+//! into a per-user cache directory and loaded from there like a file ingredient (see
+//! [`crate::signing::process_ingredients`]). Each cached file has its SHA-256 stored next to it
+//! and is only reused while its content still matches.
+//!
+//! `s3://` objects are fetched over HTTPS from the bucket's virtual-hosted endpoint
+//! (`https://<bucket>.s3.<region>.amazonaws.com/<key>`, with the region from `AWS_REGION`), or
+//! path-style from [`S3_ENDPOINT_ENV`] (e.g. a MinIO server). Requests are not signed, so the
+//! objects must be publicly readable; use a presigned `https://` URL for a private bucket.
+//! Downloads only follow redirects to `https://` URLs, time out after [`DOWNLOAD_TIMEOUT`], and
+//! are refused beyond the `max_download_bytes` of [`crate::limits::extraction_limits`].
+//! Downloading needs the `remote` feature; cached ingredients are used without it.
+
+use crate::error::{bail, Context, Result};
+use crate::hashing::{hash_bytes, HashAlgorithm};
+use crate::signing::parse_relationship;
+use serde_json::{json, Value as JsonValue};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Cache directory for downloaded ingredients, instead of `ingredients` in the per-user cache
+/// directory.
+pub const CACHE_DIR_ENV: &str = "CRTOOL_INGREDIENT_CACHE";
+
+/// Endpoint for `s3://` ingredients (e.g. `http://localhost:9000`), used path-style.
+pub const S3_ENDPOINT_ENV: &str = "CRTOOL_S3_ENDPOINT";
+
+/// How long one ingredient download may take, including reading the body.
+pub const DOWNLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Most redirects followed for one download.
+const MAX_REDIRECTS: usize = 10;
+
+/// Whether an ingredient `file_path` is a URL rather than a local path.
+pub fn is_ingredient_url(path: &str) -> bool {
+    ["https://", "http://", "s3://"]
+        .iter()
+        .any(|scheme| path.starts_with(scheme))
+}
+
+/// The HTTPS URL an ingredient URL is downloaded from. Plain `http://` is refused.
+pub fn download_url(url: &str) -> Result<String> {
+    if url.starts_with("https://") {
+        return Ok(url.to_string());
+    }
+    if let Some(object) = url.strip_prefix("s3://") {
+        let Some((bucket, key)) = object
+            .split_once('/')
+            .filter(|(b, k)| !b.is_empty() && !k.is_empty())
+        else {
+            bail!(
+                "Invalid S3 ingredient URL {} (expected s3://bucket/key)",
+                url
+            );
+        };
+        if let Ok(endpoint) = std::env::var(S3_ENDPOINT_ENV) {
+            return Ok(format!(
+                "{}/{}/{}",
+                endpoint.trim_end_matches('/'),
+                bucket,
+                key
+            ));
+        }
+        return Ok(match std::env::var("AWS_REGION") {
+            Ok(region) => format!("https://{bucket}.s3.{region}.amazonaws.com/{key}"),
+            Err(_) => format!("https://{bucket}.s3.amazonaws.com/{key}"),
+        });
+    }
+    bail!(
+        "Unsupported ingredient URL {} (use an https:// or s3:// URL)",
+        url
+    )
+}
+
+/// The ingredient cache directory: [`CACHE_DIR_ENV`], or `ingredients` in the per-user cache
+/// directory ([`crate::trust::user_cache_dir`]).
+pub fn cache_dir() -> PathBuf {
+    std::env::var_os(CACHE_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| crate::trust::user_cache_dir().join("ingredients"))
+}
+
+/// Create `dir` and its missing parents so that only the current user can use them (mode 0700
+/// on Unix). An existing `dir` is restricted to the current user as well.
+fn create_private_dir(dir: &Path) -> Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder
+        .create(dir)
+        .with_context(|| format!("Failed to create directory {:?}", dir))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("Failed to restrict the permissions of {:?}", dir))?;
+    }
+    Ok(())
+}
+
+/// Where the SHA-256 of the cached file at `path` is stored.
+fn digest_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".sha256");
+    PathBuf::from(name)
+}
+
+/// Whether `path` is cached and its content matches the SHA-256 stored with it.
+fn is_cached(path: &Path) -> Result<bool> {
+    if !path.is_file() {
+        return Ok(false);
+    }
+    let Ok(expected) = fs::read_to_string(digest_path(path)) else {
+        return Ok(false);
+    };
+    Ok(crate::hashing::hash_file(path, HashAlgorithm::Sha256)? == expected.trim())
+}
+
+/// Where `url` is cached below `cache_dir`: a directory named by the URL's hash, holding the
+/// file under the URL's file name, so the ingredient keeps its title and format extension.
+pub fn cached_path(cache_dir: &Path, url: &str) -> PathBuf {
+    let digest = hash_bytes(url.as_bytes(), HashAlgorithm::Sha256);
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let name = path
+        .rsplit('/')
+        .next()
+        .filter(|n| !n.is_empty() && *n != "." && *n != "..")
+        .unwrap_or("ingredient");
+    cache_dir.join(&digest[..16]).join(name)
+}
+
+/// The local copy of the ingredient at `url`, downloading it into `cache_dir` unless it is
+/// already cached with matching content. With `CRTOOL_OFFLINE` set, only cached ingredients are
+/// available.
+pub fn fetch_ingredient(url: &str, cache_dir: &Path) -> Result<PathBuf> {
+    let path = cached_path(cache_dir, url);
+    if is_cached(&path)? {
+        return Ok(path);
+    }
+    let source = download_url(url)?;
+    if crate::update::offline_from_env() {
+        bail!(
+            "Ingredient {} is not in the ingredient cache {:?} (or no longer matches its \
+            recorded hash) and network access is disabled",
+            url,
+            cache_dir
+        );
+    }
+    create_private_dir(cache_dir)?;
+    let dir = path.parent().context("Invalid ingredient cache path")?;
+    create_private_dir(dir)?;
+    download(&source, &path).with_context(|| format!("Failed to download ingredient {}", url))?;
+    Ok(path)
+}
+
+/// Download `source` to `path` through a temporary file, so an interrupted download is never
+/// mistaken for a cached ingredient. The file's SHA-256 is written before it is moved into
+/// place.
+#[cfg(feature = "remote")]
+fn download(source: &str, path: &Path) -> Result<()> {
+    use std::io::Read;
+
+    let dir = path.parent().context("Invalid ingredient cache path")?;
+    let limits = crate::limits::extraction_limits();
+    let response = download_client()?
+        .get(source)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| crate::error::format_err!("{}: {}", source, e))?;
+    if let Some(len) = response.content_length() {
+        limits.check_download_size(len)?;
+    }
+    let partial = dir.join(format!(".{}.part", uuid::Uuid::new_v4()));
+    let mut file =
+        fs::File::create(&partial).with_context(|| format!("Failed to create {:?}", partial))?;
+    // One byte past the limit tells an oversized body (without a Content-Length) apart
+    let copied = std::io::copy(
+        &mut response.take(limits.max_download_bytes.saturating_add(1)),
+        &mut file,
+    );
+    drop(file);
+    let checked = match copied {
+        Ok(len) => limits.check_download_size(len).map_err(|mut exceeded| {
+            exceeded.actual = None;
+            exceeded.into()
+        }),
+        Err(e) => Err(crate::error::format_err!("{}: {}", source, e)),
+    };
+    if let Err(e) = checked {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    let digest = crate::hashing::hash_file(&partial, HashAlgorithm::Sha256)?;
+    fs::write(digest_path(path), format!("{digest}\n"))
+        .with_context(|| format!("Failed to write {:?}", digest_path(path)))?;
+    fs::rename(&partial, path).with_context(|| format!("Failed to write {:?}", path))?;
+    Ok(())
+}
+
+/// HTTP client for ingredient downloads: redirects only to `https://` URLs, at most
+/// [`MAX_REDIRECTS`] of them, and a [`DOWNLOAD_TIMEOUT`] per request.
+#[cfg(feature = "remote")]
+fn download_client() -> Result<reqwest::blocking::Client> {
+    let redirects = reqwest::redirect::Policy::custom(|attempt| {
+        if attempt.url().scheme() != "https" {
+            let error = format!("refusing redirect to non-https URL {}", attempt.url());
+            attempt.error(error)
+        } else if attempt.previous().len() >= MAX_REDIRECTS {
+            attempt.error(format!("more than {MAX_REDIRECTS} redirects"))
+        } else {
+            attempt.follow()
+        }
+    });
+    reqwest::blocking::Client::builder()
+        .user_agent(concat!("crTool/", env!("CARGO_PKG_VERSION")))
+        .redirect(redirects)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()
+        .context("Failed to create HTTP client")
+}
+
+#[cfg(not(feature = "remote"))]
+fn download(source: &str, _path: &Path) -> Result<()> {
+    bail!(
+        "Downloading {} needs a build with the `remote` feature",
+        source
+    )
+}
+
+/// Append an ingredient entry to the `ingredients` of `manifest` for each value of the CLI's
+/// `--ingredient-url`: an `https://` or `s3://` URL, optionally prefixed by its relationship
+/// (`parentOf=s3://bucket/parent.jpg`; `componentOf` when absent).
+pub fn add_ingredient_urls(manifest: &mut JsonValue, values: &[String]) -> Result<()> {
+    let Some(obj) = manifest.as_object_mut() else {
+        bail!("Manifest definition is not a JSON object");
+    };
+    let ingredients = obj
+        .entry("ingredients")
+        .or_insert_with(|| JsonValue::Array(Vec::new()))
+        .as_array_mut()
+        .context("Manifest `ingredients` is not an array")?;
+    for value in values {
+        let (relationship, url) = match value.split_once('=') {
+            Some((rel, url)) if !rel.contains([':', '/']) => {
+                parse_relationship(rel)?;
+                (rel, url)
+            }
+            _ => ("componentOf", value.as_str()),
+        };
+        if !is_ingredient_url(url) {
+            bail!(
+                "--ingredient-url {} is not an https:// or s3:// URL; list local files in the \
+                manifest's ingredients",
+                value
+            );
+        }
+        download_url(url)?;
+        ingredients.push(json!({ "file_path": url, "relationship": relationship }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_url() {
+        assert_eq!(
+            download_url("https://example.com/a.jpg").unwrap(),
+            "https://example.com/a.jpg"
+        );
+        assert!(download_url("http://example.com/a.jpg").is_err());
+        assert!(download_url("s3://bucket").is_err());
+        assert!(download_url("ftp://example.com/a.jpg").is_err());
+        if std::env::var_os(S3_ENDPOINT_ENV).is_none() && std::env::var_os("AWS_REGION").is_none() {
+            assert_eq!(
+                download_url("s3://shared-assets/parents/dog.jpg").unwrap(),
+                "https://shared-assets.s3.amazonaws.com/parents/dog.jpg"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cached_path() {
+        let dir = Path::new("/cache");
+        let a = cached_path(dir, "https://example.com/assets/dog.jpg?v=2");
+        assert_eq!(a.file_name().unwrap(), "dog.jpg");
+        assert_ne!(a, cached_path(dir, "https://example.com/other/dog.jpg"));
+        assert_eq!(
+            cached_path(dir, "https://example.com/")
+                .file_name()
+                .unwrap(),
+            "ingredient"
+        );
+    }
+
+    #[test]
+    fn test_fetch_ingredient_uses_cache() {
+        let dir = std::env::temp_dir().join(format!("crtool-test-ingcache-{}", std::process::id()));
+        let url = "https://example.invalid/assets/dog.jpg";
+        let path = cached_path(&dir, url);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, b"cached").unwrap();
+        // Without a recorded hash the file is not trusted (and example.invalid cannot be reached)
+        assert!(fetch_ingredient(url, &dir).is_err());
+
+        fs::write(
+            digest_path(&path),
+            hash_bytes(b"cached", HashAlgorithm::Sha256),
+        )
+        .unwrap();
+        assert_eq!(fetch_ingredient(url, &dir).unwrap(), path);
+
+        // A cached file whose content changed is not used
+        fs::write(&path, b"tampered").unwrap();
+        assert!(!is_cached(&path).unwrap());
+        assert!(fetch_ingredient(url, &dir).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_private_dir() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("crtool-test-private-{}", std::process::id()));
+        let nested = dir.join("a/b");
+        create_private_dir(&nested).unwrap();
+        for d in [&dir, &nested] {
+            assert_eq!(fs::metadata(d).unwrap().permissions().mode() & 0o777, 0o700);
+        }
+        fs::set_permissions(&nested, fs::Permissions::from_mode(0o777)).unwrap();
+        create_private_dir(&nested).unwrap();
+        assert_eq!(
+            fs::metadata(&nested).unwrap().permissions().mode() & 0o777,
+            0o700
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_add_ingredient_urls() {
+        let mut manifest = json!({ "title": "t" });
+        add_ingredient_urls(
+            &mut manifest,
+            &[
+                "parentOf=s3://bucket/parent.jpg".to_string(),
+                "https://example.com/a.png?x=1".to_string(),
+            ],
+        )
+        .unwrap();
+        let ingredients = manifest["ingredients"].as_array().unwrap();
+        assert_eq!(ingredients[0]["relationship"], "parentOf");
+        assert_eq!(ingredients[0]["file_path"], "s3://bucket/parent.jpg");
+        assert_eq!(ingredients[1]["relationship"], "componentOf");
+        assert_eq!(ingredients[1]["file_path"], "https://example.com/a.png?x=1");
+
+        assert!(add_ingredient_urls(&mut manifest, &["photo.jpg".to_string()]).is_err());
+        assert!(add_ingredient_urls(
+            &mut manifest,
+            &["siblingOf=https://e.com/a.jpg".to_string()]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,569 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Minimal read-only JUMBF (ISO/IEC 19566-5) walker for the C2PA manifest store.
+//!
+//! c2pa-rs decodes the store for us, but some reports need the raw boxes it does not expose
+//! (e.g. the COSE_Sign1 bytes of a claim signature).
+
+use crate::error::{bail, format_err, Context, Result};
+use std::fs;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// A JUMBF superbox (`jumb`) with its description label and child boxes.
+#[derive(Debug, Clone)]
+pub struct Superbox<'a> {
+    /// Label from the `jumd` description box (e.g. "c2pa", a manifest URN, "c2pa.signature").
+    pub label: Option<String>,
+    /// Child boxes in document order (excluding the description box).
+    pub children: Vec<JumbfBox<'a>>,
+}
+
+/// A box inside a superbox: either a nested superbox or a content box with its raw payload.
+#[derive(Debug, Clone)]
+pub enum JumbfBox<'a> {
+    Superbox(Superbox<'a>),
+    Content { box_type: [u8; 4], data: &'a [u8] },
+}
+
+impl<'a> Superbox<'a> {
+    /// Child superboxes only.
+    pub fn superboxes(&self) -> impl Iterator<Item = &Superbox<'a>> {
+        self.children.iter().filter_map(|c| match c {
+            JumbfBox::Superbox(sb) => Some(sb),
+            JumbfBox::Content { .. } => None,
+        })
+    }
+
+    /// First child superbox with the given label.
+    pub fn child(&self, label: &str) -> Option<&Superbox<'a>> {
+        self.superboxes()
+            .find(|sb| sb.label.as_deref() == Some(label))
+    }
+
+    /// First content box of the given type (e.g. `b"cbor"`).
+    pub fn content(&self, box_type: &[u8; 4]) -> Option<&'a [u8]> {
+        self.children.iter().find_map(|c| match c {
+            JumbfBox::Content { box_type: t, data } if t == box_type => Some(*data),
+            _ => None,
+        })
+    }
+}
+
+/// Split a byte range into consecutive ISO BMFF-style boxes: (type, payload).
+fn split_boxes(mut data: &[u8]) -> Result<Vec<([u8; 4], &[u8])>> {
+    let mut out = Vec::new();
+    while !data.is_empty() {
+        if data.len() < 8 {
+            bail!("Truncated JUMBF box header ({} bytes left)", data.len());
+        }
+        let lbox = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as u64;
+        let box_type = [data[4], data[5], data[6], data[7]];
+        let (header_len, box_len) = match lbox {
+            0 => (8u64, data.len() as u64),
+            1 => {
+                if data.len() < 16 {
+                    bail!("Truncated JUMBF extended box header");
+                }
+                let mut xl = [0u8; 8];
+                xl.copy_from_slice(&data[8..16]);
+                (16u64, u64::from_be_bytes(xl))
+            }
+            n => (8u64, n),
+        };
+        if box_len < header_len || box_len > data.len() as u64 {
+            bail!(
+                "Invalid JUMBF box length {} for box {:?}",
+                box_len,
+                String::from_utf8_lossy(&box_type)
+            );
+        }
+        out.push((box_type, &data[header_len as usize..box_len as usize]));
+        data = &data[box_len as usize..];
+    }
+    Ok(out)
+}
+
+/// Label from a `jumd` description box payload (UUID, toggles, optional null-terminated label).
+fn description_label(jumd: &[u8]) -> Option<String> {
+    const LABEL_PRESENT: u8 = 0x02;
+    let toggles = *jumd.get(16)?;
+    if toggles & LABEL_PRESENT == 0 {
+        return None;
+    }
+    let rest = &jumd[17..];
+    let end = rest.iter().position(|b| *b == 0).unwrap_or(rest.len());
+    Some(String::from_utf8_lossy(&rest[..end]).into_owned())
+}
+
+/// Parse the payload of a `jumb` superbox.
+fn parse_superbox(payload: &[u8]) -> Result<Superbox<'_>> {
+    let mut label = None;
+    let mut children = Vec::new();
+    for (i, (box_type, data)) in split_boxes(payload)?.into_iter().enumerate() {
+        match &box_type {
+            b"jumd" if i == 0 => label = description_label(data),
+            b"jumb" => children.push(JumbfBox::Superbox(parse_superbox(data)?)),
+            _ => children.push(JumbfBox::Content { box_type, data }),
+        }
+    }
+    Ok(Superbox { label, children })
+}
+
+/// Parse a serialized C2PA manifest store (the outer `jumb` box labelled "c2pa").
+pub fn parse_manifest_store(store: &[u8]) -> Result<Superbox<'_>> {
+    let boxes = split_boxes(store)?;
+    let (box_type, payload) = boxes
+        .first()
+        .context("Manifest store is empty (no JUMBF boxes)")?;
+    if box_type != b"jumb" {
+        bail!(
+            "Manifest store does not start with a JUMBF superbox (found {:?})",
+            String::from_utf8_lossy(box_type)
+        );
+    }
+    parse_superbox(payload)
+}
+
+/// Raw COSE_Sign1 bytes of the claim signature for the manifest with the given label.
+pub fn manifest_signature_bytes<'a>(
+    store: &Superbox<'a>,
+    manifest_label: &str,
+) -> Option<&'a [u8]> {
+    store
+        .child(manifest_label)?
+        .child("c2pa.signature")?
+        .content(b"cbor")
+}
+
+/// Read the raw JUMBF manifest store embedded in an asset file (format from the extension), or
+/// the detached store of a text document (see [`crate::documents`]). The store's size is checked
+/// against [`crate::limits::extraction_limits`] before it is read (see [`check_store_size`]).
+pub fn load_manifest_store<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let path = path.as_ref();
+    if crate::documents::is_text_document_path(path) {
+        return crate::documents::load_detached_store(path);
+    }
+    let format = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .context("Asset file has no extension")?;
+    let mut file = fs::File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    check_store_size(&format, &mut file)?;
+    c2pa::load_jumbf_from_stream(&format, &mut file)
+        .map_err(|e| format_err!("Failed to read C2PA manifest store: {}", e))
+}
+
+/// Read a manifest store file (a sidecar or detached `.c2pa` store), refusing one over
+/// [`crate::limits::extraction_limits`] before reading it.
+pub fn read_store_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let path = path.as_ref();
+    let len = fs::metadata(path)
+        .with_context(|| format!("Failed to read {:?}", path))?
+        .len();
+    crate::limits::extraction_limits().check_store_size(len)?;
+    fs::read(path).with_context(|| format!("Failed to read {:?}", path))
+}
+
+/// Size of the manifest store embedded in an asset, as declared by the container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreSize {
+    /// The container has no C2PA store.
+    Absent,
+    /// Length in bytes from the store's segment, chunk or box header.
+    Declared(u64),
+    /// The store of this format cannot be located from headers alone.
+    Unknown,
+}
+
+/// ISO BMFF `uuid` box type of a C2PA manifest store.
+const C2PA_BMFF_UUID: [u8; 16] = [
+    0xd8, 0xfe, 0xc3, 0xd6, 0x1b, 0x0e, 0x48, 0x3c, 0x92, 0x97, 0x58, 0x28, 0x87, 0x7e, 0xc4, 0x81,
+];
+
+/// Bytes read from the start of a store to recognise it: the superbox header with an extended
+/// length, then the `jumd` header, UUID, toggles and the "c2pa" label.
+const STORE_HEADER_PEEK: usize = 16 + 8 + 16 + 1 + 5;
+
+fn read_bytes<R: Read, const N: usize>(r: &mut R) -> std::io::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    r.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Up to `max` bytes from the reader, fewer at the end of the stream.
+fn peek<R: Read>(r: &mut R, max: usize) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(max);
+    r.take(max as u64).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// `None` at the end of the stream, so a container that simply ends has no store.
+fn read_header<R: Read, const N: usize>(r: &mut R) -> Result<Option<[u8; N]>> {
+    match read_bytes(r) {
+        Ok(header) => Ok(Some(header)),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Declared length of the JUMBF superbox starting `header` when it is the C2PA manifest store
+/// (a `jumb` box labelled "c2pa").
+fn c2pa_superbox_len(header: &[u8]) -> Result<Option<u64>> {
+    if header.len() < 8 || &header[4..8] != b"jumb" {
+        return Ok(None);
+    }
+    let lbox = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    let header_len = if lbox == 1 { 16 } else { 8 };
+    let Some(jumd) = header.get(header_len..).filter(|d| d.len() >= 8) else {
+        return Ok(None);
+    };
+    if &jumd[4..8] != b"jumd" || description_label(&jumd[8..]).as_deref() != Some("c2pa") {
+        return Ok(None);
+    }
+    match lbox {
+        0 => bail!("C2PA manifest store has no declared length"),
+        1 => {
+            let mut xl = [0u8; 8];
+            xl.copy_from_slice(&header[8..16]);
+            Ok(Some(u64::from_be_bytes(xl)))
+        }
+        n => Ok(Some(n as u64)),
+    }
+}
+
+/// JPEG: the store is split over APP11 (JPEG XT) segments; the first one (sequence number 1)
+/// starts with the superbox header holding the length of the whole store.
+fn jpeg_store_size<R: Read + Seek>(r: &mut R) -> Result<StoreSize> {
+    if read_bytes::<_, 2>(r)? != [0xff, 0xd8] {
+        bail!("Not a JPEG file (no SOI marker)");
+    }
+    loop {
+        // Image data follows the application segments, so a JPEG cannot end before it
+        let [prefix, mut marker] = read_bytes::<_, 2>(r)?;
+        if prefix != 0xff {
+            bail!("Malformed JPEG: expected a marker, found 0x{prefix:02x}");
+        }
+        while marker == 0xff {
+            marker = read_bytes::<_, 1>(r)?[0];
+        }
+        match marker {
+            // End of image or start of scan: application segments come before the image data
+            0xd9 | 0xda => return Ok(StoreSize::Absent),
+            // Markers without a length
+            0x01 | 0xd0..=0xd7 => continue,
+            _ => {}
+        }
+        let len = u16::from_be_bytes(read_bytes(r)?) as u64;
+        if len < 2 {
+            bail!("Malformed JPEG: segment length {len}");
+        }
+        let start = r.stream_position()?;
+        if marker == 0xeb {
+            // Common identifier "JP", box instance number, packet sequence number, then JUMBF
+            let payload = peek(r, (len as usize - 2).min(8 + STORE_HEADER_PEEK))?;
+            if payload.len() > 8 && &payload[0..2] == b"JP" && payload[4..8] == 1u32.to_be_bytes() {
+                if let Some(size) = c2pa_superbox_len(&payload[8..])? {
+                    return Ok(StoreSize::Declared(size));
+                }
+            }
+        }
+        r.seek(SeekFrom::Start(start + len - 2))?;
+    }
+}
+
+/// PNG: the store is the payload of the `caBX` chunk.
+fn png_store_size<R: Read + Seek>(r: &mut R) -> Result<StoreSize> {
+    if &read_bytes::<_, 8>(r)? != b"\x89PNG\r\n\x1a\n" {
+        bail!("Not a PNG file (bad signature)");
+    }
+    while let Some(header) = read_header::<_, 8>(r)? {
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as i64;
+        match &header[4..8] {
+            b"caBX" => return Ok(StoreSize::Declared(len as u64)),
+            b"IEND" => break,
+            // Skip the data and the CRC
+            _ => r.seek(SeekFrom::Current(len + 4))?,
+        };
+    }
+    Ok(StoreSize::Absent)
+}
+
+/// ISO BMFF: the store follows a short preamble in a top-level `uuid` box with the C2PA UUID
+/// and the purpose "manifest".
+fn bmff_store_size<R: Read + Seek>(r: &mut R) -> Result<StoreSize> {
+    // Version and flags, the purpose, and the offset of the first Merkle box
+    const PREAMBLE: u64 = 4 + 9 + 8;
+    let end = r.seek(SeekFrom::End(0))?;
+    let mut pos = 0;
+    while pos + 8 <= end {
+        r.seek(SeekFrom::Start(pos))?;
+        let header: [u8; 8] = read_bytes(r)?;
+        let (header_len, size) =
+            match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+                0 => (8, end - pos),
+                1 => (16, u64::from_be_bytes(read_bytes(r)?)),
+                n => (8, n as u64),
+            };
+        if size < header_len {
+            bail!("Malformed ISO BMFF: box size {size} at offset {pos}");
+        }
+        if &header[4..8] == b"uuid" && read_bytes::<_, 16>(r)? == C2PA_BMFF_UUID {
+            let preamble = peek(r, 13)?;
+            if preamble.get(4..) == Some(&b"manifest\0"[..]) {
+                return Ok(StoreSize::Declared(
+                    size.saturating_sub(header_len + 16 + PREAMBLE),
+                ));
+            }
+        }
+        pos = pos.saturating_add(size);
+    }
+    Ok(StoreSize::Absent)
+}
+
+/// RIFF (WebP, WAV, AVI): the store is the payload of a top-level `C2PA` chunk.
+fn riff_store_size<R: Read + Seek>(r: &mut R) -> Result<StoreSize> {
+    let header: [u8; 12] = read_bytes(r)?;
+    if &header[0..4] != b"RIFF" {
+        bail!("Not a RIFF file");
+    }
+    let riff_end = 8 + u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as u64;
+    let end = r.seek(SeekFrom::End(0))?.min(riff_end);
+    let mut pos = 12;
+    while pos + 8 <= end {
+        r.seek(SeekFrom::Start(pos))?;
+        let chunk: [u8; 8] = read_bytes(r)?;
+        let size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]) as u64;
+        if &chunk[0..4] == b"C2PA" {
+            return Ok(StoreSize::Declared(size));
+        }
+        // Chunks are padded to an even length
+        pos += 8 + size + (size & 1);
+    }
+    Ok(StoreSize::Absent)
+}
+
+/// Size of the manifest store embedded in `stream`, an asset of type `format` (an extension or
+/// MIME type), read from the container's segment, chunk or box length headers without reading
+/// the store itself. Supports JPEG, PNG, ISO BMFF and RIFF containers and bare `.c2pa` stores;
+/// other formats are [`StoreSize::Unknown`]. The stream is left at an unspecified position.
+pub fn embedded_store_size<R: Read + Seek>(format: &str, stream: &mut R) -> Result<StoreSize> {
+    let Some(format) = crate::asset_format_for_type(format) else {
+        return Ok(StoreSize::Unknown);
+    };
+    stream.rewind()?;
+    let size = match format.mime {
+        "image/jpeg" => jpeg_store_size(stream),
+        "image/png" => png_store_size(stream),
+        "image/webp" | "audio/wav" | "video/msvideo" => riff_store_size(stream),
+        "application/c2pa" => Ok(StoreSize::Declared(stream.seek(SeekFrom::End(0))?)),
+        _ if format.is_bmff() => bmff_store_size(stream),
+        _ => Ok(StoreSize::Unknown),
+    };
+    // A container that ends inside a header is malformed; report it rather than skip the check
+    size.map_err(|e| match e {
+        crate::error::CrtoolError::Io(io) if io.kind() == ErrorKind::UnexpectedEof => {
+            format_err!(
+                "Truncated {} file: cannot locate the C2PA manifest store",
+                format.extension
+            )
+        }
+        e => e,
+    })
+}
+
+/// Fail when the manifest store embedded in `stream` (see [`embedded_store_size`]) is over
+/// [`crate::limits::extraction_limits`], before anything reads the store. Malformed containers
+/// fail too, so the check is never skipped. For formats whose store cannot be located from
+/// headers, the store is loaded to measure it, but only when the asset itself is over the limit.
+/// Leaves the stream rewound.
+pub fn check_store_size<R: Read + Seek>(format: &str, stream: &mut R) -> Result<()> {
+    let limits = crate::limits::extraction_limits();
+    let size = match embedded_store_size(format, stream)? {
+        StoreSize::Absent => None,
+        StoreSize::Declared(len) => Some(len),
+        // The store cannot be larger than the asset it is embedded in
+        StoreSize::Unknown if stream.seek(SeekFrom::End(0))? <= limits.max_manifest_store_bytes => {
+            None
+        }
+        StoreSize::Unknown => {
+            stream.rewind()?;
+            match c2pa::load_jumbf_from_stream(format, stream) {
+                Ok(store) => Some(store.len() as u64),
+                Err(c2pa::Error::JumbfNotFound | c2pa::Error::ProvenanceMissing) => None,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    };
+    stream.rewind()?;
+    if let Some(len) = size {
+        limits.check_store_size(len)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jbox(box_type: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut out = ((payload.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(box_type);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn superbox(label: &str, children: &[Vec<u8>]) -> Vec<u8> {
+        let mut jumd = vec![0u8; 16];
+        jumd.push(0x03);
+        jumd.extend_from_slice(label.as_bytes());
+        jumd.push(0);
+        let mut payload = jbox(b"jumd", &jumd);
+        for c in children {
+            payload.extend_from_slice(c);
+        }
+        jbox(b"jumb", &payload)
+    }
+
+    #[test]
+    fn test_manifest_signature_bytes() {
+        let sig = superbox("c2pa.signature", &[jbox(b"cbor", &[0xd2, 0x84])]);
+        let manifest = superbox("urn:c2pa:1234", &[sig]);
+        let store_bytes = superbox("c2pa", &[manifest]);
+
+        let store = parse_manifest_store(&store_bytes).unwrap();
+        assert_eq!(store.label.as_deref(), Some("c2pa"));
+        assert_eq!(
+            manifest_signature_bytes(&store, "urn:c2pa:1234"),
+            Some(&[0xd2u8, 0x84][..])
+        );
+        assert!(manifest_signature_bytes(&store, "urn:c2pa:other").is_none());
+    }
+
+    #[test]
+    fn test_truncated_store_is_error() {
+        assert!(parse_manifest_store(&[0, 0, 0, 40, b'j', b'u', b'm', b'b']).is_err());
+    }
+
+    /// Start of a C2PA store declaring `len` bytes; the rest of the store is not there.
+    fn store_header(len: u32) -> Vec<u8> {
+        let mut store = superbox("c2pa", &[]);
+        store[..4].copy_from_slice(&len.to_be_bytes());
+        store
+    }
+
+    fn jpeg_with_store(header: &[u8]) -> Vec<u8> {
+        let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe0, 0, 4, 0, 0];
+        jpeg.extend_from_slice(&[0xff, 0xeb]);
+        jpeg.extend_from_slice(&((2 + 8 + header.len()) as u16).to_be_bytes());
+        jpeg.extend_from_slice(b"JP\x00\x01\x00\x00\x00\x01");
+        jpeg.extend_from_slice(header);
+        jpeg.extend_from_slice(&[0xff, 0xda, 0, 2, 0xff, 0xd9]);
+        jpeg
+    }
+
+    fn png_with_store(header: &[u8], declared: u32) -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend_from_slice(&13u32.to_be_bytes());
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&[0; 13 + 4]);
+        png.extend_from_slice(&declared.to_be_bytes());
+        png.extend_from_slice(b"caBX");
+        png.extend_from_slice(header);
+        png
+    }
+
+    fn mp4_with_store(declared: u32) -> Vec<u8> {
+        let mut mp4 = jbox(b"ftyp", b"isom\0\0\0\0");
+        mp4.extend_from_slice(&declared.to_be_bytes());
+        mp4.extend_from_slice(b"uuid");
+        mp4.extend_from_slice(&C2PA_BMFF_UUID);
+        mp4.extend_from_slice(&[0; 4]);
+        mp4.extend_from_slice(b"manifest\0");
+        mp4.extend_from_slice(&[0; 8]);
+        mp4.extend_from_slice(&store_header(1000));
+        mp4
+    }
+
+    fn webp_with_store(declared: u32) -> Vec<u8> {
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8 \x02\0\0\0\0\0".to_vec();
+        webp.extend_from_slice(b"C2PA");
+        webp.extend_from_slice(&declared.to_le_bytes());
+        webp.extend_from_slice(&store_header(declared));
+        let riff_len = (webp.len() - 8) as u32;
+        webp[4..8].copy_from_slice(&riff_len.to_le_bytes());
+        webp
+    }
+
+    fn size_of(format: &str, data: &[u8]) -> Result<StoreSize> {
+        embedded_store_size(format, &mut std::io::Cursor::new(data))
+    }
+
+    #[test]
+    fn test_embedded_store_size_from_headers() {
+        let big = 200 * 1024 * 1024;
+        let jpeg = jpeg_with_store(&store_header(big));
+        assert_eq!(
+            size_of("jpg", &jpeg).unwrap(),
+            StoreSize::Declared(big as u64)
+        );
+        assert_eq!(
+            size_of("image/png", &png_with_store(&store_header(big), big)).unwrap(),
+            StoreSize::Declared(big as u64)
+        );
+        let box_overhead = 8 + 16 + 4 + 9 + 8;
+        assert_eq!(
+            size_of("mp4", &mp4_with_store(1000 + box_overhead)).unwrap(),
+            StoreSize::Declared(1000)
+        );
+        assert_eq!(
+            size_of("webp", &webp_with_store(64)).unwrap(),
+            StoreSize::Declared(64)
+        );
+
+        // An APP11 segment that is not a C2PA store, and no store at all
+        let other = jpeg_with_store(&superbox("jp360", &[]));
+        assert_eq!(size_of("jpg", &other).unwrap(), StoreSize::Absent);
+        assert_eq!(
+            size_of("jpg", &[0xff, 0xd8, 0xff, 0xd9]).unwrap(),
+            StoreSize::Absent
+        );
+        assert_eq!(size_of("gif", b"GIF89a").unwrap(), StoreSize::Unknown);
+    }
+
+    /// A store declared over the limit is refused from its header alone, and a container too
+    /// malformed to measure fails rather than skipping the check.
+    #[test]
+    fn test_check_store_size_fails_closed() {
+        let limit = crate::limits::extraction_limits().max_manifest_store_bytes as u32;
+        let over = jpeg_with_store(&store_header(limit + 1));
+        let err = check_store_size("jpg", &mut std::io::Cursor::new(&over)).unwrap_err();
+        let exceeded = err.limit_exceeded().expect("a limit error");
+        assert_eq!(exceeded.actual, Some(limit as u64 + 1));
+        let over = png_with_store(&store_header(limit + 1), limit + 1);
+        assert!(check_store_size("png", &mut std::io::Cursor::new(&over))
+            .unwrap_err()
+            .limit_exceeded()
+            .is_some());
+
+        let under = jpeg_with_store(&store_header(1024));
+        let mut stream = std::io::Cursor::new(&under);
+        check_store_size("jpg", &mut stream).unwrap();
+        assert_eq!(stream.position(), 0);
+
+        let truncated = &jpeg_with_store(&store_header(1024))[..9];
+        let err = check_store_size("jpg", &mut std::io::Cursor::new(truncated)).unwrap_err();
+        assert!(err.to_string().contains("Truncated"), "{err}");
+    }
+}
@@ -0,0 +1,357 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Signing keys and certificates: parsing PEM private keys (PKCS#8, SEC1 `EC PRIVATE KEY` and
+//! PKCS#1 `RSA PRIVATE KEY`), choosing the signing algorithm for a certificate's key, and
+//! checking that a private key belongs to its certificate before signing.
+//!
+//! RSA keys sign with PS256, PS384 or PS512; [`rsa_signing_algorithm`] picks one by key size
+//! when the algorithm is detected from a certificate.
+
+use crate::error::{bail, format_err, Context, Result};
+use c2pa::SigningAlg;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::pkcs8::{DecodePrivateKey, PrivateKeyInfo};
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::traits::PublicKeyParts;
+use std::fs;
+use std::path::Path;
+use x509_parser::certificate::X509Certificate;
+use x509_parser::prelude::FromDer;
+use x509_parser::public_key::PublicKey;
+use x509_parser::x509::SubjectPublicKeyInfo;
+
+const OID_EC_PUBLIC_KEY: &str = "1.2.840.10045.2.1";
+const OID_RSA_ENCRYPTION: &str = "1.2.840.113549.1.1.1";
+const OID_RSASSA_PSS: &str = "1.2.840.113549.1.1.10";
+const OID_ED25519: &str = "1.3.101.112";
+const OID_P256: &str = "1.2.840.10045.3.1.7";
+const OID_P384: &str = "1.3.132.0.34";
+const OID_P521: &str = "1.3.132.0.35";
+
+/// Smallest RSA key, in bits, signed with PS384 by [`rsa_signing_algorithm`].
+pub const RSA_PS384_MIN_BITS: usize = 3072;
+
+/// Smallest RSA key, in bits, signed with PS512 by [`rsa_signing_algorithm`].
+pub const RSA_PS512_MIN_BITS: usize = 4096;
+
+/// Signing algorithm for an RSA key of `bits`: PS256 below 3072 bits, PS384 below 4096 bits,
+/// PS512 from 4096 bits, so the hash strength grows with the key.
+pub fn rsa_signing_algorithm(bits: usize) -> SigningAlg {
+    match bits {
+        b if b >= RSA_PS512_MIN_BITS => SigningAlg::Ps512,
+        b if b >= RSA_PS384_MIN_BITS => SigningAlg::Ps384,
+        _ => SigningAlg::Ps256,
+    }
+}
+
+/// Signing algorithm for a certificate's public key: ES256/ES384/ES512 by EC curve, Ed25519,
+/// or PS256/PS384/PS512 by RSA key size ([`rsa_signing_algorithm`]).
+pub fn certificate_signing_algorithm(public_key: &SubjectPublicKeyInfo) -> Result<SigningAlg> {
+    match public_key.algorithm.algorithm.to_id_string().as_str() {
+        OID_EC_PUBLIC_KEY => {
+            let Some(params) = &public_key.algorithm.parameters else {
+                bail!("EC key missing curve parameters")
+            };
+            let curve_oid = params
+                .as_oid()
+                .map_err(|_| format_err!("Failed to parse curve OID"))?;
+            match curve_oid.to_id_string().as_str() {
+                OID_P256 => Ok(SigningAlg::Es256),
+                OID_P384 => Ok(SigningAlg::Es384),
+                OID_P521 => Ok(SigningAlg::Es512),
+                other => bail!(
+                    "Unsupported EC curve OID: {} (C2PA signs with P-256, P-384 or P-521)",
+                    other
+                ),
+            }
+        }
+        OID_RSA_ENCRYPTION | OID_RSASSA_PSS => match public_key.parsed() {
+            Ok(PublicKey::RSA(rsa)) => Ok(rsa_signing_algorithm(rsa.key_size())),
+            _ => Ok(SigningAlg::Ps256),
+        },
+        OID_ED25519 => Ok(SigningAlg::Ed25519),
+        other => bail!("Unsupported public key algorithm OID: {}", other),
+    }
+}
+
+/// A private signing key.
+pub enum PrivateKey {
+    Ed25519(ed25519_dalek::SigningKey),
+    P256(p256::SecretKey),
+    P384(p384::SecretKey),
+    P521(p521::SecretKey),
+    Rsa(rsa::RsaPrivateKey),
+}
+
+impl std::fmt::Debug for PrivateKey {
+    /// The kind of key only, never key material.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PrivateKey({})", self.description())
+    }
+}
+
+impl PrivateKey {
+    /// Parse the first private key of a PEM file: `PRIVATE KEY` (PKCS#8 v1 or v2), `EC PRIVATE
+    /// KEY` (SEC1) or `RSA PRIVATE KEY` (PKCS#1). Other blocks before it, such as the `EC
+    /// PARAMETERS` written by `openssl ecparam -genkey`, are skipped.
+    pub fn from_pem(data: &[u8]) -> Result<Self> {
+        let blocks = ::pem::parse_many(data)
+            .map_err(|e| format_err!("Failed to parse private key PEM: {}", e))?;
+        let Some(block) = blocks.iter().find(|b| b.tag().ends_with("PRIVATE KEY")) else {
+            match blocks.first() {
+                Some(block) => bail!(
+                    "Expected a PEM private key, found `{}`; the key file and the certificate \
+                    file may be swapped",
+                    block.tag()
+                ),
+                None => bail!("No PEM private key found"),
+            }
+        };
+        match block.tag() {
+            "PRIVATE KEY" => Self::from_pkcs8_der(block.contents()),
+            "EC PRIVATE KEY" => Self::from_sec1_der(block.contents()),
+            "RSA PRIVATE KEY" => rsa::RsaPrivateKey::from_pkcs1_der(block.contents())
+                .map(Self::Rsa)
+                .map_err(|e| format_err!("Failed to parse PKCS#1 RSA private key: {}", e)),
+            "ENCRYPTED PRIVATE KEY" => bail!(
+                "The private key is encrypted; decrypt it first, e.g. `openssl pkcs8 -in key.pem \
+                -out key-decrypted.pem`"
+            ),
+            other => bail!("Unsupported private key type `{}`", other),
+        }
+    }
+
+    /// [`PrivateKey::from_pem`] of a file.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let data = fs::read(path)
+            .with_context(|| format!("Failed to read private key file {:?}", path))?;
+        Self::from_pem(&data).with_context(|| format!("Invalid private key file {:?}", path))
+    }
+
+    /// Parse a DER PKCS#8 `PrivateKeyInfo` (v1, or v2 with the public key).
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self> {
+        let info = PrivateKeyInfo::try_from(der)
+            .map_err(|e| format_err!("Failed to parse PKCS#8 private key: {}", e))?;
+        let invalid = |e: p256::pkcs8::Error| format_err!("Invalid PKCS#8 private key: {}", e);
+        match info.algorithm.oid.to_string().as_str() {
+            OID_ED25519 => {
+                // CurvePrivateKey: an OCTET STRING holding the 32-byte seed
+                let seed: &[u8; 32] = match info.private_key {
+                    [0x04, 0x20, seed @ ..] => seed.try_into().ok(),
+                    _ => None,
+                }
+                .context("Invalid Ed25519 private key: expected a 32-byte seed")?;
+                Ok(Self::Ed25519(ed25519_dalek::SigningKey::from_bytes(seed)))
+            }
+            OID_EC_PUBLIC_KEY => {
+                let curve = info
+                    .algorithm
+                    .parameters_oid()
+                    .map_err(|_| format_err!("EC private key missing curve parameters"))?;
+                match curve.to_string().as_str() {
+                    OID_P256 => p256::SecretKey::from_pkcs8_der(der)
+                        .map(Self::P256)
+                        .map_err(invalid),
+                    OID_P384 => p384::SecretKey::from_pkcs8_der(der)
+                        .map(Self::P384)
+                        .map_err(invalid),
+                    OID_P521 => p521::SecretKey::from_pkcs8_der(der)
+                        .map(Self::P521)
+                        .map_err(invalid),
+                    other => bail!(
+                        "Unsupported EC curve OID: {} (C2PA signs with P-256, P-384 or P-521)",
+                        other
+                    ),
+                }
+            }
+            OID_RSA_ENCRYPTION => rsa::RsaPrivateKey::from_pkcs8_der(der)
+                .map(Self::Rsa)
+                .map_err(invalid),
+            OID_RSASSA_PSS => bail!(
+                "RSASSA-PSS-restricted private keys are not supported; convert the key to a \
+                plain RSA key, e.g. `openssl pkey -in key.pem -traditional`"
+            ),
+            other => bail!("Unsupported private key algorithm OID: {}", other),
+        }
+    }
+
+    /// Parse a DER SEC1 `ECPrivateKey` on P-256, P-384 or P-521.
+    pub fn from_sec1_der(der: &[u8]) -> Result<Self> {
+        p256::SecretKey::from_sec1_der(der)
+            .map(Self::P256)
+            .or_else(|_| p384::SecretKey::from_sec1_der(der).map(Self::P384))
+            .or_else(|_| p521::SecretKey::from_sec1_der(der).map(Self::P521))
+            .map_err(|_| format_err!("Failed to parse SEC1 EC private key (P-256, P-384 or P-521)"))
+    }
+
+    /// Kind of key for messages, e.g. `EC P-384` or `RSA 3072-bit`.
+    pub fn description(&self) -> String {
+        match self {
+            Self::Ed25519(_) => "Ed25519".to_string(),
+            Self::P256(_) => "EC P-256".to_string(),
+            Self::P384(_) => "EC P-384".to_string(),
+            Self::P521(_) => "EC P-521".to_string(),
+            Self::Rsa(key) => format!("RSA {}-bit", key.size() * 8),
+        }
+    }
+
+    /// The signing algorithm for this key, as [`certificate_signing_algorithm`] detects it.
+    pub fn signing_algorithm(&self) -> SigningAlg {
+        match self {
+            Self::Ed25519(_) => SigningAlg::Ed25519,
+            Self::P256(_) => SigningAlg::Es256,
+            Self::P384(_) => SigningAlg::Es384,
+            Self::P521(_) => SigningAlg::Es512,
+            Self::Rsa(key) => rsa_signing_algorithm(key.size() * 8),
+        }
+    }
+
+    /// Whether the key can sign with `alg`. EC keys sign with the algorithm of their curve; RSA
+    /// keys with any of PS256, PS384 and PS512.
+    pub fn supports(&self, alg: SigningAlg) -> bool {
+        match self {
+            Self::Rsa(_) => matches!(
+                alg,
+                SigningAlg::Ps256 | SigningAlg::Ps384 | SigningAlg::Ps512
+            ),
+            _ => self.signing_algorithm() == alg,
+        }
+    }
+
+    /// Whether this is the private key of `public_key`.
+    pub fn matches(&self, public_key: &SubjectPublicKeyInfo) -> bool {
+        let spki = public_key.subject_public_key.data.as_ref();
+        // certificates normally hold uncompressed points (0x04 prefix)
+        let compressed = spki.first().is_some_and(|b| *b != 0x04);
+        match self {
+            Self::Ed25519(key) => key.verifying_key().to_bytes() == spki,
+            Self::P256(key) => key.public_key().to_encoded_point(compressed).as_bytes() == spki,
+            Self::P384(key) => key.public_key().to_encoded_point(compressed).as_bytes() == spki,
+            Self::P521(key) => key.public_key().to_encoded_point(compressed).as_bytes() == spki,
+            Self::Rsa(key) => match public_key.parsed() {
+                Ok(PublicKey::RSA(rsa)) => {
+                    let trim = |b: &[u8]| -> Vec<u8> {
+                        b.iter().skip_while(|&&x| x == 0).copied().collect()
+                    };
+                    trim(rsa.modulus) == key.n().to_bytes_be()
+                        && trim(rsa.exponent) == key.e().to_bytes_be()
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Check, before signing, that the private key in `key_path` belongs to the first (signing)
+/// certificate in `cert_path` and can sign with `alg`, with an error saying what to change
+/// when it does not.
+pub fn check_key_pair(cert_path: &Path, key_path: &Path, alg: SigningAlg) -> Result<()> {
+    let cert_data = fs::read(cert_path)
+        .with_context(|| format!("Failed to read certificate file {:?}", cert_path))?;
+    let pem = ::pem::parse_many(&cert_data)
+        .map_err(|e| format_err!("Failed to parse certificate PEM: {}", e))?;
+    let Some(first) = pem.iter().find(|b| b.tag() == "CERTIFICATE") else {
+        match pem.first() {
+            Some(block) if block.tag().ends_with("PRIVATE KEY") => bail!(
+                "{:?} holds a private key, not a certificate; the certificate and key files \
+                may be swapped",
+                cert_path
+            ),
+            _ => bail!("No PEM certificate found in {:?}", cert_path),
+        }
+    };
+    let (_, cert) = X509Certificate::from_der(first.contents())
+        .map_err(|e| format_err!("Failed to parse X.509 certificate: {}", e))?;
+    let key = PrivateKey::from_file(key_path)?;
+
+    if !key.matches(cert.public_key()) {
+        bail!(
+            "The private key {:?} ({}) does not belong to the certificate {:?} (subject {}). Use \
+            the key the certificate was issued for, and list the signing certificate first in a \
+            chain file",
+            key_path,
+            key.description(),
+            cert_path,
+            cert.subject()
+        );
+    }
+    if !key.supports(alg) {
+        bail!(
+            "The {} key {:?} cannot sign with {}; use {} (or leave the algorithm out to detect \
+            it from the certificate)",
+            key.description(),
+            key_path,
+            alg,
+            key.signing_algorithm()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::certs::generate_test_credentials_for;
+
+    #[test]
+    fn test_rsa_signing_algorithm() {
+        assert_eq!(rsa_signing_algorithm(2048), SigningAlg::Ps256);
+        assert_eq!(rsa_signing_algorithm(3072), SigningAlg::Ps384);
+        assert_eq!(rsa_signing_algorithm(4096), SigningAlg::Ps512);
+        assert_eq!(rsa_signing_algorithm(8192), SigningAlg::Ps512);
+    }
+
+    #[test]
+    fn test_check_key_pair() {
+        let dir = std::env::temp_dir().join(format!("crtool-test-keys-{}", std::process::id()));
+        let ed =
+            generate_test_credentials_for(&dir.join("ed"), "Keys", SigningAlg::Ed25519).unwrap();
+        let es384 =
+            generate_test_credentials_for(&dir.join("es384"), "Keys", SigningAlg::Es384).unwrap();
+
+        // rcgen writes PKCS#8 v2 Ed25519 keys, with the public key after the seed
+        let key = PrivateKey::from_file(&ed.key).unwrap();
+        assert_eq!(key.signing_algorithm(), SigningAlg::Ed25519);
+        check_key_pair(&ed.cert_chain, &ed.key, SigningAlg::Ed25519).unwrap();
+        check_key_pair(&es384.cert_chain, &es384.key, SigningAlg::Es384).unwrap();
+
+        let err = check_key_pair(&es384.cert_chain, &es384.key, SigningAlg::Es256).unwrap_err();
+        assert!(err.to_string().contains("use es384"), "{err}");
+        let err = check_key_pair(&ed.cert_chain, &es384.key, SigningAlg::Es384).unwrap_err();
+        assert!(err.to_string().contains("does not belong"), "{err}");
+        let err = check_key_pair(&ed.key, &ed.cert_chain, SigningAlg::Ed25519).unwrap_err();
+        assert!(err.to_string().contains("swapped"), "{err}");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_sec1_key() {
+        let dir = std::env::temp_dir().join(format!("crtool-test-sec1-{}", std::process::id()));
+        let creds = generate_test_credentials_for(&dir, "Keys", SigningAlg::Es256).unwrap();
+        let pkcs8 = fs::read_to_string(&creds.key).unwrap();
+        let secret = p256::SecretKey::from_pkcs8_pem(&pkcs8).unwrap();
+        let sec1 = ::pem::encode(&::pem::Pem::new(
+            "EC PRIVATE KEY",
+            secret.to_sec1_der().unwrap().to_vec(),
+        ));
+        // as written by `openssl ecparam -genkey` without -noout
+        let params = ::pem::encode(&::pem::Pem::new("EC PARAMETERS", vec![0x06, 0x00]));
+        let sec1_path = dir.join("sec1.key");
+        fs::write(&sec1_path, format!("{params}{sec1}")).unwrap();
+
+        let key = PrivateKey::from_file(&sec1_path).unwrap();
+        assert!(matches!(key, PrivateKey::P256(_)));
+        check_key_pair(&creds.cert_chain, &sec1_path, SigningAlg::Es256).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,180 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Manifest label rules: validation of pinned manifest labels and vendor prefixes supplied in a
+//! manifest definition (`label`, `vendor`) before they are handed to the SDK.
+//!
+//! Accepted label forms:
+//! - `urn:c2pa:<uuid>[:<claim generator>[:<version>_<reason>]]` (C2PA 2.x)
+//! - `[<vendor>:]urn:uuid:<uuid>` (C2PA 1.x)
+
+use crate::error::{bail, format_err, Result};
+
+/// Longest vendor prefix / claim generator identifier accepted in a label.
+const MAX_VENDOR_LEN: usize = 64;
+
+/// True if `s` is a hyphenated UUID (`8-4-4-4-12` hex digits).
+fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Validate a vendor prefix (also used as the claim generator component of 2.x labels).
+/// Allowed: ASCII letters, digits, `.`, `_`, `-`; must start with a letter or digit; no `:`.
+pub fn validate_vendor_prefix(vendor: &str) -> Result<()> {
+    if vendor.is_empty() {
+        bail!("Vendor prefix must not be empty");
+    }
+    if vendor.len() > MAX_VENDOR_LEN {
+        bail!(
+            "Vendor prefix '{}' is longer than {} characters",
+            vendor,
+            MAX_VENDOR_LEN
+        );
+    }
+    if !vendor.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        bail!(
+            "Vendor prefix '{}' must start with a letter or digit",
+            vendor
+        );
+    }
+    if let Some(c) = vendor
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')))
+    {
+        bail!(
+            "Vendor prefix '{}' contains invalid character '{}'",
+            vendor,
+            c
+        );
+    }
+    if vendor.to_ascii_lowercase().starts_with("urn") || vendor == "c2pa" {
+        bail!("Vendor prefix '{}' is reserved", vendor);
+    }
+    Ok(())
+}
+
+/// Validate a pinned manifest label against the C2PA manifest label formats.
+pub fn validate_manifest_label(label: &str) -> Result<()> {
+    if let Some(rest) = label.strip_prefix("urn:c2pa:") {
+        let mut parts = rest.split(':');
+        let uuid = parts.next().unwrap_or_default();
+        if !is_uuid(uuid) {
+            bail!("Manifest label '{}' does not contain a valid UUID", label);
+        }
+        if let Some(generator) = parts.next() {
+            validate_vendor_prefix(generator)
+                .map_err(|e| format_err!("Manifest label '{}': {}", label, e))?;
+        }
+        if let Some(version_reason) = parts.next() {
+            let valid = version_reason
+                .split_once('_')
+                .is_some_and(|(v, r)| v.parse::<u32>().is_ok() && r.parse::<u32>().is_ok());
+            if !valid {
+                bail!(
+                    "Manifest label '{}' has an invalid version/reason component '{}' \
+                    (expected <version>_<reason>)",
+                    label,
+                    version_reason
+                );
+            }
+        }
+        if parts.next().is_some() {
+            bail!("Manifest label '{}' has too many components", label);
+        }
+        return Ok(());
+    }
+
+    let (vendor, rest) = match label.find("urn:uuid:") {
+        Some(0) => (None, label),
+        Some(i) if label[..i].ends_with(':') => (Some(&label[..i - 1]), &label[i..]),
+        _ => bail!(
+            "Manifest label '{}' must be of the form 'urn:c2pa:<uuid>[:<generator>]' or \
+            '[<vendor>:]urn:uuid:<uuid>'",
+            label
+        ),
+    };
+    if let Some(vendor) = vendor {
+        validate_vendor_prefix(vendor)
+            .map_err(|e| format_err!("Manifest label '{}': {}", label, e))?;
+    }
+    if !is_uuid(&rest["urn:uuid:".len()..]) {
+        bail!("Manifest label '{}' does not contain a valid UUID", label);
+    }
+    Ok(())
+}
+
+/// Validate the optional `vendor` and `label` fields of a manifest definition, and check that a
+/// pinned label agrees with the vendor prefix when both are given.
+pub fn validate_manifest_naming(manifest: &serde_json::Value) -> Result<()> {
+    let vendor = manifest.get("vendor").and_then(|v| v.as_str());
+    let label = manifest.get("label").and_then(|v| v.as_str());
+
+    if let Some(vendor) = vendor {
+        validate_vendor_prefix(vendor)?;
+    }
+    if let Some(label) = label {
+        validate_manifest_label(label)?;
+        if let Some(vendor) = vendor {
+            let matches_vendor = label.starts_with(&format!("{}:urn:uuid:", vendor))
+                || label
+                    .strip_prefix("urn:c2pa:")
+                    .and_then(|rest| rest.split(':').nth(1))
+                    == Some(vendor);
+            if !matches_vendor {
+                bail!(
+                    "Manifest label '{}' does not use the vendor prefix '{}'",
+                    label,
+                    vendor
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "3fa85f64-5717-4562-b3fc-2c963f66afa6";
+
+    #[test]
+    fn test_validate_manifest_label() {
+        assert!(validate_manifest_label(&format!("urn:c2pa:{UUID}")).is_ok());
+        assert!(validate_manifest_label(&format!("urn:c2pa:{UUID}:acme")).is_ok());
+        assert!(validate_manifest_label(&format!("urn:c2pa:{UUID}:acme:1_1")).is_ok());
+        assert!(validate_manifest_label(&format!("urn:uuid:{UUID}")).is_ok());
+        assert!(validate_manifest_label(&format!("acme:urn:uuid:{UUID}")).is_ok());
+
+        assert!(validate_manifest_label("urn:c2pa:not-a-uuid").is_err());
+        assert!(validate_manifest_label(&format!("urn:c2pa:{UUID}:acme:x")).is_err());
+        assert!(validate_manifest_label(&format!("ac me:urn:uuid:{UUID}")).is_err());
+        assert!(validate_manifest_label("my-manifest").is_err());
+    }
+
+    #[test]
+    fn test_validate_manifest_naming() {
+        let ok = serde_json::json!({"vendor": "acme", "label": format!("urn:c2pa:{UUID}:acme")});
+        assert!(validate_manifest_naming(&ok).is_ok());
+
+        let mismatch = serde_json::json!({"vendor": "acme", "label": format!("urn:uuid:{UUID}")});
+        assert!(validate_manifest_naming(&mismatch).is_err());
+
+        let bad_vendor = serde_json::json!({"vendor": "acme:corp"});
+        assert!(validate_manifest_naming(&bad_vendor).is_err());
+    }
+}
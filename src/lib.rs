@@ -13,30 +13,98 @@ governing permissions and limitations under the License.
 //! # crTool Library
 //!
 //! Core library for extracting and validating C2PA manifests in crJSON format.
-
-use anyhow::{Context, Result};
+//!
+//! Functions that take a file path need the default `fs` feature. Without it, assets are read
+//! from streams ([`extract_crjson_manifest_from_stream`], [`extract_crjson_manifest_from_bytes`])
+//! and the library builds for `wasm32-unknown-unknown`.
+//!
+//! Fallible functions return [`CrtoolError`]; see the [`error`] module for telling an asset
+//! without a manifest apart from an unreadable file or an unusable schema.
+
+#[cfg(feature = "signing")]
+pub mod asset_metadata;
+pub mod audit_log;
+pub mod badge;
+pub mod cbom;
+#[cfg(feature = "signing")]
+pub mod certs;
+pub mod cose;
+pub mod diff;
+pub mod documents;
+pub mod error;
+#[cfg(feature = "fs")]
+pub mod evidence;
+pub mod expectations;
+pub mod filter;
+#[cfg(feature = "fs")]
+pub mod fingerprint;
+pub mod formats;
+pub mod graph_export;
+pub mod grep;
+pub mod hashing;
+#[cfg(feature = "signing")]
+pub mod identity;
+pub mod indicators;
+#[cfg(feature = "signing")]
+pub mod ingredient_url;
+pub mod jumbf;
+#[cfg(feature = "signing")]
+pub mod keys;
+pub mod labels;
+pub mod limits;
+pub mod profiles;
+pub mod provenance;
+pub mod removal;
+pub mod schema;
+pub mod scratch;
+pub mod severity;
+#[cfg(feature = "signing")]
+pub mod signer;
+#[cfg(feature = "signing")]
+pub mod signing;
+#[cfg(feature = "fs")]
+pub mod snapshot;
+pub mod soft_binding;
+pub mod strip;
+pub mod template;
+pub mod trust;
+pub mod trust_manifest;
+pub mod trust_presentation;
+pub mod unsupported;
+pub mod update;
+pub mod verify;
+
+use crate::error::{format_err, Context};
 use c2pa::{Context as C2paContext, Reader};
 
 /// Re-export so callers (e.g. GUI, CLI) can use explicit Settings without depending on c2pa.
 pub use c2pa::Settings;
 
-/// File extensions for asset types supported by c2pa-rs for reading/embedding C2PA manifests.
-/// Matches the formats listed in c2pa-rs [supported-formats](https://github.com/contentauth/c2pa-rs/blob/main/docs/supported-formats.md).
-pub const SUPPORTED_ASSET_EXTENSIONS: &[&str] = &[
-    "avi", "avif", "c2pa", "dng", "gif", "heic", "heif", "jpg", "jpeg", "m4a", "mov", "mp3", "mp4",
-    "pdf", "png", "svg", "tif", "tiff", "wav", "webp",
-];
+#[cfg(feature = "signing")]
+pub use c2pa::SigningAlg;
+#[cfg(feature = "signing")]
+pub use signing::{
+    sign_asset, sign_document, sign_stream, IngredientCache, SignOptions, SignedAsset, SignedStream,
+};
+
+pub use diff::{diff_indicators, IndicatorsDiff};
+pub use error::{is_no_manifest_error, CrtoolError, Result};
+pub use schema::{SchemaValidator, ValidationProfile};
+pub use severity::{Severity, SeverityRules};
+#[cfg(feature = "fs")]
+pub use strip::remove_c2pa;
+
+pub use formats::{
+    asset_format_for_extension, asset_format_for_path, asset_format_for_type, extensions_for_kind,
+    supported_asset_extensions, walk_assets, AssetFormat, MediaKind, SUPPORTED_ASSET_FORMATS,
+};
 
 /// Returns whether a file path has an extension that c2pa-rs supports for C2PA operations.
 pub fn is_supported_asset_path<P: AsRef<Path>>(path: P) -> bool {
-    let ext = match path.as_ref().extension().and_then(|e| e.to_str()) {
-        Some(e) => e.to_lowercase(),
-        None => return false,
-    };
-    SUPPORTED_ASSET_EXTENSIONS.contains(&ext.as_str())
+    asset_format_for_path(path).is_some()
 }
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::io::{Read, Seek};
 use std::path::Path;
 
 /// Builds a `validationResults` value that conforms to the crJSON schema: `activeManifest`
@@ -134,37 +202,127 @@ pub struct ManifestExtractionResult {
     pub input_path: String,
     /// The active manifest label
     pub active_label: String,
-    /// The computed asset hash (SHA-256)
+    /// The computed asset hash (hex; SHA-256 unless another algorithm was requested)
     pub asset_hash: Option<String>,
+    /// Algorithm of `asset_hash`, when one was computed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_hash_alg: Option<hashing::HashAlgorithm>,
     /// The extracted manifest as a JSON string
     pub manifest_json: String,
     /// Parsed manifest as serde_json::Value for easier processing
     pub manifest_value: serde_json::Value,
 }
 
+impl ManifestExtractionResult {
+    /// Record the asset digest: sets `asset_hash`/`asset_hash_alg` and adds the JPEG Trust
+    /// `asset_info` object (`alg` and base64 `hash`) to the crJSON.
+    pub fn set_asset_hash(&mut self, algorithm: hashing::HashAlgorithm, hex: String) -> Result<()> {
+        use base64::Engine;
+        let digest = hashing::from_hex(&hex).context("Invalid asset hash")?;
+        if let Some(obj) = self.manifest_value.as_object_mut() {
+            obj.insert(
+                "asset_info".to_string(),
+                serde_json::json!({
+                    "alg": algorithm.name(),
+                    "hash": base64::engine::general_purpose::STANDARD.encode(digest),
+                }),
+            );
+            self.manifest_json = serde_json::to_string_pretty(&self.manifest_value)
+                .context("Failed to re-serialize crJSON with asset_info")?;
+        }
+        self.asset_hash = Some(hex);
+        self.asset_hash_alg = Some(algorithm);
+        Ok(())
+    }
+}
+
+/// How [`extract_crjson_manifest_hashed_with`] and [`extract_crjson_manifest_from_stream_with`]
+/// hash the asset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashOptions {
+    /// Digest algorithm for `asset_hash` (SHA-256 by default)
+    pub algorithm: hashing::HashAlgorithm,
+}
+
 /// Result of validating a JSON file against the indicators schema
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResult {
     /// The file path that was validated
     pub file_path: String,
-    /// Whether the file passed validation
+    /// Whether the file passed validation (no finding of [`Severity::Error`])
     pub is_valid: bool,
-    /// Validation error messages (empty if valid)
+    /// Validation findings of every severity (empty if there are none)
     pub errors: Vec<ValidationError>,
 }
 
-/// A single validation error
+impl ValidationResult {
+    /// A result for `errors`, valid unless one of them is an error.
+    pub fn from_findings(file_path: String, errors: Vec<ValidationError>) -> Self {
+        Self {
+            file_path,
+            is_valid: !errors.iter().any(|e| e.severity == Severity::Error),
+            errors,
+        }
+    }
+
+    /// Number of findings of `severity`.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.errors
+            .iter()
+            .filter(|e| e.severity == severity)
+            .count()
+    }
+}
+
+/// A single validation finding
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationError {
     /// The JSON path where the error occurred
     pub instance_path: String,
     /// The error message
     pub message: String,
+    /// How serious the finding is; only errors make the document invalid
+    #[serde(default)]
+    pub severity: Severity,
+    /// Rule that produced the finding: the failed schema keyword (e.g. `required`) or
+    /// [`severity::RECOMMENDED_FIELD_RULE`]. See [`SeverityRules`].
+    #[serde(default)]
+    pub rule: String,
+    /// Label of the schema that reported the finding, when validating with a
+    /// [`schema::ValidationProfile`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+}
+
+/// Fail when the manifest store of the asset at `path`, embedded or in a sidecar `.c2pa` file
+/// the SDK would fall back to, is over [`limits::extraction_limits`]. Both are measured before
+/// anything reads them (see [`jumbf::check_store_size`]).
+#[cfg(feature = "fs")]
+fn check_file_store_size(path: &Path) -> Result<()> {
+    // The SDK refuses unsupported formats itself
+    let Some(format) = asset_format_for_path(path) else {
+        return Ok(());
+    };
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    jumbf::check_store_size(format.mime, &mut file)?;
+    let sidecar = path.with_extension("c2pa");
+    if format.extension != "c2pa" && sidecar.is_file() {
+        let len = std::fs::metadata(&sidecar)
+            .with_context(|| format!("Failed to read {:?}", sidecar))?
+            .len();
+        limits::extraction_limits().check_store_size(len)?;
+    }
+    Ok(())
 }
 
 /// Extracts a C2PA manifest in crJSON format using the given Settings (e.g. trust configuration).
 /// Use this when you have explicit settings so that trust validation uses the same configuration
 /// regardless of thread (avoids thread-local timing/threading issues).
+///
+/// Like every extraction function, this enforces [`limits::extraction_limits`]; a store or crJSON
+/// document over a limit fails with a [`limits::LimitExceeded`] error.
+#[cfg(feature = "fs")]
 pub fn extract_crjson_manifest_with_settings<P: AsRef<Path>>(
     input_path: P,
     settings: &Settings,
@@ -172,30 +330,319 @@ pub fn extract_crjson_manifest_with_settings<P: AsRef<Path>>(
     let input_path = input_path.as_ref();
 
     if !input_path.exists() {
-        anyhow::bail!("Input file does not exist: {:?}", input_path);
+        return Err(CrtoolError::FileNotFound(input_path.to_path_buf()));
     }
+    if documents::is_text_document_path(input_path) {
+        return documents::extract_document_manifest(input_path, settings);
+    }
+
+    check_file_store_size(input_path)?;
 
     let context = C2paContext::new()
         .with_settings(settings)
-        .map_err(|e| anyhow::anyhow!("Invalid settings: {}", e))?;
+        .map_err(|e| format_err!("Invalid settings: {}", e))?;
     let reader = Reader::from_context(context)
         .with_file(input_path)
         .context(
             "Failed to read C2PA data from input file. The file may not contain a C2PA manifest.",
         )?;
 
+    extraction_result_from_reader(&reader, input_path.to_string_lossy().to_string())
+}
+
+/// Extract crJSON from an asset using a manifest store supplied separately (a sidecar `.c2pa`
+/// file or a fetched remote manifest), validating the store against the asset's bytes.
+#[cfg(feature = "fs")]
+pub fn extract_crjson_manifest_with_store<P: AsRef<Path>>(
+    input_path: P,
+    store: &[u8],
+    settings: &Settings,
+) -> Result<ManifestExtractionResult> {
+    let input_path = input_path.as_ref();
+    let mime = documents::text_document_mime(input_path)
+        .or_else(|| asset_format_for_path(input_path).map(|f| f.mime))
+        .with_context(|| format!("Unsupported file format: {:?}", input_path))?;
+    limits::extraction_limits().check_store_size(store.len() as u64)?;
+
+    let mut file = std::fs::File::open(input_path)
+        .with_context(|| format!("Failed to open {:?}", input_path))?;
+    let context = C2paContext::new()
+        .with_settings(settings)
+        .map_err(|e| format_err!("Invalid settings: {}", e))?;
+    let reader = Reader::from_context(context)
+        .with_manifest_data_and_stream(store, mime, &mut file)
+        .context("Failed to read the manifest store")?;
+
+    extraction_result_from_reader(&reader, input_path.to_string_lossy().to_string())
+}
+
+/// crJSON of the active manifest read by `reader`, with normalized validation results.
+pub(crate) fn extraction_result_from_reader(
+    reader: &Reader,
+    input_path: String,
+) -> Result<ManifestExtractionResult> {
     let active_label = reader
         .active_label()
         .context("No active C2PA manifest found in the input file")?
         .to_string();
 
-    let manifest_json = reader.crjson();
+    let mut manifest_value =
+        limits::parse_json(&reader.crjson()).context("Failed to parse extracted crJSON")?;
+
+    normalize_crjson_validation_results(&mut manifest_value);
+
+    let manifest_json = serde_json::to_string_pretty(&manifest_value)
+        .context("Failed to re-serialize crJSON after normalization")?;
+
+    Ok(ManifestExtractionResult {
+        input_path,
+        active_label,
+        asset_hash: None,
+        asset_hash_alg: None,
+        manifest_json,
+        manifest_value,
+    })
+}
+
+/// Extracts a C2PA manifest in crJSON format from an asset held in a stream rather than a file,
+/// so services can process uploads without writing temporary files. `format` is a file
+/// extension or MIME type (e.g. `"jpg"` or `"image/jpeg"`).
+///
+/// `asset_hash` is the SHA-256 of the whole stream, computed while the manifest is read.
+/// `input_path` is empty, since the asset has no path.
+pub fn extract_crjson_manifest_from_stream<R: Read + Seek + Send>(
+    stream: R,
+    format: &str,
+    settings: &Settings,
+) -> Result<ManifestExtractionResult> {
+    extract_crjson_manifest_from_stream_with(stream, format, settings, &HashOptions::default())
+}
+
+/// Like [`extract_crjson_manifest_from_stream`], hashing the stream as `options` requests.
+pub fn extract_crjson_manifest_from_stream_with<R: Read + Seek + Send>(
+    mut stream: R,
+    format: &str,
+    settings: &Settings,
+    options: &HashOptions,
+) -> Result<ManifestExtractionResult> {
+    let asset_format = asset_format_for_type(format)
+        .with_context(|| format!("Unsupported asset format: {}", format))?;
+
+    stream
+        .rewind()
+        .context("Failed to rewind the asset stream")?;
+    extract_from_stream_hashed(
+        stream,
+        asset_format.mime,
+        settings,
+        String::new(),
+        options.algorithm,
+    )
+}
+
+/// Like [`extract_crjson_manifest_with_settings`], and also sets `asset_hash` to the file's
+/// SHA-256. The file is hashed during the same streaming read the manifest is validated from
+/// rather than in a second pass over the file, which matters for large video.
+#[cfg(feature = "fs")]
+pub fn extract_crjson_manifest_hashed<P: AsRef<Path>>(
+    input_path: P,
+    settings: &Settings,
+) -> Result<ManifestExtractionResult> {
+    extract_crjson_manifest_hashed_with(input_path, settings, &HashOptions::default())
+}
+
+/// Like [`extract_crjson_manifest_hashed`], hashing the file as `options` requests (e.g. with
+/// SHA-384 or SHA-512 where a stronger digest is mandated).
+#[cfg(feature = "fs")]
+pub fn extract_crjson_manifest_hashed_with<P: AsRef<Path>>(
+    input_path: P,
+    settings: &Settings,
+    options: &HashOptions,
+) -> Result<ManifestExtractionResult> {
+    let input_path = input_path.as_ref();
+    if !input_path.exists() {
+        return Err(CrtoolError::FileNotFound(input_path.to_path_buf()));
+    }
+    let algorithm = options.algorithm;
+    let extract_then_hash = || {
+        let mut result = extract_crjson_manifest_with_settings(input_path, settings)?;
+        result.set_asset_hash(algorithm, hashing::hash_file(input_path, algorithm)?)?;
+        Ok(result)
+    };
+    // Text documents (small, with a detached store) and files without an embedded manifest
+    // (which may have a sidecar only the file-based reader picks up) are hashed separately.
+    let Some(asset_format) = asset_format_for_path(input_path) else {
+        return extract_then_hash();
+    };
+    let file = std::fs::File::open(input_path)
+        .with_context(|| format!("Failed to open {:?}", input_path))?;
+    let result = extract_from_stream_hashed(
+        std::io::BufReader::new(file),
+        asset_format.mime,
+        settings,
+        input_path.to_string_lossy().to_string(),
+        algorithm,
+    );
+    match result {
+        Err(e) if e.is_no_manifest() => extract_then_hash(),
+        result => result,
+    }
+}
+
+/// Read the manifest of an asset stream positioned at its start, hashing the stream with
+/// `algorithm` in the same pass (see [`hashing::HashingReader`]).
+///
+/// The store's size is checked against the limit from the container headers first. The embedded
+/// store is then loaded once and handed to the SDK, so the part of the asset in front of it is
+/// not scanned a second time; the SDK then reads the stream only to check the hard binding.
+fn extract_from_stream_hashed<R: Read + Seek + Send>(
+    mut stream: R,
+    mime: &str,
+    settings: &Settings,
+    input_path: String,
+    algorithm: hashing::HashAlgorithm,
+) -> Result<ManifestExtractionResult> {
+    jumbf::check_store_size(mime, &mut stream)?;
+    let mut stream = hashing::HashingReader::with_algorithm(stream, algorithm);
+    let store = c2pa::load_jumbf_from_stream(mime, &mut stream).ok();
+    stream
+        .rewind()
+        .context("Failed to rewind the asset stream")?;
+
+    let context = C2paContext::new()
+        .with_settings(settings)
+        .map_err(|e| format_err!("Invalid settings: {}", e))?;
+    let reader = Reader::from_context(context);
+    let reader = match &store {
+        Some(store) => reader.with_manifest_data_and_stream(store, mime, &mut stream),
+        None => reader.with_stream(mime, &mut stream),
+    }
+    .context("Failed to read C2PA data from the asset. It may not contain a C2PA manifest.")?;
+    let (_, asset_hash) = stream.finish()?;
+
+    let mut result = extraction_result_from_reader(&reader, input_path)?;
+    result.set_asset_hash(algorithm, asset_hash)?;
+    Ok(result)
+}
+
+/// Extracts a C2PA manifest in crJSON format from an asset in memory. See
+/// [`extract_crjson_manifest_from_stream`].
+pub fn extract_crjson_manifest_from_bytes(
+    data: &[u8],
+    format: &str,
+    settings: &Settings,
+) -> Result<ManifestExtractionResult> {
+    extract_crjson_manifest_from_stream(std::io::Cursor::new(data), format, settings)
+}
+
+/// Validation status code prefixes reported when an asset's content does not match a hard-binding
+/// hash assertion. These are the checks skipped by [`extract_crjson_manifest_fast`].
+pub const CONTENT_HASH_CODE_PREFIXES: &[&str] = &[
+    "assertion.dataHash.",
+    "assertion.bmffHash.",
+    "assertion.boxesHash.",
+    "assertion.collectionHash.",
+];
+
+/// Remove content-hash failure codes from every `failure` list in a crJSON document.
+/// Returns the codes that were removed.
+#[cfg(feature = "fs")]
+fn strip_content_hash_failures(value: &mut serde_json::Value) -> Vec<String> {
+    let mut removed = Vec::new();
+    match value {
+        serde_json::Value::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                if key == "failure" {
+                    if let Some(entries) = child.as_array_mut() {
+                        entries.retain(|entry| {
+                            let code = entry.get("code").and_then(|c| c.as_str()).unwrap_or("");
+                            let is_hash = CONTENT_HASH_CODE_PREFIXES
+                                .iter()
+                                .any(|prefix| code.starts_with(prefix));
+                            if is_hash {
+                                removed.push(code.to_string());
+                            }
+                            !is_hash
+                        });
+                    }
+                } else {
+                    removed.extend(strip_content_hash_failures(child));
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                removed.extend(strip_content_hash_failures(item));
+            }
+        }
+        _ => {}
+    }
+    removed
+}
+
+/// Fast, partial extraction for large corpora: parses the manifest store and verifies claim
+/// signatures and certificate chains, but does not hash the asset content.
+///
+/// The manifest store is read from the asset and handed to the SDK with an empty content stream,
+/// so hard-binding checks cannot succeed; their failure codes are removed and the document is
+/// marked with a top-level `partialValidation` object. Results must not be read as a statement
+/// that the content is unmodified.
+///
+/// ISO BMFF assets (MP4, MOV, HEIF, AVIF, ...; see [`AssetFormat::is_bmff`]) are read in full
+/// instead: their `c2pa.hash.bmff` binding is checked against the box structure of the asset, so
+/// the SDK cannot read the manifest against an empty stream. Their results carry no
+/// `partialValidation` object.
+#[cfg(feature = "fs")]
+pub fn extract_crjson_manifest_fast<P: AsRef<Path>>(
+    input_path: P,
+    settings: &Settings,
+) -> Result<ManifestExtractionResult> {
+    let input_path = input_path.as_ref();
+
+    if !input_path.exists() {
+        return Err(CrtoolError::FileNotFound(input_path.to_path_buf()));
+    }
+    if asset_format_for_path(input_path).is_some_and(AssetFormat::is_bmff) {
+        return extract_crjson_manifest_with_settings(input_path, settings);
+    }
+    let mime = asset_format_for_path(input_path)
+        .map(|f| f.mime)
+        .or_else(|| documents::text_document_mime(input_path))
+        .with_context(|| format!("Unsupported asset format: {:?}", input_path))?;
+
+    let store = jumbf::load_manifest_store(input_path)?;
+
+    let context = C2paContext::new()
+        .with_settings(settings)
+        .map_err(|e| format_err!("Invalid settings: {}", e))?;
+    let reader = Reader::from_context(context)
+        .with_manifest_data_and_stream(&store, mime, &mut std::io::Cursor::new(Vec::<u8>::new()))
+        .context("Failed to parse C2PA manifest store")?;
 
-    let mut manifest_value: serde_json::Value =
-        serde_json::from_str(&manifest_json).context("Failed to parse extracted crJSON")?;
+    let active_label = reader
+        .active_label()
+        .context("No active C2PA manifest found in the input file")?
+        .to_string();
+
+    let mut manifest_value =
+        limits::parse_json(&reader.crjson()).context("Failed to parse extracted crJSON")?;
 
     normalize_crjson_validation_results(&mut manifest_value);
 
+    let mut skipped_codes = strip_content_hash_failures(&mut manifest_value);
+    skipped_codes.sort();
+    skipped_codes.dedup();
+    if let Some(obj) = manifest_value.as_object_mut() {
+        obj.insert(
+            "partialValidation".to_string(),
+            serde_json::json!({
+                "mode": "fast",
+                "skipped": ["contentHash"],
+                "suppressedCodes": skipped_codes,
+            }),
+        );
+    }
+
     let manifest_json = serde_json::to_string_pretty(&manifest_value)
         .context("Failed to re-serialize crJSON after normalization")?;
 
@@ -203,6 +650,7 @@ pub fn extract_crjson_manifest_with_settings<P: AsRef<Path>>(
         input_path: input_path.to_string_lossy().to_string(),
         active_label,
         asset_hash: None,
+        asset_hash_alg: None,
         manifest_json,
         manifest_value,
     })
@@ -229,13 +677,16 @@ pub fn extract_crjson_manifest_with_settings<P: AsRef<Path>>(
 /// - The file does not exist
 /// - The file does not contain a valid C2PA manifest
 /// - The manifest cannot be parsed to crJSON
+#[cfg(feature = "fs")]
 pub fn extract_crjson_manifest<P: AsRef<Path>>(input_path: P) -> Result<ManifestExtractionResult> {
     let input_path = input_path.as_ref();
 
     if !input_path.exists() {
-        anyhow::bail!("Input file does not exist: {:?}", input_path);
+        return Err(CrtoolError::FileNotFound(input_path.to_path_buf()));
     }
 
+    check_file_store_size(input_path)?;
+
     let reader = Reader::from_file(input_path).context(
         "Failed to read C2PA data from input file. The file may not contain a C2PA manifest.",
     )?;
@@ -247,8 +698,8 @@ pub fn extract_crjson_manifest<P: AsRef<Path>>(input_path: P) -> Result<Manifest
 
     let manifest_json = reader.crjson();
 
-    let mut manifest_value: serde_json::Value =
-        serde_json::from_str(&manifest_json).context("Failed to parse extracted crJSON")?;
+    let mut manifest_value =
+        limits::parse_json(&manifest_json).context("Failed to parse extracted crJSON")?;
 
     normalize_crjson_validation_results(&mut manifest_value);
 
@@ -259,13 +710,112 @@ pub fn extract_crjson_manifest<P: AsRef<Path>>(input_path: P) -> Result<Manifest
         input_path: input_path.to_string_lossy().to_string(),
         active_label,
         asset_hash: None,
+        asset_hash_alg: None,
         manifest_json,
         manifest_value,
     })
 }
 
+/// Resolve a manifest resource URI to its absolute JUMBF form. Resource references inside a
+/// manifest (e.g. an ingredient's `thumbnail.url`) are usually relative to that manifest
+/// (`self#jumbf=c2pa.assertions/...`); the absolute form (`self#jumbf=/c2pa/<label>/...`)
+/// identifies the resource uniquely across the whole manifest store.
+pub fn resolve_resource_uri(manifest_label: &str, uri: &str) -> String {
+    match uri.strip_prefix("self#jumbf=") {
+        Some(rest) if !rest.starts_with('/') => {
+            format!("self#jumbf=/c2pa/{}/{}", manifest_label, rest)
+        }
+        _ => uri.to_string(),
+    }
+}
+
+/// Absolute URI of the claim thumbnail (`c2pa.thumbnail.claim.*`) of manifest `manifest_label`
+/// in a crJSON document, for [`read_resource_with_settings`]. Looks at the claim's assertion
+/// references first, then the manifest's assertion labels.
+pub fn claim_thumbnail_uri(crjson: &serde_json::Value, manifest_label: &str) -> Option<String> {
+    const CLAIM_THUMBNAIL: &str = "c2pa.thumbnail.claim";
+    let manifest = provenance::find_manifest(crjson, manifest_label)?;
+    let claim = manifest.get("claim.v2").or_else(|| manifest.get("claim"));
+    let referenced = ["created_assertions", "assertions"]
+        .iter()
+        .filter_map(|key| claim?.get(*key)?.as_array())
+        .flatten()
+        .filter_map(|r| r.get("url").and_then(|u| u.as_str()))
+        .find(|url| url.contains(CLAIM_THUMBNAIL))
+        .map(str::to_string);
+    let uri = referenced.or_else(|| {
+        manifest
+            .get("assertions")?
+            .as_object()?
+            .keys()
+            .find(|key| key.starts_with(CLAIM_THUMBNAIL))
+            .map(|key| format!("self#jumbf=c2pa.assertions/{}", key))
+    })?;
+    Some(resolve_resource_uri(manifest_label, &uri))
+}
+
+/// Read the bytes of a manifest resource (e.g. an ingredient thumbnail) from `input_path`.
+/// `uri` is tried as given and, when it is relative, also resolved against `manifest_label`.
+/// Use [`ResourceReader`] to read several resources of the same asset.
+#[cfg(feature = "fs")]
+pub fn read_resource_with_settings<P: AsRef<Path>>(
+    input_path: P,
+    manifest_label: &str,
+    uri: &str,
+    settings: &Settings,
+) -> Result<Vec<u8>> {
+    ResourceReader::open(input_path, settings)?.read(manifest_label, uri)
+}
+
+/// The manifest store of an asset, read once, for reading many of its resources (e.g. every
+/// ingredient thumbnail shown for a document).
+#[cfg(feature = "fs")]
+pub struct ResourceReader {
+    reader: Reader,
+}
+
+#[cfg(feature = "fs")]
+impl ResourceReader {
+    /// Read and validate the manifest store of `input_path`.
+    pub fn open<P: AsRef<Path>>(input_path: P, settings: &Settings) -> Result<Self> {
+        let context = C2paContext::new()
+            .with_settings(settings)
+            .map_err(|e| format_err!("Invalid settings: {}", e))?;
+        let reader = Reader::from_context(context)
+            .with_file(input_path.as_ref())
+            .context("Failed to read C2PA data from input file")?;
+        Ok(Self { reader })
+    }
+
+    /// Bytes of the resource `uri`, tried as given and, when it is relative, also resolved
+    /// against `manifest_label`.
+    pub fn read(&self, manifest_label: &str, uri: &str) -> Result<Vec<u8>> {
+        let mut candidates = vec![uri.to_string()];
+        let absolute = resolve_resource_uri(manifest_label, uri);
+        if absolute != uri {
+            candidates.push(absolute);
+        }
+
+        let mut last_error = None;
+        for candidate in &candidates {
+            let mut stream = std::io::Cursor::new(Vec::new());
+            match self.reader.resource_to_stream(candidate, &mut stream) {
+                Ok(_) => return Ok(stream.into_inner()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(format_err!(
+            "Resource {} not found: {}",
+            uri,
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        ))
+    }
+}
+
 /// Validate a JSON value against a JSON schema.
 ///
+/// Compiles the schema on every call; use [`SchemaValidator`] to validate many documents.
+///
 /// # Arguments
 ///
 /// * `json_value` - The JSON value to validate
@@ -278,51 +828,13 @@ pub fn validate_json_value(
     json_value: &serde_json::Value,
     schema_path: &Path,
 ) -> Result<ValidationResult> {
-    if !schema_path.exists() {
-        anyhow::bail!("Schema file not found at: {:?}", schema_path);
-    }
-
-    let schema_content =
-        fs::read_to_string(schema_path).context("Failed to read indicators schema file")?;
-
-    let schema_json: serde_json::Value =
-        serde_json::from_str(&schema_content).context("Failed to parse indicators schema JSON")?;
-
-    // Compile the schema
-    let compiled_schema = jsonschema::validator_for(&schema_json)
-        .map_err(|e| anyhow::anyhow!("Failed to compile JSON schema: {}", e))?;
-
-    // Validate
-    let validation_result = compiled_schema.validate(json_value);
-
-    let mut errors = Vec::new();
-    let is_valid = match validation_result {
-        Ok(_) => true,
-        Err(validation_errors) => {
-            for error in validation_errors {
-                let instance_path = if error.instance_path.to_string().is_empty() {
-                    "root".to_string()
-                } else {
-                    error.instance_path.to_string()
-                };
-                errors.push(ValidationError {
-                    instance_path,
-                    message: error.to_string(),
-                });
-            }
-            false
-        }
-    };
-
-    Ok(ValidationResult {
-        file_path: String::new(), // Filled in by caller if needed
-        is_valid,
-        errors,
-    })
+    Ok(SchemaValidator::from_path(schema_path)?.validate(json_value))
 }
 
 /// Validate a JSON file against a JSON schema.
 ///
+/// Compiles the schema on every call; use [`SchemaValidator::validate_file`] to validate many files.
+///
 /// # Arguments
 ///
 /// * `json_file_path` - Path to the JSON file to validate
@@ -331,22 +843,12 @@ pub fn validate_json_value(
 /// # Returns
 ///
 /// A `ValidationResult` containing validation status and any errors
+#[cfg(feature = "fs")]
 pub fn validate_json_file<P: AsRef<Path>>(
     json_file_path: P,
     schema_path: &Path,
 ) -> Result<ValidationResult> {
-    let json_file_path = json_file_path.as_ref();
-
-    let json_content = fs::read_to_string(json_file_path)
-        .context(format!("Failed to read file: {:?}", json_file_path))?;
-
-    let json_value: serde_json::Value = serde_json::from_str(&json_content)
-        .context(format!("Invalid JSON in file: {:?}", json_file_path))?;
-
-    let mut result = validate_json_value(&json_value, schema_path)?;
-    result.file_path = json_file_path.to_string_lossy().to_string();
-
-    Ok(result)
+    SchemaValidator::from_path(schema_path)?.validate_file(json_file_path)
 }
 
 /// Get the crJSON schema path relative to the crate root
@@ -406,7 +908,7 @@ pub fn build_trust_settings(
     let toml = trust_settings_toml(trust_anchors, allowed_list, trust_config);
     Settings::default()
         .with_toml(&toml)
-        .map_err(|e| anyhow::anyhow!("Failed to build trust settings: {}", e))
+        .map_err(|e| format_err!("Failed to build trust settings: {}", e))
 }
 
 /// Returns default Settings for extraction when trust lists are not used.
@@ -434,8 +936,7 @@ pub fn apply_trust_settings(
     trust_config: Option<&str>,
 ) -> Result<()> {
     let toml = trust_settings_toml(trust_anchors, allowed_list, trust_config);
-    Settings::from_toml(&toml)
-        .map_err(|e| anyhow::anyhow!("Failed to apply trust settings: {}", e))?;
+    Settings::from_toml(&toml).map_err(|e| format_err!("Failed to apply trust settings: {}", e))?;
     Ok(())
 }
 
@@ -452,4 +953,67 @@ mod tests {
             schema_path
         );
     }
+
+    #[test]
+    fn test_strip_content_hash_failures() {
+        let mut value = serde_json::json!({
+            "manifests": [{
+                "validationResults": {
+                    "success": [{ "code": "claimSignature.validated" }],
+                    "failure": [
+                        { "code": "assertion.dataHash.mismatch" },
+                        { "code": "signingCredential.untrusted" }
+                    ]
+                }
+            }]
+        });
+        let removed = strip_content_hash_failures(&mut value);
+        assert_eq!(removed, vec!["assertion.dataHash.mismatch".to_string()]);
+        let failures = &value["manifests"][0]["validationResults"]["failure"];
+        assert_eq!(failures.as_array().unwrap().len(), 1);
+        assert_eq!(failures[0]["code"], "signingCredential.untrusted");
+    }
+
+    #[test]
+    fn test_resolve_resource_uri() {
+        assert_eq!(
+            resolve_resource_uri(
+                "urn:c2pa:1",
+                "self#jumbf=c2pa.assertions/c2pa.thumbnail.ingredient.jpeg"
+            ),
+            "self#jumbf=/c2pa/urn:c2pa:1/c2pa.assertions/c2pa.thumbnail.ingredient.jpeg"
+        );
+        let absolute = "self#jumbf=/c2pa/urn:c2pa:2/c2pa.assertions/c2pa.thumbnail.claim.jpeg";
+        assert_eq!(resolve_resource_uri("urn:c2pa:1", absolute), absolute);
+    }
+
+    #[test]
+    fn test_claim_thumbnail_uri() {
+        let crjson = serde_json::json!({
+            "manifests": [
+                {
+                    "label": "urn:c2pa:1",
+                    "claim.v2": {"created_assertions": [
+                        {"url": "self#jumbf=c2pa.assertions/c2pa.actions.v2"},
+                        {"url": "self#jumbf=c2pa.assertions/c2pa.thumbnail.claim"}
+                    ]}
+                },
+                {
+                    "label": "urn:c2pa:2",
+                    "assertions": {"c2pa.thumbnail.claim.png": {}, "c2pa.actions": {}}
+                },
+                {"label": "urn:c2pa:3", "assertions": {"c2pa.actions": {}}}
+            ]
+        });
+        assert_eq!(
+            claim_thumbnail_uri(&crjson, "urn:c2pa:1").as_deref(),
+            Some("self#jumbf=/c2pa/urn:c2pa:1/c2pa.assertions/c2pa.thumbnail.claim")
+        );
+        assert_eq!(
+            claim_thumbnail_uri(&crjson, "urn:c2pa:2").as_deref(),
+            Some("self#jumbf=/c2pa/urn:c2pa:2/c2pa.assertions/c2pa.thumbnail.claim.png")
+        );
+        assert_eq!(claim_thumbnail_uri(&crjson, "urn:c2pa:3"), None);
+        assert_eq!(claim_thumbnail_uri(&crjson, "urn:c2pa:9"), None);
+    }
 }
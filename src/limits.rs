@@ -0,0 +1,277 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Resource limits that keep a crafted file from exhausting memory in the GUI or a server.
+//!
+//! Extraction checks the size of the raw manifest store before the SDK decodes it, and JSON text
+//! (extracted crJSON, documents given to validation) is scanned for nesting depth and
+//! string/array lengths before it is parsed. A violation is a [`CrtoolError::LimitExceeded`],
+//! which callers can tell apart from other failures with [`limit_exceeded`].
+
+use crate::error::{CrtoolError, Result};
+use serde::Serialize;
+use std::fmt;
+use std::sync::RwLock;
+
+/// Limits applied during extraction and JSON parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractionLimits {
+    /// Largest raw JUMBF manifest store read from an asset, in bytes.
+    pub max_manifest_store_bytes: u64,
+    /// Deepest nesting of JSON objects and arrays.
+    pub max_json_depth: usize,
+    /// Longest JSON string (keys included), in bytes.
+    pub max_string_len: usize,
+    /// Most elements in one JSON array.
+    pub max_array_len: usize,
+    /// Largest file downloaded from a URL (e.g. an ingredient), in bytes.
+    pub max_download_bytes: u64,
+}
+
+impl ExtractionLimits {
+    /// Defaults: 128 MiB manifest store, depth 128, 32 MiB strings, 1,000,000 array elements,
+    /// 1 GiB downloads.
+    pub const DEFAULT: Self = Self {
+        max_manifest_store_bytes: 128 * 1024 * 1024,
+        max_json_depth: 128,
+        max_string_len: 32 * 1024 * 1024,
+        max_array_len: 1_000_000,
+        max_download_bytes: 1024 * 1024 * 1024,
+    };
+
+    /// Fail if a manifest store of `len` bytes is over the limit.
+    pub fn check_store_size(&self, len: u64) -> std::result::Result<(), LimitExceeded> {
+        if len > self.max_manifest_store_bytes {
+            return Err(LimitExceeded {
+                kind: LimitKind::ManifestStoreSize,
+                max: self.max_manifest_store_bytes,
+                actual: Some(len),
+            });
+        }
+        Ok(())
+    }
+
+    /// Fail if a download of `len` bytes is over the limit.
+    pub fn check_download_size(&self, len: u64) -> std::result::Result<(), LimitExceeded> {
+        if len > self.max_download_bytes {
+            return Err(LimitExceeded {
+                kind: LimitKind::DownloadSize,
+                max: self.max_download_bytes,
+                actual: Some(len),
+            });
+        }
+        Ok(())
+    }
+
+    /// Scan JSON text for nesting depth and string/array lengths without parsing it. Malformed
+    /// JSON is not reported here; it is left to the parser.
+    pub fn check_json(&self, text: &str) -> std::result::Result<(), LimitExceeded> {
+        let exceeded = |kind, max: usize| LimitExceeded {
+            kind,
+            max: max as u64,
+            actual: None,
+        };
+        // One entry per open container: `Some(elements so far)` for arrays, `None` for objects.
+        let mut open: Vec<Option<usize>> = Vec::new();
+        let mut bytes = text.bytes();
+        while let Some(b) = bytes.next() {
+            match b {
+                b'"' => {
+                    let mut len = 0usize;
+                    while let Some(c) = bytes.next() {
+                        match c {
+                            b'"' => break,
+                            b'\\' => {
+                                bytes.next();
+                                len += 1;
+                            }
+                            _ => len += 1,
+                        }
+                        if len > self.max_string_len {
+                            return Err(exceeded(LimitKind::StringLength, self.max_string_len));
+                        }
+                    }
+                }
+                b'[' | b'{' => {
+                    if open.len() >= self.max_json_depth {
+                        return Err(exceeded(LimitKind::JsonDepth, self.max_json_depth));
+                    }
+                    open.push((b == b'[').then_some(1));
+                }
+                b']' | b'}' => {
+                    open.pop();
+                }
+                b',' => {
+                    if let Some(Some(elements)) = open.last_mut() {
+                        *elements += 1;
+                        if *elements > self.max_array_len {
+                            return Err(exceeded(LimitKind::ArrayLength, self.max_array_len));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for ExtractionLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static LIMITS: RwLock<ExtractionLimits> = RwLock::new(ExtractionLimits::DEFAULT);
+
+/// Use `limits` for all extraction and JSON parsing in this process.
+pub fn set_extraction_limits(limits: ExtractionLimits) {
+    if let Ok(mut current) = LIMITS.write() {
+        *current = limits;
+    }
+}
+
+/// Limits currently in force ([`ExtractionLimits::DEFAULT`] unless changed).
+pub fn extraction_limits() -> ExtractionLimits {
+    LIMITS.read().map(|l| *l).unwrap_or_default()
+}
+
+/// Which limit was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LimitKind {
+    ManifestStoreSize,
+    JsonDepth,
+    StringLength,
+    ArrayLength,
+    DownloadSize,
+}
+
+/// A resource limit violation: the input was refused rather than processed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LimitExceeded {
+    pub kind: LimitKind,
+    pub max: u64,
+    /// The measured value, when it is known (the scan stops at the first violation).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<u64>,
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            LimitKind::ManifestStoreSize => write!(
+                f,
+                "Limit exceeded: manifest store of {} bytes is larger than the {}-byte limit",
+                self.actual.unwrap_or_default(),
+                self.max
+            ),
+            LimitKind::JsonDepth => write!(
+                f,
+                "Limit exceeded: JSON nesting is deeper than {} levels",
+                self.max
+            ),
+            LimitKind::StringLength => write!(
+                f,
+                "Limit exceeded: JSON string longer than {} bytes",
+                self.max
+            ),
+            LimitKind::ArrayLength => write!(
+                f,
+                "Limit exceeded: JSON array with more than {} elements",
+                self.max
+            ),
+            LimitKind::DownloadSize => match self.actual {
+                Some(actual) => write!(
+                    f,
+                    "Limit exceeded: download of {} bytes is larger than the {}-byte limit",
+                    actual, self.max
+                ),
+                None => write!(
+                    f,
+                    "Limit exceeded: download is larger than the {}-byte limit",
+                    self.max
+                ),
+            },
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// The limit violation behind `error`, if it was caused by one. `error` may be a
+/// [`CrtoolError`] or an error wrapping one, e.g. an `anyhow::Error` (`limit_exceeded(&*e)`).
+pub fn limit_exceeded(error: &(dyn std::error::Error + 'static)) -> Option<&LimitExceeded> {
+    std::iter::successors(Some(error), |&e| e.source()).find_map(|e| {
+        e.downcast_ref::<LimitExceeded>()
+            .or_else(|| e.downcast_ref::<CrtoolError>()?.limit_exceeded())
+    })
+}
+
+/// Parse JSON text after checking it against the current [`extraction_limits`].
+pub fn parse_json(text: &str) -> Result<serde_json::Value> {
+    extraction_limits().check_json(text)?;
+    Ok(serde_json::from_str(text)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Context;
+
+    fn small() -> ExtractionLimits {
+        ExtractionLimits {
+            max_manifest_store_bytes: 100,
+            max_json_depth: 3,
+            max_string_len: 5,
+            max_array_len: 3,
+            max_download_bytes: 10,
+        }
+    }
+
+    #[test]
+    fn test_check_json_limits() {
+        let limits = small();
+        assert!(limits
+            .check_json(r#"{"a": [1, 2, 3], "b": {"c": "12345"}}"#)
+            .is_ok());
+        // Brackets and commas inside strings are not structure
+        assert!(limits.check_json(r#"["[[,,", "]]]"]"#).is_ok());
+
+        let kind = |text: &str| limits.check_json(text).unwrap_err().kind;
+        assert_eq!(kind("[[[[1]]]]"), LimitKind::JsonDepth);
+        assert_eq!(kind(r#"{"key": "123456"}"#), LimitKind::StringLength);
+        assert_eq!(kind(r#"["a\"b\"cd"]"#), LimitKind::StringLength);
+        assert_eq!(kind("[1, 2, 3, 4]"), LimitKind::ArrayLength);
+        // Objects have no element limit
+        assert!(limits.check_json(r#"{"a":1,"b":2,"c":3,"d":4}"#).is_ok());
+
+        assert!(limits.check_store_size(100).is_ok());
+        assert_eq!(limits.check_store_size(101).unwrap_err().actual, Some(101));
+        assert!(limits.check_download_size(10).is_ok());
+        let err = limits.check_download_size(11).unwrap_err();
+        assert_eq!(err.kind, LimitKind::DownloadSize);
+        assert!(err.to_string().contains("10-byte limit"), "{err}");
+    }
+
+    #[test]
+    fn test_limit_exceeded_survives_context() {
+        let err = small()
+            .check_json("[[[[]]]]")
+            .context("Failed to parse extracted crJSON")
+            .unwrap_err();
+        assert_eq!(limit_exceeded(&err).unwrap().kind, LimitKind::JsonDepth);
+
+        let other = CrtoolError::Message("No C2PA manifest found".to_string());
+        assert!(limit_exceeded(&other).is_none());
+    }
+}
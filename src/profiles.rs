@@ -0,0 +1,417 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! JPEG Trust "Trust Profile" evaluation: a tree of conditions over the trust indicators of an
+//! extracted crJSON document, producing a trust report (the trust declaration for the asset)
+//! with the result of every condition.
+//!
+//! A Trust Profile is JSON of the form:
+//!
+//! ```json
+//! {
+//!   "name": "Newsroom trust profile",
+//!   "condition": { "all": [
+//!     { "id": "alg", "indicator": "/activeManifest/signature/algorithm", "oneOf": ["ES256", "Ed25519"] },
+//!     { "any": [
+//!       { "indicator": "/activeManifest/assertions/c2pa.actions.v2", "exists": true },
+//!       { "indicator": "/activeManifest/assertions/c2pa.actions", "exists": true }
+//!     ] },
+//!     { "not": { "indicator": "/activeManifest/validationResults/failure", "minItems": 1 } }
+//!   ] }
+//! }
+//! ```
+//!
+//! A condition is a group (`all`, `any`) of conditions, the negation (`not`) of one, or a test
+//! of one indicator with the expectations of a Trust Manifest clause (see
+//! [`crate::trust_manifest::TrustClause`]). Every condition may have an `id` (by default its
+//! JSON Pointer in the profile) and a `description`. Tests with `"required": false` whose
+//! indicator is missing are not applicable and left out of the groups above them.
+
+use crate::error::{bail, Context, Result};
+use crate::trust_manifest::{check_clause, resolve_indicator, ClauseStatus, TrustClause};
+use crate::ManifestExtractionResult;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::path::Path;
+
+/// Keys that select the kind of a condition; each condition has exactly one.
+const OPERATOR_KEYS: &[&str] = &["all", "any", "not", "indicator"];
+
+/// A Trust Profile, as loaded from JSON.
+#[derive(Debug, Clone)]
+pub struct TrustProfile {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub condition: Condition,
+}
+
+/// One node of a profile's condition tree.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    pub id: String,
+    pub description: Option<String>,
+    pub expression: Expression,
+}
+
+/// What a condition checks.
+#[derive(Debug, Clone)]
+pub enum Expression {
+    /// Every applicable condition holds.
+    All(Vec<Condition>),
+    /// At least one applicable condition holds.
+    Any(Vec<Condition>),
+    /// The condition does not hold.
+    Not(Box<Condition>),
+    /// An indicator meets the clause's expectations.
+    Test(TrustClause),
+}
+
+/// Kind of a condition in a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    All,
+    Any,
+    Not,
+    Test,
+}
+
+fn optional_string(object: &serde_json::Map<String, JsonValue>, key: &str) -> Option<String> {
+    object.get(key).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+impl Condition {
+    /// Parse the condition at JSON Pointer `path` of a profile.
+    fn from_value(value: &JsonValue, path: &str) -> Result<Self> {
+        let Some(object) = value.as_object() else {
+            bail!("{}: a condition must be a JSON object", path);
+        };
+        let operators: Vec<&str> = OPERATOR_KEYS
+            .iter()
+            .copied()
+            .filter(|key| object.contains_key(*key))
+            .collect();
+        let [operator] = operators.as_slice() else {
+            bail!(
+                "{}: a condition needs exactly one of {}",
+                path,
+                OPERATOR_KEYS.join(", ")
+            );
+        };
+        let id = optional_string(object, "id").unwrap_or_else(|| path.to_string());
+        let group = |key: &str| -> Result<Vec<Condition>> {
+            let Some(items) = object[key].as_array() else {
+                bail!("{}/{}: must be an array of conditions", path, key);
+            };
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| Condition::from_value(item, &format!("{}/{}/{}", path, key, i)))
+                .collect()
+        };
+        let expression = match *operator {
+            "all" => Expression::All(group("all")?),
+            "any" => Expression::Any(group("any")?),
+            "not" => Expression::Not(Box::new(Condition::from_value(
+                &object["not"],
+                &format!("{}/not", path),
+            )?)),
+            _ => {
+                let mut clause = object.clone();
+                clause.insert("id".to_string(), JsonValue::String(id.clone()));
+                Expression::Test(
+                    serde_json::from_value(JsonValue::Object(clause))
+                        .with_context(|| format!("{}: invalid indicator test", path))?,
+                )
+            }
+        };
+        Ok(Self {
+            id,
+            description: optional_string(object, "description"),
+            expression,
+        })
+    }
+
+    fn evaluate(&self, indicators: &JsonValue, active_label: &str) -> ConditionResult {
+        let mut result = ConditionResult {
+            id: self.id.clone(),
+            description: self.description.clone(),
+            operator: Operator::Test,
+            status: ClauseStatus::Pass,
+            indicator: None,
+            actual: None,
+            message: None,
+            conditions: Vec::new(),
+        };
+        match &self.expression {
+            Expression::All(conditions) | Expression::Any(conditions) => {
+                let all = matches!(self.expression, Expression::All(_));
+                result.operator = if all { Operator::All } else { Operator::Any };
+                result.conditions = conditions
+                    .iter()
+                    .map(|c| c.evaluate(indicators, active_label))
+                    .collect();
+                let count = |status| {
+                    result
+                        .conditions
+                        .iter()
+                        .filter(|c| c.status == status)
+                        .count()
+                };
+                let (passed, failed) = (count(ClauseStatus::Pass), count(ClauseStatus::Fail));
+                result.status = if passed + failed == 0 && !conditions.is_empty() {
+                    ClauseStatus::NotApplicable
+                } else if all && failed > 0 {
+                    result.message = Some(format!("{} of {} failed", failed, passed + failed));
+                    ClauseStatus::Fail
+                } else if !all && passed == 0 {
+                    result.message = Some("no condition holds".to_string());
+                    ClauseStatus::Fail
+                } else {
+                    ClauseStatus::Pass
+                };
+            }
+            Expression::Not(condition) => {
+                result.operator = Operator::Not;
+                let inner = condition.evaluate(indicators, active_label);
+                result.status = match inner.status {
+                    ClauseStatus::Pass => {
+                        result.message = Some("condition holds but must not".to_string());
+                        ClauseStatus::Fail
+                    }
+                    ClauseStatus::Fail => ClauseStatus::Pass,
+                    ClauseStatus::NotApplicable => ClauseStatus::NotApplicable,
+                };
+                result.conditions = vec![inner];
+            }
+            Expression::Test(clause) => {
+                let actual = resolve_indicator(indicators, active_label, &clause.indicator);
+                result.indicator = Some(clause.indicator.clone());
+                result.actual = actual.cloned();
+                result.status = match check_clause(clause, actual) {
+                    None => ClauseStatus::Pass,
+                    Some(message) => {
+                        result.message = Some(message);
+                        if actual.is_none() && !clause.required {
+                            ClauseStatus::NotApplicable
+                        } else {
+                            ClauseStatus::Fail
+                        }
+                    }
+                };
+            }
+        }
+        result
+    }
+}
+
+impl TrustProfile {
+    /// Parse a Trust Profile; errors name the JSON Pointer of the invalid condition.
+    pub fn from_value(value: &JsonValue) -> Result<Self> {
+        let Some(object) = value.as_object() else {
+            bail!("A trust profile must be a JSON object");
+        };
+        let Some(condition) = object.get("condition") else {
+            bail!("A trust profile needs a \"condition\"");
+        };
+        Ok(Self {
+            name: optional_string(object, "name"),
+            version: optional_string(object, "version"),
+            description: optional_string(object, "description"),
+            condition: Condition::from_value(condition, "/condition")?,
+        })
+    }
+}
+
+/// Load and parse a Trust Profile JSON file.
+pub fn load_trust_profile<P: AsRef<Path>>(path: P) -> Result<TrustProfile> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read trust profile: {:?}", path))?;
+    let value = crate::limits::parse_json(&text)
+        .with_context(|| format!("Failed to parse trust profile: {:?}", path))?;
+    TrustProfile::from_value(&value).with_context(|| format!("Invalid trust profile: {:?}", path))
+}
+
+/// Result of one condition, with the results of the conditions below it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionResult {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub operator: Operator,
+    pub status: ClauseStatus,
+    /// Indicator of a test.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indicator: Option<String>,
+    /// Indicator value found in the crJSON, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<JsonValue>,
+    /// Why the condition failed or was not applicable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<ConditionResult>,
+}
+
+impl ConditionResult {
+    /// Number of tests below (or at) this condition with `status`.
+    pub fn count_tests(&self, status: ClauseStatus) -> usize {
+        match self.operator {
+            Operator::Test => usize::from(self.status == status),
+            _ => self.conditions.iter().map(|c| c.count_tests(status)).sum(),
+        }
+    }
+}
+
+/// Trust report for one asset against one Trust Profile.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustReport {
+    pub trust_profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub asset: String,
+    pub active_manifest: String,
+    /// Whether the root condition holds (or is not applicable).
+    pub satisfied: bool,
+    /// Tests that passed, failed, or were not applicable.
+    pub passed: usize,
+    pub failed: usize,
+    pub not_applicable: usize,
+    pub result: ConditionResult,
+}
+
+/// Evaluate `profile` against the crJSON `indicators` (with `active_label`).
+pub fn evaluate(
+    profile: &TrustProfile,
+    indicators: &JsonValue,
+    active_label: &str,
+    asset: &str,
+) -> TrustReport {
+    let result = profile.condition.evaluate(indicators, active_label);
+    TrustReport {
+        trust_profile: profile.name.clone(),
+        version: profile.version.clone(),
+        asset: asset.to_string(),
+        active_manifest: active_label.to_string(),
+        satisfied: result.status != ClauseStatus::Fail,
+        passed: result.count_tests(ClauseStatus::Pass),
+        failed: result.count_tests(ClauseStatus::Fail),
+        not_applicable: result.count_tests(ClauseStatus::NotApplicable),
+        result,
+    }
+}
+
+/// Evaluate `profile` against a crJSON document read from a file, whose first manifest is the
+/// active one.
+pub fn evaluate_indicators(
+    profile: &TrustProfile,
+    indicators: &JsonValue,
+    asset: &str,
+) -> TrustReport {
+    let active_label = indicators
+        .get("manifests")
+        .and_then(|m| m.as_array())
+        .and_then(|m| m.first())
+        .and_then(|m| m.get("label"))
+        .and_then(|l| l.as_str())
+        .unwrap_or_default();
+    evaluate(profile, indicators, active_label, asset)
+}
+
+/// Evaluate `profile` against an extraction result.
+pub fn evaluate_extraction(
+    profile: &TrustProfile,
+    result: &ManifestExtractionResult,
+) -> TrustReport {
+    evaluate(
+        profile,
+        &result.manifest_value,
+        &result.active_label,
+        &result.input_path,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn crjson() -> JsonValue {
+        json!({
+            "manifests": [{
+                "label": "urn:c2pa:active",
+                "signature": { "algorithm": "ES256" },
+                "assertions": { "c2pa.actions.v2": { "actions": [{ "action": "c2pa.created" }] } },
+                "validationResults": { "failure": [] }
+            }]
+        })
+    }
+
+    #[test]
+    fn test_evaluate_condition_tree() {
+        let profile = TrustProfile::from_value(&json!({
+            "name": "newsroom",
+            "condition": { "all": [
+                { "id": "alg", "indicator": "/activeManifest/signature/algorithm",
+                  "oneOf": ["ES256", "Ed25519"] },
+                { "any": [
+                    { "indicator": "/activeManifest/assertions/c2pa.actions", "exists": true },
+                    { "indicator": "/activeManifest/assertions/c2pa.actions.v2", "exists": true }
+                ] },
+                { "not": { "indicator": "/activeManifest/validationResults/failure",
+                           "minItems": 1 } },
+                { "indicator": "/activeManifest/assertions/c2pa.training-mining",
+                  "required": false, "exists": true }
+            ] }
+        }))
+        .unwrap();
+        let report = evaluate_indicators(&profile, &crjson(), "a.json");
+        assert!(report.satisfied, "{:#?}", report.result);
+        assert_eq!(report.active_manifest, "urn:c2pa:active");
+        assert_eq!(
+            (report.passed, report.failed, report.not_applicable),
+            (2, 2, 1)
+        );
+        let children = &report.result.conditions;
+        assert_eq!(children[0].id, "alg");
+        assert_eq!(children[1].conditions[0].id, "/condition/all/1/any/0");
+        assert_eq!(children[1].status, ClauseStatus::Pass);
+        assert_eq!(children[2].operator, Operator::Not);
+        assert_eq!(children[2].status, ClauseStatus::Pass);
+        assert_eq!(children[3].status, ClauseStatus::NotApplicable);
+
+        let strict = TrustProfile::from_value(&json!({
+            "condition": { "all": [
+                { "indicator": "/activeManifest/signature/algorithm", "equals": "PS256" }
+            ] }
+        }))
+        .unwrap();
+        let report = evaluate_indicators(&strict, &crjson(), "a.json");
+        assert!(!report.satisfied);
+        assert_eq!(report.result.message.as_deref(), Some("1 of 1 failed"));
+    }
+
+    #[test]
+    fn test_invalid_profiles() {
+        let error = TrustProfile::from_value(&json!({
+            "condition": { "all": [{ "any": [], "not": {} }] }
+        }))
+        .unwrap_err();
+        assert!(error.to_string().contains("/condition/all/0"), "{error}");
+        assert!(TrustProfile::from_value(&json!({ "name": "x" })).is_err());
+        assert!(TrustProfile::from_value(&json!({ "condition": { "all": {} } })).is_err());
+    }
+}
@@ -0,0 +1,978 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Provenance graph over a crJSON document: manifests are nodes and ingredient assertions that
+//! reference another manifest in the store are edges. Shared by `--where` facts, the
+//! post-signing tree view, ingredient conflict detection, and anything else that needs to walk
+//! the ingredient chain.
+//!
+//! [`ProvenanceGraph`] holds every manifest of the store once, with its ingredients as edges,
+//! and finds reference cycles; it backs the GUI's ingredient tree and the graph exports.
+//! [`build_tree`] expands the chain below one manifest into a tree.
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Deepest ingredient chain followed before an edge is left unexpanded.
+pub const MAX_PROVENANCE_DEPTH: usize = 64;
+
+/// Most manifest nodes built for one tree; bounds the blow-up of heavily shared chains.
+pub const MAX_PROVENANCE_NODES: usize = 10_000;
+
+/// Find a manifest in the document's `manifests` list by label (or claim instanceID).
+pub fn find_manifest<'a>(crjson: &'a JsonValue, label: &str) -> Option<&'a JsonValue> {
+    crjson.get("manifests")?.as_array()?.iter().find(|m| {
+        m.get("label").and_then(|v| v.as_str()) == Some(label)
+            || m.get("claim.v2")
+                .or_else(|| m.get("claim"))
+                .and_then(|c| c.get("instanceID").or_else(|| c.get("instance_id")))
+                .and_then(|v| v.as_str())
+                == Some(label)
+    })
+}
+
+/// True for ingredient assertion labels: `c2pa.ingredient`, `.v2`, `.v3`, and `__N` instances.
+fn is_ingredient_label(key: &str) -> bool {
+    key == "c2pa.ingredient"
+        || key.starts_with("c2pa.ingredient.")
+        || key.starts_with("c2pa.ingredient__")
+}
+
+/// Ingredient assertion payloads of a manifest, in assertion order.
+pub fn ingredient_assertions(manifest: &JsonValue) -> Vec<&JsonValue> {
+    manifest
+        .get("assertions")
+        .and_then(|a| a.as_object())
+        .map(|a| {
+            a.iter()
+                .filter(|(k, _)| is_ingredient_label(k))
+                .map(|(_, v)| v)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Label of the manifest an ingredient points to (`activeManifest` string or hashed URI).
+pub fn ingredient_manifest_label(ingredient: &JsonValue) -> Option<&str> {
+    let am = ingredient
+        .get("activeManifest")
+        .or_else(|| ingredient.get("active_manifest"))?;
+    let s = am.as_str().or_else(|| {
+        am.get("url")
+            .or_else(|| am.get("uri"))
+            .and_then(|v| v.as_str())
+    })?;
+    match s.find("urn:c2pa:") {
+        Some(start) => {
+            let rest = &s[start..];
+            Some(&rest[..rest.find('/').unwrap_or(rest.len())])
+        }
+        None => Some(s),
+    }
+}
+
+/// Title of a manifest (claim `dc:title`/`title`, else a top-level `title`).
+pub fn manifest_title(manifest: &JsonValue) -> Option<&str> {
+    manifest
+        .get("claim.v2")
+        .or_else(|| manifest.get("claim"))
+        .and_then(|c| c.get("dc:title").or_else(|| c.get("title")))
+        .or_else(|| manifest.get("title"))
+        .and_then(|v| v.as_str())
+}
+
+/// Action names from the manifest's `c2pa.actions` / `c2pa.actions.v2` assertions.
+pub fn manifest_actions(manifest: &JsonValue) -> Vec<String> {
+    let Some(assertions) = manifest.get("assertions").and_then(|a| a.as_object()) else {
+        return Vec::new();
+    };
+    assertions
+        .iter()
+        .filter(|(k, _)| k.starts_with("c2pa.actions"))
+        .filter_map(|(_, v)| v.get("actions").and_then(|a| a.as_array()))
+        .flatten()
+        .filter_map(|a| a.get("action").and_then(|v| v.as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Display title of an ingredient: its title, else an identifier or file path.
+pub fn ingredient_title(ingredient: &JsonValue) -> String {
+    ["title", "dc:title", "instanceID", "documentID", "file_path"]
+        .iter()
+        .find_map(|k| ingredient.get(*k).and_then(|v| v.as_str()))
+        .unwrap_or("(no title)")
+        .to_string()
+}
+
+/// Identifier fields of an ingredient used for conflict detection, as (field, value) pairs.
+pub fn ingredient_ids(ingredient: &JsonValue) -> Vec<(&'static str, &str)> {
+    [
+        ("instanceID", ["instanceID", "instance_id"]),
+        ("documentID", ["documentID", "document_id"]),
+    ]
+    .into_iter()
+    .filter_map(|(field, keys)| {
+        keys.iter()
+            .find_map(|k| ingredient.get(*k).and_then(|v| v.as_str()))
+            .map(|id| (field, id))
+    })
+    .collect()
+}
+
+/// Hash identifying the ingredient's content or manifest: the hash of its `activeManifest`
+/// (`c2pa_manifest` in v1), `claimSignature`, or `data` reference, else a plain `hash` field.
+pub fn ingredient_fingerprint(ingredient: &JsonValue) -> Option<&str> {
+    ["activeManifest", "c2pa_manifest", "claimSignature", "data"]
+        .iter()
+        .find_map(|k| ingredient.get(*k)?.get("hash")?.as_str())
+        .or_else(|| ingredient.get("hash").and_then(|v| v.as_str()))
+}
+
+/// One ingredient assertion taking part in a conflict.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictingIngredient {
+    pub manifest: String,
+    pub assertion: String,
+    pub title: String,
+    pub hash: String,
+}
+
+/// Ingredients that declare the same instanceID/documentID with different hashes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngredientConflict {
+    /// `instanceID` or `documentID`.
+    pub field: &'static str,
+    pub id: String,
+    pub ingredients: Vec<ConflictingIngredient>,
+}
+
+impl std::fmt::Display for IngredientConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let titles: Vec<&str> = self.ingredients.iter().map(|i| i.title.as_str()).collect();
+        write!(
+            f,
+            "ingredient conflict: {} {} declared with different hashes ({})",
+            self.field,
+            self.id,
+            titles.join(", ")
+        )
+    }
+}
+
+/// Find ingredients, within one manifest or across the whole store, that share an instanceID
+/// or documentID but carry different hashes. Ingredients without a hash are not compared.
+pub fn detect_ingredient_conflicts(crjson: &JsonValue) -> Vec<IngredientConflict> {
+    let mut by_id: BTreeMap<(&'static str, &str), Vec<ConflictingIngredient>> = BTreeMap::new();
+    let manifests = crjson
+        .get("manifests")
+        .and_then(|m| m.as_array())
+        .map(|m| m.as_slice())
+        .unwrap_or_default();
+    for manifest in manifests {
+        let label = manifest.get("label").and_then(|l| l.as_str()).unwrap_or("");
+        let Some(assertions) = manifest.get("assertions").and_then(|a| a.as_object()) else {
+            continue;
+        };
+        for (key, ingredient) in assertions.iter().filter(|(k, _)| is_ingredient_label(k)) {
+            let Some(hash) = ingredient_fingerprint(ingredient) else {
+                continue;
+            };
+            for (field, id) in ingredient_ids(ingredient) {
+                by_id
+                    .entry((field, id))
+                    .or_default()
+                    .push(ConflictingIngredient {
+                        manifest: label.to_string(),
+                        assertion: key.clone(),
+                        title: ingredient_title(ingredient),
+                        hash: hash.to_string(),
+                    });
+            }
+        }
+    }
+    by_id
+        .into_iter()
+        .filter(|(_, entries)| entries.iter().any(|e| e.hash != entries[0].hash))
+        .map(|((field, id), ingredients)| IngredientConflict {
+            field,
+            id: id.to_string(),
+            ingredients,
+        })
+        .collect()
+}
+
+/// A broken ingredient relationship constraint of one manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum RelationshipViolation {
+    /// More than one `parentOf` ingredient; a manifest has at most one parent.
+    #[serde(rename_all = "camelCase")]
+    MultipleParents {
+        manifest: String,
+        ingredients: Vec<String>,
+    },
+    /// An ingredient refers to the manifest or asset that declares it.
+    #[serde(rename_all = "camelCase")]
+    SelfReference {
+        manifest: String,
+        ingredient: String,
+    },
+    /// The same ingredient is declared both `componentOf` and `parentOf`.
+    #[serde(rename_all = "camelCase")]
+    ComponentAlsoParent {
+        manifest: String,
+        ingredient: String,
+    },
+}
+
+impl std::fmt::Display for RelationshipViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelationshipViolation::MultipleParents {
+                manifest,
+                ingredients,
+            } => write!(
+                f,
+                "{}: {} parentOf ingredients ({}); at most one is allowed",
+                manifest,
+                ingredients.len(),
+                ingredients.join(", ")
+            ),
+            RelationshipViolation::SelfReference {
+                manifest,
+                ingredient,
+            } => write!(
+                f,
+                "{}: ingredient {} refers to the manifest itself",
+                manifest, ingredient
+            ),
+            RelationshipViolation::ComponentAlsoParent {
+                manifest,
+                ingredient,
+            } => write!(
+                f,
+                "{}: ingredient {} is declared both componentOf and parentOf",
+                manifest, ingredient
+            ),
+        }
+    }
+}
+
+/// Relationship of an ingredient; `componentOf` when absent, as in c2pa-rs.
+fn ingredient_relationship(ingredient: &JsonValue) -> &str {
+    ingredient
+        .get("relationship")
+        .and_then(|v| v.as_str())
+        .unwrap_or("componentOf")
+}
+
+/// Values that identify an ingredient across entries: its instanceID, the manifest it
+/// references, and (in manifest definitions) its `file_path`.
+fn ingredient_keys(ingredient: &JsonValue) -> Vec<&str> {
+    let mut keys: Vec<&str> = ingredient_ids(ingredient)
+        .into_iter()
+        .filter(|(field, _)| *field == "instanceID")
+        .map(|(_, id)| id)
+        .collect();
+    keys.extend(ingredient_manifest_label(ingredient));
+    keys.extend(ingredient.get("file_path").and_then(|v| v.as_str()));
+    keys
+}
+
+/// Check the ingredients of one manifest (crJSON ingredient assertions or manifest definition
+/// entries) against the relationship constraints. `self_ids` identify the manifest or asset
+/// itself (label, instanceID, asset path); an ingredient matching one is a self-reference.
+pub fn relationship_violations(
+    manifest: &str,
+    self_ids: &[&str],
+    ingredients: &[&JsonValue],
+) -> Vec<RelationshipViolation> {
+    let mut violations = Vec::new();
+    let parents: Vec<&JsonValue> = ingredients
+        .iter()
+        .copied()
+        .filter(|i| ingredient_relationship(i) == "parentOf")
+        .collect();
+    if parents.len() > 1 {
+        violations.push(RelationshipViolation::MultipleParents {
+            manifest: manifest.to_string(),
+            ingredients: parents.iter().map(|i| ingredient_title(i)).collect(),
+        });
+    }
+    for ingredient in ingredients {
+        if ingredient_keys(ingredient)
+            .iter()
+            .any(|key| self_ids.contains(key))
+        {
+            violations.push(RelationshipViolation::SelfReference {
+                manifest: manifest.to_string(),
+                ingredient: ingredient_title(ingredient),
+            });
+        }
+    }
+    for component in ingredients
+        .iter()
+        .filter(|i| ingredient_relationship(i) == "componentOf")
+    {
+        let keys = ingredient_keys(component);
+        if parents
+            .iter()
+            .any(|parent| ingredient_keys(parent).iter().any(|k| keys.contains(k)))
+        {
+            violations.push(RelationshipViolation::ComponentAlsoParent {
+                manifest: manifest.to_string(),
+                ingredient: ingredient_title(component),
+            });
+        }
+    }
+    violations
+}
+
+/// Relationship constraint violations of every manifest in a crJSON document.
+pub fn detect_relationship_violations(crjson: &JsonValue) -> Vec<RelationshipViolation> {
+    let manifests = crjson
+        .get("manifests")
+        .and_then(|m| m.as_array())
+        .map(|m| m.as_slice())
+        .unwrap_or_default();
+    manifests
+        .iter()
+        .flat_map(|manifest| {
+            let label = manifest.get("label").and_then(|l| l.as_str()).unwrap_or("");
+            let mut self_ids = vec![label];
+            self_ids.extend(
+                manifest
+                    .get("claim.v2")
+                    .or_else(|| manifest.get("claim"))
+                    .and_then(|c| c.get("instanceID").or_else(|| c.get("instance_id")))
+                    .and_then(|v| v.as_str()),
+            );
+            relationship_violations(label, &self_ids, &ingredient_assertions(manifest))
+        })
+        .collect()
+}
+
+/// One manifest in the provenance tree.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceNode {
+    pub label: String,
+    pub title: Option<String>,
+    pub actions: Vec<String>,
+    pub ingredients: Vec<ProvenanceEdge>,
+}
+
+/// An ingredient of a manifest, with the ingredient's own manifest when it has one.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceEdge {
+    pub title: String,
+    pub relationship: Option<String>,
+    /// Label the ingredient references, even when that manifest is not in the store.
+    pub manifest_label: Option<String>,
+    pub manifest: Option<Box<ProvenanceNode>>,
+    /// The reference points back to a manifest already on the current path.
+    pub cycle: bool,
+    /// Not expanded because [`MAX_PROVENANCE_DEPTH`] or [`MAX_PROVENANCE_NODES`] was reached.
+    pub truncated: bool,
+}
+
+/// Build the provenance tree rooted at manifest `label`. Cycles and chains beyond the depth or
+/// node limits are cut off and marked on the edge; see [`ProvenanceNode::diagnostics`].
+pub fn build_tree(crjson: &JsonValue, label: &str) -> Option<ProvenanceNode> {
+    build_node(crjson, label, &mut Walk::default())
+}
+
+/// State of one tree walk: manifests on the current path (the visited set for cycle detection)
+/// and the number of nodes built so far.
+#[derive(Default)]
+struct Walk {
+    path: HashSet<String>,
+    nodes: usize,
+}
+
+fn build_node(crjson: &JsonValue, label: &str, walk: &mut Walk) -> Option<ProvenanceNode> {
+    let manifest = find_manifest(crjson, label)?;
+    walk.path.insert(label.to_string());
+    walk.nodes += 1;
+    let ingredients = ingredient_assertions(manifest)
+        .into_iter()
+        .map(|ingredient| {
+            let manifest_label = ingredient_manifest_label(ingredient);
+            let cycle = manifest_label.is_some_and(|l| walk.path.contains(l));
+            let truncated = manifest_label.is_some()
+                && !cycle
+                && (walk.path.len() >= MAX_PROVENANCE_DEPTH || walk.nodes >= MAX_PROVENANCE_NODES);
+            ProvenanceEdge {
+                title: ingredient_title(ingredient),
+                relationship: ingredient
+                    .get("relationship")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+                manifest_label: manifest_label.map(str::to_string),
+                manifest: match manifest_label {
+                    Some(l) if !cycle && !truncated => build_node(crjson, l, walk).map(Box::new),
+                    _ => None,
+                },
+                cycle,
+                truncated,
+            }
+        })
+        .collect();
+    walk.path.remove(label);
+    Some(ProvenanceNode {
+        label: label.to_string(),
+        title: manifest_title(manifest).map(str::to_string),
+        actions: manifest_actions(manifest),
+        ingredients,
+    })
+}
+
+/// Longest chain of ingredient manifests below `label` (0 when no ingredient has a manifest).
+pub fn chain_depth(crjson: &JsonValue, label: &str) -> usize {
+    build_tree(crjson, label).map_or(0, |node| node.depth())
+}
+
+impl ProvenanceNode {
+    /// Longest chain of ingredient manifests below this node.
+    pub fn depth(&self) -> usize {
+        self.ingredients
+            .iter()
+            .filter_map(|e| e.manifest.as_ref())
+            .map(|m| 1 + m.depth())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Problems found while building the tree (cycles, depth or node limits), one line each.
+    pub fn diagnostics(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        self.collect_diagnostics(&mut out);
+        out
+    }
+
+    fn collect_diagnostics(&self, out: &mut Vec<String>) {
+        for edge in &self.ingredients {
+            match (&edge.manifest, &edge.manifest_label) {
+                (Some(node), _) => node.collect_diagnostics(out),
+                (None, Some(target)) if edge.cycle => out.push(format!(
+                    "ingredient cycle: {} references {} already on the chain",
+                    self.label, target
+                )),
+                (None, Some(target)) if edge.truncated => out.push(format!(
+                    "ingredient chain cut at {} → {}: depth or size limit reached",
+                    self.label, target
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    /// Condensed text tree: title, actions, and ingredients with relationships.
+    pub fn render_tree(&self) -> String {
+        let mut out = String::new();
+        self.render_into(&mut out, "");
+        out
+    }
+
+    fn render_into(&self, out: &mut String, indent: &str) {
+        out.push_str(&format!(
+            "{} [{}]\n",
+            self.title.as_deref().unwrap_or("(untitled)"),
+            self.label
+        ));
+        if !self.actions.is_empty() {
+            let branch = if self.ingredients.is_empty() {
+                "   "
+            } else {
+                "│  "
+            };
+            out.push_str(&format!(
+                "{indent}{branch}actions: {}\n",
+                self.actions.join(", ")
+            ));
+        }
+        for (i, edge) in self.ingredients.iter().enumerate() {
+            let last = i + 1 == self.ingredients.len();
+            let (branch, child_indent) = if last {
+                ("└─ ", format!("{indent}   "))
+            } else {
+                ("├─ ", format!("{indent}│  "))
+            };
+            let relationship = edge.relationship.as_deref().unwrap_or("ingredient");
+            out.push_str(&format!("{indent}{branch}{relationship}: {}", edge.title));
+            match (&edge.manifest, edge.cycle, &edge.manifest_label) {
+                (Some(node), _, _) => {
+                    out.push_str(" → ");
+                    node.render_into(out, &child_indent);
+                }
+                (None, true, Some(label)) => out.push_str(&format!(" (cycle to {})\n", label)),
+                (None, _, Some(label)) if edge.truncated => {
+                    out.push_str(&format!(" (limit reached, {} not expanded)\n", label))
+                }
+                (None, _, Some(label)) => {
+                    out.push_str(&format!(" (manifest {} not in store)\n", label))
+                }
+                (None, _, None) => out.push_str(" (no manifest)\n"),
+            }
+        }
+    }
+}
+
+/// What a graph node stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphNodeKind {
+    /// A manifest in the store.
+    Manifest,
+    /// A manifest referenced by an ingredient but not present in the store.
+    MissingManifest,
+    /// An ingredient without a manifest of its own.
+    Ingredient,
+}
+
+impl GraphNodeKind {
+    /// camelCase name, as serialized.
+    pub fn name(self) -> &'static str {
+        match self {
+            GraphNodeKind::Manifest => "manifest",
+            GraphNodeKind::MissingManifest => "missingManifest",
+            GraphNodeKind::Ingredient => "ingredient",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNode {
+    /// Manifest label, or a blank-node id (`_:ingredientN`) for ingredients without a manifest.
+    pub id: String,
+    pub kind: GraphNodeKind,
+    pub title: Option<String>,
+    pub actions: Vec<String>,
+    /// The asset's active manifest.
+    pub active: bool,
+}
+
+/// `source` (a manifest) used `target` as an ingredient.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    /// Label of the ingredient assertion in the source manifest.
+    pub assertion: String,
+    /// Ingredient relationship: `parentOf`, `componentOf`, `inputTo`.
+    pub relationship: Option<String>,
+    /// Title the ingredient assertion gives the ingredient.
+    pub title: Option<String>,
+}
+
+impl GraphEdge {
+    /// The ingredient assertion payload behind this edge.
+    pub fn ingredient<'a>(&self, crjson: &'a JsonValue) -> Option<&'a JsonValue> {
+        find_manifest(crjson, &self.source)?
+            .get("assertions")?
+            .get(&self.assertion)
+    }
+}
+
+/// A chain of ingredient references that leads back to where it started, as manifest labels
+/// (the first label is repeated at the end).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngredientCycle {
+    pub manifests: Vec<String>,
+}
+
+impl std::fmt::Display for IngredientCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ingredient cycle: {}", self.manifests.join(" → "))
+    }
+}
+
+/// Manifests and ingredients of one crJSON document as nodes and edges.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl ProvenanceGraph {
+    /// Build the graph of every manifest in `crjson`, marking `active_label` as the active one.
+    /// Ingredients referencing a manifest by claim instanceID point to that manifest's label.
+    pub fn from_crjson(crjson: &JsonValue, active_label: Option<&str>) -> Self {
+        let manifests = crjson
+            .get("manifests")
+            .and_then(|m| m.as_array())
+            .map(|m| m.as_slice())
+            .unwrap_or_default();
+        let mut graph = ProvenanceGraph::default();
+        let mut missing = BTreeSet::new();
+        for manifest in manifests {
+            let Some(label) = manifest.get("label").and_then(|l| l.as_str()) else {
+                continue;
+            };
+            graph.nodes.push(GraphNode {
+                id: label.to_string(),
+                kind: GraphNodeKind::Manifest,
+                title: manifest_title(manifest).map(str::to_string),
+                actions: manifest_actions(manifest),
+                active: active_label == Some(label),
+            });
+            let Some(assertions) = manifest.get("assertions").and_then(|a| a.as_object()) else {
+                continue;
+            };
+            for (key, ingredient) in assertions.iter().filter(|(k, _)| is_ingredient_label(k)) {
+                let title = ["title", "dc:title"]
+                    .iter()
+                    .find_map(|k| ingredient.get(*k).and_then(|v| v.as_str()))
+                    .map(str::to_string);
+                let target = match ingredient_manifest_label(ingredient) {
+                    Some(target) => match find_manifest(crjson, target) {
+                        Some(m) => m
+                            .get("label")
+                            .and_then(|l| l.as_str())
+                            .unwrap_or(target)
+                            .to_string(),
+                        None => {
+                            missing.insert((target.to_string(), title.clone()));
+                            target.to_string()
+                        }
+                    },
+                    None => {
+                        let id = format!("_:ingredient{}", graph.nodes.len());
+                        graph.nodes.push(GraphNode {
+                            id: id.clone(),
+                            kind: GraphNodeKind::Ingredient,
+                            title: title.clone(),
+                            actions: Vec::new(),
+                            active: false,
+                        });
+                        id
+                    }
+                };
+                graph.edges.push(GraphEdge {
+                    source: label.to_string(),
+                    target,
+                    assertion: key.clone(),
+                    relationship: ingredient
+                        .get("relationship")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                    title,
+                });
+            }
+        }
+
+        let mut seen = BTreeSet::new();
+        for (id, title) in missing {
+            if seen.insert(id.clone()) {
+                graph.nodes.push(GraphNode {
+                    id,
+                    kind: GraphNodeKind::MissingManifest,
+                    title,
+                    actions: Vec::new(),
+                    active: false,
+                });
+            }
+        }
+        graph
+    }
+
+    /// Node with id (manifest label) `id`.
+    pub fn node(&self, id: &str) -> Option<&GraphNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// The active manifest's node.
+    pub fn active(&self) -> Option<&GraphNode> {
+        self.nodes.iter().find(|n| n.active)
+    }
+
+    /// Ingredients of manifest `id`, in assertion order.
+    pub fn ingredients_of<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a GraphEdge> {
+        self.edges.iter().filter(move |e| e.source == id)
+    }
+
+    /// Edges of the manifests that use `id` as an ingredient.
+    pub fn used_by<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a GraphEdge> {
+        self.edges.iter().filter(move |e| e.target == id)
+    }
+
+    /// Reference cycles among the manifests, one per edge that leads back onto the chain being
+    /// walked (a depth-first walk from each manifest in store order).
+    pub fn cycles(&self) -> Vec<IngredientCycle> {
+        let mut targets: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            targets
+                .entry(edge.source.as_str())
+                .or_default()
+                .push(edge.target.as_str());
+        }
+        let mut done: HashSet<&str> = HashSet::new();
+        let mut cycles = Vec::new();
+        for root in self
+            .nodes
+            .iter()
+            .filter(|n| n.kind == GraphNodeKind::Manifest)
+        {
+            if done.contains(root.id.as_str()) {
+                continue;
+            }
+            // Walked iteratively so long chains cannot overflow the stack: each entry is a
+            // manifest on the current chain and the index of its next edge.
+            let mut path: Vec<(&str, usize)> = vec![(root.id.as_str(), 0)];
+            while let Some((id, next)) = path.last_mut() {
+                let id = *id;
+                let Some(target) = targets.get(id).and_then(|t| t.get(*next)).copied() else {
+                    done.insert(id);
+                    path.pop();
+                    continue;
+                };
+                *next += 1;
+                if let Some(start) = path.iter().position(|(p, _)| *p == target) {
+                    let mut manifests: Vec<String> =
+                        path[start..].iter().map(|(p, _)| p.to_string()).collect();
+                    manifests.push(target.to_string());
+                    cycles.push(IngredientCycle { manifests });
+                } else if !done.contains(target) {
+                    path.push((target, 0));
+                }
+            }
+        }
+        cycles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crjson() -> JsonValue {
+        serde_json::json!({
+            "manifests": [
+                {
+                    "label": "urn:c2pa:a",
+                    "claim.v2": { "dc:title": "final.jpg" },
+                    "assertions": {
+                        "c2pa.actions.v2": { "actions": [{ "action": "c2pa.opened" }, { "action": "c2pa.edited" }] },
+                        "c2pa.ingredient.v3": {
+                            "title": "source.jpg", "relationship": "parentOf",
+                            "activeManifest": { "url": "self#jumbf=/c2pa/urn:c2pa:b/c2pa.claim" }
+                        },
+                        "c2pa.ingredient.v3__1": { "title": "logo.png", "relationship": "componentOf" }
+                    }
+                },
+                {
+                    "label": "urn:c2pa:b",
+                    "claim.v2": { "dc:title": "source.jpg" },
+                    "assertions": {
+                        "c2pa.ingredient.v3": { "title": "loop", "activeManifest": "urn:c2pa:a" }
+                    }
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_build_tree_follows_ingredients_and_stops_cycles() {
+        let tree = build_tree(&crjson(), "urn:c2pa:a").unwrap();
+        assert_eq!(tree.actions, vec!["c2pa.opened", "c2pa.edited"]);
+        assert_eq!(tree.ingredients.len(), 2);
+        let parent = tree.ingredients[0].manifest.as_ref().unwrap();
+        assert_eq!(parent.label, "urn:c2pa:b");
+        assert!(parent.ingredients[0].cycle);
+        assert_eq!(tree.depth(), 1);
+        assert_eq!(chain_depth(&crjson(), "urn:c2pa:a"), 1);
+        assert_eq!(
+            tree.diagnostics(),
+            vec!["ingredient cycle: urn:c2pa:b references urn:c2pa:a already on the chain"]
+        );
+    }
+
+    #[test]
+    fn test_build_tree_limits_depth() {
+        let count = MAX_PROVENANCE_DEPTH + 10;
+        let manifests: Vec<JsonValue> = (0..count)
+            .map(|i| {
+                serde_json::json!({
+                    "label": format!("urn:c2pa:{i}"),
+                    "assertions": {
+                        "c2pa.ingredient.v3": { "title": "prev", "activeManifest": format!("urn:c2pa:{}", i + 1) }
+                    }
+                })
+            })
+            .collect();
+        let crjson = serde_json::json!({ "manifests": manifests });
+        let tree = build_tree(&crjson, "urn:c2pa:0").unwrap();
+        assert_eq!(tree.depth(), MAX_PROVENANCE_DEPTH - 1);
+        let diagnostics = tree.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].contains("limit reached"));
+    }
+
+    #[test]
+    fn test_detect_ingredient_conflicts() {
+        let crjson = serde_json::json!({
+            "manifests": [
+                {
+                    "label": "urn:c2pa:a",
+                    "assertions": {
+                        "c2pa.ingredient.v3": { "title": "x.jpg", "instanceID": "xmp:iid:1", "documentID": "doc:1", "data": { "hash": "AAA" } },
+                        "c2pa.ingredient.v3__1": { "title": "y.jpg", "instanceID": "xmp:iid:2", "data": { "hash": "BBB" } }
+                    }
+                },
+                {
+                    "label": "urn:c2pa:b",
+                    "assertions": {
+                        "c2pa.ingredient.v3": { "title": "x-copy.jpg", "instanceID": "xmp:iid:1", "documentID": "doc:1", "data": { "hash": "AAA" } },
+                        "c2pa.ingredient.v3__1": { "title": "y-edit.jpg", "instanceID": "xmp:iid:2", "data": { "hash": "CCC" } }
+                    }
+                }
+            ]
+        });
+        let conflicts = detect_ingredient_conflicts(&crjson);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, "instanceID");
+        assert_eq!(conflicts[0].id, "xmp:iid:2");
+        let manifests: Vec<_> = conflicts[0]
+            .ingredients
+            .iter()
+            .map(|i| i.manifest.as_str())
+            .collect();
+        assert_eq!(manifests, vec!["urn:c2pa:a", "urn:c2pa:b"]);
+    }
+
+    #[test]
+    fn test_relationship_violations() {
+        // The fixture store satisfies the constraints
+        assert!(detect_relationship_violations(&crjson()).is_empty());
+
+        let crjson = serde_json::json!({
+            "manifests": [{
+                "label": "urn:c2pa:x",
+                "claim.v2": { "instanceID": "xmp:iid:x" },
+                "assertions": {
+                    "c2pa.ingredient.v3": { "title": "a.jpg", "relationship": "parentOf", "instanceID": "xmp:iid:a" },
+                    "c2pa.ingredient.v3__1": { "title": "b.jpg", "relationship": "parentOf", "instanceID": "xmp:iid:b" },
+                    "c2pa.ingredient.v3__2": { "title": "a copy", "relationship": "componentOf", "instanceID": "xmp:iid:a" },
+                    "c2pa.ingredient.v3__3": { "title": "itself", "relationship": "inputTo", "activeManifest": "urn:c2pa:x" }
+                }
+            }]
+        });
+        assert_eq!(
+            detect_relationship_violations(&crjson),
+            vec![
+                RelationshipViolation::MultipleParents {
+                    manifest: "urn:c2pa:x".to_string(),
+                    ingredients: vec!["a.jpg".to_string(), "b.jpg".to_string()],
+                },
+                RelationshipViolation::SelfReference {
+                    manifest: "urn:c2pa:x".to_string(),
+                    ingredient: "itself".to_string(),
+                },
+                RelationshipViolation::ComponentAlsoParent {
+                    manifest: "urn:c2pa:x".to_string(),
+                    ingredient: "a copy".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_provenance_graph() {
+        let crjson = serde_json::json!({
+            "manifests": [
+                {
+                    "label": "urn:c2pa:a",
+                    "claim.v2": { "dc:title": "final.jpg" },
+                    "assertions": {
+                        "c2pa.actions.v2": { "actions": [{ "action": "c2pa.edited" }] },
+                        "c2pa.ingredient.v3": {
+                            "title": "source.jpg", "relationship": "parentOf",
+                            "activeManifest": { "url": "self#jumbf=/c2pa/urn:c2pa:b/c2pa.claim" }
+                        },
+                        "c2pa.ingredient.v3__1": { "title": "logo.png", "relationship": "componentOf" },
+                        "c2pa.ingredient.v3__2": { "title": "gone.jpg", "activeManifest": "urn:c2pa:z" }
+                    }
+                },
+                {
+                    "label": "urn:c2pa:b",
+                    "claim.v2": { "dc:title": "source.jpg", "instanceID": "xmp:iid:b" },
+                    "assertions": {}
+                }
+            ]
+        });
+        let graph = ProvenanceGraph::from_crjson(&crjson, Some("urn:c2pa:a"));
+        let kinds: Vec<(&str, GraphNodeKind)> = graph
+            .nodes
+            .iter()
+            .map(|n| (n.id.as_str(), n.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("urn:c2pa:a", GraphNodeKind::Manifest),
+                ("_:ingredient1", GraphNodeKind::Ingredient),
+                ("urn:c2pa:b", GraphNodeKind::Manifest),
+                ("urn:c2pa:z", GraphNodeKind::MissingManifest),
+            ]
+        );
+        assert_eq!(graph.active().unwrap().id, "urn:c2pa:a");
+        let edges: Vec<&GraphEdge> = graph.ingredients_of("urn:c2pa:a").collect();
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges[0].target, "urn:c2pa:b");
+        assert_eq!(edges[0].relationship.as_deref(), Some("parentOf"));
+        assert_eq!(edges[1].assertion, "c2pa.ingredient.v3__1");
+        assert_eq!(edges[1].ingredient(&crjson).unwrap()["title"], "logo.png");
+        assert_eq!(graph.used_by("urn:c2pa:b").count(), 1);
+        assert!(graph.cycles().is_empty());
+
+        // Second manifest referencing the first by label, and itself by instanceID
+        let cyclic = serde_json::json!({
+            "manifests": [
+                { "label": "urn:c2pa:a", "assertions": {
+                    "c2pa.ingredient.v3": { "title": "b", "activeManifest": "urn:c2pa:b" } } },
+                { "label": "urn:c2pa:b", "claim.v2": { "instanceID": "xmp:iid:b" }, "assertions": {
+                    "c2pa.ingredient.v3": { "title": "a", "activeManifest": "urn:c2pa:a" },
+                    "c2pa.ingredient.v3__1": { "title": "self", "activeManifest": "xmp:iid:b" } } }
+            ]
+        });
+        let cycles = ProvenanceGraph::from_crjson(&cyclic, Some("urn:c2pa:a")).cycles();
+        assert_eq!(
+            cycles.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+            vec![
+                "ingredient cycle: urn:c2pa:a → urn:c2pa:b → urn:c2pa:a",
+                "ingredient cycle: urn:c2pa:b → urn:c2pa:b",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let rendered = build_tree(&crjson(), "urn:c2pa:a").unwrap().render_tree();
+        let expected = "\
+final.jpg [urn:c2pa:a]
+│  actions: c2pa.opened, c2pa.edited
+├─ parentOf: source.jpg → source.jpg [urn:c2pa:b]
+│  └─ ingredient: loop (cycle to urn:c2pa:a)
+└─ componentOf: logo.png (no manifest)
+";
+        assert_eq!(rendered, expected);
+    }
+}
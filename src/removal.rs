@@ -0,0 +1,252 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Heuristics for assets whose Content Credentials were stripped: remnants such as an XMP
+//! `dcterms:provenance` that still points at an embedded manifest, or JPEG APP11 JUMBF
+//! segments cut short. Only meaningful once reading the manifest has failed; the findings turn
+//! a plain "no manifest found" into "credentials likely removed".
+
+use crate::error::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// XMP property C2PA uses to point at the active manifest.
+pub(crate) const XMP_PROVENANCE: &[u8] = b"dcterms:provenance";
+
+/// JPEG APP11 marker, which carries JUMBF boxes.
+pub(crate) const APP11: u8 = 0xEB;
+
+/// One remnant suggesting Content Credentials were removed from the asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum RemovalSign {
+    /// XMP still references a manifest embedded in the file (`self#jumbf=...`).
+    #[serde(rename_all = "camelCase")]
+    DanglingXmpReference { reference: String },
+    /// APP11 JUMBF segments hold fewer bytes than the box they declare.
+    #[serde(rename_all = "camelCase")]
+    TruncatedApp11 {
+        declared_bytes: u64,
+        present_bytes: u64,
+    },
+}
+
+impl std::fmt::Display for RemovalSign {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemovalSign::DanglingXmpReference { reference } => {
+                write!(f, "XMP dcterms:provenance still references {}", reference)
+            }
+            RemovalSign::TruncatedApp11 {
+                declared_bytes,
+                present_bytes,
+            } => write!(
+                f,
+                "APP11 JUMBF segments truncated ({} of {} bytes present)",
+                present_bytes, declared_bytes
+            ),
+        }
+    }
+}
+
+/// `dcterms:provenance` values (attribute or element form) that point inside the file.
+fn xmp_self_references(data: &[u8]) -> Vec<String> {
+    xmp_provenance_values(data)
+        .into_iter()
+        .filter(|value| value.contains("jumbf="))
+        .collect()
+}
+
+/// Remote manifest URL (http or https) referenced by the asset's XMP `dcterms:provenance`, if
+/// any.
+pub fn remote_manifest_url(data: &[u8]) -> Option<String> {
+    xmp_provenance_values(data)
+        .into_iter()
+        .find(|value| value.starts_with("https://") || value.starts_with("http://"))
+}
+
+/// All distinct `dcterms:provenance` values, in order.
+fn xmp_provenance_values(data: &[u8]) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut rest = data;
+    while let Some(pos) = find(rest, XMP_PROVENANCE) {
+        rest = &rest[pos + XMP_PROVENANCE.len()..];
+        let (value, end) = match rest {
+            [b'=', b'"', tail @ ..] => (tail, b'"'),
+            [b'=', b'\'', tail @ ..] => (tail, b'\''),
+            [b'>', tail @ ..] => (tail, b'<'),
+            _ => continue,
+        };
+        let value = &value[..value.iter().position(|b| *b == end).unwrap_or(0)];
+        let value = String::from_utf8_lossy(value).trim().to_string();
+        if !value.is_empty() && !refs.contains(&value) {
+            refs.push(value);
+        }
+    }
+    refs
+}
+
+pub(crate) fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Compare declared and present JUMBF bytes per APP11 box instance in a JPEG.
+fn truncated_app11(data: &[u8]) -> Vec<RemovalSign> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Vec::new();
+    }
+    // Box instance number -> (declared size, bytes present).
+    let mut boxes: BTreeMap<u16, (u64, u64)> = BTreeMap::new();
+    let mut pos = 2;
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        if marker == 0xD9 || marker == 0xDA {
+            break;
+        }
+        if (0xD0..=0xD7).contains(&marker) || marker == 0x01 {
+            pos += 2;
+            continue;
+        }
+        let len = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let end = (pos + 2 + len).min(data.len());
+        let payload = &data[(pos + 4).min(end)..end];
+        // CI "JP", En (box instance), Z (sequence), then the repeated LBox/TBox header.
+        if marker == APP11 && payload.len() >= 16 && payload.starts_with(b"JP") {
+            let instance = u16::from_be_bytes([payload[2], payload[3]]);
+            let lbox = u32::from_be_bytes([payload[8], payload[9], payload[10], payload[11]]);
+            let (declared, header) = if lbox == 1 && payload.len() >= 24 {
+                let mut xl = [0u8; 8];
+                xl.copy_from_slice(&payload[16..24]);
+                (u64::from_be_bytes(xl), 16)
+            } else {
+                (lbox as u64, 8)
+            };
+            let entry = boxes.entry(instance).or_insert((declared, header as u64));
+            entry.1 += payload.len().saturating_sub(8 + header) as u64;
+        }
+        if end == data.len() {
+            break;
+        }
+        pos = end;
+    }
+    boxes
+        .into_values()
+        .filter(|(declared, present)| present < declared)
+        .map(
+            |(declared_bytes, present_bytes)| RemovalSign::TruncatedApp11 {
+                declared_bytes,
+                present_bytes,
+            },
+        )
+        .collect()
+}
+
+/// Look for credential-removal remnants in raw asset bytes.
+pub fn removal_signs_in_bytes(data: &[u8]) -> Vec<RemovalSign> {
+    let mut signs: Vec<RemovalSign> = xmp_self_references(data)
+        .into_iter()
+        .map(|reference| RemovalSign::DanglingXmpReference { reference })
+        .collect();
+    signs.extend(truncated_app11(data));
+    signs
+}
+
+/// Look for credential-removal remnants in an asset file. Call this after reading the manifest
+/// failed; in a file with an intact manifest the XMP reference is expected.
+pub fn detect_credential_removal<P: AsRef<Path>>(path: P) -> Result<Vec<RemovalSign>> {
+    let path = path.as_ref();
+    let data = fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+    Ok(removal_signs_in_bytes(&data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app11(instance: u16, seq: u32, lbox: u32, body: &[u8]) -> Vec<u8> {
+        let mut payload = b"JP".to_vec();
+        payload.extend_from_slice(&instance.to_be_bytes());
+        payload.extend_from_slice(&seq.to_be_bytes());
+        payload.extend_from_slice(&lbox.to_be_bytes());
+        payload.extend_from_slice(b"jumb");
+        payload.extend_from_slice(body);
+        let mut seg = vec![0xFF, APP11];
+        seg.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        seg.extend_from_slice(&payload);
+        seg
+    }
+
+    fn jpeg(segments: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = vec![0xFF, 0xD8];
+        for s in segments {
+            data.extend_from_slice(s);
+        }
+        data.extend_from_slice(&[0xFF, 0xD9]);
+        data
+    }
+
+    #[test]
+    fn test_dangling_xmp_reference() {
+        let xmp = br#"<rdf:Description dcterms:provenance="self#jumbf=/c2pa/urn:c2pa:abc"/>
+            <dcterms:provenance>self#jumbf=/c2pa/urn:c2pa:def</dcterms:provenance>
+            <dcterms:provenance>https://example.com/remote.c2pa</dcterms:provenance>"#;
+        assert_eq!(
+            removal_signs_in_bytes(xmp),
+            vec![
+                RemovalSign::DanglingXmpReference {
+                    reference: "self#jumbf=/c2pa/urn:c2pa:abc".to_string()
+                },
+                RemovalSign::DanglingXmpReference {
+                    reference: "self#jumbf=/c2pa/urn:c2pa:def".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            remote_manifest_url(xmp).as_deref(),
+            Some("https://example.com/remote.c2pa")
+        );
+        assert_eq!(remote_manifest_url(b"no xmp here"), None);
+    }
+
+    #[test]
+    fn test_truncated_app11() {
+        // Box declares 8 + 20 bytes; two segments carry 10 + 6.
+        let data = jpeg(&[app11(1, 1, 28, &[0; 10]), app11(1, 2, 28, &[0; 6])]);
+        assert_eq!(
+            removal_signs_in_bytes(&data),
+            vec![RemovalSign::TruncatedApp11 {
+                declared_bytes: 28,
+                present_bytes: 24
+            }]
+        );
+
+        let complete = jpeg(&[app11(1, 1, 28, &[0; 12]), app11(1, 2, 28, &[0; 8])]);
+        assert!(removal_signs_in_bytes(&complete).is_empty());
+
+        // Segment running past the end of the file.
+        let mut cut = jpeg(&[app11(1, 1, 28, &[0; 20])]);
+        cut.truncate(cut.len() - 10);
+        assert_eq!(
+            removal_signs_in_bytes(&cut),
+            vec![RemovalSign::TruncatedApp11 {
+                declared_bytes: 28,
+                present_bytes: 20
+            }]
+        );
+    }
+}
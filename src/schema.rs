@@ -0,0 +1,371 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Precompiled JSON schema validation. Compiling the crJSON schema dominates the cost of
+//! validating one document, so validate many documents with one [`SchemaValidator`]; it is
+//! `Send + Sync` and can be shared across threads.
+//!
+//! Findings carry a [`Severity`]: schema violations are errors and missing recommended crJSON
+//! fields are warnings, adjusted by the validator's [`SeverityRules`].
+//!
+//! A [`ValidationProfile`] applies several schemas in sequence (e.g. the core crJSON schema and
+//! an organization's extension schema) and merges their findings into one result.
+
+use crate::error::{Context, CrtoolError, Result};
+use crate::severity::{recommended_field_findings, Severity, SeverityRules};
+use crate::{ValidationError, ValidationResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The crJSON schema at [`crate::crjson_schema_path`], embedded at build time.
+pub const BUNDLED_CRJSON_SCHEMA: &str = include_str!("../INTERNAL/schemas/crJSON-schema.json");
+
+/// Schema spec naming [`BUNDLED_CRJSON_SCHEMA`] (see [`SchemaValidator::from_spec`]).
+pub const BUNDLED_SCHEMA_SPEC: &str = "bundled";
+
+/// A JSON schema compiled once and reused for every validation.
+pub struct SchemaValidator {
+    validator: jsonschema::Validator,
+    schema_path: Option<PathBuf>,
+    rules: SeverityRules,
+}
+
+impl std::fmt::Debug for SchemaValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SchemaValidator")
+            .field("schema_path", &self.schema_path)
+            .field("rules", &self.rules)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SchemaValidator {
+    /// Compile an in-memory schema document.
+    pub fn from_value(schema: &serde_json::Value) -> Result<Self> {
+        let validator = jsonschema::validator_for(schema).map_err(|e| {
+            CrtoolError::InvalidSchema(format!("Failed to compile JSON schema: {}", e))
+        })?;
+        Ok(Self {
+            validator,
+            schema_path: None,
+            rules: SeverityRules::default(),
+        })
+    }
+
+    /// Load and compile the schema file at `path`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(CrtoolError::InvalidSchema(format!(
+                "Schema file not found at: {:?}",
+                path
+            )));
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read schema file {:?}", path))?;
+        let schema: serde_json::Value = serde_json::from_str(&content).map_err(|e| {
+            CrtoolError::InvalidSchema(format!("Failed to parse schema JSON {:?}: {}", path, e))
+        })?;
+        let mut compiled =
+            Self::from_value(&schema).with_context(|| format!("Invalid schema {:?}", path))?;
+        compiled.schema_path = Some(path.to_path_buf());
+        Ok(compiled)
+    }
+
+    /// Compile the crJSON schema bundled with crTool (see [`crate::crjson_schema_path`]). The
+    /// schema is compiled into the library, so this works without the source tree (e.g. in a
+    /// browser).
+    pub fn from_bundled() -> Result<Self> {
+        let schema: serde_json::Value = serde_json::from_str(BUNDLED_CRJSON_SCHEMA)
+            .context("Failed to parse the bundled crJSON schema")?;
+        let mut compiled = Self::from_value(&schema).context("Invalid bundled crJSON schema")?;
+        compiled.schema_path = Some(crate::crjson_schema_path());
+        Ok(compiled)
+    }
+
+    /// Compile the schema named by `spec`: [`BUNDLED_SCHEMA_SPEC`] (case-insensitive) for the
+    /// bundled crJSON schema, else the path of a schema file.
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        if spec.eq_ignore_ascii_case(BUNDLED_SCHEMA_SPEC) {
+            Self::from_bundled()
+        } else {
+            Self::from_path(spec)
+        }
+    }
+
+    /// Use `rules` for the severity of findings instead of the default levels.
+    pub fn with_rules(mut self, rules: SeverityRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Path of the schema file, when it was loaded from one.
+    pub fn schema_path(&self) -> Option<&Path> {
+        self.schema_path.as_deref()
+    }
+
+    /// Severity rules applied to findings.
+    pub fn rules(&self) -> &SeverityRules {
+        &self.rules
+    }
+
+    /// Validate a JSON value. `file_path` of the result is empty.
+    pub fn validate(&self, value: &serde_json::Value) -> ValidationResult {
+        let mut findings = self.schema_findings(value);
+        findings.extend(recommended_field_findings(value));
+        ValidationResult::from_findings(String::new(), self.rules.apply(findings))
+    }
+
+    /// Violations of the schema alone, as errors, before any [`SeverityRules`].
+    fn schema_findings(&self, value: &serde_json::Value) -> Vec<ValidationError> {
+        match self.validator.validate(value) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors
+                .map(|error| {
+                    let instance_path = error.instance_path.to_string();
+                    // The last segment of the schema path is the keyword that failed.
+                    let schema_path = error.schema_path.to_string();
+                    let rule = schema_path.rsplit('/').next().unwrap_or_default();
+                    ValidationError {
+                        instance_path: if instance_path.is_empty() {
+                            "root".to_string()
+                        } else {
+                            instance_path
+                        },
+                        message: error.to_string(),
+                        severity: Severity::Error,
+                        rule: rule.to_string(),
+                        schema: None,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Read, parse (within [`crate::limits::extraction_limits`]), and validate a JSON file.
+    pub fn validate_file<P: AsRef<Path>>(&self, path: P) -> Result<ValidationResult> {
+        let path = path.as_ref();
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Failed to read file: {:?}", path))?;
+        let value = crate::limits::parse_json(&content)
+            .with_context(|| format!("Invalid JSON in file: {:?}", path))?;
+        let mut result = self.validate(&value);
+        result.file_path = path.to_string_lossy().to_string();
+        Ok(result)
+    }
+}
+
+/// Schemas applied in sequence to each document, e.g. the core crJSON schema followed by an
+/// organization's extension schema. Findings of every schema are merged into one result, each
+/// tagged with the label of its schema, and a document is valid only when it passes them all.
+#[derive(Debug, Default)]
+pub struct ValidationProfile {
+    schemas: Vec<(String, SchemaValidator)>,
+    rules: SeverityRules,
+}
+
+impl ValidationProfile {
+    /// A profile without schemas; add them with [`ValidationProfile::with_schema`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compile the schemas named by `specs` (see [`SchemaValidator::from_spec`]), labelled by
+    /// their spec, in order.
+    pub fn from_specs<S: AsRef<str>>(specs: &[S]) -> Result<Self> {
+        let mut profile = Self::new();
+        for spec in specs {
+            let spec = spec.as_ref();
+            let schema = SchemaValidator::from_spec(spec)
+                .with_context(|| format!("Failed to load schema {}", spec))?;
+            profile = profile.with_schema(spec, schema);
+        }
+        Ok(profile)
+    }
+
+    /// Apply `schema` after the schemas already in the profile.
+    pub fn with_schema(mut self, label: impl Into<String>, schema: SchemaValidator) -> Self {
+        self.schemas.push((label.into(), schema));
+        self
+    }
+
+    /// Use `rules` for the severity of findings of every schema.
+    pub fn with_rules(mut self, rules: SeverityRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Labels of the schemas, in the order they are applied.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.schemas.iter().map(|(label, _)| label.as_str())
+    }
+
+    /// Schemas with their labels, in the order they are applied.
+    pub fn schemas(&self) -> impl Iterator<Item = (&str, &SchemaValidator)> {
+        self.schemas
+            .iter()
+            .map(|(label, schema)| (label.as_str(), schema))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+
+    /// Validate a JSON value against every schema. `file_path` of the result is empty.
+    pub fn validate(&self, value: &serde_json::Value) -> ValidationResult {
+        let mut findings = Vec::new();
+        for (label, schema) in &self.schemas {
+            findings.extend(
+                schema
+                    .schema_findings(value)
+                    .into_iter()
+                    .map(|mut finding| {
+                        finding.schema = Some(label.clone());
+                        finding
+                    }),
+            );
+        }
+        findings.extend(recommended_field_findings(value));
+        ValidationResult::from_findings(String::new(), self.rules.apply(findings))
+    }
+
+    /// Read, parse (within [`crate::limits::extraction_limits`]), and validate a JSON file.
+    pub fn validate_file<P: AsRef<Path>>(&self, path: P) -> Result<ValidationResult> {
+        let path = path.as_ref();
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Failed to read file: {:?}", path))?;
+        let value = crate::limits::parse_json(&content)
+            .with_context(|| format!("Invalid JSON in file: {:?}", path))?;
+        let mut result = self.validate(&value);
+        result.file_path = path.to_string_lossy().to_string();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validator_reused_across_threads() {
+        let validator = SchemaValidator::from_value(&json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {"name": {"type": "string"}}
+        }))
+        .unwrap();
+
+        std::thread::scope(|scope| {
+            for i in 0..4 {
+                let validator = &validator;
+                scope.spawn(move || {
+                    assert!(
+                        validator
+                            .validate(&json!({"name": format!("n{i}")}))
+                            .is_valid
+                    );
+                    let invalid = validator.validate(&json!({"name": i}));
+                    assert!(!invalid.is_valid);
+                    assert_eq!(invalid.errors[0].instance_path, "/name");
+                });
+            }
+        });
+        assert_eq!(
+            validator.validate(&json!([])).errors[0].instance_path,
+            "root"
+        );
+    }
+
+    #[test]
+    fn test_rules_and_warnings() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {"name": {"type": "string"}}
+        });
+        let validator = SchemaValidator::from_value(&schema).unwrap();
+        let result = validator.validate(&json!({}));
+        assert!(!result.is_valid);
+        assert_eq!(result.errors[0].rule, "required");
+
+        let lenient = SchemaValidator::from_value(&schema)
+            .unwrap()
+            .with_rules(SeverityRules::new().with_level("required", Severity::Warning));
+        let result = lenient.validate(&json!({}));
+        assert!(result.is_valid);
+        assert_eq!(result.count(Severity::Warning), 1);
+
+        // A missing recommended field alone is a warning, unless warnings are errors.
+        let document = json!({"manifests": [{"claim.v2": {}}]});
+        let validator = SchemaValidator::from_value(&json!({})).unwrap();
+        let result = validator.validate(&document);
+        assert!(result.is_valid);
+        assert_eq!(result.count(Severity::Warning), 1);
+        let strict = validator.with_rules(SeverityRules::new().warnings_as_errors(true));
+        assert!(!strict.validate(&document).is_valid);
+    }
+
+    #[test]
+    fn test_profile_merges_schemas() {
+        // The extension requires a field the core schema does not know about.
+        let core = SchemaValidator::from_value(&json!({
+            "type": "object",
+            "required": ["name"]
+        }))
+        .unwrap();
+        let extension = SchemaValidator::from_value(&json!({
+            "required": ["department"],
+            "properties": {"department": {"type": "string"}}
+        }))
+        .unwrap();
+        let profile = ValidationProfile::new()
+            .with_schema("core", core)
+            .with_schema("org", extension);
+        assert_eq!(profile.labels().collect::<Vec<_>>(), ["core", "org"]);
+
+        assert!(
+            profile
+                .validate(&json!({"name": "n", "department": "news"}))
+                .is_valid
+        );
+        let result = profile.validate(&json!({"department": 1}));
+        assert!(!result.is_valid);
+        let schemas: Vec<_> = result
+            .errors
+            .iter()
+            .map(|e| e.schema.as_deref().unwrap())
+            .collect();
+        assert_eq!(schemas, ["core", "org"]);
+
+        let lenient = profile.with_rules(SeverityRules::new().suppress("type"));
+        assert!(
+            lenient
+                .validate(&json!({"name": "n", "department": 1}))
+                .is_valid
+        );
+
+        let bundled = ValidationProfile::from_specs(&["Bundled"]).unwrap();
+        assert_eq!(bundled.labels().collect::<Vec<_>>(), ["Bundled"]);
+        assert!(ValidationProfile::from_specs(&["/nonexistent/schema.json"]).is_err());
+    }
+
+    #[test]
+    fn test_from_bundled_and_missing_path() {
+        let bundled = SchemaValidator::from_bundled().unwrap();
+        assert_eq!(
+            bundled.schema_path(),
+            Some(crate::crjson_schema_path().as_path())
+        );
+        assert!(SchemaValidator::from_path("/nonexistent/schema.json").is_err());
+    }
+}
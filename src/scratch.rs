@@ -0,0 +1,119 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Scratch space for temporary files (pre-sign working copies, GUI drag-out exports).
+//!
+//! Temporaries go below [`scratch_dir`]: the directory set with [`set_scratch_dir`] (the CLI's
+//! `--temp-dir`), else `CRTOOL_TEMP_DIR`, else the system temp directory. Each user gets a
+//! [`ScratchDir`] that is removed when dropped, including while unwinding from a panic.
+
+use crate::error::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Environment variable naming the scratch directory when none is set explicitly.
+pub const TEMP_DIR_ENV: &str = "CRTOOL_TEMP_DIR";
+
+/// Free space below which [`check_scratch_space`] warns (1 GiB).
+pub const LOW_SCRATCH_SPACE: u64 = 1024 * 1024 * 1024;
+
+static SCRATCH_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Use `dir` for all temporary files of this process.
+pub fn set_scratch_dir(dir: impl Into<PathBuf>) {
+    if let Ok(mut current) = SCRATCH_DIR.write() {
+        *current = Some(dir.into());
+    }
+}
+
+/// Directory temporary files are created in.
+pub fn scratch_dir() -> PathBuf {
+    SCRATCH_DIR
+        .read()
+        .ok()
+        .and_then(|dir| dir.clone())
+        .or_else(|| std::env::var_os(TEMP_DIR_ENV).map(PathBuf::from))
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Create the scratch directory if needed and return the space available in it, in bytes.
+#[cfg(feature = "fs")]
+pub fn check_scratch_space() -> Result<u64> {
+    let dir = scratch_dir();
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create temp directory {:?}", dir))?;
+    fs2::available_space(&dir)
+        .with_context(|| format!("Failed to query free space in temp directory {:?}", dir))
+}
+
+/// A uniquely named directory below [`scratch_dir`], removed with its contents on drop.
+#[derive(Debug)]
+pub struct ScratchDir {
+    path: PathBuf,
+}
+
+impl ScratchDir {
+    /// Create `<scratch>/<prefix>-<pid>-<n>`.
+    pub fn new(prefix: &str) -> Result<Self> {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let path = scratch_dir().join(format!(
+            "{}-{}-{}",
+            prefix,
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create temp directory {:?}", path))?;
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of `name` inside this directory.
+    pub fn join(&self, name: impl AsRef<Path>) -> PathBuf {
+        self.path.join(name)
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scratch_dir_is_removed_on_drop_and_unwind() {
+        let dir = ScratchDir::new("crtool-scratch-test").unwrap();
+        let path = dir.path().to_path_buf();
+        fs::write(dir.join("file.bin"), b"data").unwrap();
+        assert!(path.starts_with(scratch_dir()));
+        drop(dir);
+        assert!(!path.exists());
+
+        let path = std::panic::catch_unwind(|| {
+            let dir = ScratchDir::new("crtool-scratch-test").unwrap();
+            std::panic::panic_any(dir.path().to_path_buf());
+        })
+        .unwrap_err()
+        .downcast::<PathBuf>()
+        .unwrap();
+        assert!(!path.exists());
+    }
+}
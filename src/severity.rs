@@ -0,0 +1,272 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Severity of validation findings. Every [`ValidationError`] names the rule that produced it:
+//! the JSON schema keyword that failed (`required`, `type`, `additionalProperties`, ...) or
+//! [`RECOMMENDED_FIELD_RULE`] for a field crJSON documents should carry but the schema does not
+//! require. Schema violations are errors and missing recommended fields are warnings;
+//! [`SeverityRules`] changes the level of a rule, suppresses it, or turns warnings into errors.
+//! Only errors make a document invalid.
+
+use crate::error::{bail, CrtoolError, Result};
+use crate::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Rule of the findings for missing recommended fields (see [`recommended_field_findings`]).
+pub const RECOMMENDED_FIELD_RULE: &str = "recommended-field";
+
+/// Fields of a claim (`claim.v2` or `claim`) that are recommended but not required.
+const RECOMMENDED_CLAIM_FIELDS: &[&str] = &["dc:title"];
+
+/// Fields of each `claim_generator_info` entry that are recommended but not required.
+const RECOMMENDED_GENERATOR_FIELDS: &[&str] = &["version"];
+
+/// How serious a validation finding is, from least to most.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Informational; never affects the outcome.
+    Info,
+    /// Worth fixing, but the document is still valid.
+    Warning,
+    /// The document is invalid.
+    #[default]
+    Error,
+}
+
+impl Severity {
+    /// Lowercase name (e.g. "warning").
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = CrtoolError;
+
+    /// Case-insensitive: "error", "warning" (or "warn"), "info".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "error" => Ok(Severity::Error),
+            "warning" | "warn" => Ok(Severity::Warning),
+            "info" => Ok(Severity::Info),
+            other => bail!(
+                "Unknown severity '{}'. Expected one of: error, warning, info",
+                other
+            ),
+        }
+    }
+}
+
+/// A rule with the severity its findings should have, written `RULE=LEVEL` (e.g.
+/// `additionalProperties=warning`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSeverity {
+    pub rule: String,
+    pub severity: Severity,
+}
+
+impl std::str::FromStr for RuleSeverity {
+    type Err = CrtoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((rule, level)) = s.split_once('=') else {
+            bail!("Expected RULE=LEVEL (e.g. required=warning), got '{}'", s);
+        };
+        let rule = rule.trim();
+        if rule.is_empty() {
+            bail!("Missing rule name in '{}'", s);
+        }
+        Ok(Self {
+            rule: rule.to_string(),
+            severity: level.parse()?,
+        })
+    }
+}
+
+/// Per-rule severity configuration applied to validation findings. The default keeps every
+/// finding at the level it was reported with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeverityRules {
+    levels: BTreeMap<String, Severity>,
+    suppressed: BTreeSet<String>,
+    warnings_as_errors: bool,
+}
+
+impl SeverityRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report findings of `rule` at `severity`.
+    pub fn with_level(mut self, rule: impl Into<String>, severity: Severity) -> Self {
+        self.levels.insert(rule.into(), severity);
+        self
+    }
+
+    /// Drop findings of `rule` entirely.
+    pub fn suppress(mut self, rule: impl Into<String>) -> Self {
+        self.suppressed.insert(rule.into());
+        self
+    }
+
+    /// Report warnings as errors, so they fail validation.
+    pub fn warnings_as_errors(mut self, enabled: bool) -> Self {
+        self.warnings_as_errors = enabled;
+        self
+    }
+
+    /// Severity of a finding of `rule` reported at `reported`, or `None` when it is suppressed.
+    pub fn severity_of(&self, rule: &str, reported: Severity) -> Option<Severity> {
+        if self.suppressed.contains(rule) {
+            return None;
+        }
+        let severity = self.levels.get(rule).copied().unwrap_or(reported);
+        if self.warnings_as_errors && severity == Severity::Warning {
+            Some(Severity::Error)
+        } else {
+            Some(severity)
+        }
+    }
+
+    /// Apply the rules to `findings`, dropping suppressed ones.
+    pub fn apply(&self, findings: Vec<ValidationError>) -> Vec<ValidationError> {
+        findings
+            .into_iter()
+            .filter_map(|mut finding| {
+                finding.severity = self.severity_of(&finding.rule, finding.severity)?;
+                Some(finding)
+            })
+            .collect()
+    }
+}
+
+/// Warnings for recommended fields missing from a crJSON document: the claim title and the
+/// version of each claim generator. Documents without a `manifests` array yield none.
+pub fn recommended_field_findings(value: &serde_json::Value) -> Vec<ValidationError> {
+    let Some(manifests) = value.get("manifests").and_then(|m| m.as_array()) else {
+        return Vec::new();
+    };
+    let missing = |instance_path: String, field: &str| ValidationError {
+        instance_path,
+        message: format!("Missing recommended field \"{}\"", field),
+        severity: Severity::Warning,
+        rule: RECOMMENDED_FIELD_RULE.to_string(),
+        schema: None,
+    };
+    let mut findings = Vec::new();
+    for (index, manifest) in manifests.iter().enumerate() {
+        let Some((key, claim)) = ["claim.v2", "claim"]
+            .into_iter()
+            .find_map(|key| manifest.get(key).map(|claim| (key, claim)))
+        else {
+            continue;
+        };
+        let claim_path = format!("/manifests/{}/{}", index, key);
+        for field in RECOMMENDED_CLAIM_FIELDS {
+            if claim.get(field).is_none() {
+                findings.push(missing(claim_path.clone(), field));
+            }
+        }
+        let generators = claim
+            .get("claim_generator_info")
+            .and_then(|g| g.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for (position, generator) in generators.iter().enumerate() {
+            for field in RECOMMENDED_GENERATOR_FIELDS {
+                if generator.get(field).is_none() {
+                    findings.push(missing(
+                        format!("{}/claim_generator_info/{}", claim_path, position),
+                        field,
+                    ));
+                }
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn finding(rule: &str, severity: Severity) -> ValidationError {
+        ValidationError {
+            instance_path: "root".to_string(),
+            message: rule.to_string(),
+            severity,
+            rule: rule.to_string(),
+            schema: None,
+        }
+    }
+
+    #[test]
+    fn test_rules_apply() {
+        let findings = vec![
+            finding("required", Severity::Error),
+            finding(RECOMMENDED_FIELD_RULE, Severity::Warning),
+            finding("type", Severity::Error),
+        ];
+        let rules = SeverityRules::new()
+            .with_level("required", Severity::Warning)
+            .suppress("type");
+        let applied = rules.apply(findings.clone());
+        assert_eq!(applied.len(), 2);
+        assert_eq!(applied[0].severity, Severity::Warning);
+        assert_eq!(applied[1].severity, Severity::Warning);
+
+        let strict = rules.warnings_as_errors(true).apply(findings);
+        assert!(strict.iter().all(|f| f.severity == Severity::Error));
+
+        let parsed: RuleSeverity = "additionalProperties=Warn".parse().unwrap();
+        assert_eq!(parsed.rule, "additionalProperties");
+        assert_eq!(parsed.severity, Severity::Warning);
+        assert!("required".parse::<RuleSeverity>().is_err());
+        assert!("required=fatal".parse::<RuleSeverity>().is_err());
+    }
+
+    #[test]
+    fn test_recommended_field_findings() {
+        let document = json!({
+            "manifests": [
+                {"claim.v2": {"dc:title": "a.jpg", "claim_generator_info": [{"name": "x"}]}},
+                {"claim": {"claim_generator_info": [{"name": "y", "version": "1.0"}]}}
+            ]
+        });
+        let findings = recommended_field_findings(&document);
+        let paths: Vec<&str> = findings.iter().map(|f| f.instance_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/manifests/0/claim.v2/claim_generator_info/0",
+                "/manifests/1/claim"
+            ]
+        );
+        assert!(findings.iter().all(|f| f.severity == Severity::Warning));
+        assert!(recommended_field_findings(&json!({"name": "n"})).is_empty());
+    }
+}
@@ -0,0 +1,878 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Signing backends: where the private key of a signing lives (requires the `signing`
+//! feature).
+//!
+//! [`SignOptions::key`] is either a PEM file ([`PemBackend`]) or a `pkcs11:` URI (RFC 7512) of
+//! a key on an HSM, smart card or YubiKey ([`Pkcs11Backend`]), e.g.
+//! `pkcs11:token=YubiKey%20PIV;object=c2pa?module-path=/usr/lib/libykcs11.so`.
+//! [`SignOptions::keychain`] names a key in the macOS keychain instead ([`KeychainBackend`]),
+//! and [`SignOptions::remote_signer`] a remote signing service such as a KMS front end
+//! ([`RemoteBackend`]). These keys never leave their store: only the data to sign is handed to
+//! them, and the certificate chain is still read from the PEM file [`SignOptions::cert`].
+//!
+//! The PKCS#11, keychain and remote backends are built with the `pkcs11`, `keychain` and
+//! `remote` features.
+
+use crate::error::{bail, format_err, Context, Result};
+use crate::keys::{check_key_pair, PrivateKey};
+use crate::signing::SignOptions;
+use c2pa::{create_signer, CallbackSigner, SigningAlg};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Scheme of PKCS#11 URIs.
+pub const PKCS11_SCHEME: &str = "pkcs11:";
+
+/// PKCS#11 module library used when the URI has no `module-path`.
+pub const PKCS11_MODULE_ENV: &str = "CRTOOL_PKCS11_MODULE";
+
+/// User PIN used when the PKCS#11 URI has no `pin-value`.
+pub const PKCS11_PIN_ENV: &str = "CRTOOL_PKCS11_PIN";
+
+/// Where the private key of a signing lives.
+pub trait SignerBackend {
+    /// Short name for messages: `pem`, `pkcs11`, `keychain` or `remote`.
+    fn name(&self) -> &'static str;
+
+    /// C2PA signer for the certificate chain, algorithm and time-stamp authority of `options`.
+    fn signer(&self, options: &SignOptions) -> Result<Box<dyn c2pa::Signer>>;
+
+    /// Whether signers of this backend may be built on several threads at once. A PKCS#11
+    /// signer initializes its module, which fails while another signer holds it, and a keychain
+    /// signer may prompt for access, so both are used from one thread at a time.
+    fn parallel(&self) -> bool {
+        true
+    }
+}
+
+/// The backend holding the key named by `options`.
+pub fn backend_for(options: &SignOptions) -> Result<Box<dyn SignerBackend>> {
+    if let Some(remote) = &options.remote_signer {
+        return Ok(Box::new(RemoteBackend {
+            remote: remote.clone(),
+        }));
+    }
+    if let Some(name) = &options.keychain {
+        return Ok(Box::new(KeychainBackend { name: name.clone() }));
+    }
+    if is_pkcs11_uri(&options.key) {
+        let uri = Pkcs11Uri::parse(&options.key.to_string_lossy())?;
+        return Ok(Box::new(Pkcs11Backend { uri }));
+    }
+    Ok(Box::new(PemBackend))
+}
+
+/// Whether assets may be signed with `options` on several threads at once (see
+/// [`SignerBackend::parallel`]). A key that names no valid backend fails when signing instead.
+pub fn supports_parallel_signing(options: &SignOptions) -> bool {
+    backend_for(options).map_or(true, |backend| backend.parallel())
+}
+
+/// Whether `key` is a `pkcs11:` URI rather than a file path.
+pub fn is_pkcs11_uri(key: &Path) -> bool {
+    key.to_str().is_some_and(|k| k.starts_with(PKCS11_SCHEME))
+}
+
+/// `CallbackSigner` that signs with `sign` and embeds the certificate chain of `options`,
+/// countersigned by its time-stamp authority unless `allow_self_signed` is set.
+#[cfg_attr(
+    not(any(
+        feature = "pkcs11",
+        feature = "remote",
+        all(feature = "keychain", target_os = "macos")
+    )),
+    allow(dead_code)
+)]
+fn callback_signer<F>(options: &SignOptions, sign: F) -> Result<Box<dyn c2pa::Signer>>
+where
+    F: Fn(*const (), &[u8]) -> c2pa::Result<Vec<u8>> + Send + Sync + 'static,
+{
+    let cert_data = fs::read(&options.cert).context("Failed to read certificate file")?;
+    let mut signer = CallbackSigner::new(sign, options.signing_alg, cert_data);
+    if let Some(tsa_url) = options
+        .tsa_url
+        .clone()
+        .filter(|_| !options.allow_self_signed)
+    {
+        signer = signer.set_tsa_url(tsa_url);
+    }
+    Ok(Box::new(signer))
+}
+
+/// Fixed-size `r || s` ECDSA signature, as COSE expects, from a DER `ECDSA-Sig-Value`;
+/// `size` is the byte length of the curve order (32 for P-256, 48 for P-384, 66 for P-521).
+pub fn ecdsa_signature_from_der(der: &[u8], size: usize) -> Result<Vec<u8>> {
+    fn read_tlv(data: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+        let (&t, rest) = data.split_first()?;
+        let (&len, rest) = rest.split_first()?;
+        let (len, rest) = match len {
+            0x81 => {
+                let (&len, rest) = rest.split_first()?;
+                (len as usize, rest)
+            }
+            len if len < 0x80 => (len as usize, rest),
+            _ => return None,
+        };
+        (t == tag && rest.len() >= len).then(|| rest.split_at(len))
+    }
+
+    let invalid = || format_err!("Invalid DER ECDSA signature");
+    let (sequence, _) = read_tlv(der, 0x30).ok_or_else(invalid)?;
+    let (r, rest) = read_tlv(sequence, 0x02).ok_or_else(invalid)?;
+    let (s, _) = read_tlv(rest, 0x02).ok_or_else(invalid)?;
+    let mut raw = vec![0u8; 2 * size];
+    for (i, int) in [r, s].into_iter().enumerate() {
+        let start = int.iter().position(|&b| b != 0).unwrap_or(int.len());
+        let int = &int[start..];
+        if int.len() > size {
+            return Err(invalid());
+        }
+        raw[(i + 1) * size - int.len()..(i + 1) * size].copy_from_slice(int);
+    }
+    Ok(raw)
+}
+
+// ─── PEM files ────────────────────────────────────────────────────────────────
+
+/// Private key in the PEM (PKCS#8) file [`SignOptions::key`].
+pub struct PemBackend;
+
+impl SignerBackend for PemBackend {
+    fn name(&self) -> &'static str {
+        "pem"
+    }
+
+    fn signer(&self, options: &SignOptions) -> Result<Box<dyn c2pa::Signer>> {
+        check_key_pair(&options.cert, &options.key, options.signing_alg)?;
+        if options.allow_self_signed {
+            let signer = create_callback_signer(&options.cert, &options.key, options.signing_alg)
+                .context("Failed to create callback signer")?;
+            Ok(Box::new(signer))
+        } else {
+            create_signer::from_files(
+                options.cert.to_str().context("Invalid cert path")?,
+                options.key.to_str().context("Invalid key path")?,
+                options.signing_alg,
+                options.tsa_url.clone(),
+            )
+            .context("Failed to create signer")
+        }
+    }
+}
+
+/// Create a `CallbackSigner` that bypasses certificate chain validation.
+/// Used for development and test certificates that are self-signed.
+fn create_callback_signer(
+    cert_path: &Path,
+    key_path: &Path,
+    signing_alg: SigningAlg,
+) -> Result<CallbackSigner> {
+    let cert_data = fs::read(cert_path).context("Failed to read certificate file")?;
+    let key_data = fs::read(key_path).context("Failed to read private key file")?;
+
+    let signer = match signing_alg {
+        SigningAlg::Ed25519 => {
+            let ed_signer = move |_context: *const (), data: &[u8]| ed25519_sign(data, &key_data);
+            CallbackSigner::new(ed_signer, signing_alg, cert_data)
+        }
+        SigningAlg::Es256 | SigningAlg::Es384 | SigningAlg::Es512 => {
+            let es_signer =
+                move |_context: *const (), data: &[u8]| ecdsa_sign(data, &key_data, signing_alg);
+            CallbackSigner::new(es_signer, signing_alg, cert_data)
+        }
+        SigningAlg::Ps256 | SigningAlg::Ps384 | SigningAlg::Ps512 => {
+            let ps_signer =
+                move |_context: *const (), data: &[u8]| rsa_sign(data, &key_data, signing_alg);
+            CallbackSigner::new(ps_signer, signing_alg, cert_data)
+        }
+    };
+
+    Ok(signer)
+}
+
+/// Parse the PEM private key of a callback signer, as a signing error.
+fn callback_key(private_key: &[u8]) -> c2pa::Result<PrivateKey> {
+    use c2pa::crypto::raw_signature::RawSignerError;
+
+    PrivateKey::from_pem(private_key)
+        .map_err(|e| RawSignerError::InternalError(e.to_string()).into())
+}
+
+fn ed25519_sign(data: &[u8], private_key: &[u8]) -> c2pa::Result<Vec<u8>> {
+    use c2pa::crypto::raw_signature::RawSignerError;
+    use ed25519_dalek::{Signature, Signer};
+
+    let PrivateKey::Ed25519(signing_key) = callback_key(private_key)? else {
+        return Err(RawSignerError::InternalError("Not an Ed25519 private key".into()).into());
+    };
+    let signature: Signature = signing_key.sign(data);
+    Ok(signature.to_bytes().to_vec())
+}
+
+/// ECDSA signature over `data` as fixed-size `r || s`, with SHA-256 on P-256 (ES256), SHA-384
+/// on P-384 (ES384) or SHA-512 on P-521 (ES512). The key's curve must be the one of `alg`.
+fn ecdsa_sign(data: &[u8], private_key: &[u8], alg: SigningAlg) -> c2pa::Result<Vec<u8>> {
+    use c2pa::crypto::raw_signature::RawSignerError;
+    use p256::ecdsa::signature::Signer;
+
+    let internal = |e: p256::ecdsa::Error| RawSignerError::InternalError(e.to_string());
+    let key = callback_key(private_key)?;
+    let signature = match (&key, alg) {
+        (PrivateKey::P256(secret), SigningAlg::Es256) => {
+            let signing_key = p256::ecdsa::SigningKey::from(secret);
+            let signature: p256::ecdsa::Signature = signing_key.sign(data);
+            signature.to_bytes().to_vec()
+        }
+        (PrivateKey::P384(secret), SigningAlg::Es384) => {
+            let signing_key = p384::ecdsa::SigningKey::from(secret);
+            let signature: p384::ecdsa::Signature = signing_key.sign(data);
+            signature.to_bytes().to_vec()
+        }
+        (PrivateKey::P521(secret), SigningAlg::Es512) => {
+            let signing_key =
+                p521::ecdsa::SigningKey::from_bytes(&secret.to_bytes()).map_err(internal)?;
+            let signature: p521::ecdsa::Signature = signing_key.sign(data);
+            signature.to_bytes().to_vec()
+        }
+        _ => {
+            return Err(RawSignerError::InternalError(format!(
+                "Cannot sign {} with an {} key",
+                alg,
+                key.description()
+            ))
+            .into())
+        }
+    };
+    Ok(signature)
+}
+
+/// RSASSA-PSS signature over `data` with SHA-256 (PS256), SHA-384 (PS384) or SHA-512
+/// (PS512), MGF1 with the same hash, and a random salt as long as the hash.
+fn rsa_sign(data: &[u8], private_key: &[u8], alg: SigningAlg) -> c2pa::Result<Vec<u8>> {
+    use c2pa::crypto::raw_signature::RawSignerError;
+    use rsa::pss::BlindedSigningKey;
+    use rsa::rand_core::OsRng;
+    use rsa::sha2::{Sha256, Sha384, Sha512};
+    use rsa::signature::{RandomizedSigner, SignatureEncoding};
+
+    let PrivateKey::Rsa(private_key) = callback_key(private_key)? else {
+        return Err(RawSignerError::InternalError("Not an RSA private key".into()).into());
+    };
+    let signature = match alg {
+        SigningAlg::Ps256 => BlindedSigningKey::<Sha256>::new(private_key)
+            .try_sign_with_rng(&mut OsRng, data)
+            .map(|s| s.to_vec()),
+        SigningAlg::Ps384 => BlindedSigningKey::<Sha384>::new(private_key)
+            .try_sign_with_rng(&mut OsRng, data)
+            .map(|s| s.to_vec()),
+        SigningAlg::Ps512 => BlindedSigningKey::<Sha512>::new(private_key)
+            .try_sign_with_rng(&mut OsRng, data)
+            .map(|s| s.to_vec()),
+        _ => {
+            return Err(RawSignerError::InternalError(format!(
+                "Cannot sign {} with an RSA key",
+                alg
+            ))
+            .into())
+        }
+    };
+    signature.map_err(|e| RawSignerError::InternalError(e.to_string()).into())
+}
+
+// ─── PKCS#11 tokens ───────────────────────────────────────────────────────────
+
+/// The attributes of a PKCS#11 URI (RFC 7512) crTool uses to find a key, e.g.
+/// `pkcs11:token=YubiKey%20PIV;object=c2pa?module-path=/usr/lib/libykcs11.so&pin-value=123456`.
+/// Other attributes (`manufacturer`, `serial`, `slot-id`, ...) are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pkcs11Uri {
+    /// Label of the token (`token`); every token is searched when absent.
+    pub token: Option<String>,
+    /// Label of the private key (`object`).
+    pub object: Option<String>,
+    /// ID of the private key (`id`).
+    pub id: Option<Vec<u8>>,
+    /// PKCS#11 module library (`module-path`); [`PKCS11_MODULE_ENV`] when absent.
+    pub module_path: Option<PathBuf>,
+    /// User PIN (`pin-value`); [`PKCS11_PIN_ENV`] when absent, and no login without either.
+    pub pin: Option<String>,
+}
+
+impl Pkcs11Uri {
+    pub fn parse(uri: &str) -> Result<Self> {
+        let rest = uri
+            .strip_prefix(PKCS11_SCHEME)
+            .with_context(|| format!("Not a PKCS#11 URI: {}", uri))?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let text =
+            |value: Vec<u8>| String::from_utf8(value).context("PKCS#11 URI attribute is not UTF-8");
+
+        let mut parsed = Self::default();
+        for attribute in path.split(';').filter(|a| !a.is_empty()) {
+            let (name, value) = attribute
+                .split_once('=')
+                .with_context(|| format!("Malformed PKCS#11 URI attribute `{}`", attribute))?;
+            let value = percent_decode(value)?;
+            match name {
+                "token" => parsed.token = Some(text(value)?),
+                "object" => parsed.object = Some(text(value)?),
+                "id" => parsed.id = Some(value),
+                "type" if value != b"private" => {
+                    bail!("PKCS#11 URI must name a private key (`type=private`)")
+                }
+                _ => {}
+            }
+        }
+        for attribute in query.split('&').filter(|a| !a.is_empty()) {
+            let (name, value) = attribute
+                .split_once('=')
+                .with_context(|| format!("Malformed PKCS#11 URI attribute `{}`", attribute))?;
+            let value = percent_decode(value)?;
+            match name {
+                "module-path" => parsed.module_path = Some(PathBuf::from(text(value)?)),
+                "pin-value" => parsed.pin = Some(text(value)?),
+                _ => {}
+            }
+        }
+        if parsed.object.is_none() && parsed.id.is_none() {
+            bail!("PKCS#11 URI names no key: add `object=<label>` or `id=<id>`");
+        }
+        Ok(parsed)
+    }
+}
+
+/// Decode the `%XX` escapes of a URI component.
+fn percent_decode(value: &str) -> Result<Vec<u8>> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .with_context(|| format!("Invalid percent-encoding in `{}`", value))?;
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Ok(decoded)
+}
+
+/// Private key on a PKCS#11 token (HSM, smart card, YubiKey) named by a `pkcs11:` URI.
+pub struct Pkcs11Backend {
+    pub uri: Pkcs11Uri,
+}
+
+impl SignerBackend for Pkcs11Backend {
+    fn name(&self) -> &'static str {
+        "pkcs11"
+    }
+
+    fn parallel(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "pkcs11")]
+    fn signer(&self, options: &SignOptions) -> Result<Box<dyn c2pa::Signer>> {
+        let key = pkcs11::Pkcs11Key::open(&self.uri, options.signing_alg)?;
+        callback_signer(options, move |_context: *const (), data: &[u8]| {
+            key.sign(data)
+        })
+    }
+
+    #[cfg(not(feature = "pkcs11"))]
+    fn signer(&self, _options: &SignOptions) -> Result<Box<dyn c2pa::Signer>> {
+        bail!("Signing with a PKCS#11 key needs a build with the `pkcs11` feature")
+    }
+}
+
+#[cfg(feature = "pkcs11")]
+mod pkcs11 {
+    use super::{Pkcs11Uri, PKCS11_MODULE_ENV, PKCS11_PIN_ENV};
+    use crate::error::{bail, Context, Result};
+    use c2pa::crypto::raw_signature::RawSignerError;
+    use c2pa::SigningAlg;
+    use cryptoki::context::{CInitializeArgs, Pkcs11};
+    use cryptoki::mechanism::rsa::{PkcsMgfType, PkcsPssParams};
+    use cryptoki::mechanism::{Mechanism, MechanismType};
+    use cryptoki::object::{Attribute, ObjectClass, ObjectHandle};
+    use cryptoki::session::{Session, UserType};
+    use cryptoki::types::AuthPin;
+    use std::sync::Mutex;
+
+    /// A private key in a logged-in session.
+    pub(super) struct Pkcs11Key {
+        session: Mutex<Session>,
+        key: ObjectHandle,
+        alg: SigningAlg,
+        // Declared last so the session is closed before the module is finalized.
+        _context: Pkcs11,
+    }
+
+    impl Pkcs11Key {
+        /// Load the module of `uri` and find its key on the first matching token.
+        pub(super) fn open(uri: &Pkcs11Uri, alg: SigningAlg) -> Result<Self> {
+            let module = uri
+                .module_path
+                .clone()
+                .or_else(|| std::env::var_os(PKCS11_MODULE_ENV).map(Into::into))
+                .with_context(|| {
+                    format!(
+                        "PKCS#11 URI has no module-path and {} is not set",
+                        PKCS11_MODULE_ENV
+                    )
+                })?;
+            let context = Pkcs11::new(&module)
+                .with_context(|| format!("Failed to load PKCS#11 module {:?}", module))?;
+            context
+                .initialize(CInitializeArgs::OsThreads)
+                .context("Failed to initialize PKCS#11 module")?;
+            let pin = uri
+                .pin
+                .clone()
+                .or_else(|| std::env::var(PKCS11_PIN_ENV).ok());
+
+            let mut template = vec![Attribute::Class(ObjectClass::PRIVATE_KEY)];
+            if let Some(label) = &uri.object {
+                template.push(Attribute::Label(label.as_bytes().to_vec()));
+            }
+            if let Some(id) = &uri.id {
+                template.push(Attribute::Id(id.clone()));
+            }
+
+            let slots = context
+                .get_slots_with_token()
+                .context("Failed to list PKCS#11 slots")?;
+            for slot in slots {
+                if let Some(token) = &uri.token {
+                    let info = context
+                        .get_token_info(slot)
+                        .context("Failed to read PKCS#11 token info")?;
+                    if info.label() != token {
+                        continue;
+                    }
+                }
+                let session = context
+                    .open_ro_session(slot)
+                    .context("Failed to open PKCS#11 session")?;
+                if let Some(pin) = &pin {
+                    session
+                        .login(UserType::User, Some(&AuthPin::new(pin.clone())))
+                        .context("PKCS#11 login failed")?;
+                }
+                let keys = session
+                    .find_objects(&template)
+                    .context("Failed to search PKCS#11 token")?;
+                if let Some(&key) = keys.first() {
+                    return Ok(Self {
+                        session: Mutex::new(session),
+                        key,
+                        alg,
+                        _context: context,
+                    });
+                }
+            }
+            bail!("No private key matching the PKCS#11 URI was found")
+        }
+
+        pub(super) fn sign(&self, data: &[u8]) -> c2pa::Result<Vec<u8>> {
+            let pss = |hash_alg, mgf, s_len: u64| PkcsPssParams {
+                hash_alg,
+                mgf,
+                s_len: s_len.into(),
+            };
+            let mechanism = match self.alg {
+                SigningAlg::Es256 => Mechanism::EcdsaSha256,
+                SigningAlg::Es384 => Mechanism::EcdsaSha384,
+                SigningAlg::Es512 => Mechanism::EcdsaSha512,
+                SigningAlg::Ps256 => Mechanism::Sha256RsaPkcsPss(pss(
+                    MechanismType::SHA256,
+                    PkcsMgfType::MGF1_SHA256,
+                    32,
+                )),
+                SigningAlg::Ps384 => Mechanism::Sha384RsaPkcsPss(pss(
+                    MechanismType::SHA384,
+                    PkcsMgfType::MGF1_SHA384,
+                    48,
+                )),
+                SigningAlg::Ps512 => Mechanism::Sha512RsaPkcsPss(pss(
+                    MechanismType::SHA512,
+                    PkcsMgfType::MGF1_SHA512,
+                    64,
+                )),
+                SigningAlg::Ed25519 => Mechanism::Eddsa,
+            };
+            let session = self
+                .session
+                .lock()
+                .map_err(|_| RawSignerError::InternalError("PKCS#11 session poisoned".into()))?;
+            let signature = session
+                .sign(&mechanism, self.key, data)
+                .map_err(|e| RawSignerError::InternalError(e.to_string()))?;
+            Ok(signature)
+        }
+    }
+}
+
+// ─── Platform keychain ────────────────────────────────────────────────────────
+
+/// Private key in the macOS keychain, found by its label.
+pub struct KeychainBackend {
+    pub name: String,
+}
+
+impl SignerBackend for KeychainBackend {
+    fn name(&self) -> &'static str {
+        "keychain"
+    }
+
+    fn parallel(&self) -> bool {
+        false
+    }
+
+    #[cfg(all(feature = "keychain", target_os = "macos"))]
+    fn signer(&self, options: &SignOptions) -> Result<Box<dyn c2pa::Signer>> {
+        let key = keychain::find_key(&self.name)?;
+        let alg = options.signing_alg;
+        callback_signer(options, move |_context: *const (), data: &[u8]| {
+            keychain::sign(&key, alg, data)
+        })
+    }
+
+    #[cfg(not(all(feature = "keychain", target_os = "macos")))]
+    fn signer(&self, _options: &SignOptions) -> Result<Box<dyn c2pa::Signer>> {
+        bail!(
+            "Signing with keychain key `{}` needs a macOS build with the `keychain` feature",
+            self.name
+        )
+    }
+}
+
+#[cfg(all(feature = "keychain", target_os = "macos"))]
+mod keychain {
+    use crate::error::{bail, Result};
+    use c2pa::crypto::raw_signature::RawSignerError;
+    use c2pa::SigningAlg;
+    use security_framework::item::{
+        ItemClass, ItemSearchOptions, KeyClass, Reference, SearchResult,
+    };
+    use security_framework::key::{Algorithm, SecKey};
+
+    /// The private key labelled `label`.
+    pub(super) fn find_key(label: &str) -> Result<SecKey> {
+        let results = ItemSearchOptions::new()
+            .class(ItemClass::key())
+            .key_class(KeyClass::private())
+            .label(label)
+            .load_refs(true)
+            .search()
+            .unwrap_or_default();
+        match results.into_iter().next() {
+            Some(SearchResult::Ref(Reference::Key(key))) => Ok(key),
+            _ => bail!("No private key labelled `{}` in the keychain", label),
+        }
+    }
+
+    pub(super) fn sign(key: &SecKey, alg: SigningAlg, data: &[u8]) -> c2pa::Result<Vec<u8>> {
+        let internal = |message: String| RawSignerError::InternalError(message);
+        let (algorithm, ec_size) = match alg {
+            SigningAlg::Es256 => (Algorithm::ECDSASignatureMessageX962SHA256, Some(32)),
+            SigningAlg::Es384 => (Algorithm::ECDSASignatureMessageX962SHA384, Some(48)),
+            SigningAlg::Es512 => (Algorithm::ECDSASignatureMessageX962SHA512, Some(66)),
+            SigningAlg::Ps256 => (Algorithm::RSASignatureMessagePSSSHA256, None),
+            SigningAlg::Ps384 => (Algorithm::RSASignatureMessagePSSSHA384, None),
+            SigningAlg::Ps512 => (Algorithm::RSASignatureMessagePSSSHA512, None),
+            SigningAlg::Ed25519 => {
+                return Err(internal("The keychain cannot sign with Ed25519".into()).into())
+            }
+        };
+        let signature = key
+            .create_signature(algorithm, data)
+            .map_err(|e| internal(e.to_string()))?;
+        // The keychain returns ECDSA signatures DER-encoded.
+        match ec_size {
+            Some(size) => Ok(super::ecdsa_signature_from_der(&signature, size)
+                .map_err(|e| internal(e.to_string()))?),
+            None => Ok(signature),
+        }
+    }
+}
+
+// ─── Remote signing services ─────────────────────────────────────────────────
+
+/// Header naming the signing algorithm in requests to a remote signer.
+pub const REMOTE_ALG_HEADER: &str = "X-Signing-Alg";
+
+/// A remote signing service holding the private key, e.g. a front end to a cloud KMS or an
+/// enterprise signing service. The data to sign is POSTed to `url` as
+/// `application/octet-stream`, with the algorithm (`es256`, ...) in [`REMOTE_ALG_HEADER`];
+/// the response body is the signature. ECDSA signatures may be raw `r || s` or DER-encoded.
+#[derive(Debug, Clone, Default)]
+pub struct RemoteSigner {
+    pub url: String,
+    /// Extra request headers, e.g. `("Authorization", "Bearer ...")`.
+    pub headers: Vec<(String, String)>,
+}
+
+impl RemoteSigner {
+    /// Remote signer at `url` sending `headers`, each written `Name: value`.
+    pub fn from_header_lines(url: impl Into<String>, headers: &[String]) -> Result<Self> {
+        let headers = headers
+            .iter()
+            .map(|line| {
+                let (name, value) = line
+                    .split_once(':')
+                    .filter(|(name, _)| !name.trim().is_empty())
+                    .with_context(|| format!("Header `{}` is not `Name: value`", line))?;
+                Ok((name.trim().to_string(), value.trim().to_string()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            url: url.into(),
+            headers,
+        })
+    }
+}
+
+/// Private key behind a remote signing service: [`SignOptions::remote_signer`].
+pub struct RemoteBackend {
+    pub remote: RemoteSigner,
+}
+
+impl SignerBackend for RemoteBackend {
+    fn name(&self) -> &'static str {
+        "remote"
+    }
+
+    #[cfg(feature = "remote")]
+    fn signer(&self, options: &SignOptions) -> Result<Box<dyn c2pa::Signer>> {
+        let remote = self.remote.clone();
+        let alg = options.signing_alg;
+        let client = reqwest::blocking::Client::new();
+        callback_signer(options, move |_context: *const (), data: &[u8]| {
+            remote_sign(&client, &remote, alg, data)
+        })
+    }
+
+    #[cfg(not(feature = "remote"))]
+    fn signer(&self, _options: &SignOptions) -> Result<Box<dyn c2pa::Signer>> {
+        bail!("Signing with a remote signer needs a build with the `remote` feature")
+    }
+}
+
+#[cfg(feature = "remote")]
+fn remote_sign(
+    client: &reqwest::blocking::Client,
+    remote: &RemoteSigner,
+    alg: SigningAlg,
+    data: &[u8],
+) -> c2pa::Result<Vec<u8>> {
+    use c2pa::crypto::raw_signature::RawSignerError;
+
+    let internal = |message: String| RawSignerError::InternalError(message);
+    let mut request = client
+        .post(&remote.url)
+        .header("Content-Type", "application/octet-stream")
+        .header(REMOTE_ALG_HEADER, alg.to_string())
+        .body(data.to_vec());
+    for (name, value) in &remote.headers {
+        request = request.header(name, value);
+    }
+    let signature = request
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.bytes())
+        .map_err(|e| internal(format!("Remote signer {}: {}", remote.url, e)))?
+        .to_vec();
+
+    let size = match alg {
+        SigningAlg::Es256 => 32,
+        SigningAlg::Es384 => 48,
+        SigningAlg::Es512 => 66,
+        _ => return Ok(signature),
+    };
+    if signature.len() == 2 * size {
+        return Ok(signature);
+    }
+    Ok(ecdsa_signature_from_der(&signature, size).map_err(|e| internal(e.to_string()))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ecdsa_sign_curves() {
+        use p256::ecdsa::signature::Verifier;
+        use p256::pkcs8::{EncodePrivateKey, LineEnding};
+
+        let data = b"claim bytes";
+        let pem = |der: p256::pkcs8::SecretDocument| {
+            der.to_pem("PRIVATE KEY", LineEnding::LF)
+                .unwrap()
+                .as_bytes()
+                .to_vec()
+        };
+
+        let p256_key = p256::SecretKey::from_slice(&[1u8; 32]).unwrap();
+        let p256_pem = pem(p256_key.to_pkcs8_der().unwrap());
+        let signature = ecdsa_sign(data, &p256_pem, SigningAlg::Es256).unwrap();
+        assert_eq!(signature.len(), 64);
+        let verifying_key = p256::ecdsa::VerifyingKey::from(p256_key.public_key());
+        let signature = p256::ecdsa::Signature::from_slice(&signature).unwrap();
+        verifying_key.verify(data, &signature).unwrap();
+
+        let p384_key = p384::SecretKey::from_slice(&[2u8; 48]).unwrap();
+        let p384_pem = pem(p384_key.to_pkcs8_der().unwrap());
+        let signature = ecdsa_sign(data, &p384_pem, SigningAlg::Es384).unwrap();
+        assert_eq!(signature.len(), 96);
+        let verifying_key = p384::ecdsa::VerifyingKey::from(p384_key.public_key());
+        let signature = p384::ecdsa::Signature::from_slice(&signature).unwrap();
+        verifying_key.verify(data, &signature).unwrap();
+
+        // the top byte of a P-521 scalar holds a single bit
+        let p521_key = p521::SecretKey::from_slice(&[1u8; 66]).unwrap();
+        let p521_pem = pem(p521_key.to_pkcs8_der().unwrap());
+        let signature = ecdsa_sign(data, &p521_pem, SigningAlg::Es512).unwrap();
+        assert_eq!(signature.len(), 132);
+        let signing_key = p521::ecdsa::SigningKey::from_bytes(&p521_key.to_bytes()).unwrap();
+        let verifying_key = signing_key.verifying_key();
+        let signature = p521::ecdsa::Signature::from_slice(&signature).unwrap();
+        verifying_key.verify(data, &signature).unwrap();
+
+        // the curve must be the algorithm's
+        assert!(ecdsa_sign(data, &p256_pem, SigningAlg::Es384).is_err());
+        assert!(ecdsa_sign(data, &p521_pem, SigningAlg::Es256).is_err());
+    }
+
+    #[test]
+    fn test_rsa_sign_pss() {
+        use rsa::pkcs8::{EncodePrivateKey, LineEnding};
+        use rsa::pss::{Signature, VerifyingKey};
+        use rsa::sha2::{Sha256, Sha384, Sha512};
+        use rsa::signature::{SignatureEncoding, Verifier};
+
+        let data = b"claim bytes";
+        let key = rsa::RsaPrivateKey::new(&mut rsa::rand_core::OsRng, 2048).unwrap();
+        let pem = key.to_pkcs8_pem(LineEnding::LF).unwrap();
+        let public_key = key.to_public_key();
+
+        let signature = rsa_sign(data, pem.as_bytes(), SigningAlg::Ps256).unwrap();
+        let signature = Signature::try_from(signature.as_slice()).unwrap();
+        VerifyingKey::<Sha256>::new(public_key.clone())
+            .verify(data, &signature)
+            .unwrap();
+        // PSS, not PKCS#1 v1.5: the same key and data sign differently every time
+        let again = rsa_sign(data, pem.as_bytes(), SigningAlg::Ps256).unwrap();
+        assert_ne!(signature.to_vec(), again);
+
+        let signature = rsa_sign(data, pem.as_bytes(), SigningAlg::Ps384).unwrap();
+        let signature = Signature::try_from(signature.as_slice()).unwrap();
+        VerifyingKey::<Sha384>::new(public_key.clone())
+            .verify(data, &signature)
+            .unwrap();
+        assert!(VerifyingKey::<Sha256>::new(public_key.clone())
+            .verify(data, &signature)
+            .is_err());
+
+        let signature = rsa_sign(data, pem.as_bytes(), SigningAlg::Ps512).unwrap();
+        let signature = Signature::try_from(signature.as_slice()).unwrap();
+        VerifyingKey::<Sha512>::new(public_key)
+            .verify(data, &signature)
+            .unwrap();
+
+        assert!(rsa_sign(data, pem.as_bytes(), SigningAlg::Es256).is_err());
+    }
+
+    #[test]
+    fn test_pkcs11_uri() {
+        let uri = Pkcs11Uri::parse(
+            "pkcs11:token=YubiKey%20PIV;object=c2pa;id=%01%02;type=private\
+             ?module-path=/usr/lib/libykcs11.so&pin-value=123456",
+        )
+        .unwrap();
+        assert_eq!(uri.token.as_deref(), Some("YubiKey PIV"));
+        assert_eq!(uri.object.as_deref(), Some("c2pa"));
+        assert_eq!(uri.id, Some(vec![1, 2]));
+        assert_eq!(
+            uri.module_path,
+            Some(PathBuf::from("/usr/lib/libykcs11.so"))
+        );
+        assert_eq!(uri.pin.as_deref(), Some("123456"));
+
+        assert!(Pkcs11Uri::parse("pkcs11:token=YubiKey").is_err());
+        assert!(Pkcs11Uri::parse("pkcs11:object=c2pa;type=cert").is_err());
+        assert!(Pkcs11Uri::parse("pkcs11:object=c2%pa").is_err());
+
+        let mut options =
+            SignOptions::new("{}", "cert.pem", "pkcs11:object=c2pa", SigningAlg::Es256);
+        assert!(is_pkcs11_uri(&options.key));
+        assert_eq!(backend_for(&options).unwrap().name(), "pkcs11");
+        assert!(!supports_parallel_signing(&options));
+        options.key = PathBuf::from("key.pem");
+        assert_eq!(backend_for(&options).unwrap().name(), "pem");
+        assert!(supports_parallel_signing(&options));
+        options.keychain = Some("c2pa".into());
+        assert_eq!(backend_for(&options).unwrap().name(), "keychain");
+        assert!(!supports_parallel_signing(&options));
+        options.remote_signer = Some(RemoteSigner::default());
+        assert_eq!(backend_for(&options).unwrap().name(), "remote");
+        assert!(supports_parallel_signing(&options));
+    }
+
+    #[test]
+    fn test_remote_signer_headers() {
+        let remote = RemoteSigner::from_header_lines(
+            "https://signer.example/sign",
+            &[
+                "Authorization: Bearer abc:def".into(),
+                "X-Tenant:news".into(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            remote.headers,
+            vec![
+                ("Authorization".to_string(), "Bearer abc:def".to_string()),
+                ("X-Tenant".to_string(), "news".to_string()),
+            ]
+        );
+        assert!(
+            RemoteSigner::from_header_lines("https://signer.example", &["nocolon".into()]).is_err()
+        );
+        assert!(
+            RemoteSigner::from_header_lines("https://signer.example", &[": value".into()]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_ecdsa_signature_from_der() {
+        // r has a leading zero byte (high bit set), s is shorter than the curve size.
+        let mut der = vec![0x30, 0x26, 0x02, 0x21, 0x00];
+        der.extend([0x80; 32]);
+        der.extend([0x02, 0x01, 0x05]);
+        let raw = ecdsa_signature_from_der(&der, 32).unwrap();
+        assert_eq!(raw.len(), 64);
+        assert_eq!(&raw[..32], &[0x80; 32]);
+        assert_eq!(&raw[32..63], &[0; 31]);
+        assert_eq!(raw[63], 5);
+
+        assert!(ecdsa_signature_from_der(&der, 16).is_err());
+        assert!(ecdsa_signature_from_der(&[0x30, 0x00], 32).is_err());
+    }
+}
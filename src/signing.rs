@@ -0,0 +1,983 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Create and embed C2PA manifests (requires the `signing` feature).
+//!
+//! [`sign_asset`] is what `crTool sign` runs for each asset: it loads file-based
+//! ingredients, builds the manifest, and signs it with a certificate and private key, so other
+//! programs can create Content Credentials without shelling out to the CLI.
+
+use crate::error::{bail, format_err, Context, CrtoolError, Result};
+use crate::hashing::{hash_bytes, HashAlgorithm};
+use crate::provenance::{relationship_violations, RelationshipViolation};
+use c2pa::{Builder, Ingredient, Relationship, SigningAlg};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufReader, Cursor};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use unicode_normalization::UnicodeNormalization;
+
+/// What to sign an asset with.
+#[derive(Debug, Clone)]
+pub struct SignOptions {
+    /// Manifest definition JSON, as accepted by `c2pa::Builder::from_json`. Entries of its
+    /// `ingredients` array with a `file_path` are loaded from disk (see [`process_ingredients`]).
+    pub manifest_json: String,
+    /// Directory relative `file_path` ingredients are resolved against.
+    pub ingredients_base_dir: PathBuf,
+    /// PEM certificate chain of the signer.
+    pub cert: PathBuf,
+    /// PEM (PKCS#8) private key of the signer, or a `pkcs11:` URI of a key on an HSM or smart
+    /// card (see [`crate::signer`]).
+    pub key: PathBuf,
+    /// Label of a key in the platform keychain to sign with instead of `key`.
+    pub keychain: Option<String>,
+    /// Remote signing service to sign with instead of `key` (see [`crate::signer::RemoteSigner`]).
+    pub remote_signer: Option<crate::signer::RemoteSigner>,
+    pub signing_alg: SigningAlg,
+    /// Time-stamp authority to countersign with. Ignored with `allow_self_signed`.
+    pub tsa_url: Option<String>,
+    /// Sign without validating the certificate chain, for self-signed development and test
+    /// certificates.
+    pub allow_self_signed: bool,
+    /// Generate a thumbnail for file-based ingredients that have none.
+    pub ingredient_thumbnails: bool,
+    /// Normalize titles (including titles taken from ingredient file names) to Unicode NFC, so
+    /// the same name typed on macOS (often NFD) and elsewhere produces identical manifests.
+    pub normalize_unicode: bool,
+    /// Assertions of the parent ingredient's manifest to redact, added to the manifest's
+    /// `redactions`: assertion labels (e.g. `stds.exif`), resolved against the active manifest
+    /// of the `parentOf` file ingredient, or full `self#jumbf=` URIs (see [`add_redactions`]).
+    pub redact: Vec<String>,
+    /// File ingredients already loaded in this run, reused instead of re-reading, re-hashing
+    /// and re-thumbnailing the file for every asset that references it.
+    pub ingredient_cache: Option<Arc<IngredientCache>>,
+}
+
+impl SignOptions {
+    /// Options for signing with `manifest_json`, resolving file ingredients against the
+    /// current directory, without a time-stamp authority.
+    pub fn new(
+        manifest_json: impl Into<String>,
+        cert: impl Into<PathBuf>,
+        key: impl Into<PathBuf>,
+        signing_alg: SigningAlg,
+    ) -> Self {
+        Self {
+            manifest_json: manifest_json.into(),
+            ingredients_base_dir: PathBuf::from("."),
+            cert: cert.into(),
+            key: key.into(),
+            keychain: None,
+            remote_signer: None,
+            signing_alg,
+            tsa_url: None,
+            allow_self_signed: false,
+            ingredient_thumbnails: false,
+            normalize_unicode: false,
+            redact: Vec::new(),
+            ingredient_cache: None,
+        }
+    }
+}
+
+/// Result of a successful [`sign_asset`].
+#[derive(Debug, Clone)]
+pub struct SignedAsset {
+    pub output: PathBuf,
+    /// Number of ingredients loaded from `file_path` entries of the manifest.
+    pub file_ingredients: usize,
+    /// Ingredient relationship constraints the manifest definition breaks (see
+    /// [`check_ingredient_relationships`]). The asset is still signed, so deliberately
+    /// non-conforming test assets can be made.
+    pub relationship_violations: Vec<RelationshipViolation>,
+}
+
+/// Converts a file extension to a MIME type
+fn extension_to_mime(extension: &str) -> Option<&'static str> {
+    Some(match extension.to_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "psd" => "image/vnd.adobe.photoshop",
+        "tiff" | "tif" => "image/tiff",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        "dng" => "image/x-adobe-dng",
+        "heic" => "image/heic",
+        "heif" => "image/heif",
+        "avif" => "image/avif",
+        "avi" => "video/avi",
+        "c2pa" => "application/c2pa",
+        "mp2" | "mpa" | "mpe" | "mpeg" | "mpg" | "mpv2" => "video/mpeg",
+        "mp4" => "video/mp4",
+        "mov" | "qt" => "video/quicktime",
+        "m4a" => "audio/mp4",
+        "mid" | "rmi" => "audio/mid",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "aif" | "aifc" | "aiff" => "audio/aiff",
+        "ogg" => "audio/ogg",
+        "pdf" => "application/pdf",
+        "ai" => "application/postscript",
+        _ => return None,
+    })
+}
+
+/// Generate a thumbnail from an image stream.
+/// Returns (format, thumbnail_bytes).
+fn make_thumbnail_from_stream(format: &str, stream: &mut fs::File) -> Result<(String, Vec<u8>)> {
+    use image::ImageFormat;
+
+    let img_format = match format {
+        "image/jpeg" => ImageFormat::Jpeg,
+        "image/png" => ImageFormat::Png,
+        "image/gif" => ImageFormat::Gif,
+        "image/bmp" => ImageFormat::Bmp,
+        "image/tiff" => ImageFormat::Tiff,
+        "image/webp" => ImageFormat::WebP,
+        _ => ImageFormat::Jpeg,
+    };
+
+    let reader = BufReader::new(stream);
+    let img =
+        image::load(reader, img_format).context("Failed to load image for thumbnail generation")?;
+
+    const THUMBNAIL_SIZE: u32 = 256;
+    let thumbnail = img.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+
+    let mut buf = Cursor::new(Vec::new());
+    thumbnail
+        .write_to(&mut buf, ImageFormat::Jpeg)
+        .context("Failed to encode thumbnail")?;
+
+    Ok(("image/jpeg".to_string(), buf.into_inner()))
+}
+
+/// Load a C2PA ingredient from a file, optionally generating a thumbnail.
+fn load_ingredient_from_file(file_path: &Path, generate_thumbnail: bool) -> Result<Ingredient> {
+    if !file_path.exists() {
+        bail!("Ingredient file not found: {:?}", file_path);
+    }
+
+    let mut source = fs::File::open(file_path)
+        .context(format!("Failed to open ingredient file: {:?}", file_path))?;
+
+    let extension = file_path
+        .extension()
+        .and_then(|s| s.to_str())
+        .context(format!("Ingredient file has no extension: {:?}", file_path))?;
+
+    let format = extension_to_mime(extension)
+        .context(format!("Unsupported ingredient file format: {}", extension))?;
+
+    let mut ingredient = Ingredient::from_stream(format, &mut source).context(format!(
+        "Failed to create ingredient from file: {:?}",
+        file_path
+    ))?;
+
+    if generate_thumbnail && ingredient.thumbnail_ref().is_none() {
+        use std::io::Seek;
+        source.rewind()?;
+        let (thumb_format, thumbnail) = make_thumbnail_from_stream(format, &mut source)
+            .context("Failed to generate thumbnail for ingredient")?;
+        ingredient
+            .set_thumbnail(&thumb_format, thumbnail)
+            .context("Failed to set thumbnail for ingredient")?;
+    }
+
+    Ok(ingredient)
+}
+
+/// Identity of a loaded ingredient file: a file rewritten during the run (new modification
+/// time or size) is loaded again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct IngredientKey {
+    path: PathBuf,
+    modified: std::time::SystemTime,
+    len: u64,
+    thumbnail: bool,
+}
+
+/// File ingredients loaded during a batch signing run, keyed by canonical path and modification
+/// time. Signing hundreds of assets that share a parent then reads, hashes and thumbnails the
+/// parent once; each asset gets a clone (with the same instance ID). Safe to share between
+/// threads.
+#[derive(Debug, Default)]
+pub struct IngredientCache {
+    entries: Mutex<HashMap<IngredientKey, Ingredient>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl IngredientCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ingredient for `file_path`, loaded from the file unless it is cached and the file
+    /// is unchanged.
+    pub fn load(&self, file_path: &Path, generate_thumbnail: bool) -> Result<Ingredient> {
+        let Ok(metadata) = fs::metadata(file_path) else {
+            return load_ingredient_from_file(file_path, generate_thumbnail);
+        };
+        let key = IngredientKey {
+            path: fs::canonicalize(file_path).unwrap_or_else(|_| file_path.to_path_buf()),
+            modified: metadata.modified()?,
+            len: metadata.len(),
+            thumbnail: generate_thumbnail,
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(ingredient) = entries.get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(ingredient.clone());
+        }
+        drop(entries);
+
+        // Load without holding the lock, so other threads' cache hits are not blocked
+        let ingredient = load_ingredient_from_file(file_path, generate_thumbnail)?;
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        // Drop entries of earlier versions of the file, so a long `watch` run stays bounded
+        entries.retain(|k, _| k.path != key.path || k.thumbnail != key.thumbnail);
+        entries.insert(key, ingredient.clone());
+        Ok(ingredient)
+    }
+
+    /// Number of ingredients served from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of ingredients loaded from their files.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+/// Parse an ingredient relationship (case-insensitive): `parentOf`, `componentOf`, or `inputTo`.
+/// `inputOf` is accepted as an alias of `inputTo`.
+pub fn parse_relationship(rel: &str) -> Result<Relationship> {
+    match rel.to_lowercase().as_str() {
+        "parentof" => Ok(Relationship::ParentOf),
+        "componentof" => Ok(Relationship::ComponentOf),
+        "inputto" | "inputof" => Ok(Relationship::InputTo),
+        _ => bail!(
+            "Invalid relationship type: {} (expected parentOf, componentOf, or inputTo)",
+            rel
+        ),
+    }
+}
+
+/// Apply the optional fields of a file-based ingredient entry to a loaded ingredient:
+/// `relationship`, `label` (instance ID), `metadata`, and the v3 fields `description`,
+/// `informational_URI` and `data_types` (`[{"type": ..., "version": ...}]`).
+pub fn apply_ingredient_fields(
+    ingredient: &mut Ingredient,
+    ingredient_def: &JsonValue,
+) -> Result<()> {
+    if let Some(rel) = ingredient_def.get("relationship").and_then(|v| v.as_str()) {
+        ingredient.set_relationship(parse_relationship(rel)?);
+    }
+
+    if let Some(label) = ingredient_def.get("label").and_then(|v| v.as_str()) {
+        ingredient.set_instance_id(label);
+    }
+
+    if let Some(metadata_map) = ingredient_def.get("metadata").and_then(|v| v.as_object()) {
+        use c2pa::assertions::AssertionMetadata;
+        let mut assertion_metadata = AssertionMetadata::new();
+        for (key, value) in metadata_map {
+            assertion_metadata = assertion_metadata.set_field(key, value.clone());
+        }
+        ingredient.set_metadata(assertion_metadata);
+    }
+
+    if let Some(description) = ingredient_def.get("description").and_then(|v| v.as_str()) {
+        ingredient.set_description(description);
+    }
+
+    let informational_uri = ingredient_def
+        .get("informational_URI")
+        .or_else(|| ingredient_def.get("informational_uri"))
+        .and_then(|v| v.as_str());
+    if let Some(uri) = informational_uri {
+        ingredient.set_informational_uri(uri);
+    }
+
+    if let Some(data_types) = ingredient_def.get("data_types") {
+        let data_types: Vec<c2pa::assertions::AssetType> = serde_json::from_value(
+            data_types.clone(),
+        )
+        .context("Invalid ingredient data_types: expected [{\"type\": ..., \"version\": ...}]")?;
+        for data_type in data_types {
+            ingredient.add_data_type(data_type);
+        }
+    }
+
+    Ok(())
+}
+
+/// Process file-based ingredient entries from the `ingredients` array in the manifest JSON.
+/// Entries with a `file_path` field are loaded from disk and returned as `Ingredient` objects;
+/// an `https://` or `s3://` `file_path` is downloaded first (see [`crate::ingredient_url`]).
+/// Also returns the manifest JSON with file-based entries stripped from `ingredients`, so the
+/// result is safe to pass to `Builder::from_json` without conflicts.
+pub fn process_ingredients(
+    manifest_json: &str,
+    ingredients_base_dir: &Path,
+    generate_thumbnails: bool,
+) -> Result<(Vec<Ingredient>, String)> {
+    process_ingredients_cached(
+        manifest_json,
+        ingredients_base_dir,
+        generate_thumbnails,
+        None,
+    )
+}
+
+/// [`process_ingredients`], taking file ingredients from `cache` when given.
+pub fn process_ingredients_cached(
+    manifest_json: &str,
+    ingredients_base_dir: &Path,
+    generate_thumbnails: bool,
+    cache: Option<&IngredientCache>,
+) -> Result<(Vec<Ingredient>, String)> {
+    let mut manifest: JsonValue =
+        serde_json::from_str(manifest_json).context("Failed to parse manifest JSON")?;
+
+    let mut file_ingredients: Vec<Ingredient> = Vec::new();
+
+    if let Some(ingredients) = manifest
+        .get("ingredients")
+        .and_then(|v| v.as_array())
+        .cloned()
+    {
+        let mut inline_ingredients = Vec::new();
+
+        for ingredient_def in &ingredients {
+            let Some(file_path_str) = ingredient_def.get("file_path").and_then(|v| v.as_str())
+            else {
+                inline_ingredients.push(ingredient_def.clone());
+                continue;
+            };
+
+            let file_path = if crate::ingredient_url::is_ingredient_url(file_path_str) {
+                crate::ingredient_url::fetch_ingredient(
+                    file_path_str,
+                    &crate::ingredient_url::cache_dir(),
+                )?
+            } else if Path::new(file_path_str).is_absolute() {
+                PathBuf::from(file_path_str)
+            } else {
+                ingredients_base_dir.join(file_path_str)
+            };
+
+            let mut ingredient = match cache {
+                Some(cache) => cache.load(&file_path, generate_thumbnails)?,
+                None => load_ingredient_from_file(&file_path, generate_thumbnails)?,
+            };
+
+            if let Some(title) = ingredient_def.get("title").and_then(|v| v.as_str()) {
+                ingredient.set_title(title);
+            } else {
+                let filename = file_path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("Unknown");
+                ingredient.set_title(filename);
+            }
+
+            apply_ingredient_fields(&mut ingredient, ingredient_def)?;
+
+            file_ingredients.push(ingredient);
+        }
+
+        // Replace ingredients array with only the inline (non-file-based) entries
+        if let Some(obj) = manifest.as_object_mut() {
+            obj.insert(
+                "ingredients".to_string(),
+                JsonValue::Array(inline_ingredients),
+            );
+        }
+    }
+
+    let cleaned_json =
+        serde_json::to_string(&manifest).context("Failed to serialize cleaned manifest JSON")?;
+
+    Ok((file_ingredients, cleaned_json))
+}
+
+/// Signing algorithm names accepted by [`parse_signing_algorithm`].
+pub const SIGNING_ALGORITHMS: &[&str] = &[
+    "es256", "es384", "es512", "ps256", "ps384", "ps512", "ed25519",
+];
+
+/// Parse a signing algorithm name string (case-insensitive) into a `SigningAlg`.
+pub fn parse_signing_algorithm(alg: &str) -> Result<SigningAlg> {
+    match alg.to_lowercase().as_str() {
+        "es256" => Ok(SigningAlg::Es256),
+        "es384" => Ok(SigningAlg::Es384),
+        "es512" => Ok(SigningAlg::Es512),
+        "ps256" => Ok(SigningAlg::Ps256),
+        "ps384" => Ok(SigningAlg::Ps384),
+        "ps512" => Ok(SigningAlg::Ps512),
+        "ed25519" => Ok(SigningAlg::Ed25519),
+        _ => bail!("Unsupported signing algorithm: {}", alg),
+    }
+}
+
+/// Detect the signing algorithm from a certificate file by examining its public key (see
+/// [`crate::keys::certificate_signing_algorithm`]).
+pub fn detect_signing_algorithm(cert_path: &Path) -> Result<SigningAlg> {
+    use x509_parser::prelude::*;
+
+    let cert_data = fs::read(cert_path).context("Failed to read certificate file")?;
+
+    let pem = ::pem::parse(&cert_data)
+        .map_err(|e| format_err!("Failed to parse certificate PEM: {}", e))?;
+
+    let (_, cert) = X509Certificate::from_der(pem.contents())
+        .map_err(|e| format_err!("Failed to parse X.509 certificate: {}", e))?;
+
+    crate::keys::certificate_signing_algorithm(cert.public_key())
+}
+
+/// SHA-256 fingerprint of the (first) certificate in a PEM file, as recorded in audit logs.
+pub fn cert_fingerprint(cert_path: &Path) -> Result<String> {
+    let cert_data = fs::read(cert_path).context("Failed to read certificate file")?;
+    let pem = ::pem::parse(&cert_data)
+        .map_err(|e| format_err!("Failed to parse certificate PEM: {}", e))?;
+    Ok(hash_bytes(pem.contents(), HashAlgorithm::Sha256))
+}
+
+/// Keys whose string values are normalized by [`normalize_manifest_titles`].
+const TITLE_KEYS: &[&str] = &["title", "dc:title", "name"];
+
+fn normalize_titles(value: &mut JsonValue) {
+    match value {
+        JsonValue::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                match child {
+                    JsonValue::String(s) if TITLE_KEYS.contains(&key.as_str()) => {
+                        *s = s.nfc().collect();
+                    }
+                    _ => normalize_titles(child),
+                }
+            }
+        }
+        JsonValue::Array(items) => items.iter_mut().for_each(normalize_titles),
+        _ => {}
+    }
+}
+
+/// Normalize every `title`, `dc:title`, and `name` string in a manifest definition to Unicode
+/// NFC. Other strings (identifiers, hashes, URLs) are left untouched.
+pub fn normalize_manifest_titles(manifest_json: &str) -> Result<String> {
+    let mut manifest: JsonValue =
+        serde_json::from_str(manifest_json).context("Failed to parse manifest JSON")?;
+    normalize_titles(&mut manifest);
+    serde_json::to_string(&manifest).context("Failed to serialize manifest JSON")
+}
+
+/// JUMBF URI of the assertion `assertion_label` in the manifest `manifest_label`, the form a
+/// manifest definition's `redactions` take.
+pub fn redaction_uri(manifest_label: &str, assertion_label: &str) -> String {
+    format!("self#jumbf=/c2pa/{manifest_label}/c2pa.assertions/{assertion_label}")
+}
+
+/// Add `redact` to the `redactions` of a manifest definition. `self#jumbf=` URIs are added as
+/// they are; assertion labels are resolved with [`redaction_uri`] against `parent_label`, the
+/// active manifest of the parent ingredient, and fail without one. Entries already present are
+/// not repeated.
+pub fn add_redactions(
+    manifest: &mut JsonValue,
+    redact: &[String],
+    parent_label: Option<&str>,
+) -> Result<()> {
+    let redactions = manifest
+        .as_object_mut()
+        .context("Manifest JSON must be an object")?
+        .entry("redactions")
+        .or_insert_with(|| JsonValue::Array(Vec::new()))
+        .as_array_mut()
+        .context("Manifest 'redactions' must be an array")?;
+    for entry in redact {
+        let uri = if entry.starts_with("self#jumbf=") {
+            entry.clone()
+        } else {
+            let Some(parent_label) = parent_label else {
+                bail!(
+                    "Cannot redact {}: no parentOf file ingredient with a C2PA manifest",
+                    entry
+                );
+            };
+            redaction_uri(parent_label, entry)
+        };
+        if !redactions.iter().any(|r| r.as_str() == Some(uri.as_str())) {
+            redactions.push(JsonValue::from(uri));
+        }
+    }
+    Ok(())
+}
+
+/// Builder for the manifest in `options`, with its file-based ingredients added. `format`
+/// replaces the manifest's `format`. Also returns the number of file-based ingredients.
+fn prepare_builder(options: &SignOptions, format: Option<&str>) -> Result<(Builder, usize)> {
+    let (mut file_ingredients, mut cleaned_manifest) = process_ingredients_cached(
+        &options.manifest_json,
+        &options.ingredients_base_dir,
+        options.ingredient_thumbnails,
+        options.ingredient_cache.as_deref(),
+    )
+    .context("Failed to process ingredients")?;
+    if options.normalize_unicode {
+        cleaned_manifest = normalize_manifest_titles(&cleaned_manifest)?;
+        for ingredient in &mut file_ingredients {
+            if let Some(title) = ingredient.title().map(|t| t.nfc().collect::<String>()) {
+                ingredient.set_title(title);
+            }
+        }
+    }
+    let mut manifest: JsonValue =
+        serde_json::from_str(&cleaned_manifest).context("Failed to parse manifest JSON")?;
+    let has_identity = manifest.get(crate::identity::IDENTITY_KEY).is_some();
+    if !options.redact.is_empty() {
+        let parent_label = file_ingredients
+            .iter()
+            .find(|i| matches!(i.relationship(), Relationship::ParentOf))
+            .and_then(|i| i.active_manifest());
+        add_redactions(&mut manifest, &options.redact, parent_label)?;
+    }
+    if format.is_some() || has_identity || !options.redact.is_empty() {
+        crate::identity::strip_identity(&mut manifest);
+        if let (Some(format), Some(obj)) = (format, manifest.as_object_mut()) {
+            obj.insert("format".to_string(), JsonValue::from(format));
+        }
+        cleaned_manifest =
+            serde_json::to_string(&manifest).context("Failed to serialize manifest JSON")?;
+    }
+
+    let mut builder = Builder::from_json(&cleaned_manifest)
+        .context("Failed to create builder from JSON manifest")?;
+    let ingredient_count = file_ingredients.len();
+    for ingredient in file_ingredients {
+        builder.add_ingredient(ingredient);
+    }
+    Ok((builder, ingredient_count))
+}
+
+/// Check the ingredients of the manifest definition in `options` against the relationship
+/// constraints: at most one `parentOf`, no ingredient that is the asset being signed (`input`)
+/// or shares the manifest's `instance_id`, and no ingredient declared both `componentOf` and
+/// `parentOf`. File ingredients are compared by their resolved path.
+pub fn check_ingredient_relationships(
+    options: &SignOptions,
+    input: &Path,
+) -> Result<Vec<RelationshipViolation>> {
+    relationship_check(options, Some(input))
+}
+
+/// [`check_ingredient_relationships`] for an asset that may have no path (see [`sign_stream`]).
+fn relationship_check(
+    options: &SignOptions,
+    input: Option<&Path>,
+) -> Result<Vec<RelationshipViolation>> {
+    let manifest: JsonValue =
+        serde_json::from_str(&options.manifest_json).context("Failed to parse manifest JSON")?;
+    let resolved_path = |path: &Path| {
+        fs::canonicalize(path)
+            .unwrap_or_else(|_| path.to_path_buf())
+            .to_string_lossy()
+            .to_string()
+    };
+    let mut ingredients: Vec<JsonValue> = manifest
+        .get("ingredients")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for ingredient in &mut ingredients {
+        let Some(file_path) = ingredient
+            .get("file_path")
+            .and_then(|v| v.as_str())
+            .filter(|path| !crate::ingredient_url::is_ingredient_url(path))
+        else {
+            continue;
+        };
+        let file_path = resolved_path(&options.ingredients_base_dir.join(file_path));
+        ingredient["file_path"] = JsonValue::from(file_path);
+    }
+
+    let input_path = input.map(resolved_path);
+    let mut self_ids: Vec<&str> = input_path.as_deref().into_iter().collect();
+    self_ids.extend(manifest.get("instance_id").and_then(|v| v.as_str()));
+    let name = manifest
+        .get("title")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| input.map(|input| input.display().to_string()))
+        .unwrap_or_else(|| "asset".to_string());
+    Ok(relationship_violations(
+        &name,
+        &self_ids,
+        &ingredients.iter().collect::<Vec<_>>(),
+    ))
+}
+
+/// Signer for the credentials in `options` (see [`crate::signer`]); it also adds a CAWG identity
+/// assertion when the manifest definition has an `identity` block (see [`crate::identity`]).
+fn signer_for(options: &SignOptions) -> Result<Box<dyn c2pa::Signer>> {
+    let manifest: JsonValue =
+        serde_json::from_str(&options.manifest_json).context("Failed to parse manifest JSON")?;
+    let backend = crate::signer::backend_for(options)?;
+    if let Some(identity) = crate::identity::IdentityConfig::from_manifest(&manifest)? {
+        if backend.name() != "pem" {
+            bail!(
+                "CAWG identity assertions need a PEM signing key, not a {} key",
+                backend.name()
+            );
+        }
+        return crate::identity::identity_signer(&identity, options);
+    }
+    backend.signer(options)
+}
+
+/// Create the parent directories of `output` and remove an existing file there.
+fn prepare_output(output: &Path) -> Result<()> {
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).context("Failed to create output directory")?;
+    }
+    if output.exists() {
+        fs::remove_file(output).context("Failed to remove existing output file")?;
+    }
+    Ok(())
+}
+
+/// Sign `input` with the manifest and credentials in `options` and write the signed asset to
+/// `output` (a file path; missing parent directories are created and an existing file is
+/// replaced). The signer is built for each call, so assets signed with a PEM key or a remote
+/// signer may be signed on several threads at once; a PKCS#11 or keychain key opens its token for
+/// each call and must be used from one thread at a time (see
+/// [`crate::signer::supports_parallel_signing`]).
+pub fn sign_asset(input: &Path, output: &Path, options: &SignOptions) -> Result<SignedAsset> {
+    if !input.exists() {
+        return Err(CrtoolError::FileNotFound(input.to_path_buf()));
+    }
+    prepare_output(output)?;
+
+    let relationship_violations = check_ingredient_relationships(options, input)?;
+    let (mut builder, ingredient_count) = prepare_builder(options, None)?;
+    let signer = signer_for(options)?;
+    builder
+        .sign_file(&*signer, input, output)
+        .context("Failed to sign and embed manifest")?;
+
+    Ok(SignedAsset {
+        output: output.to_path_buf(),
+        file_ingredients: ingredient_count,
+        relationship_violations,
+    })
+}
+
+/// Result of a successful [`sign_stream`].
+#[derive(Debug, Clone)]
+pub struct SignedStream {
+    /// Number of ingredients loaded from `file_path` entries of the manifest.
+    pub file_ingredients: usize,
+    /// Ingredient relationship constraints the manifest definition breaks.
+    pub relationship_violations: Vec<RelationshipViolation>,
+}
+
+/// [`sign_asset`] for an asset held in memory or received over the network: sign `source`, an
+/// asset of MIME type or extension `format`, and write the signed asset to `dest`.
+pub fn sign_stream<R, W>(
+    format: &str,
+    source: &mut R,
+    dest: &mut W,
+    options: &SignOptions,
+) -> Result<SignedStream>
+where
+    R: std::io::Read + std::io::Seek + Send,
+    W: std::io::Write + std::io::Read + std::io::Seek + Send,
+{
+    let relationship_violations = relationship_check(options, None)?;
+    let (mut builder, ingredient_count) = prepare_builder(options, None)?;
+    let signer = signer_for(options)?;
+    builder
+        .sign(&*signer, format, source, dest)
+        .context("Failed to sign and embed manifest")?;
+
+    Ok(SignedStream {
+        file_ingredients: ingredient_count,
+        relationship_violations,
+    })
+}
+
+/// Sign a text or JSON document (see [`crate::documents`]) into a detached manifest store
+/// written to `store_output`. The document is not modified; the store is bound to its bytes by
+/// a `c2pa.hash.data` assertion over the whole file.
+pub fn sign_document(
+    input: &Path,
+    store_output: &Path,
+    options: &SignOptions,
+) -> Result<SignedAsset> {
+    let mime = crate::documents::text_document_mime(input)
+        .with_context(|| format!("Not a text document: {:?}", input))?;
+    let mut document =
+        fs::File::open(input).with_context(|| format!("Failed to open {:?}", input))?;
+    prepare_output(store_output)?;
+
+    let relationship_violations = check_ingredient_relationships(options, input)?;
+    let (mut builder, ingredient_count) = prepare_builder(options, Some(mime))?;
+    let mut data_hash = c2pa::assertions::DataHash::new("jumbf manifest", "sha256");
+    data_hash
+        .gen_hash_from_stream(&mut document)
+        .context("Failed to hash document")?;
+    let signer = signer_for(options)?;
+    let store = builder
+        .sign_data_hashed_embeddable(&*signer, &data_hash, "application/c2pa")
+        .context("Failed to sign document")?;
+    fs::write(store_output, store).context("Failed to write detached manifest store")?;
+
+    Ok(SignedAsset {
+        output: store_output.to_path_buf(),
+        file_ingredients: ingredient_count,
+        relationship_violations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+    }
+
+    #[test]
+    fn test_parse_signing_algorithm() {
+        assert_eq!(parse_signing_algorithm("es256").unwrap(), SigningAlg::Es256);
+        assert_eq!(parse_signing_algorithm("ES256").unwrap(), SigningAlg::Es256);
+        assert_eq!(
+            parse_signing_algorithm("ed25519").unwrap(),
+            SigningAlg::Ed25519
+        );
+        assert!(parse_signing_algorithm("invalid").is_err());
+        for alg in SIGNING_ALGORITHMS {
+            assert!(parse_signing_algorithm(alg).is_ok(), "{alg}");
+        }
+    }
+
+    #[test]
+    fn test_detect_signing_algorithm_ed25519() {
+        let cert = fixtures().join("certs/ed25519.pub");
+        assert_eq!(
+            detect_signing_algorithm(&cert).unwrap(),
+            SigningAlg::Ed25519
+        );
+    }
+
+    #[test]
+    fn test_add_redactions() {
+        let mut manifest = serde_json::json!({
+            "title": "t",
+            "redactions": ["self#jumbf=/c2pa/urn:c2pa:a/c2pa.assertions/c2pa.metadata"]
+        });
+        let redact = vec![
+            "stds.exif".to_string(),
+            "self#jumbf=/c2pa/urn:c2pa:a/c2pa.assertions/c2pa.metadata".to_string(),
+        ];
+        add_redactions(&mut manifest, &redact, Some("urn:c2pa:parent")).unwrap();
+        assert_eq!(
+            manifest["redactions"],
+            serde_json::json!([
+                "self#jumbf=/c2pa/urn:c2pa:a/c2pa.assertions/c2pa.metadata",
+                "self#jumbf=/c2pa/urn:c2pa:parent/c2pa.assertions/stds.exif"
+            ])
+        );
+        assert!(add_redactions(&mut manifest, &redact, None).is_err());
+    }
+
+    #[test]
+    fn test_process_ingredients_keeps_inline_entries() {
+        let manifest = r#"{"title": "t", "ingredients": [
+            {"title": "inline", "format": "image/jpeg", "relationship": "componentOf"},
+            {"file_path": "assets/Dog.jpg", "relationship": "parentOf"}
+        ]}"#;
+        let (loaded, cleaned) = process_ingredients(manifest, &fixtures(), false).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].title(), Some("Dog.jpg"));
+        let cleaned: JsonValue = serde_json::from_str(&cleaned).unwrap();
+        assert_eq!(cleaned["ingredients"].as_array().unwrap().len(), 1);
+        assert_eq!(cleaned["ingredients"][0]["title"], "inline");
+
+        let missing = r#"{"ingredients": [{"file_path": "assets/missing.jpg"}]}"#;
+        assert!(process_ingredients(missing, &fixtures(), false).is_err());
+    }
+
+    #[test]
+    fn test_ingredient_cache_reuses_loaded_ingredients() {
+        let cache = IngredientCache::new();
+        let first = r#"{"ingredients": [{"file_path": "assets/Dog.jpg", "title": "first"}]}"#;
+        let second = r#"{"ingredients": [{"file_path": "assets/Dog.jpg", "label": "dog"}]}"#;
+        let (a, _) = process_ingredients_cached(first, &fixtures(), false, Some(&cache)).unwrap();
+        let (b, _) = process_ingredients_cached(second, &fixtures(), false, Some(&cache)).unwrap();
+        assert_eq!((cache.misses(), cache.hits()), (1, 1));
+        // Entry fields are applied to each asset's copy, not to the cached ingredient
+        assert_eq!(a[0].title(), Some("first"));
+        assert_eq!(b[0].title(), Some("Dog.jpg"));
+        assert_eq!(b[0].instance_id(), "dog");
+
+        let missing = r#"{"ingredients": [{"file_path": "assets/missing.jpg"}]}"#;
+        assert!(process_ingredients_cached(missing, &fixtures(), false, Some(&cache)).is_err());
+    }
+
+    #[test]
+    fn test_process_ingredients_input_to_and_v3_fields() {
+        let manifest = r#"{"ingredients": [{
+            "file_path": "assets/Dog.png",
+            "relationship": "inputOf",
+            "description": "Prompt image",
+            "informational_URI": "https://example.com/dog",
+            "data_types": [{"type": "c2pa.types.model", "version": "1.0"}]
+        }]}"#;
+        let (loaded, _) = process_ingredients(manifest, &fixtures(), false).unwrap();
+        let ingredient = &loaded[0];
+        assert_eq!(*ingredient.relationship(), Relationship::InputTo);
+        assert_eq!(ingredient.description(), Some("Prompt image"));
+        assert_eq!(
+            ingredient.informational_uri(),
+            Some("https://example.com/dog")
+        );
+        let data_types = ingredient.data_types().unwrap();
+        assert_eq!(data_types[0].asset_type, "c2pa.types.model");
+
+        let bad =
+            r#"{"ingredients": [{"file_path": "assets/Dog.png", "relationship": "siblingOf"}]}"#;
+        assert!(process_ingredients(bad, &fixtures(), false).is_err());
+    }
+
+    #[test]
+    fn test_check_ingredient_relationships() {
+        let assets = fixtures().join("assets");
+        let manifest = serde_json::json!({
+            "title": "composite.jpg",
+            "ingredients": [
+                {"file_path": "Dog.png", "relationship": "parentOf"},
+                {"file_path": "./Dog.png", "relationship": "componentOf"},
+                {"file_path": "Dog.jpg", "relationship": "parentOf"},
+                {"title": "inline", "relationship": "inputTo"}
+            ]
+        });
+        let mut options = SignOptions::new(
+            manifest.to_string(),
+            "cert.pem",
+            "key.pem",
+            SigningAlg::Ed25519,
+        );
+        options.ingredients_base_dir = assets.clone();
+
+        let violations = check_ingredient_relationships(&options, &assets.join("Dog.jpg")).unwrap();
+        assert_eq!(violations.len(), 3, "{violations:?}");
+        assert!(matches!(
+            &violations[0],
+            RelationshipViolation::MultipleParents { ingredients, .. } if ingredients.len() == 2
+        ));
+        assert!(matches!(
+            violations[1],
+            RelationshipViolation::SelfReference { .. }
+        ));
+        assert!(matches!(
+            violations[2],
+            RelationshipViolation::ComponentAlsoParent { .. }
+        ));
+
+        // A conforming definition: one parent, other ingredients distinct
+        let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+        let mut options = options.clone();
+        options.manifest_json =
+            fs::read_to_string(examples.join("with_input_ingredients.json")).unwrap();
+        options.ingredients_base_dir = examples;
+        assert!(
+            check_ingredient_relationships(&options, &assets.join("Dog.webp"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_normalize_manifest_titles() {
+        let manifest = r#"{"title": "Cafe\u0301", "label": "Cafe\u0301",
+            "ingredients": [{"title": "e\u0301te\u0301.jpg", "instance_id": "e\u0301"}]}"#;
+        let normalized: JsonValue =
+            serde_json::from_str(&normalize_manifest_titles(manifest).unwrap()).unwrap();
+        assert_eq!(normalized["title"], "Caf\u{e9}");
+        assert_eq!(normalized["ingredients"][0]["title"], "\u{e9}t\u{e9}.jpg");
+        assert_eq!(normalized["label"], "Cafe\u{301}");
+        assert_eq!(normalized["ingredients"][0]["instance_id"], "e\u{301}");
+    }
+
+    #[test]
+    fn test_sign_asset_self_signed() {
+        let out_dir = std::env::temp_dir().join(format!("crtool-sign-{}", std::process::id()));
+        let output = out_dir.join("signed.jpg");
+        let mut options = SignOptions::new(
+            r#"{"title": "Signed by the library", "claim_generator_info": [{"name": "crtool-test"}]}"#,
+            fixtures().join("certs/ed25519.pub"),
+            fixtures().join("certs/ed25519.pem"),
+            SigningAlg::Ed25519,
+        );
+        options.allow_self_signed = true;
+
+        let signed = sign_asset(&fixtures().join("assets/Dog.jpg"), &output, &options).unwrap();
+        assert_eq!(signed.output, output);
+        assert_eq!(signed.file_ingredients, 0);
+        let result = crate::extract_crjson_manifest(&output).unwrap();
+        assert!(!result.active_label.is_empty());
+        let _ = fs::remove_dir_all(&out_dir);
+    }
+
+    #[test]
+    fn test_sign_document_detached_store() {
+        let out_dir = std::env::temp_dir().join(format!("crtool-sign-doc-{}", std::process::id()));
+        fs::create_dir_all(&out_dir).unwrap();
+        let document = out_dir.join("caption.txt");
+        fs::write(&document, "A dog sitting on a lawn.\n").unwrap();
+        let mut options = SignOptions::new(
+            r#"{"title": "Caption", "claim_generator_info": [{"name": "crtool-test"}]}"#,
+            fixtures().join("certs/ed25519.pub"),
+            fixtures().join("certs/ed25519.pem"),
+            SigningAlg::Ed25519,
+        );
+        options.allow_self_signed = true;
+
+        let store = crate::documents::detached_store_path(&document);
+        let signed = sign_document(&document, &store, &options).unwrap();
+        assert_eq!(signed.output, out_dir.join("caption.txt.c2pa"));
+
+        let settings = crate::default_extraction_settings();
+        let result = crate::extract_crjson_manifest_with_settings(&document, &settings).unwrap();
+        assert!(!result.active_label.is_empty());
+        assert!(!result.manifest_json.contains("assertion.dataHash.mismatch"));
+
+        // Editing the document breaks the data hash binding
+        fs::write(&document, "A cat sitting on a lawn.\n").unwrap();
+        let result = crate::extract_crjson_manifest_with_settings(&document, &settings).unwrap();
+        assert!(result.manifest_json.contains("assertion.dataHash.mismatch"));
+        let _ = fs::remove_dir_all(&out_dir);
+    }
+}
@@ -0,0 +1,288 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Snapshots of extracted crJSON for regression testing: each extraction is normalized and
+//! compared with the copy stored under its name, so a c2pa-rs upgrade that changes extraction
+//! output fails with a focused [`IndicatorsDiff`] instead of passing silently.
+//!
+//! Normalization removes what differs between two signings of the same manifest: the
+//! `jsonGenerator` block, values of the [`VOLATILE_KEYS`], and UUIDs (manifest labels, instance
+//! IDs, and the JUMBF URIs that contain them), which are numbered in order of appearance.
+
+use crate::diff::{diff_indicators, IndicatorsDiff};
+use crate::error::{Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Keys whose values change with every signing (hashes over labels, padding, time stamps).
+/// Their values are replaced by `"<volatile>"`; the keys stay, so a missing one is still a
+/// change.
+pub const VOLATILE_KEYS: &[&str] = &["hash", "pad", "timestamp"];
+
+const VOLATILE: &str = "<volatile>";
+
+/// `extracted` without the values that differ between signings (see the module docs).
+pub fn normalize(extracted: &Value) -> Value {
+    let mut uuids = Vec::new();
+    let mut value = normalize_value(extracted, &mut uuids);
+    if let Value::Object(map) = &mut value {
+        map.remove("jsonGenerator");
+    }
+    value
+}
+
+fn normalize_value(value: &Value, uuids: &mut Vec<String>) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = if VOLATILE_KEYS.contains(&key.as_str()) && !value.is_null() {
+                        Value::String(VOLATILE.to_string())
+                    } else {
+                        normalize_value(value, uuids)
+                    };
+                    (replace_uuids(key, uuids), value)
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| normalize_value(item, uuids))
+                .collect(),
+        ),
+        Value::String(text) => Value::String(replace_uuids(text, uuids)),
+        other => other.clone(),
+    }
+}
+
+fn is_uuid(bytes: &[u8]) -> bool {
+    bytes.len() == 36
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => *b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+/// `text` with each UUID replaced by `<uuid-N>`, N numbering distinct UUIDs from 1.
+fn replace_uuids(text: &str, uuids: &mut Vec<String>) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < text.len() {
+        if i + 36 <= bytes.len() && is_uuid(&bytes[i..i + 36]) {
+            let uuid = text[i..i + 36].to_ascii_lowercase();
+            let n = match uuids.iter().position(|u| *u == uuid) {
+                Some(position) => position + 1,
+                None => {
+                    uuids.push(uuid);
+                    uuids.len()
+                }
+            };
+            out.push_str(&format!("<uuid-{n}>"));
+            i += 36;
+        } else {
+            let c = text[i..].chars().next().unwrap_or_default();
+            out.push(c);
+            i += c.len_utf8().max(1);
+        }
+    }
+    out
+}
+
+/// Result of comparing an extraction with its snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotOutcome {
+    /// The extraction matches the snapshot.
+    Matched,
+    /// There was no snapshot; it was written (only when updating).
+    Created,
+    /// There is no snapshot and the store is not updating, so none was written.
+    Missing,
+    /// The extraction differs and the snapshot was updated to it.
+    Updated(IndicatorsDiff),
+    /// The extraction differs from the snapshot, which was left as is.
+    Changed(IndicatorsDiff),
+}
+
+impl SnapshotOutcome {
+    /// Whether the extraction differs from a snapshot that was not updated, or has none.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, SnapshotOutcome::Changed(_) | SnapshotOutcome::Missing)
+    }
+}
+
+/// A directory of snapshots, one `<name>.json` per extraction (e.g. per testset manifest).
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+    update: bool,
+}
+
+impl SnapshotStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            update: false,
+        }
+    }
+
+    /// Write snapshots that are missing and overwrite those that differ, instead of reporting
+    /// them as failures.
+    pub fn update(mut self, enabled: bool) -> Self {
+        self.update = enabled;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the snapshot `name`.
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+
+    /// The stored snapshot `name`, or `None` when there is none.
+    pub fn load(&self, name: &str) -> Result<Option<Value>> {
+        let path = self.path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read snapshot {:?}", path))?;
+        let value = crate::limits::parse_json(&text)
+            .with_context(|| format!("Invalid JSON in snapshot {:?}", path))?;
+        Ok(Some(value))
+    }
+
+    fn write(&self, name: &str, snapshot: &Value) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create snapshot directory {:?}", self.dir))?;
+        let path = self.path(name);
+        let mut text = serde_json::to_string_pretty(snapshot)?;
+        text.push('\n');
+        fs::write(&path, text).with_context(|| format!("Failed to write snapshot {:?}", path))
+    }
+
+    /// Compare the normalized `extracted` crJSON with the snapshot `name`. When updating, a
+    /// missing or different snapshot is (re)written; otherwise both are failures, so a snapshot
+    /// that was never committed cannot pass silently.
+    pub fn check(&self, name: &str, extracted: &Value) -> Result<SnapshotOutcome> {
+        let snapshot = normalize(extracted);
+        let Some(stored) = self.load(name)? else {
+            if !self.update {
+                return Ok(SnapshotOutcome::Missing);
+            }
+            self.write(name, &snapshot)?;
+            return Ok(SnapshotOutcome::Created);
+        };
+        let diff = diff_indicators(&stored, &snapshot);
+        if diff.is_empty() {
+            Ok(SnapshotOutcome::Matched)
+        } else if self.update {
+            self.write(name, &snapshot)?;
+            Ok(SnapshotOutcome::Updated(diff))
+        } else {
+            Ok(SnapshotOutcome::Changed(diff))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn extraction(label: &str, title: &str) -> Value {
+        json!({
+            "jsonGenerator": {"name": "crTool", "date": "2025-06-01T00:00:00Z"},
+            "manifests": [{
+                "label": format!("urn:c2pa:{label}"),
+                "claim.v2": {"dc:title": title, "instanceID": format!("xmp:iid:{label}")},
+                "assertions": {
+                    "c2pa.hash.data": {"alg": "sha256", "hash": label, "pad": "AAA="}
+                },
+                "validationResults": {
+                    "success": [{"url": format!("self#jumbf=/c2pa/urn:c2pa:{label}/c2pa.signature")}]
+                }
+            }]
+        })
+    }
+
+    #[test]
+    fn test_normalize_removes_signing_differences() {
+        let a = extraction("3fa85f64-5717-4562-b3fc-2c963f66afa6", "a.jpg");
+        let b = extraction("9B1DEB4D-3B7D-4BAD-9BDD-2B0D7B3DCB6D", "a.jpg");
+        let normalized = normalize(&a);
+        assert_eq!(normalized, normalize(&b));
+        assert!(normalized.get("jsonGenerator").is_none());
+        assert_eq!(normalized["manifests"][0]["label"], "urn:c2pa:<uuid-1>");
+        assert_eq!(
+            normalized["manifests"][0]["assertions"]["c2pa.hash.data"]["hash"],
+            VOLATILE
+        );
+        assert_eq!(
+            replace_uuids("ü-not-a-uuid", &mut Vec::new()),
+            "ü-not-a-uuid"
+        );
+    }
+
+    #[test]
+    fn test_store_check_and_update() {
+        let dir = std::env::temp_dir().join(format!("crtool-snapshot-{}", uuid::Uuid::new_v4()));
+        let label = "3fa85f64-5717-4562-b3fc-2c963f66afa6";
+        let store = SnapshotStore::new(&dir);
+        let name = "p-actions-created";
+
+        // A missing snapshot fails and is only written when updating
+        let outcome = store.check(name, &extraction(label, "a.jpg")).unwrap();
+        assert_eq!(outcome, SnapshotOutcome::Missing);
+        assert!(outcome.is_failure());
+        assert!(!store.path(name).exists());
+        assert_eq!(
+            store
+                .clone()
+                .update(true)
+                .check(name, &extraction(label, "a.jpg"))
+                .unwrap(),
+            SnapshotOutcome::Created
+        );
+        assert!(store.path(name).exists());
+        assert_eq!(
+            store.check(name, &extraction(label, "a.jpg")).unwrap(),
+            SnapshotOutcome::Matched
+        );
+
+        let outcome = store.check(name, &extraction(label, "b.jpg")).unwrap();
+        assert!(outcome.is_failure());
+        let SnapshotOutcome::Changed(diff) = outcome else {
+            panic!("expected a change");
+        };
+        assert_eq!(diff.manifests[0].changes[0].path, "/claim.v2/dc:title");
+        assert!(diff
+            .to_string()
+            .contains("~ /claim.v2/dc:title: \"a.jpg\" → \"b.jpg\""));
+
+        let store = store.update(true);
+        assert!(matches!(
+            store.check(name, &extraction(label, "b.jpg")).unwrap(),
+            SnapshotOutcome::Updated(_)
+        ));
+        assert_eq!(
+            store.check(name, &extraction(label, "b.jpg")).unwrap(),
+            SnapshotOutcome::Matched
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
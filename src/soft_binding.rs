@@ -0,0 +1,272 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Soft-binding verification: checks that the watermark or fingerprint recorded in a
+//! `c2pa.soft-binding` assertion matches the asset.
+//!
+//! Computing a soft-binding value is specific to each watermarking or fingerprinting
+//! algorithm, so crTool does not implement any. A [`SoftBindingResolver`] (for example the
+//! CLI's `--soft-binding-plugin` command) computes the value for the assertion's `alg`, and
+//! [`verify_soft_bindings`] compares it with the values in the assertion's blocks.
+
+use crate::error::Result;
+use crate::provenance::find_manifest;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::path::Path;
+
+/// Label of the soft-binding assertion.
+pub const SOFT_BINDING_LABEL: &str = "c2pa.soft-binding";
+
+/// A `c2pa.soft-binding` assertion of a manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftBinding {
+    /// Label of the manifest holding the assertion.
+    pub manifest_label: String,
+    /// Assertion label, with its instance suffix (e.g. `c2pa.soft-binding__1`).
+    pub assertion_label: String,
+    /// Soft-binding algorithm name (e.g. `com.digimarc.validate.1`).
+    pub alg: String,
+    /// Values of the assertion's blocks.
+    pub values: Vec<String>,
+}
+
+/// Computes soft-binding values of assets.
+pub trait SoftBindingResolver {
+    /// Short name for messages (e.g. the plugin command).
+    fn name(&self) -> &str;
+
+    /// The value of `asset` for `binding.alg`, or `None` when the resolver does not support
+    /// that algorithm.
+    fn resolve(&self, asset: &Path, binding: &SoftBinding) -> Result<Option<String>>;
+}
+
+/// Outcome of checking one soft-binding assertion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SoftBindingStatus {
+    /// The computed value is one of the assertion's block values.
+    Match,
+    /// The computed value differs from every block value.
+    Mismatch,
+    /// The resolver does not support the assertion's algorithm.
+    Unsupported,
+    /// The resolver failed.
+    Error,
+}
+
+/// Result of checking one soft-binding assertion against the asset.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftBindingCheck {
+    #[serde(flatten)]
+    pub binding: SoftBinding,
+    pub status: SoftBindingStatus,
+    /// Value computed by the resolver.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub computed: Option<String>,
+    /// Resolver error (`Error` only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl std::fmt::Display for SoftBindingCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let b = &self.binding;
+        match self.status {
+            SoftBindingStatus::Match => write!(f, "{} ({}) matches", b.assertion_label, b.alg),
+            SoftBindingStatus::Mismatch => write!(
+                f,
+                "{} ({}) does not match: computed {}",
+                b.assertion_label,
+                b.alg,
+                self.computed.as_deref().unwrap_or_default()
+            ),
+            SoftBindingStatus::Unsupported => write!(
+                f,
+                "{} ({}) not checked: algorithm not supported by the resolver",
+                b.assertion_label, b.alg
+            ),
+            SoftBindingStatus::Error => write!(
+                f,
+                "{} ({}) could not be checked: {}",
+                b.assertion_label,
+                b.alg,
+                self.error.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// Soft-binding assertions of `manifest` (any instance of `c2pa.soft-binding`).
+pub fn manifest_soft_bindings(manifest: &JsonValue) -> Vec<SoftBinding> {
+    let manifest_label = manifest
+        .get("label")
+        .and_then(|l| l.as_str())
+        .unwrap_or_default();
+    let Some(assertions) = manifest.get("assertions").and_then(|a| a.as_object()) else {
+        return Vec::new();
+    };
+    assertions
+        .iter()
+        .filter(|(k, _)| {
+            k.as_str() == SOFT_BINDING_LABEL
+                || k.strip_prefix(SOFT_BINDING_LABEL)
+                    .is_some_and(|rest| rest.starts_with("__"))
+        })
+        .map(|(k, data)| SoftBinding {
+            manifest_label: manifest_label.to_string(),
+            assertion_label: k.clone(),
+            alg: data
+                .get("alg")
+                .and_then(|a| a.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            values: data
+                .get("blocks")
+                .and_then(|b| b.as_array())
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter_map(|b| b.get("value").and_then(|v| v.as_str()))
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Check every soft-binding assertion of the active manifest of `crjson` against `asset`
+/// with `resolver`. Ingredient manifests are skipped: their soft bindings describe the
+/// ingredients, not this asset. Empty when the active manifest has no soft binding.
+pub fn verify_soft_bindings(
+    asset: &Path,
+    crjson: &JsonValue,
+    active_label: &str,
+    resolver: &dyn SoftBindingResolver,
+) -> Vec<SoftBindingCheck> {
+    let Some(active) = find_manifest(crjson, active_label) else {
+        return Vec::new();
+    };
+    manifest_soft_bindings(active)
+        .into_iter()
+        .map(|binding| {
+            let (status, computed, error) = match resolver.resolve(asset, &binding) {
+                Ok(Some(value)) if binding.values.contains(&value) => {
+                    (SoftBindingStatus::Match, Some(value), None)
+                }
+                Ok(Some(value)) => (SoftBindingStatus::Mismatch, Some(value), None),
+                Ok(None) => (SoftBindingStatus::Unsupported, None, None),
+                Err(e) => (SoftBindingStatus::Error, None, Some(e.to_string())),
+            };
+            SoftBindingCheck {
+                binding,
+                status,
+                computed,
+                error,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Resolver returning a fixed value for `com.example.wm`.
+    struct FixedResolver(&'static str);
+
+    impl SoftBindingResolver for FixedResolver {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn resolve(&self, _asset: &Path, binding: &SoftBinding) -> Result<Option<String>> {
+            Ok((binding.alg == "com.example.wm").then(|| self.0.to_string()))
+        }
+    }
+
+    fn crjson() -> JsonValue {
+        json!({
+            "manifests": [
+                {
+                    "label": "urn:c2pa:active",
+                    "assertions": {
+                        "c2pa.soft-binding": {
+                            "alg": "com.example.wm",
+                            "blocks": [{ "scope": {}, "value": "abc" }]
+                        },
+                        "c2pa.soft-binding__1": {
+                            "alg": "com.other.fp",
+                            "blocks": [{ "scope": {}, "value": "xyz" }]
+                        },
+                        "c2pa.actions.v2": { "actions": [] }
+                    }
+                },
+                {
+                    "label": "urn:c2pa:ingredient",
+                    "assertions": {
+                        "c2pa.soft-binding": {
+                            "alg": "com.example.wm",
+                            "blocks": [{ "scope": {}, "value": "other" }]
+                        }
+                    }
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_manifest_soft_bindings() {
+        let value = crjson();
+        let bindings = manifest_soft_bindings(&value["manifests"][0]);
+        assert_eq!(bindings.len(), 2);
+        let wm = bindings
+            .iter()
+            .find(|b| b.assertion_label == "c2pa.soft-binding")
+            .unwrap();
+        assert_eq!(wm.manifest_label, "urn:c2pa:active");
+        assert_eq!(wm.alg, "com.example.wm");
+        assert_eq!(wm.values, vec!["abc".to_string()]);
+    }
+
+    #[test]
+    fn test_verify_soft_bindings() {
+        let value = crjson();
+        let asset = Path::new("a.jpg");
+        let status = |checks: &[SoftBindingCheck], alg: &str| {
+            checks.iter().find(|c| c.binding.alg == alg).unwrap().status
+        };
+
+        let checks = verify_soft_bindings(asset, &value, "urn:c2pa:active", &FixedResolver("abc"));
+        assert_eq!(checks.len(), 2);
+        assert_eq!(status(&checks, "com.example.wm"), SoftBindingStatus::Match);
+        assert_eq!(
+            status(&checks, "com.other.fp"),
+            SoftBindingStatus::Unsupported
+        );
+
+        let checks = verify_soft_bindings(asset, &value, "urn:c2pa:active", &FixedResolver("zzz"));
+        assert_eq!(
+            status(&checks, "com.example.wm"),
+            SoftBindingStatus::Mismatch
+        );
+        assert!(checks[0].to_string().contains("com."));
+
+        assert!(
+            verify_soft_bindings(asset, &value, "urn:c2pa:none", &FixedResolver("abc")).is_empty()
+        );
+    }
+}
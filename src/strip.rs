@@ -0,0 +1,502 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Removing C2PA data from assets (`crTool strip`), e.g. to produce negative test cases and
+//! check "no manifest" handling.
+//!
+//! Drops the embedded JUMBF manifest store (JPEG APP11 segments, PNG `caBX` chunks) and blanks
+//! the XMP `dcterms:provenance` references to an embedded or remote manifest. The XMP packet is
+//! blanked with spaces rather than shortened, so the rest of the file is copied byte for byte.
+//! JPEG and PNG only.
+
+use crate::error::{bail, Context, Result};
+use crate::removal::{find, APP11, XMP_PROVENANCE};
+use serde::Serialize;
+
+/// JPEG APP1 marker, which carries the XMP packet.
+const APP1: u8 = 0xE1;
+
+/// Namespace header of the APP1 segment holding the XMP packet.
+const JPEG_XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// PNG chunk holding the C2PA manifest store.
+const PNG_C2PA_CHUNK: &[u8] = b"caBX";
+
+/// Keyword of the PNG `iTXt` chunk holding the XMP packet.
+const PNG_XMP_KEYWORD: &[u8] = b"XML:com.adobe.xmp\0";
+
+/// What [`remove_c2pa`] removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stripped {
+    /// JPEG segments or PNG chunks of the manifest store that were dropped.
+    pub removed_blocks: usize,
+    /// Size in bytes of the dropped segments or chunks, headers included.
+    pub removed_bytes: u64,
+    /// XMP `dcterms:provenance` references (embedded or remote manifest) that were blanked.
+    pub references: Vec<String>,
+}
+
+impl Stripped {
+    /// True when the asset had no C2PA data to remove.
+    pub fn is_empty(&self) -> bool {
+        self.removed_blocks == 0 && self.references.is_empty()
+    }
+}
+
+/// Remove the C2PA data from an asset in memory. `format` is a file extension or MIME type.
+/// Returns the stripped asset and what was removed.
+pub fn remove_c2pa_from_bytes(data: &[u8], format: &str) -> Result<(Vec<u8>, Stripped)> {
+    let mime = crate::asset_format_for_type(format)
+        .map(|f| f.mime)
+        .with_context(|| format!("Unsupported asset format: {}", format))?;
+    match mime {
+        "image/jpeg" => strip_jpeg(data),
+        "image/png" => strip_png(data),
+        other => bail!("Removing C2PA data from {other} is not supported (JPEG and PNG only)"),
+    }
+}
+
+/// Remove the C2PA manifest store and manifest references of `input` and write the result to
+/// `output`. When `output` is `input`, the file is stripped in place under an exclusive lock, and
+/// a file another process is using (e.g. a tethering or DAM sync client still writing it) is left
+/// alone with an error. How much of that is detected depends on the platform (see
+/// [`IN_USE_DETECTION`]). The stripped asset replaces the original atomically (see
+/// [`replace_file`]), so a crash or full disk never leaves a truncated file.
+#[cfg(feature = "fs")]
+pub fn remove_c2pa<P: AsRef<std::path::Path>, Q: AsRef<std::path::Path>>(
+    input: P,
+    output: Q,
+) -> Result<Stripped> {
+    use fs2::FileExt;
+    use std::fs;
+    use std::io::Read;
+
+    let (input, output) = (input.as_ref(), output.as_ref());
+    let format = crate::asset_format_for_path(input)
+        .with_context(|| format!("Unsupported file format: {:?}", input))?;
+    let target =
+        fs::canonicalize(input).with_context(|| format!("Failed to resolve {:?}", input))?;
+    let in_place = output.exists()
+        && target
+            == fs::canonicalize(output)
+                .with_context(|| format!("Failed to resolve {:?}", output))?;
+    if !in_place {
+        let data = fs::read(input).with_context(|| format!("Failed to read {:?}", input))?;
+        let (data, stripped) = remove_c2pa_from_bytes(&data, format.mime)?;
+        fs::write(output, data).with_context(|| format!("Failed to write {:?}", output))?;
+        return Ok(stripped);
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.read(true).write(true);
+    // Sharing only deletion (for the rename below), opening fails while any other process has
+    // the file open
+    #[cfg(windows)]
+    std::os::windows::fs::OpenOptionsExt::share_mode(&mut options, FILE_SHARE_DELETE);
+    let mut file = match options.open(input) {
+        #[cfg(windows)]
+        Err(e) if e.raw_os_error() == Some(ERROR_SHARING_VIOLATION) => {
+            bail!("{:?} is open in another process; not modified", input)
+        }
+        result => result.with_context(|| format!("Failed to open {:?}", input))?,
+    };
+    if file.try_lock_exclusive().is_err() {
+        bail!("{:?} is in use by another process; not modified", input);
+    }
+    #[cfg(target_os = "linux")]
+    if let Some(pid) = open_by_other_process(input) {
+        bail!(
+            "{:?} is open in another process (pid {}); not modified",
+            input,
+            pid
+        );
+    }
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)
+        .with_context(|| format!("Failed to read {:?}", input))?;
+    let (data, stripped) = remove_c2pa_from_bytes(&data, format.mime)?;
+    if !stripped.is_empty() {
+        let permissions = file
+            .metadata()
+            .with_context(|| format!("Failed to read {:?}", input))?
+            .permissions();
+        // `file` stays open, and locked, until the stripped copy has replaced it
+        replace_file(&target, &data, permissions)?;
+    }
+    drop(file);
+    Ok(stripped)
+}
+
+/// Replace the file at `path` with `data`: write a temporary file in the same directory, flush
+/// it to disk and rename it over `path`, so that `path` holds either the old or the new content
+/// at any moment. The temporary file gets `permissions`; it is removed if anything fails.
+#[cfg(feature = "fs")]
+fn replace_file(
+    path: &std::path::Path,
+    data: &[u8],
+    permissions: std::fs::Permissions,
+) -> Result<()> {
+    use std::fs;
+    use std::io::Write;
+
+    let dir = path.parent().context("Invalid file path")?;
+    let name = path.file_name().context("Invalid file path")?;
+    let temp = dir.join(format!(
+        ".{}.{}.tmp",
+        name.to_string_lossy(),
+        uuid::Uuid::new_v4()
+    ));
+    let written = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&temp)
+        .and_then(|mut file| {
+            file.write_all(data)?;
+            file.set_permissions(permissions)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(e).with_context(|| format!("Failed to write {:?}", path));
+    }
+    // Persist the rename itself
+    #[cfg(unix)]
+    fs::File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .with_context(|| format!("Failed to sync {:?}", dir))?;
+    Ok(())
+}
+
+/// Windows error opening a file another process has open without sharing it.
+#[cfg(all(feature = "fs", windows))]
+const ERROR_SHARING_VIOLATION: i32 = 32;
+
+/// Windows share mode that lets the file be renamed over while it is open.
+#[cfg(all(feature = "fs", windows))]
+const FILE_SHARE_DELETE: u32 = 0x4;
+
+/// How completely [`remove_c2pa`] detects that a file it would strip in place is in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InUseDetection {
+    /// Any other process with the file open is detected, as the file is opened without sharing
+    /// (Windows).
+    Complete,
+    /// Processes of the current user with the file open are detected through `/proc`; those of
+    /// other users cannot be inspected (Linux).
+    CurrentUser,
+    /// Only processes that lock the file themselves are detected, as locks are advisory (other
+    /// Unix systems).
+    LockingProcesses,
+}
+
+/// In-use detection of [`remove_c2pa`] on this platform.
+pub const IN_USE_DETECTION: InUseDetection = if cfg!(windows) {
+    InUseDetection::Complete
+} else if cfg!(target_os = "linux") {
+    InUseDetection::CurrentUser
+} else {
+    InUseDetection::LockingProcesses
+};
+
+/// Id of another process that has `path` open, found through the `/proc/<pid>/fd` links of the
+/// processes this user may inspect.
+#[cfg(all(feature = "fs", target_os = "linux"))]
+fn open_by_other_process(path: &std::path::Path) -> Option<u32> {
+    use std::fs;
+    use std::os::unix::fs::MetadataExt;
+
+    let target = fs::metadata(path).ok()?;
+    let own = std::process::id();
+    for entry in fs::read_dir("/proc").ok()?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+        if pid == own {
+            continue;
+        }
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let open = fds.flatten().any(|fd| {
+            fs::metadata(fd.path())
+                .is_ok_and(|meta| meta.dev() == target.dev() && meta.ino() == target.ino())
+        });
+        if open {
+            return Some(pid);
+        }
+    }
+    None
+}
+
+/// Blank every `dcterms:provenance` attribute or element of an XMP packet with spaces, so its
+/// length is unchanged. Returns the values removed.
+fn blank_provenance(xmp: &mut [u8]) -> Vec<String> {
+    const CLOSE: &[u8] = b"</dcterms:provenance>";
+    let mut values = Vec::new();
+    let mut from = 0;
+    while let Some(found) = find(&xmp[from..], XMP_PROVENANCE) {
+        let name = from + found;
+        let after = name + XMP_PROVENANCE.len();
+        let (range, value) = if name > 0 && xmp[name - 1] == b'<' {
+            // <dcterms:provenance>value</dcterms:provenance>
+            let (Some(open_end), Some(close)) = (
+                xmp[after..].iter().position(|b| *b == b'>'),
+                find(&xmp[after..], CLOSE),
+            ) else {
+                break;
+            };
+            (
+                name - 1..after + close + CLOSE.len(),
+                &xmp[after + open_end + 1..after + close],
+            )
+        } else if let [b'=', quote @ (b'"' | b'\''), rest @ ..] = &xmp[after..] {
+            // dcterms:provenance="value"
+            let Some(len) = rest.iter().position(|b| b == quote) else {
+                break;
+            };
+            (name..after + 3 + len, &rest[..len])
+        } else {
+            from = after;
+            continue;
+        };
+        let value = String::from_utf8_lossy(value).trim().to_string();
+        if !value.is_empty() {
+            values.push(value);
+        }
+        from = range.end;
+        xmp[range].fill(b' ');
+    }
+    values
+}
+
+/// Whether an APP11 payload is a segment of the C2PA manifest store. The first segment
+/// (Z = 1) of a JUMBF box instance carries the `c2pa` label; the later ones are matched by
+/// their instance number (En), which is recorded in `instances`.
+fn is_c2pa_segment(payload: &[u8], instances: &mut Vec<u16>) -> bool {
+    // CI "JP", En (box instance), Z (sequence), then the repeated LBox/TBox header.
+    if payload.len() < 16 || !payload.starts_with(b"JP") || &payload[12..16] != b"jumb" {
+        return false;
+    }
+    let instance = u16::from_be_bytes([payload[2], payload[3]]);
+    let sequence = u32::from_be_bytes([payload[4], payload[5], payload[6], payload[7]]);
+    if sequence == 1 {
+        let lbox = u32::from_be_bytes([payload[8], payload[9], payload[10], payload[11]]);
+        let jumd = if lbox == 1 { 24 } else { 16 };
+        // jumd box: LBox, TBox, 16-byte type UUID, toggles, then the label
+        let label = payload.get(jumd + 25..).unwrap_or_default();
+        if label.starts_with(b"c2pa\0") && !instances.contains(&instance) {
+            instances.push(instance);
+        }
+    }
+    instances.contains(&instance)
+}
+
+fn strip_jpeg(data: &[u8]) -> Result<(Vec<u8>, Stripped)> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        bail!("Not a JPEG file");
+    }
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..2]);
+    let mut stripped = Stripped::default();
+    let mut instances = Vec::new();
+    let mut pos = 2;
+    // Marker segments up to the first scan; the entropy-coded data after it is copied as is.
+    while pos + 1 < data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        if marker == 0xFF {
+            out.push(0xFF);
+            pos += 1;
+            continue;
+        }
+        if marker == 0xD9 || marker == 0xDA {
+            break;
+        }
+        if (0xD0..=0xD7).contains(&marker) || marker == 0x01 {
+            out.extend_from_slice(&data[pos..pos + 2]);
+            pos += 2;
+            continue;
+        }
+        let len = match data.get(pos + 2..pos + 4) {
+            Some(len) => u16::from_be_bytes([len[0], len[1]]) as usize,
+            None => bail!("Truncated JPEG segment"),
+        };
+        let end = pos + 2 + len;
+        if len < 2 || end > data.len() {
+            bail!("Truncated JPEG segment");
+        }
+        let payload = &data[pos + 4..end];
+        if marker == APP11 && is_c2pa_segment(payload, &mut instances) {
+            stripped.removed_blocks += 1;
+            stripped.removed_bytes += (end - pos) as u64;
+        } else {
+            let start = out.len();
+            out.extend_from_slice(&data[pos..end]);
+            if marker == APP1 && payload.starts_with(JPEG_XMP_HEADER) {
+                let xmp = &mut out[start + 4 + JPEG_XMP_HEADER.len()..];
+                stripped.references.extend(blank_provenance(xmp));
+            }
+        }
+        pos = end;
+    }
+    out.extend_from_slice(&data[pos..]);
+    Ok((out, stripped))
+}
+
+/// CRC-32 (ISO-HDLC) as used for PNG chunks.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn strip_png(data: &[u8]) -> Result<(Vec<u8>, Stripped)> {
+    if !data.starts_with(PNG_SIGNATURE) {
+        bail!("Not a PNG file");
+    }
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(PNG_SIGNATURE);
+    let mut stripped = Stripped::default();
+    let mut pos = PNG_SIGNATURE.len();
+    // Chunks: length, type, data, CRC over type and data.
+    while pos + 12 <= data.len() {
+        let len = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
+        let end = pos + 12 + len as usize;
+        if end > data.len() {
+            bail!("Truncated PNG chunk");
+        }
+        let chunk_type = &data[pos + 4..pos + 8];
+        if chunk_type == PNG_C2PA_CHUNK {
+            stripped.removed_blocks += 1;
+            stripped.removed_bytes += (end - pos) as u64;
+            pos = end;
+            continue;
+        }
+        let start = out.len();
+        out.extend_from_slice(&data[pos..end]);
+        if chunk_type == b"iTXt" && data[pos + 8..].starts_with(PNG_XMP_KEYWORD) {
+            let crc_at = out.len() - 4;
+            let refs = blank_provenance(&mut out[start + 8..crc_at]);
+            if !refs.is_empty() {
+                let crc = crc32(&out[start + 4..crc_at]);
+                out[crc_at..].copy_from_slice(&crc.to_be_bytes());
+                stripped.references.extend(refs);
+            }
+        }
+        pos = end;
+    }
+    out.extend_from_slice(&data[pos..]);
+    Ok((out, stripped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+        let mut seg = vec![0xFF, marker];
+        seg.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        seg.extend_from_slice(payload);
+        seg
+    }
+
+    /// APP11 payload of a JUMBF superbox segment; `label` goes in the jumd box of segment 1.
+    fn jumbf(instance: u16, seq: u32, label: &[u8]) -> Vec<u8> {
+        let mut payload = b"JP".to_vec();
+        payload.extend_from_slice(&instance.to_be_bytes());
+        payload.extend_from_slice(&seq.to_be_bytes());
+        payload.extend_from_slice(&100u32.to_be_bytes());
+        payload.extend_from_slice(b"jumb");
+        payload.extend_from_slice(&30u32.to_be_bytes());
+        payload.extend_from_slice(b"jumd");
+        payload.extend_from_slice(&[0u8; 16]);
+        payload.push(0x03);
+        payload.extend_from_slice(label);
+        payload
+    }
+
+    #[test]
+    fn test_strip_jpeg() {
+        let xmp = [
+            JPEG_XMP_HEADER,
+            br#"<rdf:Description dcterms:provenance="self#jumbf=/c2pa/urn:c2pa:abc" x="1"/>"#,
+        ]
+        .concat();
+        let mut data = vec![0xFF, 0xD8];
+        data.extend(segment(APP1, &xmp));
+        data.extend(segment(APP11, &jumbf(1, 1, b"c2pa\0")));
+        data.extend(segment(APP11, &jumbf(1, 2, b"")));
+        data.extend(segment(APP11, &jumbf(2, 1, b"other\0")));
+        data.extend([0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]);
+
+        let (out, stripped) = remove_c2pa_from_bytes(&data, "jpg").unwrap();
+        assert_eq!(stripped.removed_blocks, 2);
+        assert_eq!(out.len() as u64 + stripped.removed_bytes, data.len() as u64);
+        assert_eq!(
+            stripped.references,
+            vec!["self#jumbf=/c2pa/urn:c2pa:abc".to_string()]
+        );
+        assert!(find(&out, XMP_PROVENANCE).is_none());
+        assert!(find(&out, b"x=\"1\"").is_some());
+        assert!(find(&out, b"other").is_some());
+        assert!(out.ends_with(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]));
+
+        let (again, stripped) = remove_c2pa_from_bytes(&out, "image/jpeg").unwrap();
+        assert!(stripped.is_empty());
+        assert_eq!(again, out);
+    }
+
+    #[test]
+    fn test_strip_png() {
+        fn chunk(chunk_type: &[u8], body: &[u8]) -> Vec<u8> {
+            let mut c = (body.len() as u32).to_be_bytes().to_vec();
+            c.extend_from_slice(chunk_type);
+            c.extend_from_slice(body);
+            let crc = crc32(&c[4..]);
+            c.extend_from_slice(&crc.to_be_bytes());
+            c
+        }
+        let element = b"<dcterms:provenance>https://example.com/m.c2pa</dcterms:provenance>";
+        let xmp = [PNG_XMP_KEYWORD, b"\0\0\0\0", element].concat();
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend(chunk(b"IHDR", &[0; 13]));
+        data.extend(chunk(b"iTXt", &xmp));
+        data.extend(chunk(PNG_C2PA_CHUNK, b"jumbf"));
+        data.extend(chunk(b"IEND", b""));
+
+        let (out, stripped) = remove_c2pa_from_bytes(&data, "png").unwrap();
+        assert_eq!(stripped.removed_blocks, 1);
+        assert_eq!(
+            stripped.references,
+            vec!["https://example.com/m.c2pa".to_string()]
+        );
+        assert!(find(&out, PNG_C2PA_CHUNK).is_none());
+        assert!(find(&out, XMP_PROVENANCE).is_none());
+        let blanked = [PNG_XMP_KEYWORD, b"\0\0\0\0", &vec![b' '; element.len()]].concat();
+        assert!(find(&out, &chunk(b"iTXt", &blanked)).is_some());
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+
+        assert!(remove_c2pa_from_bytes(b"GIF89a", "gif").is_err());
+    }
+}
@@ -0,0 +1,153 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Placeholders in manifest definitions, expanded for each asset signed with them so one
+//! template gives every output its own title, time and instance IDs.
+//!
+//! | Placeholder     | Value                                                           |
+//! | --------------- | --------------------------------------------------------------- |
+//! | `{{filename}}`  | File name of the asset being signed (`photo.jpg`)               |
+//! | `{{stem}}`      | The file name without its extension (`photo`)                   |
+//! | `{{now}}`       | Time of expansion, RFC 3339 in UTC                              |
+//! | `{{uuid}}`      | A random UUID, the same for every occurrence in one manifest    |
+//! | `{{env:VAR}}`   | Environment variable `VAR` (an error when it is not set)        |
+//!
+//! Placeholders are expanded inside JSON strings only. Any other `{{...}}` is an error, so a
+//! mistyped placeholder cannot end up in a signed manifest.
+
+use crate::error::{bail, Context, Result};
+use serde_json::Value as JsonValue;
+use std::path::Path;
+
+/// Values of the placeholders for one asset.
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    pub filename: String,
+    pub stem: String,
+    pub now: String,
+    pub uuid: String,
+}
+
+impl TemplateContext {
+    /// Placeholder values for signing `input` now, with a fresh UUID.
+    pub fn for_input(input: &Path) -> Self {
+        let name = |p: Option<&std::ffi::OsStr>| {
+            p.map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        Self {
+            filename: name(input.file_name()),
+            stem: name(input.file_stem()),
+            now: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            uuid: uuid::Uuid::new_v4().to_string(),
+        }
+    }
+
+    fn value(&self, placeholder: &str) -> Result<String> {
+        Ok(match placeholder {
+            "filename" => self.filename.clone(),
+            "stem" => self.stem.clone(),
+            "now" => self.now.clone(),
+            "uuid" => self.uuid.clone(),
+            _ => match placeholder.strip_prefix("env:") {
+                Some(var) => std::env::var(var).with_context(|| {
+                    format!(
+                        "Environment variable {} of {{{{{}}}}} is not set",
+                        var, placeholder
+                    )
+                })?,
+                None => bail!(
+                    "Unknown placeholder {{{{{}}}}}; expected filename, stem, now, uuid or env:VAR",
+                    placeholder
+                ),
+            },
+        })
+    }
+}
+
+/// `text` with its placeholders replaced.
+pub fn expand_str(text: &str, context: &TemplateContext) -> Result<String> {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .with_context(|| format!("Unterminated placeholder in {:?}", text))?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&context.value(rest[start + 2..start + end].trim())?);
+        rest = &rest[start + end + 2..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Replace the placeholders in every string of `value`.
+pub fn expand_placeholders(value: &mut JsonValue, context: &TemplateContext) -> Result<()> {
+    match value {
+        JsonValue::String(s) if s.contains("{{") => *s = expand_str(s, context)?,
+        JsonValue::Array(items) => {
+            for item in items {
+                expand_placeholders(item, context)?;
+            }
+        }
+        JsonValue::Object(map) => {
+            for item in map.values_mut() {
+                expand_placeholders(item, context)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_expand_placeholders() {
+        let context = TemplateContext::for_input(Path::new("shots/photo.final.jpg"));
+        assert_eq!(context.filename, "photo.final.jpg");
+        assert_eq!(context.stem, "photo.final");
+        assert!(
+            crate::labels::validate_manifest_label(&format!("urn:c2pa:{}", context.uuid)).is_ok()
+        );
+
+        std::env::set_var("CRTOOL_TEMPLATE_TEST_AUTHOR", "Ada");
+        let mut manifest = json!({
+            "title": "{{ stem }} by {{env:CRTOOL_TEMPLATE_TEST_AUTHOR}}",
+            "instance_id": "xmp:iid:{{uuid}}",
+            "assertions": [{ "data": { "when": "{{now}}", "ref": "{{uuid}}", "count": 2 } }]
+        });
+        expand_placeholders(&mut manifest, &context).unwrap();
+        assert_eq!(manifest["title"], "photo.final by Ada");
+        assert_eq!(manifest["instance_id"], format!("xmp:iid:{}", context.uuid));
+        assert_eq!(
+            manifest["assertions"][0]["data"]["when"],
+            context.now.as_str()
+        );
+        assert_eq!(
+            manifest["assertions"][0]["data"]["ref"],
+            context.uuid.as_str()
+        );
+        assert_eq!(manifest["assertions"][0]["data"]["count"], 2);
+
+        assert_ne!(
+            TemplateContext::for_input(Path::new("a.jpg")).uuid,
+            TemplateContext::for_input(Path::new("a.jpg")).uuid
+        );
+        assert!(expand_str("{{title}}", &context).is_err());
+        assert!(expand_str("{{filename", &context).is_err());
+        assert!(expand_str("{{env:CRTOOL_TEMPLATE_TEST_UNSET}}", &context).is_err());
+    }
+}
@@ -0,0 +1,432 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! User-configurable trust policy: which trust anchors and allowed signing certificates
+//! extraction checks signing credentials against.
+//!
+//! A [`TrustConfig`] combines the published default lists (the official C2PA trust list and the
+//! Content Credentials interim list, fetched by the caller) with an organization's own anchors,
+//! allowed certificates (PEM files or SHA-256 hashes), allowed EKUs and revocation checks, and
+//! turns them into SDK [`Settings`]. It is stored as JSON or TOML:
+//!
+//! ```toml
+//! useDefaultLists = false
+//! anchorFiles = ["/etc/newsroom/root-ca.pem"]
+//! allowedHashes = ["hNb1k0O0bRk5VwTqD6Z3aS4E4yHsS3b4Qm0iN6i1xXk="]
+//! allowedEkus = ["1.3.6.1.5.5.7.3.4"]
+//!
+//! [revocation]
+//! ocspFetch = true
+//! ```
+
+use crate::error::{bail, format_err, Context, CrtoolError, Result};
+use crate::hashing::{hash_bytes, HashAlgorithm};
+use crate::{build_trust_settings, default_extraction_settings, Settings};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The published default trust lists, as fetched from [`crate::C2PA_TRUST_ANCHORS_URL`] and the
+/// interim Content Credentials URLs.
+#[derive(Debug, Clone, Default)]
+pub struct TrustLists {
+    /// PEM bundle of trust anchors.
+    pub anchors: String,
+    /// PEM bundle or SHA-256 hash list of explicitly allowed signing certificates.
+    pub allowed_list: Option<String>,
+    /// Allowed EKU OIDs in dot notation.
+    pub trust_config: Option<String>,
+}
+
+/// Trust policy chosen by the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustConfig {
+    /// Trust the published default lists in addition to the files below.
+    #[serde(default = "default_true")]
+    pub use_default_lists: bool,
+    /// PEM files of additional trust anchors (root or intermediate CA certificates).
+    #[serde(default)]
+    pub anchor_files: Vec<PathBuf>,
+    /// PEM files of end-entity certificates that are trusted even without a chain to an anchor.
+    #[serde(default)]
+    pub allowed_files: Vec<PathBuf>,
+    /// Base64 SHA-256 hashes of DER signing certificates trusted without a chain to an anchor.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_hashes: Vec<String>,
+    /// Extended key usage OIDs (dot notation) accepted for signing certificates, in addition to
+    /// those of the default lists. Empty keeps the SDK's (or the default lists') EKUs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_ekus: Vec<String>,
+    /// Certificate revocation checks.
+    #[serde(default, skip_serializing_if = "RevocationConfig::is_default")]
+    pub revocation: RevocationConfig,
+}
+
+/// Revocation checking of signing certificates.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationConfig {
+    /// Fetch OCSP responses for signing certificates without a stapled response (needs network
+    /// access).
+    #[serde(default)]
+    pub ocsp_fetch: bool,
+}
+
+impl RevocationConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// True for paths ending in `.toml`; trust configurations are JSON otherwise.
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for TrustConfig {
+    fn default() -> Self {
+        Self {
+            use_default_lists: true,
+            anchor_files: Vec::new(),
+            allowed_files: Vec::new(),
+            allowed_hashes: Vec::new(),
+            allowed_ekus: Vec::new(),
+            revocation: RevocationConfig::default(),
+        }
+    }
+}
+
+impl TrustConfig {
+    /// Read a trust configuration from a JSON file, or a TOML file (`.toml`). Relative
+    /// certificate paths are resolved against the file's directory.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read trust configuration {:?}", path))?;
+        let mut config: TrustConfig = if is_toml(path) {
+            toml::from_str(&text).map_err(CrtoolError::from_error)
+        } else {
+            serde_json::from_str(&text).map_err(CrtoolError::from_error)
+        }
+        .with_context(|| format!("Invalid trust configuration {:?}", path))?;
+        if let Some(dir) = path.parent() {
+            for file in config
+                .anchor_files
+                .iter_mut()
+                .chain(config.allowed_files.iter_mut())
+            {
+                if file.is_relative() {
+                    *file = dir.join(&*file);
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// Write the trust configuration as JSON, or TOML for a `.toml` path, creating the parent
+    /// directory if needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+        let text = if is_toml(path) {
+            toml::to_string_pretty(self).map_err(CrtoolError::from_error)?
+        } else {
+            serde_json::to_string_pretty(self)?
+        };
+        fs::write(path, text)
+            .with_context(|| format!("Failed to write trust configuration {:?}", path))
+    }
+
+    /// Add an allowed list file as given to `--allowed-list`: a PEM bundle becomes an allowed
+    /// file, and a hash list (one base64 SHA-256 per line, `#` comments) adds its hashes.
+    pub fn add_allowed_list(&mut self, path: &Path) -> Result<()> {
+        let list = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read allowed list {:?}", path))?;
+        if pem_certificates(&list).is_empty() {
+            self.allowed_hashes.extend(
+                list.lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(str::to_string),
+            );
+        } else {
+            self.allowed_files.push(path.to_path_buf());
+        }
+        Ok(())
+    }
+
+    /// True when the policy adds nothing to the default lists.
+    pub fn is_empty(&self) -> bool {
+        self.anchor_files.is_empty()
+            && self.allowed_files.is_empty()
+            && self.allowed_hashes.is_empty()
+            && self.allowed_ekus.is_empty()
+            && self.revocation.is_default()
+    }
+
+    /// Build extraction settings for this policy. `defaults` are the fetched default lists; they
+    /// are only used when `use_default_lists` is set. With no anchors and no allowed
+    /// certificates at all, every signing credential reports as untrusted.
+    pub fn settings(&self, defaults: Option<&TrustLists>) -> Result<Settings> {
+        let defaults = defaults.filter(|_| self.use_default_lists);
+
+        let mut anchors: Vec<String> = defaults.map(|d| d.anchors.clone()).into_iter().collect();
+        for path in &self.anchor_files {
+            anchors.push(read_pem_file(path)?);
+        }
+        let mut allowed: Vec<String> = defaults
+            .and_then(|d| d.allowed_list.clone())
+            .into_iter()
+            .collect();
+        for path in &self.allowed_files {
+            allowed.push(read_pem_file(path)?);
+        }
+        if !self.allowed_hashes.is_empty() {
+            allowed.push(self.allowed_hashes.join("\n"));
+        }
+
+        let settings = if anchors.is_empty() && allowed.is_empty() {
+            default_extraction_settings()
+        } else {
+            let anchors = anchors
+                .iter()
+                .map(|p| p.trim())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let allowed = (!allowed.is_empty()).then(|| merge_allowed_lists(&allowed));
+            let mut ekus: Vec<&str> = defaults
+                .and_then(|d| d.trust_config.as_deref())
+                .map(|c| c.lines().map(str::trim).filter(|l| !l.is_empty()).collect())
+                .unwrap_or_default();
+            ekus.extend(self.allowed_ekus.iter().map(|e| e.trim()));
+            let ekus = (!ekus.is_empty()).then(|| ekus.join("\n"));
+            build_trust_settings(&anchors, allowed.as_deref(), ekus.as_deref())?
+        };
+        if self.revocation.ocsp_fetch {
+            return settings
+                .with_toml("[verify]\nocsp_fetch = true\n")
+                .map_err(|e| format_err!("Failed to enable OCSP fetching: {}", e));
+        }
+        Ok(settings)
+    }
+}
+
+/// Read a PEM file and check that it contains at least one certificate.
+fn read_pem_file(path: &Path) -> Result<String> {
+    let pem = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read certificate file {:?}", path))?;
+    if pem_certificates(&pem).is_empty() {
+        bail!("No PEM certificates found in {:?}", path);
+    }
+    Ok(pem)
+}
+
+/// DER bytes of every `CERTIFICATE` block in a PEM bundle. Malformed blocks are skipped.
+pub fn pem_certificates(pem: &str) -> Vec<Vec<u8>> {
+    const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    const END: &str = "-----END CERTIFICATE-----";
+    let mut certs = Vec::new();
+    let mut rest = pem;
+    while let Some(start) = rest.find(BEGIN) {
+        let body = &rest[start + BEGIN.len()..];
+        let Some(end) = body.find(END) else {
+            break;
+        };
+        let b64: String = body[..end].split_whitespace().collect();
+        if let Ok(der) = base64::engine::general_purpose::STANDARD.decode(b64) {
+            certs.push(der);
+        }
+        rest = &body[end + END.len()..];
+    }
+    certs
+}
+
+/// SHA-256 fingerprints (lowercase hex) of the certificates in a PEM bundle.
+pub fn certificate_fingerprints(pem: &str) -> Vec<String> {
+    pem_certificates(pem)
+        .iter()
+        .map(|der| hash_bytes(der, HashAlgorithm::Sha256))
+        .collect()
+}
+
+/// The per-user crTool config directory: `%APPDATA%\crTool` on Windows,
+/// `~/Library/Application Support/crTool` on macOS, and `$XDG_CONFIG_HOME/crTool` (default
+/// `~/.config/crTool`) elsewhere. Falls back to the temp directory without a home directory.
+pub fn user_config_dir() -> PathBuf {
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|h| h.join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|h| h.join(".config")))
+    };
+    base.unwrap_or_else(std::env::temp_dir).join("crTool")
+}
+
+/// The per-user crTool cache directory: `%LOCALAPPDATA%\crTool` on Windows,
+/// `~/Library/Caches/crTool` on macOS, and `$XDG_CACHE_HOME/crTool` (default `~/.cache/crTool`)
+/// elsewhere. Falls back to the temp directory without a home directory.
+pub fn user_cache_dir() -> PathBuf {
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|h| h.join("Library").join("Caches"))
+    } else {
+        std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|h| h.join(".cache")))
+    };
+    base.unwrap_or_else(std::env::temp_dir).join("crTool")
+}
+
+/// Combine allowed lists for the SDK. An allowed list is a PEM bundle or a hash list (one
+/// base64 SHA-256 certificate hash per line). PEM bundles are concatenated as they are; when any
+/// list is a hash list, the certificates of the PEM bundles are added to it as hashes.
+pub fn merge_allowed_lists(lists: &[String]) -> String {
+    let is_pem = |list: &String| !pem_certificates(list).is_empty();
+    if lists.iter().all(is_pem) {
+        return lists
+            .iter()
+            .map(|l| l.trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let mut lines = Vec::new();
+    for list in lists {
+        if is_pem(list) {
+            lines.extend(
+                pem_certificates(list)
+                    .iter()
+                    .map(|der| b64.encode(Sha256::digest(der))),
+            );
+        } else {
+            lines.extend(
+                list.lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pem_certificates() {
+        let der = b"not really a certificate";
+        let pem = format!(
+            "junk\n{}\n{}",
+            crate::cose::der_to_pem("CERTIFICATE", der),
+            crate::cose::der_to_pem("PRIVATE KEY", b"key")
+        );
+        assert_eq!(pem_certificates(&pem), vec![der.to_vec()]);
+        assert_eq!(
+            certificate_fingerprints(&pem),
+            vec![hash_bytes(der, HashAlgorithm::Sha256)]
+        );
+        assert!(pem_certificates("no certificates here").is_empty());
+    }
+
+    #[test]
+    fn test_merge_allowed_lists() {
+        let pem = crate::cose::der_to_pem("CERTIFICATE", b"cert");
+        let hash = base64::engine::general_purpose::STANDARD.encode(Sha256::digest(b"cert"));
+        assert_eq!(
+            merge_allowed_lists(&[pem.clone(), pem.clone()]),
+            format!("{}\n{}", pem.trim(), pem.trim())
+        );
+        assert_eq!(
+            merge_allowed_lists(&[pem, "# local\nAAAA\n\n".to_string()]),
+            format!("{}\nAAAA", hash)
+        );
+    }
+
+    #[test]
+    fn test_trust_config_defaults() {
+        let config: TrustConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, TrustConfig::default());
+        assert!(config.use_default_lists);
+
+        let config: TrustConfig =
+            serde_json::from_str(r#"{"useDefaultLists": false, "anchorFiles": ["a.pem"]}"#)
+                .unwrap();
+        assert!(!config.use_default_lists);
+        assert_eq!(config.anchor_files, vec![PathBuf::from("a.pem")]);
+    }
+
+    #[test]
+    fn test_trust_config_toml() {
+        let dir = std::env::temp_dir().join(format!("crtool-trust-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("trust.toml");
+        fs::write(
+            &path,
+            r#"
+useDefaultLists = false
+anchorFiles = ["anchors/root.pem"]
+allowedHashes = ["AAAA"]
+allowedEkus = ["1.3.6.1.5.5.7.3.4"]
+
+[revocation]
+ocspFetch = true
+"#,
+        )
+        .unwrap();
+        let config = TrustConfig::load(&path).unwrap();
+        assert!(!config.use_default_lists);
+        assert_eq!(config.anchor_files, vec![dir.join("anchors/root.pem")]);
+        assert_eq!(config.allowed_hashes, vec!["AAAA"]);
+        assert_eq!(config.allowed_ekus, vec!["1.3.6.1.5.5.7.3.4"]);
+        assert!(config.revocation.ocsp_fetch);
+
+        let json = dir.join("trust.json");
+        config.save(&json).unwrap();
+        assert_eq!(TrustConfig::load(&json).unwrap(), config);
+
+        let hashes = dir.join("allowed.sha256");
+        fs::write(&hashes, "# newsroom signers\nBBBB\n\n").unwrap();
+        let mut config = TrustConfig::default();
+        assert!(config.is_empty());
+        config.add_allowed_list(&hashes).unwrap();
+        assert_eq!(config.allowed_hashes, vec!["BBBB"]);
+        assert!(config.allowed_files.is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_missing_anchor_file_is_an_error() {
+        let config = TrustConfig {
+            use_default_lists: false,
+            anchor_files: vec![PathBuf::from("/nonexistent/anchors.pem")],
+            ..Default::default()
+        };
+        assert!(config.settings(None).is_err());
+    }
+}
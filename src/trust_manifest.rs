@@ -0,0 +1,306 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! JPEG Trust "Trust Manifest" assessment: compare declared expectations against the trust
+//! indicators of an extracted crJSON document and report conformance per clause.
+//!
+//! A Trust Manifest is JSON of the form:
+//!
+//! ```json
+//! {
+//!   "name": "Newsroom photo policy",
+//!   "clauses": [
+//!     { "id": "4.1", "description": "Signed with ES256",
+//!       "indicator": "/activeManifest/signature/algorithm", "equals": "ES256" }
+//!   ]
+//! }
+//! ```
+//!
+//! `indicator` is a JSON Pointer into the crJSON; a leading `/activeManifest` resolves to the
+//! active manifest's entry in `manifests`. Every expectation given on a clause must hold.
+
+use crate::error::{Context, Result};
+use crate::ManifestExtractionResult;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::path::Path;
+
+/// Pointer prefix resolved to the active manifest object.
+const ACTIVE_MANIFEST_PREFIX: &str = "/activeManifest";
+
+/// Declared expectations, as loaded from a Trust Manifest JSON file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrustManifest {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub clauses: Vec<TrustClause>,
+}
+
+/// One clause of a Trust Manifest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustClause {
+    pub id: String,
+    pub description: Option<String>,
+    /// JSON Pointer to the indicator the clause is about.
+    pub indicator: String,
+    /// When false, a missing indicator makes the clause not applicable instead of failing it.
+    #[serde(default = "default_required")]
+    pub required: bool,
+    /// The indicator must (or, with `false`, must not) be present.
+    pub exists: Option<bool>,
+    pub equals: Option<JsonValue>,
+    pub not_equals: Option<JsonValue>,
+    /// The indicator must equal one of these values.
+    pub one_of: Option<Vec<JsonValue>>,
+    /// String indicator: substring match. Array indicator: some element equals the value.
+    pub contains: Option<JsonValue>,
+    /// Array indicator: minimum number of elements.
+    pub min_items: Option<usize>,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// Outcome of one clause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ClauseStatus {
+    Pass,
+    Fail,
+    NotApplicable,
+}
+
+/// Result of assessing one clause.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClauseResult {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub indicator: String,
+    pub status: ClauseStatus,
+    /// Indicator value found in the crJSON, if any.
+    pub actual: Option<JsonValue>,
+    /// Why the clause failed or was not applicable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Conformance report for one asset against one Trust Manifest.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConformanceReport {
+    pub trust_manifest: Option<String>,
+    pub asset: String,
+    pub active_manifest: String,
+    pub conforms: bool,
+    pub passed: usize,
+    pub failed: usize,
+    pub not_applicable: usize,
+    pub clauses: Vec<ClauseResult>,
+}
+
+/// Load and parse a Trust Manifest JSON file.
+pub fn load_trust_manifest<P: AsRef<Path>>(path: P) -> Result<TrustManifest> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read trust manifest: {:?}", path))?;
+    serde_json::from_str(&text)
+        .with_context(|| format!("Failed to parse trust manifest: {:?}", path))
+}
+
+/// Resolve an indicator pointer against the crJSON document.
+pub(crate) fn resolve_indicator<'a>(
+    crjson: &'a JsonValue,
+    active_label: &str,
+    pointer: &str,
+) -> Option<&'a JsonValue> {
+    match pointer.strip_prefix(ACTIVE_MANIFEST_PREFIX) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            let active = crjson
+                .get("manifests")?
+                .as_array()?
+                .iter()
+                .find(|m| m.get("label").and_then(|l| l.as_str()) == Some(active_label))?;
+            active.pointer(rest)
+        }
+        _ => crjson.pointer(pointer),
+    }
+}
+
+/// Check every expectation on `clause` against `actual`; returns the first violation.
+pub(crate) fn check_clause(clause: &TrustClause, actual: Option<&JsonValue>) -> Option<String> {
+    let Some(actual) = actual else {
+        return match clause.exists {
+            Some(false) => None,
+            _ => Some("indicator not present".to_string()),
+        };
+    };
+    if clause.exists == Some(false) {
+        return Some("indicator is present but must not be".to_string());
+    }
+    if let Some(expected) = &clause.equals {
+        if actual != expected {
+            return Some(format!("expected {}, found {}", expected, actual));
+        }
+    }
+    if let Some(unexpected) = &clause.not_equals {
+        if actual == unexpected {
+            return Some(format!("must not equal {}", unexpected));
+        }
+    }
+    if let Some(options) = &clause.one_of {
+        if !options.contains(actual) {
+            return Some(format!(
+                "{} is not one of {}",
+                actual,
+                JsonValue::Array(options.clone())
+            ));
+        }
+    }
+    if let Some(needle) = &clause.contains {
+        let found = match (actual, needle) {
+            (JsonValue::String(s), JsonValue::String(n)) => s.contains(n.as_str()),
+            (JsonValue::Array(items), n) => items.contains(n),
+            _ => false,
+        };
+        if !found {
+            return Some(format!("does not contain {}", needle));
+        }
+    }
+    if let Some(min) = clause.min_items {
+        let len = actual.as_array().map(|a| a.len()).unwrap_or(0);
+        if len < min {
+            return Some(format!("expected at least {} item(s), found {}", min, len));
+        }
+    }
+    None
+}
+
+/// Assess the crJSON `indicators` (with `active_label`) against `tm`.
+pub fn assess(
+    tm: &TrustManifest,
+    indicators: &JsonValue,
+    active_label: &str,
+    asset: &str,
+) -> ConformanceReport {
+    let clauses: Vec<ClauseResult> = tm
+        .clauses
+        .iter()
+        .map(|clause| {
+            let actual = resolve_indicator(indicators, active_label, &clause.indicator);
+            let (status, message) = match check_clause(clause, actual) {
+                None => (ClauseStatus::Pass, None),
+                Some(msg) if actual.is_none() && !clause.required => {
+                    (ClauseStatus::NotApplicable, Some(msg))
+                }
+                Some(msg) => (ClauseStatus::Fail, Some(msg)),
+            };
+            ClauseResult {
+                id: clause.id.clone(),
+                description: clause.description.clone(),
+                indicator: clause.indicator.clone(),
+                status,
+                actual: actual.cloned(),
+                message,
+            }
+        })
+        .collect();
+
+    let count = |s: ClauseStatus| clauses.iter().filter(|c| c.status == s).count();
+    let failed = count(ClauseStatus::Fail);
+    ConformanceReport {
+        trust_manifest: tm.name.clone(),
+        asset: asset.to_string(),
+        active_manifest: active_label.to_string(),
+        conforms: failed == 0,
+        passed: count(ClauseStatus::Pass),
+        failed,
+        not_applicable: count(ClauseStatus::NotApplicable),
+        clauses,
+    }
+}
+
+/// Assess an extraction result against `tm`.
+pub fn assess_extraction(
+    tm: &TrustManifest,
+    result: &ManifestExtractionResult,
+) -> ConformanceReport {
+    assess(
+        tm,
+        &result.manifest_value,
+        &result.active_label,
+        &result.input_path,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crjson() -> JsonValue {
+        serde_json::json!({
+            "manifests": [
+                { "label": "urn:c2pa:old", "signature": { "algorithm": "PS256" } },
+                {
+                    "label": "urn:c2pa:active",
+                    "signature": { "algorithm": "ES256" },
+                    "assertions": { "c2pa.actions.v2": { "actions": [{ "action": "c2pa.created" }] } }
+                }
+            ]
+        })
+    }
+
+    fn manifest(clauses: JsonValue) -> TrustManifest {
+        serde_json::from_value(serde_json::json!({ "name": "test", "clauses": clauses })).unwrap()
+    }
+
+    #[test]
+    fn test_assess_active_manifest_clauses() {
+        let tm = manifest(serde_json::json!([
+            { "id": "1", "indicator": "/activeManifest/signature/algorithm", "equals": "ES256" },
+            { "id": "2", "indicator": "/activeManifest/signature/algorithm", "oneOf": ["PS256"] },
+            { "id": "3", "indicator": "/activeManifest/assertions/c2pa.actions.v2/actions", "minItems": 1 },
+            { "id": "4", "indicator": "/activeManifest/assertions/c2pa.training-mining", "required": false, "exists": true },
+            { "id": "5", "indicator": "/manifests", "minItems": 3 }
+        ]));
+        let report = assess(&tm, &crjson(), "urn:c2pa:active", "a.jpg");
+        let statuses: Vec<_> = report.clauses.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ClauseStatus::Pass,
+                ClauseStatus::Fail,
+                ClauseStatus::Pass,
+                ClauseStatus::NotApplicable,
+                ClauseStatus::Fail
+            ]
+        );
+        assert!(!report.conforms);
+        assert_eq!(
+            (report.passed, report.failed, report.not_applicable),
+            (2, 2, 1)
+        );
+    }
+
+    #[test]
+    fn test_assess_exists_false_and_contains() {
+        let tm = manifest(serde_json::json!([
+            { "id": "a", "indicator": "/activeManifest/ingredients", "exists": false },
+            { "id": "b", "indicator": "/activeManifest/label", "contains": "active" }
+        ]));
+        let report = assess(&tm, &crjson(), "urn:c2pa:active", "a.jpg");
+        assert!(report.conforms, "{:?}", report.clauses);
+    }
+}
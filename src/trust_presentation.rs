@@ -0,0 +1,259 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! How trust statuses are shown: the label, icon, color and description for each status
+//! string (`signingCredential.trusted`, `untrusted`, or an organization's own `status.trust`
+//! values), shared by the GUI and the CLI summaries.
+//!
+//! Built-in mappings cover the C2PA codes and crTool's `trusted` / `untrusted` / `unknown`.
+//! A JSON file (`CRTOOL_TRUST_PRESENTATION`, else `trust-presentation.json` in the crTool config
+//! directory) adds or overrides mappings:
+//!
+//! ```json
+//! { "statuses": { "com.example.reviewed": {
+//!     "label": "Reviewed", "icon": "🏢", "color": "green",
+//!     "description": "Checked by the newsroom's review desk" } } }
+//! ```
+
+use crate::error::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Environment variable naming the trust presentation file.
+pub const TRUST_PRESENTATION_ENV: &str = "CRTOOL_TRUST_PRESENTATION";
+
+/// How one trust status is shown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustPresentation {
+    /// Short text shown instead of the raw status.
+    pub label: String,
+    /// Emoji or symbol shown before the label.
+    #[serde(default)]
+    pub icon: String,
+    /// `green`, `amber`, `red`, `gray`, or a `#rrggbb` hex color.
+    pub color: String,
+    /// Longer explanation (GUI tooltip).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl TrustPresentation {
+    fn new(label: &str, icon: &str, color: &str, description: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            icon: icon.to_string(),
+            color: color.to_string(),
+            description: Some(description.to_string()),
+        }
+    }
+
+    /// The color as RGB. Unrecognized colors are gray.
+    pub fn rgb(&self) -> [u8; 3] {
+        parse_color(&self.color).unwrap_or(GRAY)
+    }
+
+    /// `icon label`, or just the label without an icon.
+    pub fn text(&self) -> String {
+        if self.icon.is_empty() {
+            self.label.clone()
+        } else {
+            format!("{} {}", self.icon, self.label)
+        }
+    }
+}
+
+const GREEN: [u8; 3] = [0, 100, 0];
+const AMBER: [u8; 3] = [230, 140, 0];
+const RED: [u8; 3] = [255, 100, 100];
+const GRAY: [u8; 3] = [64, 64, 64];
+
+/// `green`, `amber`, `red`, `gray` (or `grey`), or `#rrggbb`.
+pub fn parse_color(color: &str) -> Option<[u8; 3]> {
+    match color.trim().to_ascii_lowercase().as_str() {
+        "green" => Some(GREEN),
+        "amber" | "orange" | "yellow" => Some(AMBER),
+        "red" => Some(RED),
+        "gray" | "grey" => Some(GRAY),
+        hex => {
+            let hex = hex.strip_prefix('#')?;
+            if hex.len() != 6 || !hex.is_ascii() {
+                return None;
+            }
+            let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            Some([byte(0)?, byte(2)?, byte(4)?])
+        }
+    }
+}
+
+/// Status → presentation mappings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustPresentations {
+    /// Mappings keyed by the exact status string.
+    #[serde(default)]
+    pub statuses: BTreeMap<String, TrustPresentation>,
+}
+
+impl Default for TrustPresentations {
+    fn default() -> Self {
+        let trusted = TrustPresentation::new(
+            "Trusted",
+            "🔒",
+            "green",
+            "The signing certificate chains to a trust anchor or is on the allowed list",
+        );
+        let untrusted = TrustPresentation::new(
+            "Untrusted",
+            "🚫",
+            "red",
+            "The signing certificate is not on any of the trust lists in use",
+        );
+        let unknown = TrustPresentation::new(
+            "Not checked",
+            "⚠️",
+            "amber",
+            "No trust list was applied, so the signer's trust is unknown",
+        );
+        let statuses = [
+            ("signingCredential.trusted", trusted.clone()),
+            ("trusted", trusted),
+            ("signingCredential.untrusted", untrusted.clone()),
+            ("untrusted", untrusted),
+            ("unknown", unknown),
+        ];
+        Self {
+            statuses: statuses
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        }
+    }
+}
+
+impl TrustPresentations {
+    /// Built-in mappings with those of the JSON file at `path` added (overriding built-ins with
+    /// the same status).
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read trust presentation {:?}", path))?;
+        let file: TrustPresentations = serde_json::from_str(&text)
+            .with_context(|| format!("Invalid trust presentation {:?}", path))?;
+        for (status, p) in &file.statuses {
+            if parse_color(&p.color).is_none() {
+                bail!(
+                    "Invalid color {:?} for trust status {:?} in {:?}",
+                    p.color,
+                    status,
+                    path
+                );
+            }
+        }
+        let mut presentations = Self::default();
+        presentations.statuses.extend(file.statuses);
+        Ok(presentations)
+    }
+
+    /// Mappings from [`default_path`] when that file exists, else the built-ins.
+    pub fn load_default() -> Result<Self> {
+        let path = default_path();
+        if path.exists() {
+            Self::load(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Presentation of `status`. Unmapped statuses show as themselves, in amber.
+    pub fn get(&self, status: &str) -> TrustPresentation {
+        self.statuses.get(status).cloned().unwrap_or_else(|| {
+            TrustPresentation::new(
+                status,
+                "⚠️",
+                "amber",
+                "Trust status without a configured presentation",
+            )
+        })
+    }
+}
+
+/// `$CRTOOL_TRUST_PRESENTATION`, else `trust-presentation.json` in [`crate::trust::user_config_dir`].
+pub fn default_path() -> PathBuf {
+    std::env::var_os(TRUST_PRESENTATION_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| crate::trust::user_config_dir().join("trust-presentation.json"))
+}
+
+static PRESENTATIONS: RwLock<Option<TrustPresentations>> = RwLock::new(None);
+
+/// Use `presentations` for all trust status display in this process.
+pub fn set_trust_presentations(presentations: TrustPresentations) {
+    if let Ok(mut current) = PRESENTATIONS.write() {
+        *current = Some(presentations);
+    }
+}
+
+/// Presentation of `status` under the mappings in force (the built-ins unless changed).
+pub fn trust_presentation(status: &str) -> TrustPresentation {
+    match PRESENTATIONS.read().ok().as_deref() {
+        Some(Some(presentations)) => presentations.get(status),
+        _ => TrustPresentations::default().get(status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trust_presentations() {
+        assert_eq!(parse_color("#2E7D32"), Some([0x2e, 0x7d, 0x32]));
+        assert_eq!(parse_color("Amber"), Some(AMBER));
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("teal"), None);
+
+        let defaults = TrustPresentations::default();
+        assert_eq!(defaults.get("signingCredential.trusted").rgb(), GREEN);
+        assert_eq!(defaults.get("untrusted").text(), "🚫 Untrusted");
+        let unmapped = defaults.get("com.example.reviewed");
+        assert_eq!(unmapped.label, "com.example.reviewed");
+        assert_eq!(unmapped.rgb(), AMBER);
+
+        let path = std::env::temp_dir().join(format!(
+            "crtool-trust-presentation-{}.json",
+            std::process::id()
+        ));
+        fs::write(
+            &path,
+            r##"{ "statuses": {
+                "com.example.reviewed": { "label": "Reviewed", "icon": "🏢", "color": "#2e7d32" },
+                "untrusted": { "label": "Unknown signer", "color": "amber" }
+            } }"##,
+        )
+        .unwrap();
+        let loaded = TrustPresentations::load(&path).unwrap();
+        assert_eq!(loaded.get("com.example.reviewed").text(), "🏢 Reviewed");
+        assert_eq!(loaded.get("untrusted").text(), "Unknown signer");
+        assert_eq!(loaded.get("trusted").label, "Trusted");
+
+        fs::write(
+            &path,
+            r#"{ "statuses": { "x": { "label": "X", "color": "teal" } } }"#,
+        )
+        .unwrap();
+        assert!(TrustPresentations::load(&path).is_err());
+        let _ = fs::remove_file(&path);
+    }
+}
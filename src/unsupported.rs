@@ -0,0 +1,248 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Detection of manifest features newer than this build understands (future claim versions,
+//! unknown assertion versions, unsupported algorithms), so they can be reported as
+//! "unsupported feature" findings rather than as a blanket failure.
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+/// Latest C2PA specification version whose claim and assertion versions are known here.
+pub const LATEST_KNOWN_SPEC: &str = "2.2";
+
+/// Highest claim version understood (`claim` is v1, `claim.v2` is C2PA 2.x).
+const MAX_CLAIM_VERSION: u32 = 2;
+
+/// Assertion families with versioned labels, and the highest version understood.
+const KNOWN_ASSERTION_VERSIONS: &[(&str, u32)] = &[
+    ("c2pa.actions", 2),
+    ("c2pa.ingredient", 3),
+    ("c2pa.hash.data", 1),
+    ("c2pa.hash.bmff", 3),
+    ("c2pa.hash.boxes", 1),
+    ("c2pa.hash.collection.data", 1),
+    ("c2pa.soft-binding", 1),
+    ("c2pa.cloud-data", 1),
+    ("c2pa.metadata", 1),
+];
+
+/// What kind of unsupported feature was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnsupportedKind {
+    ClaimVersion,
+    AssertionVersion,
+    Algorithm,
+    Other,
+}
+
+/// One feature the SDK or crTool does not support.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsupportedFeature {
+    pub kind: UnsupportedKind,
+    /// Label of the manifest the feature was found in, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<String>,
+    /// What was found (claim key, assertion label, status code, or SDK message).
+    pub found: String,
+    /// Highest version understood by this build, for versioned features.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supported: Option<String>,
+    /// Specification version the feature likely needs.
+    pub likely_spec: String,
+}
+
+impl std::fmt::Display for UnsupportedFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            UnsupportedKind::ClaimVersion => "claim version",
+            UnsupportedKind::AssertionVersion => "assertion version",
+            UnsupportedKind::Algorithm => "algorithm",
+            UnsupportedKind::Other => "feature",
+        };
+        write!(f, "unsupported {}: {}", kind, self.found)?;
+        if let Some(supported) = &self.supported {
+            write!(f, " (supported up to {})", supported)?;
+        }
+        write!(f, "; likely needs {}", self.likely_spec)
+    }
+}
+
+fn newer_spec() -> String {
+    format!("C2PA newer than {}", LATEST_KNOWN_SPEC)
+}
+
+/// Split `label.vN` (ignoring a `__N` instance suffix) into the base label and version.
+fn split_version(label: &str) -> (&str, u32) {
+    let label = label.split("__").next().unwrap_or(label);
+    match label.rsplit_once(".v") {
+        Some((base, v)) if !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()) => {
+            (base, v.parse().unwrap_or(1))
+        }
+        _ => (label, 1),
+    }
+}
+
+fn status_codes(manifest: &JsonValue) -> impl Iterator<Item = &str> {
+    let results = manifest.get("validationResults");
+    ["failure", "informational", "success"]
+        .into_iter()
+        .filter_map(move |key| results?.get(key)?.as_array())
+        .flatten()
+        .filter_map(|e| e.get("code").and_then(|c| c.as_str()))
+}
+
+/// Find unsupported features in an extracted crJSON document.
+pub fn detect_unsupported(crjson: &JsonValue) -> Vec<UnsupportedFeature> {
+    let mut found = Vec::new();
+    let manifests = crjson
+        .get("manifests")
+        .and_then(|m| m.as_array())
+        .map(|m| m.as_slice())
+        .unwrap_or_default();
+
+    for manifest in manifests {
+        let label = manifest
+            .get("label")
+            .and_then(|l| l.as_str())
+            .map(str::to_string);
+
+        if let Some(obj) = manifest.as_object() {
+            for key in obj.keys().filter(|k| k.starts_with("claim.v")) {
+                let (_, version) = split_version(key);
+                if version > MAX_CLAIM_VERSION {
+                    found.push(UnsupportedFeature {
+                        kind: UnsupportedKind::ClaimVersion,
+                        manifest: label.clone(),
+                        found: key.clone(),
+                        supported: Some(format!("claim.v{}", MAX_CLAIM_VERSION)),
+                        likely_spec: newer_spec(),
+                    });
+                }
+            }
+        }
+
+        if let Some(assertions) = manifest.get("assertions").and_then(|a| a.as_object()) {
+            for key in assertions.keys() {
+                let (base, version) = split_version(key);
+                let Some((_, max)) = KNOWN_ASSERTION_VERSIONS.iter().find(|(b, _)| *b == base)
+                else {
+                    continue;
+                };
+                if version > *max {
+                    found.push(UnsupportedFeature {
+                        kind: UnsupportedKind::AssertionVersion,
+                        manifest: label.clone(),
+                        found: key.clone(),
+                        supported: Some(if *max == 1 {
+                            base.to_string()
+                        } else {
+                            format!("{}.v{}", base, max)
+                        }),
+                        likely_spec: newer_spec(),
+                    });
+                }
+            }
+        }
+
+        for code in status_codes(manifest) {
+            let kind = if code == "algorithm.unsupported" {
+                UnsupportedKind::Algorithm
+            } else if code.contains("unsupported") || code.contains("unknownVersion") {
+                UnsupportedKind::Other
+            } else {
+                continue;
+            };
+            let feature = UnsupportedFeature {
+                kind,
+                manifest: label.clone(),
+                found: code.to_string(),
+                supported: None,
+                likely_spec: newer_spec(),
+            };
+            if !found.contains(&feature) {
+                found.push(feature);
+            }
+        }
+    }
+    found
+}
+
+/// Recognize an SDK read error caused by an unsupported feature (e.g. a future claim version).
+/// Returns `None` for ordinary failures such as a missing manifest.
+pub fn unsupported_from_error(message: &str) -> Option<UnsupportedFeature> {
+    let lower = message.to_lowercase();
+    let kind = if lower.contains("algorithm") && lower.contains("unsupported") {
+        UnsupportedKind::Algorithm
+    } else if lower.contains("claim") && lower.contains("version") {
+        UnsupportedKind::ClaimVersion
+    } else if lower.contains("assertion") && lower.contains("version") {
+        UnsupportedKind::AssertionVersion
+    } else if lower.contains("unsupported") && !lower.contains("unsupported type") {
+        UnsupportedKind::Other
+    } else {
+        return None;
+    };
+    Some(UnsupportedFeature {
+        kind,
+        manifest: None,
+        found: message.to_string(),
+        supported: None,
+        likely_spec: newer_spec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_unsupported() {
+        let crjson = serde_json::json!({
+            "manifests": [{
+                "label": "urn:c2pa:a",
+                "claim.v3": {},
+                "assertions": {
+                    "c2pa.actions.v2": {},
+                    "c2pa.actions.v4": {},
+                    "c2pa.ingredient.v3__1": {},
+                    "c2pa.hash.data": {},
+                    "com.example.custom.v9": {}
+                },
+                "validationResults": {
+                    "failure": [{ "code": "algorithm.unsupported" }, { "code": "assertion.dataHash.mismatch" }]
+                }
+            }]
+        });
+        let found = detect_unsupported(&crjson);
+        let summary: Vec<_> = found.iter().map(|f| (f.kind, f.found.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (UnsupportedKind::ClaimVersion, "claim.v3"),
+                (UnsupportedKind::AssertionVersion, "c2pa.actions.v4"),
+                (UnsupportedKind::Algorithm, "algorithm.unsupported"),
+            ]
+        );
+        assert_eq!(found[1].supported.as_deref(), Some("c2pa.actions.v2"));
+    }
+
+    #[test]
+    fn test_unsupported_from_error() {
+        let e = unsupported_from_error("claim version 3 is not supported").unwrap();
+        assert_eq!(e.kind, UnsupportedKind::ClaimVersion);
+        assert!(unsupported_from_error("No C2PA manifest found").is_none());
+        assert!(unsupported_from_error("Unsupported type: text/plain").is_none());
+    }
+}
@@ -0,0 +1,254 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Update check against a signed releases feed. The feed is a JSON document listing releases;
+//! next to it (`<feed url>.sig`) is a base64 Ed25519 signature over the exact feed bytes.
+//! Fetching is left to the CLI and GUI; this module verifies the feed and compares versions.
+//!
+//! The feed is verified with a key pinned in the binary when it is built: release builds set
+//! `CRTOOL_UPDATE_FEED_KEY` (and optionally `CRTOOL_UPDATE_FEED_URL`), and organizations that
+//! redistribute crTool build with their own. A build without a key cannot check for updates. At
+//! run time only the URL can be overridden: a key from the environment would let anyone who can
+//! set it vouch for their own feed.
+
+use crate::error::{bail, format_err, Context, Result};
+use base64::Engine;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Environment variable overriding the releases feed URL.
+pub const UPDATE_FEED_URL_ENV: &str = "CRTOOL_UPDATE_FEED_URL";
+/// Build-time environment variable with the feed's Ed25519 public key (base64, 32 bytes).
+pub const UPDATE_FEED_KEY_ENV: &str = "CRTOOL_UPDATE_FEED_KEY";
+/// Environment variable that, when set to `1` or `true`, disables network access for updates.
+pub const OFFLINE_ENV: &str = "CRTOOL_OFFLINE";
+
+/// Releases feed published with crTool's GitHub releases.
+pub const DEFAULT_UPDATE_FEED_URL: &str =
+    "https://github.com/lrosenthol/crTool/releases/latest/download/releases.json";
+
+/// Ed25519 public key (base64) the releases feed must be signed with, pinned at build time from
+/// `CRTOOL_UPDATE_FEED_KEY`. `None` in builds made without it.
+pub const PINNED_UPDATE_FEED_KEY: Option<&str> = option_env!("CRTOOL_UPDATE_FEED_KEY");
+
+/// Where to find the releases feed and the key that signs it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateFeedConfig {
+    pub url: String,
+    /// Base64 Ed25519 public key the feed must be signed with.
+    pub public_key: String,
+}
+
+impl UpdateFeedConfig {
+    /// The URL from the run-time environment, else the one baked in at build time, else the
+    /// default. The key is always the one compiled in ([`PINNED_UPDATE_FEED_KEY`]); fails when
+    /// the binary was built without one.
+    pub fn from_env() -> Result<Self> {
+        Self::with_pinned_key(PINNED_UPDATE_FEED_KEY)
+    }
+
+    fn with_pinned_key(pinned_key: Option<&str>) -> Result<Self> {
+        let Some(public_key) = pinned_key.map(str::trim).filter(|k| !k.is_empty()) else {
+            bail!(
+                "This build of crTool cannot check for updates: it was built without an update \
+                 feed key (set {} when building)",
+                UPDATE_FEED_KEY_ENV
+            );
+        };
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Ok(UpdateFeedConfig {
+            url: env(UPDATE_FEED_URL_ENV)
+                .or_else(|| option_env!("CRTOOL_UPDATE_FEED_URL").map(str::to_string))
+                .unwrap_or_else(|| DEFAULT_UPDATE_FEED_URL.to_string()),
+            public_key: public_key.to_string(),
+        })
+    }
+
+    /// URL of the detached signature for the feed.
+    pub fn signature_url(&self) -> String {
+        format!("{}.sig", self.url)
+    }
+}
+
+/// True when `CRTOOL_OFFLINE` asks for no network access.
+pub fn offline_from_env() -> bool {
+    std::env::var(OFFLINE_ENV)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// One published release.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Release {
+    pub version: String,
+    /// Download or release-notes page.
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReleasesFeed {
+    releases: Vec<Release>,
+}
+
+/// Result of comparing the running version with the feed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum UpdateStatus {
+    #[serde(rename_all = "camelCase")]
+    UpToDate { current: String },
+    #[serde(rename_all = "camelCase")]
+    Available { current: String, latest: Release },
+}
+
+/// Verify the feed's detached signature and parse its releases.
+pub fn verify_feed(feed: &[u8], signature_b64: &str, public_key_b64: &str) -> Result<Vec<Release>> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let key_bytes: [u8; 32] = b64
+        .decode(public_key_b64.trim())
+        .context("Update feed public key is not valid base64")?
+        .try_into()
+        .map_err(|_| format_err!("Update feed public key must be 32 bytes"))?;
+    let key = VerifyingKey::from_bytes(&key_bytes).context("Invalid update feed public key")?;
+    let sig_bytes = b64
+        .decode(signature_b64.trim())
+        .context("Update feed signature is not valid base64")?;
+    let signature =
+        Signature::from_slice(&sig_bytes).context("Update feed signature must be 64 bytes")?;
+    key.verify(feed, &signature)
+        .map_err(|_| format_err!("Update feed signature does not verify"))?;
+    let parsed: ReleasesFeed =
+        serde_json::from_slice(feed).context("Update feed is not a valid releases document")?;
+    Ok(parsed.releases)
+}
+
+/// Numeric components of a dotted version (`v1.2.3-beta` → [1, 2, 3]).
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()
+        .unwrap_or("")
+        .split('.')
+        .map(|p| p.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Compare dotted versions numerically; missing components count as 0.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (version_parts(a), version_parts(b));
+    (0..a.len().max(b.len()))
+        .map(|i| {
+            a.get(i)
+                .copied()
+                .unwrap_or(0)
+                .cmp(&b.get(i).copied().unwrap_or(0))
+        })
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Verify a fetched feed and decide whether a newer release than `current` exists.
+pub fn check_feed(
+    current: &str,
+    feed: &[u8],
+    signature_b64: &str,
+    public_key_b64: &str,
+) -> Result<UpdateStatus> {
+    let releases = verify_feed(feed, signature_b64, public_key_b64)?;
+    let latest = releases
+        .into_iter()
+        .max_by(|a, b| compare_versions(&a.version, &b.version));
+    Ok(match latest {
+        Some(latest) if compare_versions(&latest.version, current).is_gt() => {
+            UpdateStatus::Available {
+                current: current.to_string(),
+                latest,
+            }
+        }
+        _ => UpdateStatus::UpToDate {
+            current: current.to_string(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed_feed(feed: &str) -> (String, String) {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let b64 = base64::engine::general_purpose::STANDARD;
+        (
+            b64.encode(key.sign(feed.as_bytes()).to_bytes()),
+            b64.encode(key.verifying_key().to_bytes()),
+        )
+    }
+
+    #[test]
+    fn test_runtime_key_does_not_replace_pinned_key() {
+        let (_, key) = signed_feed("{}");
+        std::env::set_var(UPDATE_FEED_KEY_ENV, &key);
+        let config = UpdateFeedConfig::from_env();
+        let unpinned = UpdateFeedConfig::with_pinned_key(None);
+        std::env::remove_var(UPDATE_FEED_KEY_ENV);
+
+        match PINNED_UPDATE_FEED_KEY {
+            Some(pinned) => assert_eq!(config.unwrap().public_key, pinned.trim()),
+            None => assert!(config.is_err()),
+        }
+        let err = unpinned.unwrap_err().to_string();
+        assert!(err.contains(UPDATE_FEED_KEY_ENV), "{err}");
+        assert!(UpdateFeedConfig::with_pinned_key(Some("  ")).is_err());
+        assert_eq!(
+            UpdateFeedConfig::with_pinned_key(Some(&key))
+                .unwrap()
+                .public_key,
+            key
+        );
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("0.3.0", "0.3"), Ordering::Equal);
+        assert_eq!(compare_versions("v0.10.0", "0.9.9"), Ordering::Greater);
+        assert_eq!(compare_versions("0.3.0-beta", "0.3.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_check_feed() {
+        let feed = r#"{"releases":[
+            {"version":"0.3.0","url":"https://example.com/0.3.0"},
+            {"version":"0.4.1","url":"https://example.com/0.4.1","notes":"Fixes"}
+        ]}"#;
+        let (sig, key) = signed_feed(feed);
+
+        match check_feed("0.3.0", feed.as_bytes(), &sig, &key).unwrap() {
+            UpdateStatus::Available { latest, .. } => assert_eq!(latest.version, "0.4.1"),
+            other => panic!("expected an update, got {:?}", other),
+        }
+        assert!(matches!(
+            check_feed("0.4.1", feed.as_bytes(), &sig, &key).unwrap(),
+            UpdateStatus::UpToDate { .. }
+        ));
+
+        let tampered = feed.replace("0.4.1", "9.9.9");
+        assert!(check_feed("0.3.0", tampered.as_bytes(), &sig, &key).is_err());
+    }
+}
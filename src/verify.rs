@@ -0,0 +1,260 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Verification reports: the validation status codes of every manifest in a store, grouped by
+//! what they check (claim signature, hash binding, timestamp, signing credential trust).
+
+use crate::filter::FileFacts;
+use crate::provenance::{detect_relationship_violations, RelationshipViolation};
+use crate::{ManifestExtractionResult, CONTENT_HASH_CODE_PREFIXES};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+/// What a validation status code checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckCategory {
+    /// The claim signature (`claimSignature.*`).
+    Signature,
+    /// Hard bindings and hashed assertion references (`assertion.dataHash.*`,
+    /// `assertion.hashedURI.*`, ...).
+    HashBinding,
+    /// Signature timestamps (`timeStamp.*`).
+    Timestamp,
+    /// The signing credential: chain, validity, trust lists (`signingCredential.*`).
+    Trust,
+    Other,
+}
+
+/// Category of a validation status code.
+pub fn code_category(code: &str) -> CheckCategory {
+    if code.starts_with("claimSignature.") {
+        CheckCategory::Signature
+    } else if code.starts_with("assertion.hashedURI.")
+        || CONTENT_HASH_CODE_PREFIXES
+            .iter()
+            .any(|prefix| code.starts_with(prefix))
+    {
+        CheckCategory::HashBinding
+    } else if code.starts_with("timeStamp.") {
+        CheckCategory::Timestamp
+    } else if code.starts_with("signingCredential.") {
+        CheckCategory::Trust
+    } else {
+        CheckCategory::Other
+    }
+}
+
+/// One validation status entry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusEntry {
+    pub code: String,
+    pub category: CheckCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+}
+
+/// Validation status codes of one manifest.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestVerification {
+    pub label: String,
+    pub is_active: bool,
+    pub success: Vec<StatusEntry>,
+    pub informational: Vec<StatusEntry>,
+    pub failure: Vec<StatusEntry>,
+}
+
+/// Verification report of one asset.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationReport {
+    pub active_manifest: String,
+    /// `trusted`, `untrusted`, or `unknown`.
+    pub trust: String,
+    /// No validation failures other than an untrusted signer.
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_at: Option<String>,
+    pub manifests: Vec<ManifestVerification>,
+    /// Ingredient relationship constraints broken by any manifest in the store.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub relationship_violations: Vec<RelationshipViolation>,
+}
+
+fn entries(results: Option<&JsonValue>, key: &str) -> Vec<StatusEntry> {
+    let str_of =
+        |entry: &JsonValue, key: &str| entry.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    results
+        .and_then(|r| r.get(key))
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|entry| {
+                    let code = str_of(entry, "code")?;
+                    Some(StatusEntry {
+                        category: code_category(&code),
+                        code,
+                        url: str_of(entry, "url"),
+                        explanation: str_of(entry, "explanation"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl VerificationReport {
+    /// Build the report from an extraction (run with the trust settings to verify against).
+    pub fn from_extraction(result: &ManifestExtractionResult) -> Self {
+        let facts = FileFacts::from_extraction(result);
+        let crjson = &result.manifest_value;
+        let manifests = crjson
+            .get("manifests")
+            .and_then(|m| m.as_array())
+            .map(|arr| arr.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|m| {
+                let label = m.get("label")?.as_str()?.to_string();
+                let is_active = label == result.active_label;
+                // The active manifest's results may only be given at the top level
+                let results = m.get("validationResults").or_else(|| {
+                    is_active
+                        .then(|| crjson.pointer("/validationResults/activeManifest"))
+                        .flatten()
+                });
+                Some(ManifestVerification {
+                    success: entries(results, "success"),
+                    informational: entries(results, "informational"),
+                    failure: entries(results, "failure"),
+                    label,
+                    is_active,
+                })
+            })
+            .collect();
+
+        Self {
+            active_manifest: facts.active_label,
+            trust: facts.trust,
+            valid: facts.valid,
+            issuer: facts.issuer,
+            signed_at: facts.signed_at,
+            manifests,
+            relationship_violations: detect_relationship_violations(crjson),
+        }
+    }
+
+    /// Whether the asset verifies: no validation failures or relationship violations, and with
+    /// `require_trusted`, a signer that is not untrusted.
+    pub fn passed(&self, require_trusted: bool) -> bool {
+        self.valid
+            && self.relationship_violations.is_empty()
+            && !(require_trusted && self.trust == "untrusted")
+    }
+
+    /// Failure codes of every manifest, as `label: code`, followed by relationship violations.
+    pub fn failures(&self) -> Vec<String> {
+        self.manifests
+            .iter()
+            .flat_map(|m| {
+                m.failure
+                    .iter()
+                    .map(move |f| format!("{}: {}", m.label, f.code))
+            })
+            .chain(self.relationship_violations.iter().map(|v| v.to_string()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_code_category() {
+        assert_eq!(
+            code_category("claimSignature.validated"),
+            CheckCategory::Signature
+        );
+        assert_eq!(
+            code_category("assertion.dataHash.mismatch"),
+            CheckCategory::HashBinding
+        );
+        assert_eq!(
+            code_category("assertion.hashedURI.match"),
+            CheckCategory::HashBinding
+        );
+        assert_eq!(code_category("timeStamp.trusted"), CheckCategory::Timestamp);
+        assert_eq!(
+            code_category("signingCredential.untrusted"),
+            CheckCategory::Trust
+        );
+        assert_eq!(code_category("claim.missing"), CheckCategory::Other);
+    }
+
+    #[test]
+    fn test_report_from_extraction() {
+        let manifest_value = json!({
+            "manifests": [
+                {"label": "urn:c2pa:active"},
+                {
+                    "label": "urn:c2pa:ingredient",
+                    "validationResults": {
+                        "success": [{"code": "claimSignature.validated"}],
+                        "failure": [{"code": "assertion.dataHash.mismatch", "url": "self#jumbf=x"}]
+                    }
+                }
+            ],
+            "validationResults": {
+                "activeManifest": {
+                    "success": [{"code": "claimSignature.validated"}],
+                    "informational": [],
+                    "failure": [{"code": "signingCredential.untrusted"}]
+                }
+            }
+        });
+        let result = ManifestExtractionResult {
+            input_path: "a.jpg".to_string(),
+            active_label: "urn:c2pa:active".to_string(),
+            asset_hash: None,
+            asset_hash_alg: None,
+            manifest_json: manifest_value.to_string(),
+            manifest_value,
+        };
+
+        let report = VerificationReport::from_extraction(&result);
+        assert_eq!(report.manifests.len(), 2);
+        assert!(report.manifests[0].is_active);
+        assert_eq!(
+            report.manifests[0].failure[0].category,
+            CheckCategory::Trust
+        );
+        assert_eq!(
+            report.manifests[1].failure[0].url.as_deref(),
+            Some("self#jumbf=x")
+        );
+        assert_eq!(
+            report.failures(),
+            vec![
+                "urn:c2pa:active: signingCredential.untrusted",
+                "urn:c2pa:ingredient: assertion.dataHash.mismatch"
+            ]
+        );
+    }
+}
@@ -14,11 +14,12 @@ governing permissions and limitations under the License.
 #![allow(dead_code)]
 
 use anyhow::Result;
-use c2pa::{Builder, CallbackSigner, Ingredient, Reader, Relationship, SigningAlg};
+use c2pa::{Builder, CallbackSigner, Ingredient, Reader, SigningAlg};
 use std::collections::HashSet;
 use std::fs;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 /// Path to the crTool CLI binary (when built as part of the workspace).
 pub fn cli_binary_path() -> PathBuf {
@@ -343,29 +344,7 @@ fn process_ingredients_with_thumbnails(
                 ingredient.set_title(title);
             }
 
-            if let Some(rel) = ingredient_def.get("relationship").and_then(|v| v.as_str()) {
-                let relationship = match rel.to_lowercase().as_str() {
-                    "parentof" => Relationship::ParentOf,
-                    "componentof" => Relationship::ComponentOf,
-                    _ => anyhow::bail!("Invalid relationship type: {}", rel),
-                };
-                ingredient.set_relationship(relationship);
-            }
-
-            if let Some(label) = ingredient_def.get("label").and_then(|v| v.as_str()) {
-                ingredient.set_instance_id(label);
-            }
-
-            if let Some(metadata_obj) = ingredient_def.get("metadata") {
-                if let Some(metadata_map) = metadata_obj.as_object() {
-                    use c2pa::assertions::AssertionMetadata;
-                    let mut assertion_metadata = AssertionMetadata::new();
-                    for (key, value) in metadata_map {
-                        assertion_metadata = assertion_metadata.set_field(key, value.clone());
-                    }
-                    ingredient.set_metadata(assertion_metadata);
-                }
-            }
+            crtool::signing::apply_ingredient_fields(&mut ingredient, ingredient_def)?;
 
             if generate_thumbnails && ingredient.thumbnail_ref().is_none() {
                 source.rewind()?;
@@ -447,16 +426,15 @@ fn test_signer() -> CallbackSigner {
 
 fn ed_sign(data: &[u8], private_key: &[u8]) -> c2pa::Result<Vec<u8>> {
     use c2pa::crypto::raw_signature::RawSignerError;
-    use ed25519_dalek::{Signature, Signer, SigningKey};
-    use pem::parse;
-
-    // Parse the PEM data to get the private key
-    let pem = parse(private_key).map_err(|e| c2pa::Error::OtherError(Box::new(e)))?;
+    use crtool::keys::PrivateKey;
+    use ed25519_dalek::{Signature, Signer};
 
-    // For Ed25519, the key is 32 bytes long, so we skip the first 16 bytes of the PEM data
-    let key_bytes = &pem.contents()[16..];
-    let signing_key = SigningKey::try_from(key_bytes)
+    // Parse the PKCS#8 PEM data to get the private key
+    let key = PrivateKey::from_pem(private_key)
         .map_err(|e| RawSignerError::InternalError(e.to_string()))?;
+    let PrivateKey::Ed25519(signing_key) = key else {
+        return Err(RawSignerError::InternalError("Not an Ed25519 private key".into()).into());
+    };
 
     // Sign the data
     let signature: Signature = signing_key.sign(data);
@@ -580,6 +558,30 @@ fn extract_manifest_impl(input_path: &Path, output_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Write a `width` × `height` image of noise, which compresses poorly, so hashing or loading it
+/// dominates the cost being measured. The format comes from the path's extension.
+pub fn write_large_image(path: &Path, width: u32, height: u32) -> Result<()> {
+    let mut seed = 0x2545_f491u32;
+    let img = image::RgbImage::from_fn(width, height, |_, _| {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        let [r, g, b, _] = seed.to_le_bytes();
+        image::Rgb([r, g, b])
+    });
+    img.save(path)?;
+    Ok(())
+}
+
+/// Average wall time of `runs` calls of `f`.
+pub fn time_runs(runs: u32, mut f: impl FnMut() -> Result<()>) -> Result<Duration> {
+    let start = Instant::now();
+    for _ in 0..runs {
+        f()?;
+    }
+    Ok(start.elapsed() / runs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
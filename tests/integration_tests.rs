@@ -1520,6 +1520,39 @@ fn test_ingredient_thumbnails_present_when_requested() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_input_to_ingredient_with_v3_fields() -> Result<()> {
+    let input = common::testfiles_dir().join("Dog.jpg");
+    let manifest = manifests_dir().join("with_input_ingredients.json");
+    let output = generate_output_name(&input, "input_ingredients", Some("individual"));
+    let ingredients_base_dir = manifest.parent().unwrap();
+
+    sign_file_with_manifest_and_ingredients(&input, &output, &manifest, ingredients_base_dir)?;
+
+    let reader = verify_signed_file(&output)?;
+    let manifest_label = reader.active_label().expect("signed file has a manifest");
+    let ingredients = reader.get_manifest(manifest_label).unwrap().ingredients();
+    assert_eq!(ingredients.len(), 1);
+    let ingredient = &ingredients[0];
+    assert_eq!(*ingredient.relationship(), c2pa::Relationship::InputTo);
+    assert_eq!(
+        ingredient.description(),
+        Some("Image supplied as the generation prompt")
+    );
+    assert_eq!(
+        ingredient.informational_uri(),
+        Some("https://example.com/reference/dog")
+    );
+    let data_types = ingredient.data_types().expect("data_types were embedded");
+    assert_eq!(data_types[0].asset_type, "c2pa.types.generator.prompt");
+
+    println!(
+        "✓ Dog.jpg with with_input_ingredients.json: {}",
+        output.display()
+    );
+    Ok(())
+}
+
 #[test]
 fn test_multiple_ingredients_with_thumbnails() -> Result<()> {
     let input = common::testfiles_dir().join("Dog.webp");
@@ -1824,6 +1857,47 @@ fn test_multiple_files_extract() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_extract_fails_when_evidence_report_cannot_be_written() -> Result<()> {
+    use std::process::Command;
+
+    let signed_dir = common::output_dir().join("evidence_failure_input");
+    fs::create_dir_all(&signed_dir)?;
+    let input = common::testfiles_dir().join("Dog.jpg");
+    let signed = signed_dir.join("Dog_signed.jpg");
+    sign_file_with_manifest(
+        &input,
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+
+    // A directory where the report should go makes writing it fail
+    let extract_dir = common::output_dir().join("evidence_failure_output");
+    let _ = fs::remove_dir_all(&extract_dir);
+    fs::create_dir_all(extract_dir.join("Dog_signed_evidence.json"))?;
+
+    let result = Command::new(common::cli_binary_path())
+        .arg("--extract")
+        .arg(&signed)
+        .arg("--output")
+        .arg(extract_dir.join("Dog_signed_cr.json"))
+        .arg("--evidence-report")
+        .output()?;
+
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(
+        !result.status.success(),
+        "extract should fail when an evidence report cannot be written"
+    );
+    assert!(
+        stderr.contains("1 evidence report(s) could not be written"),
+        "stderr: {stderr}"
+    );
+    assert!(extract_dir.join("Dog_signed_cr.json").exists());
+
+    Ok(())
+}
+
 #[test]
 fn test_multi_file_error_handling() -> Result<()> {
     use std::process::Command;
@@ -1886,6 +1960,8 @@ fn test_create_test_missing_output_fails() -> Result<()> {
 }
 
 /// Signs testset manifests and extracts crJSON via library (Reader::crjson), then validates with crJSON schema. No CLI dependency.
+/// Each extraction is also compared with its snapshot in tests/snapshots/testset (missing ones are
+/// written); set CRTOOL_UPDATE_SNAPSHOTS=1 to accept changed extraction output.
 #[test]
 fn test_testset_manifests_crjson() -> Result<()> {
     let manifest_names = vec![
@@ -1931,6 +2007,11 @@ fn test_testset_manifests_crjson() -> Result<()> {
 
     let mut success_count = 0;
     let total_count = manifest_names.len();
+    let snapshots = crtool::snapshot::SnapshotStore::new(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/testset"),
+    )
+    .update(std::env::var_os("CRTOOL_UPDATE_SNAPSHOTS").is_some());
+    let mut snapshot_changes = Vec::new();
 
     let input = common::testfiles_dir().join("Dog.jpg");
     let testset_dir = output_dir().join("testset_crjson");
@@ -1984,6 +2065,25 @@ fn test_testset_manifests_crjson() -> Result<()> {
                 let extracted_json = testset_dir.join(format!("{}_cr.json", manifest_name));
                 std::fs::write(&extracted_json, &extraction.manifest_json)?;
 
+                match snapshots.check(manifest_name, &extraction.manifest_value)? {
+                    crtool::snapshot::SnapshotOutcome::Changed(diff) => {
+                        println!("✗ Extraction differs from snapshot for {}", manifest_name);
+                        snapshot_changes.push(format!("{}:\n{}", manifest_name, diff));
+                    }
+                    crtool::snapshot::SnapshotOutcome::Missing => {
+                        println!("✗ No snapshot for {}", manifest_name);
+                        snapshot_changes.push(format!(
+                            "{}: no snapshot at {:?}",
+                            manifest_name,
+                            snapshots.path(manifest_name)
+                        ));
+                    }
+                    crtool::snapshot::SnapshotOutcome::Created => {
+                        println!("  ✓ Snapshot written for {}", manifest_name);
+                    }
+                    _ => {}
+                }
+
                 let schema_path = crtool::crjson_schema_path();
                 let validation = crtool::validate_json_file(&extracted_json, &schema_path)?;
 
@@ -2057,6 +2157,12 @@ fn test_testset_manifests_crjson() -> Result<()> {
         success_count, total_count,
         "All image/manifest combinations should succeed for crJSON testset"
     );
+    assert!(
+        snapshot_changes.is_empty(),
+        "Extraction output changed or has no snapshot (rerun with CRTOOL_UPDATE_SNAPSHOTS=1 to \
+        accept, then commit tests/snapshots/testset):\n{}",
+        snapshot_changes.join("\n")
+    );
 
     Ok(())
 }
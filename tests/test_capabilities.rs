@@ -0,0 +1,60 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `capabilities` introspection tests.
+
+use anyhow::Result;
+use std::process::Command;
+
+mod common;
+
+#[test]
+fn test_capabilities_json() -> Result<()> {
+    let output = Command::new(common::cli_binary_path())
+        .arg("capabilities")
+        .arg("--json")
+        .output()?;
+    assert!(output.status.success());
+
+    let caps: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert!(caps["version"].is_string());
+    assert_eq!(caps["buildMode"], "full");
+    assert_eq!(caps["features"]["serverMode"], false);
+    let algorithms = caps["signingAlgorithms"].as_array().unwrap();
+    assert!(algorithms.iter().any(|a| a == "es256"));
+    let formats = caps["formats"].as_array().unwrap();
+    assert!(formats
+        .iter()
+        .any(|f| f["extension"] == "jpg" && f["canSign"] == true));
+    let crjson = caps["schemas"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["name"] == "crJSON")
+        .unwrap();
+    assert_eq!(crjson["present"], true);
+    Ok(())
+}
+
+#[test]
+fn test_capabilities_flag_is_deprecated_alias() -> Result<()> {
+    let output = Command::new(common::cli_binary_path())
+        .arg("--capabilities")
+        .arg("--json")
+        .output()?;
+    assert!(output.status.success());
+    let caps: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert!(caps["version"].is_string());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("deprecated"), "stderr: {stderr}");
+    Ok(())
+}
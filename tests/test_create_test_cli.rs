@@ -10,8 +10,8 @@ OF ANY KIND, either express or implied. See the License for the specific languag
 governing permissions and limitations under the License.
 */
 
-//! CLI integration tests for `--create-test` mode (single file, glob pattern, input override)
-//! and batch mode with `test-cases` commands.
+//! CLI integration tests for `--create-test` mode (single file, glob pattern, input override),
+//! batch mode with `test-cases` commands, `--batch-csv`, `--audit-log`, and `watch --once`.
 
 use anyhow::Result;
 use std::fs;
@@ -57,6 +57,29 @@ fn run(args: &[&str]) -> (bool, String, String) {
 
 // ─── Single exact-path tests ─────────────────────────────────────────────────
 
+/// `--show-tree` prints the signed output's provenance tree after signing.
+#[test]
+fn test_create_test_show_tree() -> Result<()> {
+    let tc = test_cases_dir().join("positive/tc-created.json");
+    let out = test_output_dir("show_tree").join("tc-created.jpg");
+
+    let (ok, stdout, stderr) = run(&[
+        "--create-test",
+        tc.to_str().unwrap(),
+        "--output",
+        out.to_str().unwrap(),
+        "--show-tree",
+    ]);
+
+    assert!(ok, "create-test should succeed: {stderr}");
+    assert!(
+        stdout.contains("[urn:c2pa:") && stdout.contains("actions: c2pa.created"),
+        "Expected provenance tree in output, got: {stdout}"
+    );
+
+    Ok(())
+}
+
 /// Basic case: single test case JSON with its own `inputAsset`, output to a file.
 #[test]
 fn test_create_test_single_exact_path() -> Result<()> {
@@ -145,6 +168,47 @@ fn test_create_test_glob_multiple_cases() -> Result<()> {
     Ok(())
 }
 
+/// Manifest placeholders are expanded per input: each output gets its own title and label.
+#[test]
+fn test_create_test_expands_placeholders() -> Result<()> {
+    let out_dir = test_output_dir("placeholders");
+    let mut tc: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        test_cases_dir().join("positive/tc-created.json"),
+    )?)?;
+    let certs = repo_root().join("tests/fixtures/certs");
+    tc["signingCert"] = certs.join("ed25519.pub").display().to_string().into();
+    tc["signingKey"] = certs.join("ed25519.pem").display().to_string().into();
+    tc["manifest"]["title"] = "{{stem}} ({{filename}})".into();
+    tc["manifest"]["label"] = "urn:c2pa:{{uuid}}".into();
+    let tc_path = out_dir.join("tc-template.json");
+    fs::write(&tc_path, serde_json::to_string_pretty(&tc)?)?;
+
+    let assets = repo_root().join("tests/fixtures/assets");
+    let (ok, stdout, stderr) = run(&[
+        "--create-test",
+        tc_path.to_str().unwrap(),
+        assets.join("Dog.jpg").to_str().unwrap(),
+        assets.join("Dog.png").to_str().unwrap(),
+        "--output",
+        out_dir.to_str().unwrap(),
+    ]);
+    println!("stdout: {stdout}");
+    assert!(ok, "templated create-test should succeed: {stderr}");
+
+    let mut labels = Vec::new();
+    for name in ["Dog.jpg", "Dog.png"] {
+        let extraction = crtool::extract_crjson_manifest(&out_dir.join(name))?;
+        assert!(
+            extraction.manifest_json.contains(&format!("Dog ({name})")),
+            "Expected the expanded title in {name}"
+        );
+        labels.push(extraction.active_label.clone());
+    }
+    assert_ne!(labels[0], labels[1], "each output should get its own UUID");
+
+    Ok(())
+}
+
 /// Glob matching multiple test cases + a single input file override.
 #[test]
 fn test_create_test_glob_with_input_override() -> Result<()> {
@@ -232,6 +296,42 @@ fn test_create_test_glob_no_matches_fails() -> Result<()> {
     Ok(())
 }
 
+/// `--jobs` with a PKCS#11 key is refused before anything is signed, since the module can only be
+/// initialized from one thread at a time.
+#[test]
+fn test_sign_jobs_rejects_pkcs11_key() -> Result<()> {
+    let test_case = test_cases_dir().join("positive/tc-created.json");
+    let assets = repo_root().join("tests/fixtures/assets");
+    let out_dir = test_output_dir("jobs_pkcs11");
+
+    let (ok, _stdout, stderr) = run(&[
+        "sign",
+        test_case.to_str().unwrap(),
+        assets.join("Dog.jpg").to_str().unwrap(),
+        assets.join("Dog.png").to_str().unwrap(),
+        "--output",
+        out_dir.to_str().unwrap(),
+        "--jobs",
+        "2",
+        "--key",
+        "pkcs11:object=c2pa?module-path=/nonexistent/module.so",
+    ]);
+
+    println!("stderr: {stderr}");
+
+    assert!(!ok, "--jobs should be refused with a PKCS#11 key");
+    assert!(
+        stderr.contains("--jobs 2 cannot be used with a PKCS#11 or keychain key"),
+        "Error should explain the --jobs restriction: {stderr}"
+    );
+    assert!(
+        !out_dir.join("Dog.jpg").exists(),
+        "Nothing should have been signed"
+    );
+
+    Ok(())
+}
+
 // ─── Batch mode tests ─────────────────────────────────────────────────────────
 
 /// Write a temp batch JSON file, run the CLI with `--batch`, and return (success, stdout, stderr).
@@ -367,3 +467,187 @@ fn test_batch_test_cases_with_input_files() -> Result<()> {
 
     Ok(())
 }
+
+// ─── Batch CSV tests ──────────────────────────────────────────────────────────
+
+/// `--batch-csv`: one signed output per row, with the row's title and preset in its manifest.
+#[test]
+fn test_batch_csv_signs_each_row() -> Result<()> {
+    let tc = test_cases_dir().join("positive/tc-created.json");
+    let out_dir = test_output_dir("batch_csv");
+    let dog = repo_root().join("tests/fixtures/assets/Dog.jpg");
+    let csv = out_dir.join("jobs.csv");
+    fs::write(
+        &csv,
+        format!(
+            "input,output,title,author,preset\n\
+             {dog},dog-captured.jpg,\"Dog, captured\",Ada,captured\n\
+             {dog},dog-ai.jpg,Dog AI,,ai-generated\n",
+            dog = dog.display()
+        ),
+    )?;
+
+    let (ok, stdout, stderr) = run(&[
+        "--create-test",
+        tc.to_str().unwrap(),
+        "--batch-csv",
+        csv.to_str().unwrap(),
+    ]);
+    println!("stdout: {stdout}");
+    println!("stderr: {stderr}");
+    assert!(ok, "batch CSV should succeed: {stderr}");
+
+    for (name, title) in [
+        ("dog-captured.jpg", "Dog, captured"),
+        ("dog-ai.jpg", "Dog AI"),
+    ] {
+        let signed = out_dir.join(name);
+        assert!(signed.exists(), "Output file should exist: {signed:?}");
+        let extraction = crtool::extract_crjson_manifest(&signed)?;
+        assert!(
+            extraction.manifest_json.contains(title),
+            "Expected title {title:?} in the signed manifest"
+        );
+    }
+
+    // An unknown preset fails its row
+    fs::write(
+        &csv,
+        format!(
+            "input,output,preset\n{},bad.jpg,watercolor\n",
+            dog.display()
+        ),
+    )?;
+    let (ok, _, stderr) = run(&[
+        "--create-test",
+        tc.to_str().unwrap(),
+        "--batch-csv",
+        csv.to_str().unwrap(),
+    ]);
+    assert!(!ok);
+    assert!(stderr.contains("Unknown preset"), "stderr: {stderr}");
+
+    Ok(())
+}
+
+/// `--audit-log` records each signing in a signed hash chain that `audit-log verify` accepts,
+/// and rejects once an entry is edited.
+#[test]
+fn test_audit_log_records_and_verifies() -> Result<()> {
+    let tc = test_cases_dir().join("positive/tc-created.json");
+    let out_dir = test_output_dir("audit_log");
+    let log = out_dir.join("signing.jsonl");
+    let key = common::certs_dir().join("audit_log.pem");
+    let public_key = common::certs_dir().join("audit_log_public.pem");
+    let _ = fs::remove_file(&log);
+
+    let (ok, _, stderr) = run(&[
+        "sign",
+        tc.to_str().unwrap(),
+        "--output",
+        out_dir.join("unkeyed.jpg").to_str().unwrap(),
+        "--audit-log",
+        log.to_str().unwrap(),
+    ]);
+    assert!(!ok, "--audit-log without a key should fail");
+    assert!(stderr.contains("--audit-log-key"), "stderr: {stderr}");
+
+    for name in ["first.jpg", "second.jpg"] {
+        let out = out_dir.join(name);
+        let (ok, _, stderr) = run(&[
+            "--create-test",
+            tc.to_str().unwrap(),
+            "--output",
+            out.to_str().unwrap(),
+            "--audit-log",
+            log.to_str().unwrap(),
+            "--audit-log-key",
+            key.to_str().unwrap(),
+        ]);
+        assert!(ok, "create-test should succeed: {stderr}");
+    }
+
+    let entries: Vec<serde_json::Value> = fs::read_to_string(&log)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<_, _>>()?;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1]["seq"], 2);
+    assert_eq!(entries[1]["prevHash"], entries[0]["hash"]);
+    assert_eq!(
+        entries[0]["certFingerprint"].as_str().map(str::len),
+        Some(64)
+    );
+    assert!(entries[0]["signature"].is_string());
+
+    let (ok, stdout, stderr) = run(&[
+        "audit-log",
+        "verify",
+        log.to_str().unwrap(),
+        "--public-key",
+        public_key.to_str().unwrap(),
+    ]);
+    assert!(ok, "intact log should verify: {stderr}");
+    assert!(stdout.contains("2 entry(ies)"), "stdout: {stdout}");
+
+    let text = fs::read_to_string(&log)?;
+    fs::write(&log, text.replacen("first.jpg", "other.jpg", 1))?;
+    let output = Command::new(binary())
+        .args(["--verify-audit-log", log.to_str().unwrap()])
+        .env("CRTOOL_AUDIT_LOG_PUBLIC_KEY", &public_key)
+        .output()?;
+    let (ok, stderr) = (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(!ok, "edited log should fail verification");
+    assert!(stderr.contains("broken at line 1"), "stderr: {stderr}");
+
+    Ok(())
+}
+
+/// `watch --once` signs the assets already in the hot folder, mirroring subdirectories, and a
+/// second run skips them because the log records them as signed.
+#[test]
+fn test_watch_once_signs_and_skips_logged_files() -> Result<()> {
+    let tc = test_cases_dir().join("positive/tc-created.json");
+    let root = test_output_dir("watch");
+    let (drop, signed) = (root.join("drop"), root.join("signed"));
+    let _ = fs::remove_dir_all(&drop);
+    let _ = fs::remove_dir_all(&signed);
+    fs::create_dir_all(drop.join("day1"))?;
+    fs::copy(
+        repo_root().join("tests/fixtures/assets/Dog.jpg"),
+        drop.join("day1/Dog.jpg"),
+    )?;
+    fs::write(drop.join("day1/.hidden.jpg"), b"not an image")?;
+
+    let args = [
+        "watch",
+        "--input-dir",
+        drop.to_str().unwrap(),
+        "--output-dir",
+        signed.to_str().unwrap(),
+        "--test-case",
+        tc.to_str().unwrap(),
+        "--once",
+    ];
+    let (ok, stdout, stderr) = run(&args);
+    println!("stdout: {stdout}");
+    assert!(ok, "watch --once should succeed: {stderr}");
+    assert!(signed.join("day1/Dog.jpg").exists());
+    assert!(!signed.join("day1/.hidden.jpg").exists());
+
+    let log = signed.join(".crtool-watch.jsonl");
+    let entries = fs::read_to_string(&log)?;
+    assert_eq!(entries.lines().count(), 1);
+    let entry: serde_json::Value = serde_json::from_str(entries.lines().next().unwrap())?;
+    assert_eq!(entry["status"], "signed");
+    assert_eq!(entry["verified"], true);
+
+    let (ok, _, stderr) = run(&args);
+    assert!(ok, "second watch --once should succeed: {stderr}");
+    assert_eq!(fs::read_to_string(&log)?.lines().count(), 1);
+
+    Ok(())
+}
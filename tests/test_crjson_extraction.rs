@@ -204,6 +204,483 @@ fn test_extract_produces_crjson_structure() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_extract_include_cose_details() -> Result<()> {
+    let input = testfiles_dir().join("Dog.jpg");
+    let manifest = manifests_dir().join("simple_manifest.json");
+    let signed_output = output_dir().join("crjson_tests/cose_details_signed.jpg");
+
+    fs::create_dir_all(signed_output.parent().unwrap())?;
+    sign_file_with_manifest(&input, &signed_output, &manifest)?;
+
+    let extract_output = generate_extraction_output("cose_details", "crjson_tests");
+
+    let result = Command::new(get_binary_path())
+        .arg("--extract")
+        .arg("--include-cose-details")
+        .arg(&signed_output)
+        .arg("--output")
+        .arg(&extract_output)
+        .output()?;
+
+    assert!(
+        result.status.success(),
+        "Extraction failed: {}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let json_value: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&extract_output)?)?;
+    let label = json_value["manifests"][0]["label"]
+        .as_str()
+        .expect("manifest label");
+    let details = &json_value["coseDetails"][label];
+    assert!(details["algorithm"].is_string(), "algorithm: {details}");
+    assert_eq!(details["x5chainPresent"], true);
+    assert_eq!(details["payloadDetached"], true);
+
+    Ok(())
+}
+
+#[test]
+fn test_extract_writes_provenance_graphs() -> Result<()> {
+    let input = testfiles_dir().join("Dog.jpg");
+    let manifest = manifests_dir().join("simple_manifest.json");
+    let signed_output = output_dir().join("crjson_tests/graph_signed.jpg");
+
+    fs::create_dir_all(signed_output.parent().unwrap())?;
+    sign_file_with_manifest(&input, &signed_output, &manifest)?;
+
+    let extract_output = generate_extraction_output("graph", "crjson_tests");
+
+    let result = Command::new(get_binary_path())
+        .arg("--extract")
+        .arg("--graph")
+        .arg("dot,graphml,jsonld")
+        .arg(&signed_output)
+        .arg("--output")
+        .arg(&extract_output)
+        .output()?;
+
+    assert!(
+        result.status.success(),
+        "Extraction failed: {}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let json_value: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&extract_output)?)?;
+    let label = json_value["manifests"][0]["label"]
+        .as_str()
+        .expect("manifest label");
+
+    let graph_path = |ext: &str| extract_output.with_file_name(format!("graph_provenance.{ext}"));
+    let dot = fs::read_to_string(graph_path("dot"))?;
+    assert!(dot.starts_with("digraph provenance {"), "{dot}");
+    assert!(dot.contains(label));
+    let graphml = fs::read_to_string(graph_path("graphml"))?;
+    assert!(
+        graphml.contains(&format!("<node id=\"{label}\">")),
+        "{graphml}"
+    );
+    let jsonld: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(graph_path("jsonld"))?)?;
+    assert_eq!(jsonld["@context"]["prov"], "http://www.w3.org/ns/prov#");
+    assert!(jsonld["@graph"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|n| n["@id"] == label && n["schema:additionalType"] == "activeManifest"));
+
+    Ok(())
+}
+
+#[test]
+fn test_extract_expectations() -> Result<()> {
+    let input = testfiles_dir().join("Dog.jpg");
+    let manifest = manifests_dir().join("simple_manifest.json");
+    let signed_output = output_dir().join("crjson_tests/expect_signed.jpg");
+
+    fs::create_dir_all(signed_output.parent().unwrap())?;
+    sign_file_with_manifest(&input, &signed_output, &manifest)?;
+
+    let extract_output = generate_extraction_output("expect", "crjson_tests");
+    let run = |args: &[&str]| {
+        Command::new(get_binary_path())
+            .arg("--extract")
+            .args(args)
+            .arg(&signed_output)
+            .arg("--output")
+            .arg(&extract_output)
+            .output()
+    };
+
+    let met = run(&[
+        "--expect-assertion",
+        "c2pa.hash.data",
+        "--expect-ingredients",
+        "0",
+    ])?;
+    assert!(
+        met.status.success(),
+        "Expectations should hold: {}",
+        String::from_utf8_lossy(&met.stderr)
+    );
+
+    let unmet = run(&[
+        "--expect-assertion",
+        "c2pa.hash.data,com.example.missing",
+        "--expect-ingredients",
+        "2",
+    ])?;
+    assert!(!unmet.status.success());
+    let stderr = String::from_utf8_lossy(&unmet.stderr);
+    assert!(
+        stderr.contains("assertion com.example.missing is missing"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("0 ingredient(s), expected 2"), "{stderr}");
+    assert!(!stderr.contains("assertion c2pa.hash.data is missing"));
+
+    Ok(())
+}
+
+#[test]
+fn test_badge_writes_svg_summary() -> Result<()> {
+    let input = testfiles_dir().join("Dog.jpg");
+    let manifest = manifests_dir().join("simple_manifest.json");
+    let signed_output = output_dir().join("crjson_tests/badge_signed.jpg");
+
+    fs::create_dir_all(signed_output.parent().unwrap())?;
+    sign_file_with_manifest(&input, &signed_output, &manifest)?;
+
+    let badge_path = output_dir().join("crjson_tests/badge_signed.svg");
+    let _ = fs::remove_file(&badge_path);
+    let output = Command::new(get_binary_path())
+        .arg("badge")
+        .arg(&signed_output)
+        .arg("-o")
+        .arg(&badge_path)
+        .output()?;
+    assert!(
+        output.status.success(),
+        "Badge should be written: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let svg = fs::read_to_string(&badge_path)?;
+    assert!(svg.starts_with("<svg "));
+    assert!(
+        svg.contains("Content Credentials"),
+        "Badge should name Content Credentials: {svg}"
+    );
+    // Without --trust, the signer's trust is not checked
+    assert!(svg.contains("Trust not checked") || svg.contains("Invalid"));
+
+    let png = Command::new(get_binary_path())
+        .arg("--badge")
+        .arg(&signed_output)
+        .arg("--output")
+        .arg(output_dir().join("crjson_tests/badge_signed.png"))
+        .output()?;
+    assert!(!png.status.success(), "Only SVG badges are rendered");
+
+    let missing_output = Command::new(get_binary_path())
+        .arg("badge")
+        .arg(&signed_output)
+        .output()?;
+    assert!(
+        !missing_output.status.success(),
+        "`badge` requires --output"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_extract_and_info_subcommands() -> Result<()> {
+    let input = testfiles_dir().join("Dog.jpg");
+    let manifest = manifests_dir().join("simple_manifest.json");
+    let signed_output = output_dir().join("crjson_tests/subcommand_signed.jpg");
+
+    fs::create_dir_all(signed_output.parent().unwrap())?;
+    sign_file_with_manifest(&input, &signed_output, &manifest)?;
+
+    let crjson_path = output_dir().join("crjson_tests/subcommand_signed_cr.json");
+    let _ = fs::remove_file(&crjson_path);
+    let extract = Command::new(get_binary_path())
+        .arg("extract")
+        .arg(&signed_output)
+        .arg("--output")
+        .arg(&crjson_path)
+        .arg("--quiet")
+        .output()?;
+    assert!(
+        extract.status.success(),
+        "extract subcommand should succeed: {}",
+        String::from_utf8_lossy(&extract.stderr)
+    );
+    assert!(crjson_path.exists());
+    assert!(
+        !String::from_utf8_lossy(&extract.stdout).contains("deprecated"),
+        "Subcommands should not warn about deprecated flags"
+    );
+
+    let info = Command::new(get_binary_path())
+        .arg("info")
+        .arg(&signed_output)
+        .arg("--json")
+        .output()?;
+    assert!(
+        info.status.success(),
+        "info subcommand should succeed: {}",
+        String::from_utf8_lossy(&info.stderr)
+    );
+    let entries: serde_json::Value = serde_json::from_slice(&info.stdout)?;
+    assert_eq!(entries[0]["manifestCount"], 1);
+    assert_eq!(entries[0]["trust"], "unknown");
+
+    // Subcommand options are not accepted by other subcommands
+    let misplaced = Command::new(get_binary_path())
+        .arg("validate")
+        .arg(&crjson_path)
+        .arg("--fast")
+        .output()?;
+    assert!(!misplaced.status.success());
+    for options in [
+        &["info", "--mirror-tree", "."][..],
+        &["validate", "--trust"],
+        &["verify", "--profile", "p.yml"],
+    ] {
+        let misplaced = Command::new(get_binary_path())
+            .args(options)
+            .arg(&signed_output)
+            .output()?;
+        assert!(
+            !misplaced.status.success(),
+            "{options:?} should be rejected"
+        );
+    }
+
+    // The deprecated flag still works, with a warning
+    let legacy = Command::new(get_binary_path())
+        .arg("--validate")
+        .arg(&crjson_path)
+        .output()?;
+    assert!(
+        String::from_utf8_lossy(&legacy.stdout).contains("--validate is deprecated"),
+        "Legacy flag should warn: {}",
+        String::from_utf8_lossy(&legacy.stdout)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fingerprint_omits_metadata() -> Result<()> {
+    let input = testfiles_dir().join("Dog.jpg");
+    let manifest = manifests_dir().join("simple_manifest.json");
+    let signed_output = output_dir().join("crjson_tests/fingerprint_signed.jpg");
+
+    fs::create_dir_all(signed_output.parent().unwrap())?;
+    sign_file_with_manifest(&input, &signed_output, &manifest)?;
+
+    let output = Command::new(get_binary_path())
+        .arg("fingerprint")
+        .arg(&signed_output)
+        .arg("--json")
+        .output()?;
+    assert!(
+        output.status.success(),
+        "fingerprint should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fingerprints: serde_json::Value = serde_json::from_str(&stdout)?;
+    let expected_hash =
+        crtool::hashing::hash_file(&signed_output, crtool::hashing::HashAlgorithm::Sha256)?;
+    assert_eq!(fingerprints[0]["assetSha256"], expected_hash.as_str());
+    assert_eq!(
+        fingerprints[0]["manifests"][0]["signerCertSha256"]
+            .as_str()
+            .map(str::len),
+        Some(64)
+    );
+    assert!(
+        !stdout.contains("fingerprint_signed") && !stdout.contains("\"title\""),
+        "Fingerprints should carry no paths or titles: {stdout}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_reports_codes_per_manifest() -> Result<()> {
+    let input = testfiles_dir().join("Dog.jpg");
+    let manifest = manifests_dir().join("simple_manifest.json");
+    let signed_output = output_dir().join("crjson_tests/verify_signed.jpg");
+
+    fs::create_dir_all(signed_output.parent().unwrap())?;
+    sign_file_with_manifest(&input, &signed_output, &manifest)?;
+
+    let output = Command::new(get_binary_path())
+        .arg("verify")
+        .arg("--json")
+        .arg(&signed_output)
+        .output()?;
+    assert!(
+        output.status.success(),
+        "Verification should pass: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["mode"], "verify");
+    assert_eq!(report["trustRequired"], false);
+    assert_eq!(report["passed"], 1);
+    let file = &report["files"][0];
+    assert_eq!(file["passed"], true);
+    let manifests = file["manifests"].as_array().expect("manifests array");
+    assert!(!manifests.is_empty());
+    assert!(manifests
+        .iter()
+        .any(|m| m["isActive"] == true && m["label"] == file["activeManifest"]));
+
+    // Trust anchors must be PEM certificates
+    let bad_anchors = output_dir().join("crjson_tests/not_anchors.pem");
+    fs::write(&bad_anchors, "not a certificate")?;
+    let output = Command::new(get_binary_path())
+        .arg("verify")
+        .arg("--trust-anchors")
+        .arg(&bad_anchors)
+        .arg(&signed_output)
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No PEM certificates found"));
+
+    // The deprecated flag is rewritten into the subcommand
+    let legacy = Command::new(get_binary_path())
+        .arg("--verify")
+        .arg(&signed_output)
+        .output()?;
+    assert!(legacy.status.success());
+    assert!(String::from_utf8_lossy(&legacy.stdout).contains("--verify is deprecated"));
+
+    Ok(())
+}
+
+#[test]
+fn test_extract_mirror_tree_keeps_relative_layout() -> Result<()> {
+    let src_root = output_dir().join("crjson_tests/mirror_src");
+    let out_dir = output_dir().join("crjson_tests/mirror_out");
+    let _ = fs::remove_dir_all(&src_root);
+    let _ = fs::remove_dir_all(&out_dir);
+    fs::create_dir_all(&out_dir)?;
+
+    let manifest = manifests_dir().join("simple_manifest.json");
+    for sub in ["a", "b/c"] {
+        fs::create_dir_all(src_root.join(sub))?;
+        sign_file_with_manifest(
+            &testfiles_dir().join("Dog.jpg"),
+            &src_root.join(sub).join("photo.jpg"),
+            &manifest,
+        )?;
+    }
+
+    let result = Command::new(get_binary_path())
+        .arg("--extract")
+        .arg("--mirror-tree")
+        .arg(&src_root)
+        .arg(src_root.join("a/photo.jpg"))
+        .arg(src_root.join("b/c/photo.jpg"))
+        .arg("--output")
+        .arg(&out_dir)
+        .output()?;
+
+    assert!(
+        result.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    assert!(out_dir.join("a/photo_cr.json").exists());
+    assert!(out_dir.join("b/c/photo_cr.json").exists());
+    Ok(())
+}
+
+#[test]
+fn test_extract_json_report_includes_stats() -> Result<()> {
+    let signed = output_dir().join("crjson_tests/json_report_signed.jpg");
+    fs::create_dir_all(signed.parent().unwrap())?;
+    sign_file_with_manifest(
+        &testfiles_dir().join("Dog.jpg"),
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+
+    let out_dir = output_dir().join("crjson_tests/json_report");
+    fs::create_dir_all(&out_dir)?;
+
+    let result = Command::new(get_binary_path())
+        .arg("--extract")
+        .arg("--json")
+        .arg(&signed)
+        .arg(testfiles_dir().join("Dog.png"))
+        .arg("--output")
+        .arg(&out_dir)
+        .output()?;
+
+    // Dog.png has no manifest, so the run fails, but the report is still printed
+    assert!(!result.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&result.stdout)?;
+    assert_eq!(report["succeeded"], 1);
+    assert_eq!(report["failed"], 1);
+    assert_eq!(report["stats"]["manifestsFound"], 1);
+    assert_eq!(report["stats"]["manifestsMissing"], 1);
+    assert_eq!(report["stats"]["formats"]["image/jpeg"], 1);
+    assert_eq!(report["files"].as_array().map(|f| f.len()), Some(2));
+    Ok(())
+}
+
+#[test]
+fn test_extract_where_filters_outputs() -> Result<()> {
+    let input = testfiles_dir().join("Dog.jpg");
+    let manifest = manifests_dir().join("simple_manifest.json");
+    let signed_output = output_dir().join("crjson_tests/where_signed.jpg");
+
+    fs::create_dir_all(signed_output.parent().unwrap())?;
+    sign_file_with_manifest(&input, &signed_output, &manifest)?;
+
+    let out_dir = output_dir().join("crjson_tests/where");
+    let _ = fs::remove_dir_all(&out_dir);
+    fs::create_dir_all(&out_dir)?;
+
+    let run = |expr: &str| {
+        Command::new(get_binary_path())
+            .arg("--extract")
+            .arg("--where")
+            .arg(expr)
+            .arg(&signed_output)
+            .arg("--output")
+            .arg(&out_dir)
+            .output()
+    };
+
+    let result = run("manifest_count > 100")?;
+    assert!(result.status.success());
+    assert!(!out_dir.join("where_signed_cr.json").exists());
+
+    let result = run("manifest_count >= 1 && kind == \"image\"")?;
+    assert!(result.status.success());
+    assert!(out_dir.join("where_signed_cr.json").exists());
+
+    let result = run("no_such_field == 1")?;
+    assert!(
+        !result.status.success(),
+        "Unknown fields should be rejected"
+    );
+
+    Ok(())
+}
+
 // ============================================================================
 // Error Handling Tests
 // ============================================================================
@@ -252,6 +729,87 @@ fn test_extract_nonexistent_file_crjson() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_extract_kind_filter_excludes_all_inputs() -> Result<()> {
+    let input = testfiles_dir().join("Dog.jpg");
+    let out_dir = output_dir().join("crjson_tests/kind_filter");
+    fs::create_dir_all(&out_dir)?;
+
+    let binary = get_binary_path();
+    let result = Command::new(binary)
+        .arg("--extract")
+        .arg("--kind")
+        .arg("video,audio")
+        .arg(&input)
+        .arg("--output")
+        .arg(&out_dir)
+        .output()?;
+
+    assert!(
+        !result.status.success(),
+        "Extraction should fail when --kind filters out every input"
+    );
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(
+        stderr.contains("match --kind"),
+        "Expected --kind error, got: {}",
+        stderr
+    );
+
+    println!("✓ --kind filter rejects a match set with no files of the requested kinds");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_extract_dedupes_symlinked_inputs() -> Result<()> {
+    let dir = output_dir().join("crjson_tests/symlinks");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("out"))?;
+
+    let signed = dir.join("linked_signed.jpg");
+    sign_file_with_manifest(
+        &testfiles_dir().join("Dog.jpg"),
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+    let link = dir.join("alias.jpg");
+    std::os::unix::fs::symlink(&signed, &link)?;
+    let hardlink = dir.join("hardlink.jpg");
+    fs::hard_link(&signed, &hardlink)?;
+
+    let result = Command::new(get_binary_path())
+        .arg("--extract")
+        .arg(&link)
+        .arg(&signed)
+        .arg(&hardlink)
+        .arg("--output")
+        .arg(dir.join("out"))
+        .output()?;
+    assert!(result.status.success());
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(
+        stdout.contains("Skipped 2 duplicate file(s)"),
+        "Expected duplicate report, got: {}",
+        stdout
+    );
+    assert_eq!(fs::read_dir(dir.join("out"))?.count(), 1);
+
+    let result = Command::new(get_binary_path())
+        .arg("--extract")
+        .arg("--no-follow-symlinks")
+        .arg(&link)
+        .arg("--output")
+        .arg(dir.join("out"))
+        .output()?;
+    assert!(
+        !result.status.success(),
+        "A lone symlinked input should be skipped with --no-follow-symlinks"
+    );
+
+    Ok(())
+}
+
 // ============================================================================
 // Programmatic API Tests (helper function)
 // ============================================================================
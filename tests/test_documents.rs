@@ -0,0 +1,141 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Signing text and JSON documents into detached manifest stores, and detecting edits to them.
+
+use anyhow::Result;
+use crtool::documents::detached_store_path;
+use crtool::{
+    default_extraction_settings, extract_crjson_manifest_with_settings, sign_document, SignOptions,
+    SigningAlg,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+use common::{certs_dir, output_dir};
+
+const DATA_HASH_MISMATCH: &str = "assertion.dataHash.mismatch";
+
+fn documents_dir(name: &str) -> Result<PathBuf> {
+    let dir = output_dir().join("documents").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn sign_options(title: &str) -> SignOptions {
+    let manifest = serde_json::json!({
+        "title": title,
+        "claim_generator_info": [{"name": "crtool-test", "version": "1.0"}]
+    });
+    let mut options = SignOptions::new(
+        manifest.to_string(),
+        certs_dir().join("ed25519.pub"),
+        certs_dir().join("ed25519.pem"),
+        SigningAlg::Ed25519,
+    );
+    options.allow_self_signed = true;
+    options
+}
+
+/// Sign `document` into its detached store and return the store path.
+fn sign(document: &Path) -> Result<PathBuf> {
+    let store = detached_store_path(document);
+    let title = document.file_name().unwrap().to_string_lossy();
+    let signed = sign_document(document, &store, &sign_options(&title))?;
+    assert_eq!(signed.output, store);
+    assert!(store.is_file(), "{:?} not written", store);
+    Ok(store)
+}
+
+/// Sign `contents` as document `name`, check it extracts cleanly, then edit it and expect the
+/// data hash to no longer match.
+fn check_sign_and_tamper(name: &str, contents: &str, edited: &str) -> Result<()> {
+    let dir = documents_dir(name)?;
+    let document = dir.join(name);
+    fs::write(&document, contents)?;
+    let store = sign(&document)?;
+    assert_eq!(
+        store.file_name().unwrap().to_string_lossy(),
+        format!("{name}.c2pa")
+    );
+
+    let settings = default_extraction_settings();
+    let result = extract_crjson_manifest_with_settings(&document, &settings)?;
+    assert!(!result.active_label.is_empty());
+    assert!(
+        !result.manifest_json.contains(DATA_HASH_MISMATCH),
+        "unmodified {name} reported a mismatch"
+    );
+    assert!(
+        result.manifest_json.contains(name),
+        "title {name} not in the manifest"
+    );
+
+    fs::write(&document, edited)?;
+    let result = extract_crjson_manifest_with_settings(&document, &settings)?;
+    assert!(
+        result.manifest_json.contains(DATA_HASH_MISMATCH),
+        "edited {name} was not reported"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_sign_json_document() -> Result<()> {
+    check_sign_and_tamper(
+        "intro.json",
+        r#"{"caption": "A dog sitting on a lawn.", "lang": "en"}"#,
+        r#"{"caption": "A cat sitting on a lawn.", "lang": "en"}"#,
+    )
+}
+
+#[test]
+fn test_sign_text_document() -> Result<()> {
+    check_sign_and_tamper(
+        "transcript.txt",
+        "00:00 A dog sits on a lawn.\n00:04 It barks.\n",
+        "00:00 A dog sits on a lawn.\n00:04 It barks twice.\n",
+    )
+}
+
+#[test]
+fn test_documents_with_same_stem_keep_their_own_store() -> Result<()> {
+    let dir = documents_dir("same_stem")?;
+    let json = dir.join("intro.json");
+    let text = dir.join("intro.txt");
+    fs::write(&json, r#"{"caption": "A dog sitting on a lawn."}"#)?;
+    fs::write(&text, "A dog sitting on a lawn.\n")?;
+    let json_store = sign(&json)?;
+    let text_store = sign(&text)?;
+    assert_ne!(json_store, text_store);
+
+    // Signing the text document left the JSON document's store alone
+    let settings = default_extraction_settings();
+    for document in [&json, &text] {
+        let result = extract_crjson_manifest_with_settings(document, &settings)?;
+        assert!(
+            !result.manifest_json.contains(DATA_HASH_MISMATCH),
+            "{:?} does not match its store",
+            document
+        );
+        let title = document.file_name().unwrap().to_string_lossy();
+        assert!(
+            result.manifest_json.contains(&*title),
+            "{:?} has the wrong store",
+            document
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Fast (verify-only headers) extraction tests, plus a timing benchmark.
+//!
+//! The benchmark is ignored by default; run it with
+//! `cargo test --release --test test_fast_verify -- --ignored --nocapture`.
+
+use anyhow::Result;
+use crtool::{
+    default_extraction_settings, extract_crjson_manifest_fast,
+    extract_crjson_manifest_with_settings,
+};
+use std::fs;
+use std::path::PathBuf;
+
+mod common;
+
+use common::{
+    manifests_dir, output_dir, sign_file_with_manifest, testfiles_dir, time_runs, write_large_image,
+};
+
+fn signed_dog() -> Result<PathBuf> {
+    let dir = output_dir().join("fast_verify_tests");
+    fs::create_dir_all(&dir)?;
+    let signed = dir.join("fast_signed.jpg");
+    sign_file_with_manifest(
+        &testfiles_dir().join("Dog.jpg"),
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+    Ok(signed)
+}
+
+#[test]
+fn test_fast_extraction_matches_full_and_is_marked_partial() -> Result<()> {
+    let signed = signed_dog()?;
+    let settings = default_extraction_settings();
+
+    let full = extract_crjson_manifest_with_settings(&signed, &settings)?;
+    let fast = extract_crjson_manifest_fast(&signed, &settings)?;
+
+    assert_eq!(fast.active_label, full.active_label);
+    assert_eq!(
+        fast.manifest_value["manifests"].as_array().map(|m| m.len()),
+        full.manifest_value["manifests"].as_array().map(|m| m.len())
+    );
+
+    let partial = &fast.manifest_value["partialValidation"];
+    assert_eq!(partial["mode"], "fast");
+    assert_eq!(partial["skipped"][0], "contentHash");
+    assert!(full.manifest_value.get("partialValidation").is_none());
+
+    let text = serde_json::to_string(&fast.manifest_value)?;
+    assert!(
+        !text.contains("assertion.dataHash.mismatch"),
+        "Content hash failures should be suppressed in fast mode"
+    );
+    Ok(())
+}
+
+/// ISO BMFF assets cannot be verified against an empty content stream, so fast extraction reads
+/// them in full and matches the full result.
+#[test]
+fn test_fast_extraction_reads_bmff_in_full() -> Result<()> {
+    // Unsigned MP4 from the c2pa-rs checkout the crate is built against
+    let video = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../c2pa-rs/sdk/tests/fixtures/video1_no_manifest.mp4");
+    assert!(video.exists(), "c2pa-rs MP4 fixture missing: {video:?}");
+    let dir = output_dir().join("fast_verify_tests");
+    fs::create_dir_all(&dir)?;
+    let signed = dir.join("fast_signed.mp4");
+    sign_file_with_manifest(
+        &video,
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+    let settings = default_extraction_settings();
+
+    let full = extract_crjson_manifest_with_settings(&signed, &settings)?;
+    let fast = extract_crjson_manifest_fast(&signed, &settings)?;
+
+    assert_eq!(fast.active_label, full.active_label);
+    assert_eq!(fast.manifest_value, full.manifest_value);
+    assert!(fast.manifest_value.get("partialValidation").is_none());
+    Ok(())
+}
+
+#[test]
+#[ignore = "benchmark; run with --ignored --nocapture"]
+fn bench_fast_vs_full_extraction() -> Result<()> {
+    let dir = output_dir().join("fast_verify_tests");
+    fs::create_dir_all(&dir)?;
+    let unsigned = dir.join("large_unsigned.jpg");
+    let signed = dir.join("large_signed.jpg");
+    write_large_image(&unsigned, 6000, 4000)?;
+    sign_file_with_manifest(
+        &unsigned,
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+
+    let settings = default_extraction_settings();
+    let runs = 5;
+    let full = time_runs(runs, || {
+        extract_crjson_manifest_with_settings(&signed, &settings).map(|_| ())
+    })?;
+    let fast = time_runs(runs, || {
+        extract_crjson_manifest_fast(&signed, &settings).map(|_| ())
+    })?;
+
+    let size_mb = fs::metadata(&signed)?.len() as f64 / (1024.0 * 1024.0);
+    println!("asset: {:.1} MiB, {} runs each", size_mb, runs);
+    println!("full extraction: {:?} per file", full);
+    println!("fast extraction: {:?} per file", fast);
+    println!(
+        "speedup: {:.1}x",
+        full.as_secs_f64() / fast.as_secs_f64().max(f64::EPSILON)
+    );
+
+    assert!(
+        fast < full,
+        "Fast path should be quicker than full verification"
+    );
+    Ok(())
+}
@@ -0,0 +1,115 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Batch signing with a shared parent ingredient through an `IngredientCache`, plus a timing
+//! benchmark.
+//!
+//! The benchmark is ignored by default; run it with
+//! `cargo test --release --test test_ingredient_cache -- --ignored --nocapture`.
+
+use anyhow::Result;
+use crtool::{extract_crjson_manifest, sign_asset, IngredientCache, SignOptions, SigningAlg};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod common;
+
+use common::{certs_dir, output_dir, testfiles_dir, time_runs, write_large_image};
+
+/// Options signing with `parent` as the `parentOf` file ingredient.
+fn options(parent: &Path, cache: Option<Arc<IngredientCache>>) -> SignOptions {
+    let manifest = serde_json::json!({
+        "title": "Batch asset",
+        "ingredients": [{"file_path": parent, "relationship": "parentOf"}],
+    });
+    let mut options = SignOptions::new(
+        manifest.to_string(),
+        certs_dir().join("ed25519.pub"),
+        certs_dir().join("ed25519.pem"),
+        SigningAlg::Ed25519,
+    );
+    options.allow_self_signed = true;
+    options.ingredient_thumbnails = true;
+    options.ingredient_cache = cache;
+    options
+}
+
+/// Sign `count` copies of Dog.jpg into `dir` with `options`.
+fn sign_batch(dir: &Path, count: usize, options: &SignOptions) -> Result<Vec<PathBuf>> {
+    let input = testfiles_dir().join("Dog.jpg");
+    (0..count)
+        .map(|i| {
+            let output = dir.join(format!("signed-{i}.jpg"));
+            sign_asset(&input, &output, options)?;
+            Ok(output)
+        })
+        .collect()
+}
+
+#[test]
+fn test_batch_reuses_parent_ingredient() -> Result<()> {
+    let dir = output_dir().join("ingredient_cache_tests");
+    fs::create_dir_all(&dir)?;
+    let parent = fs::canonicalize(testfiles_dir().join("Dog.png"))?;
+    let cache = Arc::new(IngredientCache::new());
+
+    let outputs = sign_batch(&dir, 3, &options(&parent, Some(cache.clone())))?;
+    assert_eq!((cache.misses(), cache.hits()), (1, 2));
+
+    for output in &outputs {
+        let result = extract_crjson_manifest(output)?;
+        let text = serde_json::to_string(&result.manifest_value)?;
+        assert!(text.contains("Dog.png"), "{output:?} lacks the parent");
+    }
+    Ok(())
+}
+
+fn time_batch(dir: &Path, count: usize, options: &SignOptions) -> Result<Duration> {
+    time_runs(1, || sign_batch(dir, count, options).map(drop))
+}
+
+#[test]
+#[ignore = "benchmark; run with --ignored --nocapture"]
+fn bench_batch_with_and_without_ingredient_cache() -> Result<()> {
+    let dir = output_dir().join("ingredient_cache_tests");
+    fs::create_dir_all(&dir)?;
+    let parent = fs::canonicalize(&dir)?.join("large_parent.png");
+    // Large enough that loading and thumbnailing the ingredient is expensive
+    write_large_image(&parent, 4000, 3000)?;
+
+    let count = 20;
+    let uncached = time_batch(&dir, count, &options(&parent, None))?;
+    let cache = Arc::new(IngredientCache::new());
+    let cached = time_batch(&dir, count, &options(&parent, Some(cache.clone())))?;
+
+    let size_mb = fs::metadata(&parent)?.len() as f64 / (1024.0 * 1024.0);
+    println!("parent ingredient: {:.1} MiB, {} assets", size_mb, count);
+    println!("without cache: {:?}", uncached);
+    println!(
+        "with cache:    {:?} ({} loaded, {} reused)",
+        cached,
+        cache.misses(),
+        cache.hits()
+    );
+    println!(
+        "speedup: {:.1}x",
+        uncached.as_secs_f64() / cached.as_secs_f64().max(f64::EPSILON)
+    );
+
+    assert!(
+        cached < uncached,
+        "Cached batch should be quicker than loading the parent for every asset"
+    );
+    Ok(())
+}
@@ -0,0 +1,246 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Extraction from (and signing of) in-memory buffers and streams, plus a benchmark of hashed
+//! extraction.
+//!
+//! The benchmark is ignored by default; run it with
+//! `cargo test --release --test test_stream_extraction -- --ignored --nocapture`.
+
+use anyhow::Result;
+use crtool::hashing::{hash_file, HashAlgorithm};
+use crtool::{
+    default_extraction_settings, extract_crjson_manifest_from_bytes,
+    extract_crjson_manifest_from_stream, extract_crjson_manifest_hashed,
+    extract_crjson_manifest_hashed_with, extract_crjson_manifest_with_settings, HashOptions,
+    ManifestExtractionResult, SignOptions, SigningAlg,
+};
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+mod common;
+
+use common::{
+    certs_dir, manifests_dir, output_dir, sign_file_with_manifest, testfiles_dir, time_runs,
+    write_large_image,
+};
+
+/// The crJSON of `result` without the `asset_info` added by hashed extraction.
+fn without_asset_info(result: &ManifestExtractionResult) -> serde_json::Value {
+    let mut value = result.manifest_value.clone();
+    value.as_object_mut().unwrap().remove("asset_info");
+    value
+}
+
+#[test]
+fn test_stream_extraction_matches_file_extraction() -> Result<()> {
+    let dir = output_dir().join("stream_tests");
+    fs::create_dir_all(&dir)?;
+    let signed = dir.join("stream_signed.jpg");
+    sign_file_with_manifest(
+        &testfiles_dir().join("Dog.jpg"),
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+    let settings = default_extraction_settings();
+
+    let from_file = extract_crjson_manifest_with_settings(&signed, &settings)?;
+    let data = fs::read(&signed)?;
+    let from_bytes = extract_crjson_manifest_from_bytes(&data, "image/jpeg", &settings)?;
+    let from_stream =
+        extract_crjson_manifest_from_stream(fs::File::open(&signed)?, "jpg", &settings)?;
+    let hashed = extract_crjson_manifest_hashed(&signed, &settings)?;
+
+    assert_eq!(from_bytes.active_label, from_file.active_label);
+    assert_eq!(without_asset_info(&from_bytes), from_file.manifest_value);
+    assert_eq!(without_asset_info(&from_stream), from_file.manifest_value);
+    assert_eq!(without_asset_info(&hashed), from_file.manifest_value);
+    assert_eq!(hashed.input_path, from_file.input_path);
+
+    let sha256 = hash_file(&signed, HashAlgorithm::Sha256)?;
+    assert_eq!(from_bytes.asset_hash.as_deref(), Some(sha256.as_str()));
+    assert_eq!(from_stream.asset_hash.as_deref(), Some(sha256.as_str()));
+    assert_eq!(hashed.asset_hash.as_deref(), Some(sha256.as_str()));
+    assert_eq!(hashed.asset_hash_alg, Some(HashAlgorithm::Sha256));
+    assert_eq!(hashed.manifest_value["asset_info"]["alg"], "sha256");
+
+    let options = HashOptions {
+        algorithm: HashAlgorithm::Sha512,
+    };
+    let sha512 = extract_crjson_manifest_hashed_with(&signed, &settings, &options)?;
+    assert_eq!(
+        sha512.asset_hash,
+        Some(hash_file(&signed, HashAlgorithm::Sha512)?)
+    );
+    assert_eq!(sha512.asset_hash_alg, Some(HashAlgorithm::Sha512));
+    assert_eq!(sha512.manifest_value["asset_info"]["alg"], "sha512");
+    assert_eq!(without_asset_info(&sha512), from_file.manifest_value);
+
+    assert!(extract_crjson_manifest_from_bytes(&data, "text/plain", &settings).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_sign_stream_signs_in_memory() -> Result<()> {
+    let manifest = serde_json::json!({"title": "in-memory.jpg"});
+    let mut options = SignOptions::new(
+        manifest.to_string(),
+        certs_dir().join("ed25519.pub"),
+        certs_dir().join("ed25519.pem"),
+        SigningAlg::Ed25519,
+    );
+    options.allow_self_signed = true;
+
+    let mut source = std::io::Cursor::new(fs::read(testfiles_dir().join("Dog.jpg"))?);
+    let mut dest = std::io::Cursor::new(Vec::new());
+    let signed = crtool::sign_stream("image/jpeg", &mut source, &mut dest, &options)?;
+    assert_eq!(signed.file_ingredients, 0);
+    assert!(signed.relationship_violations.is_empty());
+
+    let result = extract_crjson_manifest_from_bytes(
+        dest.get_ref(),
+        "image/jpeg",
+        &default_extraction_settings(),
+    )?;
+    let active = crtool::provenance::find_manifest(&result.manifest_value, &result.active_label)
+        .expect("active manifest");
+    assert_eq!(
+        crtool::provenance::manifest_title(active),
+        Some("in-memory.jpg")
+    );
+    Ok(())
+}
+
+/// A store whose APP11 header declares more than the store size limit is refused by every
+/// extraction path from that header alone, before the store is read.
+#[test]
+fn test_oversized_declared_store_is_refused() -> Result<()> {
+    let dir = output_dir().join("stream_tests");
+    fs::create_dir_all(&dir)?;
+    let signed = dir.join("declared_size_signed.jpg");
+    sign_file_with_manifest(
+        &testfiles_dir().join("Dog.jpg"),
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+
+    // First JPEG XT segment: marker, length, "JP", instance, sequence 1, then the store's LBox
+    let mut data = fs::read(&signed)?;
+    let app11 = data
+        .windows(6)
+        .position(|w| w[..2] == [0xff, 0xeb] && &w[4..6] == b"JP")
+        .expect("APP11 segment");
+    let lbox = app11 + 4 + 8;
+    let declared = crtool::limits::extraction_limits().max_manifest_store_bytes + 1;
+    data[lbox..lbox + 4].copy_from_slice(&(declared as u32).to_be_bytes());
+    let tampered = dir.join("declared_size_over_limit.jpg");
+    fs::write(&tampered, &data)?;
+
+    let settings = default_extraction_settings();
+    let read = std::sync::Arc::new(AtomicU64::new(0));
+    let stream = CountingReader {
+        inner: std::io::Cursor::new(data.clone()),
+        read: read.clone(),
+    };
+    let results = [
+        extract_crjson_manifest_from_stream(stream, "jpg", &settings),
+        extract_crjson_manifest_from_bytes(&data, "image/jpeg", &settings),
+        extract_crjson_manifest_with_settings(&tampered, &settings),
+        extract_crjson_manifest_hashed(&tampered, &settings),
+        crtool::extract_crjson_manifest_fast(&tampered, &settings),
+        crtool::extract_crjson_manifest(&tampered),
+    ];
+    for result in results {
+        let err = result.expect_err("over-limit store must be refused");
+        let exceeded = err.limit_exceeded().expect("a limit error");
+        assert_eq!(exceeded.actual, Some(declared));
+    }
+    assert!(
+        read.load(Ordering::Relaxed) < (app11 + 64) as u64,
+        "only the headers in front of the store should be read"
+    );
+    Ok(())
+}
+
+/// Stream wrapper counting the bytes read through it.
+struct CountingReader<R> {
+    inner: R,
+    read: std::sync::Arc<AtomicU64>,
+}
+
+impl<R: std::io::Read> std::io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+impl<R: std::io::Seek> std::io::Seek for CountingReader<R> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// Hashed extraction (one streaming read that validates and hashes, see
+/// `crtool::hashing::HashingReader`) against extracting and then hashing the file in a second
+/// pass. Reports the time per file and how many bytes each approach reads; the single pass
+/// should read the asset about once, the two-pass approach about twice.
+#[test]
+#[ignore = "benchmark; run with --ignored --nocapture"]
+fn bench_hashed_vs_two_pass_extraction() -> Result<()> {
+    let dir = output_dir().join("stream_tests");
+    fs::create_dir_all(&dir)?;
+    let unsigned = dir.join("large_unsigned.jpg");
+    let signed = dir.join("large_signed.jpg");
+    write_large_image(&unsigned, 6000, 4000)?;
+    sign_file_with_manifest(
+        &unsigned,
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+
+    let settings = default_extraction_settings();
+    let runs = 5;
+    let two_pass = time_runs(runs, || {
+        extract_crjson_manifest_with_settings(&signed, &settings)?;
+        hash_file(&signed, HashAlgorithm::Sha256)?;
+        Ok(())
+    })?;
+    let hashed = time_runs(runs, || {
+        extract_crjson_manifest_hashed(&signed, &settings).map(|_| ())
+    })?;
+
+    // Bytes read by the single pass, through the stream entry point that shares its code
+    let read = std::sync::Arc::new(AtomicU64::new(0));
+    let counted = CountingReader {
+        inner: std::io::BufReader::new(fs::File::open(&signed)?),
+        read: read.clone(),
+    };
+    crtool::extract_crjson_manifest_from_stream(counted, "jpg", &settings)?;
+
+    let size = fs::metadata(&signed)?.len();
+    println!(
+        "asset: {:.1} MiB, {} runs each",
+        size as f64 / (1024.0 * 1024.0),
+        runs
+    );
+    println!("extract, then hash: {:?} per file", two_pass);
+    println!("hashed extraction:  {:?} per file", hashed);
+    let read = read.load(Ordering::Relaxed);
+    println!(
+        "bytes read by hashed extraction: {} ({:.2}x the asset)",
+        read,
+        read as f64 / size as f64
+    );
+    Ok(())
+}
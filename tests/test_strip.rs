@@ -0,0 +1,114 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Removing C2PA data from signed assets.
+
+use anyhow::Result;
+use crtool::{extract_crjson_manifest, remove_c2pa};
+use std::fs;
+
+mod common;
+
+use common::{manifests_dir, output_dir, sign_file_with_manifest, testfiles_dir};
+
+#[test]
+fn test_strip_signed_jpeg() -> Result<()> {
+    let dir = output_dir().join("strip_tests");
+    fs::create_dir_all(&dir)?;
+    let signed = dir.join("strip_signed.jpg");
+    let stripped_path = dir.join("strip_clean.jpg");
+    sign_file_with_manifest(
+        &testfiles_dir().join("Dog.jpg"),
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+    assert!(extract_crjson_manifest(&signed).is_ok());
+
+    let stripped = remove_c2pa(&signed, &stripped_path)?;
+    assert!(stripped.removed_blocks > 0);
+    let err = extract_crjson_manifest(&stripped_path).unwrap_err();
+    assert!(err.is_no_manifest(), "unexpected error: {err}");
+
+    // Stripping again finds nothing and leaves the file unchanged
+    let again = remove_c2pa(&stripped_path, dir.join("strip_again.jpg"))?;
+    assert!(again.is_empty());
+    assert_eq!(
+        fs::read(dir.join("strip_again.jpg"))?,
+        fs::read(&stripped_path)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_strip_in_place_replaces_file() -> Result<()> {
+    let dir = output_dir().join("strip_tests").join("in_place");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let asset = dir.join("strip_in_place.jpg");
+    sign_file_with_manifest(
+        &testfiles_dir().join("Dog.jpg"),
+        &asset,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+    let expected = dir.join("strip_copy.jpg");
+    remove_c2pa(&asset, &expected)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&asset, fs::Permissions::from_mode(0o640))?;
+    }
+
+    let stripped = remove_c2pa(&asset, &asset)?;
+    assert!(stripped.removed_blocks > 0);
+    assert_eq!(fs::read(&asset)?, fs::read(&expected)?);
+    let err = extract_crjson_manifest(&asset).unwrap_err();
+    assert!(err.is_no_manifest(), "unexpected error: {err}");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(fs::metadata(&asset)?.permissions().mode() & 0o777, 0o640);
+    }
+
+    // No temporary file is left next to it
+    let mut names: Vec<_> = fs::read_dir(&dir)?
+        .map(|entry| entry.map(|e| e.file_name()))
+        .collect::<std::io::Result<_>>()?;
+    names.sort();
+    assert_eq!(names, ["strip_copy.jpg", "strip_in_place.jpg"]);
+    Ok(())
+}
+
+/// Stripping in place refuses a file another process has open, even without a lock.
+#[cfg(target_os = "linux")]
+#[test]
+fn test_strip_in_place_refuses_open_file() -> Result<()> {
+    let dir = output_dir().join("strip_tests");
+    fs::create_dir_all(&dir)?;
+    let asset = dir.join("strip_in_use.jpg");
+    fs::copy(testfiles_dir().join("Dog.jpg"), &asset)?;
+
+    let mut holder = std::process::Command::new("sleep")
+        .arg("30")
+        .stdin(fs::File::open(&asset)?)
+        .spawn()?;
+    let result = remove_c2pa(&asset, &asset);
+    holder.kill()?;
+    holder.wait()?;
+
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("open in another process"), "{err}");
+    assert_eq!(
+        fs::read(&asset)?,
+        fs::read(testfiles_dir().join("Dog.jpg"))?
+    );
+    Ok(())
+}
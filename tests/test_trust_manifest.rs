@@ -0,0 +1,117 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Trust Manifest assessment tests (CLI `assess` and `extract --trust-manifest`).
+
+use anyhow::Result;
+use std::fs;
+use std::process::Command;
+
+mod common;
+
+use common::{manifests_dir, output_dir, sign_file_with_manifest, testfiles_dir};
+
+#[test]
+fn test_trust_manifest_assessment_reports_per_clause() -> Result<()> {
+    let dir = output_dir().join("trust_manifest_tests");
+    fs::create_dir_all(&dir)?;
+
+    let signed = dir.join("assessed.jpg");
+    sign_file_with_manifest(
+        &testfiles_dir().join("Dog.jpg"),
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+
+    let tm_path = dir.join("tm.json");
+    fs::write(
+        &tm_path,
+        serde_json::json!({
+            "name": "test",
+            "clauses": [
+                { "id": "has-label", "indicator": "/activeManifest/label", "exists": true },
+                { "id": "impossible", "indicator": "/activeManifest/label", "equals": "nope" }
+            ]
+        })
+        .to_string(),
+    )?;
+
+    let result = Command::new(common::cli_binary_path())
+        .arg("assess")
+        .arg("--trust-manifest")
+        .arg(&tm_path)
+        .arg(&signed)
+        .arg("--output")
+        .arg(&dir)
+        .output()?;
+
+    assert!(
+        !result.status.success(),
+        "A failing clause should make the run exit non-zero"
+    );
+
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.join("assessed-conformance.json"))?)?;
+    assert_eq!(report["conforms"], false);
+    assert_eq!(report["clauses"][0]["status"], "pass");
+    assert_eq!(report["clauses"][1]["status"], "fail");
+    Ok(())
+}
+
+#[test]
+fn test_extract_json_report_with_trust_manifest() -> Result<()> {
+    let dir = output_dir().join("trust_manifest_tests/extract_json");
+    fs::create_dir_all(&dir)?;
+
+    let signed = dir.join("reported.jpg");
+    sign_file_with_manifest(
+        &testfiles_dir().join("Dog.jpg"),
+        &signed,
+        &manifests_dir().join("simple_manifest.json"),
+    )?;
+    let tm_path = dir.join("tm.json");
+    fs::write(
+        &tm_path,
+        serde_json::json!({
+            "name": "test",
+            "clauses": [{ "id": "has-label", "indicator": "/activeManifest/label", "exists": true }]
+        })
+        .to_string(),
+    )?;
+
+    // The assessment is progress output: stdout carries only the JSON report
+    let result = Command::new(common::cli_binary_path())
+        .arg("extract")
+        .arg("--json")
+        .arg("--trust-manifest")
+        .arg(&tm_path)
+        .arg(&signed)
+        .arg("--output")
+        .arg(&dir)
+        .output()?;
+    assert!(result.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&result.stdout)?;
+    assert_eq!(report["succeeded"], 1);
+    assert!(String::from_utf8_lossy(&result.stderr).contains("Conformance: yes"));
+    assert!(dir.join("reported-conformance.json").exists());
+
+    let quiet = Command::new(common::cli_binary_path())
+        .arg("assess")
+        .arg("--quiet")
+        .arg("--trust-manifest")
+        .arg(&tm_path)
+        .arg(&signed)
+        .output()?;
+    assert!(quiet.status.success());
+    assert!(quiet.stdout.is_empty(), "--quiet should print nothing");
+    Ok(())
+}
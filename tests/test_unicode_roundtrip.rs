@@ -0,0 +1,102 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! Non-ASCII file names and titles: signing and extraction must preserve them byte-exactly,
+//! and `normalize_unicode` must write them in NFC.
+
+use anyhow::Result;
+use crtool::provenance::{find_manifest, ingredient_assertions, manifest_title};
+use crtool::{extract_crjson_manifest, sign_asset, SignOptions, SigningAlg};
+use std::fs;
+
+mod common;
+
+use common::{certs_dir, output_dir, testfiles_dir};
+
+/// File names covering CJK, Hangul, emoji, RTL text, and decomposed (NFD) accents.
+const NAMES: &[&str] = &[
+    "写真.jpg",
+    "사진 2024.jpg",
+    "🐶 dog 🦴.jpg",
+    "صورة.jpg",
+    "Cafe\u{301} au lait.jpg",
+    "Caf\u{e9} au lait.jpg",
+];
+
+/// Sign a copy of Dog.jpg named `name`, titled `name`, with itself as a file ingredient.
+/// Returns the manifest title and the ingredient title read back from the signed asset.
+fn sign_and_read_titles(name: &str, dir: &str, normalize: bool) -> Result<(String, String)> {
+    let dir = output_dir().join(dir);
+    fs::create_dir_all(&dir)?;
+    let input = dir.join(name);
+    fs::copy(testfiles_dir().join("Dog.jpg"), &input)?;
+
+    let manifest = serde_json::json!({
+        "title": name,
+        "ingredients": [{"file_path": name, "relationship": "parentOf"}],
+    });
+    let mut options = SignOptions::new(
+        manifest.to_string(),
+        certs_dir().join("ed25519.pub"),
+        certs_dir().join("ed25519.pem"),
+        SigningAlg::Ed25519,
+    );
+    options.ingredients_base_dir = dir.clone();
+    options.allow_self_signed = true;
+    options.normalize_unicode = normalize;
+
+    let output = dir.join(format!("signed-{}", name));
+    sign_asset(&input, &output, &options)?;
+    let result = extract_crjson_manifest(&output)?;
+    let active = find_manifest(&result.manifest_value, &result.active_label).expect("active");
+
+    let title = manifest_title(active).unwrap_or_default().to_string();
+    let ingredient_title = ingredient_assertions(active)
+        .first()
+        .and_then(|i| i.get("dc:title").or_else(|| i.get("title")))
+        .and_then(|t| t.as_str())
+        .unwrap_or_default()
+        .to_string();
+    Ok((title, ingredient_title))
+}
+
+#[test]
+fn test_non_ascii_titles_round_trip_byte_exactly() -> Result<()> {
+    for name in NAMES {
+        let (title, ingredient_title) = sign_and_read_titles(name, "unicode_tests/raw", false)?;
+        assert_eq!(
+            title.as_bytes(),
+            name.as_bytes(),
+            "manifest title of {name}"
+        );
+        assert_eq!(
+            ingredient_title.as_bytes(),
+            name.as_bytes(),
+            "ingredient title of {name}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_normalize_unicode_writes_nfc_titles() -> Result<()> {
+    let nfc = "Caf\u{e9} au lait.jpg";
+    let (title, ingredient_title) =
+        sign_and_read_titles("Cafe\u{301} au lait.jpg", "unicode_tests/nfc", true)?;
+    assert_eq!(title, nfc);
+    assert_eq!(ingredient_title, nfc);
+
+    // Already-NFC names are unchanged
+    let (title, _) = sign_and_read_titles("写真.jpg", "unicode_tests/nfc", true)?;
+    assert_eq!(title, "写真.jpg");
+    Ok(())
+}
@@ -0,0 +1,79 @@
+/*
+Copyright 2025 Adobe. All rights reserved.
+This file is licensed to you under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License. You may obtain a copy
+of the License at http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software distributed under
+the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR REPRESENTATIONS
+OF ANY KIND, either express or implied. See the License for the specific language
+governing permissions and limitations under the License.
+*/
+
+//! `--self-check-update` and `--offline` tests (no network access).
+
+use anyhow::Result;
+use std::process::Command;
+
+mod common;
+
+#[test]
+fn test_update_check_honors_offline() -> Result<()> {
+    let output = Command::new(common::cli_binary_path())
+        .arg("self-check-update")
+        .arg("--offline")
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Update check skipped"),
+        "Unexpected stderr: {stderr}"
+    );
+
+    let output = Command::new(common::cli_binary_path())
+        .arg("--self-check-update")
+        .env("CRTOOL_OFFLINE", "1")
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
+
+#[test]
+fn test_update_check_requires_pinned_key() -> Result<()> {
+    // Only meaningful for builds made without a feed key, which is how the tests are built.
+    if option_env!("CRTOOL_UPDATE_FEED_KEY").is_some() {
+        return Ok(());
+    }
+    let output = Command::new(common::cli_binary_path())
+        .arg("self-check-update")
+        .env_remove("CRTOOL_OFFLINE")
+        .env(
+            "CRTOOL_UPDATE_FEED_KEY",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        )
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("built without an update feed key"),
+        "Unexpected stderr: {stderr}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_offline_rejects_trust() -> Result<()> {
+    let input = common::testfiles_dir().join("Dog.jpg");
+    let output = Command::new(common::cli_binary_path())
+        .arg("--extract")
+        .arg("--trust")
+        .arg("--offline")
+        .arg(&input)
+        .arg("--output")
+        .arg(common::output_dir())
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--offline"), "Unexpected stderr: {stderr}");
+    Ok(())
+}
@@ -131,6 +131,41 @@ fn test_validation_with_malformed_json() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_validation_refuses_json_over_depth_limit() -> Result<()> {
+    let binary = get_binary_path();
+    let nested_file = std::env::temp_dir().join("test_nested_limit.json");
+    fs::write(
+        &nested_file,
+        format!("{}{}", "[".repeat(20), "]".repeat(20)),
+    )?;
+
+    let output = Command::new(&binary)
+        .arg("--validate")
+        .arg("--max-json-depth")
+        .arg("8")
+        .arg(&nested_file)
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    println!("stdout: {}", stdout);
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    assert!(
+        !output.status.success(),
+        "Validation should fail for JSON nested deeper than the limit"
+    );
+    assert!(
+        stdout.contains("Limit exceeded: JSON nesting is deeper than 8 levels"),
+        "The limit violation should be reported as such"
+    );
+
+    fs::remove_file(nested_file)?;
+
+    Ok(())
+}
+
 #[test]
 fn test_validation_with_multiple_files() -> Result<()> {
     let binary = get_binary_path();
@@ -267,3 +302,216 @@ fn test_validation_extracts_from_signed_file() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_validation_recursive_skips_non_indicators() -> Result<()> {
+    let binary = get_binary_path();
+    let root = std::env::temp_dir().join("crtool_validate_recursive");
+    let nested = root.join("nested");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&nested)?;
+    fs::copy(
+        fixtures_dir().join("minimal_valid_crjson.json"),
+        nested.join("manifest.json"),
+    )?;
+    fs::write(root.join("package.json"), r#"{"name": "not-a-manifest"}"#)?;
+
+    let output = Command::new(&binary)
+        .arg("--validate")
+        .arg("--recursive")
+        .arg("--skip-non-indicators")
+        .arg(&root)
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    println!("stdout: {}", stdout);
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    assert!(
+        output.status.success(),
+        "Non-indicators JSON should be skipped, not failed"
+    );
+    assert!(stdout.contains("Per-directory Summary"));
+    assert!(stdout.contains("Skipped (not indicators documents): 1"));
+
+    let output = Command::new(&binary)
+        .arg("--validate")
+        .arg("--recursive")
+        .arg(&root)
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        !output.status.success(),
+        "Without --skip-non-indicators the stray JSON should fail validation"
+    );
+
+    fs::remove_dir_all(&root)?;
+    Ok(())
+}
+
+#[test]
+fn test_validation_compare_schemas_reports_divergence() -> Result<()> {
+    let binary = get_binary_path();
+    let dir = std::env::temp_dir().join("crtool_compare_schemas");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    // An empty schema accepts every document, so only the bundled schema can reject one.
+    let permissive = dir.join("permissive.schema.json");
+    fs::write(&permissive, "{}")?;
+    let stray = dir.join("package.json");
+    fs::write(&stray, r#"{"name": "not-a-manifest"}"#)?;
+    let valid = fixtures_dir().join("minimal_valid_crjson.json");
+
+    let output = Command::new(&binary)
+        .arg("--validate")
+        .arg("--json")
+        .arg("--compare-schemas")
+        .arg(format!("bundled,{}", permissive.display()))
+        .arg(&valid)
+        .arg(&stray)
+        .output()
+        .expect("Failed to execute command");
+
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(
+        !output.status.success(),
+        "A document valid under only one schema should fail the comparison"
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["total"], 2);
+    assert_eq!(report["bothValid"], 1);
+    let divergent = report["divergent"].as_array().expect("divergent array");
+    assert_eq!(divergent.len(), 1);
+    assert_eq!(divergent[0]["fails"], "bundled");
+    assert!(divergent[0]["input"]
+        .as_str()
+        .is_some_and(|p| p.ends_with("package.json")));
+
+    let output = Command::new(&binary)
+        .arg("--validate")
+        .arg("--compare-schemas")
+        .arg("bundled,bundled")
+        .arg(&valid)
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_validation_severity_rules() -> Result<()> {
+    let binary = get_binary_path();
+    let dir = std::env::temp_dir().join("crtool_validate_severity");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    // Misses every required top-level property, and nothing else.
+    let stray = dir.join("package.json");
+    fs::write(&stray, r#"{"name": "not-a-manifest"}"#)?;
+
+    let output = Command::new(&binary)
+        .arg("validate")
+        .arg("--severity")
+        .arg("required=warning")
+        .arg(&stray)
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    println!("stdout: {}", stdout);
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(
+        output.status.success(),
+        "Findings downgraded to warnings should not fail validation"
+    );
+    assert!(stdout.contains("! warning:"));
+    assert!(stdout.contains("Warnings: "));
+
+    let output = Command::new(&binary)
+        .arg("validate")
+        .arg("--severity")
+        .arg("required=warning")
+        .arg("--warnings-as-errors")
+        .arg(&stray)
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        !output.status.success(),
+        "--warnings-as-errors should fail on warnings"
+    );
+
+    let output = Command::new(&binary)
+        .arg("validate")
+        .arg("--suppress-rule")
+        .arg("required")
+        .arg("--warnings-as-errors")
+        .arg(&stray)
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "Suppressed findings are dropped");
+    assert!(!stdout.contains("Warnings:"));
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_validation_with_custom_and_extension_schemas() -> Result<()> {
+    let binary = get_binary_path();
+    let dir = std::env::temp_dir().join("crtool_validate_schemas");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    // An organization extension that requires a field the crJSON schema does not have.
+    let extension = dir.join("org-extension.schema.json");
+    fs::write(&extension, r#"{"required": ["orgReviewer"]}"#)?;
+    let valid = fixtures_dir().join("minimal_valid_crjson.json");
+
+    let output = Command::new(&binary)
+        .arg("validate")
+        .arg("--schema")
+        .arg("bundled")
+        .arg("--schema")
+        .arg(&extension)
+        .arg(&valid)
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    println!("stdout: {}", stdout);
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(
+        !output.status.success(),
+        "A file must pass every schema of the profile"
+    );
+    assert!(stdout.contains("against 2 schema(s)"));
+    assert!(stdout.contains("orgReviewer"));
+    assert!(stdout.contains("org-extension.schema.json]"));
+
+    // The extension alone replaces the bundled schema.
+    let reviewed = dir.join("reviewed.json");
+    fs::write(&reviewed, r#"{"orgReviewer": "desk"}"#)?;
+    let output = Command::new(&binary)
+        .arg("validate")
+        .arg("--schema")
+        .arg(&extension)
+        .arg(&reviewed)
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Only the given schema should apply"
+    );
+
+    let output = Command::new(&binary)
+        .arg("validate")
+        .arg("--schema")
+        .arg(dir.join("missing.schema.json"))
+        .arg(&valid)
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}